@@ -10,16 +10,20 @@ use crate::interrupts::InterruptRegisters;
 use crate::memory::Memory;
 use crate::ppu::Ppu;
 use crate::serial::SerialPort;
+use crate::sgb::SgbPort;
 use crate::sm83::Sm83;
 use crate::speed::SpeedRegister;
 use crate::timer::GbTimer;
 use crate::{HardwareMode, audio, ppu};
 use bincode::{Decode, Encode};
 use jgenesis_common::frontend::{
-    AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, PixelAspectRatio, Renderer, SaveWriter,
-    TickEffect, TickResult,
+    AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, PixelAspectRatio, Renderer,
+    SaveStateSafePoint, SaveWriter, TickEffect, TickResult,
 };
-use jgenesis_proc_macros::{ConfigDisplay, EnumAll, EnumDisplay, PartialClone};
+use jgenesis_common::instrument;
+use jgenesis_common::instrumentation::Subsystem;
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
+use jgenesis_proc_macros::{ConfigDisplay, EnumAll, EnumDisplay, PartialClone, SaveState};
 use std::fmt::{Debug, Display};
 use thiserror::Error;
 
@@ -101,7 +105,16 @@ pub enum BackgroundTileMap {
     One,
 }
 
-#[derive(Debug, Clone, Encode, Decode, PartialClone)]
+/// Memory regions that can be dumped and restored for debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumAll)]
+pub enum MemoryRegion {
+    Wram,
+    Vram,
+    Hram,
+    Sram,
+}
+
+#[derive(Debug, Clone, SaveState, PartialClone)]
 pub struct GameBoyEmulator {
     hardware_mode: HardwareMode,
     cpu: Sm83,
@@ -116,6 +129,7 @@ pub struct GameBoyEmulator {
     timer: GbTimer,
     dma_unit: DmaUnit,
     input_state: InputState,
+    #[savestate(skip)]
     rgba_buffer: RgbaFrameBuffer,
     config: GameBoyEmulatorConfig,
     frame_count: u64,
@@ -174,6 +188,61 @@ impl GameBoyEmulator {
         self.ppu.copy_palettes(out);
     }
 
+    #[must_use]
+    pub fn dump_palettes(&self) -> Vec<u8> {
+        self.ppu.dump_palettes()
+    }
+
+    #[must_use]
+    pub fn dump_memory(&self, region: MemoryRegion) -> Vec<u8> {
+        match region {
+            MemoryRegion::Wram => self.memory.main_ram().to_vec(),
+            MemoryRegion::Vram => self.ppu.vram().to_vec(),
+            MemoryRegion::Hram => self.memory.hram().to_vec(),
+            MemoryRegion::Sram => self.cartridge.sram().to_vec(),
+        }
+    }
+
+    /// Overwrite a memory region, e.g. with the contents of a file previously written using
+    /// [`Self::dump_memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not the same length as the memory region.
+    pub fn restore_memory(&mut self, region: MemoryRegion, bytes: &[u8]) {
+        match region {
+            MemoryRegion::Wram => self.memory.main_ram_mut().copy_from_slice(bytes),
+            MemoryRegion::Vram => self.ppu.vram_mut().copy_from_slice(bytes),
+            MemoryRegion::Hram => self.memory.hram_mut().copy_from_slice(bytes),
+            MemoryRegion::Sram => self.cartridge.restore_sram(bytes),
+        }
+    }
+
+    /// Describe the SM83 address space as currently mapped.
+    #[must_use]
+    pub fn memory_map_description(&self) -> Vec<MemoryMap> {
+        let mut map = MemoryMap::new("SM83", 16);
+        self.cartridge.describe_memory_map(&mut map);
+        self.memory.describe_memory_map(&mut map, self.hardware_mode);
+
+        match self.hardware_mode {
+            HardwareMode::Dmg => map.add(0x8000, 0x9FFF, RegionKind::Ram, "VRAM"),
+            HardwareMode::Cgb => map.add_mapped(
+                0x8000,
+                0x9FFF,
+                RegionKind::Ram,
+                "VRAM",
+                format!("Bank {}", self.ppu.vram_bank()),
+            ),
+        };
+        map.add(0xFE00, 0xFE9F, RegionKind::Ram, "OAM")
+            .add(0xFF00, 0xFF7F, RegionKind::Registers, "I/O registers")
+            .add(0xFFFF, 0xFFFF, RegionKind::Registers, "Interrupt enable");
+        map.fill_unmapped();
+
+        vec![map]
+    }
+
     #[inline]
     #[must_use]
     pub fn is_using_double_height_sprites(&self) -> bool {
@@ -185,6 +254,153 @@ impl GameBoyEmulator {
     pub fn is_cgb_mode(&self) -> bool {
         self.hardware_mode == HardwareMode::Cgb
     }
+
+    // Returns the number of M-cycles elapsed
+    fn execute_instruction(&mut self) -> u32 {
+        let mut bus = Bus {
+            hardware_mode: self.hardware_mode,
+            ppu: &mut self.ppu,
+            apu: &mut self.apu,
+            memory: &mut self.memory,
+            serial_port: &mut self.serial_port,
+            cartridge: &mut self.cartridge,
+            interrupt_registers: &mut self.interrupt_registers,
+            speed_register: &mut self.speed_register,
+            timer: &mut self.timer,
+            dma_unit: &mut self.dma_unit,
+            input_state: &mut self.input_state,
+            m_cycles_elapsed: 0,
+        };
+
+        // PPU and APU time spent inside CPU memory accesses is attributed to the PPU and APU
+        instrument!(Subsystem::Cpu, self.cpu.execute_instruction(&mut bus));
+        let m_cycles_elapsed = bus.m_cycles_elapsed;
+
+        self.input_state.check_for_joypad_interrupt(&mut self.interrupt_registers);
+
+        m_cycles_elapsed
+    }
+}
+
+/// Interface used by the SNES core's Super Game Boy cartridge (ICD2 chip) to drive the Game Boy.
+///
+/// In this mode the SNES side is responsible for timing, video, and joypad input. The Game Boy
+/// always runs in DMG mode, and its frame buffer contains 2-bit shades rather than colors.
+impl GameBoyEmulator {
+    /// Create an emulator to run inside a Super Game Boy. The Game Boy does not start running
+    /// until the first call to [`Self::sgb_reset`], which is when the ICD2 releases its reset line.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if it cannot load the ROM (e.g. unsupported mapper).
+    pub fn create_super_game_boy<S: SaveWriter>(
+        rom: Vec<u8>,
+        config: GameBoyEmulatorConfig,
+        save_writer: &mut S,
+    ) -> Result<Self, GameBoyLoadError> {
+        let config = GameBoyEmulatorConfig { force_dmg_mode: true, ..config };
+        let mut emulator = Self::create(rom, config, save_writer)?;
+        emulator.input_state.enable_sgb_port();
+
+        Ok(emulator)
+    }
+
+    /// Execute a single CPU instruction, returning the number of M-cycles elapsed.
+    pub fn sgb_step(&mut self) -> u32 {
+        let m_cycles_elapsed = self.execute_instruction();
+
+        if self.ppu.frame_complete() {
+            self.ppu.clear_frame_complete();
+            self.cartridge.update_rtc_time();
+        }
+
+        m_cycles_elapsed
+    }
+
+    /// Reset everything except for the cartridge, as when the ICD2 releases the Game Boy's reset
+    /// line. This also queues the cartridge header packets that the SGB boot ROM would send.
+    pub fn sgb_reset(&mut self) {
+        let rom = self.cartridge.rom();
+        self.cpu = Sm83::new(HardwareMode::Dmg, false);
+        self.ppu = Ppu::new(HardwareMode::Dmg, rom);
+        self.apu.reset_keeping_resampler(self.config);
+        self.memory = Memory::new(HardwareMode::Dmg);
+        self.serial_port = SerialPort::new(HardwareMode::Dmg);
+        self.interrupt_registers = InterruptRegisters::default();
+        self.speed_register = SpeedRegister::new();
+        self.timer = GbTimer::new();
+        self.dma_unit = DmaUnit::new();
+
+        let mut sgb_port = SgbPort::new();
+        sgb_port.queue_header_packets(|address| rom.get(address as usize).copied().unwrap_or(0xFF));
+        self.input_state.set_sgb_port(sgb_port);
+    }
+
+    /// The line that the PPU is currently on (0-153).
+    #[must_use]
+    pub fn sgb_scanline(&self) -> u8 {
+        self.ppu.scanline()
+    }
+
+    /// The 2-bit shades of the given line (0-143) in the frame buffer.
+    pub fn sgb_line(&self, line: u8) -> impl Iterator<Item = u8> + '_ {
+        self.ppu.frame_buffer().line(line).iter().map(|&shade| (shade & 3) as u8)
+    }
+
+    /// Take the next command packet that the Game Boy sent over the joypad port, if any.
+    pub fn sgb_take_packet(&mut self) -> Option<[u8; 16]> {
+        self.input_state.sgb_port_mut().and_then(SgbPort::take_packet)
+    }
+
+    /// Set the joypad states for players 1-4.
+    pub fn sgb_set_joypads(&mut self, joypads: [GameBoyInputs; 4]) {
+        if let Some(sgb_port) = self.input_state.sgb_port_mut() {
+            sgb_port.set_joypads(joypads);
+        }
+    }
+
+    /// Set the number of players for multiplayer joypad reads (1, 2, or 4).
+    pub fn sgb_set_player_count(&mut self, player_count: u8) {
+        if let Some(sgb_port) = self.input_state.sgb_port_mut() {
+            sgb_port.set_player_count(player_count);
+        }
+    }
+
+    /// Set the frequency that the Game Boy is clocked at, which is derived from the SNES master
+    /// clock rather than being the standard 4.194304 MHz. Audio is resampled based on this.
+    pub fn sgb_set_clock_frequency(&mut self, clock_frequency: f64) {
+        self.apu.update_clock_frequency(clock_frequency);
+    }
+
+    /// Move any audio samples generated so far into the given audio output.
+    ///
+    /// # Errors
+    ///
+    /// Propagates any error returned by the audio output.
+    pub fn sgb_drain_audio<A: AudioOutput>(&mut self, audio_output: &mut A) -> Result<(), A::Err> {
+        self.apu.drain_samples_into(audio_output)
+    }
+
+    #[must_use]
+    pub fn has_battery(&self) -> bool {
+        self.cartridge.has_battery()
+    }
+
+    #[must_use]
+    pub fn sram(&self) -> &[u8] {
+        self.cartridge.sram()
+    }
+
+    pub fn sram_mut(&mut self) -> &mut [u8] {
+        self.cartridge.sram_mut()
+    }
+
+    /// # Errors
+    ///
+    /// Propagates any error returned by the save writer.
+    pub fn save_rtc_state<S: SaveWriter>(&self, save_writer: &mut S) -> Result<(), S::Err> {
+        self.cartridge.save_rtc_state(save_writer)
+    }
 }
 
 impl EmulatorTrait for GameBoyEmulator {
@@ -214,24 +430,10 @@ impl EmulatorTrait for GameBoyEmulator {
     {
         self.input_state.set_inputs(*inputs);
 
-        self.cpu.execute_instruction(&mut Bus {
-            hardware_mode: self.hardware_mode,
-            ppu: &mut self.ppu,
-            apu: &mut self.apu,
-            memory: &mut self.memory,
-            serial_port: &mut self.serial_port,
-            cartridge: &mut self.cartridge,
-            interrupt_registers: &mut self.interrupt_registers,
-            speed_register: &mut self.speed_register,
-            timer: &mut self.timer,
-            dma_unit: &mut self.dma_unit,
-            input_state: &mut self.input_state,
-        });
+        self.execute_instruction();
 
         self.apu.drain_samples_into(audio_output).map_err(GameBoyError::Audio)?;
 
-        self.input_state.check_for_joypad_interrupt(&mut self.interrupt_registers);
-
         if self.ppu.frame_complete() {
             self.ppu.clear_frame_complete();
             self.rgba_buffer.copy_from(
@@ -307,6 +509,12 @@ impl EmulatorTrait for GameBoyEmulator {
             .expect("Hard reset should never fail to load cartridge");
     }
 
+    fn save_state_safe_point() -> SaveStateSafePoint {
+        // The PPU writes pixels to the frame buffer as it draws each line, and the frame buffer is
+        // not saved
+        SaveStateSafePoint::FrameBoundary
+    }
+
     fn target_fps(&self) -> f64 {
         if self.config.audio_60hz_hack {
             60.0
@@ -321,3 +529,56 @@ impl EmulatorTrait for GameBoyEmulator {
         self.apu.update_output_frequency(output_frequency);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jgenesis_common::frontend::NullSaveWriter;
+    use jgenesis_common::savestate;
+
+    // Enables VBlank interrupts, then loops forever while the interrupt handler increments a
+    // counter in HRAM and writes it to the background palette
+    fn test_rom() -> Vec<u8> {
+        let interrupt_handler: &[u8] = &[
+            0xF0, 0x80, // LDH A, ($80)
+            0x3C, // INC A
+            0xE0, 0x80, // LDH ($80), A
+            0xE0, 0x47, // LDH (BGP), A
+            0xD9, // RETI
+        ];
+
+        let entry_point: &[u8] = &[
+            0xC3, 0x50, 0x01, // JP $0150
+        ];
+
+        let main: &[u8] = &[
+            0x3E, 0x01, 0xE0, 0xFF, // IE = $01
+            0xFB, // EI
+            0x18, 0xFE, // JR -2
+        ];
+
+        savestate::test_rom(
+            0x8000,
+            &[(0x0040, interrupt_handler), (0x0100, entry_point), (0x0150, main)],
+        )
+    }
+
+    #[test]
+    fn save_state_round_trip() {
+        let config = GameBoyEmulatorConfig {
+            force_dmg_mode: false,
+            pretend_to_be_gba: false,
+            aspect_ratio: GbAspectRatio::default(),
+            gb_palette: GbPalette::default(),
+            gb_custom_palette: [(0, 0, 0); 4],
+            gbc_color_correction: GbcColorCorrection::default(),
+            audio_60hz_hack: false,
+        };
+
+        savestate::assert_round_trip_deterministic(
+            || GameBoyEmulator::create(test_rom(), config, &mut NullSaveWriter).unwrap(),
+            100_000,
+            5_000,
+        );
+    }
+}
@@ -17,6 +17,7 @@ use bincode::{Decode, Encode};
 use jgenesis_common::frontend::AudioOutput;
 use jgenesis_common::num::GetBit;
 use std::array;
+use std::mem;
 
 #[derive(Debug, Clone, Copy, Encode, Decode)]
 struct StereoControl {
@@ -331,6 +332,16 @@ impl Apu {
     pub fn update_output_frequency(&mut self, output_frequency: u64) {
         self.resampler.update_output_frequency(output_frequency);
     }
+
+    pub fn update_clock_frequency(&mut self, clock_frequency: f64) {
+        self.resampler.update_clock_frequency(clock_frequency);
+    }
+
+    // Reset to power-on state, keeping the resampler's source and output frequencies
+    pub fn reset_keeping_resampler(&mut self, config: GameBoyEmulatorConfig) {
+        let resampler = mem::replace(&mut self.resampler, GameBoyResampler::new(false));
+        *self = Self { resampler, ..Self::new(config, self.hardware_mode) };
+    }
 }
 
 fn digital_to_analog(sample: Option<u8>) -> i32 {
@@ -41,6 +41,11 @@ impl GameBoyResampler {
     pub fn update_output_frequency(&mut self, output_frequency: u64) {
         self.resampler.update_output_frequency(output_frequency);
     }
+
+    // For a Game Boy that is not clocked at the standard 4.194304 MHz, e.g. inside a Super Game Boy
+    pub fn update_clock_frequency(&mut self, clock_frequency: f64) {
+        self.resampler.update_source_frequency(GB_APU_FREQUENCY * clock_frequency / 4_194_304.0);
+    }
 }
 
 fn gb_source_frequency(audio_60hz_hack: bool) -> f64 {
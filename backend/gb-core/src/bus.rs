@@ -13,6 +13,8 @@ use crate::sm83::InterruptType;
 use crate::sm83::bus::BusInterface;
 use crate::speed::{CpuSpeed, SpeedRegister};
 use crate::timer::GbTimer;
+use jgenesis_common::instrument;
+use jgenesis_common::instrumentation::Subsystem;
 
 trait HardwareModeExt {
     fn read_opri(self) -> u8;
@@ -41,6 +43,7 @@ pub struct Bus<'a> {
     pub timer: &'a mut GbTimer,
     pub dma_unit: &'a mut DmaUnit,
     pub input_state: &'a mut InputState,
+    pub m_cycles_elapsed: u32,
 }
 
 macro_rules! cgb_only_read {
@@ -118,6 +121,8 @@ impl Bus<'_> {
     }
 
     fn tick_components(&mut self) {
+        self.m_cycles_elapsed += 1;
+
         self.timer.tick_m_cycle(self.interrupt_registers);
         self.dma_unit.oam_dma_tick_m_cycle(self.cartridge, self.memory, self.ppu);
         self.serial_port.tick(self.interrupt_registers);
@@ -134,11 +139,17 @@ impl Bus<'_> {
             self.dma_unit.vram_dma_copy_byte(self.cartridge, self.memory, self.ppu);
         }
 
-        for _ in 0..4 {
-            self.ppu.tick_dot(self.speed_register.speed, self.dma_unit, self.interrupt_registers);
-        }
+        instrument!(Subsystem::Ppu, {
+            for _ in 0..4 {
+                self.ppu.tick_dot(
+                    self.speed_register.speed,
+                    self.dma_unit,
+                    self.interrupt_registers,
+                );
+            }
+        });
 
-        self.apu.tick_m_cycle(self.timer, self.speed_register.speed);
+        instrument!(Subsystem::Apu, self.apu.tick_m_cycle(self.timer, self.speed_register.speed));
 
         self.cartridge.tick_cpu();
     }
@@ -8,6 +8,8 @@ use crate::cartridge::mappers::mbc3::Mbc3Rtc;
 use crate::cartridge::mappers::{Mbc1, Mbc2, Mbc3, Mbc5};
 use bincode::{Decode, Encode};
 use jgenesis_common::frontend::SaveWriter;
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
+use jgenesis_common::{memmap, sram};
 use jgenesis_proc_macros::{FakeDecode, FakeEncode, PartialClone};
 use std::mem;
 use std::ops::Deref;
@@ -99,6 +101,18 @@ impl Mapper {
         }
     }
 
+    fn describe_ram_mapping(&self, sram_len: usize) -> String {
+        match self {
+            Self::None if sram_len != 0 => format!("{sram_len} bytes"),
+            Self::None => "No RAM".into(),
+            Self::Mbc1(mbc1) => mbc1.describe_ram_mapping(),
+            Self::Mbc2(mbc2) => mbc2.describe_ram_mapping(),
+            Self::Mbc3(mbc3) => mbc3.describe_ram_mapping(),
+            Self::Mbc5(mbc5) => mbc5.describe_ram_mapping(),
+            Self::Huc3(huc3) => huc3.describe_ram_mapping(),
+        }
+    }
+
     fn mapper_type(&self) -> &'static str {
         match self {
             Self::None => "None",
@@ -130,6 +144,17 @@ impl SoftwareType {
     }
 }
 
+// Other emulators commonly append MBC3 RTC state to the end of the save file, in a 48-byte format
+// (or 44 bytes with a 32-bit timestamp). The RTC is saved separately here, so ignore it
+fn strip_rtc_footer(mut sram: Vec<u8>, sram_len: usize) -> Vec<u8> {
+    if sram.len() == sram_len + 48 || sram.len() == sram_len + 44 {
+        log::info!("Ignoring {}-byte RTC footer in save file", sram.len() - sram_len);
+        sram.truncate(sram_len);
+    }
+
+    sram
+}
+
 #[derive(Debug, Clone, Encode, Decode, PartialClone)]
 pub struct Cartridge {
     #[partial_clone(default)]
@@ -168,10 +193,10 @@ impl Cartridge {
 
         log::info!("SRAM size: {sram_len} bytes");
 
-        let mut sram = match initial_sram {
-            Some(initial_sram) if initial_sram.len() == sram_len => initial_sram,
-            _ => vec![0; sram_len],
-        };
+        let mut sram = initial_sram
+            .map(|initial_sram| strip_rtc_footer(initial_sram, sram_len))
+            .and_then(|initial_sram| sram::normalize(initial_sram, sram_len))
+            .unwrap_or_else(|| vec![0; sram_len]);
 
         let (mapper, has_battery) = match mapper_byte {
             0x00 => (Mapper::None, false),
@@ -241,6 +266,37 @@ impl Cartridge {
         self.sram_dirty = true;
     }
 
+    pub fn describe_memory_map(&self, map: &mut MemoryMap) {
+        for (start, end, name) in
+            [(0x0000, 0x3FFF, "ROM bank 0"), (0x4000, 0x7FFF, "Switchable ROM bank")]
+        {
+            let bank = self.mapper.map_rom_address(start) >> 14;
+            map.add_mapped(
+                start.into(),
+                end,
+                RegionKind::Rom,
+                name,
+                memmap::describe_bank(bank, 0x4000),
+            );
+        }
+
+        map.add_mapped(
+            0xA000,
+            0xBFFF,
+            RegionKind::Ram,
+            "Cartridge RAM",
+            format!(
+                "{}: {}",
+                self.mapper.mapper_type(),
+                self.mapper.describe_ram_mapping(self.sram.len())
+            ),
+        );
+    }
+
+    pub fn rom(&self) -> &[u8] {
+        &self.rom
+    }
+
     pub fn take_rom(&mut self) -> Vec<u8> {
         mem::take(&mut self.rom.0).into_vec()
     }
@@ -260,6 +316,22 @@ impl Cartridge {
         }
     }
 
+    pub fn sram_mut(&mut self) -> &mut [u8] {
+        self.sram_dirty = true;
+        match &mut self.mapper {
+            Mapper::Mbc2(mbc2) => mbc2.ram_mut(),
+            _ => &mut self.sram,
+        }
+    }
+
+    pub fn restore_sram(&mut self, bytes: &[u8]) {
+        match &mut self.mapper {
+            Mapper::Mbc2(mbc2) => mbc2.ram_mut().copy_from_slice(bytes),
+            _ => self.sram.copy_from_slice(bytes),
+        }
+        self.sram_dirty = true;
+    }
+
     pub fn get_and_clear_sram_dirty(&mut self) -> bool {
         mem::take(&mut self.sram_dirty)
     }
@@ -272,8 +344,8 @@ impl Cartridge {
         }
     }
 
-    pub fn save_rtc_state<S: SaveWriter>(&mut self, save_writer: &mut S) -> Result<(), S::Err> {
-        match &mut self.mapper {
+    pub fn save_rtc_state<S: SaveWriter>(&self, save_writer: &mut S) -> Result<(), S::Err> {
+        match &self.mapper {
             Mapper::Mbc3(mbc3) => mbc3.save_rtc_state(save_writer)?,
             Mapper::Huc3(huc3) => huc3.save_rtc_state(save_writer)?,
             _ => {}
@@ -288,3 +360,24 @@ impl Cartridge {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_rtc_footer_sizes() {
+        let sram: Vec<u8> = (0..32).collect();
+
+        for footer_len in [44, 48] {
+            let mut with_footer = sram.clone();
+            with_footer.extend(std::iter::repeat_n(0xAB, footer_len));
+            assert_eq!(strip_rtc_footer(with_footer, sram.len()), sram);
+        }
+
+        // Other sizes are left for the generic save file conversions to handle
+        assert_eq!(strip_rtc_footer(sram.clone(), sram.len()), sram);
+        assert_eq!(strip_rtc_footer(sram.clone(), 16), sram);
+        assert_eq!(strip_rtc_footer(sram[..20].to_vec(), 16).len(), 20);
+    }
+}
@@ -5,6 +5,7 @@ use crate::cartridge::HasBasicRamMapping;
 use crate::cartridge::mappers::mbc3::Mbc3Rtc;
 use bincode::{Decode, Encode};
 use jgenesis_common::frontend::SaveWriter;
+use jgenesis_common::memmap;
 use jgenesis_common::num::{GetBit, U16Ext};
 
 #[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
@@ -56,6 +57,10 @@ impl Mbc1 {
         }
     }
 
+    pub fn describe_ram_mapping(&self) -> String {
+        describe_sram_mapping(self.map_ram_address(0xA000))
+    }
+
     pub fn write_rom_address(&mut self, address: u16, value: u8) {
         log::trace!("MBC1 register write: {address:04X} {value:02X}");
 
@@ -133,6 +138,10 @@ impl Mbc2 {
         basic_map_rom_address(address, self.rom_bank.into(), false, self.rom_addr_mask)
     }
 
+    pub fn describe_ram_mapping(&self) -> String {
+        if self.ram_enabled { "512x4-bit internal RAM".into() } else { "RAM disabled".into() }
+    }
+
     pub fn read_ram(&self, address: u16) -> u8 {
         if !self.ram_enabled {
             return 0xFF;
@@ -155,6 +164,10 @@ impl Mbc2 {
         self.ram.as_ref()
     }
 
+    pub fn ram_mut(&mut self) -> &mut [u8] {
+        self.ram.as_mut()
+    }
+
     pub fn write_rom_address(&mut self, address: u16, value: u8) {
         // MBC2 only has two registers, both mapped to $0000-$3FFF
         if !(0x0000..0x4000).contains(&address) {
@@ -222,6 +235,19 @@ impl Mbc3 {
         }
     }
 
+    pub fn describe_ram_mapping(&self) -> String {
+        match self.ram_bank {
+            0x00..=0x03 => describe_sram_mapping(basic_map_ram_address(
+                self.ram_enabled,
+                0xA000,
+                self.ram_bank.into(),
+                self.ram_addr_mask,
+            )),
+            0x08..=0x0C if self.rtc.is_some() => format!("RTC register ${:02X}", self.ram_bank),
+            _ => "Open bus".into(),
+        }
+    }
+
     pub fn read_ram(&self, address: u16, sram: &[u8]) -> u8 {
         match self.ram_bank {
             0x00..=0x03 => {
@@ -305,6 +331,10 @@ impl Mbc5 {
         basic_map_rom_address(address, self.rom_bank.into(), true, self.rom_addr_mask)
     }
 
+    pub fn describe_ram_mapping(&self) -> String {
+        describe_sram_mapping(self.map_ram_address(0xA000))
+    }
+
     pub fn write_rom_address(&mut self, address: u16, value: u8) {
         log::trace!("MBC5 register write: {address:04X} {value:02X}");
 
@@ -342,6 +372,13 @@ impl HasBasicRamMapping for Mbc5 {
     }
 }
 
+pub(crate) fn describe_sram_mapping(ram_addr: Option<u32>) -> String {
+    match ram_addr {
+        Some(ram_addr) => memmap::describe_bank(ram_addr >> 13, 0x2000),
+        None => "RAM disabled".into(),
+    }
+}
+
 // MBC2 / MBC3 / MBC5
 fn basic_map_rom_address(
     address: u16,
@@ -360,7 +397,7 @@ fn basic_map_rom_address(
 }
 
 // MBC3 / MBC5
-fn basic_map_ram_address(
+pub(crate) fn basic_map_ram_address(
     ram_enabled: bool,
     address: u16,
     ram_bank: u32,
@@ -4,7 +4,9 @@
 //!   <https://gbdev.io/pandocs/HuC3.html>
 //!   <https://gbdev.gg8.se/forums/viewtopic.php?id=744>
 
-use crate::cartridge::mappers::{basic_map_ram_address, basic_map_rom_address};
+use crate::cartridge::mappers::{
+    basic_map_ram_address, basic_map_rom_address, describe_sram_mapping,
+};
 use bincode::{Decode, Encode};
 use jgenesis_common::frontend::SaveWriter;
 use jgenesis_common::num::GetBit;
@@ -92,6 +94,17 @@ impl Huc3 {
         }
     }
 
+    pub fn describe_ram_mapping(&self) -> String {
+        match self.ram_mapping {
+            RamMapping::RamReadOnly | RamMapping::RamReadWrite => {
+                let ram_addr =
+                    basic_map_ram_address(true, 0xA000, self.ram_bank.into(), self.ram_addr_mask);
+                format!("{:?}, {}", self.ram_mapping, describe_sram_mapping(ram_addr))
+            }
+            ram_mapping => format!("{ram_mapping:?}"),
+        }
+    }
+
     pub fn read_ram(&self, address: u16, sram: &[u8]) -> u8 {
         match self.ram_mapping {
             RamMapping::RamReadOnly | RamMapping::RamReadWrite => {
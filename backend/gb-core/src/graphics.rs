@@ -2,7 +2,6 @@ use crate::api::{GbPalette, GbcColorCorrection};
 use crate::ppu::PpuFrameBuffer;
 use crate::{HardwareMode, ppu};
 use jgenesis_common::frontend::Color;
-use jgenesis_proc_macros::{FakeDecode, FakeEncode};
 use std::array;
 use std::ops::{Deref, DerefMut};
 use std::sync::LazyLock;
@@ -25,7 +24,7 @@ pub(crate) const RGB_5_TO_8: &[u8; 32] = &[
     181, 189, 197, 206, 214, 222, 230, 239, 247, 255,
 ];
 
-#[derive(Debug, Clone, FakeEncode, FakeDecode)]
+#[derive(Debug, Clone)]
 pub struct RgbaFrameBuffer(Box<[Color; ppu::FRAME_BUFFER_LEN]>);
 
 impl RgbaFrameBuffer {
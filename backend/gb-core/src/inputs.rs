@@ -1,6 +1,7 @@
 //! Game Boy input handling
 
 use crate::interrupts::InterruptRegisters;
+use crate::sgb::SgbPort;
 use crate::sm83::InterruptType;
 use bincode::{Decode, Encode};
 use jgenesis_common::define_controller_inputs;
@@ -27,6 +28,13 @@ impl MappableInputs<GameBoyButton> for GameBoyInputs {
     fn set_field(&mut self, button: GameBoyButton, _player: Player, pressed: bool) {
         self.set_button(button, pressed);
     }
+
+    fn button_states(&self, player: Player) -> Vec<(GameBoyButton, bool)> {
+        match player {
+            Player::One => GameBoyInputs::button_states(self),
+            Player::Two => vec![],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Encode, Decode)]
@@ -35,6 +43,7 @@ pub(crate) struct InputState {
     d_pad_selected: bool,
     buttons_selected: bool,
     prev_joyp: u8,
+    sgb_port: Option<SgbPort>,
 }
 
 impl InputState {
@@ -44,9 +53,22 @@ impl InputState {
             d_pad_selected: false,
             buttons_selected: false,
             prev_joyp: 0xFF,
+            sgb_port: None,
         }
     }
 
+    pub(crate) fn enable_sgb_port(&mut self) {
+        self.set_sgb_port(SgbPort::new());
+    }
+
+    pub(crate) fn set_sgb_port(&mut self, sgb_port: SgbPort) {
+        self.sgb_port = Some(sgb_port);
+    }
+
+    pub(crate) fn sgb_port_mut(&mut self) -> Option<&mut SgbPort> {
+        self.sgb_port.as_mut()
+    }
+
     pub(crate) fn set_inputs(&mut self, inputs: GameBoyInputs) {
         self.inputs = inputs;
     }
@@ -55,6 +77,10 @@ impl InputState {
         self.buttons_selected = !value.bit(5);
         self.d_pad_selected = !value.bit(4);
 
+        if let Some(sgb_port) = &mut self.sgb_port {
+            sgb_port.handle_joyp_write(value);
+        }
+
         log::trace!("JOYP write: {value:02X}");
     }
 
@@ -71,14 +97,23 @@ impl InputState {
     }
 
     pub(crate) fn read_joyp(&self) -> u8 {
-        let bit_3_inverted = (self.buttons_selected && self.inputs.start)
-            || (self.d_pad_selected && self.inputs.down);
-        let bit_2_inverted = (self.buttons_selected && self.inputs.select)
-            || (self.d_pad_selected && self.inputs.up);
+        let inputs = self.sgb_port.as_ref().map_or(self.inputs, SgbPort::current_inputs);
+
+        // In Super Game Boy multiplayer mode, the joypad ID is readable when neither line is selected
+        if let Some(joypad_id) = self.sgb_port.as_ref().and_then(SgbPort::joypad_id) {
+            if !self.buttons_selected && !self.d_pad_selected {
+                return 0xF0 | (0x0F - joypad_id);
+            }
+        }
+
+        let bit_3_inverted =
+            (self.buttons_selected && inputs.start) || (self.d_pad_selected && inputs.down);
+        let bit_2_inverted =
+            (self.buttons_selected && inputs.select) || (self.d_pad_selected && inputs.up);
         let bit_1_inverted =
-            (self.buttons_selected && self.inputs.b) || (self.d_pad_selected && self.inputs.left);
+            (self.buttons_selected && inputs.b) || (self.d_pad_selected && inputs.left);
         let bit_0_inverted =
-            (self.buttons_selected && self.inputs.a) || (self.d_pad_selected && self.inputs.right);
+            (self.buttons_selected && inputs.a) || (self.d_pad_selected && inputs.right);
 
         0xC0 | (u8::from(!self.buttons_selected) << 5)
             | (u8::from(!self.buttons_selected) << 4)
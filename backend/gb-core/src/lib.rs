@@ -10,6 +10,7 @@ mod interrupts;
 mod memory;
 mod ppu;
 mod serial;
+mod sgb;
 mod sm83;
 mod speed;
 mod timer;
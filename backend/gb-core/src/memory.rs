@@ -2,6 +2,7 @@
 
 use crate::HardwareMode;
 use bincode::{Decode, Encode};
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
 use std::iter;
 
 const MAIN_RAM_LEN: usize = 32 * 1024;
@@ -48,6 +49,22 @@ impl Memory {
         }
     }
 
+    pub fn main_ram(&self) -> &[u8] {
+        self.main_ram.as_ref()
+    }
+
+    pub fn main_ram_mut(&mut self) -> &mut [u8] {
+        self.main_ram.as_mut()
+    }
+
+    pub fn hram(&self) -> &[u8] {
+        self.hram.as_ref()
+    }
+
+    pub fn hram_mut(&mut self) -> &mut [u8] {
+        self.hram.as_mut()
+    }
+
     pub fn read_main_ram(&self, address: u16) -> u8 {
         let ram_addr = map_ram_address(address, self.main_ram_bank);
         self.main_ram[ram_addr as usize]
@@ -66,6 +83,25 @@ impl Memory {
         self.hram[(address & 0x7F) as usize] = value;
     }
 
+    pub fn describe_memory_map(&self, map: &mut MemoryMap, hardware_mode: HardwareMode) {
+        map.add(0xC000, 0xCFFF, RegionKind::Ram, "WRAM bank 0");
+        match hardware_mode {
+            HardwareMode::Dmg => map.add(0xD000, 0xDFFF, RegionKind::Ram, "WRAM bank 1"),
+            HardwareMode::Cgb => {
+                let bank = map_ram_address(0xD000, self.main_ram_bank) >> 12;
+                map.add_mapped(
+                    0xD000,
+                    0xDFFF,
+                    RegionKind::Ram,
+                    "Switchable WRAM bank",
+                    format!("Bank {bank}"),
+                )
+            }
+        };
+        map.add_mirror(0xE000, 0xFDFF, RegionKind::Ram, "Echo RAM", 0xC000, 0xDDFF);
+        map.add(0xFF80, 0xFFFE, RegionKind::Ram, "HRAM");
+    }
+
     pub fn read_svbk(&self) -> u8 {
         0xF8 | self.main_ram_bank
     }
@@ -14,7 +14,7 @@ use crate::speed::CpuSpeed;
 use bincode::{Decode, Encode};
 use jgenesis_common::frontend::FrameSize;
 use jgenesis_common::num::GetBit;
-use jgenesis_proc_macros::{FakeDecode, FakeEncode};
+use jgenesis_proc_macros::SaveState;
 use std::ops::{Deref, DerefMut, Range};
 
 const SCREEN_WIDTH: usize = 160;
@@ -38,7 +38,7 @@ const OAM_LEN: usize = 160;
 type Vram = [u8; VRAM_LEN];
 type Oam = [u8; OAM_LEN];
 
-#[derive(Debug, Clone, FakeEncode, FakeDecode)]
+#[derive(Debug, Clone)]
 pub struct PpuFrameBuffer(Box<[u16; FRAME_BUFFER_LEN]>);
 
 impl PpuFrameBuffer {
@@ -46,6 +46,11 @@ impl PpuFrameBuffer {
         self.0.iter().copied()
     }
 
+    pub fn line(&self, line: u8) -> &[u16] {
+        let start = (line as usize) * SCREEN_WIDTH;
+        &self.0[start..start + SCREEN_WIDTH]
+    }
+
     fn set(&mut self, line: u8, pixel: u8, color: u16) {
         self[(line as usize) * SCREEN_WIDTH + (pixel as usize)] = color;
     }
@@ -183,9 +188,10 @@ struct SpriteData {
     low_priority: bool,
 }
 
-#[derive(Debug, Clone, Encode, Decode)]
+#[derive(Debug, Clone, SaveState)]
 pub struct Ppu {
     hardware_mode: HardwareMode,
+    #[savestate(skip)]
     frame_buffer: PpuFrameBuffer,
     vram: Box<Vram>,
     oam: Box<Oam>,
@@ -403,6 +409,10 @@ impl Ppu {
         &self.frame_buffer
     }
 
+    pub fn scanline(&self) -> u8 {
+        self.state.scanline
+    }
+
     pub fn frame_complete(&self) -> bool {
         self.state.frame_complete
     }
@@ -411,6 +421,10 @@ impl Ppu {
         self.state.frame_complete = false;
     }
 
+    pub fn vram_bank(&self) -> u8 {
+        self.registers.vram_bank
+    }
+
     pub fn read_vram(&self, address: u16) -> u8 {
         if self.cpu_can_access_vram() {
             let vram_addr = map_vram_address(address, self.registers.vram_bank);
@@ -11,6 +11,14 @@ impl Ppu {
         self.registers.double_height_sprites
     }
 
+    pub fn vram(&self) -> &[u8] {
+        self.vram.as_ref()
+    }
+
+    pub fn vram_mut(&mut self) -> &mut [u8] {
+        self.vram.as_mut()
+    }
+
     pub fn copy_background(&self, tile_map: BackgroundTileMap, out: &mut [Color]) {
         let tile_map_addr = match tile_map {
             BackgroundTileMap::Zero => registers::TILE_MAP_AREA_0,
@@ -138,6 +146,23 @@ impl Ppu {
         }
     }
 
+    /// Raw palette contents: the BGP/OBP0/OBP1 registers in DMG mode, or BG palette RAM followed by
+    /// OBJ palette RAM in CGB mode.
+    pub fn dump_palettes(&self) -> Vec<u8> {
+        match self.hardware_mode {
+            HardwareMode::Dmg => vec![
+                self.registers.read_bgp(),
+                self.registers.read_obp0(),
+                self.registers.read_obp1(),
+            ],
+            HardwareMode::Cgb => {
+                let mut bytes = self.bg_palette_ram.as_bytes().to_vec();
+                bytes.extend_from_slice(self.sprite_palette_ram.as_bytes());
+                bytes
+            }
+        }
+    }
+
     fn copy_palettes_dmg(&self, out: &mut [Color]) {
         for (bg_color_id, dmg_color) in self.registers.bg_palette.into_iter().enumerate() {
             let color = resolve_dmg_color(dmg_color);
@@ -290,6 +290,10 @@ impl CgbPaletteRam {
         Self::new(initial_ram.into_boxed_slice().try_into().unwrap())
     }
 
+    pub fn as_bytes(&self) -> &[u8] {
+        self.ram.as_ref()
+    }
+
     pub fn read_data_port_address(&self) -> u8 {
         0x40 | (u8::from(self.data_port_auto_increment) << 7) | self.data_port_address
     }
@@ -0,0 +1,195 @@
+//! Super Game Boy joypad port handling
+//!
+//! In the Super Game Boy, the ICD2 chip is wired to the Game Boy's P14/P15 joypad select lines. It
+//! decodes command packets that software sends by pulsing these lines, and it supplies joypad state
+//! for up to 4 players from the SNES side
+
+use crate::inputs::GameBoyInputs;
+use bincode::{Decode, Encode};
+use jgenesis_common::num::GetBit;
+use std::collections::VecDeque;
+
+pub const PACKET_LEN: usize = 16;
+
+pub type SgbPacket = [u8; PACKET_LEN];
+
+const PACKET_BITS: u8 = 8 * PACKET_LEN as u8;
+
+// The SGB boot ROM sends the cartridge header to the SNES in 6 packets before starting the game.
+// These use command bytes $F1, $F3, ..., $FB, each followed by 15 header bytes starting at $0104
+const HEADER_PACKETS: u8 = 6;
+const HEADER_PACKET_COMMAND: u8 = 0xF1;
+const HEADER_START_ADDR: u16 = 0x0104;
+const HEADER_BYTES_PER_PACKET: u16 = PACKET_LEN as u16 - 1;
+
+#[derive(Debug, Clone, Encode, Decode)]
+struct PacketReceiver {
+    data: SgbPacket,
+    bits_received: u8,
+    awaiting_release: bool,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+pub(crate) struct SgbPort {
+    receiver: Option<PacketReceiver>,
+    packets: VecDeque<SgbPacket>,
+    joypads: [GameBoyInputs; 4],
+    player_count: u8,
+    current_player: u8,
+    player_locked: bool,
+}
+
+impl SgbPort {
+    pub(crate) fn new() -> Self {
+        Self {
+            receiver: None,
+            packets: VecDeque::new(),
+            joypads: [GameBoyInputs::default(); 4],
+            player_count: 1,
+            current_player: 0,
+            player_locked: true,
+        }
+    }
+
+    // Queue the packets that the boot ROM would send, since the boot ROM is not emulated
+    pub(crate) fn queue_header_packets(&mut self, read_rom: impl Fn(u16) -> u8) {
+        for i in 0..HEADER_PACKETS {
+            let mut packet = [0; PACKET_LEN];
+            packet[0] = HEADER_PACKET_COMMAND + 2 * i;
+
+            let start_addr = HEADER_START_ADDR + u16::from(i) * HEADER_BYTES_PER_PACKET;
+            for (j, byte) in packet[1..].iter_mut().enumerate() {
+                *byte = read_rom(start_addr + j as u16);
+            }
+
+            self.packets.push_back(packet);
+        }
+    }
+
+    pub(crate) fn handle_joyp_write(&mut self, value: u8) {
+        let p14 = value.bit(4);
+        let p15 = value.bit(5);
+
+        match (p14, p15) {
+            (false, false) => {
+                // Reset pulse; starts a new packet
+                self.receiver = Some(PacketReceiver {
+                    data: [0; PACKET_LEN],
+                    bits_received: 0,
+                    awaiting_release: true,
+                });
+            }
+            (true, true) => {
+                if let Some(receiver) = &mut self.receiver {
+                    receiver.awaiting_release = false;
+                }
+
+                // Multiplayer joypad ID advances when P15 goes from low to high
+                if self.player_count > 1 && !self.player_locked {
+                    self.current_player = (self.current_player + 1) % self.player_count;
+                    self.player_locked = true;
+                }
+            }
+            (false, true) | (true, false) => {
+                if !p15 {
+                    self.player_locked = false;
+                }
+
+                // P14 low transmits a 0 bit, P15 low transmits a 1 bit
+                self.receive_bit(!p15);
+            }
+        }
+    }
+
+    fn receive_bit(&mut self, bit: bool) {
+        let Some(receiver) = &mut self.receiver else { return };
+        if receiver.awaiting_release {
+            return;
+        }
+
+        if receiver.bits_received == PACKET_BITS {
+            // Stop bit
+            log::trace!("Received SGB packet: {:02X?}", receiver.data);
+            self.packets.push_back(receiver.data);
+            self.receiver = None;
+            return;
+        }
+
+        let byte = (receiver.bits_received / 8) as usize;
+        let shift = receiver.bits_received % 8;
+        receiver.data[byte] |= u8::from(bit) << shift;
+        receiver.bits_received += 1;
+        receiver.awaiting_release = true;
+    }
+
+    pub(crate) fn take_packet(&mut self) -> Option<SgbPacket> {
+        self.packets.pop_front()
+    }
+
+    pub(crate) fn current_inputs(&self) -> GameBoyInputs {
+        self.joypads[self.current_player as usize]
+    }
+
+    pub(crate) fn joypad_id(&self) -> Option<u8> {
+        (self.player_count > 1).then_some(self.current_player)
+    }
+
+    pub(crate) fn set_joypads(&mut self, joypads: [GameBoyInputs; 4]) {
+        self.joypads = joypads;
+    }
+
+    pub(crate) fn set_player_count(&mut self, player_count: u8) {
+        let player_count = player_count.clamp(1, 4);
+        if player_count != self.player_count {
+            self.player_count = player_count;
+            self.current_player = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send_packet(port: &mut SgbPort, packet: SgbPacket) {
+        port.handle_joyp_write(0x00);
+        port.handle_joyp_write(0x30);
+        for i in 0..PACKET_BITS {
+            let bit = packet[(i / 8) as usize].bit(i % 8);
+            port.handle_joyp_write(if bit { 0x10 } else { 0x20 });
+            port.handle_joyp_write(0x30);
+        }
+        port.handle_joyp_write(0x20);
+        port.handle_joyp_write(0x30);
+    }
+
+    #[test]
+    fn packet_round_trip() {
+        let mut port = SgbPort::new();
+        let packet: SgbPacket = std::array::from_fn(|i| (i as u8).wrapping_mul(37) ^ 0xA5);
+
+        send_packet(&mut port, packet);
+
+        assert_eq!(port.take_packet(), Some(packet));
+        assert_eq!(port.take_packet(), None);
+    }
+
+    #[test]
+    fn multiplayer_joypad_id() {
+        let mut port = SgbPort::new();
+        port.set_player_count(2);
+
+        assert_eq!(port.joypad_id(), Some(0));
+        port.handle_joyp_write(0x10);
+        port.handle_joyp_write(0x30);
+        assert_eq!(port.joypad_id(), Some(1));
+
+        // Repeated deselects without P15 going low should not advance the ID
+        port.handle_joyp_write(0x30);
+        assert_eq!(port.joypad_id(), Some(1));
+
+        port.handle_joyp_write(0x10);
+        port.handle_joyp_write(0x30);
+        assert_eq!(port.joypad_id(), Some(0));
+    }
+}
@@ -2,16 +2,20 @@
 
 use crate::audio::GenesisAudioResampler;
 use crate::input::{GenesisButton, GenesisInputs, InputState};
-use crate::memory::{Cartridge, MainBus, MainBusSignals, MainBusWrites, Memory};
+use crate::memory::{self, Cartridge, MainBus, MainBusSignals, MainBusWrites, Memory};
 use crate::timing::{CycleCounters, GenesisCycleCounters};
-use crate::vdp::{TimingModeExt, Vdp, VdpConfig, VdpTickEffect};
+use crate::vdp::{DebugRegister, TimingModeExt, Vdp, VdpConfig, VdpTickEffect};
 use crate::ym2612::{Ym2612, YmTickEffect};
 use crate::{GenesisControllerType, audio, timing, vdp};
 use bincode::{Decode, Encode};
+use jgenesis_common::eventlog::EventLog;
 use jgenesis_common::frontend::{
-    AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, FrameSize, PartialClone,
-    PixelAspectRatio, Renderer, SaveWriter, TickEffect, TimingMode,
+    AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, FrameSize, GameMetadata, PartialClone,
+    PixelAspectRatio, Renderer, SaveStateSafePoint, SaveWriter, TickEffect, TimingMode,
 };
+use jgenesis_common::instrument;
+use jgenesis_common::instrumentation::Subsystem;
+use jgenesis_common::memmap::MemoryMap;
 use jgenesis_common::num::GetBit;
 use jgenesis_proc_macros::{ConfigDisplay, EnumAll, EnumDisplay, EnumFromStr};
 use m68000_emu::M68000;
@@ -44,6 +48,9 @@ pub enum GenesisAspectRatio {
     Ntsc,
     Pal,
     SquarePixels,
+    /// Square pixels in H320px mode, and H256px mode stretched to the same width so that the
+    /// displayed image does not change size when games switch between the two modes
+    SquarePixelsH320,
     Stretched,
 }
 
@@ -56,13 +63,14 @@ impl GenesisAspectRatio {
         adjust_for_2x_resolution: bool,
     ) -> Option<PixelAspectRatio> {
         let mut pixel_aspect_ratio = match (self, frame_size.width) {
-            (Self::SquarePixels, _) => Some(1.0),
+            (Self::SquarePixels, _) | (Self::SquarePixelsH320, 320..=347) => Some(1.0),
             (Self::Stretched, _) => None,
             (Self::Ntsc, 256..=284) => Some(8.0 / 7.0),
             (Self::Ntsc, 320..=347) => Some(32.0 / 35.0),
             (Self::Pal, 256..=284) => Some(11.0 / 8.0),
             (Self::Pal, 320..=347) => Some(11.0 / 10.0),
-            (Self::Ntsc | Self::Pal, _) => {
+            (Self::SquarePixelsH320, 256..=284) => Some(320.0 / 256.0),
+            (Self::Ntsc | Self::Pal | Self::SquarePixelsH320, _) => {
                 log::error!("unexpected Genesis frame width: {}", frame_size.width);
                 None
             }
@@ -88,13 +96,25 @@ pub enum GenesisRegion {
 impl GenesisRegion {
     #[must_use]
     pub fn from_rom(rom: &[u8]) -> Option<Self> {
-        if &rom[0x1F0..0x1F6] == b"EUROPE" {
+        let region_field = rom.get(0x1F0..0x200)?;
+
+        // A few headers have the region code padded with leading spaces or written in lowercase
+        let region_field: Vec<u8> = region_field
+            .iter()
+            .copied()
+            .skip_while(|&b| b == b' ')
+            .map(|b| b.to_ascii_uppercase())
+            .collect();
+
+        if region_field.starts_with(b"EUROPE") {
             // Another World (E) has the string "EUROPE" in the region section; special case this
             // so that it's not detected as U (this game does not work with NTSC timings)
             return Some(GenesisRegion::Europe);
         }
 
-        let region_bytes = &rom[0x1F0..0x1F3];
+        let Some(region_bytes) = region_field.get(..3) else {
+            return Self::from_header_titles(rom);
+        };
 
         // Prefer Americas if region code contains a 'U'
         if region_bytes.contains(&b'U') {
@@ -113,7 +133,9 @@ impl GenesisRegion {
 
         // If region code contains neither a 'U' nor a 'J', treat it as a hex char
         let c = region_bytes[0] as char;
-        let value = u8::from_str_radix(&c.to_string(), 16).ok()?;
+        let Ok(value) = u8::from_str_radix(&c.to_string(), 16) else {
+            return Self::from_header_titles(rom);
+        };
         if value.bit(2) {
             // Bit 2 = Americas
             Some(GenesisRegion::Americas)
@@ -125,10 +147,20 @@ impl GenesisRegion {
             Some(GenesisRegion::Europe)
         } else {
             // Invalid
-            None
+            Self::from_header_titles(rom)
         }
     }
 
+    // Last resort for headers with a missing or garbage region code: a ROM that only has a
+    // domestic title is almost certainly a Japanese release
+    fn from_header_titles(rom: &[u8]) -> Option<Self> {
+        let is_blank = |title: &[u8]| title.iter().all(|&b| b == b' ' || b == 0);
+
+        let domestic_title = rom.get(0x120..0x150)?;
+        let overseas_title = rom.get(0x150..0x180)?;
+        (!is_blank(domestic_title) && is_blank(overseas_title)).then_some(GenesisRegion::Japan)
+    }
+
     #[must_use]
     pub fn version_bit(self) -> bool {
         self != Self::Japan
@@ -144,6 +176,14 @@ pub enum GenesisLowPassFilter {
     Model1Va2,
 }
 
+/// Memory regions that can be dumped and restored for debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumAll)]
+pub enum MemoryRegion {
+    WorkRam,
+    Vram,
+    CartridgeRam,
+}
+
 #[derive(Debug, Clone, Copy, Encode, Decode, ConfigDisplay)]
 pub struct GenesisEmulatorConfig {
     pub p1_controller_type: GenesisControllerType,
@@ -155,6 +195,7 @@ pub struct GenesisEmulatorConfig {
     pub remove_sprite_limits: bool,
     pub m68k_clock_divider: u64,
     pub emulate_non_linear_vdp_dac: bool,
+    pub expanded_vram: bool,
     pub deinterlace: bool,
     pub render_vertical_border: bool,
     pub render_horizontal_border: bool,
@@ -184,6 +225,7 @@ impl GenesisEmulatorConfig {
             sprites_enabled: self.sprites_enabled,
             window_enabled: self.window_enabled,
             backdrop_enabled: self.backdrop_enabled,
+            expanded_vram: self.expanded_vram,
         }
     }
 
@@ -205,6 +247,21 @@ impl EmulatorConfigTrait for GenesisEmulatorConfig {
     fn with_overclocking_disabled(&self) -> Self {
         Self { m68k_clock_divider: timing::NATIVE_M68K_DIVIDER, ..*self }
     }
+
+    fn with_enhancements_disabled(&self) -> Self {
+        Self {
+            remove_sprite_limits: false,
+            expanded_vram: false,
+            plane_a_enabled: true,
+            plane_b_enabled: true,
+            sprites_enabled: true,
+            window_enabled: true,
+            backdrop_enabled: true,
+            ym2612_enabled: true,
+            psg_enabled: true,
+            ..self.with_overclocking_disabled()
+        }
+    }
 }
 
 #[derive(Debug, Encode, Decode, PartialClone)]
@@ -298,9 +355,22 @@ impl GenesisEmulator {
         emulator
     }
 
+    /// Number of bytes that [`Self::read_header_metadata`] needs from the start of a ROM image
+    pub const HEADER_PREFIX_LEN: usize = memory::HEADER_PREFIX_LEN;
+
+    /// Parse the title, serial number, region, and game-specific workarounds for a ROM image
+    /// without creating an emulator. Accepts the same ROM formats as [`Self::create`].
     #[must_use]
-    pub fn cartridge_title(&self) -> String {
-        self.memory.game_title()
+    pub fn read_game_metadata(rom: Vec<u8>) -> GameMetadata {
+        memory::read_cartridge_metadata(rom)
+    }
+
+    /// Parse the title, serial number, and region from the first [`Self::HEADER_PREFIX_LEN`] bytes
+    /// of a ROM image, e.g. to show header information for many ROM files without reading them in
+    /// full. Does not include game-specific workarounds.
+    #[must_use]
+    pub fn read_header_metadata(rom_prefix: &[u8]) -> GameMetadata {
+        memory::read_header_metadata(rom_prefix)
     }
 
     #[inline]
@@ -322,13 +392,118 @@ impl GenesisEmulator {
         self.vdp.copy_cram(out);
     }
 
+    #[must_use]
+    pub fn dump_cram(&self) -> Vec<u8> {
+        self.vdp.dump_cram()
+    }
+
     pub fn copy_vram(&self, out: &mut [Color], palette: u8, row_len: usize) {
         self.vdp.copy_vram(out, palette, row_len);
     }
 
+    /// Describe the 68000 and Z80 memory maps, including mapper bank state and where the Z80 bank
+    /// window currently points.
+    #[must_use]
+    pub fn memory_map_description(&self) -> Vec<MemoryMap> {
+        vec![self.memory.memory_map(), self.memory.z80_memory_map()]
+    }
+
+    /// Log of 68000 / Z80 handshake events, e.g. Z80 bus requests. Disabled by default.
+    #[must_use]
+    pub fn event_log(&self) -> &EventLog {
+        self.memory.event_log()
+    }
+
+    pub fn event_log_mut(&mut self) -> &mut EventLog {
+        self.memory.event_log_mut()
+    }
+
+    #[must_use]
+    pub fn dump_memory(&self, region: MemoryRegion) -> Vec<u8> {
+        match region {
+            MemoryRegion::WorkRam => self.memory.main_ram().to_vec(),
+            MemoryRegion::Vram => self.vdp.dump_vram(),
+            MemoryRegion::CartridgeRam => self.memory.external_ram().to_vec(),
+        }
+    }
+
+    /// Overwrite a memory region, e.g. with the contents of a file previously written using
+    /// [`Self::dump_memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not the same length as the memory region.
+    pub fn restore_memory(&mut self, region: MemoryRegion, bytes: &[u8]) {
+        match region {
+            MemoryRegion::WorkRam => self.memory.main_ram_mut().copy_from_slice(bytes),
+            MemoryRegion::Vram => self.vdp.restore_vram(bytes),
+            MemoryRegion::CartridgeRam => self.memory.restore_external_ram(bytes),
+        }
+    }
+
     pub fn dump_vdp_registers(&self, callback: impl FnMut(&str, &[(&str, &str)])) {
         self.vdp.dump_registers(callback);
     }
+
+    #[must_use]
+    pub fn vdp_debug_register(&self) -> DebugRegister {
+        self.vdp.debug_register()
+    }
+
+    pub fn set_vdp_debug_register(&mut self, debug_register: DebugRegister) {
+        self.vdp.set_debug_register(debug_register);
+    }
+
+    /// Mute or unmute one of the 6 YM2612 channels in the audio output.
+    pub fn set_ym2612_channel_muted(&mut self, channel: usize, muted: bool) {
+        self.ym2612.set_channel_muted(channel, muted);
+    }
+
+    /// Whether one of the 6 YM2612 channels is currently muted. Mutes are not saved in save states,
+    /// so loading a state unmutes every channel.
+    #[must_use]
+    pub fn is_ym2612_channel_muted(&self, channel: usize) -> bool {
+        self.ym2612.is_channel_muted(channel)
+    }
+
+    /// Mute or unmute one of the 4 PSG channels in the audio output (0-2 square, 3 noise).
+    pub fn set_psg_channel_muted(&mut self, channel: usize, muted: bool) {
+        self.psg.set_channel_muted(channel, muted);
+    }
+
+    /// Whether one of the 4 PSG channels is currently muted. Mutes are not saved in save states, so
+    /// loading a state unmutes every channel.
+    #[must_use]
+    pub fn is_psg_channel_muted(&self, channel: usize) -> bool {
+        self.psg.is_channel_muted(channel)
+    }
+
+    /// Plug or unplug a link cable in controller port 2. While plugged in, the frontend is
+    /// responsible for exchanging pin levels and serial bytes with the other console.
+    pub fn set_link_cable_connected(&mut self, connected: bool) {
+        self.input.set_link_cable_connected(connected);
+    }
+
+    /// Port 2 pin levels that should be sent to the other console.
+    #[must_use]
+    pub fn link_cable_output_pins(&self) -> u8 {
+        self.input.link_cable_output_pins()
+    }
+
+    /// Update port 2 pin levels received from the other console.
+    pub fn set_link_cable_input_pins(&mut self, pins: u8) {
+        self.input.set_link_cable_input_pins(pins);
+    }
+
+    /// Take a byte transmitted in port 2 serial mode that should be sent to the other console.
+    pub fn take_link_cable_tx_byte(&mut self) -> Option<u8> {
+        self.input.take_link_cable_tx_byte()
+    }
+
+    /// Receive a byte in port 2 serial mode that was transmitted by the other console.
+    pub fn receive_link_cable_byte(&mut self, byte: u8) {
+        self.input.receive_link_cable_byte(byte);
+    }
 }
 
 /// Render the current VDP frame buffer.
@@ -342,6 +517,8 @@ pub fn render_frame<R: Renderer>(
     adjust_aspect_ratio_in_2x_resolution: bool,
     renderer: &mut R,
 ) -> Result<(), R::Err> {
+    instrument!("genesis::render_frame");
+
     let frame_size = vdp.frame_size();
     let pixel_aspect_ratio =
         aspect_ratio.to_pixel_aspect_ratio(frame_size, adjust_aspect_ratio_in_2x_resolution);
@@ -387,7 +564,7 @@ impl EmulatorTrait for GenesisEmulator {
         let m68k_cycles = if self.cycles.m68k_wait_cpu_cycles != 0 {
             self.cycles.take_m68k_wait_cpu_cycles()
         } else {
-            self.m68k.execute_instruction(&mut bus)
+            instrument!(Subsystem::Cpu, self.m68k.execute_instruction(&mut bus))
         };
 
         let elapsed_mclk_cycles = self
@@ -395,7 +572,8 @@ impl EmulatorTrait for GenesisEmulator {
             .record_68k_instruction(m68k_cycles, self.m68k.last_instruction_was_mul_or_div());
 
         while self.cycles.should_tick_z80() {
-            self.z80.tick(&mut bus);
+            instrument!(Subsystem::Cpu, self.z80.tick(&mut bus));
+            bus.set_z80_stalled(self.z80.stalled());
             self.cycles.decrement_z80();
         }
 
@@ -405,12 +583,14 @@ impl EmulatorTrait for GenesisEmulator {
 
         self.main_bus_writes = bus.apply_writes();
 
+        self.memory.event_log_mut().advance(elapsed_mclk_cycles);
+
         self.memory.medium_mut().tick(m68k_cycles);
 
         self.input.tick(m68k_cycles);
 
         while self.cycles.should_tick_psg() {
-            if self.psg.tick() == Sn76489TickEffect::Clocked {
+            if instrument!(Subsystem::Apu, self.psg.tick()) == Sn76489TickEffect::Clocked {
                 // PSG only has mono output in the Genesis; stereo output is only for Game Gear
                 let (psg_sample, _) = self.psg.sample();
                 self.audio_resampler.collect_psg_sample(psg_sample);
@@ -420,7 +600,7 @@ impl EmulatorTrait for GenesisEmulator {
         }
 
         while self.cycles.should_tick_ym2612() {
-            if self.ym2612.tick() == YmTickEffect::OutputSample {
+            if instrument!(Subsystem::Apu, self.ym2612.tick()) == YmTickEffect::OutputSample {
                 let (ym_sample_l, ym_sample_r) = self.ym2612.sample();
                 self.audio_resampler.collect_ym2612_sample(ym_sample_l, ym_sample_r);
             }
@@ -431,7 +611,9 @@ impl EmulatorTrait for GenesisEmulator {
         self.audio_resampler.output_samples(audio_output).map_err(GenesisError::Audio)?;
 
         let mut tick_effect = TickEffect::None;
-        if self.vdp.tick(elapsed_mclk_cycles, &mut self.memory) == VdpTickEffect::FrameComplete {
+        if instrument!(Subsystem::Ppu, self.vdp.tick(elapsed_mclk_cycles, &mut self.memory))
+            == VdpTickEffect::FrameComplete
+        {
             self.render_frame(renderer).map_err(GenesisError::Render)?;
 
             self.input.set_inputs(*inputs);
@@ -460,6 +642,11 @@ impl EmulatorTrait for GenesisEmulator {
         self.render_frame(renderer)
     }
 
+    fn clear_audio_mutes(&mut self) {
+        self.ym2612.clear_muted_channels();
+        self.psg.clear_muted_channels();
+    }
+
     fn reload_config(&mut self, config: &Self::Config) {
         self.aspect_ratio = config.aspect_ratio;
         self.adjust_aspect_ratio_in_2x_resolution = config.adjust_aspect_ratio_in_2x_resolution;
@@ -474,6 +661,11 @@ impl EmulatorTrait for GenesisEmulator {
 
     fn take_rom_from(&mut self, other: &mut Self) {
         self.memory.take_rom_from(&mut other.memory);
+        self.input.set_link_cable_connected(other.input.link_cable_connected());
+    }
+
+    fn game_metadata(&mut self) -> GameMetadata {
+        self.memory.game_metadata()
     }
 
     fn soft_reset(&mut self) {
@@ -488,7 +680,15 @@ impl EmulatorTrait for GenesisEmulator {
         log::info!("Hard resetting console");
 
         let rom = self.memory.take_rom();
+        let link_cable_connected = self.input.link_cable_connected();
         *self = GenesisEmulator::create(rom, self.config, save_writer);
+        self.input.set_link_cable_connected(link_cable_connected);
+    }
+
+    fn save_state_safe_point() -> SaveStateSafePoint {
+        // The VDP frame buffer is not saved, so loading a state mid-frame would lose the lines that
+        // were already rendered
+        SaveStateSafePoint::FrameBoundary
     }
 
     fn target_fps(&self) -> f64 {
@@ -541,3 +741,178 @@ pub fn check_for_long_dma_skip<const REFRESH_INTERVAL: u64>(
         vdp.scanline_mclk()
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jgenesis_common::frontend::NullSaveWriter;
+    use m68000_emu::BusInterface;
+
+    fn rom_with_header(domestic_title: &[u8], overseas_title: &[u8], region: &[u8]) -> Vec<u8> {
+        let mut rom = vec![b' '; 0x200];
+        rom[0x120..0x120 + domestic_title.len()].copy_from_slice(domestic_title);
+        rom[0x150..0x150 + overseas_title.len()].copy_from_slice(overseas_title);
+        rom[0x1F0..0x1F0 + region.len()].copy_from_slice(region);
+        rom
+    }
+
+    #[test]
+    fn region_codes() {
+        let detect = |region: &[u8]| GenesisRegion::from_rom(&rom_with_header(b"A", b"A", region));
+
+        assert_eq!(detect(b"JUE"), Some(GenesisRegion::Americas));
+        assert_eq!(detect(b"J"), Some(GenesisRegion::Japan));
+        assert_eq!(detect(b"E"), Some(GenesisRegion::Europe));
+        assert_eq!(detect(b"EUROPE"), Some(GenesisRegion::Europe));
+        assert_eq!(detect(b"4"), Some(GenesisRegion::Americas));
+        assert_eq!(detect(b"1"), Some(GenesisRegion::Japan));
+        assert_eq!(detect(b"8"), Some(GenesisRegion::Europe));
+        assert_eq!(detect(b"  j"), Some(GenesisRegion::Japan));
+        assert_eq!(detect(b"e"), Some(GenesisRegion::Europe));
+        assert_eq!(detect(b"X"), None);
+    }
+
+    #[test]
+    fn region_from_titles() {
+        let rom = rom_with_header(b"DOMESTIC", b"", b"");
+        assert_eq!(GenesisRegion::from_rom(&rom), Some(GenesisRegion::Japan));
+
+        let rom = rom_with_header(b"DOMESTIC", b"OVERSEAS", b"");
+        assert_eq!(GenesisRegion::from_rom(&rom), None);
+    }
+
+    #[test]
+    fn header_metadata() {
+        let mut rom = rom_with_header(b"DOMESTIC", b"OVERSEAS   TITLE", b"JUE");
+        rom[0x180..0x18E].copy_from_slice(b"GM MK-1079 -00");
+
+        let metadata = GenesisEmulator::read_game_metadata(rom.clone());
+        assert_eq!(metadata.title.as_deref(), Some("OVERSEAS TITLE"));
+        assert_eq!(metadata.serial.as_deref(), Some("GM MK-1079 -00"));
+        assert_eq!(metadata.region.as_deref(), Some("Americas"));
+
+        let metadata = memory::parse_header_metadata(&rom, GenesisRegion::Japan);
+        assert_eq!(metadata.title.as_deref(), Some("DOMESTIC"));
+        assert!(metadata.quirks.is_empty());
+
+        // Triple Play Gold
+        rom[0x180..0x18E].copy_from_slice(b"GM T-172116-00");
+        let metadata = GenesisEmulator::read_game_metadata(rom.clone());
+        assert_eq!(metadata.quirks.len(), 1);
+
+        rom[0x100..0x104].copy_from_slice(b"SEGA");
+        let metadata = GenesisEmulator::read_header_metadata(&rom);
+        assert_eq!(metadata.title.as_deref(), Some("OVERSEAS TITLE"));
+        assert_eq!(metadata.serial.as_deref(), Some("GM T-172116-00"));
+        assert!(metadata.quirks.is_empty());
+    }
+
+    #[test]
+    fn header_metadata_from_prefix() {
+        let mut rom = rom_with_header(b"DOMESTIC", b"OVERSEAS", b"JUE");
+        rom[0x100..0x110].copy_from_slice(b"SEGA GENESIS    ");
+        rom.resize(0x8000, 0);
+
+        // Copier header and SMD interleaving
+        let mut smd = vec![0; 0x200];
+        for block in rom.chunks_exact(0x4000) {
+            smd.extend(block.iter().skip(1).step_by(2));
+            smd.extend(block.iter().step_by(2));
+        }
+
+        for image in [&rom, &smd] {
+            let metadata =
+                GenesisEmulator::read_header_metadata(&image[..GenesisEmulator::HEADER_PREFIX_LEN]);
+            assert_eq!(metadata.title.as_deref(), Some("OVERSEAS"));
+        }
+
+        // Not a Genesis header
+        assert_eq!(GenesisEmulator::read_header_metadata(&[0; 0x200]), GameMetadata::default());
+    }
+
+    fn test_config() -> GenesisEmulatorConfig {
+        GenesisEmulatorConfig {
+            p1_controller_type: GenesisControllerType::default(),
+            p2_controller_type: GenesisControllerType::default(),
+            forced_timing_mode: None,
+            forced_region: Some(GenesisRegion::Americas),
+            aspect_ratio: GenesisAspectRatio::default(),
+            adjust_aspect_ratio_in_2x_resolution: true,
+            remove_sprite_limits: false,
+            m68k_clock_divider: timing::NATIVE_M68K_DIVIDER,
+            emulate_non_linear_vdp_dac: false,
+            expanded_vram: false,
+            deinterlace: true,
+            render_vertical_border: false,
+            render_horizontal_border: false,
+            plane_a_enabled: true,
+            plane_b_enabled: true,
+            sprites_enabled: true,
+            window_enabled: true,
+            backdrop_enabled: true,
+            quantize_ym2612_output: true,
+            emulate_ym2612_ladder_effect: true,
+            low_pass: GenesisLowPassFilter::default(),
+            ym2612_enabled: true,
+            psg_enabled: true,
+        }
+    }
+
+    #[test]
+    fn z80_busack_waits_for_instruction_boundary() {
+        let rom = rom_with_header(b"", b"", b"JUE");
+        let mut emulator = GenesisEmulator::create(rom, test_config(), &mut NullSaveWriter);
+
+        let busack = |emulator: &mut GenesisEmulator| {
+            let mut bus = new_main_bus!(emulator, m68k_reset: false);
+            !bus.read_word(0xA11100).bit(8)
+        };
+        let write_word = |emulator: &mut GenesisEmulator, address: u32, value: u16| {
+            let mut bus = new_main_bus!(emulator, m68k_reset: false);
+            bus.write_word(address, value);
+            emulator.main_bus_writes = bus.apply_writes();
+        };
+        let tick_z80 = |emulator: &mut GenesisEmulator| {
+            let mut bus = new_main_bus!(emulator, m68k_reset: false);
+            emulator.z80.tick(&mut bus);
+            bus.set_z80_stalled(emulator.z80.stalled());
+            emulator.main_bus_writes = bus.apply_writes();
+        };
+
+        // Release Z80 RESET and start executing a NOP (4 cycles) from zero-filled audio RAM
+        write_word(&mut emulator, 0xA11200, 0x0100);
+        tick_z80(&mut emulator);
+
+        // BUSACK should not be asserted until the NOP finishes
+        write_word(&mut emulator, 0xA11100, 0x0100);
+        assert!(!busack(&mut emulator));
+        for _ in 0..3 {
+            tick_z80(&mut emulator);
+            assert!(!busack(&mut emulator));
+        }
+        tick_z80(&mut emulator);
+        assert!(busack(&mut emulator));
+
+        // Releasing the bus takes effect immediately
+        write_word(&mut emulator, 0xA11100, 0x0000);
+        assert!(!busack(&mut emulator));
+    }
+
+    #[test]
+    fn short_rom() {
+        assert_eq!(GenesisRegion::from_rom(&[0; 0x100]), None);
+    }
+
+    #[test]
+    fn square_pixels_h320_display_width() {
+        let display_width = |width: u32| {
+            let par = GenesisAspectRatio::SquarePixelsH320
+                .to_pixel_aspect_ratio(FrameSize { width, height: 224 }, false)
+                .unwrap();
+            f64::from(width) * f64::from(par)
+        };
+
+        assert!((display_width(320) - 320.0).abs() < 1e-9);
+        assert!((display_width(256) - 320.0).abs() < 1e-9);
+    }
+}
@@ -44,6 +44,7 @@ pub enum GenesisControllerType {
 const FLIP_COUNTER_CYCLES: u32 = 10000;
 
 const TH_BIT: u8 = 6;
+const TH_INT_BIT: u8 = 7;
 
 #[derive(Debug, Clone, Copy, Encode, Decode)]
 struct PinDirections {
@@ -155,6 +156,44 @@ impl PinDirections {
     }
 }
 
+// Serial control register bits
+const SCTRL_RXD_READY_BIT: u8 = 1;
+const SCTRL_RXD_ERROR_BIT: u8 = 2;
+const SCTRL_RX_INT_BIT: u8 = 3;
+const SCTRL_SERIAL_OUT_BIT: u8 = 4;
+const SCTRL_SERIAL_IN_BIT: u8 = 5;
+
+/// A link cable plugged into controller port 2, connecting it to port 2 of another console.
+///
+/// In parallel mode, every pin that one console sets to output is seen on the other console's
+/// corresponding input pin. In serial mode, bytes written to `TxData` are transmitted to the other
+/// console's `RxData`. Serial transfers complete instantly rather than being timed to the baud rate.
+#[derive(Debug, Clone, Encode, Decode)]
+struct LinkCable {
+    connected: bool,
+    remote_pins: u8,
+    tx_data: u8,
+    rx_data: u8,
+    serial_ctrl: u8,
+    pending_tx: Option<u8>,
+    external_interrupt_pending: bool,
+}
+
+impl Default for LinkCable {
+    fn default() -> Self {
+        Self {
+            connected: false,
+            // Unconnected input pins are pulled high
+            remote_pins: 0x7F,
+            tx_data: 0xFF,
+            rx_data: 0x00,
+            serial_ctrl: 0x00,
+            pending_tx: None,
+            external_interrupt_pending: false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct InputState {
     inputs: GenesisInputs,
@@ -162,6 +201,7 @@ pub struct InputState {
     p2_controller_type: GenesisControllerType,
     p1_pin_directions: PinDirections,
     p2_pin_directions: PinDirections,
+    link_cable: LinkCable,
 }
 
 // All 1s signals to games that nothing is connected to the port
@@ -179,6 +219,7 @@ impl InputState {
             p2_controller_type,
             p1_pin_directions: PinDirections::default(),
             p2_pin_directions: PinDirections::default(),
+            link_cable: LinkCable::default(),
         }
     }
 
@@ -207,6 +248,14 @@ impl InputState {
 
     #[must_use]
     pub fn read_p2_data(&self) -> u8 {
+        if self.link_cable.connected {
+            // Only bits set to input come from the other console; bit 7 always comes from the last
+            // data write
+            let ctrl = self.p2_pin_directions.last_ctrl_write;
+            let data = self.p2_pin_directions.last_data_write;
+            return (self.link_cable.remote_pins & !ctrl & 0x7F) | (data & (ctrl | 0x80));
+        }
+
         if self.p2_controller_type == GenesisControllerType::None {
             return DATA_NO_CONTROLLER;
         }
@@ -240,8 +289,156 @@ impl InputState {
         self.p2_pin_directions.write_ctrl(value, self.p2_controller_type);
     }
 
+    #[must_use]
+    pub fn read_p2_tx_data(&self) -> u8 {
+        self.link_cable.tx_data
+    }
+
+    pub fn write_p2_tx_data(&mut self, value: u8) {
+        self.link_cable.tx_data = value;
+        if self.link_cable.connected && self.link_cable.serial_ctrl.bit(SCTRL_SERIAL_OUT_BIT) {
+            self.link_cable.pending_tx = Some(value);
+        }
+    }
+
+    pub fn read_p2_rx_data(&mut self) -> u8 {
+        self.link_cable.serial_ctrl &= !((1 << SCTRL_RXD_READY_BIT) | (1 << SCTRL_RXD_ERROR_BIT));
+        self.link_cable.rx_data
+    }
+
+    #[must_use]
+    pub fn read_p2_serial_ctrl(&self) -> u8 {
+        self.link_cable.serial_ctrl
+    }
+
+    pub fn write_p2_serial_ctrl(&mut self, value: u8) {
+        // Lowest 3 bits are read-only status bits
+        self.link_cable.serial_ctrl = (value & 0xF8) | (self.link_cable.serial_ctrl & 0x07);
+    }
+
+    /// Whether a link cable is connected to port 2. While connected, port 2 reads come from the
+    /// other console instead of from the P2 controller.
+    #[must_use]
+    pub fn link_cable_connected(&self) -> bool {
+        self.link_cable.connected
+    }
+
+    pub fn set_link_cable_connected(&mut self, connected: bool) {
+        self.link_cable.connected = connected;
+    }
+
+    /// Pin levels that this console is driving onto the link cable. Pins set to input are pulled
+    /// high.
+    #[must_use]
+    pub fn link_cable_output_pins(&self) -> u8 {
+        let ctrl = self.p2_pin_directions.last_ctrl_write;
+        let data = self.p2_pin_directions.last_data_write;
+        ((data & ctrl) | !ctrl) & 0x7F
+    }
+
+    /// Update the pin levels driven by the other console.
+    pub fn set_link_cable_input_pins(&mut self, pins: u8) {
+        let prev_th = self.link_cable.remote_pins.bit(TH_BIT);
+        self.link_cable.remote_pins = pins & 0x7F;
+
+        // TH set to input can raise an external interrupt on a high-to-low transition
+        let ctrl = self.p2_pin_directions.last_ctrl_write;
+        if ctrl.bit(TH_INT_BIT) && !ctrl.bit(TH_BIT) && prev_th && !pins.bit(TH_BIT) {
+            self.link_cable.external_interrupt_pending = true;
+        }
+    }
+
+    /// Take the last byte written to `TxData` in serial output mode, if it has not been sent yet.
+    pub fn take_link_cable_tx_byte(&mut self) -> Option<u8> {
+        self.link_cable.pending_tx.take()
+    }
+
+    /// Receive a byte sent by the other console in serial mode.
+    pub fn receive_link_cable_byte(&mut self, byte: u8) {
+        let link_cable = &mut self.link_cable;
+        if !link_cable.serial_ctrl.bit(SCTRL_SERIAL_IN_BIT) {
+            return;
+        }
+
+        if link_cable.serial_ctrl.bit(SCTRL_RXD_READY_BIT) {
+            // Previous byte was never read
+            link_cable.serial_ctrl |= 1 << SCTRL_RXD_ERROR_BIT;
+        }
+
+        link_cable.rx_data = byte;
+        link_cable.serial_ctrl |= 1 << SCTRL_RXD_READY_BIT;
+
+        if link_cable.serial_ctrl.bit(SCTRL_RX_INT_BIT) {
+            link_cable.external_interrupt_pending = true;
+        }
+    }
+
+    #[must_use]
+    pub fn external_interrupt_pending(&self) -> bool {
+        self.link_cable.external_interrupt_pending
+    }
+
+    pub fn acknowledge_external_interrupt(&mut self) {
+        self.link_cable.external_interrupt_pending = false;
+    }
+
     pub fn tick(&mut self, m68k_cycles: u32) {
         self.p1_pin_directions.tick(m68k_cycles);
         self.p2_pin_directions.tick(m68k_cycles);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_cable_parallel() {
+        let mut a = InputState::new(GenesisControllerType::SixButton, GenesisControllerType::None);
+        let mut b = InputState::new(GenesisControllerType::SixButton, GenesisControllerType::None);
+        a.set_link_cable_connected(true);
+        b.set_link_cable_connected(true);
+
+        // A drives the low nibble, B drives TH and the rest as input
+        a.write_p2_ctrl(0x0F);
+        a.write_p2_data(0x05);
+        b.write_p2_ctrl(0xC0);
+        b.write_p2_data(0x00);
+
+        b.set_link_cable_input_pins(a.link_cable_output_pins());
+        a.set_link_cable_input_pins(b.link_cable_output_pins());
+
+        assert_eq!(b.read_p2_data() & 0x3F, 0x35);
+        assert_eq!(a.read_p2_data() & 0x70, 0x30);
+
+        // TH falling edge with TH interrupts enabled
+        a.write_p2_ctrl(0x80);
+        b.write_p2_data(0x40);
+        a.set_link_cable_input_pins(b.link_cable_output_pins());
+        assert!(!a.external_interrupt_pending());
+        b.write_p2_data(0x00);
+        a.set_link_cable_input_pins(b.link_cable_output_pins());
+        assert!(a.external_interrupt_pending());
+    }
+
+    #[test]
+    fn link_cable_serial() {
+        let mut a = InputState::new(GenesisControllerType::SixButton, GenesisControllerType::None);
+        let mut b = InputState::new(GenesisControllerType::SixButton, GenesisControllerType::None);
+        a.set_link_cable_connected(true);
+        b.set_link_cable_connected(true);
+
+        a.write_p2_serial_ctrl(1 << SCTRL_SERIAL_OUT_BIT);
+        b.write_p2_serial_ctrl((1 << SCTRL_SERIAL_IN_BIT) | (1 << SCTRL_RX_INT_BIT));
+
+        a.write_p2_tx_data(0xA5);
+        let byte = a.take_link_cable_tx_byte().unwrap();
+        assert_eq!(a.take_link_cable_tx_byte(), None);
+
+        b.receive_link_cable_byte(byte);
+        assert!(b.read_p2_serial_ctrl().bit(SCTRL_RXD_READY_BIT));
+        assert!(b.external_interrupt_pending());
+        assert_eq!(b.read_p2_rx_data(), 0xA5);
+        assert!(!b.read_p2_serial_ctrl().bit(SCTRL_RXD_READY_BIT));
+    }
+}
@@ -9,6 +9,7 @@ pub mod ym2612;
 
 pub use api::{
     GenesisAspectRatio, GenesisEmulator, GenesisEmulatorConfig, GenesisError, GenesisLowPassFilter,
-    GenesisRegion, GenesisResult, check_for_long_dma_skip, render_frame, target_framerate,
+    GenesisRegion, GenesisResult, MemoryRegion, check_for_long_dma_skip, render_frame,
+    target_framerate,
 };
 pub use input::{GenesisControllerType, GenesisInputs, GenesisJoypadState};
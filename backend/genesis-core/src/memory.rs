@@ -2,22 +2,25 @@
 
 pub mod eeprom;
 mod external;
+mod quirks;
 
 use crate::api::GenesisRegion;
 use crate::input::InputState;
 use crate::memory::external::ExternalMemory;
+use crate::memory::quirks::GenesisQuirk;
 use crate::svp::Svp;
 use crate::vdp::Vdp;
 use crate::ym2612::Ym2612;
 use bincode::{Decode, Encode};
 use crc::Crc;
-use jgenesis_common::frontend::TimingMode;
+use jgenesis_common::eventlog::EventLog;
+use jgenesis_common::frontend::{GameMetadata, TimingMode};
+use jgenesis_common::memmap;
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
 use jgenesis_common::num::{GetBit, U16Ext};
 use jgenesis_proc_macros::{FakeDecode, FakeEncode, PartialClone};
-use regex::Regex;
 use smsgg_core::psg::Sn76489;
 use std::ops::Index;
-use std::sync::LazyLock;
 use std::{array, iter, mem};
 use z80_emu::traits::InterruptLine;
 
@@ -76,6 +79,20 @@ impl SegaMapper {
         (bank_number << 19) | (address & 0x07FFFF)
     }
 
+    /// Describe the eight 512KB ROM bank windows at $000000-$3FFFFF.
+    pub fn describe_memory_map(self, map: &mut MemoryMap) {
+        for (i, &bank_number) in self.bank_numbers.iter().enumerate() {
+            let start = (i as u32) << 19;
+            map.add_mapped(
+                start,
+                start | 0x07FFFF,
+                RegionKind::Rom,
+                "ROM bank window",
+                memmap::describe_bank(bank_number.into(), 0x080000),
+            );
+        }
+    }
+
     #[must_use]
     pub fn should_use(rom: &[u8]) -> bool {
         // Only one game uses the bank switching Sega mapper, Super Street Fighter 2
@@ -116,14 +133,12 @@ pub struct Cartridge {
     mapper: Option<SegaMapper>,
     svp: Option<Svp>,
     region: GenesisRegion,
+    quirks: Vec<GenesisQuirk>,
     is_unlicensed_rockman_x3: bool,
+    #[partial_clone(default)]
+    event_log: EventLog,
 }
 
-const TRIPLE_PLAY_GOLD_SERIAL: &[u8] = b"T-172116";
-const TRIPLE_PLAY_96_SERIAL: &[u8] = b"T-172026";
-
-const ROCKMAN_X3_CHECKSUM: u32 = 0x3EE639F0;
-
 impl Cartridge {
     pub fn from_rom(
         rom_bytes: Vec<u8>,
@@ -144,7 +159,10 @@ impl Cartridge {
         });
         log::info!("Genesis hardware region: {region:?}");
 
-        let external_memory = ExternalMemory::from_rom(&rom_bytes, checksum, initial_ram_bytes);
+        let quirks = quirks::find(checksum, &rom_bytes);
+
+        let external_memory =
+            ExternalMemory::from_rom(&rom_bytes, checksum, &quirks, initial_ram_bytes);
 
         // Initialize ram_mapped to true if external memory is present
         // Only one game ever unmaps RAM (Phantasy Star 4)
@@ -158,11 +176,8 @@ impl Cartridge {
         // Only one game uses the SVP, Virtua Racing
         let svp = is_virtua_racing(serial_number).then(Svp::new);
 
-        if rom_bytes.len() >= 0x300000
-            && (serial_number == TRIPLE_PLAY_GOLD_SERIAL || serial_number == TRIPLE_PLAY_96_SERIAL)
-        {
-            // Triple Play expects the third MB of the ROM to be mapped to $300000-$3FFFFF instead
-            // of $200000-$2FFFFF; accomplish this by duplicating the data
+        if rom_bytes.len() >= 0x300000 && quirks.contains(&GenesisQuirk::MirrorThirdRomMegabyte) {
+            // Map the third MB of the ROM to $300000-$3FFFFF by duplicating the data
             if rom_bytes.len() < 0x400000 {
                 rom_bytes.extend(iter::repeat(0xFF).take(0x400000 - rom_bytes.len()));
             }
@@ -171,7 +186,7 @@ impl Cartridge {
             second[..0x100000].copy_from_slice(&first[0x200000..0x300000]);
         }
 
-        let is_unlicensed_rockman_x3 = checksum == ROCKMAN_X3_CHECKSUM;
+        let is_unlicensed_rockman_x3 = quirks.contains(&GenesisQuirk::RockmanX3Protection);
 
         Self {
             rom: Rom(rom_bytes),
@@ -180,7 +195,9 @@ impl Cartridge {
             mapper,
             svp,
             region,
+            quirks,
             is_unlicensed_rockman_x3,
+            event_log: EventLog::default(),
         }
     }
 
@@ -219,6 +236,10 @@ impl Cartridge {
         self.external_memory.get_memory()
     }
 
+    fn restore_external_ram(&mut self, ram: &[u8]) {
+        self.external_memory.restore_memory(ram);
+    }
+
     fn is_ram_persistent(&self) -> bool {
         self.external_memory.is_persistent()
     }
@@ -227,14 +248,33 @@ impl Cartridge {
         self.external_memory.get_and_clear_dirty_bit()
     }
 
-    fn program_title(&self) -> String {
-        parse_title_from_header(&self.rom.0, self.region)
+    fn game_metadata(&self) -> GameMetadata {
+        GameMetadata {
+            quirks: jgenesis_common::quirks::describe_quirks(&self.quirks),
+            ..parse_header_metadata(&self.rom.0, self.region)
+        }
+    }
+
+    fn describe_rom(&self, map: &mut MemoryMap) {
+        match self.mapper {
+            Some(mapper) => mapper.describe_memory_map(map),
+            None => {
+                map.add_mapped(
+                    0x000000,
+                    0x3FFFFF,
+                    RegionKind::Rom,
+                    "Cartridge ROM",
+                    format!("{}KB", self.rom.0.len() / 1024),
+                );
+            }
+        }
     }
 }
 
-fn ensure_rom_in_expected_format(rom: Vec<u8>) -> Vec<u8> {
+pub(crate) fn ensure_rom_in_expected_format(rom: Vec<u8>) -> Vec<u8> {
     let rom = remove_copier_header(rom);
     let rom = deinterleave_rom(rom);
+    let rom = deinterleave_mgd_rom(rom);
     ensure_big_endian(rom)
 }
 
@@ -245,12 +285,15 @@ fn remove_copier_header(rom: Vec<u8>) -> Vec<u8> {
         return rom;
     }
 
+    // Slicing with get() because this also runs on arbitrary files when building the ROM list
+    let bytes = |start: usize, end: usize| rom.get(start..end).unwrap_or(&[]);
+
     // TMSS header is normally at $100-$103, would be at $303-$304 with the 512-byte header
-    let tmss_header = &rom[0x300..0x304];
+    let tmss_header = bytes(0x300, 0x304);
 
     // Interleaved header bytes are normally at $80-$81 for even and $2080-$2081 for odd
-    let interleaved_tmss_even = &rom[0x0280..0x0282];
-    let interleaved_tmss_odd = &rom[0x2280..0x2282];
+    let interleaved_tmss_even = bytes(0x0280, 0x0282);
+    let interleaved_tmss_odd = bytes(0x2280, 0x2282);
 
     if tmss_header != b"SEGA"
         && tmss_header != b"ESAG"
@@ -268,7 +311,7 @@ fn remove_copier_header(rom: Vec<u8>) -> Vec<u8> {
 fn ensure_big_endian(mut rom: Vec<u8>) -> Vec<u8> {
     // Every licensed game contains the ASCII string "SEGA" at $100-$104 in ROM
     // If the string "ESAG" is detected there, byteswap the ROM
-    if &rom[0x100..0x104] == "ESAG".as_bytes() {
+    if rom.get(0x100..0x104) == Some(b"ESAG") {
         log::info!("Byteswapping ROM because it appears to be little-endian");
 
         for chunk in rom.chunks_exact_mut(2) {
@@ -283,7 +326,7 @@ fn deinterleave_rom(rom: Vec<u8>) -> Vec<u8> {
     // Some older ROM images, usually with the .smd file extension, are interleaved.
     // This format consists of 16KB blocks where each block contains 8KB of even bytes followed by
     // 8KB of odd bytes.
-    if rom.len() % (16 * 1024) != 0 {
+    if rom.is_empty() || rom.len() % (16 * 1024) != 0 {
         // Interleaved ROM sizes should always be a multiple of 16KB
         return rom;
     }
@@ -311,18 +354,82 @@ fn deinterleave_rom(rom: Vec<u8>) -> Vec<u8> {
     deinterleaved
 }
 
+fn deinterleave_mgd_rom(rom: Vec<u8>) -> Vec<u8> {
+    // ROM images in Multi Game Doctor format, usually with the .mgd file extension, store all of
+    // the odd bytes in the first half of the file followed by all of the even bytes
+    if rom.len() < 0x4000 || rom.len() & 1 != 0 {
+        return rom;
+    }
+
+    if &rom[0x100..0x104] == b"SEGA" || &rom[0x100..0x104] == b"ESAG" {
+        // ROM image already contains valid TMSS text; don't try to deinterleave
+        return rom;
+    }
+
+    let (odd_bytes, even_bytes) = rom.split_at(rom.len() / 2);
+    if &odd_bytes[0x80..0x82] != b"EA" || &even_bytes[0x80..0x82] != b"SG" {
+        // Deinterleaving would not produce valid TMSS text; don't try to deinterleave
+        return rom;
+    }
+
+    log::info!("ROM image appears to be in MGD format; deinterleaving it");
+
+    even_bytes.iter().zip(odd_bytes).flat_map(|(&even, &odd)| [even, odd]).collect()
+}
+
+/// Parse the game title, serial number, and region from a ROM header. The title is taken from the
+/// domestic or overseas title field depending on `region`.
+///
+/// `rom` should start at the beginning of the ROM, or at the start of the first sector's data for
+/// Sega CD discs.
 #[must_use]
-#[allow(clippy::missing_panics_doc, clippy::items_after_statements)]
-pub fn parse_title_from_header(rom: &[u8], region: GenesisRegion) -> String {
-    let addr = match region {
+pub fn parse_header_metadata(rom: &[u8], region: GenesisRegion) -> GameMetadata {
+    let title_addr = match region {
         GenesisRegion::Americas | GenesisRegion::Europe => 0x0150,
         GenesisRegion::Japan => 0x0120,
     };
-    let bytes = &rom[addr..addr + 48];
-    let title = bytes.iter().copied().map(|b| b as char).collect::<String>();
+    let title = rom.get(title_addr..title_addr + 48).and_then(GameMetadata::parse_header_string);
+    let serial = rom.get(0x0180..0x018E).and_then(GameMetadata::parse_header_string);
 
-    static RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r" +").unwrap());
-    RE.replace_all(title.trim(), " ").into()
+    GameMetadata { title, serial, region: Some(region.to_string()), quirks: vec![] }
+}
+
+/// Parse metadata from a cartridge ROM image, including any game-specific workarounds that would
+/// apply to it. Accepts the same ROM formats as [`Cartridge::from_rom`].
+#[must_use]
+pub(crate) fn read_cartridge_metadata(rom: Vec<u8>) -> GameMetadata {
+    let checksum = CRC.checksum(&rom);
+    let rom = ensure_rom_in_expected_format(rom);
+    let region = GenesisRegion::from_rom(&rom).unwrap_or(GenesisRegion::Americas);
+
+    GameMetadata {
+        quirks: jgenesis_common::quirks::describe_quirks(&quirks::find(checksum, &rom)),
+        ..parse_header_metadata(&rom, region)
+    }
+}
+
+/// Number of bytes from the start of a ROM image that [`read_header_metadata`] looks at: a 512-byte
+/// copier header followed by one 16KB interleaved block
+pub(crate) const HEADER_PREFIX_LEN: usize = 0x4200;
+
+/// Parse metadata from the start of a ROM image without reading the rest of it. Game-specific
+/// workarounds are not included because some are keyed by the CRC32 of the full ROM.
+///
+/// MGD-format images store the header bytes in both halves of the file and are not recognized.
+#[must_use]
+pub(crate) fn read_header_metadata(rom_prefix: &[u8]) -> GameMetadata {
+    // Always use the same length so that the copier header and interleaving checks look at the
+    // same bytes regardless of the ROM's actual size
+    let mut rom = rom_prefix[..rom_prefix.len().min(HEADER_PREFIX_LEN)].to_vec();
+    rom.resize(HEADER_PREFIX_LEN, 0);
+    let rom = ensure_rom_in_expected_format(rom);
+
+    if !rom[0x100..0x110].windows(4).any(|bytes| bytes == b"SEGA") {
+        return GameMetadata::default();
+    }
+
+    let region = GenesisRegion::from_rom(&rom).unwrap_or(GenesisRegion::Americas);
+    parse_header_metadata(&rom, region)
 }
 
 fn is_virtua_racing(serial_number: &[u8]) -> bool {
@@ -343,6 +450,16 @@ pub trait PhysicalMedium {
     fn write_word(&mut self, address: u32, value: u16);
 
     fn region(&self) -> GenesisRegion;
+
+    /// Describe the parts of the 68000 address space that are mapped to this medium, for the
+    /// debugger's memory map view.
+    fn describe_memory_map(&self, map: &mut MemoryMap);
+
+    /// The log of cross-CPU events for the whole system. This lives in the physical medium because
+    /// that is where the Sega CD and 32X handle most of their inter-CPU communication registers.
+    fn event_log(&self) -> &EventLog;
+
+    fn event_log_mut(&mut self) -> &mut EventLog;
 }
 
 impl PhysicalMedium for Cartridge {
@@ -456,6 +573,43 @@ impl PhysicalMedium for Cartridge {
     fn region(&self) -> GenesisRegion {
         self.region
     }
+
+    fn describe_memory_map(&self, map: &mut MemoryMap) {
+        if self.svp.is_some() {
+            map.add(0x000000, 0x1FFFFF, RegionKind::Rom, "Cartridge ROM")
+                .add(0x300000, 0x31FFFF, RegionKind::Ram, "SVP DRAM")
+                .add_mirror(0x320000, 0x37FFFF, RegionKind::Ram, "SVP DRAM", 0x300000, 0x31FFFF)
+                .add(0xA15000, 0xA15007, RegionKind::Registers, "SVP registers");
+            return;
+        }
+
+        self.describe_rom(map);
+
+        let sram_status = match (&self.external_memory, self.ram_mapped) {
+            (ExternalMemory::None, _) => "No SRAM",
+            (_, true) => "SRAM mapped",
+            (_, false) => "SRAM unmapped",
+        };
+        map.add_mapped(
+            0xA13000,
+            0xA130FF,
+            RegionKind::Registers,
+            "Cartridge registers",
+            sram_status,
+        );
+
+        if self.ram_mapped {
+            self.external_memory.describe_memory_map(map);
+        }
+    }
+
+    fn event_log(&self) -> &EventLog {
+        &self.event_log
+    }
+
+    fn event_log_mut(&mut self) -> &mut EventLog {
+        &mut self.event_log
+    }
 }
 
 const MAIN_RAM_LEN: usize = 64 * 1024;
@@ -483,17 +637,21 @@ impl Z80BankRegister {
 struct Signals {
     z80_busreq: bool,
     z80_reset: bool,
+    // Whether the Z80 has actually stopped in response to BUSREQ. The Z80 only samples BUSREQ at
+    // the end of its current instruction, so BUSACK is not asserted until then
+    z80_stalled: bool,
 }
 
 impl Default for Signals {
     fn default() -> Self {
-        Self { z80_busreq: false, z80_reset: true }
+        Self { z80_busreq: false, z80_reset: true, z80_stalled: false }
     }
 }
 
 impl Signals {
     fn z80_busack(self) -> bool {
-        self.z80_busreq && !self.z80_reset
+        // Releasing BUSREQ or asserting RESET takes effect immediately
+        self.z80_busreq && self.z80_stalled && !self.z80_reset
     }
 }
 
@@ -535,12 +693,70 @@ impl<Medium: PhysicalMedium> Memory<Medium> {
         }
     }
 
+    /// Describe the 68000 address space.
+    #[must_use]
+    pub fn memory_map(&self) -> MemoryMap {
+        let mut map = MemoryMap::new("68000", 24);
+        self.physical_medium.describe_memory_map(&mut map);
+
+        let z80_bus_status =
+            if self.signals.z80_busack() { "Bus granted to 68000" } else { "Bus held by Z80" };
+        map.add_mapped(0xA00000, 0xA0FFFF, RegionKind::Ram, "Z80 address space", z80_bus_status)
+            .add(0xA10000, 0xA1001F, RegionKind::Registers, "I/O registers")
+            .add(0xA11100, 0xA11101, RegionKind::Registers, "Z80 BUSREQ")
+            .add(0xA11200, 0xA11201, RegionKind::Registers, "Z80 RESET")
+            .add(0xC00000, 0xC0001F, RegionKind::Registers, "VDP / PSG ports")
+            .add_mirror(0xE00000, 0xFEFFFF, RegionKind::Ram, "Work RAM", 0xFF0000, 0xFFFFFF)
+            .add(0xFF0000, 0xFFFFFF, RegionKind::Ram, "Work RAM");
+        map.fill_unmapped();
+
+        map
+    }
+
+    /// Describe the Z80 address space, including where the 68000 bank window currently points.
+    #[must_use]
+    pub fn z80_memory_map(&self) -> MemoryMap {
+        let m68k_map = self.memory_map();
+
+        let bank_start = self.z80_bank_register.map_to_68k_address(0x8000);
+        let bank_end = bank_start | 0x7FFF;
+        let bank_detail = match m68k_map.entry_at(bank_start) {
+            Some(entry) => {
+                format!("{} ({})", m68k_map.format_range(bank_start, bank_end), entry.name)
+            }
+            None => m68k_map.format_range(bank_start, bank_end),
+        };
+
+        let mut map = MemoryMap::new("Z80", 16);
+        map.add(0x0000, 0x1FFF, RegionKind::Ram, "Audio RAM")
+            .add_mirror(0x2000, 0x3FFF, RegionKind::Ram, "Audio RAM", 0x0000, 0x1FFF)
+            .add(0x4000, 0x5FFF, RegionKind::Registers, "YM2612")
+            .add(0x6000, 0x60FF, RegionKind::Registers, "Bank register")
+            .add(0x7F00, 0x7F1F, RegionKind::Registers, "VDP / PSG ports")
+            .add_mapped(0x8000, 0xFFFF, RegionKind::Rom, "68000 bank window", bank_detail);
+        map.fill_unmapped();
+
+        map
+    }
+
     #[inline]
     #[must_use]
     pub fn hardware_region(&self) -> GenesisRegion {
         self.physical_medium.region()
     }
 
+    #[inline]
+    #[must_use]
+    pub fn main_ram(&self) -> &[u8] {
+        self.main_ram.as_slice()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn main_ram_mut(&mut self) -> &mut [u8] {
+        self.main_ram.as_mut_slice()
+    }
+
     #[inline]
     #[must_use]
     pub fn medium(&self) -> &Medium {
@@ -553,6 +769,18 @@ impl<Medium: PhysicalMedium> Memory<Medium> {
         &mut self.physical_medium
     }
 
+    #[inline]
+    #[must_use]
+    pub fn event_log(&self) -> &EventLog {
+        self.physical_medium.event_log()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn event_log_mut(&mut self) -> &mut EventLog {
+        self.physical_medium.event_log_mut()
+    }
+
     #[inline]
     pub fn reset_z80_signals(&mut self) {
         self.signals = Signals::default();
@@ -570,8 +798,8 @@ impl Memory<Cartridge> {
     }
 
     #[must_use]
-    pub fn game_title(&self) -> String {
-        self.physical_medium.program_title()
+    pub fn game_metadata(&self) -> GameMetadata {
+        self.physical_medium.game_metadata()
     }
 
     #[inline]
@@ -580,6 +808,10 @@ impl Memory<Cartridge> {
         self.physical_medium.external_ram()
     }
 
+    pub fn restore_external_ram(&mut self, ram: &[u8]) {
+        self.physical_medium.restore_external_ram(ram);
+    }
+
     #[inline]
     #[must_use]
     pub fn is_external_ram_persistent(&self) -> bool {
@@ -658,7 +890,7 @@ impl<'a, Medium: PhysicalMedium> MainBus<'a, Medium> {
         }
     }
 
-    fn read_io_register(&self, address: u32) -> u8 {
+    fn read_io_register(&mut self, address: u32) -> u8 {
         match address {
             // Version register
             0xA10000 | 0xA10001 => {
@@ -669,8 +901,11 @@ impl<'a, Medium: PhysicalMedium> MainBus<'a, Medium> {
             0xA10004 | 0xA10005 => self.input.read_p2_data(),
             0xA10008 | 0xA10009 => self.input.read_p1_ctrl(),
             0xA1000A | 0xA1000B => self.input.read_p2_ctrl(),
+            0xA10014 | 0xA10015 => self.input.read_p2_tx_data(),
+            0xA10016 | 0xA10017 => self.input.read_p2_rx_data(),
+            0xA10018 | 0xA10019 => self.input.read_p2_serial_ctrl(),
             // TxData registers return 0xFF by default
-            0xA1000E | 0xA1000F | 0xA10014 | 0xA10015 | 0xA1001A | 0xA1001B => 0xFF,
+            0xA1000E | 0xA1000F | 0xA1001A | 0xA1001B => 0xFF,
             // Other I/O registers return 0x00 by default
             _ => 0x00,
         }
@@ -690,6 +925,12 @@ impl<'a, Medium: PhysicalMedium> MainBus<'a, Medium> {
             0xA1000A | 0xA1000B => {
                 self.input.write_p2_ctrl(value);
             }
+            0xA10014 | 0xA10015 => {
+                self.input.write_p2_tx_data(value);
+            }
+            0xA10018 | 0xA10019 => {
+                self.input.write_p2_serial_ctrl(value);
+            }
             _ => {}
         }
     }
@@ -776,12 +1017,10 @@ impl<'a, Medium: PhysicalMedium> MainBus<'a, Medium> {
                 self.write_io_register(address, value);
             }
             0xA11100..=0xA11101 => {
-                self.memory.signals.z80_busreq = value.bit(0);
-                log::trace!("Set Z80 BUSREQ to {}", self.memory.signals.z80_busreq);
+                self.write_z80_busreq(value.bit(0));
             }
             0xA11200..=0xA11201 => {
-                self.memory.signals.z80_reset = !value.bit(0);
-                log::trace!("Set Z80 RESET to {}", self.memory.signals.z80_reset);
+                self.write_z80_reset(!value.bit(0));
             }
             0xC00000..=0xC0001F => {
                 self.write_vdp_byte(address, value);
@@ -793,6 +1032,34 @@ impl<'a, Medium: PhysicalMedium> MainBus<'a, Medium> {
         }
     }
 
+    fn write_z80_busreq(&mut self, busreq: bool) {
+        if busreq != self.memory.signals.z80_busreq {
+            self.memory.event_log_mut().log("68000", || {
+                if busreq { "Requested Z80 bus".into() } else { "Released Z80 bus".into() }
+            });
+        }
+
+        self.memory.signals.z80_busreq = busreq;
+        log::trace!("Set Z80 BUSREQ to {busreq}");
+    }
+
+    fn write_z80_reset(&mut self, reset: bool) {
+        if reset != self.memory.signals.z80_reset {
+            self.memory.event_log_mut().log("68000", || {
+                if reset { "Asserted Z80 RESET".into() } else { "Released Z80 RESET".into() }
+            });
+
+            // The YM2612's reset pin is tied to the Z80 RESET line. Some sound drivers depend on
+            // this to silence the FM channels when the 68000 reloads the Z80 program
+            if reset {
+                self.ym2612.reset_chip();
+            }
+        }
+
+        self.memory.signals.z80_reset = reset;
+        log::trace!("Set Z80 RESET to {reset}");
+    }
+
     fn apply_word_write(&mut self, address: u32, value: u16) {
         let address = address & ADDRESS_MASK;
         log::trace!("Main bus word write: address={address:06X}, value={value:02X}");
@@ -808,12 +1075,10 @@ impl<'a, Medium: PhysicalMedium> MainBus<'a, Medium> {
                 self.write_io_register(address, value.lsb());
             }
             0xA11100..=0xA11101 => {
-                self.memory.signals.z80_busreq = value.bit(8);
-                log::trace!("Set Z80 BUSREQ to {}", self.memory.signals.z80_busreq);
+                self.write_z80_busreq(value.bit(8));
             }
             0xA11200..=0xA11201 => {
-                self.memory.signals.z80_reset = !value.bit(8);
-                log::trace!("Set Z80 RESET to {}", self.memory.signals.z80_reset);
+                self.write_z80_reset(!value.bit(8));
             }
             0xC00000..=0xC00003 => {
                 self.vdp.write_data(value);
@@ -833,6 +1098,13 @@ impl<'a, Medium: PhysicalMedium> MainBus<'a, Medium> {
         }
     }
 
+    /// Update whether the Z80 is stopped in response to BUSREQ. Should be called after every Z80
+    /// tick, with the value of [`Z80::stalled`](z80_emu::Z80::stalled).
+    #[inline]
+    pub fn set_z80_stalled(&mut self, stalled: bool) {
+        self.memory.signals.z80_stalled = stalled;
+    }
+
     #[inline]
     #[must_use]
     pub fn z80_accessed_68k_bus(&self) -> bool {
@@ -931,11 +1203,23 @@ impl<Medium: PhysicalMedium> m68000_emu::BusInterface for MainBus<'_, Medium> {
 
     #[inline]
     fn interrupt_level(&self) -> u8 {
-        self.vdp.m68k_interrupt_level()
+        match self.vdp.m68k_interrupt_level() {
+            0 if self.vdp.external_interrupts_enabled()
+                && self.input.external_interrupt_pending() =>
+            {
+                2
+            }
+            level => level,
+        }
     }
 
     #[inline]
     fn acknowledge_interrupt(&mut self, _interrupt_level: u8) {
+        if self.interrupt_level() == 2 {
+            self.input.acknowledge_external_interrupt();
+            return;
+        }
+
         // When the 68000 acknowledges a VDP interrupt, the VDP acknowledges whatever level it is
         // currently raising rather than paying attention to the 68000's IACK lines. This is noted
         // in official documentation which describes this hardware bug: If both HINT are VINT are
@@ -1089,3 +1373,76 @@ impl<Medium: PhysicalMedium> z80_emu::BusInterface for MainBus<'_, Medium> {
         self.memory.signals.z80_reset
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_rom(system: &[u8]) -> Vec<u8> {
+        let mut rom = vec![0; 0x100000];
+        rom[0x100..0x100 + system.len()].copy_from_slice(system);
+        rom
+    }
+
+    #[test]
+    fn deinterleave_formats() {
+        let rom = test_rom(b"SEGA MEGA DRIVE");
+
+        let smd: Vec<_> = rom
+            .chunks_exact(0x4000)
+            .flat_map(|block| {
+                let odd = block.iter().skip(1).step_by(2);
+                let even = block.iter().step_by(2);
+                odd.chain(even).copied().collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(ensure_rom_in_expected_format(smd), rom);
+
+        let mgd: Vec<_> =
+            rom.iter().skip(1).step_by(2).chain(rom.iter().step_by(2)).copied().collect();
+        assert_eq!(ensure_rom_in_expected_format(mgd), rom);
+
+        let mut copier_header = vec![0; 512];
+        copier_header.extend_from_slice(&rom);
+        assert_eq!(ensure_rom_in_expected_format(copier_header), rom);
+    }
+
+    #[test]
+    fn memory_map_sram() {
+        let mut rom = test_rom(b"SEGA MEGA DRIVE");
+        rom[0x1B0..0x1BC].copy_from_slice(&[
+            b'R', b'A', 0xF8, 0x20, 0x00, 0x20, 0x00, 0x01, 0x00, 0x20, 0x3F, 0xFF,
+        ]);
+
+        let memory = Memory::new(Cartridge::from_rom(rom, None, Some(GenesisRegion::Americas)));
+
+        let map = memory.memory_map();
+        assert!(map.is_complete());
+        assert_eq!(map.entry_at(0x200001).map(|entry| entry.name), Some("Cartridge SRAM"));
+        assert_eq!(map.entry_at(0x1FFFFF).map(|entry| entry.name), Some("Cartridge ROM"));
+        assert_eq!(map.entry_at(0x204000).map(|entry| entry.name), Some("Cartridge ROM"));
+    }
+
+    #[test]
+    fn memory_map_sega_mapper() {
+        let mut cartridge =
+            Cartridge::from_rom(test_rom(b"SEGA SSF        "), None, Some(GenesisRegion::Americas));
+        cartridge.write_byte(0xA130F5, 0x03);
+
+        let memory = Memory::new(cartridge);
+
+        let map = memory.memory_map();
+        assert!(map.is_complete());
+        assert_eq!(
+            map.entry_at(0x100000).and_then(|entry| entry.detail.as_deref()),
+            Some("Bank $03 ($180000-$1FFFFF)")
+        );
+
+        let z80_map = memory.z80_memory_map();
+        assert!(z80_map.is_complete());
+        assert_eq!(
+            z80_map.entry_at(0x8000).and_then(|entry| entry.detail.as_deref()),
+            Some("$000000-$007FFF (ROM bank window)")
+        );
+    }
+}
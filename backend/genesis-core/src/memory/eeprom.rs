@@ -347,6 +347,11 @@ impl<State: EepromState + Debug, const N: usize> EepromChip<State, N> {
     pub fn get_memory(&self) -> &[u8] {
         &self.memory
     }
+
+    pub fn restore_memory(&mut self, memory: &[u8]) {
+        self.memory.copy_from_slice(memory);
+        self.dirty = true;
+    }
 }
 
 pub type X24C01Chip = EepromChip<X24C01State, 128>;
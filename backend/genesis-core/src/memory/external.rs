@@ -4,15 +4,11 @@ mod metadata;
 
 use crate::memory::eeprom::{X24C01Chip, X24C02Chip, X24C08Chip, X24C16Chip};
 use crate::memory::external::metadata::{EepromMetadata, EepromType};
+use crate::memory::quirks::GenesisQuirk;
 use bincode::{Decode, Encode};
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
 use jgenesis_common::num::GetBit;
-
-// Games that expect to have 8KB of SRAM mapped to $200001-$203FFF but don't specify that in the header
-const FORCE_SRAM_CHECKSUMS: &[u32] = &[
-    0x8135702C, // NHL 96 (UE)
-    0xF509145F, // Might and Magic: Gates to Another World (UE)
-    0x6EF7104A, // Might and Magic III: Isles of Terra (U) (Proto)
-];
+use jgenesis_common::sram;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
 pub(crate) enum RamType {
@@ -21,6 +17,26 @@ pub(crate) enum RamType {
     EightBitEvenAddress,
 }
 
+// Save files are stored with one byte per SRAM byte, but other emulators and flash carts commonly
+// store 8-bit SRAM as it appears on the 68000 bus (one SRAM byte per word), possibly padded
+fn convert_initial_ram(ram: Vec<u8>, ram_type: RamType, ram_len: usize) -> Option<Vec<u8>> {
+    if ram.len() == ram_len {
+        return Some(ram);
+    }
+
+    if ram_type != RamType::SixteenBit && ram.len() >= 2 * ram_len {
+        let odd_addresses = ram_type == RamType::EightBitOddAddress;
+        if let Some(ram) = sram::deinterleave_8bit(&ram, odd_addresses)
+            .and_then(|ram| sram::normalize(ram, ram_len))
+        {
+            log::info!("Loaded SRAM from a save file with 8-bit SRAM stored as 16-bit words");
+            return Some(ram);
+        }
+    }
+
+    sram::normalize(ram, ram_len)
+}
+
 #[derive(Debug, Clone, Encode, Decode)]
 pub(crate) struct Ram {
     ram: Vec<u8>,
@@ -35,10 +51,10 @@ pub(crate) struct Ram {
 impl Ram {
     pub(crate) fn from_rom_header(
         rom: &[u8],
-        checksum: u32,
+        quirks: &[GenesisQuirk],
         initial_ram: &mut Option<Vec<u8>>,
     ) -> Option<Self> {
-        if FORCE_SRAM_CHECKSUMS.contains(&checksum) {
+        if quirks.contains(&GenesisQuirk::ForceSram) {
             // Several games have 8KB of SRAM but don't declare it in the header
             log::info!("Forcibly mapping 8KB of SRAM to $200001-$203FFF");
             return Some(Self::forced_8kb_sram(initial_ram));
@@ -88,10 +104,10 @@ impl Ram {
             (end_address - start_address) / 2 + 1
         };
 
-        let ram = match initial_ram.take() {
-            Some(ram) if ram.len() as u32 == ram_len => ram,
-            _ => vec![0; ram_len as usize],
-        };
+        let ram = initial_ram
+            .take()
+            .and_then(|ram| convert_initial_ram(ram, ram_type, ram_len as usize))
+            .unwrap_or_else(|| vec![0; ram_len as usize]);
 
         // TODO support RAM persistence
         Some(Self {
@@ -167,10 +183,10 @@ impl Ram {
     fn forced_8kb_sram(initial_ram: &mut Option<Vec<u8>>) -> Self {
         const SRAM_LEN: usize = 8 * 1024;
 
-        let ram = match initial_ram.take() {
-            Some(ram) if ram.len() == SRAM_LEN => ram,
-            _ => vec![0; SRAM_LEN],
-        };
+        let ram = initial_ram
+            .take()
+            .and_then(|ram| convert_initial_ram(ram, RamType::EightBitOddAddress, SRAM_LEN))
+            .unwrap_or_else(|| vec![0; SRAM_LEN]);
 
         Self {
             ram,
@@ -228,6 +244,10 @@ impl Eeprom {
     fn get_memory(&self) -> &[u8] {
         match_each_eeprom!(self, chip => chip.get_memory())
     }
+
+    fn restore_memory(&mut self, memory: &[u8]) {
+        match_each_eeprom!(self, chip => chip.restore_memory(memory));
+    }
 }
 
 #[derive(Debug, Clone, Encode, Decode)]
@@ -246,8 +266,13 @@ pub(crate) enum ExternalMemory {
 }
 
 impl ExternalMemory {
-    pub(crate) fn from_rom(rom: &[u8], checksum: u32, mut initial_ram: Option<Vec<u8>>) -> Self {
-        if let Some(ram) = Ram::from_rom_header(rom, checksum, &mut initial_ram) {
+    pub(crate) fn from_rom(
+        rom: &[u8],
+        checksum: u32,
+        quirks: &[GenesisQuirk],
+        mut initial_ram: Option<Vec<u8>>,
+    ) -> Self {
+        if let Some(ram) = Ram::from_rom_header(rom, quirks, &mut initial_ram) {
             return Self::Ram(ram);
         }
 
@@ -259,6 +284,31 @@ impl ExternalMemory {
         Self::None
     }
 
+    pub(crate) fn describe_memory_map(&self, map: &mut MemoryMap) {
+        match self {
+            Self::None => {}
+            Self::Ram(ram) => {
+                let bus_width = match ram.ram_type {
+                    RamType::SixteenBit => "16-bit",
+                    RamType::EightBitOddAddress => "8-bit, odd addresses",
+                    RamType::EightBitEvenAddress => "8-bit, even addresses",
+                };
+                map.add_overlay(
+                    ram.start_address,
+                    ram.end_address,
+                    RegionKind::Ram,
+                    "Cartridge SRAM",
+                    Some(format!("{} bytes, {bus_width}", ram.ram.len())),
+                );
+            }
+            &Self::Eeprom { sda_in_addr, sda_out_addr, scl_addr, .. } => {
+                let start = sda_in_addr.min(sda_out_addr).min(scl_addr);
+                let end = sda_in_addr.max(sda_out_addr).max(scl_addr);
+                map.add_overlay(start, end, RegionKind::Registers, "EEPROM lines", None);
+            }
+        }
+    }
+
     pub(crate) fn read_byte(&self, address: u32) -> Option<u8> {
         match self {
             Self::None => None,
@@ -330,6 +380,18 @@ impl ExternalMemory {
         }
     }
 
+    // Marks memory dirty so that the restored contents will be persisted
+    pub(crate) fn restore_memory(&mut self, memory: &[u8]) {
+        match self {
+            Self::None => {}
+            Self::Ram(ram) => {
+                ram.ram.copy_from_slice(memory);
+                ram.dirty = true;
+            }
+            Self::Eeprom { chip, .. } => chip.restore_memory(memory),
+        }
+    }
+
     pub(crate) fn is_persistent(&self) -> bool {
         match self {
             Self::None => false,
@@ -382,3 +444,50 @@ fn new_eeprom(
         scl_bit: metadata.scl_bit,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_initial_ram_exact_size() {
+        let ram = vec![1, 2, 3, 4];
+        assert_eq!(convert_initial_ram(ram.clone(), RamType::SixteenBit, 4), Some(ram.clone()));
+        assert_eq!(convert_initial_ram(ram.clone(), RamType::EightBitOddAddress, 4), Some(ram));
+    }
+
+    #[test]
+    fn convert_initial_ram_interleaved() {
+        assert_eq!(
+            convert_initial_ram(vec![0, 1, 0, 2, 0, 3, 0, 4], RamType::EightBitOddAddress, 4),
+            Some(vec![1, 2, 3, 4])
+        );
+        assert_eq!(
+            convert_initial_ram(vec![1, 0xFF, 2, 0xFF], RamType::EightBitEvenAddress, 2),
+            Some(vec![1, 2])
+        );
+        // Interleaved and padded
+        assert_eq!(
+            convert_initial_ram(vec![0, 1, 0, 2, 0, 0, 0, 0], RamType::EightBitOddAddress, 2),
+            Some(vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn convert_initial_ram_16_bit_is_not_deinterleaved() {
+        assert_eq!(
+            convert_initial_ram(vec![0, 1, 0, 2, 0, 0, 0, 0], RamType::SixteenBit, 4),
+            Some(vec![0, 1, 0, 2])
+        );
+        assert_eq!(convert_initial_ram(vec![0, 1, 0, 2], RamType::SixteenBit, 2), None);
+    }
+
+    #[test]
+    fn convert_initial_ram_rejects_unrecognized_files() {
+        assert_eq!(
+            convert_initial_ram(vec![1, 2, 3, 4, 5, 6, 7, 8], RamType::EightBitOddAddress, 4),
+            None
+        );
+        assert_eq!(convert_initial_ram(vec![1, 2, 3], RamType::EightBitOddAddress, 4), None);
+    }
+}
@@ -0,0 +1,61 @@
+//! Games that need game-specific workarounds to run correctly
+
+use bincode::{Decode, Encode};
+use jgenesis_common::quirks::{GameKey, GameQuirk, QuirkEntry};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub(crate) enum GenesisQuirk {
+    /// Game has 8KB of SRAM mapped to $200001-$203FFF but does not declare it in the header
+    ForceSram,
+    /// Game expects the third MB of the ROM to be mapped to $300000-$3FFFFF instead of
+    /// $200000-$2FFFFF
+    MirrorThirdRomMegabyte,
+    /// Game depends on $A13000 reads returning a value where the lower 4 bits are $C or else it
+    /// will immediately crash and display "decode error"
+    RockmanX3Protection,
+}
+
+impl GameQuirk for GenesisQuirk {
+    fn description(self) -> &'static str {
+        match self {
+            Self::ForceSram => "Undeclared 8KB SRAM",
+            Self::MirrorThirdRomMegabyte => "Third ROM MB mapped to $300000",
+            Self::RockmanX3Protection => "Protection register at $A13000",
+        }
+    }
+}
+
+const DATABASE: &[QuirkEntry<GenesisQuirk>] = &[
+    QuirkEntry {
+        game: "NHL 96 (UE)",
+        keys: &[GameKey::Crc32(0x8135702C)],
+        quirks: &[GenesisQuirk::ForceSram],
+    },
+    QuirkEntry {
+        game: "Might and Magic: Gates to Another World (UE)",
+        keys: &[GameKey::Crc32(0xF509145F)],
+        quirks: &[GenesisQuirk::ForceSram],
+    },
+    QuirkEntry {
+        game: "Might and Magic III: Isles of Terra (U) (Proto)",
+        keys: &[GameKey::Crc32(0x6EF7104A)],
+        quirks: &[GenesisQuirk::ForceSram],
+    },
+    QuirkEntry {
+        game: "Triple Play Gold / Triple Play '96",
+        keys: &[GameKey::Serial(b"T-172116"), GameKey::Serial(b"T-172026")],
+        quirks: &[GenesisQuirk::MirrorThirdRomMegabyte],
+    },
+    QuirkEntry {
+        game: "Rockman X3 (Unlicensed)",
+        keys: &[GameKey::Crc32(0x3EE639F0)],
+        quirks: &[GenesisQuirk::RockmanX3Protection],
+    },
+];
+
+/// Look up the workarounds for a cartridge. `checksum` should be the CRC32 of the ROM as loaded,
+/// and `rom` should already be in the expected format.
+pub(crate) fn find(checksum: u32, rom: &[u8]) -> Vec<GenesisQuirk> {
+    let serial_number = rom.get(0x183..0x18B).unwrap_or(&[]);
+    jgenesis_common::quirks::find_quirks(DATABASE, checksum, serial_number)
+}
@@ -8,20 +8,21 @@ mod render;
 mod sprites;
 mod timing;
 
+pub use registers::{DebugRegister, Plane};
+
 use crate::memory::{Memory, PhysicalMedium};
 use crate::vdp::colors::ColorModifier;
 use crate::vdp::registers::{
-    DebugRegister, DmaMode, H40_LEFT_BORDER, HorizontalDisplaySize, InterlacingMode,
-    NTSC_BOTTOM_BORDER, NTSC_TOP_BORDER, PAL_V28_BOTTOM_BORDER, PAL_V28_TOP_BORDER,
-    PAL_V30_BOTTOM_BORDER, PAL_V30_TOP_BORDER, RIGHT_BORDER, Registers, VerticalDisplaySize,
-    VramSizeKb,
+    DmaMode, H40_LEFT_BORDER, HorizontalDisplaySize, InterlacingMode, NTSC_BOTTOM_BORDER,
+    NTSC_TOP_BORDER, PAL_V28_BOTTOM_BORDER, PAL_V28_TOP_BORDER, PAL_V30_BOTTOM_BORDER,
+    PAL_V30_TOP_BORDER, RIGHT_BORDER, Registers, VerticalDisplaySize, VramA16Bits, VramSizeKb,
 };
 use crate::vdp::sprites::{SpriteBuffers, SpriteState};
-use crate::vdp::timing::{DmaTracker, FifoTracker, LineType};
+use crate::vdp::timing::{DmaTracker, FifoTracker, FifoWrite, LineType};
 use bincode::{Decode, Encode};
 use jgenesis_common::frontend::{Color, FrameSize, TimingMode};
 use jgenesis_common::num::GetBit;
-use jgenesis_proc_macros::{FakeDecode, FakeEncode};
+use jgenesis_proc_macros::SaveState;
 use std::array;
 use std::ops::{Deref, DerefMut};
 use z80_emu::traits::InterruptLine;
@@ -226,7 +227,7 @@ pub enum VdpTickEffect {
     FrameComplete,
 }
 
-#[derive(Debug, Clone, FakeEncode, FakeDecode)]
+#[derive(Debug, Clone)]
 struct FrameBuffer(Box<[Color; FRAME_BUFFER_LEN]>);
 
 impl FrameBuffer {
@@ -279,6 +280,11 @@ const V_INTERRUPT_DELAY: u64 = 48;
 const M68K_DIVIDER: u64 = crate::timing::NATIVE_M68K_DIVIDER;
 const VINT_FLAG_MCLK: u64 = MCLK_CYCLES_PER_SCANLINE - (20 * M68K_DIVIDER - V_INTERRUPT_DELAY);
 
+// The Z80 INT line is asserted at the same time as the 68000 V interrupt and held for 171 Z80
+// cycles, somewhat less than one scanline. Sound drivers that keep interrupts disabled for longer
+// than this will miss the interrupt for that frame
+const Z80_INT_DURATION_MCLK: u64 = 171 * crate::timing::Z80_DIVIDER;
+
 pub(crate) trait TimingModeExt: Copy {
     fn scanlines_per_frame(self) -> u16;
 
@@ -322,16 +328,20 @@ pub struct VdpConfig {
     pub sprites_enabled: bool,
     pub window_enabled: bool,
     pub backdrop_enabled: bool,
+    pub expanded_vram: bool,
 }
 
 type Vram = [u8; VRAM_LEN];
 type Cram = [u16; CRAM_LEN_WORDS];
 type Vsram = [u8; VSRAM_LEN];
 
-#[derive(Debug, Clone, Encode, Decode)]
+#[derive(Debug, Clone, SaveState)]
 pub struct Vdp {
+    #[savestate(skip, default = FrameBuffer::new())]
     frame_buffer: FrameBuffer,
     vram: Box<Vram>,
+    // Upper 64KB of VRAM; only allocated with expanded VRAM, and only accessible in 128KB mode
+    vram_expansion: Option<Box<Vram>>,
     cram: Box<Cram>,
     vsram: Box<Vsram>,
     timing_mode: TimingMode,
@@ -358,6 +368,7 @@ impl Vdp {
         Self {
             frame_buffer: FrameBuffer::new(),
             vram: vec![0; VRAM_LEN].into_boxed_slice().try_into().unwrap(),
+            vram_expansion: config.expanded_vram.then(new_vram),
             cram: vec![0; CRAM_LEN_WORDS].into_boxed_slice().try_into().unwrap(),
             vsram: vec![0; VSRAM_LEN].into_boxed_slice().try_into().unwrap(),
             timing_mode,
@@ -570,6 +581,10 @@ impl Vdp {
             return 0xFFFF;
         }
 
+        // Reads wait for all pending FIFO writes to complete
+        self.fifo_tracker.flush();
+        self.commit_fifo_writes();
+
         self.dma_tracker.record_data_port_read();
 
         let data_port_location = self.state.data_port_location;
@@ -583,8 +598,12 @@ impl Vdp {
                     }
                     VramSizeKb::OneTwentyEight => {
                         // Reads in 128KB mode duplicate a single byte to both halves of the word
-                        let address = convert_128kb_vram_address(self.state.data_address);
-                        let byte = self.vram[address as usize];
+                        let byte = if self.config.expanded_vram {
+                            self.read_expanded_vram_byte(self.state.data_address)
+                        } else {
+                            let address = convert_128kb_vram_address(self.state.data_address);
+                            self.vram[address as usize]
+                        };
                         u16::from_be_bytes([byte, byte])
                     }
                 }
@@ -608,7 +627,12 @@ impl Vdp {
         self.increment_data_address();
 
         let line_type = LineType::from_vdp(self);
-        self.fifo_tracker.record_access(line_type, data_port_location, self.registers.vram_size);
+        self.fifo_tracker.record_access(
+            line_type,
+            data_port_location,
+            self.registers.vram_size,
+            None,
+        );
 
         data
     }
@@ -644,31 +668,49 @@ impl Vdp {
             return;
         }
 
+        // During active display, writes go through the FIFO and are not applied until the VDP
+        // reaches an external access slot
         let data_port_location = self.state.data_port_location;
-        match data_port_location {
+        let write =
+            FifoWrite { location: data_port_location, address: self.state.data_address, value };
+
+        self.increment_data_address();
+
+        let line_type = LineType::from_vdp(self);
+        self.fifo_tracker.record_access(
+            line_type,
+            data_port_location,
+            self.registers.vram_size,
+            Some(write),
+        );
+        self.commit_fifo_writes();
+    }
+
+    fn commit_fifo_writes(&mut self) {
+        while let Some(write) = self.fifo_tracker.pop_completed_write() {
+            self.apply_fifo_write(write);
+        }
+    }
+
+    fn apply_fifo_write(&mut self, FifoWrite { location, address, value }: FifoWrite) {
+        match location {
             DataPortLocation::Vram => {
                 // VRAM reads/writes ignore A0
-                log::trace!("Writing to {:04X} in VRAM", self.state.data_address);
-                self.write_vram_word(self.state.data_address, value);
+                log::trace!("Writing to {address:04X} in VRAM");
+                self.write_vram_word(address, value);
             }
             DataPortLocation::Cram => {
-                let address = (self.state.data_address & 0x7F) as usize;
-                log::trace!("Writing to {address:02X} in CRAM");
-                self.write_cram_word(self.state.data_address, value);
+                log::trace!("Writing to {:02X} in CRAM", address & 0x7F);
+                self.write_cram_word(address, value);
             }
             DataPortLocation::Vsram => {
-                let address = (self.state.data_address as usize) % VSRAM_LEN;
+                let address = (address as usize) % VSRAM_LEN;
                 log::trace!("Writing to {address:02X} in VSRAM");
                 let [msb, lsb] = value.to_be_bytes();
                 self.vsram[address] = msb;
                 self.vsram[(address + 1) % VSRAM_LEN] = lsb;
             }
         }
-
-        self.increment_data_address();
-
-        let line_type = LineType::from_vdp(self);
-        self.fifo_tracker.record_access(line_type, data_port_location, self.registers.vram_size);
     }
 
     fn maybe_push_pending_write(&mut self, write: PendingWrite) -> bool {
@@ -689,6 +731,16 @@ impl Vdp {
         log::trace!("VDP debug register write: {:?}", self.debug_register);
     }
 
+    #[must_use]
+    pub fn debug_register(&self) -> DebugRegister {
+        self.debug_register
+    }
+
+    /// Directly override the debug register state, e.g. for layer forcing from a debugger UI.
+    pub fn set_debug_register(&mut self, debug_register: DebugRegister) {
+        self.debug_register = debug_register;
+    }
+
     pub fn read_status(&mut self) -> u16 {
         log::trace!("VDP status register read");
 
@@ -976,11 +1028,16 @@ impl Vdp {
         let pixel = scanline_mclk_to_pixel(self.state.scanline_mclk_cycles, h_display_size);
 
         self.fifo_tracker.advance_to_pixel(self.state.scanline, pixel, h_display_size, line_type);
+        self.commit_fifo_writes();
 
         // Count down DMA time before checking if a DMA was initiated in the last CPU instruction
         self.dma_tracker.advance_to_pixel(self.state.scanline, pixel, h_display_size, line_type);
 
         if let Some(active_dma) = self.state.pending_dma {
+            // DMA does not start until the FIFO has drained
+            self.fifo_tracker.flush();
+            self.commit_fifo_writes();
+
             // TODO accurate DMA timing
             self.run_dma(memory, active_dma);
         }
@@ -1033,6 +1090,10 @@ impl Vdp {
                     self.maybe_update_sprite_cache((vram_addr ^ 0x1) as u16, lsb);
                 }
             }
+            VramSizeKb::OneTwentyEight if self.config.expanded_vram => {
+                // Only LSB is written in 128KB mode
+                self.write_expanded_vram_byte(address, lsb);
+            }
             VramSizeKb::OneTwentyEight => {
                 // Only LSB is written in 128KB mode
                 let vram_addr = convert_128kb_vram_address(address);
@@ -1045,6 +1106,37 @@ impl Vdp {
         }
     }
 
+    // With expanded VRAM, 128KB mode addresses all 128KB linearly using address bits 16-0
+    fn read_expanded_vram_byte(&self, address: u32) -> u8 {
+        vram_bank(&self.vram, self.vram_expansion.as_deref(), address.bit(16))
+            [(address & 0xFFFF) as usize]
+    }
+
+    fn write_expanded_vram_byte(&mut self, address: u32, value: u8) {
+        let bank_addr = address as u16;
+        match &mut self.vram_expansion {
+            Some(vram_expansion) if address.bit(16) => vram_expansion[bank_addr as usize] = value,
+            _ => self.vram[bank_addr as usize] = value,
+        }
+
+        if address.bit(16) == self.registers.vram_a16.sprite_attribute_table {
+            self.maybe_update_sprite_cache(bank_addr, value);
+        }
+    }
+
+    fn vram_expansion_active(&self) -> bool {
+        self.config.expanded_vram && self.registers.vram_size == VramSizeKb::OneTwentyEight
+    }
+
+    // Bit 16 of each VRAM table address, or all zeros if the VRAM expansion is not in use
+    fn vram_a16_bits(&self, registers: &Registers) -> VramA16Bits {
+        if self.config.expanded_vram && registers.vram_size == VramSizeKb::OneTwentyEight {
+            registers.vram_a16
+        } else {
+            VramA16Bits::default()
+        }
+    }
+
     fn write_cram_word(&mut self, address: u32, value: u16) {
         if !address.bit(0) {
             self.cram[((address & 0x7F) >> 1) as usize] = value;
@@ -1106,7 +1198,7 @@ impl Vdp {
 
     #[must_use]
     pub fn m68k_interrupt_level(&self) -> u8 {
-        // TODO external interrupts at level 2
+        // Level 2 external interrupts are raised by the I/O ports and handled by the bus
         if self.state.v_interrupt_pending
             && self.registers.v_interrupt_enabled
             && !self.state.delayed_v_interrupt
@@ -1119,6 +1211,12 @@ impl Vdp {
         }
     }
 
+    #[inline]
+    #[must_use]
+    pub fn external_interrupts_enabled(&self) -> bool {
+        self.registers.external_interrupts_enabled
+    }
+
     pub fn acknowledge_m68k_interrupt(&mut self) {
         let interrupt_level = self.m68k_interrupt_level();
         log::trace!("M68K interrupt acknowledged; level {interrupt_level}");
@@ -1146,8 +1244,11 @@ impl Vdp {
     #[inline]
     #[must_use]
     pub fn z80_interrupt_line(&self) -> InterruptLine {
-        // Z80 INT line is low only during the first scanline of VBlank
-        if self.state.scanline == self.registers.vertical_display_size.active_scanlines() {
+        // Z80 INT line is low only for a short time at the start of VBlank
+        if self.state.scanline == self.registers.vertical_display_size.active_scanlines()
+            && (V_INTERRUPT_DELAY..V_INTERRUPT_DELAY + Z80_INT_DURATION_MCLK)
+                .contains(&self.state.scanline_mclk_cycles)
+        {
             InterruptLine::Low
         } else {
             InterruptLine::High
@@ -1253,7 +1354,31 @@ impl Vdp {
 
     #[inline]
     pub fn reload_config(&mut self, config: VdpConfig) {
+        let prev_expanded_vram = self.config.expanded_vram;
         self.config = config;
+
+        if config.expanded_vram != prev_expanded_vram {
+            self.vram_expansion = config.expanded_vram.then(new_vram);
+
+            // The sprite attribute table may now be in a different bank
+            self.refresh_sprite_cache();
+        }
+    }
+
+    // Rebuild the sprite attribute cache from the current sprite attribute table. Only needed
+    // when the emulated VRAM layout changes or VRAM is overwritten from outside the emulated
+    // system; actual hardware only updates the cache on writes
+    fn refresh_sprite_cache(&mut self) {
+        let sprite_table_a16 = self.vram_a16_bits(&self.registers).sprite_attribute_table;
+        let sprite_table_addr = self.registers.masked_sprite_attribute_table_addr();
+        let sprite_table_len = self.registers.horizontal_display_size.sprite_table_len();
+
+        for i in 0..8 * sprite_table_len {
+            let address = sprite_table_addr.wrapping_add(i);
+            let vram = vram_bank(&self.vram, self.vram_expansion.as_deref(), sprite_table_a16);
+            let value = vram[address as usize];
+            self.maybe_update_sprite_cache(address, value);
+        }
     }
 
     #[inline]
@@ -1269,6 +1394,21 @@ impl Vdp {
     }
 }
 
+fn new_vram() -> Box<Vram> {
+    vec![0; VRAM_LEN].into_boxed_slice().try_into().unwrap()
+}
+
+fn vram_bank<'vram>(
+    vram: &'vram Vram,
+    vram_expansion: Option<&'vram Vram>,
+    a16: bool,
+) -> &'vram Vram {
+    match vram_expansion {
+        Some(vram_expansion) if a16 => vram_expansion,
+        _ => vram,
+    }
+}
+
 fn convert_128kb_vram_address(address: u32) -> u32 {
     // Formula from https://plutiedev.com/mirror/kabuto-hardware-notes#128k-abuse
     (((address & 0x2) ^ 0x2) >> 1)
@@ -1321,20 +1461,25 @@ fn scanline_mclk_to_pixel_h40(scanline_mclk: u64) -> u16 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::vdp::render::RasterLine;
 
     fn new_vdp() -> Vdp {
-        Vdp::new(TimingMode::Ntsc, VdpConfig {
-            enforce_sprite_limits: true,
-            emulate_non_linear_dac: false,
-            deinterlace: true,
-            render_vertical_border: false,
-            render_horizontal_border: false,
-            plane_a_enabled: true,
-            plane_b_enabled: true,
-            window_enabled: true,
-            sprites_enabled: true,
-            backdrop_enabled: true,
-        })
+        Vdp::new(
+            TimingMode::Ntsc,
+            VdpConfig {
+                enforce_sprite_limits: true,
+                emulate_non_linear_dac: false,
+                deinterlace: true,
+                render_vertical_border: false,
+                render_horizontal_border: false,
+                plane_a_enabled: true,
+                plane_b_enabled: true,
+                window_enabled: true,
+                sprites_enabled: true,
+                backdrop_enabled: true,
+                expanded_vram: false,
+            },
+        )
     }
 
     #[test]
@@ -1382,4 +1527,202 @@ mod tests {
         assert_eq!(vdp.h_counter(MCLK_CYCLES_PER_SCANLINE - 16), 0xFF);
         assert_eq!(vdp.h_counter(MCLK_CYCLES_PER_SCANLINE - 1), 0xFF);
     }
+
+    #[test]
+    fn z80_interrupt_line_timing() {
+        let mut vdp = new_vdp();
+        let vblank_start = vdp.registers.vertical_display_size.active_scanlines();
+
+        for (scanline, scanline_mclk, expected) in [
+            (vblank_start - 1, V_INTERRUPT_DELAY, InterruptLine::High),
+            (vblank_start, 0, InterruptLine::High),
+            (vblank_start, V_INTERRUPT_DELAY - 1, InterruptLine::High),
+            (vblank_start, V_INTERRUPT_DELAY, InterruptLine::Low),
+            (vblank_start, V_INTERRUPT_DELAY + 170 * 15, InterruptLine::Low),
+            (vblank_start, V_INTERRUPT_DELAY + 171 * 15, InterruptLine::High),
+            (vblank_start + 1, V_INTERRUPT_DELAY, InterruptLine::High),
+        ] {
+            vdp.state.scanline = scanline;
+            vdp.state.scanline_mclk_cycles = scanline_mclk;
+            assert_eq!(
+                vdp.z80_interrupt_line(),
+                expected,
+                "scanline {scanline}, mclk {scanline_mclk}"
+            );
+        }
+    }
+
+    fn test_sprite(h_position: u16, h_size_cells: u8) -> SpriteData {
+        SpriteData {
+            pattern_generator: 0,
+            v_position: 0x80,
+            h_position,
+            h_size_cells,
+            v_size_cells: 1,
+            palette: 0,
+            vertical_flip: false,
+            horizontal_flip: false,
+            priority: false,
+            link_data: 0,
+        }
+    }
+
+    #[test]
+    fn masked_sprites_count_towards_dot_limit() {
+        let mut vdp = new_vdp();
+        vdp.latched_registers.horizontal_display_size = HorizontalDisplaySize::FortyCell;
+        vdp.vram[..32].fill(0x11);
+
+        let raster_line = RasterLine::from_scanline(0, &vdp.latched_registers, vdp.timing_mode);
+
+        // Visible sprite, then an H=0 masking sprite, then enough masked sprites to exceed
+        // the 40 tile limit
+        vdp.sprite_buffers.sprites.clear();
+        vdp.sprite_buffers.sprites.push(test_sprite(0x100, 4));
+        vdp.sprite_buffers.sprites.push(test_sprite(0, 4));
+        for _ in 0..9 {
+            vdp.sprite_buffers.sprites.push(test_sprite(0x90, 4));
+        }
+        vdp.render_sprite_pixels(raster_line, false);
+
+        assert_eq!(vdp.sprite_buffers.pixels[0x80].color, 1);
+        assert_eq!(vdp.sprite_buffers.pixels[0x10].color, 0);
+        assert!(vdp.sprite_state.overflow_flag());
+
+        // Dot overflow on the previous line enables masking from a leading H=0 sprite
+        vdp.sprite_buffers.sprites.clear();
+        vdp.sprite_buffers.sprites.push(test_sprite(0, 1));
+        vdp.sprite_buffers.sprites.push(test_sprite(0x100, 4));
+        vdp.render_sprite_pixels(raster_line, false);
+
+        assert_eq!(vdp.sprite_buffers.pixels[0x80].color, 0);
+
+        // No dot overflow on the previous line; a leading H=0 sprite does not mask
+        vdp.render_sprite_pixels(raster_line, false);
+
+        assert_eq!(vdp.sprite_buffers.pixels[0x80].color, 1);
+    }
+
+    #[test]
+    fn fifo_writes_delayed_during_active_display() {
+        let mut vdp = new_vdp();
+        vdp.registers.display_enabled = true;
+        vdp.registers.horizontal_display_size = HorizontalDisplaySize::FortyCell;
+        vdp.registers.data_port_auto_increment = 2;
+        vdp.state.scanline = 10;
+        vdp.state.data_address = 0x100;
+
+        vdp.write_data(0x1234);
+        assert_eq!([vdp.vram[0x100], vdp.vram[0x101]], [0x00, 0x00]);
+        assert!(!vdp.fifo_tracker.is_empty());
+
+        // A 64KB-mode VRAM write takes two access slots
+        let h_size = HorizontalDisplaySize::FortyCell;
+        vdp.fifo_tracker.advance_to_pixel(10, 2, h_size, LineType::Active);
+        vdp.commit_fifo_writes();
+        assert_eq!([vdp.vram[0x100], vdp.vram[0x101]], [0x00, 0x00]);
+
+        vdp.fifo_tracker.advance_to_pixel(10, 18, h_size, LineType::Active);
+        vdp.commit_fifo_writes();
+        assert_eq!([vdp.vram[0x100], vdp.vram[0x101]], [0x12, 0x34]);
+        assert!(vdp.fifo_tracker.is_empty());
+    }
+
+    #[test]
+    fn fifo_writes_immediate_during_blanking() {
+        let mut vdp = new_vdp();
+        vdp.registers.display_enabled = false;
+        vdp.state.data_address = 0x100;
+
+        vdp.write_data(0x1234);
+        assert_eq!([vdp.vram[0x100], vdp.vram[0x101]], [0x12, 0x34]);
+        assert!(vdp.fifo_tracker.is_empty());
+    }
+
+    fn new_expanded_vram_vdp() -> Vdp {
+        let mut vdp = new_vdp();
+        vdp.reload_config(VdpConfig { expanded_vram: true, ..vdp.config });
+        vdp.registers.vram_size = VramSizeKb::OneTwentyEight;
+        vdp
+    }
+
+    #[test]
+    fn expanded_vram_128kb_mode() {
+        let mut vdp = new_expanded_vram_vdp();
+        vdp.state.data_address = 0x10101;
+
+        // Only the LSB is written, and bit 16 selects the expansion bank
+        vdp.write_data(0x1234);
+        assert_eq!(vdp.vram_expansion.as_ref().unwrap()[0x0101], 0x34);
+        assert_eq!(vdp.vram[0x0101], 0x00);
+
+        vdp.state.data_port_mode = DataPortMode::Read;
+        vdp.state.data_address = 0x10101;
+        assert_eq!(vdp.read_data(), 0x3434);
+    }
+
+    #[test]
+    fn expanded_vram_only_saved_when_enabled() {
+        let config = bincode::config::standard();
+        let mut vdp = new_vdp();
+        let unexpanded_len = bincode::encode_to_vec(&vdp, config).unwrap().len();
+
+        vdp.reload_config(VdpConfig { expanded_vram: true, ..vdp.config });
+        let expanded_len = bincode::encode_to_vec(&vdp, config).unwrap().len();
+        assert_eq!(expanded_len - unexpanded_len, VRAM_LEN);
+
+        vdp.reload_config(VdpConfig { expanded_vram: false, ..vdp.config });
+        assert!(vdp.vram_expansion.is_none());
+    }
+
+    #[test]
+    fn expanded_vram_copy_stays_in_bank() {
+        let mut vdp = new_expanded_vram_vdp();
+        vdp.vram[0x100..0x104].copy_from_slice(&[1, 2, 3, 4]);
+        vdp.vram_expansion.as_mut().unwrap()[0x100..0x104].copy_from_slice(&[5, 6, 7, 8]);
+
+        vdp.registers.data_port_auto_increment = 1;
+        vdp.registers.dma_length = 4;
+        vdp.registers.dma_source_address = 0x100 << 1;
+        vdp.state.data_address = 0x10200;
+        vdp.run_vram_copy();
+
+        assert_eq!(vdp.vram_expansion.as_ref().unwrap()[0x200..0x204], [5, 6, 7, 8]);
+        assert_eq!(vdp.vram[0x200..0x204], [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn expanded_vram_toggle_refreshes_sprite_cache() {
+        let mut vdp = new_vdp();
+        vdp.registers.vram_size = VramSizeKb::OneTwentyEight;
+        vdp.registers.vram_a16.sprite_attribute_table = true;
+
+        // Sprite 0 link field in the stock VRAM bank
+        vdp.vram[3] = 0x07;
+        vdp.maybe_update_sprite_cache(3, 0x07);
+        assert_eq!(vdp.cached_sprite_attributes[0].link_data, 0x07);
+
+        // Sprite attribute table is now in the (empty) expansion bank
+        vdp.reload_config(VdpConfig { expanded_vram: true, ..vdp.config });
+        assert_eq!(vdp.cached_sprite_attributes[0].link_data, 0x00);
+
+        vdp.state.data_address = 0x10003;
+        vdp.write_data(0x0005);
+        assert_eq!(vdp.cached_sprite_attributes[0].link_data, 0x05);
+
+        vdp.reload_config(VdpConfig { expanded_vram: false, ..vdp.config });
+        assert_eq!(vdp.cached_sprite_attributes[0].link_data, 0x07);
+    }
+
+    #[test]
+    fn restore_vram_refreshes_sprite_cache() {
+        let mut vdp = new_vdp();
+
+        let mut vram = vdp.dump_vram();
+        let sprite_table_addr = vdp.registers.masked_sprite_attribute_table_addr() as usize;
+        vram[sprite_table_addr + 3] = 0x09;
+        vdp.restore_vram(&vram);
+
+        assert_eq!(vdp.cached_sprite_attributes[0].link_data, 0x09);
+    }
 }
@@ -67,41 +67,54 @@ impl Vdp {
 
                 let [msb, _] = fill_data.to_be_bytes();
                 for _ in 0..self.registers.dma_length() {
-                    let vram_addr = (self.state.data_address ^ 0x1) & 0xFFFF;
-                    self.vram[vram_addr as usize] = msb;
-                    self.maybe_update_sprite_cache(vram_addr as u16, msb);
+                    if self.vram_expansion_active() {
+                        self.write_expanded_vram_byte(self.state.data_address ^ 0x1, msb);
+                    } else {
+                        let vram_addr = (self.state.data_address ^ 0x1) & 0xFFFF;
+                        self.vram[vram_addr as usize] = msb;
+                        self.maybe_update_sprite_cache(vram_addr as u16, msb);
+                    }
 
                     self.increment_data_address();
                 }
             }
-            ActiveDma::VramCopy => {
-                self.init_dma_timing(DmaMode::VramCopy, DataPortLocation::Vram);
-
-                log::trace!(
-                    "Running VRAM copy with source addr {:04X}, dest addr {:04X}, and length {}",
-                    self.registers.dma_source_address,
-                    self.state.data_address,
-                    self.registers.dma_length()
-                );
-
-                // VRAM copy DMA treats the source address as A15-A0 instead of A23-A1
-                let mut source_addr = (self.registers.dma_source_address >> 1) as u16;
-                for _ in 0..self.registers.dma_length() {
-                    let dest_addr = self.state.data_address & 0xFFFF;
-                    let byte = self.vram[source_addr as usize];
-                    self.vram[dest_addr as usize] = byte;
-                    self.maybe_update_sprite_cache(dest_addr as u16, byte);
+            ActiveDma::VramCopy => self.run_vram_copy(),
+        }
 
-                    source_addr = source_addr.wrapping_add(1);
-                    self.increment_data_address();
-                }
+        self.state.pending_dma = None;
+        self.registers.dma_length = 0;
+    }
 
-                self.registers.dma_source_address = u32::from(source_addr) << 1;
+    pub(super) fn run_vram_copy(&mut self) {
+        self.init_dma_timing(DmaMode::VramCopy, DataPortLocation::Vram);
+
+        log::trace!(
+            "Running VRAM copy with source addr {:04X}, dest addr {:04X}, and length {}",
+            self.registers.dma_source_address,
+            self.state.data_address,
+            self.registers.dma_length()
+        );
+
+        // VRAM copy DMA treats the source address as A15-A0 instead of A23-A1
+        let mut source_addr = (self.registers.dma_source_address >> 1) as u16;
+        for _ in 0..self.registers.dma_length() {
+            if self.vram_expansion_active() {
+                // The source address has no A16, so copies stay within the destination bank
+                let source_a16 = self.state.data_address & 0x10000;
+                let byte = self.read_expanded_vram_byte(source_a16 | u32::from(source_addr));
+                self.write_expanded_vram_byte(self.state.data_address, byte);
+            } else {
+                let byte = self.vram[source_addr as usize];
+                let dest_addr = self.state.data_address & 0xFFFF;
+                self.vram[dest_addr as usize] = byte;
+                self.maybe_update_sprite_cache(dest_addr as u16, byte);
             }
+
+            source_addr = source_addr.wrapping_add(1);
+            self.increment_data_address();
         }
 
-        self.state.pending_dma = None;
-        self.registers.dma_length = 0;
+        self.registers.dma_source_address = u32::from(source_addr) << 1;
     }
 
     fn init_dma_timing(&mut self, mode: DmaMode, data_port_location: DataPortLocation) {
@@ -1,6 +1,7 @@
 use bincode::{Decode, Encode};
 use jgenesis_common::frontend::TimingMode;
 use jgenesis_common::num::{GetBit, U16Ext};
+use jgenesis_proc_macros::EnumAll;
 use std::fmt::{Display, Formatter};
 
 // Values from https://gendev.spritesmind.net/forum/viewtopic.php?p=37011#p37011
@@ -369,6 +370,27 @@ impl Display for VramSizeKb {
     }
 }
 
+// Bit 16 of each VRAM table address, which is only used in 128KB mode with expanded VRAM
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode)]
+pub struct VramA16Bits {
+    // Register #2 bit 6
+    pub scroll_a_nt: bool,
+    // Register #3 bit 6
+    pub window_nt: bool,
+    // Register #4 bit 3
+    pub scroll_b_nt: bool,
+    // Register #5 bit 7
+    pub sprite_attribute_table: bool,
+    // Register #6 bit 5
+    pub sprite_pattern: bool,
+    // Register #13 bit 6
+    pub h_scroll_table: bool,
+    // Register #14 bit 0
+    pub scroll_a_pattern: bool,
+    // Register #14 bit 4
+    pub scroll_b_pattern: bool,
+}
+
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct Registers {
     // Register #0
@@ -395,7 +417,7 @@ pub struct Registers {
     // Register #10
     pub h_interrupt_interval: u16,
     // Register #11
-    // TODO external interrupts enabled
+    pub external_interrupts_enabled: bool,
     pub vertical_scroll_mode: VerticalScrollMode,
     pub horizontal_scroll_mode: HorizontalScrollMode,
     // Register #12
@@ -420,6 +442,8 @@ pub struct Registers {
     // Registers #21, #22, & #23
     pub dma_source_address: u32,
     pub dma_mode: DmaMode,
+    // Registers #2-6, #13, & #14 (128KB VRAM only)
+    pub vram_a16: VramA16Bits,
 }
 
 impl Registers {
@@ -440,6 +464,7 @@ impl Registers {
             background_palette: 0,
             background_color_id: 0,
             h_interrupt_interval: 0,
+            external_interrupts_enabled: false,
             vertical_scroll_mode: VerticalScrollMode::default(),
             horizontal_scroll_mode: HorizontalScrollMode::default(),
             horizontal_display_size: HorizontalDisplaySize::default(),
@@ -456,6 +481,7 @@ impl Registers {
             dma_length: 0,
             dma_source_address: 0,
             dma_mode: DmaMode::default(),
+            vram_a16: VramA16Bits::default(),
         }
     }
 
@@ -509,6 +535,7 @@ impl Registers {
             2 => {
                 // Register #2: Scroll A name table base address (bits 15-13)
                 self.scroll_a_base_nt_addr = u16::from(value & 0x38) << 10;
+                self.vram_a16.scroll_a_nt = value.bit(6);
 
                 log::trace!(
                     "  Scroll A base nametable address: {:04X}",
@@ -518,12 +545,14 @@ impl Registers {
             3 => {
                 // Register #3: Window name table base address (bits 15-11)
                 self.window_base_nt_addr = u16::from(value & 0x3E) << 10;
+                self.vram_a16.window_nt = value.bit(6);
 
                 log::trace!("  Window base nametable address: {:04X}", self.window_base_nt_addr);
             }
             4 => {
                 // Register #4: Scroll B name table base address (bits 15-13)
                 self.scroll_b_base_nt_addr = u16::from(value & 0x07) << 13;
+                self.vram_a16.scroll_b_nt = value.bit(3);
 
                 log::trace!(
                     "  Scroll B base nametable address: {:04X}",
@@ -533,12 +562,19 @@ impl Registers {
             5 => {
                 // Register #5: Sprite attribute table base address (bits 15-9)
                 self.sprite_attribute_table_base_addr = u16::from(value & 0x7F) << 9;
+                self.vram_a16.sprite_attribute_table = value.bit(7);
 
                 log::trace!(
                     "  Sprite attribute table base address: {:04X}",
                     self.sprite_attribute_table_base_addr
                 );
             }
+            6 => {
+                // Register #6: Sprite pattern generator base address (bit 16, 128KB VRAM only)
+                self.vram_a16.sprite_pattern = value.bit(5);
+
+                log::trace!("  Sprite pattern A16: {}", self.vram_a16.sprite_pattern);
+            }
             7 => {
                 // Register #7: Background color
                 self.background_palette = (value >> 4) & 0x03;
@@ -555,7 +591,7 @@ impl Registers {
             }
             11 => {
                 // Register #11: Mode set register 3
-                // TODO external interrupt enable
+                self.external_interrupts_enabled = value.bit(3);
                 self.vertical_scroll_mode = if value.bit(2) {
                     VerticalScrollMode::TwoCell
                 } else {
@@ -569,6 +605,7 @@ impl Registers {
                     _ => unreachable!("value & 0x03 is always <= 0x03"),
                 };
 
+                log::trace!("  External interrupts enabled: {}", self.external_interrupts_enabled);
                 log::trace!("  Vertical scroll mode: {:?}", self.vertical_scroll_mode);
                 log::trace!("  Horizontal scroll mode: {:?}", self.horizontal_scroll_mode);
             }
@@ -595,9 +632,18 @@ impl Registers {
             13 => {
                 // Register #13: Horizontal scroll table base address (bits 15-10)
                 self.h_scroll_table_base_addr = u16::from(value & 0x3F) << 10;
+                self.vram_a16.h_scroll_table = value.bit(6);
 
                 log::trace!("  H scroll table base address: {:04X}", self.h_scroll_table_base_addr);
             }
+            14 => {
+                // Register #14: Name table pattern generator base address (bit 16, 128KB VRAM only)
+                self.vram_a16.scroll_a_pattern = value.bit(0);
+                self.vram_a16.scroll_b_pattern = value.bit(4);
+
+                log::trace!("  Scroll A pattern A16: {}", self.vram_a16.scroll_a_pattern);
+                log::trace!("  Scroll B pattern A16: {}", self.vram_a16.scroll_b_pattern);
+            }
             15 => {
                 // Register #15: VRAM address auto increment
                 self.data_port_auto_increment = value.into();
@@ -709,7 +755,7 @@ impl Registers {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode, EnumAll)]
 pub enum Plane {
     #[default]
     Background,
@@ -729,13 +775,14 @@ impl Display for Plane {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode)]
 pub struct DebugRegister {
     pub display_disabled: bool,
     pub forced_plane: Plane,
 }
 
 impl DebugRegister {
+    #[must_use]
     pub fn new() -> Self {
         Self { display_disabled: false, forced_plane: Plane::default() }
     }
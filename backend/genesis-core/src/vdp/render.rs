@@ -3,7 +3,9 @@ use crate::vdp::registers::{
     DebugRegister, HorizontalDisplaySize, HorizontalScrollMode, InterlacingMode, Plane,
     RIGHT_BORDER, Registers, ScrollSize, VerticalDisplaySize, VerticalScrollMode,
 };
-use crate::vdp::{Cram, FrameBuffer, TilePixel, TimingModeExt, Vdp, Vram, Vsram, colors};
+use crate::vdp::{
+    Cram, FrameBuffer, TilePixel, TimingModeExt, Vdp, Vram, Vsram, colors, vram_bank,
+};
 use jgenesis_common::frontend::TimingMode;
 use jgenesis_common::num::GetBit;
 use std::{array, cmp};
@@ -307,8 +309,10 @@ impl Vdp {
             InterlacingMode::Progressive | InterlacingMode::Interlaced => raster_line,
             InterlacingMode::InterlacedDouble => raster_line / 2,
         };
+        let vram_a16 = self.vram_a16_bits(&self.latched_registers);
+
         let (h_scroll_a, h_scroll_b) = read_h_scroll(
-            &self.vram,
+            vram_bank(&self.vram, self.vram_expansion.as_deref(), vram_a16.h_scroll_table),
             self.latched_registers.h_scroll_table_base_addr,
             self.latched_registers.horizontal_scroll_mode,
             // Only the lowest 8 bits of raster line are used for H scroll lookups
@@ -336,6 +340,24 @@ impl Vdp {
                     h_scroll_b,
                 ),
             };
+            let (nametable_vram, pattern_vram) = match plane {
+                BgPlane::A => (
+                    vram_bank(&self.vram, self.vram_expansion.as_deref(), vram_a16.scroll_a_nt),
+                    vram_bank(
+                        &self.vram,
+                        self.vram_expansion.as_deref(),
+                        vram_a16.scroll_a_pattern,
+                    ),
+                ),
+                BgPlane::B => (
+                    vram_bank(&self.vram, self.vram_expansion.as_deref(), vram_a16.scroll_b_nt),
+                    vram_bank(
+                        &self.vram,
+                        self.vram_expansion.as_deref(),
+                        vram_a16.scroll_b_pattern,
+                    ),
+                ),
+            };
 
             if !enabled {
                 pixel_buffer.fill(TilePixel::default());
@@ -382,7 +404,7 @@ impl Vdp {
                         & (h_scroll_size_cells - 1);
 
                     let nametable_word = read_name_table_word(
-                        &self.vram,
+                        nametable_vram,
                         nametable_base_addr,
                         h_scroll_size.into(),
                         scrolled_v_cell,
@@ -394,13 +416,16 @@ impl Vdp {
                         self.state.last_scroll_b_palettes[1] = nametable_word.palette;
                     }
 
-                    let colors = read_pattern_generator_row(&self.vram, PatternGeneratorRowArgs {
-                        vertical_flip: nametable_word.vertical_flip,
-                        horizontal_flip: nametable_word.horizontal_flip,
-                        pattern_generator: nametable_word.pattern_generator,
-                        row: scrolled_scanline,
-                        cell_height_shift,
-                    });
+                    let colors = read_pattern_generator_row(
+                        pattern_vram,
+                        PatternGeneratorRowArgs {
+                            vertical_flip: nametable_word.vertical_flip,
+                            horizontal_flip: nametable_word.horizontal_flip,
+                            pattern_generator: nametable_word.pattern_generator,
+                            row: scrolled_scanline,
+                            cell_height_shift,
+                        },
+                    );
 
                     for pixel_offset in 0..8 {
                         let fb_col = cell_fb_col + pixel_offset;
@@ -444,6 +469,13 @@ impl Vdp {
             return;
         }
 
+        // The window uses the same pattern generator base address as scroll A
+        let vram_a16 = self.vram_a16_bits(&self.latched_registers);
+        let nametable_vram =
+            vram_bank(&self.vram, self.vram_expansion.as_deref(), vram_a16.window_nt);
+        let pattern_vram =
+            vram_bank(&self.vram, self.vram_expansion.as_deref(), vram_a16.scroll_a_pattern);
+
         let window_start_cell = window_start / 8;
         let window_end_cell = window_end / 8;
         for h_cell in window_start_cell..window_end_cell {
@@ -457,20 +489,23 @@ impl Vdp {
             let v_cell = raster_line >> cell_height_shift;
 
             let nametable_word = read_name_table_word(
-                &self.vram,
+                nametable_vram,
                 self.latched_registers.window_base_nt_addr,
                 self.latched_registers.horizontal_display_size.window_width_cells(),
                 v_cell,
                 h_cell,
             );
 
-            let colors = read_pattern_generator_row(&self.vram, PatternGeneratorRowArgs {
-                vertical_flip: nametable_word.vertical_flip,
-                horizontal_flip: nametable_word.horizontal_flip,
-                pattern_generator: nametable_word.pattern_generator,
-                row: raster_line,
-                cell_height_shift,
-            });
+            let colors = read_pattern_generator_row(
+                pattern_vram,
+                PatternGeneratorRowArgs {
+                    vertical_flip: nametable_word.vertical_flip,
+                    horizontal_flip: nametable_word.horizontal_flip,
+                    pattern_generator: nametable_word.pattern_generator,
+                    row: raster_line,
+                    cell_height_shift,
+                },
+            );
 
             for pixel_offset in 0..8 {
                 let fb_col = cell_fb_col + pixel_offset;
@@ -521,8 +556,10 @@ impl Vdp {
             let scroll_a_pixel = self.bg_buffers.plane_a_pixels[frame_buffer_col as usize];
             let scroll_b_pixel = self.bg_buffers.plane_b_pixels[frame_buffer_col as usize];
 
-            let (pixel_color, color_modifier) =
-                determine_pixel_color(&self.cram, self.debug_register, PixelColorArgs {
+            let (pixel_color, color_modifier) = determine_pixel_color(
+                &self.cram,
+                self.debug_register,
+                PixelColorArgs {
                     sprite_pixel,
                     scroll_a_pixel,
                     scroll_b_pixel,
@@ -530,7 +567,8 @@ impl Vdp {
                     shadow_highlight_flag: self.latched_registers.shadow_highlight_flag,
                     in_h_border: !(0..active_display_pixels as i16).contains(&pixel),
                     in_v_border: raster_line.in_v_border && !self.state.v_border_forgotten,
-                });
+                },
+            );
 
             set_in_frame_buffer(
                 &mut self.frame_buffer,
@@ -1,6 +1,6 @@
 use crate::vdp::registers::{HorizontalDisplaySize, InterlacingMode};
 use crate::vdp::render::{PatternGeneratorRowArgs, RasterLine, read_pattern_generator_row};
-use crate::vdp::{CachedSpriteData, SpriteData, TilePixel, Vdp};
+use crate::vdp::{CachedSpriteData, SpriteData, TilePixel, Vdp, vram_bank};
 use bincode::{Decode, Encode};
 
 // Sprites with X = $080 display at the left edge of the screen
@@ -193,6 +193,8 @@ impl Vdp {
     }
 
     fn do_sprite_attribute_fetch(&mut self, use_interlaced_buffers: bool) {
+        let sprite_table_a16 = self.vram_a16_bits(&self.registers).sprite_attribute_table;
+
         let buffers = if use_interlaced_buffers {
             &mut self.interlaced_sprite_buffers
         } else {
@@ -202,12 +204,14 @@ impl Vdp {
         buffers.sprites.clear();
 
         let sprite_table_addr = self.registers.masked_sprite_attribute_table_addr();
+        let sprite_table_vram =
+            vram_bank(&self.vram, self.vram_expansion.as_deref(), sprite_table_a16);
 
         for &sprite_idx in &buffers.scanned_ids {
             let sprite_addr = sprite_table_addr.wrapping_add(8 * u16::from(sprite_idx)) as usize;
             let sprite = SpriteData::create(
                 self.cached_sprite_attributes[sprite_idx as usize],
-                &self.vram[sprite_addr + 4..sprite_addr + 8],
+                &sprite_table_vram[sprite_addr + 4..sprite_addr + 8],
             );
             buffers.sprites.push(sprite);
         }
@@ -223,6 +227,9 @@ impl Vdp {
         raster_line: RasterLine,
         use_interlaced_buffers: bool,
     ) {
+        let pattern_a16 = self.vram_a16_bits(&self.latched_registers).sprite_pattern;
+        let pattern_vram = vram_bank(&self.vram, self.vram_expansion.as_deref(), pattern_a16);
+
         let buffers = if use_interlaced_buffers {
             &mut self.interlaced_sprite_buffers
         } else {
@@ -262,12 +269,16 @@ impl Vdp {
         // Sprites with H position 0 mask all lower priority sprites on the same scanline...with
         // some quirks. There must be at least one sprite with H != 0 before the H=0 sprite, unless
         // there was a sprite pixel overflow on the previous scanline.
+        //
+        // Masked sprites are not displayed, but their tiles are still fetched and still count
+        // towards the sprite pixel limit, which can in turn enable masking on the next line
         let mut found_non_zero = self.sprite_state.dot_overflow_on_prev_line;
+        let mut masked = false;
 
         'outer: for sprite in &buffers.sprites {
             if sprite.h_position == 0 && found_non_zero {
                 // Sprite masking from H=0 sprite; no more sprites will display on this line
-                break;
+                masked = true;
             } else if sprite.h_position != 0 {
                 found_non_zero = true;
             }
@@ -319,6 +330,10 @@ impl Vdp {
                     tiles_fetched += 1;
                 }
 
+                if masked {
+                    continue;
+                }
+
                 let cell_left = sprite.h_position + 8 * h_cell;
                 let cell_right = cell_left + 8;
                 if cell_left >= sprite_display_area.end || cell_right <= sprite_display_area.start {
@@ -326,13 +341,16 @@ impl Vdp {
                     continue;
                 }
 
-                let colors = read_pattern_generator_row(&self.vram, PatternGeneratorRowArgs {
-                    vertical_flip: false,
-                    horizontal_flip: sprite.horizontal_flip,
-                    pattern_generator,
-                    row: cell_row,
-                    cell_height_shift,
-                });
+                let colors = read_pattern_generator_row(
+                    pattern_vram,
+                    PatternGeneratorRowArgs {
+                        vertical_flip: false,
+                        horizontal_flip: sprite.horizontal_flip,
+                        pattern_generator,
+                        row: cell_row,
+                        cell_height_shift,
+                    },
+                );
 
                 let cell_h_position = sprite.h_position + 8 * h_cell;
                 for pixel_offset in 0..8 {
@@ -359,7 +377,10 @@ impl Vdp {
             }
         }
 
-        self.sprite_state.dot_overflow_on_prev_line = dot_overflow;
+        // Exactly reaching the sprite pixel limit is enough to enable masking on the next line,
+        // even though the overflow status flag is only set if the limit was exceeded
+        self.sprite_state.dot_overflow_on_prev_line =
+            dot_overflow || (tiles_fetched != 0 && tiles_fetched == max_sprite_tiles_per_line);
     }
 }
 
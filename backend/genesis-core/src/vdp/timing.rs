@@ -71,9 +71,23 @@ impl LineType {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct FifoWrite {
+    pub location: DataPortLocation,
+    pub address: u32,
+    pub value: u16,
+}
+
+#[derive(Debug, Clone, Copy, Encode, Decode)]
+struct FifoEntry {
+    slots_required: u8,
+    write: Option<FifoWrite>,
+}
+
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct FifoTracker {
-    slots_required_fifo: VecDeque<u8>,
+    fifo: VecDeque<FifoEntry>,
+    completed_writes: VecDeque<FifoWrite>,
     last_scanline: u16,
     last_slot_index: u8,
 }
@@ -81,7 +95,8 @@ pub struct FifoTracker {
 impl FifoTracker {
     pub fn new() -> Self {
         Self {
-            slots_required_fifo: VecDeque::with_capacity(FIFO_CAPACITY + 1),
+            fifo: VecDeque::with_capacity(FIFO_CAPACITY + 1),
+            completed_writes: VecDeque::with_capacity(FIFO_CAPACITY + 1),
             last_scanline: 0,
             last_slot_index: u8::MAX,
         }
@@ -92,9 +107,11 @@ impl FifoTracker {
         line_type: LineType,
         data_port_location: DataPortLocation,
         vram_size: VramSizeKb,
+        write: Option<FifoWrite>,
     ) {
-        // VRAM/CRAM/VSRAM accesses can only delay the CPU during active display
+        // VRAM/CRAM/VSRAM accesses can only be delayed during active display
         if line_type == LineType::Blanked {
+            self.completed_writes.extend(write);
             return;
         }
 
@@ -103,11 +120,22 @@ impl FifoTracker {
             (DataPortLocation::Vram, VramSizeKb::OneTwentyEight)
             | (DataPortLocation::Cram | DataPortLocation::Vsram, _) => 1,
         };
-        self.slots_required_fifo.push_back(slots_required);
+        self.fifo.push_back(FifoEntry { slots_required, write });
 
         log::trace!("FIFO access recorded; current state {self:?}");
     }
 
+    /// Immediately complete all pending FIFO entries, e.g. because the VDP must wait for the FIFO
+    /// to drain before servicing a read or starting a DMA.
+    pub fn flush(&mut self) {
+        self.completed_writes.extend(self.fifo.drain(..).filter_map(|entry| entry.write));
+    }
+
+    /// Writes that have reached an access slot and should now be applied to VRAM/CRAM/VSRAM.
+    pub fn pop_completed_write(&mut self) -> Option<FifoWrite> {
+        self.completed_writes.pop_front()
+    }
+
     pub fn advance_to_pixel(
         &mut self,
         scanline: u16,
@@ -117,14 +145,14 @@ impl FifoTracker {
     ) {
         let slot_pixels = h_display_size.fifo_slot_pixels();
 
-        if self.slots_required_fifo.is_empty() {
+        if self.fifo.is_empty() {
             self.skip_to_pixel(scanline, pixel, slot_pixels);
             return;
         }
 
         if line_type == LineType::Blanked {
             // CPU never gets delayed during VBlank or when the display is off
-            self.slots_required_fifo.clear();
+            self.flush();
             self.skip_to_pixel(scanline, pixel, slot_pixels);
             return;
         }
@@ -160,24 +188,25 @@ impl FifoTracker {
     }
 
     fn pop_slot(&mut self) {
-        let Some(front) = self.slots_required_fifo.front_mut() else { return };
-        if *front == 1 {
-            self.slots_required_fifo.pop_front();
+        let Some(front) = self.fifo.front_mut() else { return };
+        if front.slots_required == 1 {
+            let entry = self.fifo.pop_front().unwrap();
+            self.completed_writes.extend(entry.write);
         } else {
-            *front -= 1;
+            front.slots_required -= 1;
         }
     }
 
     pub fn is_empty(&self) -> bool {
-        self.slots_required_fifo.is_empty()
+        self.fifo.is_empty()
     }
 
     pub fn is_full(&self) -> bool {
-        self.slots_required_fifo.len() >= FIFO_CAPACITY
+        self.fifo.len() >= FIFO_CAPACITY
     }
 
     pub fn should_halt_cpu(&self) -> bool {
-        self.slots_required_fifo.len() > FIFO_CAPACITY
+        self.fifo.len() > FIFO_CAPACITY
     }
 }
 
@@ -15,6 +15,7 @@ use crate::ym2612::phase::PhaseGenerator;
 use crate::ym2612::timer::{TimerA, TimerB, TimerTickEffect};
 use bincode::{Decode, Encode};
 use jgenesis_common::num::{GetBit, U16Ext};
+use jgenesis_proc_macros::SaveState;
 use std::array;
 use std::sync::LazyLock;
 
@@ -352,7 +353,7 @@ enum RegisterGroup {
     Two,
 }
 
-#[derive(Debug, Clone, Encode, Decode)]
+#[derive(Debug, Clone, SaveState)]
 pub struct Ym2612 {
     channels: [FmChannel; 6],
     pcm_enabled: bool,
@@ -368,11 +369,18 @@ pub struct Ym2612 {
     csm_enabled: bool,
     quantize_output: bool,
     emulate_ladder_effect: bool,
+    // Debug-only setting controlled by the frontend, not part of emulated state
+    #[savestate(skip)]
+    muted_channels: [bool; 6],
 }
 
 impl Ym2612 {
     #[must_use]
     pub fn new(config: GenesisEmulatorConfig) -> Self {
+        Self::with_settings(config.quantize_ym2612_output, config.emulate_ym2612_ladder_effect)
+    }
+
+    fn with_settings(quantize_output: bool, emulate_ladder_effect: bool) -> Self {
         Self {
             channels: array::from_fn(|_| FmChannel::default()),
             pcm_enabled: false,
@@ -386,8 +394,9 @@ impl Ym2612 {
             timer_a: TimerA::new(),
             timer_b: TimerB::new(),
             csm_enabled: false,
-            quantize_output: config.quantize_ym2612_output,
-            emulate_ladder_effect: config.emulate_ym2612_ladder_effect,
+            quantize_output,
+            emulate_ladder_effect,
+            muted_channels: [false; 6],
         }
     }
 
@@ -395,6 +404,14 @@ impl Ym2612 {
         *self = Self::new(config);
     }
 
+    /// Reset all chip state as if the /IC pin was asserted, which on the Genesis happens whenever
+    /// the Z80 is reset. Settings and muted channels are retained.
+    pub fn reset_chip(&mut self) {
+        let muted_channels = self.muted_channels;
+        *self = Self::with_settings(self.quantize_output, self.emulate_ladder_effect);
+        self.muted_channels = muted_channels;
+    }
+
     // Set the address register and set group to 1 (system registers + channels 1-3)
     pub fn write_address_1(&mut self, value: u8) {
         self.selected_register = value;
@@ -584,6 +601,10 @@ impl Ym2612 {
         let mut sum_l = 0;
         let mut sum_r = 0;
         for (i, channel) in self.channels.iter().enumerate() {
+            if self.muted_channels[i] {
+                continue;
+            }
+
             let (mut sample_l, mut sample_r) = if i == 5 && self.pcm_enabled {
                 // Channel 6 is in DAC mode; play PCM sample instead of FM output
                 // Convert unsigned 8-bit sample to a signed 14-bit sample
@@ -830,4 +851,19 @@ impl Ym2612 {
         self.quantize_output = config.quantize_ym2612_output;
         self.emulate_ladder_effect = config.emulate_ym2612_ladder_effect;
     }
+
+    /// Mute or unmute an individual channel (0-5) in the mixed output. Muted channels are still
+    /// emulated, they just do not contribute to [`sample`](Self::sample).
+    pub fn set_channel_muted(&mut self, channel: usize, muted: bool) {
+        self.muted_channels[channel] = muted;
+    }
+
+    #[must_use]
+    pub fn is_channel_muted(&self, channel: usize) -> bool {
+        self.muted_channels[channel]
+    }
+
+    pub fn clear_muted_channels(&mut self) {
+        self.muted_channels = [false; 6];
+    }
 }
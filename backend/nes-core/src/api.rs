@@ -10,9 +10,12 @@ use crate::{apu, audio, cpu, graphics, ppu};
 use bincode::{Decode, Encode};
 use jgenesis_common::frontend::{
     AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, FrameSize, PixelAspectRatio, Renderer,
-    SaveWriter, TickEffect, TickResult, TimingMode,
+    SaveStateSafePoint, SaveWriter, TickEffect, TickResult, TimingMode,
 };
-use jgenesis_proc_macros::{ConfigDisplay, EnumAll, EnumDisplay, PartialClone};
+use jgenesis_common::instrument;
+use jgenesis_common::instrumentation::Subsystem;
+use jgenesis_common::memmap::MemoryMap;
+use jgenesis_proc_macros::{ConfigDisplay, EnumAll, EnumDisplay, PartialClone, SaveState};
 use std::fmt::{Debug, Display, Formatter};
 use std::mem;
 use thiserror::Error;
@@ -71,6 +74,14 @@ impl Display for Overscan {
     }
 }
 
+/// Memory regions that can be dumped and restored for debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumAll)]
+pub enum MemoryRegion {
+    WorkRam,
+    Vram,
+    PrgRam,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, ConfigDisplay)]
 pub struct NesEmulatorConfig {
     /// Force timing mode to NTSC/PAL if set
@@ -95,7 +106,16 @@ pub struct NesEmulatorConfig {
     pub allow_opposing_joypad_inputs: bool,
 }
 
-impl EmulatorConfigTrait for NesEmulatorConfig {}
+impl EmulatorConfigTrait for NesEmulatorConfig {
+    fn with_enhancements_disabled(&self) -> Self {
+        Self {
+            remove_sprite_limit: false,
+            silence_ultrasonic_triangle_output: false,
+            allow_opposing_joypad_inputs: false,
+            ..*self
+        }
+    }
+}
 
 #[derive(Debug, Error)]
 pub enum NesError<RErr, AErr, SErr> {
@@ -113,7 +133,7 @@ pub enum NesInitializationError {
     CartridgeLoad(#[from] CartridgeFileError),
 }
 
-#[derive(Debug, Clone, Encode, Decode, PartialClone)]
+#[derive(Debug, Clone, SaveState, PartialClone)]
 pub struct NesEmulator {
     #[partial_clone(partial)]
     bus: Bus,
@@ -121,10 +141,13 @@ pub struct NesEmulator {
     ppu_state: PpuState,
     apu_state: ApuState,
     config: NesEmulatorConfig,
+    // Fully overwritten from the PPU frame buffer every time a frame is rendered
+    #[savestate(skip, default = new_rgba_frame_buffer())]
     rgba_frame_buffer: Vec<Color>,
     audio_resampler: AudioResampler,
-    // Kept around to enable hard reset
+    // Kept around to enable hard reset; restored in take_rom_from() after loading a state
     #[partial_clone(default)]
+    #[savestate(skip)]
     raw_rom_bytes: Vec<u8>,
 }
 
@@ -165,18 +188,33 @@ impl NesEmulator {
     }
 
     fn ntsc_tick(&mut self) {
-        cpu::tick(&mut self.cpu_state, &mut self.bus.cpu(), self.apu_state.is_active_cycle());
-        apu::tick(&mut self.apu_state, &mut self.bus.cpu(), self.config);
-        ppu::tick(&mut self.ppu_state, &mut self.bus.ppu(), self.config);
+        instrument!(
+            Subsystem::Cpu,
+            cpu::tick(&mut self.cpu_state, &mut self.bus.cpu(), self.apu_state.is_active_cycle())
+        );
+        instrument!(
+            Subsystem::Apu,
+            apu::tick(&mut self.apu_state, &mut self.bus.cpu(), self.config)
+        );
+        instrument!(
+            Subsystem::Ppu,
+            ppu::tick(&mut self.ppu_state, &mut self.bus.ppu(), self.config)
+        );
         self.bus.tick_cpu();
         self.bus.tick();
 
         self.bus.poll_interrupt_lines();
 
-        ppu::tick(&mut self.ppu_state, &mut self.bus.ppu(), self.config);
+        instrument!(
+            Subsystem::Ppu,
+            ppu::tick(&mut self.ppu_state, &mut self.bus.ppu(), self.config)
+        );
         self.bus.tick();
 
-        ppu::tick(&mut self.ppu_state, &mut self.bus.ppu(), self.config);
+        instrument!(
+            Subsystem::Ppu,
+            ppu::tick(&mut self.ppu_state, &mut self.bus.ppu(), self.config)
+        );
         self.bus.tick();
 
         self.push_audio_sample();
@@ -184,9 +222,18 @@ impl NesEmulator {
 
     fn pal_tick(&mut self) {
         // Both CPU and PPU tick on the first master clock cycle
-        cpu::tick(&mut self.cpu_state, &mut self.bus.cpu(), self.apu_state.is_active_cycle());
-        apu::tick(&mut self.apu_state, &mut self.bus.cpu(), self.config);
-        ppu::tick(&mut self.ppu_state, &mut self.bus.ppu(), self.config);
+        instrument!(
+            Subsystem::Cpu,
+            cpu::tick(&mut self.cpu_state, &mut self.bus.cpu(), self.apu_state.is_active_cycle())
+        );
+        instrument!(
+            Subsystem::Apu,
+            apu::tick(&mut self.apu_state, &mut self.bus.cpu(), self.config)
+        );
+        instrument!(
+            Subsystem::Ppu,
+            ppu::tick(&mut self.ppu_state, &mut self.bus.ppu(), self.config)
+        );
         self.bus.tick_cpu();
         self.bus.tick();
 
@@ -196,12 +243,18 @@ impl NesEmulator {
 
         for i in 1..PAL_MASTER_CLOCK_TICKS {
             if i % PAL_CPU_DIVIDER == 0 {
-                cpu::tick(
-                    &mut self.cpu_state,
-                    &mut self.bus.cpu(),
-                    self.apu_state.is_active_cycle(),
+                instrument!(
+                    Subsystem::Cpu,
+                    cpu::tick(
+                        &mut self.cpu_state,
+                        &mut self.bus.cpu(),
+                        self.apu_state.is_active_cycle(),
+                    )
+                );
+                instrument!(
+                    Subsystem::Apu,
+                    apu::tick(&mut self.apu_state, &mut self.bus.cpu(), self.config)
                 );
-                apu::tick(&mut self.apu_state, &mut self.bus.cpu(), self.config);
                 self.bus.tick_cpu();
                 self.bus.tick();
 
@@ -209,13 +262,18 @@ impl NesEmulator {
 
                 self.push_audio_sample();
             } else if i % PAL_PPU_DIVIDER == 0 {
-                ppu::tick(&mut self.ppu_state, &mut self.bus.ppu(), self.config);
+                instrument!(
+                    Subsystem::Ppu,
+                    ppu::tick(&mut self.ppu_state, &mut self.bus.ppu(), self.config)
+                );
                 self.bus.tick();
             }
         }
     }
 
     fn render_frame<R: Renderer>(&mut self, renderer: &mut R) -> Result<(), R::Err> {
+        instrument!("nes::render_frame");
+
         let overscan = self.config.overscan;
         let timing_mode = self.bus.mapper().timing_mode();
         graphics::ppu_frame_buffer_to_rgba(
@@ -268,6 +326,41 @@ impl NesEmulator {
         graphics::copy_palette_ram(&self.bus.ppu(), out);
     }
 
+    /// Raw palette RAM contents, 6-bit NES color indices.
+    #[must_use]
+    pub fn dump_palette_ram(&mut self) -> Vec<u8> {
+        graphics::dump_palette_ram(&self.bus.ppu())
+    }
+
+    /// Describe the CPU memory map, including the mapper's current PRG ROM and PRG RAM banks.
+    #[must_use]
+    pub fn memory_map_description(&self) -> Vec<MemoryMap> {
+        vec![self.bus.memory_map()]
+    }
+
+    #[must_use]
+    pub fn dump_memory(&self, region: MemoryRegion) -> Vec<u8> {
+        match region {
+            MemoryRegion::WorkRam => self.bus.cpu_internal_ram().to_vec(),
+            MemoryRegion::Vram => self.bus.ppu_vram().to_vec(),
+            MemoryRegion::PrgRam => self.bus.mapper().cartridge_prg_ram().to_vec(),
+        }
+    }
+
+    /// Overwrite a memory region, e.g. with the contents of a file previously written using
+    /// [`Self::dump_memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not the same length as the memory region.
+    pub fn restore_memory(&mut self, region: MemoryRegion, bytes: &[u8]) {
+        match region {
+            MemoryRegion::WorkRam => self.bus.cpu_internal_ram_mut().copy_from_slice(bytes),
+            MemoryRegion::Vram => self.bus.ppu_vram_mut().copy_from_slice(bytes),
+            MemoryRegion::PrgRam => self.bus.mapper_mut().restore_prg_ram(bytes),
+        }
+    }
+
     #[inline]
     pub fn using_double_height_sprites(&mut self) -> bool {
         self.bus.ppu().get_ppu_registers().double_height_sprites()
@@ -379,6 +472,12 @@ impl EmulatorTrait for NesEmulator {
             .expect("Creation during hard reset should never fail");
     }
 
+    fn save_state_safe_point() -> SaveStateSafePoint {
+        // The PPU frame buffer is saved along with the rest of the state; only the RGBA copy that is
+        // fully rewritten at the end of each frame is skipped
+        SaveStateSafePoint::Tick
+    }
+
     fn target_fps(&self) -> f64 {
         let timing_mode = self.bus.mapper().timing_mode();
         match (timing_mode, self.config.audio_refresh_rate_adjustment) {
@@ -405,3 +504,65 @@ fn init_apu(apu_state: &mut ApuState, bus: &mut Bus, config: NesEmulatorConfig)
         bus.tick();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jgenesis_common::frontend::NullSaveWriter;
+    use jgenesis_common::savestate;
+
+    // NROM cartridge that enables rendering and NMIs, then loops forever while the NMI handler
+    // increments a counter in RAM and writes it to the backdrop palette entry
+    fn test_rom() -> Vec<u8> {
+        let main: &[u8] = &[
+            0x78, // SEI
+            0xD8, // CLD
+            0xA9, 0x80, 0x8D, 0x00, 0x20, // PPUCTRL = $80
+            0xA9, 0x1E, 0x8D, 0x01, 0x20, // PPUMASK = $1E
+            0x4C, 0x0C, 0x80, // JMP $800C
+        ];
+
+        let nmi_handler: &[u8] = &[
+            0xE6, 0x00, // INC $00
+            0x2C, 0x02, 0x20, // BIT PPUSTATUS
+            0xA2, 0x3F, 0x8E, 0x06, 0x20, 0xA2, 0x00, 0x8E, 0x06, 0x20, // PPUADDR = $3F00
+            0xA5, 0x00, 0x29, 0x3F, 0x8D, 0x07, 0x20, // PPUDATA = $00 & $3F
+            0x8E, 0x06, 0x20, 0x8E, 0x06, 0x20, // PPUADDR = $0000
+            0x40, // RTI
+        ];
+
+        // NMI, reset, and IRQ vectors
+        let vectors: &[u8] = &[0x10, 0x80, 0x00, 0x80, 0x10, 0x80];
+
+        let prg_rom = savestate::test_rom(
+            16 * 1024,
+            &[(0x0000, main), (0x0010, nmi_handler), (0x3FFA, vectors)],
+        );
+
+        let mut rom = b"NES\x1A\x01\x01".to_vec();
+        rom.resize(16, 0);
+        rom.extend(prg_rom);
+        rom.extend([0; 8 * 1024]);
+        rom
+    }
+
+    #[test]
+    fn save_state_round_trip() {
+        let config = NesEmulatorConfig {
+            forced_timing_mode: None,
+            aspect_ratio: NesAspectRatio::default(),
+            overscan: Overscan::default(),
+            remove_sprite_limit: false,
+            pal_black_border: false,
+            silence_ultrasonic_triangle_output: false,
+            audio_refresh_rate_adjustment: false,
+            allow_opposing_joypad_inputs: false,
+        };
+
+        savestate::assert_round_trip_deterministic(
+            || NesEmulator::create(test_rom(), config, &mut NullSaveWriter).unwrap(),
+            300_000,
+            7_919,
+        );
+    }
+}
@@ -41,6 +41,7 @@ use crate::graphics::TimingModeGraphicsExt;
 use crate::input::{LatchedJoypadState, NesInputDevice, NesJoypadState, ZapperState};
 use bincode::{Decode, Encode};
 use jgenesis_common::frontend::TimingMode;
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
 use jgenesis_common::num::GetBit;
 use jgenesis_proc_macros::PartialClone;
 use mos6502_emu::bus::BusInterface;
@@ -824,6 +825,37 @@ impl Bus {
         self.interrupt_lines.tick();
     }
 
+    /// Describe the CPU address space, including which PRG ROM and PRG RAM banks the mapper
+    /// currently has mapped.
+    pub(crate) fn memory_map(&self) -> MemoryMap {
+        let mut map = MemoryMap::new("6502", 16);
+        map.add(0x0000, 0x07FF, RegionKind::Ram, "Internal RAM")
+            .add_mirror(0x0800, 0x1FFF, RegionKind::Ram, "Internal RAM", 0x0000, 0x07FF)
+            .add(0x2000, 0x2007, RegionKind::Registers, "PPU registers")
+            .add_mirror(0x2008, 0x3FFF, RegionKind::Registers, "PPU registers", 0x2000, 0x2007)
+            .add(0x4000, 0x401F, RegionKind::Registers, "APU / I/O registers");
+        self.mapper.describe_memory_map(&mut map);
+        map.fill_unmapped();
+
+        map
+    }
+
+    pub(crate) fn cpu_internal_ram(&self) -> &[u8; 2048] {
+        &self.cpu_internal_ram
+    }
+
+    pub(crate) fn cpu_internal_ram_mut(&mut self) -> &mut [u8; 2048] {
+        &mut self.cpu_internal_ram
+    }
+
+    pub(crate) fn ppu_vram(&self) -> &[u8; 2048] {
+        &self.ppu_vram
+    }
+
+    pub(crate) fn ppu_vram_mut(&mut self) -> &mut [u8; 2048] {
+        &mut self.ppu_vram
+    }
+
     pub(crate) fn mapper(&self) -> &Mapper {
         &self.mapper
     }
@@ -1145,4 +1177,46 @@ mod tests {
 
         assert_ne!(bus1.cpu_internal_ram, bus2.cpu_internal_ram);
     }
+
+    #[test]
+    fn memory_map_merges_contiguous_prg_banks() {
+        let mapper = cartridge::new_mmc1(vec![0; 32768]);
+        let bus = Bus::from_cartridge(mapper, Overscan::default());
+
+        let map = bus.memory_map();
+        assert!(map.is_complete());
+
+        let prg_rom = map.entry_at(0x8000).unwrap();
+        assert_eq!((prg_rom.start, prg_rom.end), (0x8000, 0xFFFF));
+        assert_eq!(prg_rom.detail.as_deref(), Some("$000000-$007FFF"));
+
+        let prg_ram = map.entry_at(0x6000).unwrap();
+        assert_eq!((prg_ram.start, prg_ram.end, prg_ram.name), (0x6000, 0x7FFF, "PRG RAM"));
+    }
+
+    #[test]
+    fn memory_map_masks_non_power_of_two_prg_rom() {
+        // 24KB PRG ROM where every byte holds the number of the 8KB block that it's in
+        let prg_rom = (0..0x6000).map(|i| (i / 0x2000) as u8).collect();
+        let mut bus = Bus::from_cartridge(cartridge::new_mmc1(prg_rom), Overscan::default());
+
+        // Switch PRG bank 2 into $8000-$BFFF, which is past the end of PRG ROM
+        for i in 0..5 {
+            bus.mapper.write_cpu_address(0xE000, (2 >> i) & 1);
+            bus.mapper.tick_cpu();
+            bus.mapper.tick_cpu();
+        }
+
+        // Offsets $8000-$BFFF are masked with $5FFF, so both halves of the bank map to block 0
+        let map = bus.memory_map();
+        for start in [0x8000, 0xA000] {
+            let prg_rom = map.entry_at(start).unwrap();
+            assert_eq!((prg_rom.start, prg_rom.end), (start, start + 0x1FFF));
+            assert_eq!(prg_rom.detail.as_deref(), Some("$000000-$001FFF"));
+        }
+
+        // The memory map should describe the bytes that are actually read
+        assert_eq!(bus.mapper.read_cpu_address(0x8000), 0);
+        assert_eq!(bus.mapper.read_cpu_address(0xA000), 0);
+    }
 }
@@ -5,7 +5,9 @@ use bincode::enc::Encoder;
 use bincode::error::{DecodeError, EncodeError};
 use bincode::{BorrowDecode, Decode, Encode};
 use jgenesis_common::frontend::{PartialClone, TimingMode};
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
 use jgenesis_common::num::GetBit;
+use jgenesis_common::sram;
 use jgenesis_proc_macros::MatchEachVariantMacro;
 use std::fmt::{Display, Formatter};
 use std::{io, mem};
@@ -23,7 +25,9 @@ use crate::bus::cartridge::mappers::namco175::Namco175;
 use crate::bus::cartridge::mappers::nrom::{Axrom, Bnrom, Cnrom, Gxrom, Nrom, Uxrom};
 use crate::bus::cartridge::mappers::sunsoft::Sunsoft;
 use crate::bus::cartridge::mappers::unrom512::Unrom512;
-use crate::bus::cartridge::mappers::{ChrType, NametableMirroring, PpuMapResult, unrom512};
+use crate::bus::cartridge::mappers::{
+    ChrType, CpuMapResult, NametableMirroring, PpuMapResult, unrom512,
+};
 #[cfg(test)]
 pub(crate) use mappers::new_mmc1;
 
@@ -167,6 +171,22 @@ where
     }
 }
 
+struct PrgRegion {
+    start: u32,
+    end: u32,
+    kind: RegionKind,
+    offset_start: u32,
+    offset_end: u32,
+}
+
+impl PrgRegion {
+    fn add_to(self, map: &mut MemoryMap) {
+        let name = if self.kind == RegionKind::Rom { "PRG ROM" } else { "PRG RAM" };
+        let detail = format!("${:06X}-${:06X}", self.offset_start, self.offset_end);
+        map.add_mapped(self.start, self.end, self.kind, name, detail);
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Encode, Decode, PartialClone, MatchEachVariantMacro)]
 pub(crate) enum Mapper {
@@ -222,6 +242,72 @@ impl Mapper {
         match_each_variant!(self, mapper => mapper.read_cpu_address(address))
     }
 
+    /// Map an address in the CPU address space to PRG ROM or PRG RAM without any side effects.
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
+        match_each_variant!(self, mapper => mapper.map_cpu_address(address))
+    }
+
+    /// Describe the cartridge's portion of the CPU address space, based on which PRG ROM and PRG
+    /// RAM banks are currently mapped.
+    pub(crate) fn describe_memory_map(&self, map: &mut MemoryMap) {
+        let (prg_rom_len, prg_ram_len) = match_each_variant!(self, mapper => (
+            mapper.cartridge.prg_rom.len() as u32,
+            mapper.cartridge.prg_ram.len() as u32,
+        ));
+
+        map.add_mapped(
+            0x4020,
+            0x5FFF,
+            RegionKind::Registers,
+            "Cartridge expansion area",
+            self.name(),
+        );
+
+        // PRG banks are never smaller than 8KB, so probe each 8KB window and merge windows that are
+        // contiguous in PRG ROM/RAM
+        let mut current: Option<PrgRegion> = None;
+        for window_start in (0x6000..=0xE000).step_by(0x2000) {
+            let (kind, offset) = match self.map_cpu_address(window_start) {
+                // Mask the same way that reads do
+                CpuMapResult::PrgROM(address) => (RegionKind::Rom, address & (prg_rom_len - 1)),
+                CpuMapResult::PrgRAM(address) if prg_ram_len != 0 => {
+                    (RegionKind::Ram, address & (prg_ram_len - 1))
+                }
+                CpuMapResult::PrgRAM(_) | CpuMapResult::None { .. } => {
+                    if let Some(region) = current.take() {
+                        region.add_to(map);
+                    }
+                    continue;
+                }
+            };
+
+            match &mut current {
+                Some(region) if region.kind == kind && region.offset_end + 1 == offset => {
+                    region.end += 0x2000;
+                    region.offset_end += 0x2000;
+                }
+                _ => {
+                    if let Some(region) = current.take() {
+                        region.add_to(map);
+                    }
+
+                    let start: u32 = window_start.into();
+                    current = Some(PrgRegion {
+                        start,
+                        end: start + 0x1FFF,
+                        kind,
+                        offset_start: offset,
+                        offset_end: offset + 0x1FFF,
+                    });
+                }
+            }
+        }
+
+        if let Some(region) = current {
+            region.add_to(map);
+        }
+    }
+
     /// Write a value to the given address in the CPU address space.
     pub(crate) fn write_cpu_address(&mut self, address: u16, value: u8) {
         match_each_variant!(self, mapper => mapper.write_cpu_address(address, value));
@@ -373,6 +459,23 @@ impl Mapper {
         match_each_variant!(self, mapper => &mapper.cartridge.prg_ram)
     }
 
+    /// Return the cartridge's PRG RAM, ignoring board-specific save memory such as EEPROM.
+    pub(crate) fn cartridge_prg_ram(&self) -> &[u8] {
+        match_each_variant!(self, mapper => &mapper.cartridge.prg_ram)
+    }
+
+    /// Overwrite the cartridge's PRG RAM and mark it dirty so that it will be persisted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not the same length as PRG RAM.
+    pub(crate) fn restore_prg_ram(&mut self, bytes: &[u8]) {
+        match_each_variant!(self, mapper => {
+            mapper.cartridge.prg_ram.copy_from_slice(bytes);
+            mapper.cartridge.prg_ram_dirty_bit = true;
+        });
+    }
+
     /// Retrieve the timing mode of the cartridge (NTSC/PAL).
     pub(crate) fn timing_mode(&self) -> TimingMode {
         match_each_variant!(self, mapper => mapper.cartridge.timing_mode)
@@ -642,15 +745,10 @@ pub(crate) fn from_ines_file(
         }
     }
 
-    let prg_ram = if let Some(sav_bytes) = &sav_bytes {
-        if sav_bytes.len() == header.prg_ram_size as usize {
-            sav_bytes.clone()
-        } else {
-            vec![0; header.prg_ram_size as usize]
-        }
-    } else {
-        vec![0; header.prg_ram_size as usize]
-    };
+    let prg_ram = sav_bytes
+        .clone()
+        .and_then(|sav_bytes| sram::normalize(sav_bytes, header.prg_ram_size as usize))
+        .unwrap_or_else(|| vec![0; header.prg_ram_size as usize]);
 
     let timing_mode = forced_timing_mode.unwrap_or(header.timing_mode);
     if timing_mode != header.timing_mode {
@@ -1,7 +1,7 @@
 //! Code for the unlicensed board used in Action 52 and Cheetahmen II (iNES mapper 228).
 
 use crate::bus;
-use crate::bus::cartridge::mappers::{BankSizeKb, NametableMirroring, PpuMapResult};
+use crate::bus::cartridge::mappers::{BankSizeKb, CpuMapResult, NametableMirroring, PpuMapResult};
 use crate::bus::cartridge::{HasBasicPpuMapping, MapperImpl};
 use bincode::{Decode, Encode};
 use jgenesis_common::num::GetBit;
@@ -55,11 +55,9 @@ impl MapperImpl<Action52> {
         self.data = Action52::new_with_mask(self.data.prg_bank_mask);
     }
 
-    pub fn read_cpu_address(&self, address: u16) -> u8 {
-        let open_bus = bus::cpu_open_bus(address);
-
+    pub fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         let prg_bank = match address {
-            0x0000..=0x7FFF => return open_bus,
+            0x0000..=0x7FFF => return CpuMapResult::None { original_address: address },
             0x8000..=0xBFFF => match self.data.prg_mode {
                 PrgMode::Zero => self.data.prg_bank & !1,
                 PrgMode::One => self.data.prg_bank,
@@ -71,8 +69,20 @@ impl MapperImpl<Action52> {
         };
 
         let full_prg_bank = (prg_bank & self.data.prg_bank_mask) | (self.data.selected_chip << 5);
-        let rom_addr = BankSizeKb::Sixteen.to_absolute_address(full_prg_bank, address);
-        self.cartridge.prg_rom.get(rom_addr as usize).copied().unwrap_or(open_bus)
+        CpuMapResult::PrgROM(BankSizeKb::Sixteen.to_absolute_address(full_prg_bank, address))
+    }
+
+    pub fn read_cpu_address(&self, address: u16) -> u8 {
+        match self.map_cpu_address(address) {
+            // Unpopulated chip selects read open bus rather than mirroring PRG ROM
+            CpuMapResult::PrgROM(rom_addr) => self
+                .cartridge
+                .prg_rom
+                .get(rom_addr as usize)
+                .copied()
+                .unwrap_or_else(|| bus::cpu_open_bus(address)),
+            map_result => map_result.read(&self.cartridge),
+        }
     }
 
     pub fn write_cpu_address(&mut self, address: u16, value: u8) {
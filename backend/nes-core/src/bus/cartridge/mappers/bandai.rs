@@ -4,7 +4,9 @@ mod eeprom;
 
 use crate::bus;
 use crate::bus::cartridge::mappers::bandai::eeprom::{X24C01Chip, X24C02Chip};
-use crate::bus::cartridge::mappers::{BankSizeKb, ChrType, NametableMirroring, PpuMapResult};
+use crate::bus::cartridge::mappers::{
+    BankSizeKb, ChrType, CpuMapResult, NametableMirroring, PpuMapResult,
+};
 use crate::bus::cartridge::{HasBasicPpuMapping, MapperImpl};
 use bincode::{Decode, Encode};
 use jgenesis_common::num::GetBit;
@@ -146,31 +148,23 @@ fn eeprom_read(address: u16, data: bool) -> u8 {
 }
 
 impl MapperImpl<BandaiFcg> {
-    pub(crate) fn read_cpu_address(&mut self, address: u16) -> u8 {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: {address:04X}"),
-            0x4020..=0x5FFF => bus::cpu_open_bus(address),
-            0x6000..=0x7FFF => match self.data.variant {
-                Variant::Fcg | Variant::Lz93D50(MemoryVariant::None) => bus::cpu_open_bus(address),
-                Variant::Lz93D50(MemoryVariant::RAM) => {
-                    if self.data.ram_enabled {
-                        self.cartridge.get_prg_ram((address & 0x1FFF).into())
-                    } else {
-                        bus::cpu_open_bus(address)
-                    }
+            0x4020..=0x5FFF => CpuMapResult::None { original_address: address },
+            0x6000..=0x7FFF => {
+                if self.data.variant == Variant::Lz93D50(MemoryVariant::RAM)
+                    && self.data.ram_enabled
+                {
+                    CpuMapResult::PrgRAM((address & 0x1FFF).into())
+                } else {
+                    CpuMapResult::None { original_address: address }
                 }
-                Variant::Lz93D50(MemoryVariant::X24C01 | MemoryVariant::X24C02)
-                | Variant::Unknown => match &self.data.eeprom {
-                    Some(Eeprom::X24C01(chip)) => eeprom_read(address, chip.handle_read()),
-                    Some(Eeprom::X24C02(chip)) => eeprom_read(address, chip.handle_read()),
-                    None => bus::cpu_open_bus(address),
-                },
-            },
+            }
             0x8000..=0xBFFF => {
                 let prg_rom_addr =
                     BankSizeKb::Sixteen.to_absolute_address(self.data.prg_bank, address);
-                self.cartridge
-                    .get_prg_rom(prg_rom_addr | (u32::from(self.data.prg_256kb_bank) << 18))
+                CpuMapResult::PrgROM(prg_rom_addr | (u32::from(self.data.prg_256kb_bank) << 18))
             }
             0xC000..=0xFFFF => {
                 // Gross, but necessary; the one game that uses LZ93D50 w/ SRAM has two 256KB "outer"
@@ -183,12 +177,25 @@ impl MapperImpl<BandaiFcg> {
                 };
                 let prg_rom_addr =
                     BankSizeKb::Sixteen.to_absolute_address_last_bank(prg_rom_len as u32, address);
-                self.cartridge
-                    .get_prg_rom(prg_rom_addr | (u32::from(self.data.prg_256kb_bank) << 18))
+                CpuMapResult::PrgROM(prg_rom_addr | (u32::from(self.data.prg_256kb_bank) << 18))
             }
         }
     }
 
+    pub(crate) fn read_cpu_address(&mut self, address: u16) -> u8 {
+        match (self.data.variant, address) {
+            (
+                Variant::Lz93D50(MemoryVariant::X24C01 | MemoryVariant::X24C02) | Variant::Unknown,
+                0x6000..=0x7FFF,
+            ) => match &self.data.eeprom {
+                Some(Eeprom::X24C01(chip)) => eeprom_read(address, chip.handle_read()),
+                Some(Eeprom::X24C02(chip)) => eeprom_read(address, chip.handle_read()),
+                None => bus::cpu_open_bus(address),
+            },
+            _ => self.map_cpu_address(address).read(&self.cartridge),
+        }
+    }
+
     pub(crate) fn write_cpu_address(&mut self, address: u16, value: u8) {
         log::trace!("Wrote address={address:04X}, value={value:02X}");
         match (self.data.variant, address) {
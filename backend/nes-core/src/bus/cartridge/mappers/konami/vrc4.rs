@@ -3,7 +3,7 @@
 use crate::bus;
 use crate::bus::cartridge::mappers::konami::irq::VrcIrqCounter;
 use crate::bus::cartridge::mappers::{
-    BankSizeKb, ChrType, NametableMirroring, PpuMapResult, konami,
+    BankSizeKb, ChrType, CpuMapResult, NametableMirroring, PpuMapResult, konami,
 };
 use crate::bus::cartridge::{HasBasicPpuMapping, MapperImpl};
 use bincode::{Decode, Encode};
@@ -168,33 +168,26 @@ impl Vrc4 {
 }
 
 impl MapperImpl<Vrc4> {
-    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match (self.data.variant.to_type(), address) {
             (_, 0x0000..=0x401F) => panic!("invalid CPU map address: {address:04X}"),
-            (_, 0x4020..=0x5FFF) => bus::cpu_open_bus(address),
+            (_, 0x4020..=0x5FFF) => CpuMapResult::None { original_address: address },
             (Type::Vrc2, 0x6000..=0x7FFF) => {
                 if !self.cartridge.prg_ram.is_empty() {
-                    self.cartridge.get_prg_ram((address & 0x1FFF).into())
+                    CpuMapResult::PrgRAM((address & 0x1FFF).into())
                 } else {
-                    let open_bus = bus::cpu_open_bus(address);
-                    if address < 0x7000 {
-                        (open_bus & 0xFE) | self.data.vrc2_ram_bit
-                    } else {
-                        open_bus
-                    }
+                    CpuMapResult::None { original_address: address }
                 }
             }
             (Type::Vrc4, 0x6000..=0x7FFF) => {
                 if self.data.ram_enabled {
                     match (self.cartridge.prg_ram.len(), address) {
-                        (8192, _) => self.cartridge.get_prg_ram((address & 0x1FFF).into()),
-                        (2048, 0x6000..=0x6FFF) => {
-                            self.cartridge.get_prg_ram((address & 0x07FF).into())
-                        }
-                        _ => bus::cpu_open_bus(address),
+                        (8192, _) => CpuMapResult::PrgRAM((address & 0x1FFF).into()),
+                        (2048, 0x6000..=0x6FFF) => CpuMapResult::PrgRAM((address & 0x07FF).into()),
+                        _ => CpuMapResult::None { original_address: address },
                     }
                 } else {
-                    bus::cpu_open_bus(address)
+                    CpuMapResult::None { original_address: address }
                 }
             }
             (_, 0x8000..=0xFFFF) => match (self.data.prg_mode, address) {
@@ -202,12 +195,12 @@ impl MapperImpl<Vrc4> {
                 (PrgMode::Mode0, 0x8000..=0x9FFF) | (PrgMode::Mode1, 0xC000..=0xDFFF) => {
                     let prg_rom_addr =
                         BankSizeKb::Eight.to_absolute_address(self.data.prg_bank_0, address);
-                    self.cartridge.get_prg_rom(prg_rom_addr)
+                    CpuMapResult::PrgROM(prg_rom_addr)
                 }
                 (_, 0xA000..=0xBFFF) => {
                     let prg_rom_addr =
                         BankSizeKb::Eight.to_absolute_address(self.data.prg_bank_1, address);
-                    self.cartridge.get_prg_rom(prg_rom_addr)
+                    CpuMapResult::PrgROM(prg_rom_addr)
                 }
                 (PrgMode::Mode0, 0xC000..=0xDFFF) | (PrgMode::Mode1, 0x8000..=0x9FFF) => {
                     let prg_rom_addr = BankSizeKb::Eight.to_absolute_address_from_end(
@@ -215,19 +208,31 @@ impl MapperImpl<Vrc4> {
                         self.cartridge.prg_rom.len() as u32,
                         address,
                     );
-                    self.cartridge.get_prg_rom(prg_rom_addr)
+                    CpuMapResult::PrgROM(prg_rom_addr)
                 }
                 (_, 0xE000..=0xFFFF) => {
                     let prg_rom_addr = BankSizeKb::Eight.to_absolute_address_last_bank(
                         self.cartridge.prg_rom.len() as u32,
                         address,
                     );
-                    self.cartridge.get_prg_rom(prg_rom_addr)
+                    CpuMapResult::PrgROM(prg_rom_addr)
                 }
             },
         }
     }
 
+    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+        match self.map_cpu_address(address) {
+            CpuMapResult::None { original_address: address @ 0x6000..=0x6FFF }
+                if self.data.variant.to_type() == Type::Vrc2 =>
+            {
+                // VRC2 boards without PRG RAM have a 1-bit latch at $6000-$6FFF
+                (bus::cpu_open_bus(address) & 0xFE) | self.data.vrc2_ram_bit
+            }
+            map_result => map_result.read(&self.cartridge),
+        }
+    }
+
     pub(crate) fn write_cpu_address(&mut self, address: u16, value: u8) {
         log::trace!("CPU write: address={address:04X}, value={value:02X}");
         match address {
@@ -1,10 +1,9 @@
 //! Code for Konami's VRC6 board (iNES mappers 24 + 26).
 
 use crate::apu::units::PhaseTimer;
-use crate::bus;
 use crate::bus::cartridge::mappers::konami::irq::VrcIrqCounter;
 use crate::bus::cartridge::mappers::{
-    BankSizeKb, ChrType, NametableMirroring, PpuMapResult, konami,
+    BankSizeKb, ChrType, CpuMapResult, NametableMirroring, PpuMapResult, konami,
 };
 use crate::bus::cartridge::{HasBasicPpuMapping, MapperImpl};
 use bincode::{Decode, Encode};
@@ -193,35 +192,39 @@ impl Vrc6 {
 }
 
 impl MapperImpl<Vrc6> {
-    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: {address:04X}"),
-            0x4020..=0x5FFF => bus::cpu_open_bus(address),
+            0x4020..=0x5FFF => CpuMapResult::None { original_address: address },
             0x6000..=0x7FFF => {
                 if self.data.ram_enabled && !self.cartridge.prg_ram.is_empty() {
-                    self.cartridge.get_prg_ram((address & 0x1FFF).into())
+                    CpuMapResult::PrgRAM((address & 0x1FFF).into())
                 } else {
-                    bus::cpu_open_bus(address)
+                    CpuMapResult::None { original_address: address }
                 }
             }
             0x8000..=0xBFFF => {
                 let prg_rom_addr =
                     BankSizeKb::Sixteen.to_absolute_address(self.data.prg_16kb_bank, address);
-                self.cartridge.get_prg_rom(prg_rom_addr)
+                CpuMapResult::PrgROM(prg_rom_addr)
             }
             0xC000..=0xDFFF => {
                 let prg_rom_addr =
                     BankSizeKb::Eight.to_absolute_address(self.data.prg_8kb_bank, address);
-                self.cartridge.get_prg_rom(prg_rom_addr)
+                CpuMapResult::PrgROM(prg_rom_addr)
             }
             0xE000..=0xFFFF => {
                 let prg_rom_addr = BankSizeKb::Eight
                     .to_absolute_address_last_bank(self.cartridge.prg_rom.len() as u32, address);
-                self.cartridge.get_prg_rom(prg_rom_addr)
+                CpuMapResult::PrgROM(prg_rom_addr)
             }
         }
     }
 
+    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+        self.map_cpu_address(address).read(&self.cartridge)
+    }
+
     pub(crate) fn write_cpu_address(&mut self, address: u16, value: u8) {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: {address:04X}"),
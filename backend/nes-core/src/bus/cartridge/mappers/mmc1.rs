@@ -60,7 +60,7 @@ impl Mmc1 {
 }
 
 impl MapperImpl<Mmc1> {
-    fn map_cpu_address(&self, address: u16) -> CpuMapResult {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: 0x{address:04X}"),
             0x4020..=0x5FFF => CpuMapResult::None { original_address: address },
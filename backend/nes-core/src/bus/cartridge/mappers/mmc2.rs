@@ -55,7 +55,7 @@ impl Mmc2 {
 }
 
 impl MapperImpl<Mmc2> {
-    fn map_cpu_address(&self, address: u16) -> CpuMapResult {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match (self.data.variant, address) {
             (_, 0x0000..=0x401F) => panic!("invalid CPU map address: {address:04X}"),
             (_, 0x4020..=0x5FFF) => CpuMapResult::None { original_address: address },
@@ -6,9 +6,10 @@
 //! * NAMCOT-3446 (iNES mapper 76)
 //! * NAMCOT-3453 (iNES mapper 154)
 
-use crate::bus;
 use crate::bus::cartridge::MapperImpl;
-use crate::bus::cartridge::mappers::{BankSizeKb, ChrType, NametableMirroring, PpuMapResult};
+use crate::bus::cartridge::mappers::{
+    BankSizeKb, ChrType, CpuMapResult, NametableMirroring, PpuMapResult,
+};
 use bincode::{Decode, Encode};
 use jgenesis_common::num::GetBit;
 
@@ -257,23 +258,27 @@ impl Mmc3 {
 }
 
 impl MapperImpl<Mmc3> {
-    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: 0x{address:04X}"),
-            0x4020..=0x5FFF => bus::cpu_open_bus(address),
+            0x4020..=0x5FFF => CpuMapResult::None { original_address: address },
             0x6000..=0x7FFF => {
                 if self.data.ram_mode.reads_enabled(address) && !self.cartridge.prg_ram.is_empty() {
-                    self.cartridge.get_prg_ram(u32::from(address & 0x1FFF))
+                    CpuMapResult::PrgRAM(u32::from(address & 0x1FFF))
                 } else {
-                    bus::cpu_open_bus(address)
+                    CpuMapResult::None { original_address: address }
                 }
             }
             0x8000..=0xFFFF => {
-                self.cartridge.get_prg_rom(self.data.bank_mapping.map_prg_rom_address(address))
+                CpuMapResult::PrgROM(self.data.bank_mapping.map_prg_rom_address(address))
             }
         }
     }
 
+    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+        self.map_cpu_address(address).read(&self.cartridge)
+    }
+
     pub(crate) fn write_cpu_address(&mut self, address: u16, value: u8) {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: 0x{address:04X}"),
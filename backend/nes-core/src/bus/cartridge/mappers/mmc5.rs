@@ -742,6 +742,18 @@ impl MapperImpl<Mmc5> {
         }
     }
 
+    /// Map a CPU address to PRG ROM/RAM. Internal registers and extended RAM at $5000-$5FFF are
+    /// reported as unmapped.
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
+        match address {
+            0x0000..=0x401F => panic!("invalid CPU map address: {address:04X}"),
+            0x4020..=0x5FFF => CpuMapResult::None { original_address: address },
+            0x6000..=0xFFFF => {
+                self.data.prg_banking_mode.map_prg_address(self.data.prg_bank_registers, address)
+            }
+        }
+    }
+
     pub(crate) fn read_cpu_address(&mut self, address: u16) -> u8 {
         if address == 0xFFFA || address == 0xFFFB {
             self.data.scanline_counter.nmi_vector_fetched();
@@ -760,11 +772,7 @@ impl MapperImpl<Mmc5> {
                 }
             },
             0x6000..=0xFFFF => {
-                let value = self
-                    .data
-                    .prg_banking_mode
-                    .map_prg_address(self.data.prg_bank_registers, address)
-                    .read(&self.cartridge);
+                let value = self.map_cpu_address(address).read(&self.cartridge);
 
                 self.data.pcm_channel.process_cpu_read(address, value);
 
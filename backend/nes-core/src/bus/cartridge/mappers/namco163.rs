@@ -1,7 +1,6 @@
 //! Code for the Namco 129 and Namco 163 boards (iNES mapper 19).
 
-use crate::bus;
-use crate::bus::cartridge::mappers::{BankSizeKb, ChrType, PpuMapResult};
+use crate::bus::cartridge::mappers::{BankSizeKb, ChrType, CpuMapResult, PpuMapResult};
 use crate::bus::cartridge::{HasBasicPpuMapping, MapperImpl};
 use bincode::{Decode, Encode};
 use jgenesis_common::num::GetBit;
@@ -283,24 +282,15 @@ impl Namco163 {
 }
 
 impl MapperImpl<Namco163> {
-    pub(crate) fn read_cpu_address(&mut self, address: u16) -> u8 {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: {address:04X}"),
-            0x4020..=0x47FF => bus::cpu_open_bus(address),
-            0x4800..=0x4FFF => {
-                let byte = self.data.internal_ram[self.data.internal_ram_addr as usize];
-                if self.data.internal_ram_auto_increment {
-                    self.data.internal_ram_addr = (self.data.internal_ram_addr + 1) & 0x7F;
-                }
-                byte
-            }
-            0x5000..=0x57FF => self.data.irq.get_counter_low_bits(),
-            0x5800..=0x5FFF => self.data.irq.get_counter_high_bits(),
+            0x4020..=0x5FFF => CpuMapResult::None { original_address: address },
             0x6000..=0x7FFF => {
                 if !self.cartridge.prg_ram.is_empty() {
-                    self.cartridge.get_prg_ram((address & 0x1FFF).into())
+                    CpuMapResult::PrgRAM((address & 0x1FFF).into())
                 } else {
-                    bus::cpu_open_bus(address)
+                    CpuMapResult::None { original_address: address }
                 }
             }
             0x8000..=0xDFFF => {
@@ -310,13 +300,28 @@ impl MapperImpl<Namco163> {
                 let bank_index = (address & 0x7FFF) / 0x2000;
                 let bank_number = self.data.prg_banks[bank_index as usize];
                 let prg_rom_addr = BankSizeKb::Eight.to_absolute_address(bank_number, address);
-                self.cartridge.get_prg_rom(prg_rom_addr)
+                CpuMapResult::PrgROM(prg_rom_addr)
             }
             0xE000..=0xFFFF => {
                 let prg_rom_addr = BankSizeKb::Eight
                     .to_absolute_address_last_bank(self.cartridge.prg_rom.len() as u32, address);
-                self.cartridge.get_prg_rom(prg_rom_addr)
+                CpuMapResult::PrgROM(prg_rom_addr)
+            }
+        }
+    }
+
+    pub(crate) fn read_cpu_address(&mut self, address: u16) -> u8 {
+        match address {
+            0x4800..=0x4FFF => {
+                let byte = self.data.internal_ram[self.data.internal_ram_addr as usize];
+                if self.data.internal_ram_auto_increment {
+                    self.data.internal_ram_addr = (self.data.internal_ram_addr + 1) & 0x7F;
+                }
+                byte
             }
+            0x5000..=0x57FF => self.data.irq.get_counter_low_bits(),
+            0x5800..=0x5FFF => self.data.irq.get_counter_high_bits(),
+            _ => self.map_cpu_address(address).read(&self.cartridge),
         }
     }
 
@@ -1,7 +1,8 @@
 //! Code for the Namco 175 and Namco 340 boards (iNES mapper 210).
 
-use crate::bus;
-use crate::bus::cartridge::mappers::{BankSizeKb, ChrType, NametableMirroring, PpuMapResult};
+use crate::bus::cartridge::mappers::{
+    BankSizeKb, ChrType, CpuMapResult, NametableMirroring, PpuMapResult,
+};
 use crate::bus::cartridge::{HasBasicPpuMapping, MapperImpl};
 use bincode::{Decode, Encode};
 use jgenesis_common::num::GetBit;
@@ -49,31 +50,35 @@ impl Namco175 {
 }
 
 impl MapperImpl<Namco175> {
-    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: {address:04X}"),
-            0x4020..=0x5FFF => bus::cpu_open_bus(address),
+            0x4020..=0x5FFF => CpuMapResult::None { original_address: address },
             0x6000..=0x7FFF => {
                 if self.data.ram_enabled && !self.cartridge.prg_ram.is_empty() {
-                    self.cartridge.get_prg_ram((address & 0x1FFF).into())
+                    CpuMapResult::PrgRAM((address & 0x1FFF).into())
                 } else {
-                    bus::cpu_open_bus(address)
+                    CpuMapResult::None { original_address: address }
                 }
             }
             0x8000..=0xDFFF => {
                 let bank_index = (address & 0x7FFF) / 0x2000;
                 let bank_number = self.data.prg_banks[bank_index as usize];
                 let prg_rom_addr = BankSizeKb::Eight.to_absolute_address(bank_number, address);
-                self.cartridge.get_prg_rom(prg_rom_addr)
+                CpuMapResult::PrgROM(prg_rom_addr)
             }
             0xE000..=0xFFFF => {
                 let prg_rom_addr = BankSizeKb::Eight
                     .to_absolute_address_last_bank(self.cartridge.prg_rom.len() as u32, address);
-                self.cartridge.get_prg_rom(prg_rom_addr)
+                CpuMapResult::PrgROM(prg_rom_addr)
             }
         }
     }
 
+    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+        self.map_cpu_address(address).read(&self.cartridge)
+    }
+
     pub(crate) fn write_cpu_address(&mut self, address: u16, value: u8) {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: {address:04X}"),
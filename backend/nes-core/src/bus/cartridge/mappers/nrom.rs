@@ -11,17 +11,18 @@
 //! * Codemasters unlicensed board (iNES mapper 71, very similar to UxROM)
 //! * Jaleco JF-11 / JF-14 (iNES mapper 140, very similar to GxROM)
 
-use crate::bus;
-use crate::bus::cartridge::mappers::{BankSizeKb, ChrType, NametableMirroring, PpuMapResult};
-use crate::bus::cartridge::{Cartridge, HasBasicPpuMapping, MapperImpl};
+use crate::bus::cartridge::mappers::{
+    BankSizeKb, ChrType, CpuMapResult, NametableMirroring, PpuMapResult,
+};
+use crate::bus::cartridge::{HasBasicPpuMapping, MapperImpl};
 use bincode::{Decode, Encode};
 use jgenesis_common::num::GetBit;
 
-fn basic_read_cpu_address(address: u16, cartridge: &Cartridge) -> u8 {
+fn basic_map_cpu_address(address: u16) -> CpuMapResult {
     match address {
         0x0000..=0x401F => panic!("invalid CPU map address: 0x{address:04X}"),
-        0x4020..=0x7FFF => bus::cpu_open_bus(address),
-        0x8000..=0xFFFF => cartridge.get_prg_rom(u32::from(address & 0x7FFF)),
+        0x4020..=0x7FFF => CpuMapResult::None { original_address: address },
+        0x8000..=0xFFFF => CpuMapResult::PrgROM(u32::from(address & 0x7FFF)),
     }
 }
 
@@ -50,8 +51,13 @@ impl Nrom {
 }
 
 impl MapperImpl<Nrom> {
+    #[allow(clippy::unused_self)]
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
+        basic_map_cpu_address(address)
+    }
+
     pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
-        basic_read_cpu_address(address, &self.cartridge)
+        self.map_cpu_address(address).read(&self.cartridge)
     }
 
     // Intentionally blank implementation that is present because every MapperImpl must have a
@@ -106,24 +112,28 @@ impl Uxrom {
 }
 
 impl MapperImpl<Uxrom> {
-    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: 0x{address:04X}"),
-            0x4020..=0x5FFF => bus::cpu_open_bus(address),
-            0x6000..=0x7FFF => self.cartridge.get_prg_ram((address & 0x1FFF).into()),
+            0x4020..=0x5FFF => CpuMapResult::None { original_address: address },
+            0x6000..=0x7FFF => CpuMapResult::PrgRAM((address & 0x1FFF).into()),
             0x8000..=0xBFFF => {
                 let prg_rom_addr =
                     BankSizeKb::Sixteen.to_absolute_address(self.data.prg_bank, address);
-                self.cartridge.get_prg_rom(prg_rom_addr)
+                CpuMapResult::PrgROM(prg_rom_addr)
             }
             0xC000..=0xFFFF => {
                 let prg_rom_addr = BankSizeKb::Sixteen
                     .to_absolute_address_last_bank(self.cartridge.prg_rom.len() as u32, address);
-                self.cartridge.get_prg_rom(prg_rom_addr)
+                CpuMapResult::PrgROM(prg_rom_addr)
             }
         }
     }
 
+    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+        self.map_cpu_address(address).read(&self.cartridge)
+    }
+
     pub(crate) fn write_cpu_address(&mut self, address: u16, value: u8) {
         match (self.data.variant, address) {
             (_, 0x0000..=0x401F) => panic!("invalid CPU map address: 0x{address:04X}"),
@@ -186,8 +196,13 @@ impl Cnrom {
 }
 
 impl MapperImpl<Cnrom> {
+    #[allow(clippy::unused_self)]
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
+        basic_map_cpu_address(address)
+    }
+
     pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
-        basic_read_cpu_address(address, &self.cartridge)
+        self.map_cpu_address(address).read(&self.cartridge)
     }
 
     pub(crate) fn write_cpu_address(&mut self, address: u16, value: u8) {
@@ -238,13 +253,17 @@ impl Axrom {
 }
 
 impl MapperImpl<Axrom> {
-    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         if address < 0x8000 {
-            return bus::cpu_open_bus(address);
+            return CpuMapResult::None { original_address: address };
         }
 
         let prg_rom_addr = BankSizeKb::ThirtyTwo.to_absolute_address(self.data.prg_bank, address);
-        self.cartridge.get_prg_rom(prg_rom_addr)
+        CpuMapResult::PrgROM(prg_rom_addr)
+    }
+
+    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+        self.map_cpu_address(address).read(&self.cartridge)
     }
 
     pub(crate) fn write_cpu_address(&mut self, address: u16, value: u8) {
@@ -296,18 +315,22 @@ impl Gxrom {
 }
 
 impl MapperImpl<Gxrom> {
-    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: {address:04X}"),
-            0x4020..=0x7FFF => bus::cpu_open_bus(address),
+            0x4020..=0x7FFF => CpuMapResult::None { original_address: address },
             0x8000..=0xFFFF => {
                 let prg_rom_addr =
                     BankSizeKb::ThirtyTwo.to_absolute_address(self.data.prg_bank, address);
-                self.cartridge.get_prg_rom(prg_rom_addr)
+                CpuMapResult::PrgROM(prg_rom_addr)
             }
         }
     }
 
+    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+        self.map_cpu_address(address).read(&self.cartridge)
+    }
+
     pub(crate) fn write_cpu_address(&mut self, address: u16, value: u8) {
         match (self.data.variant, address) {
             (_, 0x0000..=0x401F) => panic!("invalid CPU map address: {address:04X}"),
@@ -374,25 +397,29 @@ impl Bnrom {
 }
 
 impl MapperImpl<Bnrom> {
-    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: {address:04X}"),
-            0x4020..=0x5FFF => bus::cpu_open_bus(address),
+            0x4020..=0x5FFF => CpuMapResult::None { original_address: address },
             0x6000..=0x7FFF => {
                 if !self.cartridge.prg_ram.is_empty() {
-                    self.cartridge.get_prg_ram((address & 0x1FFF).into())
+                    CpuMapResult::PrgRAM((address & 0x1FFF).into())
                 } else {
-                    bus::cpu_open_bus(address)
+                    CpuMapResult::None { original_address: address }
                 }
             }
             0x8000..=0xFFFF => {
                 let prg_rom_addr =
                     BankSizeKb::ThirtyTwo.to_absolute_address(self.data.prg_bank, address);
-                self.cartridge.get_prg_rom(prg_rom_addr)
+                CpuMapResult::PrgROM(prg_rom_addr)
             }
         }
     }
 
+    pub(crate) fn read_cpu_address(&self, address: u16) -> u8 {
+        self.map_cpu_address(address).read(&self.cartridge)
+    }
+
     pub(crate) fn write_cpu_address(&mut self, address: u16, value: u8) {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: {address:04X}"),
@@ -238,7 +238,7 @@ impl Sunsoft {
 }
 
 impl MapperImpl<Sunsoft> {
-    fn map_cpu_address(&self, address: u16) -> CpuMapResult {
+    pub(crate) fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match address {
             0x0000..=0x401F => panic!("invalid CPU map address: {address:04X}"),
             0x4020..=0x5FFF => CpuMapResult::None { original_address: address },
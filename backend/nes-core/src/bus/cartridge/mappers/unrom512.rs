@@ -8,8 +8,7 @@
 //!
 //! <https://www.nesdev.org/wiki/UNROM_512>
 
-use crate::bus;
-use crate::bus::cartridge::mappers::{BankSizeKb, NametableMirroring};
+use crate::bus::cartridge::mappers::{BankSizeKb, CpuMapResult, NametableMirroring};
 use crate::bus::cartridge::{INesHeader, MapperImpl};
 use bincode::{Decode, Encode};
 use crc::Crc;
@@ -156,24 +155,28 @@ impl MapperImpl<Unrom512> {
         dirty
     }
 
-    pub fn read_cpu_address(&mut self, address: u16) -> u8 {
+    pub fn map_cpu_address(&self, address: u16) -> CpuMapResult {
         match address {
             0x8000..=0xBFFF => {
                 // Mappable 16KB PRG ROM bank
                 let rom_addr = BankSizeKb::Sixteen.to_absolute_address(self.data.prg_bank, address);
-                self.cartridge.get_prg_rom(rom_addr)
+                CpuMapResult::PrgROM(rom_addr)
             }
             0xC000..=0xFFFF => {
                 // Fixed to last 16KB of PRG ROM
                 let rom_addr = BankSizeKb::Sixteen
                     .to_absolute_address_last_bank(self.cartridge.prg_rom.len() as u32, address);
-                self.cartridge.get_prg_rom(rom_addr)
+                CpuMapResult::PrgROM(rom_addr)
             }
             0x0000..=0x401F => panic!("Invalid CPU map address: {address:04X}"),
-            0x4020..=0x7FFF => bus::cpu_open_bus(address),
+            0x4020..=0x7FFF => CpuMapResult::None { original_address: address },
         }
     }
 
+    pub fn read_cpu_address(&mut self, address: u16) -> u8 {
+        self.map_cpu_address(address).read(&self.cartridge)
+    }
+
     pub fn write_cpu_address(&mut self, address: u16, value: u8) {
         // This mapper only has one register located at $8000-$FFFF
         if !address.bit(15) {
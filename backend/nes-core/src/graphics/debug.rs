@@ -140,6 +140,10 @@ pub fn copy_oam(pattern_table: PatternTable, bus: &mut PpuBus<'_>, out: &mut [Co
     }
 }
 
+pub fn dump_palette_ram(bus: &PpuBus<'_>) -> Vec<u8> {
+    bus.get_palette_ram().to_vec()
+}
+
 pub fn copy_palette_ram(bus: &PpuBus<'_>, out: &mut [Color]) {
     let palette_ram = bus.get_palette_ram();
     for (&nes_color, out_color) in palette_ram.iter().zip(out) {
@@ -1,6 +1,6 @@
 use bincode::{Decode, Encode};
 use jgenesis_common::define_controller_inputs;
-use jgenesis_common::frontend::{DisplayArea, FrameSize, MappableInputs};
+use jgenesis_common::frontend::{AnalogInput, MappableInputs};
 use jgenesis_common::input::Player;
 
 define_controller_inputs! {
@@ -117,29 +117,26 @@ impl MappableInputs<NesButton> for NesInputs {
         }
     }
 
-    #[inline]
-    fn handle_mouse_motion(
-        &mut self,
-        x: i32,
-        y: i32,
-        frame_size: FrameSize,
-        display_area: DisplayArea,
-    ) {
-        if let NesInputDevice::Zapper(zapper_state) = &mut self.p2 {
-            zapper_state.position = jgenesis_common::input::viewport_position_to_frame_position(
-                x,
-                y,
-                frame_size,
-                display_area,
-            );
-            log::debug!("Set Zapper position to {:?}", zapper_state.position);
+    fn button_states(&self, player: Player) -> Vec<(NesButton, bool)> {
+        match (player, self.p2) {
+            (Player::One, _) => self.p1.button_states(),
+            (Player::Two, NesInputDevice::Controller(joypad_state)) => joypad_state.button_states(),
+            (Player::Two, NesInputDevice::Zapper(zapper_state)) => vec![
+                (NesButton::ZapperFire, zapper_state.fire),
+                (NesButton::ZapperForceOffscreen, zapper_state.force_offscreen),
+            ],
         }
     }
 
     #[inline]
-    fn handle_mouse_leave(&mut self) {
+    fn handle_analog_input(&mut self, input: AnalogInput) {
+        if !matches!(input, AnalogInput::MousePosition { .. } | AnalogInput::MouseLeave) {
+            return;
+        }
+
         if let NesInputDevice::Zapper(zapper_state) = &mut self.p2 {
-            zapper_state.position = None;
+            zapper_state.position = input.frame_position();
+            log::debug!("Set Zapper position to {:?}", zapper_state.position);
         }
     }
 }
@@ -8,21 +8,30 @@ use bincode::{Decode, Encode};
 use genesis_core::input::{GenesisButton, InputState};
 use genesis_core::memory::{MainBus, MainBusSignals, MainBusWrites, Memory};
 use genesis_core::timing::GenesisCycleCounters;
-use genesis_core::vdp::{Vdp, VdpTickEffect};
+use genesis_core::vdp::{DebugRegister, Vdp, VdpTickEffect};
 use genesis_core::ym2612::{Ym2612, YmTickEffect};
 use genesis_core::{GenesisEmulatorConfig, GenesisInputs, GenesisRegion};
+use jgenesis_common::eventlog::EventLog;
 use jgenesis_common::frontend::{
-    AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, Renderer, SaveWriter, TickEffect,
-    TickResult, TimingMode,
+    AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, GameMetadata, Renderer,
+    SaveStateSafePoint, SaveWriter, TickEffect, TickResult, TimingMode,
 };
+use jgenesis_common::instrument;
+use jgenesis_common::instrumentation::Subsystem;
+use jgenesis_common::memmap::MemoryMap;
 use jgenesis_proc_macros::{ConfigDisplay, EnumAll, EnumDisplay, PartialClone};
 use m68000_emu::M68000;
+use sh2_emu::Sh2;
 use smsgg_core::psg::{Sn76489, Sn76489TickEffect, Sn76489Version};
 use std::fmt::{Debug, Display};
 use std::mem;
 use thiserror::Error;
 use z80_emu::Z80;
 
+pub use crate::bus::WhichCpu;
+pub use sh2_emu::debug::expr::Expression;
+pub use sh2_emu::debug::{ConditionalBreakpoint, Sh2Register, WatchHit};
+
 #[derive(Debug, Error)]
 pub enum Sega32XError<RErr, AErr, SErr> {
     #[error("Rendering error: {0}")]
@@ -37,12 +46,25 @@ pub enum Sega32XError<RErr, AErr, SErr> {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "clap", derive(jgenesis_proc_macros::CustomValueEnum))]
 pub enum S32XVideoOut {
+    /// Composite the 32X frame with the Genesis VDP frame using the 32X priority bit
     #[default]
     Combined,
+    /// Display only the Genesis VDP frame, for debugging
     GenesisOnly,
+    /// Display only the 32X frame, ignoring priority and with the Genesis VDP frame blacked out,
+    /// for debugging
     S32XOnly,
 }
 
+/// Memory regions that can be dumped and restored for debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumAll)]
+pub enum MemoryRegion {
+    WorkRam,
+    Vram,
+    Sdram,
+    CartridgeRam,
+}
+
 #[derive(Debug, Clone, Copy, Encode, Decode, ConfigDisplay)]
 pub struct Sega32XEmulatorConfig {
     #[cfg_display(skip)]
@@ -56,6 +78,15 @@ impl EmulatorConfigTrait for Sega32XEmulatorConfig {
     fn with_overclocking_disabled(&self) -> Self {
         Self { genesis: self.genesis.with_overclocking_disabled(), ..*self }
     }
+
+    fn with_enhancements_disabled(&self) -> Self {
+        Self {
+            genesis: self.genesis.with_enhancements_disabled(),
+            video_out: S32XVideoOut::Combined,
+            pwm_enabled: true,
+            ..*self
+        }
+    }
 }
 
 macro_rules! new_main_bus {
@@ -152,26 +183,144 @@ impl Sega32XEmulator {
         emulator
     }
 
+    pub fn copy_cram(&self, out: &mut [Color]) {
+        self.vdp.copy_cram(out);
+    }
+
     #[must_use]
-    pub fn cartridge_title(&self) -> String {
-        genesis_core::memory::parse_title_from_header(
-            &self.memory.medium().cartridge.rom,
-            self.region,
-        )
+    pub fn dump_cram(&self) -> Vec<u8> {
+        self.vdp.dump_cram()
     }
 
-    pub fn copy_cram(&self, out: &mut [Color]) {
-        self.vdp.copy_cram(out);
+    /// Copy the 32X VDP's 256-color palette RAM, which is separate from the Genesis VDP's CRAM.
+    pub fn copy_32x_cram(&self, out: &mut [Color]) {
+        self.memory.medium().vdp.copy_cram(out);
+    }
+
+    #[must_use]
+    pub fn dump_32x_cram(&self) -> Vec<u8> {
+        self.memory.medium().vdp.dump_cram()
     }
 
     pub fn copy_vram(&self, out: &mut [Color], palette: u8, row_len: usize) {
         self.vdp.copy_vram(out, palette, row_len);
     }
 
+    /// SDRAM is dumped as big-endian words, matching how the SH-2s address it.
+    #[must_use]
+    pub fn dump_memory(&self, region: MemoryRegion) -> Vec<u8> {
+        let s32x = self.memory.medium();
+        match region {
+            MemoryRegion::WorkRam => self.memory.main_ram().to_vec(),
+            MemoryRegion::Vram => self.vdp.dump_vram(),
+            MemoryRegion::Sdram => s32x.sdram.iter().flat_map(|word| word.to_be_bytes()).collect(),
+            MemoryRegion::CartridgeRam => s32x.cartridge.persistent_memory().to_vec(),
+        }
+    }
+
+    /// Overwrite a memory region, e.g. with the contents of a file previously written using
+    /// [`Self::dump_memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not the same length as the memory region.
+    pub fn restore_memory(&mut self, region: MemoryRegion, bytes: &[u8]) {
+        let s32x = self.memory.medium_mut();
+        match region {
+            MemoryRegion::WorkRam => self.memory.main_ram_mut().copy_from_slice(bytes),
+            MemoryRegion::Vram => self.vdp.restore_vram(bytes),
+            MemoryRegion::Sdram => {
+                assert_eq!(bytes.len(), 2 * s32x.sdram.len());
+                for (word, chunk) in s32x.sdram.iter_mut().zip(bytes.chunks_exact(2)) {
+                    *word = u16::from_be_bytes([chunk[0], chunk[1]]);
+                }
+            }
+            MemoryRegion::CartridgeRam => s32x.cartridge.restore_persistent_memory(bytes),
+        }
+    }
+
+    /// Describe the 68000, Z80, and SH-2 memory maps, including the 68000 ROM bank and frame buffer
+    /// access state.
+    #[must_use]
+    pub fn memory_map_description(&self) -> Vec<MemoryMap> {
+        vec![
+            self.memory.memory_map(),
+            self.memory.z80_memory_map(),
+            self.memory.medium().sh2_memory_map(),
+        ]
+    }
+
+    /// Log of handshake events between the 68000, Z80, and both SH-2s, e.g. communication port
+    /// writes and command interrupts. Disabled by default.
+    #[must_use]
+    pub fn event_log(&self) -> &EventLog {
+        self.memory.event_log()
+    }
+
+    pub fn event_log_mut(&mut self) -> &mut EventLog {
+        self.memory.event_log_mut()
+    }
+
     pub fn dump_vdp_registers(&self, callback: impl FnMut(&str, &[(&str, &str)])) {
         self.vdp.dump_registers(callback);
     }
 
+    #[must_use]
+    pub fn vdp_debug_register(&self) -> DebugRegister {
+        self.vdp.debug_register()
+    }
+
+    pub fn set_vdp_debug_register(&mut self, debug_register: DebugRegister) {
+        self.vdp.set_debug_register(debug_register);
+    }
+
+    /// Mute or unmute one of the 6 YM2612 channels in the audio output.
+    pub fn set_ym2612_channel_muted(&mut self, channel: usize, muted: bool) {
+        self.ym2612.set_channel_muted(channel, muted);
+    }
+
+    /// Whether one of the 6 YM2612 channels is currently muted. Mutes are not saved in save states,
+    /// so loading a state unmutes every channel.
+    #[must_use]
+    pub fn is_ym2612_channel_muted(&self, channel: usize) -> bool {
+        self.ym2612.is_channel_muted(channel)
+    }
+
+    /// Mute or unmute one of the 4 PSG channels in the audio output (0-2 square, 3 noise).
+    pub fn set_psg_channel_muted(&mut self, channel: usize, muted: bool) {
+        self.psg.set_channel_muted(channel, muted);
+    }
+
+    /// Whether one of the 4 PSG channels is currently muted. Mutes are not saved in save states, so
+    /// loading a state unmutes every channel.
+    #[must_use]
+    pub fn is_psg_channel_muted(&self, channel: usize) -> bool {
+        self.psg.is_channel_muted(channel)
+    }
+
+    #[must_use]
+    pub fn sh2(&self, which: WhichCpu) -> &Sh2 {
+        self.memory.medium().sh2(which)
+    }
+
+    /// Mutable access to one of the SH-2 CPUs for debugging: registers, breakpoints, stepping.
+    pub fn sh2_mut(&mut self, which: WhichCpu) -> &mut Sh2 {
+        self.memory.medium_mut().sh2_mut(which)
+    }
+
+    /// Read a byte from the given SH-2's address space for debugging without side effects. Returns
+    /// `None` for registers and for addresses outside of the cached and cache-through areas.
+    #[must_use]
+    pub fn read_sh2_memory(&self, which: WhichCpu, address: u32) -> Option<u8> {
+        self.memory.medium().debug_read_byte(which, address)
+    }
+
+    /// Write a byte to the given SH-2's address space for debugging. Returns `false` for addresses
+    /// outside of the cached and cache-through areas.
+    pub fn write_sh2_memory(&mut self, which: WhichCpu, address: u32, value: u8) -> bool {
+        self.memory.medium_mut().debug_write_byte(which, address, value)
+    }
+
     fn render_frame<R: Renderer>(&mut self, renderer: &mut R) -> Result<(), R::Err> {
         let frame_size = self.vdp.frame_size();
         let aspect_ratio = self.config.genesis.aspect_ratio.to_pixel_aspect_ratio(frame_size, true);
@@ -215,14 +364,15 @@ impl EmulatorTrait for Sega32XEmulator {
         let m68k_cycles = if self.cycles.m68k_wait_cpu_cycles != 0 {
             self.cycles.take_m68k_wait_cpu_cycles()
         } else {
-            self.m68k.execute_instruction(&mut bus)
+            instrument!(Subsystem::Cpu, self.m68k.execute_instruction(&mut bus))
         };
 
         let mclk_cycles = u64::from(m68k_cycles) * self.cycles.m68k_divider.get();
         self.cycles.increment_mclk_counters(mclk_cycles);
 
         while self.cycles.should_tick_z80() {
-            self.z80.tick(&mut bus);
+            instrument!(Subsystem::Cpu, self.z80.tick(&mut bus));
+            bus.set_z80_stalled(self.z80.stalled());
             self.cycles.decrement_z80();
         }
 
@@ -232,11 +382,15 @@ impl EmulatorTrait for Sega32XEmulator {
 
         self.main_bus_writes = bus.apply_writes();
 
-        self.memory.medium_mut().tick(mclk_cycles, self.audio_resampler.pwm_resampler_mut());
+        // SH-2s, 32X VDP, and PWM
+        instrument!(
+            Subsystem::Cpu,
+            self.memory.medium_mut().tick(mclk_cycles, self.audio_resampler.pwm_resampler_mut())
+        );
         self.input.tick(m68k_cycles);
 
         while self.cycles.should_tick_ym2612() {
-            if self.ym2612.tick() == YmTickEffect::OutputSample {
+            if instrument!(Subsystem::Apu, self.ym2612.tick()) == YmTickEffect::OutputSample {
                 let (sample_l, sample_r) = self.ym2612.sample();
                 self.audio_resampler.collect_ym2612_sample(sample_l, sample_r);
             }
@@ -244,7 +398,7 @@ impl EmulatorTrait for Sega32XEmulator {
         }
 
         while self.cycles.should_tick_psg() {
-            if self.psg.tick() == Sn76489TickEffect::Clocked {
+            if instrument!(Subsystem::Apu, self.psg.tick()) == Sn76489TickEffect::Clocked {
                 // PSG output is mono in Genesis; stereo output is only for Game Gear
                 let (psg_sample, _) = self.psg.sample();
                 self.audio_resampler.collect_psg_sample(psg_sample);
@@ -255,7 +409,9 @@ impl EmulatorTrait for Sega32XEmulator {
         self.audio_resampler.output_samples(audio_output).map_err(Sega32XError::Audio)?;
 
         let mut tick_effect = TickEffect::None;
-        if self.vdp.tick(mclk_cycles, &mut self.memory) == VdpTickEffect::FrameComplete {
+        if instrument!(Subsystem::Ppu, self.vdp.tick(mclk_cycles, &mut self.memory))
+            == VdpTickEffect::FrameComplete
+        {
             self.memory.medium_mut().vdp.composite_frame(
                 self.vdp.frame_size(),
                 self.vdp.border_size(),
@@ -287,6 +443,11 @@ impl EmulatorTrait for Sega32XEmulator {
         self.render_frame(renderer)
     }
 
+    fn clear_audio_mutes(&mut self) {
+        self.ym2612.clear_muted_channels();
+        self.psg.clear_muted_channels();
+    }
+
     fn reload_config(&mut self, config: &Self::Config) {
         self.vdp.reload_config(config.genesis.to_vdp_config());
         self.ym2612.reload_config(config.genesis);
@@ -302,6 +463,13 @@ impl EmulatorTrait for Sega32XEmulator {
         self.memory.medium_mut().take_rom_from(other.memory.medium_mut());
     }
 
+    fn game_metadata(&mut self) -> GameMetadata {
+        genesis_core::memory::parse_header_metadata(
+            &self.memory.medium().cartridge.rom,
+            self.region,
+        )
+    }
+
     fn soft_reset(&mut self) {
         log::info!("Soft resetting console");
 
@@ -318,6 +486,11 @@ impl EmulatorTrait for Sega32XEmulator {
         *self = Self::create(rom, self.config, save_writer);
     }
 
+    fn save_state_safe_point() -> SaveStateSafePoint {
+        // Neither the Genesis VDP frame buffer nor the 32X VDP frame buffer is saved
+        SaveStateSafePoint::FrameBoundary
+    }
+
     fn target_fps(&self) -> f64 {
         genesis_core::target_framerate(self.timing_mode)
     }
@@ -2,17 +2,20 @@
 
 use crate::bootrom;
 use crate::cartridge::Cartridge;
+use crate::core;
 use crate::core::{Sdram, Sega32X, SerialInterface};
 use crate::pwm::PwmChip;
 use crate::registers::{Access, SystemRegisters};
 use crate::vdp::Vdp;
 use genesis_core::GenesisRegion;
 use genesis_core::memory::PhysicalMedium;
+use jgenesis_common::addrdecode::{AddressDecoder, AddressRange, Decoded};
+use jgenesis_common::eventlog::EventLog;
+use jgenesis_common::memmap;
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
 use jgenesis_common::num::{GetBit, U16Ext};
 use sh2_emu::bus::BusInterface;
-use std::array;
-
-const SDRAM_MASK: u32 = 0x3FFFF;
+use std::{array, mem};
 
 // $000000-$0000FF: 256-byte fixed vector ROM (except for HINT vector which is R/W)
 const M68K_VECTORS_START: u32 = 0x000000;
@@ -82,6 +85,16 @@ impl Sega32X {
                 .unwrap(),
         )
     }
+
+    fn log_register_write(&mut self, address: u32) {
+        if !self.event_log.is_enabled() {
+            return;
+        }
+
+        if let Some(message) = self.registers.describe_m68k_write(address) {
+            self.event_log.log("68000", || message);
+        }
+    }
 }
 
 macro_rules! word_to_byte {
@@ -282,6 +295,7 @@ impl PhysicalMedium for Sega32X {
             M68K_SYSTEM_REGISTERS_START..=M68K_SYSTEM_REGISTERS_END => {
                 log::trace!("M68K write byte {address:06X} {value:02X}");
                 self.registers.m68k_write_byte(address, value);
+                self.log_register_write(address);
             }
             M68K_VDP_START..=M68K_VDP_END => {
                 log::trace!("M68K write byte {address:06X} {value:02X}");
@@ -343,6 +357,7 @@ impl PhysicalMedium for Sega32X {
                 // System registers
                 log::trace!("M68K write word {address:06X} {value:04X}");
                 self.registers.m68k_write(address, value);
+                self.log_register_write(address);
             }
             M68K_PWM_START..=M68K_PWM_END => {
                 // PWM registers
@@ -389,6 +404,93 @@ impl PhysicalMedium for Sega32X {
     fn region(&self) -> GenesisRegion {
         self.region
     }
+
+    fn describe_memory_map(&self, map: &mut MemoryMap) {
+        self.cartridge.describe_memory_map(map);
+        if self.registers.adapter_enabled {
+            map.add_overlay(
+                M68K_VECTORS_START,
+                M68K_VECTORS_END,
+                RegionKind::Rom,
+                "32X vector ROM",
+                Some("H INT vector is writable".into()),
+            );
+        }
+
+        let frame_buffer_access = format!("{} access", self.registers.vdp_access);
+        map.add_mapped(
+            M68K_FRAME_BUFFER_START,
+            M68K_FRAME_BUFFER_END,
+            RegionKind::Ram,
+            "Frame buffer",
+            frame_buffer_access.clone(),
+        )
+        .add_mapped(
+            M68K_OVERWRITE_IMAGE_START,
+            M68K_OVERWRITE_IMAGE_END,
+            RegionKind::Ram,
+            "Frame buffer overwrite image",
+            frame_buffer_access,
+        )
+        .add_mapped(
+            M68K_FIRST_CART_BANK_START,
+            M68K_FIRST_CART_BANK_END,
+            RegionKind::Rom,
+            "Cartridge ROM",
+            "$000000-$07FFFF (fixed)",
+        )
+        .add_mapped(
+            M68K_MAPPABLE_CART_BANK_START,
+            M68K_MAPPABLE_CART_BANK_END,
+            RegionKind::Rom,
+            "ROM bank window",
+            memmap::describe_bank(self.registers.m68k_rom_bank.into(), 0x100000),
+        )
+        .add(M68K_32X_ID_START, M68K_32X_ID_END, RegionKind::Registers, "32X ID")
+        .add_mapped(
+            M68K_SYSTEM_REGISTERS_START,
+            M68K_SYSTEM_REGISTERS_END,
+            RegionKind::Registers,
+            "32X system registers",
+            if self.registers.adapter_enabled { "Adapter enabled" } else { "Adapter disabled" },
+        )
+        .add(M68K_PWM_START, M68K_PWM_END, RegionKind::Registers, "PWM registers")
+        .add_mapped(
+            M68K_VDP_START,
+            M68K_VDP_END,
+            RegionKind::Registers,
+            "32X VDP registers",
+            format!("{} access", self.registers.vdp_access),
+        )
+        .add(M68K_CRAM_START, M68K_CRAM_END, RegionKind::Ram, "32X palette RAM");
+
+        let ram_register = if self.cartridge.read_ram_register() != 0 {
+            "Cartridge RAM mapped"
+        } else {
+            "Cartridge RAM unmapped"
+        };
+        map.add_mapped(
+            M68K_CART_RAM_REGISTER_WORD,
+            M68K_CART_RAM_REGISTER_BYTE,
+            RegionKind::Registers,
+            "Cartridge RAM register",
+            ram_register,
+        )
+        .add(
+            M68K_SSF_BANK_REGISTERS_START,
+            M68K_SSF_BANK_REGISTERS_END,
+            RegionKind::Registers,
+            "SSF bank registers",
+        );
+    }
+
+    fn event_log(&self) -> &EventLog {
+        &self.event_log
+    }
+
+    fn event_log_mut(&mut self) -> &mut EventLog {
+        &mut self.event_log
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -397,6 +499,15 @@ pub enum WhichCpu {
     Slave,
 }
 
+impl WhichCpu {
+    fn event_source(self) -> &'static str {
+        match self {
+            Self::Master => "Master SH-2",
+            Self::Slave => "Slave SH-2",
+        }
+    }
+}
+
 // SH-2 memory map
 pub struct Sh2Bus<'a> {
     pub which: WhichCpu,
@@ -406,7 +517,33 @@ pub struct Sh2Bus<'a> {
     pub registers: &'a mut SystemRegisters,
     pub sdram: &'a mut Sdram,
     pub serial: &'a mut SerialInterface,
+    pub event_log: &'a mut EventLog,
     pub cycle_counter: u64,
+    pub sdram_cas_latency: u8,
+    pub sdram_refresh_cycles: u64,
+}
+
+impl Sh2Bus<'_> {
+    fn log_register_write(&mut self, address: u32) {
+        if !self.event_log.is_enabled() {
+            return;
+        }
+
+        if let Some(message) = self.registers.describe_sh2_write(address, self.cycle_counter) {
+            let timestamp = core::sh2_cycles_to_mclk(self.cycle_counter);
+            self.event_log.log_at(timestamp, self.which.event_source(), || message);
+        }
+    }
+
+    // Any refresh that occurred since the last SDRAM access delays the next access
+    fn sdram_access_cycles(&mut self, cycles: u64) -> u64 {
+        cycles + mem::take(&mut self.sdram_refresh_cycles)
+    }
+
+    fn sdram_read_cycles(&mut self) -> u64 {
+        let cas_latency = u64::from(self.sdram_cas_latency);
+        self.sdram_access_cycles(SH2_SDRAM_READ_CYCLES + cas_latency - SH2_SDRAM_BASE_CAS_LATENCY)
+    }
 }
 
 // $00000000-$00003FFF: Boot ROM
@@ -450,6 +587,59 @@ const SH2_FB_MIRROR_END: u32 = 0x0405FFFF;
 const SH2_SDRAM_START: u32 = 0x06000000;
 const SH2_SDRAM_END: u32 = 0x0603FFFF;
 
+// $02400000-$027FFFFF: Unknown; Sega CD maybe?
+const SH2_UNKNOWN_START: u32 = 0x02400000;
+const SH2_UNKNOWN_END: u32 = 0x027FFFFF;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Sh2Region {
+    BootRom,
+    SystemRegisters,
+    Pwm,
+    VdpRegisters,
+    Cram,
+    Cartridge,
+    Unknown,
+    FrameBuffer,
+    OverwriteImage,
+    Sdram,
+    Unmapped,
+}
+
+const SH2_ADDRESS_DECODER: AddressDecoder<Sh2Region, 11> = AddressDecoder::new([
+    AddressRange::new(SH2_BOOT_ROM_START, SH2_BOOT_ROM_END, Sh2Region::BootRom),
+    AddressRange::new(
+        SH2_SYSTEM_REGISTERS_START,
+        SH2_SYSTEM_REGISTERS_END,
+        Sh2Region::SystemRegisters,
+    ),
+    AddressRange::new(SH2_PWM_START, SH2_PWM_END, Sh2Region::Pwm),
+    AddressRange::new(SH2_VDP_START, SH2_VDP_END, Sh2Region::VdpRegisters),
+    AddressRange::new(SH2_CRAM_START, SH2_CRAM_END, Sh2Region::Cram),
+    AddressRange::new(SH2_CARTRIDGE_START, SH2_CARTRIDGE_END, Sh2Region::Cartridge),
+    AddressRange::new(SH2_UNKNOWN_START, SH2_UNKNOWN_END, Sh2Region::Unknown),
+    AddressRange::new(SH2_FRAME_BUFFER_START, SH2_FRAME_BUFFER_END, Sh2Region::FrameBuffer),
+    AddressRange::new(
+        SH2_OVERWRITE_IMAGE_START,
+        SH2_OVERWRITE_IMAGE_END,
+        Sh2Region::OverwriteImage,
+    ),
+    AddressRange::mirrored(
+        SH2_FB_MIRROR_START,
+        SH2_FB_MIRROR_END,
+        SH2_FRAME_BUFFER_END - SH2_FRAME_BUFFER_START + 1,
+        Sh2Region::FrameBuffer,
+    ),
+    AddressRange::new(SH2_SDRAM_START, SH2_SDRAM_END, Sh2Region::Sdram),
+]);
+
+#[inline]
+fn decode_sh2_address(address: u32) -> Decoded<Sh2Region> {
+    SH2_ADDRESS_DECODER
+        .decode(address)
+        .unwrap_or(Decoded { region: Sh2Region::Unmapped, offset: address })
+}
+
 // All values are minus one because every access takes at least 1 cycle
 const SH2_CARTRIDGE_CYCLES: u64 = 5;
 const SH2_FRAME_BUFFER_READ_CYCLES: u64 = 4;
@@ -457,34 +647,157 @@ const SH2_VDP_CYCLES: u64 = 4;
 const SH2_SDRAM_READ_CYCLES: u64 = 11;
 const SH2_SDRAM_WRITE_CYCLES: u64 = 1;
 
+// SDRAM read timings are for CAS latency 2, which is what both boot ROMs configure
+const SH2_SDRAM_BASE_CAS_LATENCY: u64 = 2;
+
+impl Sega32X {
+    /// Describe the SH-2 address space. Both SH-2s see the same memory map except for the contents
+    /// of boot ROM.
+    #[must_use]
+    pub fn sh2_memory_map(&self) -> MemoryMap {
+        let cartridge_status = if self.registers.dma.rom_to_vram_dma {
+            "Blocked while ROM-to-VRAM DMA is enabled"
+        } else {
+            "Accessible"
+        };
+        let frame_buffer_access = format!("{} access", self.registers.vdp_access);
+
+        let mut map = MemoryMap::new("SH-2", 32);
+        map.add(SH2_BOOT_ROM_START, SH2_BOOT_ROM_END, RegionKind::Rom, "Boot ROM")
+            .add(
+                SH2_SYSTEM_REGISTERS_START,
+                SH2_SYSTEM_REGISTERS_END,
+                RegionKind::Registers,
+                "32X system registers",
+            )
+            .add(SH2_PWM_START, SH2_PWM_END, RegionKind::Registers, "PWM registers")
+            .add_mapped(
+                SH2_VDP_START,
+                SH2_VDP_END,
+                RegionKind::Registers,
+                "32X VDP registers",
+                frame_buffer_access.clone(),
+            )
+            .add(SH2_CRAM_START, SH2_CRAM_END, RegionKind::Ram, "32X palette RAM")
+            .add_mapped(
+                SH2_CARTRIDGE_START,
+                SH2_CARTRIDGE_END,
+                RegionKind::Rom,
+                "Cartridge ROM",
+                cartridge_status,
+            )
+            .add_mapped(
+                SH2_FRAME_BUFFER_START,
+                SH2_FRAME_BUFFER_END,
+                RegionKind::Ram,
+                "Frame buffer",
+                frame_buffer_access.clone(),
+            )
+            .add_mapped(
+                SH2_OVERWRITE_IMAGE_START,
+                SH2_OVERWRITE_IMAGE_END,
+                RegionKind::Ram,
+                "Frame buffer overwrite image",
+                frame_buffer_access,
+            )
+            .add_mirror(
+                SH2_FB_MIRROR_START,
+                SH2_FB_MIRROR_END,
+                RegionKind::Ram,
+                "Frame buffer",
+                SH2_FRAME_BUFFER_START,
+                SH2_FRAME_BUFFER_END,
+            )
+            .add(SH2_SDRAM_START, SH2_SDRAM_END, RegionKind::Ram, "SDRAM")
+            .add_mirror(
+                0x20000000,
+                0x3FFFFFFF,
+                RegionKind::Ram,
+                "Cache-through",
+                0x00000000,
+                0x1FFFFFFF,
+            )
+            .add(0x40000000, 0x5FFFFFFF, RegionKind::Registers, "Cache purge")
+            .add(0xC0000000, 0xDFFFFFFF, RegionKind::Ram, "Cache data array")
+            .add(0xE0000000, 0xFFFFFFFF, RegionKind::Registers, "SH-2 internal registers");
+        map.fill_unmapped();
+
+        map
+    }
+
+    /// Read a byte from the SH-2 external address space without any side effects, for debugging.
+    /// Returns `None` for registers because reading them can change emulated state.
+    #[must_use]
+    pub fn peek_sh2_byte(&self, which: WhichCpu, address: u32) -> Option<u8> {
+        peek_sh2_byte(which, address, &self.sdram, &self.cartridge, &self.vdp)
+    }
+}
+
+fn peek_sh2_byte(
+    which: WhichCpu,
+    address: u32,
+    sdram: &Sdram,
+    cartridge: &Cartridge,
+    vdp: &Vdp,
+) -> Option<u8> {
+    let Decoded { region, offset } = decode_sh2_address(address);
+
+    match region {
+        Sh2Region::Sdram => {
+            let word = sdram[(offset >> 1) as usize];
+            Some(if !address.bit(0) { word.msb() } else { word.lsb() })
+        }
+        Sh2Region::Cartridge => Some(cartridge.read_byte(offset)),
+        Sh2Region::BootRom => Some(match which {
+            WhichCpu::Master => read_u8(bootrom::SH2_MASTER, address),
+            WhichCpu::Slave => read_u8(bootrom::SH2_SLAVE, address),
+        }),
+        Sh2Region::Cram => {
+            let word = vdp.read_cram(address & !1);
+            Some(if !address.bit(0) { word.msb() } else { word.lsb() })
+        }
+        Sh2Region::FrameBuffer | Sh2Region::OverwriteImage => {
+            let word = vdp.read_frame_buffer(address & !1);
+            Some(if !address.bit(0) { word.msb() } else { word.lsb() })
+        }
+        Sh2Region::SystemRegisters
+        | Sh2Region::VdpRegisters
+        | Sh2Region::Pwm
+        | Sh2Region::Unknown
+        | Sh2Region::Unmapped => None,
+    }
+}
+
 impl BusInterface for Sh2Bus<'_> {
     #[inline]
     fn read_byte(&mut self, address: u32) -> u8 {
         self.cycle_counter += 1;
 
-        match address {
-            SH2_SDRAM_START..=SH2_SDRAM_END => {
-                self.cycle_counter += SH2_SDRAM_READ_CYCLES;
+        let Decoded { region, offset } = decode_sh2_address(address);
 
-                let word = self.sdram[((address & SDRAM_MASK) >> 1) as usize];
+        match region {
+            Sh2Region::Sdram => {
+                self.cycle_counter += self.sdram_read_cycles();
+
+                let word = self.sdram[(offset >> 1) as usize];
                 if !address.bit(0) { word.msb() } else { word.lsb() }
             }
-            SH2_CARTRIDGE_START..=SH2_CARTRIDGE_END => {
+            Sh2Region::Cartridge => {
                 self.cycle_counter += SH2_CARTRIDGE_CYCLES;
 
-                self.cartridge.read_byte(address & 0x3FFFFF)
+                self.cartridge.read_byte(offset)
             }
-            SH2_BOOT_ROM_START..=SH2_BOOT_ROM_END => match self.which {
+            Sh2Region::BootRom => match self.which {
                 WhichCpu::Master => read_u8(bootrom::SH2_MASTER, address),
                 WhichCpu::Slave => read_u8(bootrom::SH2_SLAVE, address),
             },
-            SH2_SYSTEM_REGISTERS_START..=SH2_SYSTEM_REGISTERS_END => {
+            Sh2Region::SystemRegisters => {
                 log::trace!("SH-2 {:?} read byte {address:08X}", self.which);
                 let value =
                     self.registers.sh2_read(address & !1, self.which, self.vdp, self.cycle_counter);
                 if !address.bit(0) { value.msb() } else { value.lsb() }
             }
-            SH2_VDP_START..=SH2_VDP_END => {
+            Sh2Region::VdpRegisters => {
                 self.cycle_counter += SH2_VDP_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -494,7 +807,7 @@ impl BusInterface for Sh2Bus<'_> {
                     0xFF
                 }
             }
-            SH2_CRAM_START..=SH2_CRAM_END => {
+            Sh2Region::Cram => {
                 self.cycle_counter += SH2_VDP_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -505,7 +818,7 @@ impl BusInterface for Sh2Bus<'_> {
                     0xFF
                 }
             }
-            SH2_FRAME_BUFFER_START..=SH2_FB_MIRROR_END => {
+            Sh2Region::FrameBuffer | Sh2Region::OverwriteImage => {
                 self.cycle_counter += SH2_FRAME_BUFFER_READ_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -515,10 +828,10 @@ impl BusInterface for Sh2Bus<'_> {
                     0xFF
                 }
             }
-            SH2_PWM_START..=SH2_PWM_END => {
+            Sh2Region::Pwm => {
                 word_to_byte!(address, self.pwm.read_register)
             }
-            _ => {
+            Sh2Region::Unknown | Sh2Region::Unmapped => {
                 log::warn!("SH-2 {:?} invalid address byte read {address:08X}", self.which);
                 0
             }
@@ -529,28 +842,30 @@ impl BusInterface for Sh2Bus<'_> {
     fn read_word(&mut self, address: u32) -> u16 {
         self.cycle_counter += 1;
 
-        match address {
-            SH2_SDRAM_START..=SH2_SDRAM_END => {
-                self.cycle_counter += SH2_SDRAM_READ_CYCLES;
-                self.sdram[((address & SDRAM_MASK) >> 1) as usize]
+        let Decoded { region, offset } = decode_sh2_address(address);
+
+        match region {
+            Sh2Region::Sdram => {
+                self.cycle_counter += self.sdram_read_cycles();
+                self.sdram[(offset >> 1) as usize]
             }
-            SH2_CARTRIDGE_START..=SH2_CARTRIDGE_END => {
+            Sh2Region::Cartridge => {
                 self.cycle_counter += SH2_CARTRIDGE_CYCLES;
-                self.cartridge.read_word(address & 0x3FFFFF)
+                self.cartridge.read_word(offset)
             }
-            SH2_BOOT_ROM_START..=SH2_BOOT_ROM_END => match self.which {
+            Sh2Region::BootRom => match self.which {
                 WhichCpu::Master => read_u16(bootrom::SH2_MASTER, address),
                 WhichCpu::Slave => read_u16(bootrom::SH2_SLAVE, address),
             },
-            SH2_SYSTEM_REGISTERS_START..=SH2_SYSTEM_REGISTERS_END => {
+            Sh2Region::SystemRegisters => {
                 log::trace!("SH-2 {:?} read word {address:08X}", self.which);
                 self.registers.sh2_read(address, self.which, self.vdp, self.cycle_counter)
             }
-            SH2_PWM_START..=SH2_PWM_END => {
+            Sh2Region::Pwm => {
                 log::trace!("SH-2 {:?} PWM register read {address:08X}", self.which);
                 self.pwm.read_register(address)
             }
-            SH2_VDP_START..=SH2_VDP_END => {
+            Sh2Region::VdpRegisters => {
                 self.cycle_counter += SH2_VDP_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -560,7 +875,7 @@ impl BusInterface for Sh2Bus<'_> {
                     0xFFFF
                 }
             }
-            SH2_CRAM_START..=SH2_CRAM_END => {
+            Sh2Region::Cram => {
                 self.cycle_counter += SH2_VDP_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -570,7 +885,7 @@ impl BusInterface for Sh2Bus<'_> {
                     0xFFFF
                 }
             }
-            SH2_FRAME_BUFFER_START..=SH2_FB_MIRROR_END => {
+            Sh2Region::FrameBuffer | Sh2Region::OverwriteImage => {
                 self.cycle_counter += SH2_FRAME_BUFFER_READ_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -580,13 +895,12 @@ impl BusInterface for Sh2Bus<'_> {
                     0xFFFF
                 }
             }
-            0x02400000..=0x027FFFFF => {
-                // Not sure what these addresses are; Doom 32X Resurrection reads from them
-                // Sega CD maybe?
+            Sh2Region::Unknown => {
+                // Doom 32X Resurrection reads from these addresses
                 log::debug!("Invalid address word read {address:08X}");
                 0
             }
-            _ => {
+            Sh2Region::Unmapped => {
                 log::warn!("SH-2 {:?} invalid address word read {address:08X}", self.which);
                 0
             }
@@ -597,25 +911,27 @@ impl BusInterface for Sh2Bus<'_> {
     fn read_longword(&mut self, address: u32) -> u32 {
         self.cycle_counter += 2;
 
-        match address {
-            SH2_SDRAM_START..=SH2_SDRAM_END => {
+        let Decoded { region, offset } = decode_sh2_address(address);
+
+        match region {
+            Sh2Region::Sdram => {
                 // Subtract one because SDRAM access times are not doubled for longword reads
-                self.cycle_counter += SH2_SDRAM_READ_CYCLES - 1;
+                self.cycle_counter += self.sdram_read_cycles() - 1;
 
-                let word_addr = (((address & SDRAM_MASK) >> 1) & !1) as usize;
+                let word_addr = ((offset >> 1) & !1) as usize;
                 let high_word = self.sdram[word_addr];
                 let low_word = self.sdram[word_addr | 1];
                 (u32::from(high_word) << 16) | u32::from(low_word)
             }
-            SH2_CARTRIDGE_START..=SH2_CARTRIDGE_END => {
+            Sh2Region::Cartridge => {
                 self.cycle_counter += 2 * SH2_CARTRIDGE_CYCLES;
-                self.cartridge.read_longword(address & 0x3FFFFF)
+                self.cartridge.read_longword(offset)
             }
-            SH2_BOOT_ROM_START..=SH2_BOOT_ROM_END => match self.which {
+            Sh2Region::BootRom => match self.which {
                 WhichCpu::Master => read_u32(bootrom::SH2_MASTER, address),
                 WhichCpu::Slave => read_u32(bootrom::SH2_SLAVE, address),
             },
-            SH2_SYSTEM_REGISTERS_START..=SH2_SYSTEM_REGISTERS_END => {
+            Sh2Region::SystemRegisters => {
                 if log::log_enabled!(log::Level::Trace) && !(0x4020..0x4030).contains(&address) {
                     log::trace!("SH-2 {:?} read longword {address:08X}", self.which);
                 }
@@ -625,7 +941,7 @@ impl BusInterface for Sh2Bus<'_> {
                     self.registers.sh2_read(address | 2, self.which, self.vdp, self.cycle_counter);
                 (u32::from(high) << 16) | u32::from(low)
             }
-            SH2_VDP_START..=SH2_VDP_END => {
+            Sh2Region::VdpRegisters => {
                 self.cycle_counter += 2 * SH2_VDP_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -637,7 +953,7 @@ impl BusInterface for Sh2Bus<'_> {
                     0xFFFFFFFF
                 }
             }
-            SH2_CRAM_START..=SH2_CRAM_END => {
+            Sh2Region::Cram => {
                 self.cycle_counter += 2 * SH2_VDP_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -649,7 +965,7 @@ impl BusInterface for Sh2Bus<'_> {
                     0xFFFFFFFF
                 }
             }
-            SH2_FRAME_BUFFER_START..=SH2_FB_MIRROR_END => {
+            Sh2Region::FrameBuffer | Sh2Region::OverwriteImage => {
                 self.cycle_counter += 2 * SH2_FRAME_BUFFER_READ_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -661,7 +977,7 @@ impl BusInterface for Sh2Bus<'_> {
                     0xFFFFFFFF
                 }
             }
-            _ => {
+            Sh2Region::Pwm | Sh2Region::Unknown | Sh2Region::Unmapped => {
                 log::warn!("SH-2 {:?} invalid address longword read {address:08X}", self.which);
                 0
             }
@@ -670,11 +986,11 @@ impl BusInterface for Sh2Bus<'_> {
 
     #[inline]
     fn read_cache_line(&mut self, address: u32) -> [u32; 4] {
-        if (SH2_SDRAM_START..=SH2_SDRAM_END).contains(&address) {
+        if let Decoded { region: Sh2Region::Sdram, offset } = decode_sh2_address(address) {
             // The SH-2s can read a full 16-byte cache line in 12 cycles
-            self.cycle_counter += SH2_SDRAM_READ_CYCLES + 1;
+            self.cycle_counter += self.sdram_read_cycles() + 1;
 
-            let base_addr = ((address & SDRAM_MASK) >> 1) as usize;
+            let base_addr = (offset >> 1) as usize;
             return array::from_fn(|i| {
                 let high_word = self.sdram[base_addr | (i << 1)];
                 let low_word = self.sdram[(base_addr | (i << 1)) + 1];
@@ -685,22 +1001,28 @@ impl BusInterface for Sh2Bus<'_> {
         array::from_fn(|i| self.read_longword(address | ((i as u32) << 2)))
     }
 
+    fn peek_byte(&self, address: u32) -> Option<u8> {
+        peek_sh2_byte(self.which, address, self.sdram, self.cartridge, self.vdp)
+    }
+
     #[inline]
     fn write_byte(&mut self, address: u32, value: u8) {
         self.cycle_counter += 1;
 
-        match address {
-            SH2_SDRAM_START..=SH2_SDRAM_END => {
-                self.cycle_counter += SH2_SDRAM_WRITE_CYCLES;
+        let Decoded { region, offset } = decode_sh2_address(address);
+
+        match region {
+            Sh2Region::Sdram => {
+                self.cycle_counter += self.sdram_access_cycles(SH2_SDRAM_WRITE_CYCLES);
 
-                let word_addr = ((address & SDRAM_MASK) >> 1) as usize;
+                let word_addr = (offset >> 1) as usize;
                 if !address.bit(0) {
                     self.sdram[word_addr].set_msb(value);
                 } else {
                     self.sdram[word_addr].set_lsb(value);
                 }
             }
-            SH2_SYSTEM_REGISTERS_START..=SH2_SYSTEM_REGISTERS_END => {
+            Sh2Region::SystemRegisters => {
                 log::trace!("SH-2 {:?} byte write {address:08X} {value:02X}", self.which);
                 self.registers.sh2_write_byte(
                     address,
@@ -709,8 +1031,9 @@ impl BusInterface for Sh2Bus<'_> {
                     self.vdp,
                     self.cycle_counter,
                 );
+                self.log_register_write(address);
             }
-            SH2_VDP_START..=SH2_VDP_END => {
+            Sh2Region::VdpRegisters => {
                 self.cycle_counter += SH2_VDP_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -719,7 +1042,7 @@ impl BusInterface for Sh2Bus<'_> {
                     log::warn!("VDP register write with FM=0: {address:08X} {value:02X}");
                 }
             }
-            SH2_CRAM_START..=SH2_CRAM_END => {
+            Sh2Region::Cram => {
                 self.cycle_counter += SH2_VDP_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -728,7 +1051,7 @@ impl BusInterface for Sh2Bus<'_> {
                     log::warn!("CRAM write with FM=0: {address:08X} {value:02X}");
                 }
             }
-            SH2_FRAME_BUFFER_START..=SH2_FB_MIRROR_END => {
+            Sh2Region::FrameBuffer | Sh2Region::OverwriteImage => {
                 if self.registers.vdp_access == Access::Sh2 {
                     // Treat write as an overwrite because 0 bytes are never written to the frame buffer
                     self.vdp.write_frame_buffer_byte(address, value);
@@ -736,7 +1059,7 @@ impl BusInterface for Sh2Bus<'_> {
                     log::warn!("Frame buffer write with FM=0: {address:08X} {value:02X}");
                 }
             }
-            SH2_PWM_START..=SH2_PWM_END => {
+            Sh2Region::Pwm => {
                 let mut word = self.pwm.read_register(address & !1);
                 if !address.bit(0) {
                     word.set_msb(value);
@@ -745,19 +1068,19 @@ impl BusInterface for Sh2Bus<'_> {
                 }
                 self.pwm.sh2_write_register(address & !1, word);
             }
-            SH2_CARTRIDGE_START..=SH2_CARTRIDGE_END => {
+            Sh2Region::Cartridge => {
                 self.cycle_counter += SH2_CARTRIDGE_CYCLES;
 
                 // TODO can the SH-2s write to cartridge RAM?
-                self.cartridge.write_byte(address & 0x3FFFFF, value);
+                self.cartridge.write_byte(offset, value);
             }
-            SH2_BOOT_ROM_START..=SH2_BOOT_ROM_END => {
+            Sh2Region::BootRom => {
                 log::debug!(
                     "Ignoring SH-2 {:?} byte write to boot ROM: {address:08X} {value:02X}",
                     self.which
                 );
             }
-            _ => {
+            Sh2Region::Unknown | Sh2Region::Unmapped => {
                 log::warn!(
                     "SH-2 {:?} invalid address byte write: {address:08X} {value:02X}",
                     self.which
@@ -770,20 +1093,23 @@ impl BusInterface for Sh2Bus<'_> {
     fn write_word(&mut self, address: u32, value: u16) {
         self.cycle_counter += 1;
 
-        match address {
-            SH2_SDRAM_START..=SH2_SDRAM_END => {
-                self.cycle_counter += SH2_SDRAM_WRITE_CYCLES;
-                self.sdram[((address & SDRAM_MASK) >> 1) as usize] = value;
+        let Decoded { region, offset } = decode_sh2_address(address);
+
+        match region {
+            Sh2Region::Sdram => {
+                self.cycle_counter += self.sdram_access_cycles(SH2_SDRAM_WRITE_CYCLES);
+                self.sdram[(offset >> 1) as usize] = value;
             }
-            SH2_SYSTEM_REGISTERS_START..=SH2_SYSTEM_REGISTERS_END => {
+            Sh2Region::SystemRegisters => {
                 log::trace!("SH-2 {:?} word write {address:08X} {value:04X}", self.which);
                 self.registers.sh2_write(address, value, self.which, self.vdp, self.cycle_counter);
+                self.log_register_write(address);
             }
-            SH2_PWM_START..=SH2_PWM_END => {
+            Sh2Region::Pwm => {
                 log::trace!("SH-2 {:?} PWM register write {address:08X} {value:04X}", self.which);
                 self.pwm.sh2_write_register(address, value);
             }
-            SH2_VDP_START..=SH2_VDP_END => {
+            Sh2Region::VdpRegisters => {
                 self.cycle_counter += SH2_VDP_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -792,7 +1118,7 @@ impl BusInterface for Sh2Bus<'_> {
                     log::warn!("VDP register write with FM=0: {address:08X} {value:04X}");
                 }
             }
-            SH2_CRAM_START..=SH2_CRAM_END => {
+            Sh2Region::Cram => {
                 self.cycle_counter += SH2_VDP_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -801,15 +1127,14 @@ impl BusInterface for Sh2Bus<'_> {
                     log::warn!("CRAM write with FM=0: {address:08X} {value:04X}");
                 }
             }
-            SH2_FRAME_BUFFER_START..=SH2_FRAME_BUFFER_END
-            | SH2_FB_MIRROR_START..=SH2_FB_MIRROR_END => {
+            Sh2Region::FrameBuffer => {
                 if self.registers.vdp_access == Access::Sh2 {
                     self.vdp.write_frame_buffer_word(address, value);
                 } else {
                     log::warn!("Frame buffer write with FM=0: {address:08X} {value:04X}");
                 }
             }
-            SH2_OVERWRITE_IMAGE_START..=SH2_OVERWRITE_IMAGE_END => {
+            Sh2Region::OverwriteImage => {
                 if self.registers.vdp_access == Access::Sh2 {
                     self.vdp.frame_buffer_overwrite_word(address, value);
                 } else {
@@ -818,16 +1143,16 @@ impl BusInterface for Sh2Bus<'_> {
                     );
                 }
             }
-            SH2_CARTRIDGE_START..=SH2_CARTRIDGE_END => {
+            Sh2Region::Cartridge => {
                 self.cycle_counter += SH2_CARTRIDGE_CYCLES;
 
                 // TODO can the SH-2s write to cartridge RAM?
-                self.cartridge.write_word(address & 0x3FFFFF, value);
+                self.cartridge.write_word(offset, value);
             }
-            SH2_BOOT_ROM_START..=SH2_BOOT_ROM_END => {
+            Sh2Region::BootRom => {
                 log::debug!("SH-2 {:?} write to boot ROM: {address:08X} {value:04X}", self.which);
             }
-            _ => {
+            Sh2Region::Unknown | Sh2Region::Unmapped => {
                 log::warn!(
                     "SH-2 {:?} invalid address write: {address:08X} {value:04X}",
                     self.which
@@ -840,15 +1165,17 @@ impl BusInterface for Sh2Bus<'_> {
     fn write_longword(&mut self, address: u32, value: u32) {
         self.cycle_counter += 2;
 
-        match address {
-            SH2_SDRAM_START..=SH2_SDRAM_END => {
-                self.cycle_counter += 2 * SH2_SDRAM_WRITE_CYCLES;
+        let Decoded { region, offset } = decode_sh2_address(address);
 
-                let sdram_addr = (((address & SDRAM_MASK) >> 1) & !1) as usize;
+        match region {
+            Sh2Region::Sdram => {
+                self.cycle_counter += self.sdram_access_cycles(2 * SH2_SDRAM_WRITE_CYCLES);
+
+                let sdram_addr = ((offset >> 1) & !1) as usize;
                 self.sdram[sdram_addr] = (value >> 16) as u16;
                 self.sdram[sdram_addr | 1] = value as u16;
             }
-            SH2_SYSTEM_REGISTERS_START..=SH2_SYSTEM_REGISTERS_END => {
+            Sh2Region::SystemRegisters => {
                 log::trace!("SH-2 {:?} longword write {address:08X} {value:08X}", self.which);
                 self.registers.sh2_write(
                     address,
@@ -864,8 +1191,10 @@ impl BusInterface for Sh2Bus<'_> {
                     self.vdp,
                     self.cycle_counter,
                 );
+                self.log_register_write(address);
+                self.log_register_write(address | 2);
             }
-            SH2_VDP_START..=SH2_VDP_END => {
+            Sh2Region::VdpRegisters => {
                 self.cycle_counter += 2 * SH2_VDP_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -875,7 +1204,7 @@ impl BusInterface for Sh2Bus<'_> {
                     log::warn!("VDP register write with FM=0: {address:08X} {value:08X}");
                 }
             }
-            SH2_CRAM_START..=SH2_CRAM_END => {
+            Sh2Region::Cram => {
                 self.cycle_counter += 2 * SH2_VDP_CYCLES;
 
                 if self.registers.vdp_access == Access::Sh2 {
@@ -885,8 +1214,7 @@ impl BusInterface for Sh2Bus<'_> {
                     log::warn!("CRAM write with FM=0: {address:08X} {value:08X}");
                 }
             }
-            SH2_FRAME_BUFFER_START..=SH2_FRAME_BUFFER_END
-            | SH2_FB_MIRROR_START..=SH2_FB_MIRROR_END => {
+            Sh2Region::FrameBuffer => {
                 if self.registers.vdp_access == Access::Sh2 {
                     self.vdp.write_frame_buffer_word(address, (value >> 16) as u16);
                     self.vdp.write_frame_buffer_word(address | 2, value as u16);
@@ -894,7 +1222,7 @@ impl BusInterface for Sh2Bus<'_> {
                     log::warn!("Frame buffer write with FM=0: {address:08X} {value:08X}");
                 }
             }
-            SH2_OVERWRITE_IMAGE_START..=SH2_OVERWRITE_IMAGE_END => {
+            Sh2Region::OverwriteImage => {
                 if self.registers.vdp_access == Access::Sh2 {
                     self.vdp.frame_buffer_overwrite_word(address, (value >> 16) as u16);
                     self.vdp.frame_buffer_overwrite_word(address | 2, value as u16);
@@ -902,24 +1230,24 @@ impl BusInterface for Sh2Bus<'_> {
                     log::warn!("Frame buffer write with FM=0: {address:08X} {value:08X}");
                 }
             }
-            SH2_PWM_START..=SH2_PWM_END => {
+            Sh2Region::Pwm => {
                 self.pwm.sh2_write_register(address, (value >> 16) as u16);
                 self.pwm.sh2_write_register(address | 2, value as u16);
             }
-            SH2_CARTRIDGE_START..=SH2_CARTRIDGE_END => {
+            Sh2Region::Cartridge => {
                 self.cycle_counter += 2 * SH2_CARTRIDGE_CYCLES;
 
                 // TODO can the SH-2s write to cartridge RAM?
-                self.cartridge.write_word(address & 0x3FFFFF, (value >> 16) as u16);
-                self.cartridge.write_word((address & 0x3FFFFF) | 1, value as u16);
+                self.cartridge.write_word(offset, (value >> 16) as u16);
+                self.cartridge.write_word(offset | 1, value as u16);
             }
-            SH2_BOOT_ROM_START..=SH2_BOOT_ROM_END => {
+            Sh2Region::BootRom => {
                 log::debug!(
                     "SH-2 {:?} longword write to boot ROM address: {address:08X} {value:08X}",
                     self.which
                 );
             }
-            _ => {
+            Sh2Region::Unknown | Sh2Region::Unmapped => {
                 log::warn!(
                     "SH-2 {:?} invalid address write: {address:08X} {value:08X}",
                     self.which
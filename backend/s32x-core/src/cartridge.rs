@@ -1,7 +1,9 @@
 use bincode::{Decode, Encode};
 use genesis_core::memory::SegaMapper;
 use genesis_core::memory::eeprom::X24C02Chip;
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
 use jgenesis_common::num::GetBit;
+use jgenesis_common::sram;
 use jgenesis_proc_macros::{FakeDecode, FakeEncode, PartialClone};
 use std::ops::Deref;
 
@@ -110,6 +112,19 @@ impl PersistentMemory {
     }
 }
 
+// Cartridge RAM is 8-bit at odd addresses; accept save files that store it as 16-bit words
+fn convert_initial_ram(ram: Vec<u8>, ram_len: usize) -> Option<Vec<u8>> {
+    if ram.len() != ram_len && ram.len() >= 2 * ram_len {
+        if let Some(ram) =
+            sram::deinterleave_8bit(&ram, true).and_then(|ram| sram::normalize(ram, ram_len))
+        {
+            return Some(ram);
+        }
+    }
+
+    sram::normalize(ram, ram_len)
+}
+
 fn map_ram_address(address: u32, start_address: u32) -> usize {
     ((address - start_address) >> 1) as usize
 }
@@ -146,10 +161,10 @@ impl Cartridge {
             let end_address = u32::from_be_bytes(rom[0x1B8..0x1BC].try_into().unwrap());
             let ram_len = (((end_address >> 1) + 1) - (start_address >> 1)) as usize;
 
-            let ram = match initial_ram {
-                Some(initial_ram) if initial_ram.len() == ram_len => initial_ram.into_boxed_slice(),
-                _ => vec![0xFF; ram_len].into_boxed_slice(),
-            };
+            let ram = initial_ram
+                .and_then(|initial_ram| convert_initial_ram(initial_ram, ram_len))
+                .unwrap_or_else(|| vec![0xFF; ram_len])
+                .into_boxed_slice();
 
             log::info!("Cartridge RAM address range: ${start_address:06X}-${end_address:06X}");
 
@@ -222,6 +237,47 @@ impl Cartridge {
         mapper.write(address, value);
     }
 
+    pub fn describe_memory_map(&self, map: &mut MemoryMap) {
+        match self.mapper {
+            Some(mapper) => mapper.describe_memory_map(map),
+            None => {
+                map.add_mapped(
+                    0x000000,
+                    0x3FFFFF,
+                    RegionKind::Rom,
+                    "Cartridge ROM",
+                    format!("{}KB", self.rom.len() / 1024),
+                );
+            }
+        }
+
+        if !self.ram_mapped {
+            return;
+        }
+
+        match &self.persistent {
+            PersistentMemory::None => {}
+            PersistentMemory::Ram { ram, start_address, end_address_exclusive, .. } => {
+                map.add_overlay(
+                    *start_address,
+                    end_address_exclusive - 1,
+                    RegionKind::Ram,
+                    "Cartridge SRAM",
+                    Some(format!("{} bytes, odd addresses", ram.len())),
+                );
+            }
+            PersistentMemory::Eeprom { .. } => {
+                map.add_overlay(
+                    EEPROM_SCL_ADDRESS,
+                    EEPROM_SDA_ADDRESS,
+                    RegionKind::Registers,
+                    "EEPROM lines",
+                    None,
+                );
+            }
+        }
+    }
+
     pub fn persistent_memory(&self) -> &[u8] {
         match &self.persistent {
             PersistentMemory::None => &[],
@@ -230,6 +286,20 @@ impl Cartridge {
         }
     }
 
+    pub fn restore_persistent_memory(&mut self, memory: &[u8]) {
+        match &mut self.persistent {
+            PersistentMemory::None => {}
+            PersistentMemory::Ram { ram, dirty, .. } => {
+                ram.copy_from_slice(memory);
+                *dirty = true;
+            }
+            PersistentMemory::Eeprom { chip, dirty } => {
+                chip.restore_memory(memory);
+                *dirty = true;
+            }
+        }
+    }
+
     pub fn persistent_memory_dirty(&self) -> bool {
         match &self.persistent {
             PersistentMemory::None => false,
@@ -246,3 +316,16 @@ impl Cartridge {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_initial_ram_formats() {
+        assert_eq!(convert_initial_ram(vec![1, 2, 3, 4], 4), Some(vec![1, 2, 3, 4]));
+        assert_eq!(convert_initial_ram(vec![0xFF, 1, 0xFF, 2], 2), Some(vec![1, 2]));
+        assert_eq!(convert_initial_ram(vec![1, 2, 0, 0], 2), Some(vec![1, 2]));
+        assert_eq!(convert_initial_ram(vec![1, 2, 3, 4], 2), None);
+    }
+}
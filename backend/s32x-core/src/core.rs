@@ -12,9 +12,11 @@ use crate::vdp::Vdp;
 use bincode::{Decode, Encode};
 use genesis_core::{GenesisRegion, timing};
 use jgenesis_common::boxedarray::BoxedWordArray;
+use jgenesis_common::eventlog::EventLog;
 use jgenesis_common::frontend::TimingMode;
 use jgenesis_proc_macros::PartialClone;
 use sh2_emu::Sh2;
+use sh2_emu::bus::BusInterface;
 use std::mem;
 
 const M68K_DIVIDER: u64 = timing::NATIVE_M68K_DIVIDER;
@@ -23,10 +25,18 @@ const SH2_MULTIPLIER: u64 = 3;
 // Only execute SH-2 instructions in batches of at least 10 for slightly better performance
 const SH2_EXECUTION_SLICE_LEN: u64 = 10;
 
+const SH2_EXTERNAL_ADDRESS_MASK: u32 = 0x1FFFFFFF;
+
 const SDRAM_LEN_WORDS: usize = 256 * 1024 / 2;
 
 pub type Sdram = [u16; SDRAM_LEN_WORDS];
 
+/// Convert an SH-2 cycle count to Genesis master clock cycles, which the event log uses as its time
+/// base.
+pub fn sh2_cycles_to_mclk(sh2_cycles: u64) -> u64 {
+    sh2_cycles * M68K_DIVIDER / SH2_MULTIPLIER
+}
+
 #[derive(Debug, Clone, Default, Encode, Decode)]
 pub struct SerialInterface {
     pub master_to_slave: Option<u8>,
@@ -50,6 +60,8 @@ pub struct Sega32X {
     pub sdram: BoxedWordArray<SDRAM_LEN_WORDS>,
     pub serial: SerialInterface,
     pub region: GenesisRegion,
+    #[partial_clone(default)]
+    pub event_log: EventLog,
 }
 
 impl Sega32X {
@@ -64,7 +76,7 @@ impl Sega32X {
 
         Self {
             sh2_master: Sh2::new("Master".into()),
-            sh2_slave: Sh2::new("Slave".into()),
+            sh2_slave: Sh2::new_slave("Slave".into()),
             mclk_counter: 0,
             global_cycles: 0,
             master_cycles: 0,
@@ -77,6 +89,7 @@ impl Sega32X {
             sdram: BoxedWordArray::new(),
             serial: SerialInterface::default(),
             region,
+            event_log: EventLog::default(),
         }
     }
 
@@ -88,6 +101,7 @@ impl Sega32X {
         let elapsed_sh2_cycles = self.mclk_counter * SH2_MULTIPLIER / M68K_DIVIDER;
         self.mclk_counter -= elapsed_sh2_cycles * M68K_DIVIDER / SH2_MULTIPLIER;
         self.global_cycles += elapsed_sh2_cycles;
+        self.event_log.set_timestamp(sh2_cycles_to_mclk(self.global_cycles));
 
         let mut bus = Sh2Bus {
             which: WhichCpu::Master,
@@ -97,7 +111,12 @@ impl Sega32X {
             registers: &mut self.registers,
             sdram: &mut self.sdram,
             serial: &mut self.serial,
+            event_log: &mut self.event_log,
             cycle_counter: 0,
+            sdram_cas_latency: self.sh2_master.sdram_cas_latency(),
+            // Refresh cycles are accumulated while ticking peripherals at the end of the previous call.
+            // Only the master SH-2 performs refresh
+            sdram_refresh_cycles: self.sh2_master.take_sdram_refresh_cycles(),
         };
 
         // Brutal Unleashed: Above the Claw requires fairly close synchronization to prevent
@@ -115,6 +134,7 @@ impl Sega32X {
                 while self.slave_cycles <= self.master_cycles {
                     bus.which = WhichCpu::Slave;
                     bus.cycle_counter = self.slave_cycles;
+                    bus.sdram_cas_latency = self.sh2_slave.sdram_cas_latency();
                     self.sh2_slave.execute(SH2_EXECUTION_SLICE_LEN, &mut bus);
                     self.slave_cycles = bus.cycle_counter + SH2_EXECUTION_SLICE_LEN;
                 }
@@ -124,6 +144,7 @@ impl Sega32X {
                 while self.master_cycles <= self.slave_cycles {
                     bus.which = WhichCpu::Master;
                     bus.cycle_counter = self.master_cycles;
+                    bus.sdram_cas_latency = self.sh2_master.sdram_cas_latency();
                     self.sh2_master.execute(SH2_EXECUTION_SLICE_LEN, &mut bus);
                     self.master_cycles = bus.cycle_counter + SH2_EXECUTION_SLICE_LEN;
                 }
@@ -141,6 +162,60 @@ impl Sega32X {
 
     pub fn take_rom_from(&mut self, other: &mut Self) {
         self.cartridge.rom.0 = mem::take(&mut other.cartridge.rom.0);
+
+        // Breakpoints belong to the attached debugger, not to the emulated state
+        self.sh2_master.take_debug_state_from(&mut other.sh2_master);
+        self.sh2_slave.take_debug_state_from(&mut other.sh2_slave);
+    }
+
+    pub fn sh2(&self, which: WhichCpu) -> &Sh2 {
+        match which {
+            WhichCpu::Master => &self.sh2_master,
+            WhichCpu::Slave => &self.sh2_slave,
+        }
+    }
+
+    pub fn sh2_mut(&mut self, which: WhichCpu) -> &mut Sh2 {
+        match which {
+            WhichCpu::Master => &mut self.sh2_master,
+            WhichCpu::Slave => &mut self.sh2_slave,
+        }
+    }
+
+    // Debugger memory access; only the cached and cache-through external address areas are visible
+    pub fn debug_read_byte(&self, which: WhichCpu, address: u32) -> Option<u8> {
+        if address >> 29 > 1 {
+            return None;
+        }
+
+        self.peek_sh2_byte(which, address & SH2_EXTERNAL_ADDRESS_MASK)
+    }
+
+    pub fn debug_write_byte(&mut self, which: WhichCpu, address: u32, value: u8) -> bool {
+        if address >> 29 > 1 {
+            return false;
+        }
+
+        self.debug_bus(which).write_byte(address & SH2_EXTERNAL_ADDRESS_MASK, value);
+        true
+    }
+
+    fn debug_bus(&mut self, which: WhichCpu) -> Sh2Bus<'_> {
+        let sdram_cas_latency = self.sh2(which).sdram_cas_latency();
+
+        Sh2Bus {
+            which,
+            cartridge: &mut self.cartridge,
+            vdp: &mut self.vdp,
+            pwm: &mut self.pwm,
+            registers: &mut self.registers,
+            sdram: &mut self.sdram,
+            serial: &mut self.serial,
+            event_log: &mut self.event_log,
+            cycle_counter: self.global_cycles,
+            sdram_cas_latency,
+            sdram_refresh_cycles: 0,
+        }
     }
 
     pub fn reload_config(&mut self, config: Sega32XEmulatorConfig) {
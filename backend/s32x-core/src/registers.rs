@@ -347,6 +347,47 @@ impl SystemRegisters {
         }
     }
 
+    /// Describe the effect of a 68000 write to a register that the 68000 and the SH-2s use to
+    /// communicate, for the event log. Should be called after the write has been applied.
+    pub fn describe_m68k_write(&self, address: u32) -> Option<String> {
+        match address & !1 {
+            0xA15100 => Some(format!(
+                "Adapter control: enabled={}, SH-2 reset={}, VDP access={}",
+                self.adapter_enabled, self.reset_sh2, self.vdp_access
+            )),
+            0xA15102 => Some(format!(
+                "Command interrupt: master={}, slave={}",
+                self.master_interrupts.command_pending, self.slave_interrupts.command_pending
+            )),
+            0xA15120..=0xA1512F => {
+                let idx = (address >> 1) & 0x7;
+                Some(format!(
+                    "Communication port {idx} = ${:04X}",
+                    self.m68k_read_communication_port(address)
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Describe the effect of an SH-2 write to a register that the 68000 and the SH-2s use to
+    /// communicate, for the event log. Should be called after the write has been applied.
+    pub fn describe_sh2_write(&self, address: u32, cycle_counter: u64) -> Option<String> {
+        match address & !1 {
+            0x4000 => Some(format!("Interrupt mask write, VDP access={}", self.vdp_access)),
+            0x4014 => Some("Cleared reset interrupt".into()),
+            0x401A => Some("Cleared command interrupt".into()),
+            0x4020..=0x402F => {
+                let idx = (address >> 1) & 0x7;
+                Some(format!(
+                    "Communication port {idx} = ${:04X}",
+                    self.sh2_read_communication_port(address, cycle_counter)
+                ))
+            }
+            _ => None,
+        }
+    }
+
     // 68000: $A15100
     fn read_adapter_control(&self) -> u16 {
         // TODO bit 7? (REN / reset enabled)
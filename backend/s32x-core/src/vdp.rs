@@ -9,7 +9,7 @@ use bincode::{Decode, Encode};
 use genesis_core::vdp::BorderSize;
 use jgenesis_common::frontend::{Color, FrameSize, PixelAspectRatio, Renderer, TimingMode};
 use jgenesis_common::num::{GetBit, U16Ext};
-use jgenesis_proc_macros::{FakeDecode, FakeEncode};
+use jgenesis_proc_macros::SaveState;
 use std::ops::{Deref, DerefMut, Range};
 
 const NTSC_SCANLINES_PER_FRAME: u16 = genesis_core::vdp::NTSC_SCANLINES_PER_FRAME;
@@ -38,7 +38,7 @@ const RGB_5_TO_8: &[u8; 32] = &[
     181, 189, 197, 206, 214, 222, 230, 239, 247, 255,
 ];
 
-#[derive(Debug, Clone, FakeEncode, FakeDecode)]
+#[derive(Debug, Clone)]
 struct H32FrameBuffer(Box<[Color; H32_FRAME_BUFFER_LEN]>);
 
 impl Default for H32FrameBuffer {
@@ -121,13 +121,14 @@ impl State {
     }
 }
 
-#[derive(Debug, Clone, Encode, Decode)]
+#[derive(Debug, Clone, SaveState)]
 pub struct Vdp {
     frame_buffer_0: Box<FrameBufferRam>,
     frame_buffer_1: Box<FrameBufferRam>,
     rendered_frame: Box<RenderedFrame>,
     // 1280x224 or 1280x240 (not including borders)
     // Needed for when a game enables H32 mode on the Genesis side (NFL Quarterback Club does this)
+    #[savestate(skip)]
     h32_frame_buffer: H32FrameBuffer,
     cram: Box<Cram>,
     registers: Registers,
@@ -446,6 +447,18 @@ impl Vdp {
         self.cram[((address & 0x1FF) >> 1) as usize] = value;
     }
 
+    pub fn copy_cram(&self, out: &mut [Color]) {
+        for (out_color, &color) in out.iter_mut().zip(self.cram.iter()) {
+            *out_color = u16_to_rgb(color);
+        }
+    }
+
+    /// Raw palette RAM contents, as big-endian words in the same layout that the SH-2s write them.
+    #[must_use]
+    pub fn dump_cram(&self) -> Vec<u8> {
+        self.cram.iter().flat_map(|word| word.to_be_bytes()).collect()
+    }
+
     // Interrupt mask bit 7: HEN (H interrupts enabled during VBlank)
     pub fn hen_bit(&self) -> bool {
         self.registers.h_interrupt_in_vblank
@@ -536,6 +549,13 @@ impl Vdp {
         // Default to rendering from the Genesis VDP frame buffer, switch later if necessary
         self.state.next_render_buffer = WhichFrameBuffer::Genesis;
 
+        if self.video_out == S32XVideoOut::S32XOnly {
+            // Hide everything from the Genesis VDP, including the borders and any lines outside of
+            // the 32X's active display area
+            let len = (genesis_frame_size.width * genesis_frame_size.height) as usize;
+            genesis_frame_buffer[..len].fill(Color::BLACK);
+        }
+
         if self.registers.frame_buffer_mode == FrameBufferMode::Blank
             || self.video_out == S32XVideoOut::GenesisOnly
         {
@@ -585,11 +605,10 @@ impl Vdp {
     ) {
         debug_assert!(genesis_frame_size.width == 256 + border_size.left + border_size.right);
 
-        let s32x_only = self.video_out == S32XVideoOut::S32XOnly;
+        // In 32X-only mode the Genesis frame buffer has already been cleared to black
+        self.copy_genesis_frame_buffer_to_h32(genesis_frame_buffer, genesis_frame_size);
 
-        if !s32x_only {
-            self.copy_genesis_frame_buffer_to_h32(genesis_frame_buffer, genesis_frame_size);
-        }
+        let s32x_only = self.video_out == S32XVideoOut::S32XOnly;
 
         let priority = self.registers.priority;
         let active_lines_per_frame: u32 =
@@ -3,21 +3,26 @@
 use crate::audio::AudioResampler;
 use crate::graphics::GraphicsCoprocessor;
 use crate::memory;
+pub use crate::memory::BackupRamConvertError;
 use crate::memory::{SegaCd, SubBus};
 use crate::rf5c164::Rf5c164;
 use bincode::{Decode, Encode};
 use cdrom::CdRomError;
-use cdrom::reader::{CdRom, CdRomFileFormat};
+use cdrom::reader::{CdRom, CdRomFileFormat, LoadProgress, SectorCacheConfig};
 use genesis_core::input::{GenesisButton, InputState};
 use genesis_core::memory::{MainBus, MainBusSignals, MainBusWrites, Memory};
 use genesis_core::timing::CycleCounters;
-use genesis_core::vdp::{Vdp, VdpTickEffect};
+use genesis_core::vdp::{DebugRegister, Vdp, VdpTickEffect};
 use genesis_core::ym2612::{Ym2612, YmTickEffect};
 use genesis_core::{GenesisEmulatorConfig, GenesisInputs, GenesisRegion};
+use jgenesis_common::eventlog::EventLog;
 use jgenesis_common::frontend::{
-    AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, PartialClone, Renderer, SaveWriter,
-    TickEffect, TimingMode,
+    AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, GameMetadata, PartialClone, Renderer,
+    SaveStateSafePoint, SaveWriter, TickEffect, TimingMode,
 };
+use jgenesis_common::instrument;
+use jgenesis_common::instrumentation::Subsystem;
+use jgenesis_common::memmap::MemoryMap;
 use jgenesis_proc_macros::{ConfigDisplay, EnumAll, EnumDisplay, EnumFromStr};
 use m68000_emu::M68000;
 use smsgg_core::psg::{Sn76489, Sn76489TickEffect, Sn76489Version};
@@ -29,12 +34,41 @@ use z80_emu::Z80;
 
 pub const DEFAULT_SUB_CPU_DIVIDER: u64 = 4;
 
+pub const DEFAULT_DISC_CACHE_SIZE_MB: u16 = 16;
+pub const DEFAULT_DISC_PREFETCH_SECTORS: u16 = 16;
+
 const NTSC_GENESIS_MASTER_CLOCK_RATE: u64 = 53_693_175;
 const PAL_GENESIS_MASTER_CLOCK_RATE: u64 = 53_203_424;
 pub const SEGA_CD_MASTER_CLOCK_RATE: u64 = 50_000_000;
 
 const BIOS_LEN: usize = memory::BIOS_LEN;
 
+// The BIOS loads the disc's initial program (IP) to the start of main RAM and jumps to it after the
+// boot animation. The IP begins with a region-specific security code that displays the license
+// screen before falling through to the game's own code
+const IP_ADDRESS: u32 = 0xFF0000;
+// The BIOS interrupt jump table is at the end of main RAM
+const BIOS_JUMP_TABLE_ADDRESS: u32 = 0xFFFD00;
+
+fn security_code_len(region: GenesisRegion) -> u32 {
+    match region {
+        GenesisRegion::Japan => 0x156,
+        GenesisRegion::Americas => 0x584,
+        GenesisRegion::Europe => 0x56E,
+    }
+}
+
+// Whether the main CPU is executing the game's own code rather than the BIOS or the IP's security
+// code: either from word RAM, or from main RAM between the security code and the BIOS jump table
+fn is_game_code(main_cpu_pc: u32, disc_region: GenesisRegion) -> bool {
+    // Main RAM is mirrored throughout $E00000-$FFFFFF
+    let pc = main_cpu_pc & 0xFFFFFF;
+    let pc = if pc >= 0xE00000 { 0xFF0000 | (pc & 0xFFFF) } else { pc };
+
+    let game_code_start = IP_ADDRESS + security_code_len(disc_region);
+    (0x200000..0x240000).contains(&pc) || (game_code_start..BIOS_JUMP_TABLE_ADDRESS).contains(&pc)
+}
+
 // Stall the main CPU for 2 out of every 172 mclk cycles instead of 2 out of 128 because this fixes
 // some tests in mcd-verificator.
 // I have no evidence that the main CPU actually does run faster with Sega CD compared to standalone
@@ -52,6 +86,13 @@ pub enum SegaCdLoadError {
     CdRom(#[from] CdRomError),
 }
 
+impl SegaCdLoadError {
+    #[must_use]
+    pub fn is_load_cancelled(&self) -> bool {
+        matches!(self, Self::CdRom(CdRomError::LoadCancelled))
+    }
+}
+
 pub type SegaCdLoadResult<T> = Result<T, SegaCdLoadError>;
 
 #[derive(Debug, Error)]
@@ -92,13 +133,27 @@ pub enum PcmLowPassFilter {
     SegaCd,
 }
 
+/// Memory regions that can be dumped and restored for debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumAll)]
+pub enum MemoryRegion {
+    WorkRam,
+    Vram,
+    PrgRam,
+    WordRam,
+    BackupRam,
+}
+
 #[derive(Debug, Clone, Copy, Encode, Decode, ConfigDisplay)]
 pub struct SegaCdEmulatorConfig {
     #[cfg_display(skip)]
     pub genesis: GenesisEmulatorConfig,
     pub pcm_interpolation: PcmInterpolation,
     pub enable_ram_cartridge: bool,
+    pub backup_ram_write_protected: bool,
     pub load_disc_into_ram: bool,
+    pub disc_cache_enabled: bool,
+    pub disc_cache_size_mb: u16,
+    pub disc_prefetch_sectors: u16,
     pub disc_drive_speed: NonZeroU16,
     pub sub_cpu_divider: NonZeroU64,
     pub pcm_low_pass: PcmLowPassFilter,
@@ -108,6 +163,17 @@ pub struct SegaCdEmulatorConfig {
     pub cd_audio_enabled: bool,
 }
 
+impl SegaCdEmulatorConfig {
+    pub(crate) fn sector_cache_config(&self) -> Option<SectorCacheConfig> {
+        self.disc_cache_enabled.then(|| {
+            SectorCacheConfig::from_size_mb(
+                self.disc_cache_size_mb.into(),
+                self.disc_prefetch_sectors.into(),
+            )
+        })
+    }
+}
+
 impl EmulatorConfigTrait for SegaCdEmulatorConfig {
     fn with_overclocking_disabled(&self) -> Self {
         Self {
@@ -117,6 +183,15 @@ impl EmulatorConfigTrait for SegaCdEmulatorConfig {
             ..*self
         }
     }
+
+    fn with_enhancements_disabled(&self) -> Self {
+        Self {
+            genesis: self.genesis.with_enhancements_disabled(),
+            pcm_enabled: true,
+            cd_audio_enabled: true,
+            ..self.with_overclocking_disabled()
+        }
+    }
 }
 
 #[derive(Debug, Encode, Decode, PartialClone)]
@@ -135,7 +210,7 @@ pub struct SegaCdEmulator {
     audio_resampler: AudioResampler,
     timing_mode: TimingMode,
     main_bus_writes: MainBusWrites,
-    disc_title: String,
+    disc_metadata: Option<GameMetadata>,
     cycles: SegaCdCycleCounters,
     sega_cd_mclk_cycles: u64,
     sega_cd_mclk_cycle_product: u64,
@@ -171,7 +246,6 @@ impl SegaCdEmulator {
     /// * Unable to read the given CUE or CHD file
     /// * Unable to read every BIN file that is referenced in the CUE file
     /// * Unable to read boot information from the beginning of the CD-ROM data track
-    #[allow(clippy::if_then_some_else_none)]
     pub fn create<P: AsRef<Path>, S: SaveWriter>(
         bios: Vec<u8>,
         rom_path: P,
@@ -179,10 +253,37 @@ impl SegaCdEmulator {
         run_without_disc: bool,
         emulator_config: SegaCdEmulatorConfig,
         save_writer: &mut S,
+    ) -> SegaCdLoadResult<Self> {
+        Self::create_with_progress(
+            bios,
+            rom_path,
+            format,
+            run_without_disc,
+            emulator_config,
+            save_writer,
+            &LoadProgress::new(),
+        )
+    }
+
+    /// Same as [`Self::create`], but reports progress through the given progress tracker while
+    /// loading the disc into memory. Loading can be cancelled through the progress tracker.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::create`], and additionally returns an error if loading is cancelled.
+    #[allow(clippy::if_then_some_else_none)]
+    pub fn create_with_progress<P: AsRef<Path>, S: SaveWriter>(
+        bios: Vec<u8>,
+        rom_path: P,
+        format: CdRomFileFormat,
+        run_without_disc: bool,
+        emulator_config: SegaCdEmulatorConfig,
+        save_writer: &mut S,
+        load_progress: &LoadProgress,
     ) -> SegaCdLoadResult<Self> {
         let disc = if !run_without_disc {
             Some(if emulator_config.load_disc_into_ram {
-                CdRom::open_in_memory(rom_path, format)?
+                CdRom::open_in_memory_with_progress(rom_path, format, load_progress)?
             } else {
                 CdRom::open(rom_path, format)?
             })
@@ -210,6 +311,34 @@ impl SegaCdEmulator {
         Self::create_from_disc(bios, Some(disc), emulator_config, save_writer)
     }
 
+    /// Read the title, serial number, and region from a disc's header without creating an emulator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if unable to open the CUE or CHD file or to read the first data sector.
+    pub fn read_disc_metadata<P: AsRef<Path>>(
+        rom_path: P,
+        format: CdRomFileFormat,
+    ) -> SegaCdLoadResult<GameMetadata> {
+        let mut disc = CdRom::open(rom_path, format)?;
+        let region = memory::parse_disc_region(&mut disc)?;
+        memory::read_disc_metadata(&mut disc, region)
+    }
+
+    /// Read the 512-byte boot header from the start of a disc's data track, e.g. to identify the
+    /// game by hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if unable to open the CUE or CHD file or to read the first data sector.
+    pub fn read_disc_boot_header<P: AsRef<Path>>(
+        rom_path: P,
+        format: CdRomFileFormat,
+    ) -> SegaCdLoadResult<Vec<u8>> {
+        let mut disc = CdRom::open(rom_path, format)?;
+        memory::read_disc_boot_header(&mut disc)
+    }
+
     fn create_from_disc<S: SaveWriter>(
         bios: Vec<u8>,
         disc: Option<CdRom>,
@@ -224,7 +353,7 @@ impl SegaCdEmulator {
         let initial_ram_cartridge = save_writer.load_bytes("ramc").ok();
         let mut sega_cd =
             SegaCd::new(bios, disc, initial_backup_ram, initial_ram_cartridge, &emulator_config)?;
-        let disc_title = sega_cd.disc_title()?.unwrap_or("(no disc)".into());
+        let disc_metadata = sega_cd.disc_metadata()?;
 
         let memory = Memory::new(sega_cd);
         let timing_mode =
@@ -265,7 +394,7 @@ impl SegaCdEmulator {
             audio_resampler,
             timing_mode,
             main_bus_writes: MainBusWrites::new(),
-            disc_title,
+            disc_metadata,
             cycles: SegaCdCycleCounters::new(emulator_config.genesis.clamped_m68k_divider()),
             sega_cd_mclk_cycles: 0,
             sega_cd_mclk_cycle_product: 0,
@@ -315,14 +444,63 @@ impl SegaCdEmulator {
         )
     }
 
-    #[must_use]
-    pub fn disc_title(&self) -> &str {
-        &self.disc_title
-    }
-
     pub fn remove_disc(&mut self) {
         self.memory.medium_mut().remove_disc();
-        self.disc_title = "(no disc)".into();
+        self.disc_metadata = None;
+    }
+
+    /// Erase internal backup RAM and write an empty filesystem to it. This ignores the backup RAM
+    /// write-protect setting.
+    pub fn format_backup_ram(&mut self) {
+        self.memory.medium_mut().format_backup_ram();
+    }
+
+    /// Replace internal backup RAM with a backup RAM image from another source, e.g. a Genesis Plus
+    /// GX `scd_*.brm` file. Images of other sizes are converted to 8KB if the files fit.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the image is not a formatted backup RAM image or if its
+    /// contents do not fit in 8KB.
+    pub fn import_backup_ram(&mut self, image: &[u8]) -> Result<(), BackupRamConvertError> {
+        let backup_ram = memory::resize_backup_ram(image, memory::BACKUP_RAM_LEN)?;
+        self.memory.medium_mut().restore_backup_ram(&backup_ram);
+
+        Ok(())
+    }
+
+    /// Replace RAM cartridge contents with a backup RAM image from another source. Genesis Plus GX
+    /// emulates a 512KB RAM cartridge (`cart.brm`), which is converted to 128KB if the files fit.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the image is not a formatted backup RAM image or if its
+    /// contents do not fit in 128KB.
+    pub fn import_ram_cartridge(&mut self, image: &[u8]) -> Result<(), BackupRamConvertError> {
+        let ram_cartridge = memory::resize_backup_ram(image, memory::RAM_CARTRIDGE_LEN)?;
+        self.memory.medium_mut().restore_ram_cartridge(&ram_cartridge);
+
+        Ok(())
+    }
+
+    /// Export internal backup RAM as an image of the given size. 8KB images are a direct copy.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if `len` is not a supported backup RAM size or if the
+    /// current contents do not fit.
+    pub fn export_backup_ram(&self, len: usize) -> Result<Vec<u8>, BackupRamConvertError> {
+        memory::resize_backup_ram(self.memory.medium().backup_ram(), len)
+    }
+
+    /// Export the RAM cartridge as an image of the given size, e.g. 512KB for Genesis Plus GX.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if `len` is not a supported backup RAM size or if the
+    /// current contents do not fit.
+    pub fn export_ram_cartridge(&self, len: usize) -> Result<Vec<u8>, BackupRamConvertError> {
+        memory::resize_backup_ram(self.memory.medium().ram_cartridge(), len)
     }
 
     /// # Errors
@@ -334,8 +512,8 @@ impl SegaCdEmulator {
         format: CdRomFileFormat,
     ) -> SegaCdLoadResult<()> {
         let sega_cd = self.memory.medium_mut();
-        sega_cd.change_disc(rom_path, format, self.config.load_disc_into_ram)?;
-        self.disc_title = sega_cd.disc_title()?.unwrap_or_else(|| "(no disc)".into());
+        sega_cd.change_disc(rom_path, format, &self.config)?;
+        self.disc_metadata = sega_cd.disc_metadata()?;
 
         Ok(())
     }
@@ -344,13 +522,102 @@ impl SegaCdEmulator {
         self.vdp.copy_cram(out);
     }
 
+    #[must_use]
+    pub fn dump_cram(&self) -> Vec<u8> {
+        self.vdp.dump_cram()
+    }
+
     pub fn copy_vram(&self, out: &mut [Color], palette: u8, row_len: usize) {
         self.vdp.copy_vram(out, palette, row_len);
     }
 
+    /// Describe the main 68000, Z80, and sub 68000 memory maps, including the PRG RAM bank and
+    /// word RAM mode.
+    #[must_use]
+    pub fn memory_map_description(&self) -> Vec<MemoryMap> {
+        vec![
+            self.memory.memory_map(),
+            self.memory.z80_memory_map(),
+            self.memory.medium().sub_cpu_memory_map(),
+        ]
+    }
+
+    /// Log of handshake events between the main 68000, sub 68000, and Z80, e.g. communication
+    /// register writes and word RAM ownership changes. Disabled by default.
+    #[must_use]
+    pub fn event_log(&self) -> &EventLog {
+        self.memory.event_log()
+    }
+
+    pub fn event_log_mut(&mut self) -> &mut EventLog {
+        self.memory.event_log_mut()
+    }
+
+    #[must_use]
+    pub fn dump_memory(&self, region: MemoryRegion) -> Vec<u8> {
+        let sega_cd = self.memory.medium();
+        match region {
+            MemoryRegion::WorkRam => self.memory.main_ram().to_vec(),
+            MemoryRegion::Vram => self.vdp.dump_vram(),
+            MemoryRegion::PrgRam => sega_cd.prg_ram().to_vec(),
+            MemoryRegion::WordRam => sega_cd.word_ram().raw().to_vec(),
+            MemoryRegion::BackupRam => sega_cd.backup_ram().to_vec(),
+        }
+    }
+
+    /// Overwrite a memory region, e.g. with the contents of a file previously written using
+    /// [`Self::dump_memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not the same length as the memory region.
+    pub fn restore_memory(&mut self, region: MemoryRegion, bytes: &[u8]) {
+        let sega_cd = self.memory.medium_mut();
+        match region {
+            MemoryRegion::WorkRam => self.memory.main_ram_mut().copy_from_slice(bytes),
+            MemoryRegion::Vram => self.vdp.restore_vram(bytes),
+            MemoryRegion::PrgRam => sega_cd.prg_ram_mut().copy_from_slice(bytes),
+            MemoryRegion::WordRam => sega_cd.word_ram_mut().raw_mut().copy_from_slice(bytes),
+            MemoryRegion::BackupRam => sega_cd.restore_backup_ram(bytes),
+        }
+    }
+
     pub fn dump_vdp_registers(&self, callback: impl FnMut(&str, &[(&str, &str)])) {
         self.vdp.dump_registers(callback);
     }
+
+    #[must_use]
+    pub fn vdp_debug_register(&self) -> DebugRegister {
+        self.vdp.debug_register()
+    }
+
+    pub fn set_vdp_debug_register(&mut self, debug_register: DebugRegister) {
+        self.vdp.set_debug_register(debug_register);
+    }
+
+    /// Mute or unmute one of the 6 YM2612 channels in the audio output.
+    pub fn set_ym2612_channel_muted(&mut self, channel: usize, muted: bool) {
+        self.ym2612.set_channel_muted(channel, muted);
+    }
+
+    /// Whether one of the 6 YM2612 channels is currently muted. Mutes are not saved in save states,
+    /// so loading a state unmutes every channel.
+    #[must_use]
+    pub fn is_ym2612_channel_muted(&self, channel: usize) -> bool {
+        self.ym2612.is_channel_muted(channel)
+    }
+
+    /// Mute or unmute one of the 4 PSG channels in the audio output (0-2 square, 3 noise).
+    pub fn set_psg_channel_muted(&mut self, channel: usize, muted: bool) {
+        self.psg.set_channel_muted(channel, muted);
+    }
+
+    /// Whether one of the 4 PSG channels is currently muted. Mutes are not saved in save states, so
+    /// loading a state unmutes every channel.
+    #[must_use]
+    pub fn is_psg_channel_muted(&self, channel: usize) -> bool {
+        self.psg.is_channel_muted(channel)
+    }
 }
 
 impl EmulatorTrait for SegaCdEmulator {
@@ -385,7 +652,7 @@ impl EmulatorTrait for SegaCdEmulator {
         let main_cpu_cycles = if self.cycles.m68k_wait_cpu_cycles != 0 {
             self.cycles.take_m68k_wait_cpu_cycles()
         } else {
-            self.main_cpu.execute_instruction(&mut main_bus)
+            instrument!(Subsystem::Cpu, self.main_cpu.execute_instruction(&mut main_bus))
         };
         let genesis_mclk_elapsed = self.cycles.record_68k_instruction(
             main_cpu_cycles,
@@ -394,7 +661,8 @@ impl EmulatorTrait for SegaCdEmulator {
 
         // Z80
         while self.cycles.should_tick_z80() {
-            self.z80.tick(&mut main_bus);
+            instrument!(Subsystem::Cpu, self.z80.tick(&mut main_bus));
+            main_bus.set_z80_stalled(self.z80.stalled());
             self.cycles.decrement_z80();
         }
 
@@ -404,6 +672,8 @@ impl EmulatorTrait for SegaCdEmulator {
 
         self.main_bus_writes = main_bus.take_writes();
 
+        self.memory.event_log_mut().advance(genesis_mclk_elapsed);
+
         self.sega_cd_mclk_cycle_product += genesis_mclk_elapsed * SEGA_CD_MASTER_CLOCK_RATE;
         let scd_mclk_elapsed = match self.timing_mode {
             TimingMode::Ntsc => {
@@ -456,7 +726,7 @@ impl EmulatorTrait for SegaCdEmulator {
         }
 
         // Sub 68000
-        self.tick_sub_cpu(sub_cpu_cycles);
+        instrument!(Subsystem::Cpu, self.tick_sub_cpu(sub_cpu_cycles));
 
         // Apply main CPU writes after ticking the sub CPU; this fixes random freezing in Silpheed
         self.main_bus_writes = new_main_bus!(self, m68k_reset: false).apply_writes();
@@ -466,7 +736,7 @@ impl EmulatorTrait for SegaCdEmulator {
 
         // PSG
         while self.cycles.should_tick_psg() {
-            if self.psg.tick() == Sn76489TickEffect::Clocked {
+            if instrument!(Subsystem::Apu, self.psg.tick()) == Sn76489TickEffect::Clocked {
                 // PSG output is mono in Genesis; stereo output is only for Game Gear
                 let (psg_sample, _) = self.psg.sample();
                 self.audio_resampler.collect_psg_sample(psg_sample);
@@ -476,7 +746,7 @@ impl EmulatorTrait for SegaCdEmulator {
 
         // YM2612
         while self.cycles.should_tick_ym2612() {
-            if self.ym2612.tick() == YmTickEffect::OutputSample {
+            if instrument!(Subsystem::Apu, self.ym2612.tick()) == YmTickEffect::OutputSample {
                 let (ym2612_sample_l, ym2612_sample_r) = self.ym2612.sample();
                 self.audio_resampler.collect_ym2612_sample(ym2612_sample_l, ym2612_sample_r);
             }
@@ -484,16 +754,22 @@ impl EmulatorTrait for SegaCdEmulator {
         }
 
         // RF5C164
-        self.pcm.tick(pcm_cycles, |(pcm_sample_l, pcm_sample_r)| {
-            self.audio_resampler.collect_pcm_sample(pcm_sample_l, pcm_sample_r);
-        });
+        instrument!(
+            Subsystem::Apu,
+            self.pcm.tick(pcm_cycles, |(pcm_sample_l, pcm_sample_r)| {
+                self.audio_resampler.collect_pcm_sample(pcm_sample_l, pcm_sample_r);
+            })
+        );
 
         // Output any audio samples that are queued up
+        self.audio_resampler.advance_timeline(genesis_mclk_elapsed);
         self.audio_resampler.output_samples(audio_output).map_err(SegaCdError::Audio)?;
 
         // VDP
         let mut tick_effect = TickEffect::None;
-        if self.vdp.tick(genesis_mclk_elapsed, &mut self.memory) == VdpTickEffect::FrameComplete {
+        if instrument!(Subsystem::Ppu, self.vdp.tick(genesis_mclk_elapsed, &mut self.memory))
+            == VdpTickEffect::FrameComplete
+        {
             self.render_frame(renderer).map_err(SegaCdError::Render)?;
 
             self.input.set_inputs(*inputs);
@@ -525,6 +801,21 @@ impl EmulatorTrait for SegaCdEmulator {
         self.render_frame(renderer)
     }
 
+    fn is_booting(&self) -> bool {
+        let sega_cd = self.memory.medium();
+        if !sega_cd.has_disc() {
+            // BIOS will sit in its menu forever
+            return false;
+        }
+
+        !is_game_code(self.main_cpu.pc(), sega_cd.disc_region())
+    }
+
+    fn clear_audio_mutes(&mut self) {
+        self.ym2612.clear_muted_channels();
+        self.psg.clear_muted_channels();
+    }
+
     fn reload_config(&mut self, config: &Self::Config) {
         self.vdp.reload_config(config.genesis.to_vdp_config());
         self.ym2612.reload_config(config.genesis);
@@ -544,6 +835,13 @@ impl EmulatorTrait for SegaCdEmulator {
         self.memory.medium_mut().take_rom_from(other.memory.medium_mut());
     }
 
+    fn game_metadata(&mut self) -> GameMetadata {
+        self.disc_metadata.clone().unwrap_or_else(|| GameMetadata {
+            title: Some("(no disc)".into()),
+            ..GameMetadata::default()
+        })
+    }
+
     fn soft_reset(&mut self) {
         // Reset main CPU
         self.main_cpu.execute_instruction(&mut new_main_bus!(self, m68k_reset: true));
@@ -564,6 +862,11 @@ impl EmulatorTrait for SegaCdEmulator {
             .expect("Hard reset should not cause an I/O error");
     }
 
+    fn save_state_safe_point() -> SaveStateSafePoint {
+        // The in-progress Genesis VDP frame is not saved
+        SaveStateSafePoint::FrameBoundary
+    }
+
     fn target_fps(&self) -> f64 {
         genesis_core::target_framerate(self.timing_mode)
     }
@@ -572,3 +875,74 @@ impl EmulatorTrait for SegaCdEmulator {
         self.audio_resampler.update_output_frequency(output_frequency);
     }
 }
+
+#[cfg(test)]
+pub(crate) fn test_config() -> SegaCdEmulatorConfig {
+    use genesis_core::{GenesisAspectRatio, GenesisControllerType, GenesisLowPassFilter};
+
+    SegaCdEmulatorConfig {
+        genesis: GenesisEmulatorConfig {
+            p1_controller_type: GenesisControllerType::default(),
+            p2_controller_type: GenesisControllerType::default(),
+            forced_timing_mode: None,
+            forced_region: Some(GenesisRegion::Americas),
+            aspect_ratio: GenesisAspectRatio::default(),
+            adjust_aspect_ratio_in_2x_resolution: true,
+            remove_sprite_limits: false,
+            m68k_clock_divider: genesis_core::timing::NATIVE_M68K_DIVIDER,
+            emulate_non_linear_vdp_dac: false,
+            expanded_vram: false,
+            deinterlace: true,
+            render_vertical_border: false,
+            render_horizontal_border: false,
+            plane_a_enabled: true,
+            plane_b_enabled: true,
+            sprites_enabled: true,
+            window_enabled: true,
+            backdrop_enabled: true,
+            quantize_ym2612_output: true,
+            emulate_ym2612_ladder_effect: true,
+            low_pass: GenesisLowPassFilter::default(),
+            ym2612_enabled: true,
+            psg_enabled: true,
+        },
+        pcm_interpolation: PcmInterpolation::default(),
+        enable_ram_cartridge: true,
+        backup_ram_write_protected: false,
+        load_disc_into_ram: false,
+        disc_cache_enabled: false,
+        disc_cache_size_mb: 0,
+        disc_prefetch_sectors: 0,
+        disc_drive_speed: NonZeroU16::new(1).unwrap(),
+        sub_cpu_divider: NonZeroU64::new(DEFAULT_SUB_CPU_DIVIDER).unwrap(),
+        pcm_low_pass: PcmLowPassFilter::default(),
+        apply_genesis_lpf_to_pcm: false,
+        apply_genesis_lpf_to_cd_da: false,
+        pcm_enabled: true,
+        cd_audio_enabled: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_code_detection() {
+        // BIOS ROM and the boot animation
+        assert!(!is_game_code(0x000400, GenesisRegion::Americas));
+        // IP security code, which differs in length by region
+        assert!(!is_game_code(IP_ADDRESS, GenesisRegion::Americas));
+        assert!(!is_game_code(IP_ADDRESS + 0x200, GenesisRegion::Americas));
+        assert!(is_game_code(IP_ADDRESS + 0x200, GenesisRegion::Japan));
+        // Game code in main RAM, including mirrors and 24-bit address wraparound
+        assert!(is_game_code(IP_ADDRESS + 0x584, GenesisRegion::Americas));
+        assert!(is_game_code(0xE01000, GenesisRegion::Europe));
+        assert!(is_game_code(0xFFFF1000, GenesisRegion::Europe));
+        // BIOS jump table at the end of main RAM
+        assert!(!is_game_code(BIOS_JUMP_TABLE_ADDRESS, GenesisRegion::Americas));
+        // Word RAM
+        assert!(is_game_code(0x200000, GenesisRegion::Americas));
+        assert!(!is_game_code(0x240000, GenesisRegion::Americas));
+    }
+}
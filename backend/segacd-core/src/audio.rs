@@ -8,8 +8,8 @@ use crate::api::{PcmLowPassFilter, SegaCdEmulatorConfig};
 use bincode::{Decode, Encode};
 use genesis_core::GenesisLowPassFilter;
 use genesis_core::audio::{GenesisAudioFilter, Ym2612Resampler};
-use jgenesis_common::audio::FirResampler;
 use jgenesis_common::audio::iir::{FirstOrderIirFilter, SecondOrderIirFilter};
+use jgenesis_common::audio::{DEFAULT_OUTPUT_FREQUENCY, FirResampler};
 use jgenesis_common::frontend::{AudioOutput, TimingMode};
 use smsgg_core::audio::PsgResampler;
 use std::cmp;
@@ -28,6 +28,14 @@ const PCM_COEFFICIENT: f64 = 0.35481338923357547;
 // -7 dB (10 ^ -7/20)
 const CD_COEFFICIENT: f64 = 0.44668359215096315;
 
+// How much to speed up or slow down a source's resampling per output sample that its resampler is
+// ahead of or behind the shared output timeline. Each resampler is normally within a sample or two
+// of the timeline depending on when its most recent input sample arrived
+const DRIFT_CORRECTION_PER_SAMPLE: f64 = 0.0002;
+
+// Maximum resampling ratio correction. A 0.5% change in pitch (under 9 cents) is not audible
+const MAX_DRIFT_CORRECTION: f64 = 0.005;
+
 type PcmResampler = FirResampler<{ constants::PCM_LPF_TAPS }, { constants::PCM_ZERO_PADDING }>;
 type CdResampler = FirResampler<{ constants::CD_LPF_TAPS }, { constants::CD_ZERO_PADDING }>;
 
@@ -145,6 +153,38 @@ impl SegaCdAudioFilter {
     }
 }
 
+// Tracks how many output samples should have been generated based on how much emulated time has
+// passed, which is the same for every audio source regardless of its sample rate
+#[derive(Debug, Clone, Encode, Decode)]
+struct OutputTimeline {
+    genesis_mclk_frequency: u64,
+    output_frequency: u64,
+    sample_product: u64,
+    pending_samples: u64,
+}
+
+impl OutputTimeline {
+    fn new(genesis_mclk_frequency: u64) -> Self {
+        Self {
+            genesis_mclk_frequency,
+            output_frequency: DEFAULT_OUTPUT_FREQUENCY,
+            sample_product: 0,
+            pending_samples: 0,
+        }
+    }
+
+    fn advance(&mut self, genesis_mclk_cycles: u64) {
+        self.sample_product += genesis_mclk_cycles * self.output_frequency;
+        let samples = self.sample_product / self.genesis_mclk_frequency;
+        self.sample_product -= samples * self.genesis_mclk_frequency;
+        self.pending_samples += samples;
+    }
+
+    fn consume(&mut self, samples: u64) {
+        self.pending_samples = self.pending_samples.saturating_sub(samples);
+    }
+}
+
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct AudioResampler {
     gen_filter: GenesisAudioFilter,
@@ -153,6 +193,7 @@ pub struct AudioResampler {
     psg_resampler: PsgResampler,
     pcm_resampler: PcmResampler,
     cd_resampler: CdResampler,
+    timeline: OutputTimeline,
     ym2612_enabled: bool,
     psg_enabled: bool,
     pcm_enabled: bool,
@@ -165,6 +206,7 @@ impl AudioResampler {
             TimingMode::Ntsc => NTSC_GENESIS_MCLK_FREQUENCY,
             TimingMode::Pal => PAL_GENESIS_MCLK_FREQUENCY,
         };
+        let timeline = OutputTimeline::new(genesis_mclk_frequency as u64);
 
         let ym2612_resampler = genesis_core::audio::new_ym2612_resampler(genesis_mclk_frequency);
         let psg_resampler = smsgg_core::audio::new_psg_resampler(genesis_mclk_frequency);
@@ -178,6 +220,7 @@ impl AudioResampler {
             psg_resampler,
             pcm_resampler,
             cd_resampler,
+            timeline,
             ym2612_enabled: config.genesis.ym2612_enabled,
             psg_enabled: config.genesis.psg_enabled,
             pcm_enabled: config.pcm_enabled,
@@ -205,6 +248,12 @@ impl AudioResampler {
         self.cd_resampler.collect_sample(sample_l, sample_r);
     }
 
+    /// Advance the shared output timeline. Should be called once per tick with the same elapsed
+    /// time used to clock all of the audio sources.
+    pub fn advance_timeline(&mut self, genesis_mclk_cycles: u64) {
+        self.timeline.advance(genesis_mclk_cycles);
+    }
+
     pub fn output_samples<A: AudioOutput>(&mut self, audio_output: &mut A) -> Result<(), A::Err> {
         let sample_count = cmp::min(
             cmp::min(
@@ -248,9 +297,24 @@ impl AudioResampler {
             audio_output.push_sample(sample_l, sample_r)?;
         }
 
+        self.timeline.consume(sample_count as u64);
+        self.realign_to_timeline();
+
         Ok(())
     }
 
+    // Mixing only outputs as many samples as the source with the fewest buffered samples, so if
+    // one source falls behind (or gets ahead), every source after it in the mix would be
+    // permanently offset. Continuously nudge each source's resampling ratio towards the shared
+    // timeline
+    fn realign_to_timeline(&mut self) {
+        let target_len = self.timeline.pending_samples;
+        realign_resampler(&mut self.ym2612_resampler, target_len);
+        realign_resampler(&mut self.psg_resampler, target_len);
+        realign_resampler(&mut self.pcm_resampler, target_len);
+        realign_resampler(&mut self.cd_resampler, target_len);
+    }
+
     pub fn reload_config(&mut self, config: SegaCdEmulatorConfig) {
         self.ym2612_enabled = config.genesis.ym2612_enabled;
         self.psg_enabled = config.genesis.psg_enabled;
@@ -262,6 +326,7 @@ impl AudioResampler {
     }
 
     pub fn update_output_frequency(&mut self, output_frequency: u64) {
+        self.timeline.output_frequency = output_frequency;
         self.ym2612_resampler.update_output_frequency(output_frequency);
         self.psg_resampler.update_output_frequency(output_frequency);
         self.pcm_resampler.update_output_frequency(output_frequency);
@@ -272,3 +337,86 @@ impl AudioResampler {
 fn check_enabled(sample: (f64, f64), enabled: bool) -> (f64, f64) {
     if enabled { sample } else { (0.0, 0.0) }
 }
+
+fn realign_resampler<const LPF_TAPS: usize, const ZERO_PADDING: usize>(
+    resampler: &mut FirResampler<LPF_TAPS, ZERO_PADDING>,
+    target_len: u64,
+) {
+    // A resampler that is ahead should generate fewer output samples per input sample, which is
+    // the same as resampling from a higher source frequency
+    let drift = resampler.output_buffer_len() as f64 - target_len as f64;
+    let correction =
+        (drift * DRIFT_CORRECTION_PER_SAMPLE).clamp(-MAX_DRIFT_CORRECTION, MAX_DRIFT_CORRECTION);
+    resampler.adjust_source_frequency(1.0 + correction);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_timeline() {
+        let mclk_frequency = NTSC_GENESIS_MCLK_FREQUENCY as u64;
+        let mut timeline = OutputTimeline::new(mclk_frequency);
+
+        // One second in uneven chunks should produce exactly one second of output samples
+        let mut remaining = mclk_frequency;
+        while remaining != 0 {
+            let chunk = remaining.min(1234);
+            timeline.advance(chunk);
+            remaining -= chunk;
+        }
+        assert_eq!(timeline.pending_samples, DEFAULT_OUTPUT_FREQUENCY);
+
+        timeline.consume(DEFAULT_OUTPUT_FREQUENCY - 5);
+        assert_eq!(timeline.pending_samples, 5);
+        timeline.consume(10);
+        assert_eq!(timeline.pending_samples, 0);
+    }
+
+    #[test]
+    fn realignment_keeps_drift_bounded() {
+        const FRAMES: u32 = 20 * 60;
+        const MAX_DRIFT: f64 = 16.0;
+
+        let mclk_frequency = NTSC_GENESIS_MCLK_FREQUENCY as u64;
+        let mclk_per_frame = mclk_frequency / 60;
+        let mut timeline = OutputTimeline::new(mclk_frequency);
+        let mut resampler = new_cd_resampler();
+
+        // Simulate a CD-DA source that runs 0.2% fast relative to the Genesis clock, starting 100
+        // samples ahead of the timeline
+        let cd_samples_per_frame = CD_DA_FREQUENCY * 1.002 / 60.0;
+        for _ in 0..100 * 44_100 / 48_000 {
+            resampler.collect_sample(0.0, 0.0);
+        }
+
+        let mut cd_samples = 0.0;
+        let mut max_steady_state_drift: f64 = 0.0;
+        for frame in 0..FRAMES {
+            timeline.advance(mclk_per_frame);
+
+            cd_samples += cd_samples_per_frame;
+            while cd_samples >= 1.0 {
+                resampler.collect_sample(0.0, 0.0);
+                cd_samples -= 1.0;
+            }
+
+            // Consume samples the same way as mixing with a source that is exactly on the timeline
+            let sample_count =
+                cmp::min(resampler.output_buffer_len() as u64, timeline.pending_samples);
+            for _ in 0..sample_count {
+                resampler.output_buffer_pop_front();
+            }
+            timeline.consume(sample_count);
+            realign_resampler(&mut resampler, timeline.pending_samples);
+
+            let drift = resampler.output_buffer_len() as f64 - timeline.pending_samples as f64;
+            if frame >= 60 {
+                max_steady_state_drift = max_steady_state_drift.max(drift.abs());
+            }
+        }
+
+        assert!(max_steady_state_drift <= MAX_DRIFT, "drift was {max_steady_state_drift} samples");
+    }
+}
@@ -3,16 +3,15 @@
 pub mod cdc;
 pub mod cdd;
 
+use crate::api;
 use crate::api::{SegaCdEmulatorConfig, SegaCdLoadResult};
 use crate::cddrive::cdc::RchipDmaArgs;
-use crate::memory::wordram::WordRam;
-use crate::rf5c164::Rf5c164;
-use crate::{api, memory};
 use bincode::{Decode, Encode};
 use cdc::Rchip;
 use cdd::CdDrive;
 use cdrom::reader::CdRom;
 use genesis_core::GenesisRegion;
+use jgenesis_common::frontend::GameMetadata;
 use jgenesis_proc_macros::PartialClone;
 use std::array;
 
@@ -70,19 +69,12 @@ impl CdController {
     pub fn tick(
         &mut self,
         mclk_cycles: u64,
-        word_ram: &mut WordRam,
-        prg_ram: &mut [u8; memory::PRG_RAM_LEN],
-        prg_ram_accessible: bool,
-        pcm: &mut Rf5c164,
+        mut rchip_dma_args: RchipDmaArgs<'_>,
         mut audio_callback: impl FnMut(f64, f64),
     ) -> SegaCdLoadResult<()> {
         self.prescaler.tick(mclk_cycles, || {
-            let (sample_l, sample_r) = self.drive.clock_44100hz(&mut self.rchip, RchipDmaArgs {
-                word_ram,
-                prg_ram,
-                prg_ram_accessible,
-                pcm,
-            })?;
+            let (sample_l, sample_r) =
+                self.drive.clock_44100hz(&mut self.rchip, rchip_dma_args.reborrow())?;
 
             audio_callback(sample_l, sample_r);
 
@@ -106,8 +98,15 @@ impl CdController {
         &mut self.rchip
     }
 
-    pub fn disc_title(&mut self, region: GenesisRegion) -> SegaCdLoadResult<Option<String>> {
-        self.drive.disc_title(region)
+    pub fn disc_metadata(
+        &mut self,
+        region: GenesisRegion,
+    ) -> SegaCdLoadResult<Option<GameMetadata>> {
+        self.drive.disc_metadata(region)
+    }
+
+    pub fn has_disc(&self) -> bool {
+        self.drive.has_disc()
     }
 
     pub fn take_disc(&mut self) -> Option<CdRom> {
@@ -73,6 +73,7 @@ pub struct RchipDmaArgs<'a> {
     pub word_ram: &'a mut WordRam,
     pub prg_ram: &'a mut [u8; memory::PRG_RAM_LEN],
     pub prg_ram_accessible: bool,
+    pub prg_ram_write_protect_boundary: u32,
     pub pcm: &'a mut Rf5c164,
 }
 
@@ -82,6 +83,7 @@ impl RchipDmaArgs<'_> {
             word_ram: &mut *self.word_ram,
             prg_ram: &mut *self.prg_ram,
             prg_ram_accessible: self.prg_ram_accessible,
+            prg_ram_write_protect_boundary: self.prg_ram_write_protect_boundary,
             pcm: &mut *self.pcm,
         }
     }
@@ -100,6 +102,12 @@ pub struct Rchip {
     dma_address: u32,
     decoder_enabled: bool,
     decoder_writes_enabled: bool,
+    auto_mode_detection: bool,
+    mode_2_requested: bool,
+    form_2_requested: bool,
+    decoded_mode_2: bool,
+    decoded_form_2: bool,
+    status_valid: bool,
     decoded_first_written_block: bool,
     decoded_last_75hz_cycle: bool,
     cycles_44100hz_since_decode: u32,
@@ -133,6 +141,12 @@ impl Rchip {
             dma_address: 0,
             decoder_enabled: false,
             decoder_writes_enabled: false,
+            auto_mode_detection: false,
+            mode_2_requested: false,
+            form_2_requested: false,
+            decoded_mode_2: false,
+            decoded_form_2: false,
+            status_valid: false,
             decoded_first_written_block: false,
             decoded_last_75hz_cycle: false,
             cycles_44100hz_since_decode: 0,
@@ -339,17 +353,17 @@ impl Rchip {
                 // STAT2 (Status 2)
                 log::trace!("STAT2 read");
 
-                // TODO figure out what to put here
-                0x00
+                // MODE and FORM report the mode of the last decoded block; hardcode the error
+                // correction bits (RMOD3-0 and NOCOR) to 0
+                (u8::from(self.decoded_mode_2) << 3) | (u8::from(self.decoded_form_2) << 2)
             }
             15 => {
                 // STAT3 (Status 3)
                 log::trace!("STAT3 read");
 
-                // In actual hardware VALST remains low for a short amount of time after the
-                // decoder interrupt is generated, but the BIOS shouldn't read STAT3 multiple
-                // times per interrupt
-                let value = u8::from(!self.decoder_interrupt_pending) << 7;
+                // VALST (active low) is independent of DECI: it goes low when the decoder updates
+                // the status registers, and reading STAT3 does not change it
+                let value = u8::from(!self.status_valid) << 7;
 
                 // Reading STAT3 clears the decoder interrupt
                 self.decoder_interrupt_pending = false;
@@ -454,8 +468,6 @@ impl Rchip {
             }
             10 => {
                 // CTRL0 (Control 0)
-                // Intentionally ignore all bits except DECEN and WRRQ; the other bits are related
-                // to error detection and correction settings
                 log::trace!("CTRL0 write: {value:02X}");
 
                 self.write_ctrl0(value);
@@ -529,12 +541,17 @@ impl Rchip {
     }
 
     fn write_ctrl0(&mut self, value: u8) {
+        // Intentionally ignoring EDCRQ, E01RQ, ERAMRQ, QRQ, and PRQ; these only control error
+        // detection and correction, and blocks read from disc images never contain errors
         self.decoder_enabled = value.bit(7);
+        self.auto_mode_detection = value.bit(3);
         self.decoder_writes_enabled = value.bit(2);
 
-        // Disabling the decoder also disables any pending interrupt
+        // Disabling the decoder also disables any pending interrupt and invalidates the status
+        // registers
         if !self.decoder_enabled {
             self.decoder_interrupt_pending = false;
+            self.status_valid = false;
         }
 
         if !self.decoder_enabled || !self.decoder_writes_enabled {
@@ -542,11 +559,19 @@ impl Rchip {
         }
 
         log::trace!("  DECEN: {}", self.decoder_enabled);
+        log::trace!("  AUTORQ: {}", self.auto_mode_detection);
         log::trace!("  WRRQ: {}", self.decoder_writes_enabled);
     }
 
     fn write_ctrl1(&mut self, value: u8) {
+        // Intentionally ignoring SYIEN, SYDEN, DSCREN, COWREN, and MBCKRQ; Sega CD always feeds
+        // the CDC descrambled blocks with valid sync patterns
+        self.mode_2_requested = value.bit(3);
+        self.form_2_requested = value.bit(2);
         self.subheader_data_enabled = value.bit(0);
+
+        log::trace!("  MODRQ: {}", self.mode_2_requested);
+        log::trace!("  FORMRQ: {}", self.form_2_requested);
         log::trace!("  SHDREN: {}", self.subheader_data_enabled);
     }
 
@@ -589,6 +614,9 @@ impl Rchip {
         self.header_data.copy_from_slice(&sector_buffer[12..16]);
         self.subheader_data.copy_from_slice(&sector_buffer[16..20]);
 
+        self.update_decoded_mode();
+        self.status_valid = true;
+
         self.set_decoder_interrupt_flag();
 
         if self.decoder_writes_enabled {
@@ -616,6 +644,18 @@ impl Rchip {
         }
     }
 
+    fn update_decoded_mode(&mut self) {
+        if self.auto_mode_detection {
+            // Mode is read from the header's mode byte, and form is read from the submode byte
+            // (bit 5) in the subheader; only Mode 2 blocks have a form
+            self.decoded_mode_2 = self.header_data[3] == 2;
+            self.decoded_form_2 = self.decoded_mode_2 && self.subheader_data[2].bit(5);
+        } else {
+            self.decoded_mode_2 = self.mode_2_requested;
+            self.decoded_form_2 = self.mode_2_requested && self.form_2_requested;
+        }
+    }
+
     fn set_decoder_interrupt_flag(&mut self) {
         // Decoder interrupt always triggers INT5, even if not acknowledged in CDC
         self.decoder_interrupt_pending = true;
@@ -662,7 +702,13 @@ impl Rchip {
 
     fn progress_dma(
         &mut self,
-        RchipDmaArgs { word_ram, prg_ram, prg_ram_accessible, pcm }: RchipDmaArgs<'_>,
+        RchipDmaArgs {
+            word_ram,
+            prg_ram,
+            prg_ram_accessible,
+            prg_ram_write_protect_boundary,
+            pcm,
+        }: RchipDmaArgs<'_>,
     ) {
         if self.device_destination == DeviceDestination::PrgRam && !prg_ram_accessible {
             log::trace!("CDC DMA to PRG RAM is halted because sub CPU is removed from the bus");
@@ -719,8 +765,12 @@ impl Rchip {
 
                     match self.device_destination {
                         DeviceDestination::PrgRam => {
-                            prg_ram[dma_address as usize] = msb;
-                            prg_ram[((dma_address + 1) & dma_address_mask) as usize] = lsb;
+                            // Writes below the PRG RAM write protection boundary are dropped, same
+                            // as sub CPU writes
+                            if dma_address >= prg_ram_write_protect_boundary {
+                                prg_ram[dma_address as usize] = msb;
+                                prg_ram[((dma_address + 1) & dma_address_mask) as usize] = lsb;
+                            }
                         }
                         DeviceDestination::WordRam => {
                             word_ram.dma_write(dma_address, msb);
@@ -793,3 +843,116 @@ impl Rchip {
         self.scd_interrupt_flag = false;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_register(rchip: &mut Rchip, address: u8) -> u8 {
+        rchip.set_register_address(address);
+        rchip.read_register()
+    }
+
+    fn write_register(rchip: &mut Rchip, address: u8, value: u8) {
+        rchip.set_register_address(address);
+        rchip.write_register(value);
+    }
+
+    fn mode_2_block(form_2: bool) -> [u8; cdrom::BYTES_PER_SECTOR as usize] {
+        let mut block = [0; cdrom::BYTES_PER_SECTOR as usize];
+        block[15] = 2;
+        block[18] = u8::from(form_2) << 5;
+        block
+    }
+
+    #[test]
+    fn stat3_valst() {
+        let mut rchip = Rchip::new();
+        write_register(&mut rchip, 10, 0x80);
+
+        assert_eq!(read_register(&mut rchip, 15), 0x80);
+
+        rchip.decode_block(&mode_2_block(false));
+        assert!(rchip.decoder_interrupt_pending);
+
+        // Reading STAT3 clears DECI but not VALST
+        assert_eq!(read_register(&mut rchip, 15), 0x00);
+        assert!(!rchip.decoder_interrupt_pending);
+        assert_eq!(read_register(&mut rchip, 15), 0x00);
+
+        // DECI automatically clearing partway through the 75Hz frame also does not change VALST
+        rchip.decode_block(&mode_2_block(false));
+        let mut word_ram = WordRam::new();
+        let mut prg_ram: Box<[u8; memory::PRG_RAM_LEN]> =
+            vec![0; memory::PRG_RAM_LEN].into_boxed_slice().try_into().unwrap();
+        let mut pcm = Rf5c164::new(&crate::api::test_config());
+        for _ in 0..44100 / 75 {
+            rchip.clock_44100hz(RchipDmaArgs {
+                word_ram: &mut word_ram,
+                prg_ram: &mut prg_ram,
+                prg_ram_accessible: true,
+                prg_ram_write_protect_boundary: 0,
+                pcm: &mut pcm,
+            });
+        }
+        assert!(!rchip.decoder_interrupt_pending);
+        assert_eq!(read_register(&mut rchip, 15), 0x00);
+
+        // Disabling the decoder invalidates status
+        write_register(&mut rchip, 10, 0x00);
+        assert_eq!(read_register(&mut rchip, 15), 0x80);
+    }
+
+    #[test]
+    fn prg_ram_dma_write_protection() {
+        let mut rchip = Rchip::new();
+        rchip.buffer_ram[..0x800].fill(0xAA);
+
+        let mut word_ram = WordRam::new();
+        let mut prg_ram: Box<[u8; memory::PRG_RAM_LEN]> =
+            vec![0; memory::PRG_RAM_LEN].into_boxed_slice().try_into().unwrap();
+        let mut pcm = Rf5c164::new(&crate::api::test_config());
+
+        // Transfer $800 bytes to PRG RAM $0000-$07FF with $0000-$03FF write-protected
+        rchip.set_device_destination(DeviceDestination::PrgRam);
+        rchip.set_dma_address(0);
+        write_register(&mut rchip, 1, 0x02);
+        write_register(&mut rchip, 2, 0xFF);
+        write_register(&mut rchip, 3, 0x07);
+        write_register(&mut rchip, 6, 0x00);
+        while rchip.data_transfer_in_progress {
+            rchip.clock_44100hz(RchipDmaArgs {
+                word_ram: &mut word_ram,
+                prg_ram: &mut prg_ram,
+                prg_ram_accessible: true,
+                prg_ram_write_protect_boundary: 0x400,
+                pcm: &mut pcm,
+            });
+        }
+
+        assert!(prg_ram[..0x400].iter().all(|&b| b == 0x00));
+        assert!(prg_ram[0x400..0x800].iter().all(|&b| b == 0xAA));
+        assert!(prg_ram[0x800..].iter().all(|&b| b == 0x00));
+    }
+
+    #[test]
+    fn stat2_mode_and_form() {
+        let mut rchip = Rchip::new();
+
+        // AUTORQ=1: mode and form come from the block
+        write_register(&mut rchip, 10, 0x88);
+        rchip.decode_block(&mode_2_block(true));
+        assert_eq!(read_register(&mut rchip, 14), 0x0C);
+        rchip.decode_block(&mode_2_block(false));
+        assert_eq!(read_register(&mut rchip, 14), 0x08);
+
+        // AUTORQ=0: mode and form come from MODRQ and FORMRQ
+        write_register(&mut rchip, 10, 0x80);
+        write_register(&mut rchip, 11, 0x04);
+        rchip.decode_block(&mode_2_block(true));
+        assert_eq!(read_register(&mut rchip, 14), 0x00);
+        write_register(&mut rchip, 11, 0x0C);
+        rchip.decode_block(&mode_2_block(false));
+        assert_eq!(read_register(&mut rchip, 14), 0x0C);
+    }
+}
@@ -2,13 +2,14 @@
 
 use crate::api::{SegaCdEmulatorConfig, SegaCdLoadResult};
 use crate::cddrive::cdc::{Rchip, RchipDmaArgs};
+use crate::memory;
 use bincode::{Decode, Encode};
 use cdrom::cdtime::CdTime;
 use cdrom::cue::{Track, TrackType};
 use cdrom::reader::{CdRom, CdRomFileFormat};
 use genesis_core::GenesisRegion;
+use jgenesis_common::frontend::GameMetadata;
 use jgenesis_proc_macros::PartialClone;
-use regex::Regex;
 use std::cmp::Ordering;
 use std::path::Path;
 use std::sync::LazyLock;
@@ -192,7 +193,11 @@ pub struct CdDrive {
 }
 
 impl CdDrive {
-    pub(super) fn new(disc: Option<CdRom>, config: &SegaCdEmulatorConfig) -> Self {
+    pub(super) fn new(mut disc: Option<CdRom>, config: &SegaCdEmulatorConfig) -> Self {
+        if let Some(disc) = &mut disc {
+            disc.set_sector_cache(config.sector_cache_config());
+        }
+
         Self {
             disc,
             sector_buffer: Box::new(array::from_fn(|_| 0)),
@@ -852,30 +857,15 @@ impl CdDrive {
         self.interrupt_pending = false;
     }
 
-    #[allow(clippy::items_after_statements)]
-    pub fn disc_title(&mut self, region: GenesisRegion) -> SegaCdLoadResult<Option<String>> {
-        let Some(disc) = &mut self.disc else { return Ok(None) };
-
-        // Title information is always stored in the first sector of track 1
-        disc.read_sector(1, CdTime::SECTOR_0_START, self.sector_buffer.as_mut())?;
-
-        let title_bytes = match region {
-            GenesisRegion::Japan => &self.sector_buffer[0x130..0x160],
-            GenesisRegion::Americas | GenesisRegion::Europe => &self.sector_buffer[0x160..0x190],
-        };
-        let title: String = title_bytes
-            .iter()
-            .copied()
-            .filter_map(|b| {
-                let c = b as char;
-                (c.is_ascii_alphanumeric() || c.is_ascii_whitespace() || c.is_ascii_punctuation())
-                    .then_some(c)
-            })
-            .collect();
-
-        static WHITESPACE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r" +").unwrap());
+    pub fn disc_metadata(
+        &mut self,
+        region: GenesisRegion,
+    ) -> SegaCdLoadResult<Option<GameMetadata>> {
+        self.disc.as_mut().map(|disc| memory::read_disc_metadata(disc, region)).transpose()
+    }
 
-        Ok(Some(WHITESPACE_RE.replace_all(title.trim(), " ").to_string()))
+    pub fn has_disc(&self) -> bool {
+        self.disc.is_some()
     }
 
     pub fn take_disc(&mut self) -> Option<CdRom> {
@@ -904,17 +894,20 @@ impl CdDrive {
         &mut self,
         rom_path: P,
         format: CdRomFileFormat,
-        load_disc_into_ram: bool,
+        config: &SegaCdEmulatorConfig,
     ) -> SegaCdLoadResult<()> {
         let cue_path = rom_path.as_ref();
 
         log::info!("Changing disc to '{}'", cue_path.display());
 
-        self.disc = Some(if load_disc_into_ram {
+        let mut disc = if config.load_disc_into_ram {
             CdRom::open_in_memory(cue_path, format)?
         } else {
             CdRom::open(cue_path, format)?
-        });
+        };
+        disc.set_sector_cache(config.sector_cache_config());
+
+        self.disc = Some(disc);
         self.state = State::TrayOpening { auto_close: true };
 
         Ok(())
@@ -922,6 +915,10 @@ impl CdDrive {
 
     pub fn reload_config(&mut self, config: &SegaCdEmulatorConfig) {
         self.data_speed = config.disc_drive_speed.get();
+
+        if let Some(disc) = &mut self.disc {
+            disc.set_sector_cache(config.sector_cache_config());
+        }
     }
 }
 
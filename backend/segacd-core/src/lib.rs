@@ -5,4 +5,4 @@ mod graphics;
 mod memory;
 mod rf5c164;
 
-pub use cdrom::reader::CdRomFileFormat;
+pub use cdrom::reader::{CdRomFileFormat, LoadProgress};
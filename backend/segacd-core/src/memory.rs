@@ -5,7 +5,7 @@ mod font;
 pub(crate) mod wordram;
 
 use crate::api::{SegaCdEmulatorConfig, SegaCdLoadResult};
-use crate::cddrive::cdc::{DeviceDestination, Rchip};
+use crate::cddrive::cdc::{DeviceDestination, Rchip, RchipDmaArgs};
 use crate::cddrive::cdd::CdDrive;
 use crate::cddrive::{CdController, cdc};
 use crate::graphics::GraphicsCoprocessor;
@@ -17,6 +17,10 @@ use cdrom::reader::{CdRom, CdRomFileFormat};
 use genesis_core::GenesisRegion;
 use genesis_core::memory::{Memory, PhysicalMedium};
 use jgenesis_common::boxedarray::BoxedByteArray;
+use jgenesis_common::eventlog::EventLog;
+use jgenesis_common::frontend::GameMetadata;
+use jgenesis_common::memmap;
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
 use jgenesis_common::num::{GetBit, U16Ext};
 use jgenesis_proc_macros::{FakeDecode, FakeEncode, PartialClone};
 use m68000_emu::BusInterface;
@@ -25,6 +29,8 @@ use std::path::Path;
 use std::{array, mem};
 use wordram::WordRam;
 
+pub use backupram::{BackupRamConvertError, resize_backup_ram};
+
 pub const BIOS_LEN: usize = 128 * 1024;
 pub const PRG_RAM_LEN: usize = 512 * 1024;
 pub const BACKUP_RAM_LEN: usize = 8 * 1024;
@@ -36,6 +42,9 @@ const RAM_CARTRIDGE_SIZE_BYTE: u8 = 0x04;
 
 const TIMER_DIVIDER: u64 = 1536;
 
+const MAIN_CPU_EVENT_SOURCE: &str = "68000";
+const SUB_CPU_EVENT_SOURCE: &str = "Sub 68000";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
 pub enum ScdCpu {
     Main,
@@ -136,6 +145,7 @@ pub struct SegaCd {
     prg_ram: BoxedByteArray<PRG_RAM_LEN>,
     word_ram: WordRam,
     backup_ram: Box<[u8; BACKUP_RAM_LEN]>,
+    backup_ram_write_protected: bool,
     enable_ram_cartridge: bool,
     ram_cartridge: BoxedByteArray<RAM_CARTRIDGE_LEN>,
     ram_cartridge_writes_enabled: bool,
@@ -145,6 +155,8 @@ pub struct SegaCd {
     disc_region: GenesisRegion,
     forced_region: Option<GenesisRegion>,
     timer_divider: u64,
+    #[partial_clone(default)]
+    event_log: EventLog,
 }
 
 impl SegaCd {
@@ -176,6 +188,7 @@ impl SegaCd {
             prg_ram: BoxedByteArray::new(),
             word_ram: WordRam::new(),
             backup_ram,
+            backup_ram_write_protected: config.backup_ram_write_protected,
             enable_ram_cartridge: config.enable_ram_cartridge,
             ram_cartridge: ram_cartridge.into(),
             ram_cartridge_writes_enabled: true,
@@ -185,6 +198,7 @@ impl SegaCd {
             disc_region,
             forced_region: config.genesis.forced_region,
             timer_divider: TIMER_DIVIDER,
+            event_log: EventLog::default(),
         })
     }
 
@@ -314,12 +328,25 @@ impl SegaCd {
                 // Initialization / reset, high byte
                 self.registers.software_interrupt_pending = value.bit(0);
 
+                if value.bit(0) {
+                    self.event_log.log(MAIN_CPU_EVENT_SOURCE, || "Raised sub CPU INT2".into());
+                }
+
                 log::trace!("  INT2 pending write: {}", self.registers.software_interrupt_pending);
             }
             0xA12001 => {
                 // Initialization / reset, low byte
-                self.registers.sub_cpu_busreq = value.bit(1);
-                self.registers.sub_cpu_reset = !value.bit(0);
+                let busreq = value.bit(1);
+                let reset = !value.bit(0);
+                if busreq != self.registers.sub_cpu_busreq || reset != self.registers.sub_cpu_reset
+                {
+                    self.event_log.log(MAIN_CPU_EVENT_SOURCE, || {
+                        format!("Sub CPU BUSREQ={}, RESET={}", u8::from(busreq), u8::from(reset))
+                    });
+                }
+
+                self.registers.sub_cpu_busreq = busreq;
+                self.registers.sub_cpu_reset = reset;
 
                 log::trace!("  Sub CPU BUSREQ: {}", self.registers.sub_cpu_busreq);
                 log::trace!("  Sub CPU RESET: {}", self.registers.sub_cpu_reset);
@@ -335,6 +362,14 @@ impl SegaCd {
                 self.registers.prg_ram_bank = value >> 6;
                 self.word_ram.main_cpu_write_control(value);
 
+                self.event_log.log(MAIN_CPU_EVENT_SOURCE, || {
+                    format!(
+                        "Word RAM control write, DMNA={}; {}",
+                        u8::from(value.bit(1)),
+                        self.word_ram.describe_ownership()
+                    )
+                });
+
                 log::trace!("  PRG RAM bank: {}", self.registers.prg_ram_bank);
             }
             0xA12006..=0xA12007 => {
@@ -345,17 +380,18 @@ impl SegaCd {
                 self.cdc_mut().write_host_data(ScdCpu::Main);
             }
             0xA1200E..=0xA1200F => {
-                self.registers.main_cpu_communication_flags = value;
+                self.write_main_cpu_communication_flags(value);
             }
             0xA12010..=0xA1201F => {
                 // Communication command buffers
                 let idx = (address & 0xF) >> 1;
-                let commands = &mut self.registers.communication_commands;
+                let mut command = self.registers.communication_commands[idx as usize];
                 if address.bit(0) {
-                    commands[idx as usize].set_lsb(value);
+                    command.set_lsb(value);
                 } else {
-                    commands[idx as usize].set_msb(value);
+                    command.set_msb(value);
                 }
+                self.write_communication_command(idx, command);
             }
             _ => {}
         }
@@ -381,17 +417,59 @@ impl SegaCd {
             }
             0xA1200E => {
                 // Communication flags; only main CPU flags are writable
-                self.registers.main_cpu_communication_flags = value.msb();
+                self.write_main_cpu_communication_flags(value.msb());
             }
             0xA12010..=0xA1201F => {
                 // Communication command buffers
                 let idx = (address & 0xF) >> 1;
-                self.registers.communication_commands[idx as usize] = value;
+                self.write_communication_command(idx, value);
             }
             _ => {}
         }
     }
 
+    fn write_main_cpu_communication_flags(&mut self, value: u8) {
+        self.registers.main_cpu_communication_flags = value;
+        self.event_log.log(MAIN_CPU_EVENT_SOURCE, || format!("Communication flags = ${value:02X}"));
+    }
+
+    fn write_communication_command(&mut self, idx: u32, value: u16) {
+        self.registers.communication_commands[idx as usize] = value;
+        self.event_log.log(MAIN_CPU_EVENT_SOURCE, || format!("Command {idx} = ${value:04X}"));
+    }
+
+    fn write_sub_cpu_communication_flags(&mut self, value: u8) {
+        self.registers.sub_cpu_communication_flags = value;
+        self.event_log.log(SUB_CPU_EVENT_SOURCE, || format!("Communication flags = ${value:02X}"));
+    }
+
+    fn write_communication_status(&mut self, idx: u32, value: u16) {
+        self.registers.communication_statuses[idx as usize] = value;
+        self.event_log.log(SUB_CPU_EVENT_SOURCE, || format!("Status {idx} = ${value:04X}"));
+    }
+
+    /// Describe the sub CPU address space, including the current word RAM mode.
+    pub fn sub_cpu_memory_map(&self) -> MemoryMap {
+        let mut map = MemoryMap::new("Sub 68000", 24);
+        map.add(0x000000, 0x07FFFF, RegionKind::Ram, "PRG RAM");
+        self.word_ram.describe_sub_cpu_map(&mut map);
+        map.add_mapped(0xFE0000, 0xFEFFFF, RegionKind::Ram, "Backup RAM", "Odd addresses only")
+            .add(0xFF0000, 0xFF3FFF, RegionKind::Registers, "PCM")
+            .add_mirror(0xFF4000, 0xFF7FFF, RegionKind::Registers, "PCM", 0xFF0000, 0xFF3FFF)
+            .add(0xFF8000, 0xFF81FF, RegionKind::Registers, "Sub CPU registers")
+            .add_mirror(
+                0xFF8200,
+                0xFFFFFF,
+                RegionKind::Registers,
+                "Sub CPU registers",
+                0xFF8000,
+                0xFF81FF,
+            );
+        map.fill_unmapped();
+
+        map
+    }
+
     fn read_ram_cartridge_byte(&self, address: u32) -> u8 {
         if !self.enable_ram_cartridge {
             return 0xFF;
@@ -472,6 +550,17 @@ impl SegaCd {
         }
     }
 
+    fn write_backup_ram(&mut self, address: u32, value: u8) {
+        if self.backup_ram_write_protected {
+            log::trace!("Backup RAM write while write-protected: {address:06X} {value:02X}");
+            return;
+        }
+
+        let backup_ram_addr = (address & 0x3FFF) >> 1;
+        self.backup_ram[backup_ram_addr as usize] = value;
+        self.backup_ram_dirty = true;
+    }
+
     fn cdc(&self) -> &Rchip {
         self.disc_drive.cdc()
     }
@@ -494,14 +583,20 @@ impl SegaCd {
         pcm: &mut Rf5c164,
         audio_callback: impl FnMut(f64, f64),
     ) -> SegaCdLoadResult<()> {
-        // CDC DMA can only write to PRG RAM while the sub CPU is on the bus
+        // CDC DMA can only write to PRG RAM while the sub CPU is on the bus, and it is subject to
+        // the same write protection as sub CPU writes
         let prg_ram_accessible = !(self.registers.sub_cpu_busreq || self.registers.sub_cpu_reset);
+        let prg_ram_write_protect_boundary =
+            u32::from(self.registers.prg_ram_write_protect) * 0x200;
         self.disc_drive.tick(
             master_clock_cycles,
-            &mut self.word_ram,
-            &mut self.prg_ram,
-            prg_ram_accessible,
-            pcm,
+            RchipDmaArgs {
+                word_ram: &mut self.word_ram,
+                prg_ram: &mut self.prg_ram,
+                prg_ram_accessible,
+                prg_ram_write_protect_boundary,
+                pcm,
+            },
             audio_callback,
         )?;
 
@@ -528,8 +623,16 @@ impl SegaCd {
         self.registers.stopwatch_counter = (self.registers.stopwatch_counter + 1) & 0x0FFF;
     }
 
-    pub fn disc_title(&mut self) -> SegaCdLoadResult<Option<String>> {
-        self.disc_drive.disc_title(self.region())
+    pub fn disc_metadata(&mut self) -> SegaCdLoadResult<Option<GameMetadata>> {
+        self.disc_drive.disc_metadata(self.region())
+    }
+
+    pub fn has_disc(&self) -> bool {
+        self.disc_drive.has_disc()
+    }
+
+    pub fn disc_region(&self) -> GenesisRegion {
+        self.disc_region
     }
 
     pub fn word_ram(&self) -> &WordRam {
@@ -544,14 +647,37 @@ impl SegaCd {
         self.bios.0.as_slice()
     }
 
+    pub fn prg_ram(&self) -> &[u8] {
+        self.prg_ram.as_slice()
+    }
+
+    pub fn prg_ram_mut(&mut self) -> &mut [u8] {
+        self.prg_ram.as_mut_slice()
+    }
+
     pub fn backup_ram(&self) -> &[u8] {
         self.backup_ram.as_slice()
     }
 
+    pub fn restore_backup_ram(&mut self, backup_ram: &[u8]) {
+        self.backup_ram.copy_from_slice(backup_ram);
+        self.backup_ram_dirty = true;
+    }
+
     pub fn ram_cartridge(&self) -> &[u8] {
         self.ram_cartridge.as_slice()
     }
 
+    pub fn restore_ram_cartridge(&mut self, ram_cartridge: &[u8]) {
+        self.ram_cartridge.copy_from_slice(ram_cartridge);
+        self.backup_ram_dirty = true;
+    }
+
+    pub fn format_backup_ram(&mut self) {
+        backupram::format_backup_ram(&mut self.backup_ram);
+        self.backup_ram_dirty = true;
+    }
+
     pub fn graphics_interrupt_enabled(&self) -> bool {
         self.registers.graphics_interrupt_enabled
     }
@@ -573,6 +699,7 @@ impl SegaCd {
 
     pub fn reload_config(&mut self, config: &SegaCdEmulatorConfig) {
         self.forced_region = config.genesis.forced_region;
+        self.backup_ram_write_protected = config.backup_ram_write_protected;
         self.enable_ram_cartridge = config.enable_ram_cartridge;
         self.cdd_mut().reload_config(config);
     }
@@ -590,13 +717,13 @@ impl SegaCd {
         &mut self,
         rom_path: P,
         format: CdRomFileFormat,
-        load_disc_into_ram: bool,
+        config: &SegaCdEmulatorConfig,
     ) -> SegaCdLoadResult<()> {
-        self.cdd_mut().change_disc(rom_path, format, load_disc_into_ram)
+        self.cdd_mut().change_disc(rom_path, format, config)
     }
 }
 
-fn parse_disc_region(disc: &mut CdRom) -> SegaCdLoadResult<GenesisRegion> {
+pub(crate) fn parse_disc_region(disc: &mut CdRom) -> SegaCdLoadResult<GenesisRegion> {
     // ROM header is always located at track 1 sector 0
     let mut rom_header = [0; cdrom::BYTES_PER_SECTOR as usize];
     disc.read_sector(1, CdTime::SECTOR_0_START, &mut rom_header)?;
@@ -622,7 +749,62 @@ fn parse_disc_region(disc: &mut CdRom) -> SegaCdLoadResult<GenesisRegion> {
     Ok(region)
 }
 
+pub(crate) fn read_disc_metadata(
+    disc: &mut CdRom,
+    region: GenesisRegion,
+) -> SegaCdLoadResult<GameMetadata> {
+    let mut rom_header = [0; cdrom::BYTES_PER_SECTOR as usize];
+    disc.read_sector(1, CdTime::SECTOR_0_START, &mut rom_header)?;
+
+    // Skip sync + data track header, same as when parsing region
+    Ok(genesis_core::memory::parse_header_metadata(&rom_header[0x010..], region))
+}
+
+pub(crate) fn read_disc_boot_header(disc: &mut CdRom) -> SegaCdLoadResult<Vec<u8>> {
+    let mut sector = [0; cdrom::BYTES_PER_SECTOR as usize];
+    disc.read_sector(1, CdTime::SECTOR_0_START, &mut sector)?;
+
+    // Skip sync + data track header, same as when parsing region
+    Ok(sector[0x010..0x210].to_vec())
+}
+
 impl PhysicalMedium for SegaCd {
+    fn describe_memory_map(&self, map: &mut MemoryMap) {
+        map.add(0x000000, 0x01FFFF, RegionKind::Rom, "BIOS")
+            .add_mapped(
+                0x020000,
+                0x03FFFF,
+                RegionKind::Ram,
+                "PRG RAM window",
+                memmap::describe_bank(self.registers.prg_ram_bank.into(), 0x020000),
+            )
+            .add_mirror(0x040000, 0x1FFFFF, RegionKind::Rom, "BIOS / PRG RAM", 0x000000, 0x03FFFF);
+
+        self.word_ram.describe_main_cpu_map(map);
+
+        if self.enable_ram_cartridge {
+            map.add(0x400000, 0x4FFFFF, RegionKind::Registers, "RAM cartridge size")
+                .add_mapped(
+                    0x600000,
+                    0x6FFFFF,
+                    RegionKind::Ram,
+                    "RAM cartridge",
+                    "Odd addresses only",
+                )
+                .add(0x700000, 0x7FFFFF, RegionKind::Registers, "RAM cartridge write enable");
+        }
+
+        map.add(0xA12000, 0xA1202F, RegionKind::Registers, "Sega CD registers");
+    }
+
+    fn event_log(&self) -> &EventLog {
+        &self.event_log
+    }
+
+    fn event_log_mut(&mut self) -> &mut EventLog {
+        &mut self.event_log
+    }
+
     #[inline]
     fn read_byte(&mut self, address: u32) -> u8 {
         match address {
@@ -1046,7 +1228,16 @@ impl<'a> SubBus<'a> {
             }
             0x0002..=0x0003 => {
                 // Memory mode
-                self.sega_cd_mut().word_ram.sub_cpu_write_control(value);
+                let sega_cd = self.sega_cd_mut();
+                sega_cd.word_ram.sub_cpu_write_control(value);
+
+                sega_cd.event_log.log(SUB_CPU_EVENT_SOURCE, || {
+                    format!(
+                        "Word RAM control write, RET={}; {}",
+                        u8::from(value.bit(0)),
+                        sega_cd.word_ram.describe_ownership()
+                    )
+                });
             }
             0x0004 => {
                 // CDC mode
@@ -1083,17 +1274,18 @@ impl<'a> SubBus<'a> {
             }
             0x000E..=0x000F => {
                 // Communication flags
-                self.sega_cd_mut().registers.sub_cpu_communication_flags = value;
+                self.sega_cd_mut().write_sub_cpu_communication_flags(value);
             }
             0x0020..=0x002F => {
                 // Communication status buffers
                 let idx = (address & 0xF) >> 1;
-                let statuses = &mut self.sega_cd_mut().registers.communication_statuses;
+                let mut status = self.sega_cd().registers.communication_statuses[idx as usize];
                 if address.bit(0) {
-                    statuses[idx as usize].set_lsb(value);
+                    status.set_lsb(value);
                 } else {
-                    statuses[idx as usize].set_msb(value);
+                    status.set_msb(value);
                 }
+                self.sega_cd_mut().write_communication_status(idx, status);
             }
             0x0030..=0x0031 => {
                 // Timer
@@ -1202,12 +1394,12 @@ impl<'a> SubBus<'a> {
             }
             0x000E => {
                 // Communication flags, only low byte (sub CPU) is writable
-                self.sega_cd_mut().registers.sub_cpu_communication_flags = value as u8;
+                self.sega_cd_mut().write_sub_cpu_communication_flags(value as u8);
             }
             0x0020..=0x002F => {
                 // Communication status buffers
                 let idx = (address & 0xF) >> 1;
-                self.sega_cd_mut().registers.communication_statuses[idx as usize] = value;
+                self.sega_cd_mut().write_communication_status(idx, value);
             }
             0x0030 => {
                 // Timer, only low byte is writable
@@ -1354,10 +1546,7 @@ impl BusInterface for SubBus<'_> {
             0xFE0000..=0xFEFFFF => {
                 // Backup RAM (odd addresses)
                 if address.bit(0) {
-                    let backup_ram_addr = (address & 0x3FFF) >> 1;
-                    let sega_cd = self.sega_cd_mut();
-                    sega_cd.backup_ram[backup_ram_addr as usize] = value;
-                    sega_cd.backup_ram_dirty = true;
+                    self.sega_cd_mut().write_backup_ram(address, value);
                 }
             }
             0xFF0000..=0xFF7FFF => {
@@ -1394,10 +1583,7 @@ impl BusInterface for SubBus<'_> {
             }
             0xFE0000..=0xFEFFFF => {
                 // Backup RAM (odd addresses)
-                let backup_ram_addr = (address & 0x3FFF) >> 1;
-                let sega_cd = self.sega_cd_mut();
-                sega_cd.backup_ram[backup_ram_addr as usize] = value as u8;
-                sega_cd.backup_ram_dirty = true;
+                self.sega_cd_mut().write_backup_ram(address, value as u8);
             }
             0xFF0000..=0xFF7FFF => {
                 // PCM sound chip (odd addresses); canonically located at $FF0000-$FF3FFF and mirrored at $FF4000-$FF7FFF
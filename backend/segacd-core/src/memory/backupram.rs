@@ -1,4 +1,7 @@
 use crate::memory;
+use jgenesis_common::sram;
+use std::borrow::Cow;
+use thiserror::Error;
 
 const BACKUP_RAM_LEN: usize = memory::BACKUP_RAM_LEN;
 const RAM_CARTRIDGE_LEN: usize = memory::RAM_CARTRIDGE_LEN;
@@ -29,6 +32,13 @@ const RAM_CARTRIDGE_FOOTER: [u8; BACKUP_RAM_FOOTER_LEN] = [
     0x52, 0x41, 0x4D, 0x5F, 0x43, 0x41, 0x52, 0x54, 0x52, 0x49, 0x44, 0x47, 0x45, 0x5F, 0x5F, 0x5F,
 ];
 
+fn format<const LEN: usize>(ram: &mut [u8; LEN], footer: &[u8; BACKUP_RAM_FOOTER_LEN]) {
+    // Most of a freshly-formatted backup RAM is filled with 0s, but the last 64 bytes need to be
+    // filled in
+    ram.fill(0);
+    ram[LEN - BACKUP_RAM_FOOTER_LEN..].copy_from_slice(footer);
+}
+
 // Boxing is desired because these boxed arrays will be large (8KB / 128KB)
 #[allow(clippy::unnecessary_box_returns)]
 fn new_formatted_backup_ram<const LEN: usize>(
@@ -38,14 +48,17 @@ fn new_formatted_backup_ram<const LEN: usize>(
     // Some games (Popful Mail) blow up horribly if backup RAM is not formatted, so it's more
     // convenient to initialize it already formatted.
     let mut backup_ram: Box<[u8; LEN]> = vec![0; LEN].into_boxed_slice().try_into().unwrap();
-
-    // Most of a freshly-formatted backup RAM is filled with 0s, but the last 64 bytes need to be
-    // filled in
-    backup_ram[LEN - BACKUP_RAM_FOOTER_LEN..].copy_from_slice(footer);
+    format(&mut backup_ram, footer);
 
     backup_ram
 }
 
+/// Erase internal backup RAM and write an empty filesystem, same as formatting from the BIOS memory
+/// manager.
+pub fn format_backup_ram(backup_ram: &mut [u8; BACKUP_RAM_LEN]) {
+    format(backup_ram, &BACKUP_RAM_FOOTER);
+}
+
 pub fn load_initial_backup_ram(
     initial_backup_ram: Option<&Vec<u8>>,
     initial_ram_cartridge: Option<&Vec<u8>>,
@@ -75,3 +88,179 @@ pub fn load_initial_backup_ram(
 
     (backup_ram, ram_cartridge)
 }
+
+const MIN_IMAGE_LEN: usize = BACKUP_RAM_LEN;
+const MAX_IMAGE_LEN: usize = 1024 * 1024;
+
+// Offset within the footer of the four 16-bit words that track the number of free blocks
+const FOOTER_FREE_BLOCKS_OFFSET: usize = 0x10;
+const FOOTER_SIGNATURE_OFFSET: usize = 0x20;
+const FOOTER_SIGNATURE: &[u8] = b"SEGA_CD_ROM";
+
+// The footer block plus the two reserved directory blocks
+const RESERVED_BLOCKS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum BackupRamConvertError {
+    #[error("Unsupported backup RAM image size: {0} bytes")]
+    UnsupportedSize(usize),
+    #[error("Backup RAM image is not formatted")]
+    NotFormatted,
+    #[error(
+        "Backup RAM image has {used_blocks} blocks in use, which do not fit in a {len}-byte image"
+    )]
+    TooFull { used_blocks: usize, len: usize },
+}
+
+fn check_image_len(len: usize) -> Result<(), BackupRamConvertError> {
+    if !len.is_power_of_two() || !(MIN_IMAGE_LEN..=MAX_IMAGE_LEN).contains(&len) {
+        return Err(BackupRamConvertError::UnsupportedSize(len));
+    }
+
+    Ok(())
+}
+
+fn has_signature(footer: &[u8]) -> bool {
+    &footer[FOOTER_SIGNATURE_OFFSET..FOOTER_SIGNATURE_OFFSET + FOOTER_SIGNATURE.len()]
+        == FOOTER_SIGNATURE
+}
+
+fn read_free_blocks(footer: &[u8], i: usize) -> usize {
+    let offset = FOOTER_FREE_BLOCKS_OFFSET + 2 * i;
+    u16::from_be_bytes([footer[offset], footer[offset + 1]]).into()
+}
+
+/// Convert a formatted backup RAM image to a different size, e.g. to convert a 512KB RAM cartridge
+/// image from Genesis Plus GX to the 128KB RAM cartridge emulated here.
+///
+/// Files are stored starting from the beginning of backup RAM and the directory grows downwards
+/// from the footer at the end, so conversion copies both regions and then adjusts the free block
+/// count in the footer.
+pub fn resize_backup_ram(image: &[u8], new_len: usize) -> Result<Vec<u8>, BackupRamConvertError> {
+    check_image_len(image.len())?;
+    check_image_len(new_len)?;
+
+    // Some tools write images with every 16-bit word byteswapped
+    let mut swapped_footer = image[image.len() - BACKUP_RAM_FOOTER_LEN..].to_vec();
+    sram::byteswap_words(&mut swapped_footer);
+    let image: Cow<'_, [u8]> = if has_signature(&swapped_footer) {
+        log::info!("Backup RAM image is byteswapped; swapping to big-endian");
+        let mut image = image.to_vec();
+        sram::byteswap_words(&mut image);
+        image.into()
+    } else {
+        image.into()
+    };
+
+    let footer = &image[image.len() - BACKUP_RAM_FOOTER_LEN..];
+    if !has_signature(footer) {
+        return Err(BackupRamConvertError::NotFormatted);
+    }
+
+    if new_len == image.len() {
+        return Ok(image.to_vec());
+    }
+
+    // The free block count is stored twice (each stored as 2 identical words); use the lower of
+    // the two to be conservative about how much data needs to be copied
+    let blocks = image.len() / BACKUP_RAM_FOOTER_LEN;
+    let free_blocks = read_free_blocks(footer, 0).min(read_free_blocks(footer, 2));
+    let Some(used_blocks) = (blocks - RESERVED_BLOCKS).checked_sub(free_blocks) else {
+        return Err(BackupRamConvertError::NotFormatted);
+    };
+
+    // Every file occupies at least one block, so file data can't extend past `used_blocks` from the
+    // start and the directory can't extend more than `used_blocks` below the reserved blocks
+    let data_len = (used_blocks * BACKUP_RAM_FOOTER_LEN).min(image.len());
+    let directory_len = ((used_blocks + RESERVED_BLOCKS) * BACKUP_RAM_FOOTER_LEN).min(image.len());
+    if (2 * used_blocks + RESERVED_BLOCKS) * BACKUP_RAM_FOOTER_LEN > new_len {
+        return Err(BackupRamConvertError::TooFull { used_blocks, len: new_len });
+    }
+
+    let mut resized = vec![0; new_len];
+    resized[..data_len].copy_from_slice(&image[..data_len]);
+    resized[new_len - directory_len..].copy_from_slice(&image[image.len() - directory_len..]);
+
+    let new_blocks = new_len / BACKUP_RAM_FOOTER_LEN;
+    let new_footer = &mut resized[new_len - BACKUP_RAM_FOOTER_LEN..];
+    for i in 0..4 {
+        let offset = FOOTER_FREE_BLOCKS_OFFSET + 2 * i;
+        let new_free_blocks = read_free_blocks(new_footer, i) + new_blocks - blocks;
+        new_footer[offset..offset + 2].copy_from_slice(&(new_free_blocks as u16).to_be_bytes());
+    }
+
+    Ok(resized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formatted_ram_cartridge() -> Vec<u8> {
+        new_formatted_backup_ram::<RAM_CARTRIDGE_LEN>(&RAM_CARTRIDGE_FOOTER).to_vec()
+    }
+
+    #[test]
+    fn resize_formatted() {
+        let backup_ram = resize_backup_ram(&formatted_ram_cartridge(), BACKUP_RAM_LEN).unwrap();
+        assert_eq!(backup_ram[BACKUP_RAM_LEN - BACKUP_RAM_FOOTER_LEN..], BACKUP_RAM_FOOTER);
+
+        let ram_cartridge = resize_backup_ram(&backup_ram, RAM_CARTRIDGE_LEN).unwrap();
+        assert_eq!(ram_cartridge, formatted_ram_cartridge());
+    }
+
+    #[test]
+    fn resize_preserves_files() {
+        let mut ram_cartridge = formatted_ram_cartridge();
+        // One directory entry below the reserved blocks and two blocks of file data
+        let directory_addr = RAM_CARTRIDGE_LEN - RESERVED_BLOCKS * BACKUP_RAM_FOOTER_LEN - 32;
+        ram_cartridge[directory_addr..directory_addr + 11].copy_from_slice(b"SAVE_FILE__");
+        ram_cartridge[..128].fill(0xAB);
+        let footer_addr = RAM_CARTRIDGE_LEN - BACKUP_RAM_FOOTER_LEN + FOOTER_FREE_BLOCKS_OFFSET;
+        for i in 0..4 {
+            ram_cartridge[footer_addr + 2 * i..footer_addr + 2 * i + 2]
+                .copy_from_slice(&0x07FA_u16.to_be_bytes());
+        }
+
+        let resized = resize_backup_ram(&ram_cartridge, 512 * 1024).unwrap();
+        assert_eq!(resized[..128], [0xAB; 128]);
+        assert!(resized[128..resized.len() / 2].iter().all(|&b| b == 0));
+        let resized_directory_addr = resized.len() - RESERVED_BLOCKS * BACKUP_RAM_FOOTER_LEN - 32;
+        assert_eq!(&resized[resized_directory_addr..resized_directory_addr + 11], b"SAVE_FILE__");
+        assert_eq!(read_free_blocks(&resized[resized.len() - BACKUP_RAM_FOOTER_LEN..], 0), 0x1FFA);
+
+        assert_eq!(resize_backup_ram(&resized, RAM_CARTRIDGE_LEN).unwrap(), ram_cartridge);
+    }
+
+    #[test]
+    fn resize_byteswapped() {
+        let mut ram_cartridge = formatted_ram_cartridge();
+        sram::byteswap_words(&mut ram_cartridge);
+
+        let backup_ram = resize_backup_ram(&ram_cartridge, BACKUP_RAM_LEN).unwrap();
+        assert_eq!(backup_ram[BACKUP_RAM_LEN - BACKUP_RAM_FOOTER_LEN..], BACKUP_RAM_FOOTER);
+    }
+
+    #[test]
+    fn resize_errors() {
+        assert_eq!(
+            resize_backup_ram(&[0; 1000], BACKUP_RAM_LEN),
+            Err(BackupRamConvertError::UnsupportedSize(1000))
+        );
+        assert_eq!(
+            resize_backup_ram(&[0; BACKUP_RAM_LEN], RAM_CARTRIDGE_LEN),
+            Err(BackupRamConvertError::NotFormatted)
+        );
+
+        let mut ram_cartridge = formatted_ram_cartridge();
+        let footer_addr = RAM_CARTRIDGE_LEN - BACKUP_RAM_FOOTER_LEN + FOOTER_FREE_BLOCKS_OFFSET;
+        for i in 0..4 {
+            ram_cartridge[footer_addr + 2 * i..footer_addr + 2 * i + 2]
+                .copy_from_slice(&0x0700_u16.to_be_bytes());
+        }
+        assert_eq!(
+            resize_backup_ram(&ram_cartridge, BACKUP_RAM_LEN),
+            Err(BackupRamConvertError::TooFull { used_blocks: 0xFD, len: BACKUP_RAM_LEN })
+        );
+    }
+}
@@ -4,6 +4,7 @@
 use crate::memory::ScdCpu;
 use bincode::{Decode, Encode};
 use jgenesis_common::boxedarray::BoxedByteArray;
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
 use jgenesis_common::num::GetBit;
 
 // Word RAM is 256KB
@@ -112,6 +113,74 @@ impl WordRam {
         self.mode
     }
 
+    fn describe_mapping(&self, cpu: ScdCpu) -> String {
+        match self.mode {
+            WordRamMode::TwoM => {
+                let access = if self.owner_2m == cpu { "accessible" } else { "not accessible" };
+                format!("2M mode, {access}")
+            }
+            WordRamMode::OneM => {
+                format!("1M mode, bank {}", u8::from(cpu != self.bank_0_owner_1m))
+            }
+        }
+    }
+
+    /// Describe which CPU currently owns word RAM (2M mode) or each word RAM bank (1M mode).
+    pub fn describe_ownership(&self) -> String {
+        match self.mode {
+            WordRamMode::TwoM => format!("2M mode, owned by {} CPU", cpu_name(self.owner_2m)),
+            WordRamMode::OneM => {
+                format!("1M mode, bank 0 owned by {} CPU", cpu_name(self.bank_0_owner_1m))
+            }
+        }
+    }
+
+    pub fn describe_main_cpu_map(&self, map: &mut MemoryMap) {
+        let mapping = self.describe_mapping(ScdCpu::Main);
+        match self.mode {
+            WordRamMode::TwoM => {
+                map.add_mapped(0x200000, 0x23FFFF, RegionKind::Ram, "Word RAM", mapping);
+            }
+            WordRamMode::OneM => {
+                map.add_mapped(0x200000, 0x21FFFF, RegionKind::Ram, "Word RAM", mapping.clone())
+                    .add_mapped(
+                        0x220000,
+                        0x23FFFF,
+                        RegionKind::Ram,
+                        "Word RAM cell image",
+                        mapping,
+                    );
+            }
+        }
+
+        map.add_mirror(0x240000, 0x3FFFFF, RegionKind::Ram, "Word RAM", 0x200000, 0x23FFFF);
+    }
+
+    pub fn describe_sub_cpu_map(&self, map: &mut MemoryMap) {
+        let mapping = self.describe_mapping(ScdCpu::Sub);
+        match self.mode {
+            WordRamMode::TwoM => {
+                map.add_mapped(0x080000, 0x0BFFFF, RegionKind::Ram, "Word RAM", mapping);
+            }
+            WordRamMode::OneM => {
+                map.add_mapped(
+                    0x080000,
+                    0x0BFFFF,
+                    RegionKind::Ram,
+                    "Word RAM (dot mapped)",
+                    mapping.clone(),
+                )
+                .add_mapped(
+                    0x0C0000,
+                    0x0DFFFF,
+                    RegionKind::Ram,
+                    "Word RAM",
+                    mapping,
+                );
+            }
+        }
+    }
+
     pub fn read_control(&self) -> u8 {
         let (dmna, ret) = match self.mode {
             WordRamMode::TwoM => {
@@ -400,6 +469,15 @@ impl WordRam {
     pub fn priority_mode(&self) -> WordRamPriorityMode {
         self.priority_mode
     }
+
+    // Raw contents, with both 1M banks interleaved the same way as in 2M mode
+    pub fn raw(&self) -> &[u8] {
+        self.ram.as_slice()
+    }
+
+    pub fn raw_mut(&mut self) -> &mut [u8] {
+        self.ram.as_mut_slice()
+    }
 }
 
 fn determine_1m_address(address: u32, cpu: ScdCpu, bank_0_owner: ScdCpu) -> u32 {
@@ -421,6 +499,13 @@ fn map_cell_image_address(
     determine_1m_address(byte_addr, ScdCpu::Main, bank_0_owner)
 }
 
+fn cpu_name(cpu: ScdCpu) -> &'static str {
+    match cpu {
+        ScdCpu::Main => "main",
+        ScdCpu::Sub => "sub",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -8,15 +8,17 @@ use crate::psg::{Sn76489, Sn76489TickEffect, Sn76489Version};
 use crate::vdp::{Vdp, VdpBuffer, VdpTickEffect};
 use crate::{SmsGgButton, SmsGgInputs, VdpVersion, vdp};
 use bincode::{Decode, Encode};
+use jgenesis_common::clockratio::ClockRatio;
 use jgenesis_common::frontend::{
-    AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, FrameSize, PartialClone,
-    PixelAspectRatio, Renderer, SaveWriter, TickEffect, TimingMode,
-};
-use jgenesis_proc_macros::{
-    ConfigDisplay, EnumAll, EnumDisplay, EnumFromStr, FakeDecode, FakeEncode,
+    AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, FrameCrop, FrameSize, PartialClone,
+    PixelAspectRatio, Renderer, SaveStateSafePoint, SaveWriter, TextInputEvent, TickEffect,
+    TimingMode,
 };
+use jgenesis_common::instrument;
+use jgenesis_common::instrumentation::Subsystem;
+use jgenesis_common::memmap::MemoryMap;
+use jgenesis_proc_macros::{ConfigDisplay, EnumAll, EnumDisplay, EnumFromStr, SaveState};
 use std::fmt::{Debug, Display};
-use std::num::NonZeroU32;
 use std::ops::{Deref, DerefMut};
 use thiserror::Error;
 use ym_opll::Ym2413;
@@ -34,7 +36,7 @@ pub enum SmsGgError<RErr, AErr, SErr> {
 
 pub type SmsGgResult<RErr, AErr, SErr> = Result<TickEffect, SmsGgError<RErr, AErr, SErr>>;
 
-#[derive(Debug, Clone, FakeEncode, FakeDecode)]
+#[derive(Debug, Clone)]
 struct FrameBuffer(Vec<Color>);
 
 impl FrameBuffer {
@@ -63,10 +65,65 @@ impl DerefMut for FrameBuffer {
     }
 }
 
+// Last frame displayed on the Game Gear LCD, for frame blending
+#[derive(Debug, Clone, Default)]
+struct LcdFrame {
+    colors: FrameBuffer,
+    // None if there is no previous frame to blend with, e.g. right after loading a state
+    size: Option<FrameSize>,
+}
+
+impl LcdFrame {
+    // Blend a newly rendered frame with the previous LCD output. If the same frame is being rendered
+    // again, display the existing blended output instead of blending it a second time
+    fn apply(
+        &mut self,
+        frame_buffer: &mut [Color],
+        frame_size: FrameSize,
+        persistence: u8,
+        new_frame: bool,
+    ) {
+        let len = (frame_size.width * frame_size.height) as usize;
+        let frame_buffer = &mut frame_buffer[..len];
+
+        if !new_frame {
+            if self.size == Some(frame_size) {
+                frame_buffer.copy_from_slice(&self.colors[..len]);
+            }
+            return;
+        }
+
+        // Only blend with a previous frame of the same size; pixels would not line up otherwise
+        if self.size == Some(frame_size) {
+            for (color, &prev) in frame_buffer.iter_mut().zip(self.colors.iter()) {
+                *color = blend_lcd_color(prev, *color, persistence);
+            }
+        }
+
+        self.colors[..len].copy_from_slice(frame_buffer);
+        self.size = Some(frame_size);
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
 pub enum SmsGgHardware {
     MasterSystem,
     GameGear,
+    Sg1000,
+}
+
+impl SmsGgHardware {
+    /// Determine hardware from a ROM file extension, e.g. "sms" or "gg". Returns `None` if the
+    /// extension is not recognized.
+    #[must_use]
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "sms" => Some(Self::MasterSystem),
+            "gg" => Some(Self::GameGear),
+            "sg" | "sc" => Some(Self::Sg1000),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode, EnumDisplay, EnumAll)]
@@ -137,6 +194,14 @@ impl GgAspectRatio {
     }
 }
 
+/// Memory regions that can be dumped and restored for debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumAll)]
+pub enum MemoryRegion {
+    SystemRam,
+    Vram,
+    CartridgeRam,
+}
+
 #[derive(Debug, Clone, Copy, Encode, Decode, ConfigDisplay)]
 pub struct SmsGgEmulatorConfig {
     pub sms_timing_mode: TimingMode,
@@ -149,32 +214,110 @@ pub struct SmsGgEmulatorConfig {
     pub sms_crop_vertical_border: bool,
     pub sms_crop_left_border: bool,
     pub gg_use_sms_resolution: bool,
+    pub gg_frame_blending: bool,
+    pub gg_lcd_persistence: u8,
     pub fm_sound_unit_enabled: bool,
-    pub z80_divider: NonZeroU32,
+    pub z80_divider: ClockRatio,
+}
+
+impl Default for SmsGgEmulatorConfig {
+    fn default() -> Self {
+        Self {
+            sms_timing_mode: TimingMode::default(),
+            sms_model: SmsModel::default(),
+            forced_psg_version: None,
+            sms_aspect_ratio: SmsAspectRatio::default(),
+            gg_aspect_ratio: GgAspectRatio::default(),
+            remove_sprite_limit: false,
+            sms_region: SmsRegion::default(),
+            sms_crop_vertical_border: true,
+            sms_crop_left_border: false,
+            gg_use_sms_resolution: false,
+            gg_frame_blending: false,
+            gg_lcd_persistence: crate::DEFAULT_GG_LCD_PERSISTENCE,
+            fm_sound_unit_enabled: true,
+            z80_divider: ClockRatio::from_integer(crate::NATIVE_Z80_DIVIDER),
+        }
+    }
 }
 
 impl EmulatorConfigTrait for SmsGgEmulatorConfig {
     fn with_overclocking_disabled(&self) -> Self {
-        Self { z80_divider: NonZeroU32::new(crate::NATIVE_Z80_DIVIDER).unwrap(), ..*self }
+        Self { z80_divider: ClockRatio::from_integer(crate::NATIVE_Z80_DIVIDER), ..*self }
+    }
+
+    fn with_enhancements_disabled(&self) -> Self {
+        Self {
+            remove_sprite_limit: false,
+            gg_frame_blending: false,
+            ..self.with_overclocking_disabled()
+        }
     }
 }
 
-#[derive(Debug, Clone, Encode, Decode, PartialClone)]
+/// Master System / Game Gear / SG-1000 emulator.
+///
+/// Frontends drive the emulator by calling [`tick`](EmulatorTrait::tick) in a loop, which pushes
+/// audio samples as they are generated and renders a frame whenever one is complete:
+///
+/// ```
+/// use jgenesis_common::frontend::{
+///     Color, EmulatorTrait, FrameSize, NullAudioOutput, NullSaveWriter, PixelAspectRatio,
+///     Renderer,
+/// };
+/// use smsgg_core::{SmsGgEmulator, SmsGgEmulatorConfig, SmsGgHardware, SmsGgInputs};
+/// use std::convert::Infallible;
+///
+/// struct FrameCounter(u32);
+///
+/// impl Renderer for FrameCounter {
+///     type Err = Infallible;
+///
+///     fn render_frame(
+///         &mut self,
+///         _frame_buffer: &[Color],
+///         _frame_size: FrameSize,
+///         _pixel_aspect_ratio: Option<PixelAspectRatio>,
+///     ) -> Result<(), Self::Err> {
+///         self.0 += 1;
+///         Ok(())
+///     }
+/// }
+///
+/// // A ROM full of NOPs
+/// let rom = vec![0x00; 32 * 1024];
+/// let config = SmsGgEmulatorConfig::default();
+/// let mut emulator =
+///     SmsGgEmulator::create(rom, SmsGgHardware::MasterSystem, config, &mut NullSaveWriter);
+///
+/// let mut renderer = FrameCounter(0);
+/// let inputs = SmsGgInputs::default();
+/// while renderer.0 < 2 {
+///     emulator.tick(&mut renderer, &mut NullAudioOutput, &inputs, &mut NullSaveWriter).unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, SaveState, PartialClone)]
 pub struct SmsGgEmulator {
     #[partial_clone(partial)]
     memory: Memory,
     z80: Z80,
     vdp: Vdp,
     vdp_version: VdpVersion,
+    hardware: SmsGgHardware,
     pixel_aspect_ratio: Option<PixelAspectRatio>,
     psg: Sn76489,
     ym2413: Option<Ym2413>,
     input: InputState,
     audio_resampler: AudioResampler,
+    #[savestate(skip, default = FrameBuffer::new())]
     frame_buffer: FrameBuffer,
+    #[savestate(skip, default = LcdFrame::default())]
+    lcd_frame: LcdFrame,
     config: SmsGgEmulatorConfig,
     vdp_mclk_counter: u32,
     psg_mclk_counter: u32,
+    // Fractional master clock cycles left over from fractional Z80 dividers
+    z80_mclk_remainder: u32,
     frame_count: u64,
     reset_frames_remaining: u32,
 }
@@ -200,7 +343,7 @@ impl SmsGgEmulator {
         log::info!("VDP version: {vdp_version:?}");
         log::info!("PSG version: {psg_version:?}");
 
-        let memory = Memory::new(rom, cartridge_ram);
+        let memory = Memory::new(rom, cartridge_ram, hardware);
         let vdp = Vdp::new(vdp_version, &config);
         let psg = Sn76489::new(psg_version);
         let input = InputState::new(config.sms_region);
@@ -219,15 +362,18 @@ impl SmsGgEmulator {
             z80,
             vdp,
             vdp_version,
+            hardware,
             pixel_aspect_ratio,
             psg,
             ym2413,
             input,
             audio_resampler: AudioResampler::new(timing_mode),
             frame_buffer: FrameBuffer::new(),
+            lcd_frame: LcdFrame::default(),
             config,
             vdp_mclk_counter: 0,
             psg_mclk_counter: 0,
+            z80_mclk_remainder: 0,
             frame_count: 0,
             reset_frames_remaining: 0,
         }
@@ -235,11 +381,7 @@ impl SmsGgEmulator {
 
     #[must_use]
     pub fn hardware(&self) -> SmsGgHardware {
-        if self.vdp_version.is_master_system() {
-            SmsGgHardware::MasterSystem
-        } else {
-            SmsGgHardware::GameGear
-        }
+        self.hardware
     }
 
     #[must_use]
@@ -253,42 +395,148 @@ impl SmsGgEmulator {
         self.memory.cartridge_has_battery()
     }
 
-    fn render_frame<R: Renderer>(&mut self, renderer: &mut R) -> Result<(), R::Err> {
-        populate_frame_buffer(
-            self.vdp.frame_buffer(),
-            self.vdp_version,
-            self.config.sms_crop_vertical_border,
-            self.config.sms_crop_left_border,
-            &mut self.frame_buffer,
-        );
+    // `new_frame` is false when re-rendering the most recent frame, e.g. after loading a state
+    fn render_frame<R: Renderer>(
+        &mut self,
+        renderer: &mut R,
+        new_frame: bool,
+    ) -> Result<(), R::Err> {
+        instrument!("smsgg::render_frame");
+
+        populate_frame_buffer(self.vdp.frame_buffer(), self.vdp_version, &mut self.frame_buffer);
 
         let viewport = self.vdp.viewport();
-        let frame_width = if self.config.sms_crop_left_border {
-            viewport.width_without_border().into()
+        let frame_size = FrameSize { width: viewport.width.into(), height: viewport.height.into() };
+
+        // Simulate the slow response time of the Game Gear LCD, which some games depend on to make
+        // flickering sprites look transparent
+        if self.hardware == SmsGgHardware::GameGear && self.config.gg_frame_blending {
+            let persistence = self.config.gg_lcd_persistence.min(crate::MAX_GG_LCD_PERSISTENCE);
+            self.lcd_frame.apply(&mut self.frame_buffer, frame_size, persistence, new_frame);
         } else {
-            viewport.width.into()
+            self.lcd_frame.size = None;
+        }
+
+        let (left, width) = if self.config.sms_crop_left_border {
+            (viewport.left_border_width.into(), viewport.width_without_border().into())
+        } else {
+            (0, viewport.width.into())
         };
-        let frame_height = if self.config.sms_crop_vertical_border {
-            viewport.height_without_border().into()
+        let (top, height) = if self.config.sms_crop_vertical_border {
+            (viewport.top_border_height.into(), viewport.height_without_border().into())
         } else {
-            viewport.height.into()
+            (0, viewport.height.into())
         };
 
-        let frame_size = FrameSize { width: frame_width, height: frame_height };
-        renderer.render_frame(&self.frame_buffer, frame_size, self.pixel_aspect_ratio)
+        let crop = FrameCrop { left, top, width, height };
+        renderer.render_cropped_frame(&self.frame_buffer, frame_size, crop, self.pixel_aspect_ratio)
     }
 
     pub fn copy_cram(&self, out: &mut [Color]) {
         self.vdp.copy_cram(out);
     }
 
+    #[must_use]
+    pub fn dump_cram(&self) -> Vec<u8> {
+        self.vdp.dump_cram()
+    }
+
     pub fn copy_vram(&self, out: &mut [Color], palette: u8, row_len: usize) {
         self.vdp.copy_vram(out, palette, row_len);
     }
 
+    #[must_use]
+    pub fn dump_memory(&self, region: MemoryRegion) -> Vec<u8> {
+        match region {
+            MemoryRegion::SystemRam => self.memory.system_ram().to_vec(),
+            MemoryRegion::Vram => self.vdp.vram().to_vec(),
+            MemoryRegion::CartridgeRam => self.memory.cartridge_ram().to_vec(),
+        }
+    }
+
+    /// Overwrite a memory region, e.g. with the contents of a file previously written using
+    /// [`Self::dump_memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not the same length as the memory region.
+    pub fn restore_memory(&mut self, region: MemoryRegion, bytes: &[u8]) {
+        match region {
+            MemoryRegion::SystemRam => self.memory.system_ram_mut().copy_from_slice(bytes),
+            MemoryRegion::Vram => self.vdp.vram_mut().copy_from_slice(bytes),
+            MemoryRegion::CartridgeRam => self.memory.restore_cartridge_ram(bytes),
+        }
+    }
+
+    /// Describe the Z80 memory map, including which ROM banks are currently mapped into each slot.
+    #[must_use]
+    pub fn memory_map_description(&self) -> Vec<MemoryMap> {
+        vec![self.memory.memory_map()]
+    }
+
     pub fn dump_vdp_registers(&self, callback: impl FnMut(u32, &[(&str, &str)])) {
         self.vdp.dump_registers(callback);
     }
+
+    /// Mute or unmute one of the 4 PSG channels in the audio output (0-2 square, 3 noise).
+    pub fn set_psg_channel_muted(&mut self, channel: usize, muted: bool) {
+        self.psg.set_channel_muted(channel, muted);
+    }
+
+    /// Whether one of the 4 PSG channels is currently muted. Mutes are not saved in save states, so
+    /// loading a state unmutes every channel.
+    #[must_use]
+    pub fn is_psg_channel_muted(&self, channel: usize) -> bool {
+        self.psg.is_channel_muted(channel)
+    }
+
+    /// Plug or unplug a Gear-to-Gear cable in the Game Gear EXT port. While plugged in, the
+    /// frontend is responsible for exchanging pin levels and serial bytes with the other Game Gear.
+    pub fn set_link_cable_connected(&mut self, connected: bool) {
+        self.input.set_link_cable_connected(connected);
+    }
+
+    /// Attach or detach the SC-3000 keyboard. While attached, I/O ports $DC-$DF read the keyboard
+    /// matrix through the PPI, and typed text is entered on the keyboard.
+    pub fn set_keyboard_connected(&mut self, connected: bool) {
+        self.input.set_keyboard_connected(connected);
+    }
+
+    /// EXT port pin levels that should be sent to the other Game Gear.
+    #[must_use]
+    pub fn link_cable_output_pins(&self) -> u8 {
+        self.input.link_cable_output_pins()
+    }
+
+    /// Update EXT port pin levels received from the other Game Gear.
+    pub fn set_link_cable_input_pins(&mut self, pins: u8) {
+        self.input.set_link_cable_input_pins(pins);
+    }
+
+    /// Take a byte transmitted in serial mode that should be sent to the other Game Gear.
+    pub fn take_link_cable_tx_byte(&mut self) -> Option<u8> {
+        self.input.take_link_cable_tx_byte()
+    }
+
+    /// Receive a byte in serial mode that was transmitted by the other Game Gear.
+    pub fn receive_link_cable_byte(&mut self, byte: u8) {
+        self.input.receive_link_cable_byte(byte);
+    }
+
+    /// Exchange pin levels and serial bytes with another emulator instance in the same process,
+    /// as if the two were connected by a Gear-to-Gear cable. Should be called after every tick of
+    /// either emulator.
+    pub fn exchange_link_cable_data(&mut self, other: &mut Self) {
+        self.set_link_cable_input_pins(other.link_cable_output_pins());
+        other.set_link_cable_input_pins(self.link_cable_output_pins());
+
+        if let Some(byte) = self.take_link_cable_tx_byte() {
+            other.receive_link_cable_byte(byte);
+        }
+        if let Some(byte) = other.take_link_cable_tx_byte() {
+            self.receive_link_cable_byte(byte);
+        }
+    }
 }
 
 fn init_z80(z80: &mut Z80) {
@@ -312,13 +560,16 @@ fn determine_vdp_version(hardware: SmsGgHardware, config: &SmsGgEmulatorConfig)
             VdpVersion::PalMasterSystem2
         }
         (SmsGgHardware::GameGear, _, _) => VdpVersion::GameGear,
+        // The SG-1000's TMS9918 behaves the same as the SMS2 VDP in the legacy TMS9918 modes
+        (SmsGgHardware::Sg1000, TimingMode::Ntsc, _) => VdpVersion::NtscMasterSystem2,
+        (SmsGgHardware::Sg1000, TimingMode::Pal, _) => VdpVersion::PalMasterSystem2,
     }
 }
 
 fn determine_psg_version(hardware: SmsGgHardware, config: &SmsGgEmulatorConfig) -> Sn76489Version {
     config.forced_psg_version.unwrap_or(match hardware {
         SmsGgHardware::MasterSystem => Sn76489Version::MasterSystem2,
-        SmsGgHardware::GameGear => Sn76489Version::Standard,
+        SmsGgHardware::GameGear | SmsGgHardware::Sg1000 => Sn76489Version::Standard,
     })
 }
 
@@ -327,7 +578,9 @@ fn determine_aspect_ratio(
     config: &SmsGgEmulatorConfig,
 ) -> Option<PixelAspectRatio> {
     match hardware {
-        SmsGgHardware::MasterSystem => config.sms_aspect_ratio.to_pixel_aspect_ratio(),
+        SmsGgHardware::MasterSystem | SmsGgHardware::Sg1000 => {
+            config.sms_aspect_ratio.to_pixel_aspect_ratio()
+        }
         SmsGgHardware::GameGear => config.gg_aspect_ratio.to_pixel_aspect_ratio(),
     }
 }
@@ -363,26 +616,34 @@ impl EmulatorTrait for SmsGgEmulator {
         A: AudioOutput,
         S: SaveWriter,
     {
-        let z80_t_cycles = self.z80.execute_instruction(&mut Bus::new(
-            self.vdp_version,
-            &mut self.memory,
-            &mut self.vdp,
-            &mut self.psg,
-            self.ym2413.as_mut(),
-            &mut self.input,
-        ));
-
-        let mclk_cycles = z80_t_cycles * self.config.z80_divider.get();
+        let z80_t_cycles = instrument!(
+            Subsystem::Cpu,
+            self.z80.execute_instruction(&mut Bus::new(
+                self.vdp_version,
+                &mut self.memory,
+                &mut self.vdp,
+                &mut self.psg,
+                self.ym2413.as_mut(),
+                &mut self.input,
+            ))
+        );
+
+        let mclk_cycles =
+            self.config.z80_divider.scale_cycles(z80_t_cycles.into(), &mut self.z80_mclk_remainder)
+                as u32;
         self.vdp_mclk_counter += mclk_cycles;
         self.psg_mclk_counter += mclk_cycles;
 
         while self.psg_mclk_counter >= PSG_DIVIDER {
             self.psg_mclk_counter -= PSG_DIVIDER;
 
-            if let Some(ym2413) = &mut self.ym2413 {
-                ym2413.tick();
-            }
-            if self.psg.tick() == Sn76489TickEffect::Clocked {
+            let psg_tick_effect = instrument!(Subsystem::Apu, {
+                if let Some(ym2413) = &mut self.ym2413 {
+                    ym2413.tick();
+                }
+                self.psg.tick()
+            });
+            if psg_tick_effect == Sn76489TickEffect::Clocked {
                 let (psg_sample_l, psg_sample_r) =
                     if self.memory.psg_enabled() { self.psg.sample() } else { (0.0, 0.0) };
                 let ym_sample = if self.memory.fm_enabled() {
@@ -403,8 +664,8 @@ impl EmulatorTrait for SmsGgEmulator {
         while self.vdp_mclk_counter >= VDP_DIVIDER {
             self.vdp_mclk_counter -= VDP_DIVIDER;
 
-            if self.vdp.tick() == VdpTickEffect::FrameComplete {
-                self.render_frame(renderer).map_err(SmsGgError::Render)?;
+            if instrument!(Subsystem::Ppu, self.vdp.tick()) == VdpTickEffect::FrameComplete {
+                self.render_frame(renderer, true).map_err(SmsGgError::Render)?;
                 frame_rendered = true;
 
                 self.input.set_inputs(*inputs);
@@ -431,7 +692,19 @@ impl EmulatorTrait for SmsGgEmulator {
     where
         R: Renderer,
     {
-        self.render_frame(renderer)
+        self.render_frame(renderer, false)
+    }
+
+    fn clear_audio_mutes(&mut self) {
+        self.psg.clear_muted_channels();
+    }
+
+    fn wants_text_input(&self) -> bool {
+        self.input.keyboard_connected()
+    }
+
+    fn handle_text_input(&mut self, event: TextInputEvent) {
+        self.input.handle_text_input(event);
     }
 
     fn reload_config(&mut self, config: &Self::Config) {
@@ -450,6 +723,8 @@ impl EmulatorTrait for SmsGgEmulator {
 
     fn take_rom_from(&mut self, other: &mut Self) {
         self.memory.take_rom_from(&mut other.memory);
+        self.input.set_link_cable_connected(other.input.link_cable_connected());
+        self.input.set_keyboard_connected(other.input.keyboard_connected());
     }
 
     fn soft_reset(&mut self) {
@@ -464,20 +739,30 @@ impl EmulatorTrait for SmsGgEmulator {
         log::info!("Hard resetting console");
 
         let (rom, ram) = self.memory.take_cartridge_rom_and_ram();
-        self.memory = Memory::new(rom, Some(ram));
+        self.memory = Memory::new(rom, Some(ram), self.hardware);
 
         self.z80 = Z80::new();
         init_z80(&mut self.z80);
 
         self.vdp = Vdp::new(self.vdp_version, &self.config);
         self.psg = Sn76489::new(self.psg.version());
+        let link_cable_connected = self.input.link_cable_connected();
+        let keyboard_connected = self.input.keyboard_connected();
         self.input = InputState::new(self.input.region());
+        self.input.set_link_cable_connected(link_cable_connected);
+        self.input.set_keyboard_connected(keyboard_connected);
 
         self.vdp_mclk_counter = 0;
         self.psg_mclk_counter = 0;
+        self.z80_mclk_remainder = 0;
         self.frame_count = 0;
     }
 
+    fn save_state_safe_point() -> SaveStateSafePoint {
+        // The VDP fills in the frame buffer one line at a time, and it is not saved
+        SaveStateSafePoint::FrameBoundary
+    }
+
     fn target_fps(&self) -> f64 {
         let timing_mode = self.vdp.timing_mode();
         let mclk_frequency = timing_mode.mclk_frequency();
@@ -497,25 +782,12 @@ impl EmulatorTrait for SmsGgEmulator {
 fn populate_frame_buffer(
     vdp_buffer: &VdpBuffer,
     vdp_version: VdpVersion,
-    crop_vertical_border: bool,
-    crop_left_border: bool,
     frame_buffer: &mut [Color],
 ) {
-    let viewport = vdp_buffer.viewport();
-
-    let (row_skip, row_take) = if crop_vertical_border {
-        (viewport.top_border_height as usize, viewport.height_without_border() as usize)
-    } else {
-        (0, viewport.height as usize)
-    };
-    let (col_skip, screen_width) = if crop_left_border {
-        (viewport.left_border_width as usize, viewport.width_without_border() as usize)
-    } else {
-        (0, viewport.width as usize)
-    };
+    let screen_width = vdp_buffer.viewport().width as usize;
 
-    for (i, row) in vdp_buffer.iter().skip(row_skip).take(row_take).enumerate() {
-        for (j, color) in row.iter().copied().skip(col_skip).enumerate() {
+    for (i, row) in vdp_buffer.iter().enumerate() {
+        for (j, color) in row.iter().copied().enumerate() {
             let (r, g, b) = if vdp_version.is_master_system() {
                 (
                     vdp::convert_sms_color(color & 0x03),
@@ -534,3 +806,130 @@ fn populate_frame_buffer(
         }
     }
 }
+
+// `persistence` is the percentage of each pixel's previous color that is blended into the new color
+fn blend_lcd_color(prev: Color, current: Color, persistence: u8) -> Color {
+    let blend = |prev: u8, current: u8| -> u8 {
+        let persistence = u16::from(persistence);
+        ((u16::from(prev) * persistence + u16::from(current) * (100 - persistence) + 50) / 100)
+            as u8
+    };
+
+    Color::rgb(blend(prev.r, current.r), blend(prev.g, current.g), blend(prev.b, current.b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jgenesis_common::frontend::NullSaveWriter;
+    use jgenesis_common::savestate;
+
+    // Enables Mode 4 graphics, the display, and VBlank interrupts, then loops forever while the interrupt handler
+    // increments a counter in RAM and writes it to CRAM
+    fn test_rom() -> Vec<u8> {
+        let main: &[u8] = &[
+            0xF3, // DI
+            0x31, 0xF0, 0xDF, // LD SP, $DFF0
+            0xED, 0x56, // IM 1
+            0x3E, 0x04, 0xD3, 0xBF, 0x3E, 0x80, 0xD3, 0xBF, // VDP R0 = $04 (Mode 4)
+            0x3E, 0x60, 0xD3, 0xBF, 0x3E, 0x81, 0xD3, 0xBF, // VDP R1 = $60
+            0xFB, // EI
+            0x18, 0xFE, // JR -2
+        ];
+
+        let interrupt_handler: &[u8] = &[
+            0xDB, 0xBF, // IN A, ($BF)
+            0x3A, 0x00, 0xC0, // LD A, ($C000)
+            0x3C, // INC A
+            0x32, 0x00, 0xC0, // LD ($C000), A
+            0x47, // LD B, A
+            0x3E, 0x00, 0xD3, 0xBF, 0x3E, 0xC0, 0xD3, 0xBF, // CRAM address = $00
+            0x78, // LD A, B
+            0xD3, 0xBE, // OUT ($BE), A
+            0xFB, // EI
+            0xC9, // RET
+        ];
+
+        savestate::test_rom(0x8000, &[(0x0000, main), (0x0038, interrupt_handler)])
+    }
+
+    #[test]
+    fn lcd_blending_only_on_new_frames() {
+        let size = FrameSize { width: 2, height: 1 };
+        let white = Color::rgb(255, 255, 255);
+        let black = Color::rgb(0, 0, 0);
+        let gray = Color::rgb(128, 128, 128);
+
+        let mut lcd_frame = LcdFrame::default();
+
+        // No previous frame; nothing to blend with
+        let mut frame = vec![white; 4];
+        lcd_frame.apply(&mut frame, size, 50, true);
+        assert_eq!(frame[..2], [white, white]);
+
+        let mut frame = vec![black; 4];
+        lcd_frame.apply(&mut frame, size, 50, true);
+        assert_eq!(frame[..2], [gray, gray]);
+
+        // Re-rendering the same frame shows the same blended output
+        let mut frame = vec![black; 4];
+        lcd_frame.apply(&mut frame, size, 50, false);
+        assert_eq!(frame[..2], [gray, gray]);
+
+        // A frame of a different size is not blended
+        let mut frame = vec![black; 4];
+        lcd_frame.apply(&mut frame, FrameSize { width: 1, height: 2 }, 50, true);
+        assert_eq!(frame[..2], [black, black]);
+    }
+
+    #[test]
+    fn exchange_link_cable_data() {
+        let create = || {
+            let mut emulator = SmsGgEmulator::create(
+                test_rom(),
+                SmsGgHardware::GameGear,
+                SmsGgEmulatorConfig::default(),
+                &mut NullSaveWriter,
+            );
+            emulator.set_link_cable_connected(true);
+            emulator
+        };
+        let mut a = create();
+        let mut b = create();
+
+        // A drives PC0-PC3 and PC6, B drives PC4 low
+        a.input.write_gg_ext_direction(0xB0);
+        a.input.write_gg_ext_data(0x0A);
+        b.input.write_gg_ext_direction(0x6F);
+        b.input.write_gg_ext_data(0x00);
+
+        a.exchange_link_cable_data(&mut b);
+        assert_eq!(b.input.gg_ext_data(), 0x2A);
+        assert_eq!(a.input.gg_ext_data() & 0x10, 0x00);
+
+        // A transmits in serial mode, B receives
+        a.input.write_gg_serial_ctrl(0x10);
+        b.input.write_gg_serial_ctrl(0x20);
+        a.input.write_gg_tx_data(0x5C);
+
+        a.exchange_link_cable_data(&mut b);
+        assert_eq!(b.input.read_gg_rx_data(), 0x5C);
+        assert_eq!(a.take_link_cable_tx_byte(), None);
+    }
+
+    #[test]
+    fn save_state_round_trip() {
+        savestate::assert_round_trip_deterministic(
+            || {
+                SmsGgEmulator::create(
+                    test_rom(),
+                    SmsGgHardware::MasterSystem,
+                    SmsGgEmulatorConfig::default(),
+                    &mut NullSaveWriter,
+                )
+            },
+            100_000,
+            5_000,
+        );
+    }
+}
@@ -43,12 +43,14 @@ impl BusInterface for Bus<'_> {
     fn read_io(&mut self, address: u16) -> u8 {
         let address = address & 0xFF;
         if self.version == VdpVersion::GameGear && address <= 0x06 {
-            // TODO Game Gear registers
             return match address {
                 0x00 => (u8::from(!self.input.pause_pressed()) << 7) | 0x40,
-                0x01 => 0x7F,
-                0x02 | 0x04 | 0x06 => 0xFF,
-                0x03 | 0x05 => 0x00,
+                0x01 => self.input.gg_ext_data(),
+                0x02 => self.input.gg_ext_direction(),
+                0x03 => self.input.gg_tx_data(),
+                0x04 => self.input.read_gg_rx_data(),
+                0x05 => self.input.gg_serial_ctrl(),
+                0x06 => 0xFF,
                 _ => unreachable!("value is <= 0x06"),
             };
         }
@@ -93,8 +95,13 @@ impl BusInterface for Bus<'_> {
     fn write_io(&mut self, address: u16, value: u8) {
         let address = address & 0xFF;
         if self.version == VdpVersion::GameGear && address <= 0x06 {
-            if address == 0x06 {
-                self.psg.write_stereo_control(value);
+            match address {
+                0x01 => self.input.write_gg_ext_data(value),
+                0x02 => self.input.write_gg_ext_direction(value),
+                0x03 => self.input.write_gg_tx_data(value),
+                0x05 => self.input.write_gg_serial_ctrl(value),
+                0x06 => self.psg.write_stereo_control(value),
+                _ => {}
             }
             return;
         }
@@ -138,12 +145,16 @@ impl BusInterface for Bus<'_> {
                 log::trace!("VDP control write: {value:02X}");
                 self.vdp.write_control(value);
             }
-            (true, true, _) => {}
+            (true, true, _) => {
+                self.input.write_ppi(address as u8, value);
+            }
         }
     }
 
     fn nmi(&self) -> InterruptLine {
-        if self.version.is_master_system() && self.input.pause_pressed() {
+        if (self.version.is_master_system() && self.input.pause_pressed())
+            || (self.version == VdpVersion::GameGear && self.input.gg_ext_nmi())
+        {
             InterruptLine::Low
         } else {
             InterruptLine::High
@@ -1,8 +1,13 @@
 //! Code for handling Sega Master System / Game Gear controller input I/O registers
 
+mod keyboard;
+
 use crate::api::SmsRegion;
+use crate::input::keyboard::Keyboard;
 use bincode::{Decode, Encode};
 use jgenesis_common::define_controller_inputs;
+use jgenesis_common::frontend::{AnalogInput, MappableInputs, TextInputEvent};
+use jgenesis_common::input::Player;
 use jgenesis_common::num::GetBit;
 
 define_controller_inputs! {
@@ -16,13 +21,107 @@ define_controller_inputs! {
     },
     non_gamepad_buttons: [Pause],
     joypad: SmsGgJoypadState,
-    inputs: SmsGgInputs {
-        players: {
-            p1: Player::One,
-            p2: Player::Two,
-        },
-        buttons: [Pause -> pause],
-    },
+}
+
+/// Paddle Control (HPD-200) state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Encode, Decode)]
+pub struct PaddleState {
+    /// Knob position, from 0 (fully left) to 255 (fully right)
+    pub position: u8,
+}
+
+impl Default for PaddleState {
+    fn default() -> Self {
+        Self { position: 0x80 }
+    }
+}
+
+/// Sports Pad trackball state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, Encode, Decode)]
+pub struct SportsPadState {
+    /// Trackball position counters. These wrap freely; the Sports Pad reports how far they moved
+    /// since the previous read
+    pub x: u8,
+    pub y: u8,
+    /// Analog stick deflection, which rolls the trackball continuously at a proportional speed
+    pub stick_x: i8,
+    pub stick_y: i8,
+}
+
+/// Device plugged into controller port 1. The paddle and Sports Pad buttons are read from the
+/// player 1 joypad state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, Encode, Decode)]
+pub enum SmsGgPeripheral {
+    #[default]
+    Controller,
+    Paddle(PaddleState),
+    SportsPad(SportsPadState),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, Encode, Decode)]
+pub struct SmsGgInputs {
+    pub p1: SmsGgJoypadState,
+    pub p2: SmsGgJoypadState,
+    pub pause: bool,
+    pub p1_peripheral: SmsGgPeripheral,
+}
+
+impl MappableInputs<SmsGgButton> for SmsGgInputs {
+    #[inline]
+    fn set_field(&mut self, button: SmsGgButton, player: Player, pressed: bool) {
+        match (button, player) {
+            (SmsGgButton::Pause, _) => self.pause = pressed,
+            (button, Player::One) => self.p1.set_button(button, pressed),
+            (button, Player::Two) => self.p2.set_button(button, pressed),
+        }
+    }
+
+    fn button_states(&self, player: Player) -> Vec<(SmsGgButton, bool)> {
+        match player {
+            Player::One => {
+                let mut states = self.p1.button_states();
+                states.push((SmsGgButton::Pause, self.pause));
+                states
+            }
+            Player::Two => self.p2.button_states(),
+        }
+    }
+
+    #[inline]
+    fn handle_analog_input(&mut self, input: AnalogInput) {
+        match (&mut self.p1_peripheral, input) {
+            (SmsGgPeripheral::Paddle(paddle), AnalogInput::MousePosition { x, frame_size, .. }) => {
+                // Map the full width of the frame to the paddle's range
+                let width = f64::from(frame_size.width.saturating_sub(1).max(1));
+                paddle.position = (x * 255.0 / width).round().clamp(0.0, 255.0) as u8;
+            }
+            (SmsGgPeripheral::SportsPad(sports_pad), AnalogInput::MouseMotion { dx, dy }) => {
+                // Roll the trackball by the relative mouse motion so that it keeps moving when the
+                // cursor reaches the edge of the frame; the counters wrap, so truncating is fine
+                sports_pad.x = sports_pad.x.wrapping_add(dx as u8);
+                sports_pad.y = sports_pad.y.wrapping_add(dy as u8);
+            }
+            (
+                SmsGgPeripheral::Paddle(paddle),
+                AnalogInput::GamepadAxis { player: Player::One, axis_idx: 0, value },
+            ) => {
+                // Convert from -1.0..=1.0 to 0..=255, centered at 0x80
+                paddle.position = ((value + 1.0) * 127.5).round().clamp(0.0, 255.0) as u8;
+            }
+            (
+                SmsGgPeripheral::SportsPad(sports_pad),
+                AnalogInput::GamepadAxis { player: Player::One, axis_idx, value },
+            ) => {
+                let value = (value * 127.0).round() as i8;
+                match axis_idx {
+                    0 => sports_pad.stick_x = value,
+                    1 => sports_pad.stick_y = value,
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
@@ -40,6 +139,103 @@ impl PinDirection {
     }
 }
 
+// Game Gear serial control register bits
+const SCTRL_RXD_READY_BIT: u8 = 1;
+const SCTRL_FRAMING_ERROR_BIT: u8 = 2;
+const SCTRL_RX_NMI_BIT: u8 = 3;
+const SCTRL_SERIAL_OUT_BIT: u8 = 4;
+const SCTRL_SERIAL_IN_BIT: u8 = 5;
+
+// EXT port direction register: PC6 input can trigger NMI if bit 7 is cleared
+const EXT_NMI_PIN: u8 = 6;
+const EXT_NMI_DISABLE_BIT: u8 = 7;
+
+/// The Game Gear EXT port and its serial registers, which can be connected to another Game Gear
+/// through a Gear-to-Gear cable.
+///
+/// In parallel mode, every pin that one Game Gear sets to output is seen on the other Game Gear's
+/// corresponding input pin. In serial mode, bytes written to the transmit register are sent to the
+/// other Game Gear's receive register. Serial transfers complete instantly rather than being timed
+/// to the baud rate.
+#[derive(Debug, Clone, Encode, Decode)]
+struct ExtPort {
+    connected: bool,
+    data: u8,
+    direction: u8,
+    remote_pins: u8,
+    tx_data: u8,
+    rx_data: u8,
+    serial_ctrl: u8,
+    pending_tx: Option<u8>,
+}
+
+impl Default for ExtPort {
+    fn default() -> Self {
+        Self {
+            connected: false,
+            data: 0x7F,
+            direction: 0xFF,
+            // Unconnected input pins are pulled high
+            remote_pins: 0x7F,
+            tx_data: 0x00,
+            rx_data: 0xFF,
+            serial_ctrl: 0x00,
+            pending_tx: None,
+        }
+    }
+}
+
+/// The export Sports Pad sends its X and Y motion as 4 nibbles (X high, X low, Y high, Y low),
+/// advancing to the next nibble every time the console toggles TH. Motion is latched when a new
+/// sequence starts.
+#[derive(Debug, Clone, Default, Encode, Decode)]
+struct SportsPadReader {
+    nibble_idx: u8,
+    last_x: u8,
+    last_y: u8,
+    latched_x: u8,
+    latched_y: u8,
+    // Accumulated trackball motion from analog stick input
+    stick_offset_x: u8,
+    stick_offset_y: u8,
+}
+
+impl SportsPadReader {
+    // The stick moves the trackball by at most this many counts per frame
+    const STICK_SHIFT: u32 = 4;
+
+    fn roll_stick(&mut self, state: SportsPadState) {
+        self.stick_offset_x =
+            self.stick_offset_x.wrapping_add((state.stick_x >> Self::STICK_SHIFT) as u8);
+        self.stick_offset_y =
+            self.stick_offset_y.wrapping_add((state.stick_y >> Self::STICK_SHIFT) as u8);
+    }
+
+    fn advance(&mut self, state: SportsPadState) {
+        self.nibble_idx = (self.nibble_idx + 1) & 3;
+        if self.nibble_idx != 0 {
+            return;
+        }
+
+        // The Sports Pad reports the negated distance that the trackball moved
+        let x = state.x.wrapping_add(self.stick_offset_x);
+        let y = state.y.wrapping_add(self.stick_offset_y);
+        self.latched_x = self.last_x.wrapping_sub(x);
+        self.latched_y = self.last_y.wrapping_sub(y);
+        self.last_x = x;
+        self.last_y = y;
+    }
+
+    fn nibble(&self) -> u8 {
+        match self.nibble_idx {
+            0 => self.latched_x >> 4,
+            1 => self.latched_x & 0xF,
+            2 => self.latched_y >> 4,
+            _ => self.latched_y & 0xF,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct InputState {
     inputs: SmsGgInputs,
@@ -49,6 +245,10 @@ pub struct InputState {
     port_b_th: PinDirection,
     region: SmsRegion,
     reset: bool,
+    ext: ExtPort,
+    paddle_high_nibble: bool,
+    sports_pad: SportsPadReader,
+    keyboard: Option<Keyboard>,
 }
 
 impl InputState {
@@ -61,6 +261,10 @@ impl InputState {
             port_b_th: PinDirection::Input,
             region,
             reset: false,
+            ext: ExtPort::default(),
+            paddle_high_nibble: false,
+            sports_pad: SportsPadReader::default(),
+            keyboard: None,
         }
     }
 
@@ -70,6 +274,14 @@ impl InputState {
 
     pub fn set_inputs(&mut self, inputs: SmsGgInputs) {
         self.inputs = inputs;
+
+        if let SmsGgPeripheral::SportsPad(sports_pad) = inputs.p1_peripheral {
+            self.sports_pad.roll_stick(sports_pad);
+        }
+
+        if let Some(keyboard) = &mut self.keyboard {
+            keyboard.end_frame();
+        }
     }
 
     pub fn region(&self) -> SmsRegion {
@@ -85,6 +297,8 @@ impl InputState {
     }
 
     pub fn write_control(&mut self, value: u8) {
+        let prev_port_a_th = self.port_a_th_level();
+
         self.port_b_th =
             if value.bit(3) { PinDirection::Input } else { PinDirection::Output(value.bit(7)) };
         self.port_b_tr =
@@ -93,22 +307,76 @@ impl InputState {
             if value.bit(1) { PinDirection::Input } else { PinDirection::Output(value.bit(5)) };
         self.port_a_tr =
             if value.bit(0) { PinDirection::Input } else { PinDirection::Output(value.bit(4)) };
+
+        if let SmsGgPeripheral::SportsPad(sports_pad) = self.inputs.p1_peripheral {
+            if self.port_a_th_level() != prev_port_a_th {
+                self.sports_pad.advance(sports_pad);
+            }
+        }
+    }
+
+    // Japanese consoles cannot drive TH, so it always reads as pulled high
+    fn port_a_th_level(&self) -> bool {
+        self.region == SmsRegion::Domestic || self.port_a_th.bit(true)
     }
 
-    pub fn port_dc(&self) -> u8 {
-        let port_a_tr_bit = u8::from(self.port_a_tr.bit(!self.inputs.p1.button2)) << 5;
+    pub fn port_dc(&mut self) -> u8 {
+        if let Some(keyboard) = &self.keyboard {
+            if !keyboard.joypad_selected() {
+                return keyboard.port_a();
+            }
+        }
+
+        let port_a = match self.inputs.p1_peripheral {
+            SmsGgPeripheral::Controller => {
+                let port_a_tr_bit = u8::from(self.port_a_tr.bit(!self.inputs.p1.button2)) << 5;
 
-        (u8::from(!self.inputs.p2.down) << 7)
-            | (u8::from(!self.inputs.p2.up) << 6)
-            | port_a_tr_bit
-            | (u8::from(!self.inputs.p1.button1) << 4)
-            | (u8::from(!self.inputs.p1.right) << 3)
-            | (u8::from(!self.inputs.p1.left) << 2)
-            | (u8::from(!self.inputs.p1.down) << 1)
-            | u8::from(!self.inputs.p1.up)
+                port_a_tr_bit
+                    | (u8::from(!self.inputs.p1.button1) << 4)
+                    | (u8::from(!self.inputs.p1.right) << 3)
+                    | (u8::from(!self.inputs.p1.left) << 2)
+                    | (u8::from(!self.inputs.p1.down) << 1)
+                    | u8::from(!self.inputs.p1.up)
+            }
+            SmsGgPeripheral::Paddle(paddle) => self.paddle_port_a(paddle),
+            SmsGgPeripheral::SportsPad(_) => {
+                let port_a_tr_bit = u8::from(self.port_a_tr.bit(!self.inputs.p1.button2)) << 5;
+
+                port_a_tr_bit | (u8::from(!self.inputs.p1.button1) << 4) | self.sports_pad.nibble()
+            }
+        };
+
+        (u8::from(!self.inputs.p2.down) << 7) | (u8::from(!self.inputs.p2.up) << 6) | port_a
+    }
+
+    // The paddle sends its position one nibble at a time, with TR indicating which nibble is on the
+    // data lines. Japanese paddles switch nibbles on their own (approximated here by switching on
+    // every read), while export paddles switch based on TH. Japanese games detect the paddle by
+    // watching TR toggle, so they do not detect it on an export console unless the game drives TH
+    fn paddle_port_a(&mut self, paddle: PaddleState) -> u8 {
+        let high_nibble = match self.region {
+            SmsRegion::Domestic => {
+                self.paddle_high_nibble = !self.paddle_high_nibble;
+                self.paddle_high_nibble
+            }
+            SmsRegion::International => self.port_a_th.bit(true),
+        };
+
+        let nibble = if high_nibble { paddle.position >> 4 } else { paddle.position & 0xF };
+        (u8::from(high_nibble) << 5) | (u8::from(!self.inputs.p1.button1) << 4) | nibble
     }
 
     pub fn port_dd(&self) -> u8 {
+        let joypad_bits = self.joypad_port_dd();
+        match &self.keyboard {
+            Some(keyboard) if !keyboard.joypad_selected() => {
+                (joypad_bits & 0xF0) | keyboard.port_b_keys()
+            }
+            _ => joypad_bits,
+        }
+    }
+
+    fn joypad_port_dd(&self) -> u8 {
         let port_b_th_bit =
             u8::from(self.region == SmsRegion::International && self.port_b_th.bit(true)) << 7;
         let port_a_th_bit =
@@ -124,4 +392,239 @@ impl InputState {
             | (u8::from(!self.inputs.p2.right) << 1)
             | u8::from(!self.inputs.p2.left)
     }
+
+    // Game Gear port $01
+    pub fn gg_ext_data(&self) -> u8 {
+        let ext = &self.ext;
+        ((ext.remote_pins & ext.direction) | (ext.data & !ext.direction)) & 0x7F
+    }
+
+    pub fn write_gg_ext_data(&mut self, value: u8) {
+        self.ext.data = value & 0x7F;
+    }
+
+    // Game Gear port $02
+    pub fn gg_ext_direction(&self) -> u8 {
+        self.ext.direction
+    }
+
+    pub fn write_gg_ext_direction(&mut self, value: u8) {
+        self.ext.direction = value;
+    }
+
+    // Game Gear port $03
+    pub fn gg_tx_data(&self) -> u8 {
+        self.ext.tx_data
+    }
+
+    pub fn write_gg_tx_data(&mut self, value: u8) {
+        self.ext.tx_data = value;
+        if self.ext.connected && self.ext.serial_ctrl.bit(SCTRL_SERIAL_OUT_BIT) {
+            self.ext.pending_tx = Some(value);
+        }
+    }
+
+    // Game Gear port $04
+    pub fn read_gg_rx_data(&mut self) -> u8 {
+        self.ext.serial_ctrl &= !((1 << SCTRL_RXD_READY_BIT) | (1 << SCTRL_FRAMING_ERROR_BIT));
+        self.ext.rx_data
+    }
+
+    // Game Gear port $05
+    pub fn gg_serial_ctrl(&self) -> u8 {
+        self.ext.serial_ctrl
+    }
+
+    pub fn write_gg_serial_ctrl(&mut self, value: u8) {
+        // Lowest 3 bits are read-only status bits
+        self.ext.serial_ctrl = (value & 0xF8) | (self.ext.serial_ctrl & 0x07);
+    }
+
+    /// Whether the EXT port is currently asserting NMI, either from PC6 being pulled low by the
+    /// other Game Gear or from a received serial byte.
+    pub fn gg_ext_nmi(&self) -> bool {
+        let ext = &self.ext;
+        let pin_nmi = !ext.direction.bit(EXT_NMI_DISABLE_BIT)
+            && ext.direction.bit(EXT_NMI_PIN)
+            && !ext.remote_pins.bit(EXT_NMI_PIN);
+        let serial_nmi =
+            ext.serial_ctrl.bit(SCTRL_RX_NMI_BIT) && ext.serial_ctrl.bit(SCTRL_RXD_READY_BIT);
+        pin_nmi || serial_nmi
+    }
+
+    pub fn keyboard_connected(&self) -> bool {
+        self.keyboard.is_some()
+    }
+
+    pub fn set_keyboard_connected(&mut self, connected: bool) {
+        if connected != self.keyboard.is_some() {
+            self.keyboard = connected.then(Keyboard::new);
+        }
+    }
+
+    // SC-3000 PPI port C ($DE) and control register ($DF)
+    pub fn write_ppi(&mut self, address: u8, value: u8) {
+        let Some(keyboard) = &mut self.keyboard else { return };
+
+        match address & 3 {
+            2 => keyboard.write_port_c(value),
+            3 => keyboard.write_control(value),
+            _ => {}
+        }
+    }
+
+    pub fn handle_text_input(&mut self, event: TextInputEvent) {
+        if let Some(keyboard) = &mut self.keyboard {
+            keyboard.handle_text_input(event);
+        }
+    }
+
+    pub fn link_cable_connected(&self) -> bool {
+        self.ext.connected
+    }
+
+    pub fn set_link_cable_connected(&mut self, connected: bool) {
+        self.ext.connected = connected;
+    }
+
+    pub fn link_cable_output_pins(&self) -> u8 {
+        let ext = &self.ext;
+        ((ext.data & !ext.direction) | ext.direction) & 0x7F
+    }
+
+    pub fn set_link_cable_input_pins(&mut self, pins: u8) {
+        self.ext.remote_pins = pins & 0x7F;
+    }
+
+    pub fn take_link_cable_tx_byte(&mut self) -> Option<u8> {
+        self.ext.pending_tx.take()
+    }
+
+    pub fn receive_link_cable_byte(&mut self, byte: u8) {
+        let ext = &mut self.ext;
+        if !ext.serial_ctrl.bit(SCTRL_SERIAL_IN_BIT) {
+            return;
+        }
+
+        if ext.serial_ctrl.bit(SCTRL_RXD_READY_BIT) {
+            // Previous byte was never read
+            ext.serial_ctrl |= 1 << SCTRL_FRAMING_ERROR_BIT;
+        }
+
+        ext.rx_data = byte;
+        ext.serial_ctrl |= 1 << SCTRL_RXD_READY_BIT;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jgenesis_common::frontend::FrameSize;
+
+    #[test]
+    fn gear_to_gear() {
+        let mut a = InputState::new(SmsRegion::International);
+        let mut b = InputState::new(SmsRegion::International);
+        a.set_link_cable_connected(true);
+        b.set_link_cable_connected(true);
+
+        // A drives PC0-PC3 and PC6, B leaves everything as input with NMI enabled
+        a.write_gg_ext_direction(0xB0);
+        a.write_gg_ext_data(0x0A);
+        b.write_gg_ext_direction(0x7F);
+
+        b.set_link_cable_input_pins(a.link_cable_output_pins());
+        assert_eq!(b.gg_ext_data(), 0x3A);
+        assert!(b.gg_ext_nmi());
+
+        a.write_gg_ext_data(0x4A);
+        b.set_link_cable_input_pins(a.link_cable_output_pins());
+        assert_eq!(b.gg_ext_data(), 0x7A);
+        assert!(!b.gg_ext_nmi());
+
+        // Serial transfer
+        a.write_gg_serial_ctrl(1 << SCTRL_SERIAL_OUT_BIT);
+        b.write_gg_serial_ctrl((1 << SCTRL_SERIAL_IN_BIT) | (1 << SCTRL_RX_NMI_BIT));
+        a.write_gg_tx_data(0x5C);
+        b.receive_link_cable_byte(a.take_link_cable_tx_byte().unwrap());
+        assert!(b.gg_ext_nmi());
+        assert_eq!(b.read_gg_rx_data(), 0x5C);
+        assert!(!b.gg_ext_nmi());
+    }
+
+    #[test]
+    fn paddle() {
+        let inputs = SmsGgInputs {
+            p1_peripheral: SmsGgPeripheral::Paddle(PaddleState { position: 0xA5 }),
+            ..SmsGgInputs::default()
+        };
+
+        // Japanese paddle switches nibbles on its own, with TR set for the high nibble
+        let mut japanese = InputState::new(SmsRegion::Domestic);
+        japanese.set_inputs(inputs);
+        assert_eq!(japanese.port_dc() & 0x3F, 0x3A);
+        assert_eq!(japanese.port_dc() & 0x3F, 0x15);
+        assert_eq!(japanese.port_dc() & 0x3F, 0x3A);
+
+        // Export paddle only switches nibbles when TH changes
+        let mut export = InputState::new(SmsRegion::International);
+        export.set_inputs(inputs);
+        assert_eq!(export.port_dc() & 0x3F, 0x3A);
+        assert_eq!(export.port_dc() & 0x3F, 0x3A);
+        export.write_control(0x00);
+        assert_eq!(export.port_dc() & 0x3F, 0x15);
+    }
+
+    #[test]
+    fn sports_pad() {
+        const TH_HIGH: u8 = 0x20;
+        const TH_LOW: u8 = 0x00;
+
+        let mut input = InputState::new(SmsRegion::International);
+        input.set_inputs(SmsGgInputs {
+            p1_peripheral: SmsGgPeripheral::SportsPad(SportsPadState {
+                x: 0x10,
+                y: 0xFD,
+                ..SportsPadState::default()
+            }),
+            ..SmsGgInputs::default()
+        });
+
+        // Run through one full read sequence to latch the motion
+        for value in [TH_HIGH, TH_LOW, TH_HIGH, TH_LOW, TH_HIGH] {
+            input.write_control(value);
+        }
+
+        let mut nibbles = vec![input.port_dc() & 0xF];
+        for value in [TH_LOW, TH_HIGH, TH_LOW] {
+            input.write_control(value);
+            nibbles.push(input.port_dc() & 0xF);
+        }
+        assert_eq!(nibbles, [0xF, 0x0, 0x0, 0x3]);
+    }
+
+    #[test]
+    fn sports_pad_mouse_motion() {
+        let mut inputs = SmsGgInputs {
+            p1_peripheral: SmsGgPeripheral::SportsPad(SportsPadState {
+                x: 0xFE,
+                y: 0x10,
+                ..SportsPadState::default()
+            }),
+            ..SmsGgInputs::default()
+        };
+
+        inputs.handle_analog_input(AnalogInput::MouseMotion { dx: 3, dy: -5 });
+        inputs.handle_analog_input(AnalogInput::MouseMotion { dx: 2, dy: -300 });
+
+        let SmsGgPeripheral::SportsPad(sports_pad) = inputs.p1_peripheral else {
+            panic!("peripheral should still be a Sports Pad");
+        };
+        assert_eq!((sports_pad.x, sports_pad.y), (0x03, 0xDF));
+
+        // Absolute cursor position does not move the trackball
+        let frame_size = FrameSize { width: 256, height: 192 };
+        inputs.handle_analog_input(AnalogInput::MousePosition { x: 100.0, y: 50.0, frame_size });
+        assert_eq!(inputs.p1_peripheral, SmsGgPeripheral::SportsPad(sports_pad));
+    }
 }
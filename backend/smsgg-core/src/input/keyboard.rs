@@ -0,0 +1,294 @@
+//! SC-3000 built-in keyboard, read through the 8255 PPI at I/O ports $DC-$DF
+//!
+//! Port C bits 0-2 select one of 8 matrix rows. Rows 0-6 are keyboard keys, read active low from
+//! port A (8 columns) and the low nibble of port B (4 columns). Row 7 is the two joypads, which read
+//! the same as on the SG-1000.
+//!
+//! Keys are not mapped to host keys directly. Instead, typed text is converted to a sequence of
+//! key presses so that the host keyboard layout does not matter.
+
+use bincode::{Decode, Encode};
+use jgenesis_common::frontend::TextInputEvent;
+use jgenesis_common::num::GetBit;
+use std::collections::VecDeque;
+
+const JOYPAD_ROW: u8 = 7;
+
+// Each key is held for this many frames and then released for this many frames before the next key
+// is pressed. BASIC only scans the keyboard once per frame and ignores repeats of a held key
+const KEY_PRESS_FRAMES: u8 = 3;
+
+// Typed text that has not been entered yet is dropped past this length
+const MAX_PENDING_KEYS: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+struct Key {
+    row: u8,
+    // 0-7 are port A bits, 8-11 are port B bits
+    column: u8,
+    shift: bool,
+}
+
+impl Key {
+    const fn new(row: u8, column: u8) -> Self {
+        Self { row, column, shift: false }
+    }
+
+    const fn shifted(row: u8, column: u8) -> Self {
+        Self { row, column, shift: true }
+    }
+
+    const SHIFT: Self = Self::new(6, 11);
+    const RETURN: Self = Self::new(5, 6);
+    const INS_DEL: Self = Self::new(3, 4);
+
+    fn from_char(c: char) -> Option<Self> {
+        // Letters are always typed without Shift
+        let key = match c.to_ascii_lowercase() {
+            '1' => Self::new(0, 0),
+            'q' => Self::new(0, 1),
+            'a' => Self::new(0, 2),
+            'z' => Self::new(0, 3),
+            ',' => Self::new(0, 5),
+            'k' => Self::new(0, 6),
+            'i' => Self::new(0, 7),
+            '8' => Self::new(0, 8),
+            '2' => Self::new(1, 0),
+            'w' => Self::new(1, 1),
+            's' => Self::new(1, 2),
+            'x' => Self::new(1, 3),
+            ' ' => Self::new(1, 4),
+            '.' => Self::new(1, 5),
+            'l' => Self::new(1, 6),
+            'o' => Self::new(1, 7),
+            '9' => Self::new(1, 8),
+            '3' => Self::new(2, 0),
+            'e' => Self::new(2, 1),
+            'd' => Self::new(2, 2),
+            'c' => Self::new(2, 3),
+            '/' => Self::new(2, 5),
+            ';' => Self::new(2, 6),
+            'p' => Self::new(2, 7),
+            '0' => Self::new(2, 8),
+            '4' => Self::new(3, 0),
+            'r' => Self::new(3, 1),
+            'f' => Self::new(3, 2),
+            'v' => Self::new(3, 3),
+            ':' => Self::new(3, 6),
+            '@' => Self::new(3, 7),
+            '-' => Self::new(3, 8),
+            '5' => Self::new(4, 0),
+            't' => Self::new(4, 1),
+            'g' => Self::new(4, 2),
+            'b' => Self::new(4, 3),
+            ']' => Self::new(4, 6),
+            '[' => Self::new(4, 7),
+            '^' => Self::new(4, 8),
+            '6' => Self::new(5, 0),
+            'y' => Self::new(5, 1),
+            'h' => Self::new(5, 2),
+            'n' => Self::new(5, 3),
+            '¥' => Self::new(5, 8),
+            '7' => Self::new(6, 0),
+            'u' => Self::new(6, 1),
+            'j' => Self::new(6, 2),
+            'm' => Self::new(6, 3),
+            '!' => Self::shifted(0, 0),
+            '"' => Self::shifted(1, 0),
+            '#' => Self::shifted(2, 0),
+            '$' => Self::shifted(3, 0),
+            '%' => Self::shifted(4, 0),
+            '&' => Self::shifted(5, 0),
+            '\'' => Self::shifted(6, 0),
+            '(' => Self::shifted(0, 8),
+            ')' => Self::shifted(1, 8),
+            '=' => Self::shifted(3, 8),
+            '~' => Self::shifted(4, 8),
+            '|' => Self::shifted(5, 8),
+            '<' => Self::shifted(0, 5),
+            '>' => Self::shifted(1, 5),
+            '?' => Self::shifted(2, 5),
+            '+' => Self::shifted(2, 6),
+            '*' => Self::shifted(3, 6),
+            '}' => Self::shifted(4, 6),
+            '{' => Self::shifted(4, 7),
+            '\n' | '\r' => Self::RETURN,
+            _ => return None,
+        };
+        Some(key)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+enum KeyPhase {
+    Pressed,
+    Released,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct Keyboard {
+    port_c: u8,
+    pending: VecDeque<Key>,
+    current: Option<(Key, KeyPhase)>,
+    phase_frames_remaining: u8,
+}
+
+impl Keyboard {
+    pub fn new() -> Self {
+        Self { port_c: 0, pending: VecDeque::new(), current: None, phase_frames_remaining: 0 }
+    }
+
+    pub fn joypad_selected(&self) -> bool {
+        self.port_c & 7 == JOYPAD_ROW
+    }
+
+    // Columns in the selected row with a key held down, as a 12-bit mask
+    fn pressed_columns(&self) -> u16 {
+        let Some((key, KeyPhase::Pressed)) = self.current else { return 0 };
+
+        let row = self.port_c & 7;
+        let mut columns = 0;
+        if key.row == row {
+            columns |= 1 << key.column;
+        }
+        if key.shift && Key::SHIFT.row == row {
+            columns |= 1 << Key::SHIFT.column;
+        }
+        columns
+    }
+
+    // Port A, active low
+    pub fn port_a(&self) -> u8 {
+        !(self.pressed_columns() as u8)
+    }
+
+    // Low nibble of port B, active low
+    pub fn port_b_keys(&self) -> u8 {
+        !((self.pressed_columns() >> 8) as u8) & 0x0F
+    }
+
+    pub fn write_port_c(&mut self, value: u8) {
+        self.port_c = value;
+    }
+
+    pub fn write_control(&mut self, value: u8) {
+        if value.bit(7) {
+            // Mode set; all output ports are cleared
+            self.port_c = 0;
+        } else {
+            // Port C bit set/reset
+            let bit = (value >> 1) & 7;
+            if value.bit(0) {
+                self.port_c |= 1 << bit;
+            } else {
+                self.port_c &= !(1 << bit);
+            }
+        }
+    }
+
+    pub fn handle_text_input(&mut self, event: TextInputEvent) {
+        match event {
+            TextInputEvent::Text(text) => {
+                for c in text.chars() {
+                    let Some(key) = Key::from_char(c) else {
+                        log::debug!("No SC-3000 key for character {c:?}");
+                        continue;
+                    };
+                    self.push_key(key);
+                }
+            }
+            TextInputEvent::Backspace => self.push_key(Key::INS_DEL),
+            TextInputEvent::Submit => self.push_key(Key::RETURN),
+            TextInputEvent::Cancel => self.pending.clear(),
+        }
+    }
+
+    fn push_key(&mut self, key: Key) {
+        if self.pending.len() < MAX_PENDING_KEYS {
+            self.pending.push_back(key);
+        }
+    }
+
+    pub fn end_frame(&mut self) {
+        self.phase_frames_remaining = self.phase_frames_remaining.saturating_sub(1);
+        if self.phase_frames_remaining != 0 {
+            return;
+        }
+
+        self.current = match self.current {
+            Some((key, KeyPhase::Pressed)) => Some((key, KeyPhase::Released)),
+            Some((_, KeyPhase::Released)) | None => {
+                self.pending.pop_front().map(|key| (key, KeyPhase::Pressed))
+            }
+        };
+        if self.current.is_some() {
+            self.phase_frames_remaining = KEY_PRESS_FRAMES;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select_row(keyboard: &mut Keyboard, row: u8) {
+        // Mode set, then port C bit set/reset for the 3 row select bits
+        keyboard.write_control(0x92);
+        for bit in 0..3 {
+            keyboard.write_control((bit << 1) | ((row >> bit) & 1));
+        }
+    }
+
+    #[test]
+    fn typed_text_presses_keys_in_order() {
+        let mut keyboard = Keyboard::new();
+        keyboard.handle_text_input(TextInputEvent::Text("A!".into()));
+
+        select_row(&mut keyboard, 0);
+        assert_eq!(keyboard.port_a(), 0xFF);
+
+        keyboard.end_frame();
+        assert_eq!(keyboard.port_a(), !0x04);
+
+        for _ in 0..KEY_PRESS_FRAMES {
+            keyboard.end_frame();
+        }
+        assert_eq!(keyboard.port_a(), 0xFF);
+
+        for _ in 0..KEY_PRESS_FRAMES {
+            keyboard.end_frame();
+        }
+        assert_eq!(keyboard.port_a(), !0x01);
+        assert_eq!(keyboard.port_b_keys(), 0x0F);
+
+        // Shift is in row 6, port B bit 3
+        select_row(&mut keyboard, 6);
+        assert_eq!(keyboard.port_a(), 0xFF);
+        assert_eq!(keyboard.port_b_keys(), 0x07);
+    }
+
+    #[test]
+    fn row_7_selects_joypads() {
+        let mut keyboard = Keyboard::new();
+        assert!(!keyboard.joypad_selected());
+
+        keyboard.write_port_c(0x07);
+        assert!(keyboard.joypad_selected());
+
+        keyboard.write_control(0x92);
+        assert!(!keyboard.joypad_selected());
+    }
+
+    #[test]
+    fn cancel_drops_pending_keys() {
+        let mut keyboard = Keyboard::new();
+        keyboard.handle_text_input(TextInputEvent::Text("12".into()));
+        keyboard.handle_text_input(TextInputEvent::Cancel);
+
+        keyboard.end_frame();
+        select_row(&mut keyboard, 0);
+        assert_eq!(keyboard.port_a(), 0xFF);
+        select_row(&mut keyboard, 1);
+        assert_eq!(keyboard.port_a(), 0xFF);
+    }
+}
@@ -7,10 +7,12 @@ pub mod psg;
 mod vdp;
 
 pub use api::{
-    GgAspectRatio, SmsAspectRatio, SmsGgEmulator, SmsGgEmulatorConfig, SmsGgError, SmsGgHardware,
-    SmsGgResult, SmsModel, SmsRegion,
+    GgAspectRatio, MemoryRegion, SmsAspectRatio, SmsGgEmulator, SmsGgEmulatorConfig, SmsGgError,
+    SmsGgHardware, SmsGgResult, SmsModel, SmsRegion,
+};
+pub use input::{
+    PaddleState, SmsGgButton, SmsGgInputs, SmsGgJoypadState, SmsGgPeripheral, SportsPadState,
 };
-pub use input::{SmsGgButton, SmsGgInputs, SmsGgJoypadState};
 pub use vdp::{VdpVersion, gg_color_to_rgb, sms_color_to_rgb};
 
 // 8:7
@@ -23,3 +25,7 @@ pub const SMS_PAL_ASPECT_RATIO: f64 = 1.375;
 pub const GAME_GEAR_LCD_ASPECT_RATIO: f64 = 1.2;
 
 pub const NATIVE_Z80_DIVIDER: u32 = 15;
+
+// Percentage of the previous frame's color that is retained when Game Gear frame blending is enabled
+pub const DEFAULT_GG_LCD_PERSISTENCE: u8 = 50;
+pub const MAX_GG_LCD_PERSISTENCE: u8 = 90;
@@ -2,9 +2,12 @@
 
 mod metadata;
 
+use crate::SmsGgHardware;
 use bincode::{Decode, Encode};
 use crc::Crc;
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
 use jgenesis_common::num::GetBit;
+use jgenesis_common::{memmap, sram};
 use jgenesis_proc_macros::{FakeDecode, FakeEncode, PartialClone};
 use std::mem;
 use std::ops::{Index, RangeInclusive};
@@ -14,6 +17,8 @@ enum Mapper {
     #[default]
     Sega,
     Codemasters,
+    // SG-1000 cartridges with no banking; ROM is mapped directly to $0000-$BFFF
+    None,
 }
 
 const CODEMASTERS_CHECKSUM_ADDR: usize = 0x7FE6;
@@ -82,6 +87,7 @@ struct Cartridge {
     ram_mapped: bool,
     ram_bank: u32,
     ram_dirty: bool,
+    sg_1000_ram_address_mask: u16,
 }
 
 // Most cartridges with RAM only had 8KB, but up to 32KB was supported, and the header contains
@@ -91,9 +97,15 @@ const CARTRIDGE_RAM_SIZE: usize = 32 * 1024;
 const CRC: Crc<u32> = Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
 
 impl Cartridge {
-    fn new(rom: Vec<u8>, initial_ram: Option<Vec<u8>>) -> Self {
-        let mapper = Mapper::detect_from_rom(&rom);
-        log::info!("Detected mapper {mapper:?} from ROM header");
+    fn new(rom: Vec<u8>, initial_ram: Option<Vec<u8>>, hardware: SmsGgHardware) -> Self {
+        let mapper = match hardware {
+            SmsGgHardware::MasterSystem | SmsGgHardware::GameGear => {
+                let mapper = Mapper::detect_from_rom(&rom);
+                log::info!("Detected mapper {mapper:?} from ROM header");
+                mapper
+            }
+            SmsGgHardware::Sg1000 => Mapper::None,
+        };
 
         let checksum = CRC.checksum(&rom);
         log::info!("ROM CRC32: {checksum:08X}");
@@ -101,14 +113,24 @@ impl Cartridge {
         let has_battery = metadata::has_battery_backup(checksum);
         log::info!("Cartridge has battery-backed RAM: {has_battery}");
 
-        let ram = match initial_ram {
-            Some(ram) if ram.len() == CARTRIDGE_RAM_SIZE => {
+        let ram = match initial_ram.and_then(|ram| sram::normalize(ram, CARTRIDGE_RAM_SIZE)) {
+            Some(ram) => {
                 log::info!("Successfully loaded cartridge SRAM");
                 ram
             }
-            _ => vec![0; CARTRIDGE_RAM_SIZE],
+            None => vec![0; CARTRIDGE_RAM_SIZE],
         };
 
+        // Sega and Codemasters cartridges map RAM under software control. The few SG-1000 cartridges
+        // with RAM (e.g. The Castle) always have it mapped to $8000-$BFFF, mirrored to fill the range
+        let sg_1000_ram_len = match mapper {
+            Mapper::None => metadata::sg_1000_cartridge_ram_len(checksum),
+            Mapper::Sega | Mapper::Codemasters => None,
+        };
+        if let Some(ram_len) = sg_1000_ram_len {
+            log::info!("SG-1000 cartridge has {ram_len} bytes of RAM");
+        }
+
         Self {
             rom: Rom(rom),
             ram,
@@ -117,9 +139,10 @@ impl Cartridge {
             rom_bank_0: 0,
             rom_bank_1: 1,
             rom_bank_2: 2,
-            ram_mapped: false,
+            ram_mapped: sg_1000_ram_len.is_some(),
             ram_bank: 0,
             ram_dirty: false,
+            sg_1000_ram_address_mask: sg_1000_ram_len.map_or(0, |ram_len| ram_len - 1),
         }
     }
 
@@ -130,6 +153,10 @@ impl Cartridge {
 
     fn read(&self, address: u16) -> u8 {
         match (self.mapper, address) {
+            (Mapper::None, 0x8000..=0xBFFF) if self.ram_mapped => {
+                self.ram[(address & self.sg_1000_ram_address_mask) as usize]
+            }
+            (Mapper::None, 0x0000..=0xBFFF) => self.read_rom_address(address.into()),
             (Mapper::Sega, 0x0000..=0x03FF) => self.rom[address as usize],
             (Mapper::Sega, 0x0400..=0x3FFF) | (Mapper::Codemasters, 0x0000..=0x3FFF) => {
                 let rom_addr = (self.rom_bank_0 << 14) | u32::from(address);
@@ -165,11 +192,109 @@ impl Cartridge {
         }
     }
 
+    fn rom_bank_detail(&self, bank: u32) -> String {
+        let bank_mask = (self.rom.len() >> 14).max(1) as u32 - 1;
+        memmap::describe_bank(bank & bank_mask, 0x4000)
+    }
+
+    fn describe_memory_map(&self, map: &mut MemoryMap) {
+        match self.mapper {
+            Mapper::None => {
+                if self.ram_mapped {
+                    let ram_end = 0x8000 | u32::from(self.sg_1000_ram_address_mask);
+                    map.add(0x0000, 0x7FFF, RegionKind::Rom, "Cartridge ROM")
+                        .add(0x8000, ram_end, RegionKind::Ram, "Cartridge RAM")
+                        .add_mirror(
+                            ram_end + 1,
+                            0xBFFF,
+                            RegionKind::Ram,
+                            "Cartridge RAM",
+                            0x8000,
+                            ram_end,
+                        );
+                } else {
+                    map.add(0x0000, 0xBFFF, RegionKind::Rom, "Cartridge ROM");
+                }
+            }
+            Mapper::Sega => {
+                map.add_mapped(0x0000, 0x03FF, RegionKind::Rom, "ROM (fixed)", "$000000-$0003FF")
+                    .add_mapped(
+                        0x0400,
+                        0x3FFF,
+                        RegionKind::Rom,
+                        "ROM slot 0",
+                        self.rom_bank_detail(self.rom_bank_0),
+                    )
+                    .add_mapped(
+                        0x4000,
+                        0x7FFF,
+                        RegionKind::Rom,
+                        "ROM slot 1",
+                        self.rom_bank_detail(self.rom_bank_1),
+                    );
+
+                if self.ram_mapped {
+                    map.add_mapped(
+                        0x8000,
+                        0xBFFF,
+                        RegionKind::Ram,
+                        "Cartridge RAM",
+                        format!("RAM bank {}", self.ram_bank),
+                    );
+                } else {
+                    map.add_mapped(
+                        0x8000,
+                        0xBFFF,
+                        RegionKind::Rom,
+                        "ROM slot 2",
+                        self.rom_bank_detail(self.rom_bank_2),
+                    );
+                }
+            }
+            Mapper::Codemasters => {
+                map.add_mapped(
+                    0x0000,
+                    0x3FFF,
+                    RegionKind::Rom,
+                    "ROM slot 0",
+                    self.rom_bank_detail(self.rom_bank_0),
+                )
+                .add_mapped(
+                    0x4000,
+                    0x7FFF,
+                    RegionKind::Rom,
+                    "ROM slot 1",
+                    self.rom_bank_detail(self.rom_bank_1),
+                );
+
+                if self.ram_mapped {
+                    map.add_mapped(
+                        0x8000,
+                        0x9FFF,
+                        RegionKind::Rom,
+                        "ROM slot 2",
+                        self.rom_bank_detail(self.rom_bank_2),
+                    )
+                    .add(0xA000, 0xBFFF, RegionKind::Ram, "Cartridge RAM");
+                } else {
+                    map.add_mapped(
+                        0x8000,
+                        0xBFFF,
+                        RegionKind::Rom,
+                        "ROM slot 2",
+                        self.rom_bank_detail(self.rom_bank_2),
+                    );
+                }
+            }
+        }
+    }
+
     fn write_ram(&mut self, address: u16, value: u8) {
         if self.ram_mapped {
             let ram_addr = match self.mapper {
                 Mapper::Sega => (self.ram_bank << 14) | u32::from(address & 0x3FFF),
                 Mapper::Codemasters => (address & 0x1FFF).into(),
+                Mapper::None => (address & self.sg_1000_ram_address_mask).into(),
             };
             self.ram[ram_addr as usize] = value;
 
@@ -201,19 +326,34 @@ impl Default for AudioControl {
 
 const SYSTEM_RAM_SIZE: usize = 8 * 1024;
 
+// The SG-1000 only has 1KB of RAM, mirrored throughout $C000-$FFFF
+const SMS_RAM_ADDRESS_MASK: u16 = 0x1FFF;
+const SG_1000_RAM_ADDRESS_MASK: u16 = 0x03FF;
+
 #[derive(Debug, Clone, Encode, Decode, PartialClone)]
 pub struct Memory {
     #[partial_clone(partial)]
     cartridge: Cartridge,
     ram: [u8; SYSTEM_RAM_SIZE],
+    ram_address_mask: u16,
     audio_control: AudioControl,
 }
 
 impl Memory {
-    pub fn new(rom: Vec<u8>, initial_cartridge_ram: Option<Vec<u8>>) -> Self {
+    pub fn new(
+        rom: Vec<u8>,
+        initial_cartridge_ram: Option<Vec<u8>>,
+        hardware: SmsGgHardware,
+    ) -> Self {
+        let ram_address_mask = match hardware {
+            SmsGgHardware::MasterSystem | SmsGgHardware::GameGear => SMS_RAM_ADDRESS_MASK,
+            SmsGgHardware::Sg1000 => SG_1000_RAM_ADDRESS_MASK,
+        };
+
         Self {
-            cartridge: Cartridge::new(rom, initial_cartridge_ram),
+            cartridge: Cartridge::new(rom, initial_cartridge_ram, hardware),
             ram: [0; SYSTEM_RAM_SIZE],
+            ram_address_mask,
             audio_control: AudioControl::default(),
         }
     }
@@ -222,7 +362,7 @@ impl Memory {
         match address {
             0x0000..=0xBFFF => self.cartridge.read(address),
             0xC000..=0xFFFF => {
-                let ram_addr = address & 0x1FFF;
+                let ram_addr = address & self.ram_address_mask;
                 self.ram[ram_addr as usize]
             }
         }
@@ -230,12 +370,12 @@ impl Memory {
 
     pub fn write(&mut self, address: u16, value: u8) {
         if address >= 0xC000 {
-            let ram_addr = address & 0x1FFF;
+            let ram_addr = address & self.ram_address_mask;
             self.ram[ram_addr as usize] = value;
         }
 
         match (self.cartridge.mapper, address) {
-            (Mapper::Sega, 0x8000..=0xBFFF) => {
+            (Mapper::Sega | Mapper::None, 0x8000..=0xBFFF) => {
                 self.cartridge.write_ram(address, value);
             }
             (Mapper::Sega, 0xFFFC) => {
@@ -271,10 +411,52 @@ impl Memory {
         }
     }
 
+    pub fn memory_map(&self) -> MemoryMap {
+        let mut map = MemoryMap::new("Z80", 16);
+        self.cartridge.describe_memory_map(&mut map);
+
+        let ram_end = 0xC000 | self.ram_address_mask;
+        map.add(0xC000, ram_end.into(), RegionKind::Ram, "System RAM");
+        map.add_mirror(
+            u32::from(ram_end) + 1,
+            0xFFFF,
+            RegionKind::Ram,
+            "System RAM",
+            0xC000,
+            ram_end.into(),
+        );
+
+        if self.cartridge.mapper == Mapper::Sega {
+            // The mapper registers overlay the last 4 bytes of the RAM mirror
+            map.add_overlay(
+                0xFFFC,
+                0xFFFF,
+                RegionKind::Registers,
+                "Mapper registers",
+                Some("Reads return System RAM $DFFC-$DFFF".into()),
+            );
+        }
+
+        map
+    }
+
+    pub fn system_ram(&self) -> &[u8] {
+        &self.ram
+    }
+
+    pub fn system_ram_mut(&mut self) -> &mut [u8] {
+        &mut self.ram
+    }
+
     pub fn cartridge_ram(&self) -> &[u8] {
         &self.cartridge.ram
     }
 
+    pub fn restore_cartridge_ram(&mut self, ram: &[u8]) {
+        self.cartridge.ram.copy_from_slice(ram);
+        self.cartridge.ram_dirty = true;
+    }
+
     pub fn cartridge_has_battery(&self) -> bool {
         self.cartridge.has_battery
     }
@@ -320,3 +502,67 @@ impl Memory {
         self.audio_control.psg_enabled = control_bits == 0 || control_bits == 3;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_map_tracks_sega_mapper_banks() {
+        // An all-zero ROM would pass the Codemasters checksum check
+        let mut rom = vec![0; 128 * 1024];
+        rom[0] = 1;
+
+        let mut memory = Memory::new(rom, None, SmsGgHardware::MasterSystem);
+        memory.write(0xFFFF, 5);
+
+        let map = memory.memory_map();
+        assert!(map.is_complete());
+        assert_eq!(
+            map.entry_at(0x8000).and_then(|entry| entry.detail.as_deref()),
+            Some("Bank $05 ($014000-$017FFF)")
+        );
+
+        memory.write(0xFFFC, 0x08);
+
+        let map = memory.memory_map();
+        assert!(map.is_complete());
+        assert_eq!(map.entry_at(0x8000).map(|entry| entry.kind), Some(RegionKind::Ram));
+    }
+
+    #[test]
+    fn sg_1000_cartridge_ram() {
+        // Cartridge RAM is only mapped for known cartridges
+        let mut memory = Memory::new(vec![0xAA; 32 * 1024], None, SmsGgHardware::Sg1000);
+        memory.write(0x8001, 0x12);
+        assert_eq!(memory.read(0x8001), 0xAA);
+        assert!(!memory.cartridge_ram_dirty());
+        assert!(!memory.cartridge_has_battery());
+
+        // 2KB of RAM, mirrored every 2KB
+        memory.cartridge.ram_mapped = true;
+        memory.cartridge.sg_1000_ram_address_mask = 0x07FF;
+        memory.write(0x8001, 0x12);
+        assert_eq!(memory.read(0x8001), 0x12);
+        assert_eq!(memory.read(0x8801), 0x12);
+        assert_eq!(memory.read(0xB801), 0x12);
+        assert!(memory.cartridge_ram_dirty());
+        assert!(!memory.cartridge_has_battery());
+
+        let map = memory.memory_map();
+        assert!(map.is_complete());
+        assert_eq!(map.entry_at(0xA000).map(|entry| entry.kind), Some(RegionKind::Ram));
+    }
+
+    #[test]
+    fn sg_1000_memory_map() {
+        let memory = Memory::new(vec![0; 32 * 1024], None, SmsGgHardware::Sg1000);
+
+        let map = memory.memory_map();
+        assert!(map.is_complete());
+        assert_eq!(
+            map.entry_at(0xC400).and_then(|entry| entry.detail.as_deref()),
+            Some("Mirror of $C000-$C3FF")
+        );
+    }
+}
@@ -1,5 +1,6 @@
-//! List of Sega Master System / Game Gear games that have battery-backed SRAM in the cartridge.
-//! This is necessary because SMS/GG cartridges don't have a proper header
+//! Lists of Sega Master System / Game Gear games that have battery-backed SRAM in the cartridge, and
+//! of SG-1000 games that have cartridge RAM.
+//! This is necessary because SMS/GG/SG-1000 cartridges don't have a proper header
 //!
 //! List of games from <https://segaretro.org/Battery_backup>
 
@@ -64,7 +65,20 @@ const GG_BATTERY_BACKUP_GAMES_CRC32: &[u32] = &[
     0x7b7717b8, // Taisen-gata Daisenryaku G (J)
 ];
 
+// SG-1000 cartridges with volatile RAM mapped to $8000-$BFFF, along with the RAM size. The cartridge
+// header has no information on this, and most SG-1000 cartridges have no RAM at all
+const SG_1000_CARTRIDGE_RAM_GAMES_CRC32: &[(u32, u16)] = &[
+    (0x092f29d6, 8 * 1024), // The Castle (J)
+    (0xaf4f14bc, 2 * 1024), // Othello (J)
+];
+
 pub fn has_battery_backup(checksum: u32) -> bool {
     SMS_BATTERY_BACKUP_GAMES_CRC32.contains(&checksum)
         || GG_BATTERY_BACKUP_GAMES_CRC32.contains(&checksum)
 }
+
+pub fn sg_1000_cartridge_ram_len(checksum: u32) -> Option<u16> {
+    SG_1000_CARTRIDGE_RAM_GAMES_CRC32
+        .iter()
+        .find_map(|&(crc32, ram_len)| (crc32 == checksum).then_some(ram_len))
+}
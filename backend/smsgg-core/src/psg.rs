@@ -2,7 +2,7 @@
 
 use bincode::{Decode, Encode};
 use jgenesis_common::num::GetBit;
-use jgenesis_proc_macros::{EnumAll, EnumDisplay};
+use jgenesis_proc_macros::{EnumAll, EnumDisplay, SaveState};
 use std::{array, cmp};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
@@ -282,7 +282,7 @@ impl Default for StereoControl {
     }
 }
 
-#[derive(Debug, Clone, Encode, Decode)]
+#[derive(Debug, Clone, SaveState)]
 pub struct Sn76489 {
     version: Sn76489Version,
     square_wave_channels: [SquareWaveGenerator; 3],
@@ -290,6 +290,9 @@ pub struct Sn76489 {
     latched_register: Register,
     stereo_control: StereoControl,
     divider: u8,
+    // Debug-only setting controlled by the frontend, not part of emulated state
+    #[savestate(skip)]
+    muted_channels: [bool; 4],
 }
 
 const SN76489_DIVIDER: u8 = 16;
@@ -304,6 +307,7 @@ impl Sn76489 {
             latched_register: Register::Tone0,
             stereo_control: StereoControl::default(),
             divider: SN76489_DIVIDER,
+            muted_channels: [false; 4],
         }
     }
 
@@ -402,8 +406,15 @@ impl Sn76489 {
             Sn76489Version::Standard => &ATTENUATION_TO_VOLUME,
         };
 
-        let square_samples = self.square_wave_channels.map(|channel| channel.sample(volume_table));
-        let noise_sample = self.noise_channel.sample(volume_table);
+        let square_samples: [f64; 3] = array::from_fn(|i| {
+            if self.muted_channels[i] {
+                0.0
+            } else {
+                self.square_wave_channels[i].sample(volume_table)
+            }
+        });
+        let noise_sample =
+            if self.muted_channels[3] { 0.0 } else { self.noise_channel.sample(volume_table) };
 
         let sample_l = (f64::from(self.stereo_control.square_0_l) * square_samples[0]
             + f64::from(self.stereo_control.square_1_l) * square_samples[1]
@@ -418,4 +429,19 @@ impl Sn76489 {
 
         (sample_l, sample_r)
     }
+
+    /// Mute or unmute an individual channel in the mixed output. Channels 0-2 are the square wave
+    /// channels and channel 3 is the noise channel.
+    pub fn set_channel_muted(&mut self, channel: usize, muted: bool) {
+        self.muted_channels[channel] = muted;
+    }
+
+    #[must_use]
+    pub fn is_channel_muted(&self, channel: usize) -> bool {
+        self.muted_channels[channel]
+    }
+
+    pub fn clear_muted_channels(&mut self) {
+        self.muted_channels = [false; 4];
+    }
 }
@@ -166,8 +166,14 @@ enum Mode {
     #[default]
     Four,
     Four224Line,
+    // TMS9918 mode 0
+    GraphicsI,
+    // TMS9918 mode 1
+    Text,
     // TMS9918 mode 2
     GraphicsII,
+    // TMS9918 mode 3
+    Multicolor,
 }
 
 impl Display for Mode {
@@ -175,7 +181,10 @@ impl Display for Mode {
         match self {
             Self::Four => write!(f, "4"),
             Self::Four224Line => write!(f, "4 (224-line)"),
+            Self::GraphicsI => write!(f, "Graphics I"),
+            Self::Text => write!(f, "Text"),
             Self::GraphicsII => write!(f, "Graphics II"),
+            Self::Multicolor => write!(f, "Multicolor"),
         }
     }
 }
@@ -187,7 +196,10 @@ impl Mode {
                 Self::Four
             }
             [true, true, false, true] => Self::Four224Line,
+            [false, false, false, false] => Self::GraphicsI,
+            [true, false, false, false] => Self::Text,
             [false, true, false, false] => Self::GraphicsII,
+            [false, false, true, false] => Self::Multicolor,
             _ => {
                 log::warn!("Unsupported mode, defaulting to mode 4: {mode_bits:?}");
                 Self::Four
@@ -195,16 +207,20 @@ impl Mode {
         }
     }
 
+    const fn is_tms9918(self) -> bool {
+        matches!(self, Self::GraphicsI | Self::Text | Self::GraphicsII | Self::Multicolor)
+    }
+
     const fn name_table_rows(self) -> u16 {
         match self {
-            Self::Four | Self::GraphicsII => 28,
+            Self::Four | Self::GraphicsI | Self::Text | Self::GraphicsII | Self::Multicolor => 28,
             Self::Four224Line => 32,
         }
     }
 
     const fn active_scanlines(self) -> u16 {
         match self {
-            Self::Four | Self::GraphicsII => 192,
+            Self::Four | Self::GraphicsI | Self::Text | Self::GraphicsII | Self::Multicolor => 192,
             Self::Four224Line => 224,
         }
     }
@@ -212,7 +228,7 @@ impl Mode {
     // The number of scanlines to remove from each of the top and bottom borders when in this mode
     const fn vertical_border_offset(self) -> u16 {
         match self {
-            Self::Four | Self::GraphicsII => 0,
+            Self::Four | Self::GraphicsI | Self::Text | Self::GraphicsII | Self::Multicolor => 0,
             Self::Four224Line => 16,
         }
     }
@@ -252,6 +268,7 @@ struct Registers {
     // Registers used only in legacy TMS9918 modes
     color_table_address: u16,
     pattern_generator_address: u16,
+    text_color: u8,
 }
 
 // Data address is 14 bits
@@ -291,6 +308,7 @@ impl Registers {
             line_counter_reload_value: 0,
             color_table_address: 0,
             pattern_generator_address: 0,
+            text_color: 0,
         }
     }
 
@@ -458,8 +476,9 @@ impl Registers {
                 self.base_sprite_pattern_address = u16::from(value & 0x07) << 11;
             }
             7 => {
-                // Backdrop color
+                // Backdrop color (and text color in TMS9918 text mode)
                 self.backdrop_color = value & 0x0F;
+                self.text_color = value >> 4;
             }
             8 => {
                 // X scroll
@@ -769,7 +788,9 @@ impl Vdp {
     fn read_name_table_word(&self, row: u16, col: u16) -> BgTileData {
         let base_name_table_addr = match self.registers.mode {
             // Mask out bit 10 (only used by legacy modes)
-            Mode::Four | Mode::GraphicsII => self.registers.base_name_table_address & 0xF800,
+            Mode::Four | Mode::GraphicsI | Mode::Text | Mode::GraphicsII | Mode::Multicolor => {
+                self.registers.base_name_table_address & 0xF800
+            }
             // Mask out bit 11 and offset by $0700
             Mode::Four224Line => (self.registers.base_name_table_address & 0xF000) | 0x0700,
         };
@@ -788,8 +809,8 @@ impl Vdp {
     }
 
     fn render_scanline(&mut self) {
-        if self.registers.mode == Mode::GraphicsII {
-            self.render_graphics_2_scanline();
+        if self.registers.mode.is_tms9918() {
+            self.render_tms9918_scanline();
             return;
         }
 
@@ -918,7 +939,11 @@ impl Vdp {
 
     fn clear_scanline(&mut self) {
         let frame_buffer_row = self.frame_buffer_row();
-        let backdrop_color = self.backdrop_color();
+        let backdrop_color = if self.registers.mode.is_tms9918() {
+            tms9918::TMS9918_COLOR_TO_SMS_COLOR[self.registers.backdrop_color as usize].into()
+        } else {
+            self.backdrop_color()
+        };
 
         for pixel in 0..SCREEN_WIDTH {
             self.frame_buffer.set(frame_buffer_row, pixel, backdrop_color);
@@ -1023,11 +1048,10 @@ impl Vdp {
     }
 
     fn fill_vertical_border(&mut self) {
-        let backdrop_color = match self.registers.mode {
-            Mode::Four | Mode::Four224Line => self.backdrop_color(),
-            Mode::GraphicsII => {
-                tms9918::TMS9918_COLOR_TO_SMS_COLOR[self.registers.backdrop_color as usize].into()
-            }
+        let backdrop_color = if self.registers.mode.is_tms9918() {
+            tms9918::TMS9918_COLOR_TO_SMS_COLOR[self.registers.backdrop_color as usize].into()
+        } else {
+            self.backdrop_color()
         };
 
         let ViewportSize { top_border_height, height, bottom_border_height, .. } =
@@ -1076,14 +1100,20 @@ impl Vdp {
 
     pub fn v_counter(&self) -> u8 {
         match (self.registers.version.timing_mode(), self.registers.mode) {
-            (TimingMode::Ntsc, Mode::Four | Mode::GraphicsII) => {
+            (
+                TimingMode::Ntsc,
+                Mode::Four | Mode::GraphicsI | Mode::Text | Mode::GraphicsII | Mode::Multicolor,
+            ) => {
                 if self.scanline <= 0xDA {
                     self.scanline as u8
                 } else {
                     (self.scanline - 6) as u8
                 }
             }
-            (TimingMode::Pal, Mode::Four | Mode::GraphicsII) => {
+            (
+                TimingMode::Pal,
+                Mode::Four | Mode::GraphicsI | Mode::Text | Mode::GraphicsII | Mode::Multicolor,
+            ) => {
                 if self.scanline <= 0xF2 {
                     self.scanline as u8
                 } else {
@@ -21,6 +21,24 @@ impl Vdp {
         }
     }
 
+    /// Raw CRAM contents: one byte per color on the Master System, or one little-endian word per
+    /// color on the Game Gear.
+    pub fn dump_cram(&self) -> Vec<u8> {
+        if self.registers.version.is_master_system() {
+            self.color_ram[..32].to_vec()
+        } else {
+            self.color_ram.to_vec()
+        }
+    }
+
+    pub fn vram(&self) -> &[u8] {
+        &self.vram
+    }
+
+    pub fn vram_mut(&mut self) -> &mut [u8] {
+        &mut self.vram
+    }
+
     pub fn copy_vram(&self, out: &mut [Color], palette: u8, row_len: usize) {
         for pattern in 0..VRAM_SIZE / 32 {
             let tile = &self.vram[32 * pattern..32 * (pattern + 1)];
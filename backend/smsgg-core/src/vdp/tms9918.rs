@@ -1,10 +1,12 @@
 use crate::vdp;
-use crate::vdp::Vdp;
+use crate::vdp::{Mode, Vdp};
 use arrayvec::ArrayVec;
 use jgenesis_common::num::GetBit;
 
 const MAX_SPRITES_PER_LINE: usize = 4;
 
+const TEXT_MODE_BORDER: u16 = 8;
+
 // From https://www.smspower.org/forums/8224-TMS9918ColorsForSMSVDP
 pub const TMS9918_COLOR_TO_SMS_COLOR: &[u8; 16] = &[
     0x00, // Transparent (Black)
@@ -26,7 +28,7 @@ pub const TMS9918_COLOR_TO_SMS_COLOR: &[u8; 16] = &[
 ];
 
 #[derive(Debug, Clone, Copy)]
-struct Graphics2SpriteData {
+struct Tms9918SpriteData {
     y: u8,
     x: u8,
     name: u8,
@@ -35,54 +37,37 @@ struct Graphics2SpriteData {
 }
 
 impl Vdp {
-    pub(super) fn render_graphics_2_scanline(&mut self) {
+    pub(super) fn render_tms9918_scanline(&mut self) {
+        if self.registers.mode == Mode::Text {
+            self.render_text_scanline();
+            return;
+        }
+
         let scanline = self.scanline;
         let frame_buffer_row = self.frame_buffer_row();
         let backdrop_color = TMS9918_COLOR_TO_SMS_COLOR[self.registers.backdrop_color as usize];
 
-        let base_name_table_addr = self.registers.base_name_table_address;
-        let base_color_table_addr = self.registers.color_table_address & 0x2000;
-        let base_pattern_generator = self.registers.pattern_generator_address & 0x2000;
-
         let nametable_row = scanline / 8;
-        let line_name_table_addr = base_name_table_addr | (nametable_row * 32);
-
-        // Pattern generator and color table are split into 3 blocks of 2048 bytes each: one for the
-        // first 8 rows, one for the middle 8 rows, and one for the last 8 rows
-        let table_offset = if nametable_row >= 16 {
-            4096
-        } else if nametable_row >= 8 {
-            2048
-        } else {
-            0
-        };
-
-        let tile_row = scanline % 8;
+        let line_name_table_addr = self.registers.base_name_table_address | (nametable_row * 32);
 
         let large_sprites = self.registers.double_sprite_height;
         let magnify_sprites = self.registers.double_sprite_size;
-        let sprite_size = 16 * u8::from(large_sprites) + 16 * u8::from(magnify_sprites);
+        let sprite_size = (8 << u8::from(large_sprites)) << u8::from(magnify_sprites);
 
         // Scan for sprites on this line
         let sprite_buffer = self.find_sprites_on_line(sprite_size);
 
         for nametable_col in 0..vdp::SCREEN_WIDTH / 8 {
             let name_table_entry = self.vram[(line_name_table_addr | nametable_col) as usize];
-
-            let pattern_generator_addr =
-                base_pattern_generator + table_offset + 8 * u16::from(name_table_entry) + tile_row;
-            let pattern_generator_entry = self.vram[pattern_generator_addr as usize];
-
-            let color_table_addr =
-                base_color_table_addr + table_offset + 8 * u16::from(name_table_entry) + tile_row;
-            let color_table_entry = self.vram[color_table_addr as usize];
+            let (pattern_generator_entry, color_table_entry) =
+                self.read_tms9918_bg_tile(name_table_entry, nametable_row, scanline);
             let bg_color_0 = color_table_entry & 0x0F;
             let bg_color_1 = color_table_entry >> 4;
 
             for tile_col in 0..8 {
                 let pixel = 8 * nametable_col + u16::from(tile_col);
 
-                let sprite_color = self.determine_graphics_2_sprite_color(
+                let sprite_color = self.determine_tms9918_sprite_color(
                     &sprite_buffer,
                     scanline,
                     pixel,
@@ -106,14 +91,121 @@ impl Vdp {
         }
     }
 
+    // Returns the pattern byte and color byte for the given tile row. Color bits 7-4 are used for
+    // set pattern bits and color bits 3-0 are used for clear pattern bits
+    fn read_tms9918_bg_tile(
+        &self,
+        name_table_entry: u8,
+        nametable_row: u16,
+        scanline: u16,
+    ) -> (u8, u8) {
+        let tile_row = scanline % 8;
+
+        match self.registers.mode {
+            Mode::GraphicsI => {
+                let base_pattern_generator = self.registers.pattern_generator_address;
+                let pattern_generator_addr =
+                    base_pattern_generator + 8 * u16::from(name_table_entry) + tile_row;
+
+                // Each color table byte applies to a block of 8 consecutive patterns
+                let color_table_addr =
+                    self.registers.color_table_address + u16::from(name_table_entry >> 3);
+
+                (self.vram[pattern_generator_addr as usize], self.vram[color_table_addr as usize])
+            }
+            Mode::Multicolor => {
+                // Each pattern is 2 bytes per 4 name table rows, with each byte specifying the
+                // colors of two 4x4 blocks. Treat the left block as set pattern bits and the right
+                // block as clear pattern bits
+                let base_pattern_generator = self.registers.pattern_generator_address;
+                let pattern_generator_addr = base_pattern_generator
+                    + 8 * u16::from(name_table_entry)
+                    + 2 * (nametable_row % 4)
+                    + (tile_row / 4);
+
+                (0xF0, self.vram[pattern_generator_addr as usize])
+            }
+            _ => {
+                // Graphics II
+                let base_color_table_addr = self.registers.color_table_address & 0x2000;
+                let base_pattern_generator = self.registers.pattern_generator_address & 0x2000;
+
+                // Pattern generator and color table are split into 3 blocks of 2048 bytes each: one
+                // for the first 8 rows, one for the middle 8 rows, and one for the last 8 rows
+                let table_offset = if nametable_row >= 16 {
+                    4096
+                } else if nametable_row >= 8 {
+                    2048
+                } else {
+                    0
+                };
+
+                let pattern_generator_addr = base_pattern_generator
+                    + table_offset
+                    + 8 * u16::from(name_table_entry)
+                    + tile_row;
+                let color_table_addr = base_color_table_addr
+                    + table_offset
+                    + 8 * u16::from(name_table_entry)
+                    + tile_row;
+
+                (self.vram[pattern_generator_addr as usize], self.vram[color_table_addr as usize])
+            }
+        }
+    }
+
+    // Text mode displays 40 columns of 6x8 tiles with an 8-pixel border on each side. There are no
+    // sprites in this mode, and the only two colors are set through register #7
+    fn render_text_scanline(&mut self) {
+        let scanline = self.scanline;
+        let frame_buffer_row = self.frame_buffer_row();
+
+        let backdrop_color = TMS9918_COLOR_TO_SMS_COLOR[self.registers.backdrop_color as usize];
+        let text_color = if self.registers.text_color != 0 {
+            TMS9918_COLOR_TO_SMS_COLOR[self.registers.text_color as usize]
+        } else {
+            backdrop_color
+        };
+
+        for pixel in
+            (0..TEXT_MODE_BORDER).chain(vdp::SCREEN_WIDTH - TEXT_MODE_BORDER..vdp::SCREEN_WIDTH)
+        {
+            self.frame_buffer.set(frame_buffer_row, pixel, backdrop_color.into());
+        }
+
+        let nametable_row = scanline / 8;
+        let tile_row = scanline % 8;
+        let line_name_table_addr = self.registers.base_name_table_address + nametable_row * 40;
+
+        for nametable_col in 0..40 {
+            let name_table_entry = self.vram[(line_name_table_addr + nametable_col) as usize];
+
+            let pattern_generator_addr = self.registers.pattern_generator_address
+                + 8 * u16::from(name_table_entry)
+                + tile_row;
+            let pattern_generator_entry = self.vram[pattern_generator_addr as usize];
+
+            // Only the highest 6 bits of each pattern byte are displayed
+            for tile_col in 0..6 {
+                let pixel = TEXT_MODE_BORDER + 6 * nametable_col + u16::from(tile_col);
+                let color = if pattern_generator_entry.bit(7 - tile_col) {
+                    text_color
+                } else {
+                    backdrop_color
+                };
+                self.frame_buffer.set(frame_buffer_row, pixel, color.into());
+            }
+        }
+    }
+
     fn find_sprites_on_line(
         &mut self,
         sprite_size: u8,
-    ) -> ArrayVec<Graphics2SpriteData, MAX_SPRITES_PER_LINE> {
+    ) -> ArrayVec<Tms9918SpriteData, MAX_SPRITES_PER_LINE> {
         let scanline = self.scanline as u8;
         let base_sprite_table_addr = self.registers.base_sprite_table_address;
 
-        let mut sprite_buffer = ArrayVec::<Graphics2SpriteData, 4>::new();
+        let mut sprite_buffer = ArrayVec::<Tms9918SpriteData, 4>::new();
         for sprite_idx in 0..32 {
             // Add 1 because sprites with Y=0 should display starting on line 1
             let sprite_table_addr = base_sprite_table_addr + 4 * sprite_idx;
@@ -148,15 +240,15 @@ impl Vdp {
                 continue;
             }
 
-            sprite_buffer.push(Graphics2SpriteData { y, x, name, color, early_clock });
+            sprite_buffer.push(Tms9918SpriteData { y, x, name, color, early_clock });
         }
 
         sprite_buffer
     }
 
-    fn determine_graphics_2_sprite_color(
+    fn determine_tms9918_sprite_color(
         &self,
-        sprite_buffer: &[Graphics2SpriteData],
+        sprite_buffer: &[Tms9918SpriteData],
         scanline: u16,
         pixel: u16,
         sprite_size: u8,
@@ -202,3 +294,129 @@ impl Vdp {
             .unwrap_or(0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmsGgEmulatorConfig;
+    use crate::vdp::{SCREEN_WIDTH, VdpVersion};
+
+    const NAME_TABLE_ADDR: usize = 0x3800;
+    const COLOR_TABLE_ADDR: usize = 0x2000;
+    const SPRITE_TABLE_ADDR: usize = 0x3F00;
+    const SPRITE_PATTERN_ADDR: usize = 0x1000;
+
+    fn new_vdp(mode_control_2: u8, backdrop_color: u8) -> Vdp {
+        let mut vdp = Vdp::new(VdpVersion::NtscMasterSystem2, &SmsGgEmulatorConfig::default());
+
+        for (register, value) in [
+            (0, 0x00),
+            (1, mode_control_2),
+            (2, (NAME_TABLE_ADDR >> 10) as u8),
+            (3, (COLOR_TABLE_ADDR >> 6) as u8),
+            (4, 0x00),
+            (5, (SPRITE_TABLE_ADDR >> 7) as u8),
+            (6, (SPRITE_PATTERN_ADDR >> 11) as u8),
+            (7, backdrop_color),
+        ] {
+            vdp.write_control(value);
+            vdp.write_control(0x80 | register);
+        }
+
+        // Sprite list terminator
+        vdp.vram[SPRITE_TABLE_ADDR] = 0xCF;
+
+        vdp
+    }
+
+    fn render_line(vdp: &mut Vdp, scanline: u16) -> Vec<u16> {
+        vdp.scanline = scanline;
+        vdp.render_tms9918_scanline();
+
+        let row = vdp.frame_buffer_row() as usize;
+        let width = SCREEN_WIDTH as usize;
+        vdp.frame_buffer.buffer[row * width..(row + 1) * width].to_vec()
+    }
+
+    fn sms_color(tms9918_color: u8) -> u16 {
+        TMS9918_COLOR_TO_SMS_COLOR[tms9918_color as usize].into()
+    }
+
+    #[test]
+    fn graphics_i() {
+        let mut vdp = new_vdp(0x00, 0x06);
+        assert_eq!(vdp.registers.mode, Mode::GraphicsI);
+
+        // Pattern 9 uses the color table byte for patterns 8-15
+        vdp.vram[NAME_TABLE_ADDR] = 9;
+        vdp.vram[8 * 9] = 0xF0;
+        vdp.vram[COLOR_TABLE_ADDR + 1] = 0xF4;
+
+        let line = render_line(&mut vdp, 0);
+        assert_eq!(line[..4], [sms_color(15); 4]);
+        assert_eq!(line[4..8], [sms_color(4); 4]);
+        // Transparent tile pixels show the backdrop color
+        assert_eq!(line[8..16], [sms_color(6); 8]);
+    }
+
+    #[test]
+    fn text_mode() {
+        let mut vdp = new_vdp(0x10, 0xF4);
+        assert_eq!(vdp.registers.mode, Mode::Text);
+
+        vdp.vram[NAME_TABLE_ADDR] = 1;
+        // The lowest 2 bits are not displayed
+        vdp.vram[8] = 0xAB;
+
+        let line = render_line(&mut vdp, 0);
+        let (text, backdrop) = (sms_color(15), sms_color(4));
+        assert_eq!(line[..8], [backdrop; 8]);
+        assert_eq!(line[8..14], [text, backdrop, text, backdrop, text, backdrop]);
+        assert_eq!(line[14..20], [backdrop; 6]);
+        assert_eq!(line[SCREEN_WIDTH as usize - 8..], [backdrop; 8]);
+    }
+
+    #[test]
+    fn multicolor_mode() {
+        let mut vdp = new_vdp(0x08, 0x00);
+        assert_eq!(vdp.registers.mode, Mode::Multicolor);
+
+        vdp.vram[NAME_TABLE_ADDR] = 2;
+        vdp.vram[16] = 0x4A;
+        vdp.vram[17] = 0x3F;
+
+        let line = render_line(&mut vdp, 0);
+        assert_eq!(line[..4], [sms_color(4); 4]);
+        assert_eq!(line[4..8], [sms_color(10); 4]);
+
+        let line = render_line(&mut vdp, 4);
+        assert_eq!(line[..4], [sms_color(3); 4]);
+        assert_eq!(line[4..8], [sms_color(15); 4]);
+    }
+
+    #[test]
+    fn sprites() {
+        let mut vdp = new_vdp(0x00, 0x01);
+
+        // Sprite at Y=0xFF displays starting on line 0
+        vdp.vram[SPRITE_TABLE_ADDR..SPRITE_TABLE_ADDR + 5]
+            .copy_from_slice(&[0xFF, 16, 1, 0x0F, 0xCF]);
+        vdp.vram[SPRITE_PATTERN_ADDR + 8] = 0x81;
+
+        let line = render_line(&mut vdp, 0);
+        assert_eq!(line[16], sms_color(15));
+        assert_eq!(line[17..23], [sms_color(1); 6]);
+        assert_eq!(line[23], sms_color(15));
+        assert_eq!(line[24], sms_color(1));
+
+        // Magnified sprites double each pattern pixel
+        vdp.write_control(0x01);
+        vdp.write_control(0x81);
+
+        let line = render_line(&mut vdp, 0);
+        assert_eq!(line[16..18], [sms_color(15); 2]);
+        assert_eq!(line[18], sms_color(1));
+        assert_eq!(line[30..32], [sms_color(15); 2]);
+        assert_eq!(line[32], sms_color(1));
+    }
+}
@@ -0,0 +1,351 @@
+//! ICD2, the chip in the Super Game Boy that connects a Game Boy to the SNES
+//!
+//! The ICD2 clocks the Game Boy from the SNES master clock, captures each line that the Game Boy
+//! renders into a small buffer in 2bpp tile format, receives command packets that the Game Boy sends
+//! over its joypad port, and supplies joypad state for up to 4 players. Everything else (borders,
+//! palettes, and command handling) is done by the Super Game Boy BIOS running on the SNES
+
+use crate::common;
+use crate::common::{Rom, impl_take_set_rom};
+use bincode::{Decode, Encode};
+use gb_core::api::{
+    GameBoyEmulator, GameBoyEmulatorConfig, GameBoyLoadError, GbAspectRatio, GbPalette,
+    GbcColorCorrection,
+};
+use gb_core::inputs::GameBoyInputs;
+use jgenesis_common::frontend::{AudioOutput, EmulatorTrait, SaveWriter, TimingMode};
+use jgenesis_common::num::GetBit;
+use jgenesis_proc_macros::PartialClone;
+use std::collections::VecDeque;
+use std::convert::Infallible;
+
+// ICD2 revision reported at $600F
+const ICD2_VERSION: u8 = 0x21;
+
+// Video settings are irrelevant because the ICD2 reads raw shades from the Game Boy's frame buffer
+const GB_CONFIG: GameBoyEmulatorConfig = GameBoyEmulatorConfig {
+    force_dmg_mode: true,
+    pretend_to_be_gba: false,
+    aspect_ratio: GbAspectRatio::SquarePixels,
+    gb_palette: GbPalette::BlackAndWhite,
+    gb_custom_palette: [(0, 0, 0); 4],
+    gbc_color_correction: GbcColorCorrection::None,
+    audio_60hz_hack: false,
+};
+
+const GB_SCREEN_WIDTH: usize = 160;
+const GB_SCREEN_HEIGHT: u8 = 144;
+
+// Each line buffer holds 8 lines of 20 2bpp tiles
+const LINE_BUFFER_LEN: usize = 20 * 16;
+
+// Roughly 40ms of audio at 48000 Hz. Game Boy audio is resampled based on its actual clock rate so
+// this should only fill up if the two sides drift apart over a long time, in which case old samples
+// are dropped to keep them in sync
+const MAX_BUFFERED_SAMPLES: usize = 2048;
+
+#[derive(Debug, Clone, Default, Encode, Decode)]
+struct SampleQueue(VecDeque<(f64, f64)>);
+
+impl AudioOutput for SampleQueue {
+    type Err = Infallible;
+
+    fn push_sample(&mut self, sample_l: f64, sample_r: f64) -> Result<(), Self::Err> {
+        if self.0.len() == MAX_BUFFERED_SAMPLES {
+            self.0.pop_front();
+        }
+        self.0.push_back((sample_l, sample_r));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Encode, Decode, PartialClone)]
+pub struct Icd2 {
+    #[partial_clone(default)]
+    rom: Rom,
+    #[partial_clone(partial)]
+    gameboy: GameBoyEmulator,
+    snes_mclk_frequency: u64,
+    control: u8,
+    gb_mclk_counter: i64,
+    line_buffers: [[u8; LINE_BUFFER_LEN]; 4],
+    write_buffer: u8,
+    last_scanline: u8,
+    read_buffer: u8,
+    read_address: u16,
+    packet: [u8; 16],
+    joypads: [u8; 4],
+    audio_samples: SampleQueue,
+}
+
+impl Icd2 {
+    /// Create a Super Game Boy cartridge with the given SGB BIOS and Game Boy ROMs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Game Boy core is unable to load the Game Boy ROM.
+    pub fn new<S: SaveWriter>(
+        rom: Box<[u8]>,
+        gb_rom: Vec<u8>,
+        timing_mode: TimingMode,
+        save_writer: &mut S,
+    ) -> Result<Self, GameBoyLoadError> {
+        let gameboy = GameBoyEmulator::create_super_game_boy(gb_rom, GB_CONFIG, save_writer)?;
+
+        let snes_mclk_frequency = match timing_mode {
+            TimingMode::Ntsc => common::NTSC_MASTER_CLOCK_FREQUENCY,
+            TimingMode::Pal => common::PAL_MASTER_CLOCK_FREQUENCY,
+        };
+
+        let mut icd2 = Self {
+            rom: Rom(rom),
+            gameboy,
+            snes_mclk_frequency,
+            control: 0,
+            gb_mclk_counter: 0,
+            line_buffers: [[0; LINE_BUFFER_LEN]; 4],
+            write_buffer: 0,
+            last_scanline: 0,
+            read_buffer: 0,
+            read_address: 0,
+            packet: [0; 16],
+            joypads: [0xFF; 4],
+            audio_samples: SampleQueue::default(),
+        };
+        icd2.update_gb_clock_frequency();
+
+        Ok(icd2)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn read(&mut self, address: u32) -> Option<u8> {
+        let bank = (address >> 16) & 0xFF;
+        let offset = address & 0xFFFF;
+        match (bank, offset) {
+            (0x00..=0x3F | 0x80..=0xBF, 0x6000..=0x7FFF) => self.read_register(offset),
+            (0x00..=0x7D | 0x80..=0xFF, 0x8000..=0xFFFF) => {
+                let rom_addr = common::lorom_map_rom_address(address, self.rom.len() as u32);
+                Some(self.rom[rom_addr as usize])
+            }
+            _ => None,
+        }
+    }
+
+    fn read_register(&mut self, offset: u32) -> Option<u8> {
+        log::trace!("ICD2 register read: {offset:04X}");
+
+        match offset {
+            0x6000 => {
+                // Current character row in bits 3-7, current write buffer in bits 0-1
+                let line = self.last_scanline.min(GB_SCREEN_HEIGHT);
+                Some((line & !7) | self.write_buffer)
+            }
+            0x6002 => {
+                // Reading this register latches the next packet, if any
+                let packet = self.gameboy.sgb_take_packet();
+                if let Some(packet) = packet {
+                    self.packet = packet;
+                }
+                Some(u8::from(packet.is_some()))
+            }
+            0x600F => Some(ICD2_VERSION),
+            0x7000..=0x700F => Some(self.packet[(offset & 0xF) as usize]),
+            0x7800 => {
+                let value =
+                    self.line_buffers[self.read_buffer as usize][self.read_address as usize];
+                self.read_address = (self.read_address + 1) % LINE_BUFFER_LEN as u16;
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn write(&mut self, address: u32, value: u8) {
+        let bank = (address >> 16) & 0xFF;
+        let offset = address & 0xFFFF;
+        if !matches!((bank, offset), (0x00..=0x3F | 0x80..=0xBF, 0x6000..=0x7FFF)) {
+            return;
+        }
+
+        log::trace!("ICD2 register write: {offset:04X} {value:02X}");
+
+        match offset {
+            0x6001 => {
+                self.read_buffer = value & 3;
+                self.read_address = 0;
+            }
+            0x6003 => self.write_control(value),
+            0x6004..=0x6007 => {
+                self.joypads[(offset & 3) as usize] = value;
+                self.gameboy.sgb_set_joypads(self.joypads.map(joypad_to_gb_inputs));
+            }
+            _ => {}
+        }
+    }
+
+    fn write_control(&mut self, value: u8) {
+        // Bit 7: Game Boy reset line (0 = held in reset)
+        // Bits 4-5: Number of players (0 = 1, 1 = 2, 2/3 = 4)
+        // Bits 0-1: Game Boy clock divider
+        if !self.control.bit(7) && value.bit(7) {
+            log::debug!("Releasing Game Boy from reset");
+            self.gameboy.sgb_reset();
+            self.gameboy.sgb_set_joypads(self.joypads.map(joypad_to_gb_inputs));
+            self.gb_mclk_counter = 0;
+            self.last_scanline = 0;
+        }
+
+        let player_count = match (value >> 4) & 3 {
+            0 => 1,
+            1 => 2,
+            _ => 4,
+        };
+        self.gameboy.sgb_set_player_count(player_count);
+
+        self.control = value;
+        self.update_gb_clock_frequency();
+    }
+
+    fn update_gb_clock_frequency(&mut self) {
+        let clock_frequency = self.snes_mclk_frequency as f64 / self.gb_clock_divider() as f64;
+        self.gameboy.sgb_set_clock_frequency(clock_frequency);
+    }
+
+    fn gb_clock_divider(&self) -> i64 {
+        match self.control & 3 {
+            0 => 4,
+            1 => 5,
+            2 => 7,
+            3 => 9,
+            _ => unreachable!("value & 3 is always <= 3"),
+        }
+    }
+
+    pub fn tick(&mut self, master_cycles_elapsed: u64) {
+        if !self.control.bit(7) {
+            return;
+        }
+
+        // Each Game Boy M-cycle is 4 clocks
+        let mclks_per_m_cycle = 4 * self.gb_clock_divider();
+
+        self.gb_mclk_counter += master_cycles_elapsed as i64;
+        while self.gb_mclk_counter > 0 {
+            let m_cycles = self.gameboy.sgb_step();
+            self.gb_mclk_counter -= i64::from(m_cycles) * mclks_per_m_cycle;
+
+            self.check_for_line_change();
+        }
+
+        let Ok(()) = self.gameboy.sgb_drain_audio(&mut self.audio_samples);
+    }
+
+    fn check_for_line_change(&mut self) {
+        let scanline = self.gameboy.sgb_scanline();
+        if scanline == self.last_scanline {
+            return;
+        }
+
+        let completed_line = self.last_scanline;
+        self.last_scanline = scanline;
+
+        if completed_line >= GB_SCREEN_HEIGHT {
+            return;
+        }
+
+        // Convert the line to 2bpp tile format
+        let buffer = &mut self.line_buffers[self.write_buffer as usize];
+        let row_offset = 2 * (completed_line % 8) as usize;
+        for tile in 0..GB_SCREEN_WIDTH / 8 {
+            buffer[tile * 16 + row_offset] = 0;
+            buffer[tile * 16 + row_offset + 1] = 0;
+        }
+        for (x, shade) in self.gameboy.sgb_line(completed_line).enumerate() {
+            let addr = (x / 8) * 16 + row_offset;
+            let shift = 7 - (x % 8);
+            buffer[addr] |= (shade & 1) << shift;
+            buffer[addr + 1] |= (shade >> 1) << shift;
+        }
+
+        if completed_line % 8 == 7 {
+            self.write_buffer = (self.write_buffer + 1) & 3;
+        }
+    }
+
+    /// Mix the next buffered Game Boy audio sample into a SNES audio sample.
+    #[must_use]
+    pub fn mix_audio_sample(&mut self, sample_l: f64, sample_r: f64) -> (f64, f64) {
+        match self.audio_samples.0.pop_front() {
+            Some((gb_l, gb_r)) => {
+                ((sample_l + gb_l).clamp(-1.0, 1.0), (sample_r + gb_r).clamp(-1.0, 1.0))
+            }
+            None => (sample_l, sample_r),
+        }
+    }
+
+    pub fn update_audio_output_frequency(&mut self, output_frequency: u64) {
+        self.gameboy.update_audio_output_frequency(output_frequency);
+    }
+
+    pub fn reset(&mut self) {
+        self.control = 0;
+        self.gb_mclk_counter = 0;
+        self.line_buffers = [[0; LINE_BUFFER_LEN]; 4];
+        self.write_buffer = 0;
+        self.last_scanline = 0;
+        self.read_buffer = 0;
+        self.read_address = 0;
+        self.packet = [0; 16];
+        self.joypads = [0xFF; 4];
+        self.audio_samples.0.clear();
+        self.update_gb_clock_frequency();
+    }
+
+    #[must_use]
+    pub fn has_battery(&self) -> bool {
+        self.gameboy.has_battery()
+    }
+
+    #[must_use]
+    pub fn sram(&self) -> Option<&[u8]> {
+        let sram = self.gameboy.sram();
+        (!sram.is_empty()).then_some(sram)
+    }
+
+    #[must_use]
+    pub fn sram_mut(&mut self) -> Option<&mut [u8]> {
+        let sram = self.gameboy.sram_mut();
+        (!sram.is_empty()).then_some(sram)
+    }
+
+    /// # Errors
+    ///
+    /// Propagates any error returned by the save writer.
+    pub fn save_rtc_state<S: SaveWriter>(&self, save_writer: &mut S) -> Result<(), S::Err> {
+        self.gameboy.save_rtc_state(save_writer)
+    }
+
+    impl_take_set_rom!(rom);
+
+    pub fn take_gb_rom_from(&mut self, other: &mut Self) {
+        self.gameboy.take_rom_from(&mut other.gameboy);
+    }
+}
+
+// SNES-side joypad bytes are active low: bits 0-3 are Right/Left/Up/Down and bits 4-7 are
+// A/B/Select/Start
+fn joypad_to_gb_inputs(joypad: u8) -> GameBoyInputs {
+    GameBoyInputs {
+        right: !joypad.bit(0),
+        left: !joypad.bit(1),
+        up: !joypad.bit(2),
+        down: !joypad.bit(3),
+        a: !joypad.bit(4),
+        b: !joypad.bit(5),
+        select: !joypad.bit(6),
+        start: !joypad.bit(7),
+    }
+}
@@ -1,5 +1,6 @@
 mod common;
 pub mod cx4;
+pub mod icd2;
 pub mod obc1;
 pub mod sa1;
 pub mod sdd1;
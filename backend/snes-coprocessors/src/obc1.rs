@@ -140,5 +140,11 @@ impl Obc1 {
         self.sram.as_ref()
     }
 
+    #[inline]
+    #[must_use]
+    pub fn sram_mut(&mut self) -> &mut [u8] {
+        self.sram.as_mut()
+    }
+
     impl_take_set_rom!(rom);
 }
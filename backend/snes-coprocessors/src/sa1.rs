@@ -83,6 +83,12 @@ impl Sa1 {
         (!self.bwram.is_empty()).then_some(self.bwram.as_ref())
     }
 
+    #[inline]
+    #[must_use]
+    pub fn sram_mut(&mut self) -> Option<&mut [u8]> {
+        (!self.bwram.is_empty()).then_some(self.bwram.as_mut())
+    }
+
     #[inline]
     /// # Panics
     ///
@@ -203,6 +203,12 @@ impl Sdd1 {
         (!self.sram.is_empty()).then_some(self.sram.as_ref())
     }
 
+    #[inline]
+    #[must_use]
+    pub fn sram_mut(&mut self) -> Option<&mut [u8]> {
+        (!self.sram.is_empty()).then_some(self.sram.as_mut())
+    }
+
     #[inline]
     pub fn notify_dma_start(&mut self, channel: u8, source_address: u32) {
         if !self.dma_enabled_1[channel as usize]
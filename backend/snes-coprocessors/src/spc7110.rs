@@ -148,6 +148,12 @@ impl Spc7110 {
         self.sram.as_ref()
     }
 
+    #[inline]
+    #[must_use]
+    pub fn sram_mut(&mut self) -> &mut [u8] {
+        self.sram.as_mut()
+    }
+
     #[inline]
     #[must_use]
     pub fn rtc(&self) -> Option<&Rtc4513> {
@@ -86,20 +86,23 @@ struct RtcTime {
 }
 
 impl RtcTime {
+    // Initialize to the host's current local time, in 24-hour mode with the calendar enabled
     fn new(now_nanos: u128) -> Self {
+        let now = timeutils::current_date_time();
+
         Self {
             last_update_nanos: now_nanos,
             nanos: 0,
-            seconds: 0,
-            minutes: 0,
-            hours: 0,
-            hour_type: HourType::default(),
-            day: 0,
-            month: 0,
-            year: 0,
-            day_of_week: 0,
-            clock_hours: ClockHours::default(),
-            calendar_enabled: false,
+            seconds: now.second(),
+            minutes: now.minute(),
+            hours: now.hour(),
+            hour_type: HourType::Am,
+            day: now.day(),
+            month: now.month().into(),
+            year: now.year().rem_euclid(100) as u8,
+            day_of_week: now.weekday().number_days_from_sunday(),
+            clock_hours: ClockHours::TwentyFour,
+            calendar_enabled: true,
         }
     }
 
@@ -235,7 +238,8 @@ impl Rtc4513 {
             pending_seconds_increment: false,
             stopped: false,
             reset: false,
-            time_lost: true,
+            // Clock was just synced to host time
+            time_lost: false,
             irq: false,
             irq_enabled: true,
             irq_rate_bits: 0,
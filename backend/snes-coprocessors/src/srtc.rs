@@ -74,19 +74,26 @@ impl Default for SRtc {
 }
 
 impl SRtc {
+    /// Create a new S-RTC with the clock initialized to the host's current local time.
     #[must_use]
     pub fn new() -> Self {
+        let last_update_nanos = timeutils::current_time_nanos();
+        let now = timeutils::current_date_time();
+
+        // The century digit is relative to 1000 AD; clamp to the range that fits in 4 bits
+        let full_year = now.year().clamp(1000, 2599) as u16;
+
         Self {
-            last_update_nanos: timeutils::current_time_nanos(),
+            last_update_nanos,
             nanos: 0,
-            seconds: 0,
-            minutes: 0,
-            hours: 0,
-            day: 1,
-            month: 1,
-            year: 0,
-            century: 9,
-            day_of_week: 0,
+            seconds: now.second(),
+            minutes: now.minute(),
+            hours: now.hour(),
+            day: now.day(),
+            month: now.month().into(),
+            year: (full_year % 100) as u8,
+            century: ((full_year - 1000) / 100) as u8,
+            day_of_week: now.weekday().to_srtc_u8(),
             read_state: ReadState::default(),
             write_state: WriteState::default(),
         }
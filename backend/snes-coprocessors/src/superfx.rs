@@ -17,9 +17,9 @@ use crate::common;
 use crate::common::{Rom, impl_take_set_rom};
 use crate::superfx::gsu::{BusAccess, GraphicsSupportUnit};
 use bincode::{Decode, Encode};
+use jgenesis_common::clockratio::ClockRatio;
 use jgenesis_common::num::U16Ext;
 use jgenesis_proc_macros::PartialClone;
-use std::num::NonZeroU64;
 
 #[derive(Debug, Clone, Encode, Decode, PartialClone)]
 pub struct SuperFx {
@@ -27,17 +27,19 @@ pub struct SuperFx {
     rom: Rom,
     ram: Box<[u8]>,
     gsu: GraphicsSupportUnit,
-    gsu_overclock_factor: u64,
+    gsu_overclock_factor: ClockRatio,
+    gsu_mclk_remainder: u32,
 }
 
 impl SuperFx {
     #[must_use]
-    pub fn new(rom: Box<[u8]>, ram: Box<[u8]>, gsu_overclock_factor: NonZeroU64) -> Self {
+    pub fn new(rom: Box<[u8]>, ram: Box<[u8]>, gsu_overclock_factor: ClockRatio) -> Self {
         Self {
             rom: Rom(rom),
             ram,
             gsu: GraphicsSupportUnit::new(),
-            gsu_overclock_factor: gsu_overclock_factor.get(),
+            gsu_overclock_factor,
+            gsu_mclk_remainder: 0,
         }
     }
 
@@ -120,7 +122,10 @@ impl SuperFx {
 
     #[inline]
     pub fn tick(&mut self, master_cycles_elapsed: u64) {
-        self.gsu.tick(self.gsu_overclock_factor * master_cycles_elapsed, &self.rom, &mut self.ram);
+        let gsu_cycles = self
+            .gsu_overclock_factor
+            .scale_cycles(master_cycles_elapsed, &mut self.gsu_mclk_remainder);
+        self.gsu.tick(gsu_cycles, &self.rom, &mut self.ram);
     }
 
     #[inline]
@@ -148,10 +153,16 @@ impl SuperFx {
         self.ram.as_ref()
     }
 
+    #[inline]
+    #[must_use]
+    pub fn sram_mut(&mut self) -> &mut [u8] {
+        self.ram.as_mut()
+    }
+
     impl_take_set_rom!(rom);
 
-    pub fn update_gsu_overclock_factor(&mut self, overclock_factor: NonZeroU64) {
-        self.gsu_overclock_factor = overclock_factor.get();
+    pub fn update_gsu_overclock_factor(&mut self, overclock_factor: ClockRatio) {
+        self.gsu_overclock_factor = overclock_factor;
     }
 }
 
@@ -338,6 +338,12 @@ impl Upd77c25 {
         bytemuck::cast_slice(self.ram.as_ref())
     }
 
+    #[inline]
+    #[must_use]
+    pub fn sram_mut(&mut self) -> &mut [u8] {
+        bytemuck::cast_slice_mut(self.ram.as_mut())
+    }
+
     #[inline]
     pub fn tick(&mut self, master_cycles_elapsed: u64) {
         if self.idling {
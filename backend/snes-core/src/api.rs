@@ -1,24 +1,28 @@
 //! SNES public interface and main loop
 
-use crate::apu::{Apu, ApuTickEffect};
+use crate::apu::{Apu, ApuTickEffect, BOOT_ROM_LEN, BootRom};
 use crate::audio::AudioResampler;
 use crate::bus::Bus;
 use crate::input::{SnesButton, SnesInputs};
 use crate::memory::dma::{DmaStatus, DmaUnit};
-use crate::memory::{CpuInternalRegisters, Memory};
+use crate::memory::{CpuInternalRegisters, Memory, cartridge};
 use crate::ppu::{Ppu, PpuTickEffect};
 use bincode::error::EncodeError;
 use bincode::{Decode, Encode};
 use crc::Crc;
+use gb_core::api::GameBoyLoadError;
+use jgenesis_common::clockratio::ClockRatio;
 use jgenesis_common::frontend::{
-    AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, FrameSize, PartialClone,
-    PixelAspectRatio, Renderer, SaveWriter, TickEffect, TimingMode,
+    AudioOutput, Color, EmulatorConfigTrait, EmulatorTrait, FrameSize, GameMetadata, PartialClone,
+    PixelAspectRatio, Renderer, SaveStateSafePoint, SaveWriter, TickEffect, TimingMode,
 };
+use jgenesis_common::instrument;
+use jgenesis_common::instrumentation::Subsystem;
+use jgenesis_common::memmap::MemoryMap;
 use jgenesis_proc_macros::{
     ConfigDisplay, EnumAll, EnumDisplay, EnumFromStr, FakeDecode, FakeEncode,
 };
 use std::fmt::{Debug, Display};
-use std::num::NonZeroU64;
 use std::{io, mem};
 use thiserror::Error;
 use wdc65816_emu::core::Wdc65816;
@@ -53,14 +57,14 @@ impl SnesAspectRatio {
             }
         };
 
-        if frame_size.width == 512 && frame_size.height < 240 {
-            // Cut pixel aspect ratio in half to account for the screen being squished horizontally
-            pixel_aspect_ratio *= 0.5;
-        }
-
-        if frame_size.width == 256 && frame_size.height >= 240 {
-            // Double pixel aspect ratio to account for the screen being stretched horizontally
-            pixel_aspect_ratio *= 2.0;
+        // The ratios above are for 256px-wide progressive frames. Hi-res frames (512px) have pixels
+        // half as wide, and interlaced frames (448/478 lines) have pixels half as tall
+        let h_hi_res = frame_size.width >= 512;
+        let v_hi_res = frame_size.height >= 448;
+        match (h_hi_res, v_hi_res) {
+            (true, false) => pixel_aspect_ratio *= 0.5,
+            (false, true) => pixel_aspect_ratio *= 2.0,
+            (false, false) | (true, true) => {}
         }
 
         Some(PixelAspectRatio::try_from(pixel_aspect_ratio).unwrap())
@@ -76,6 +80,15 @@ pub enum AudioInterpolationMode {
     #[default]
     Gaussian,
     Hermite,
+    None,
+}
+
+/// Memory regions that can be dumped and restored for debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumAll)]
+pub enum MemoryRegion {
+    Wram,
+    Vram,
+    Sram,
 }
 
 #[derive(Debug, Clone, Copy, Encode, Decode, ConfigDisplay)]
@@ -83,12 +96,38 @@ pub struct SnesEmulatorConfig {
     pub forced_timing_mode: Option<TimingMode>,
     pub aspect_ratio: SnesAspectRatio,
     pub deinterlace: bool,
+    pub hi_res_blending: bool,
+    /// Debug overlay that tints every pixel by the layer that won priority resolution
+    pub priority_overlay: bool,
     pub audio_interpolation: AudioInterpolationMode,
     pub audio_60hz_hack: bool,
-    pub gsu_overclock_factor: NonZeroU64,
+    pub gsu_overclock_factor: ClockRatio,
+}
+
+#[cfg(test)]
+pub(crate) fn test_config() -> SnesEmulatorConfig {
+    SnesEmulatorConfig {
+        forced_timing_mode: None,
+        aspect_ratio: SnesAspectRatio::default(),
+        deinterlace: true,
+        hi_res_blending: false,
+        priority_overlay: false,
+        audio_interpolation: AudioInterpolationMode::default(),
+        audio_60hz_hack: false,
+        gsu_overclock_factor: ClockRatio::from_integer(1),
+    }
 }
 
-impl EmulatorConfigTrait for SnesEmulatorConfig {}
+impl EmulatorConfigTrait for SnesEmulatorConfig {
+    fn with_enhancements_disabled(&self) -> Self {
+        Self {
+            hi_res_blending: false,
+            priority_overlay: false,
+            gsu_overclock_factor: ClockRatio::from_integer(1),
+            ..*self
+        }
+    }
+}
 
 pub type CoprocessorRomFn = dyn Fn() -> Result<Vec<u8>, (io::Error, String)>;
 
@@ -100,6 +139,10 @@ pub struct CoprocessorRoms {
     pub dsp4: Option<Box<CoprocessorRomFn>>,
     pub st010: Option<Box<CoprocessorRomFn>>,
     pub st011: Option<Box<CoprocessorRomFn>>,
+    /// Game Boy ROM to insert when the SNES ROM is a Super Game Boy BIOS
+    pub sgb_cartridge: Option<Box<CoprocessorRomFn>>,
+    /// Dumped SPC700 IPL boot ROM; the built-in copy is used if not set
+    pub spc700_ipl: Option<Box<CoprocessorRomFn>>,
 }
 
 impl CoprocessorRoms {
@@ -135,6 +178,12 @@ pub enum SnesLoadError {
     MissingSt010Rom,
     #[error("Cannot load ST011 cartridge because ST011 ROM is not configured")]
     MissingSt011Rom,
+    #[error("Cannot load Super Game Boy BIOS because no Game Boy ROM was provided")]
+    MissingSgbCartridge,
+    #[error("Failed to load Game Boy ROM into Super Game Boy: {0}")]
+    SgbCartridge(#[from] GameBoyLoadError),
+    #[error("SPC700 IPL ROM must be exactly {expected} bytes, was {actual} bytes")]
+    InvalidSpc700Ipl { expected: usize, actual: usize },
     #[error("Failed to load required coprocessor ROM from '{path}': {source}")]
     CoprocessorRomLoad {
         #[source]
@@ -182,6 +231,9 @@ pub struct SnesEmulator {
     aspect_ratio: SnesAspectRatio,
     frame_count: u64,
     last_sram_checksum: u32,
+    // Set when SRAM is overwritten from outside the emulated system, e.g. restored from a dump in
+    // the debugger, so that it is persisted without waiting for the next periodic checksum
+    sram_dirty: bool,
     // Following fields only stored here to enable hard reset
     #[partial_clone(default)]
     coprocessor_roms: CoprocessorRoms,
@@ -204,7 +256,7 @@ impl SnesEmulator {
 
         let initial_sram = save_writer.load_bytes("sav").ok();
         let sram_checksum = initial_sram.as_ref().map_or(0, |sram| CRC.checksum(sram));
-        let mut memory = Memory::create(
+        let memory = Memory::create(
             rom,
             initial_sram,
             &coprocessor_roms,
@@ -213,10 +265,20 @@ impl SnesEmulator {
             save_writer,
         )?;
 
-        let timing_mode =
-            config.forced_timing_mode.unwrap_or_else(|| memory.cartridge_timing_mode());
+        let boot_rom = match &coprocessor_roms.spc700_ipl {
+            Some(ipl_fn) => {
+                let ipl = ipl_fn()
+                    .map_err(|(source, path)| SnesLoadError::CoprocessorRomLoad { source, path })?;
+                Some(BootRom::try_from(ipl.as_slice()).map_err(|_| {
+                    SnesLoadError::InvalidSpc700Ipl { expected: BOOT_ROM_LEN, actual: ipl.len() }
+                })?)
+            }
+            None => None,
+        };
+
+        let timing_mode = memory.timing_mode();
         let ppu = Ppu::new(timing_mode, config);
-        let apu = Apu::new(timing_mode, config);
+        let apu = Apu::new(timing_mode, config, boot_rom);
 
         log::info!("Running with timing/display mode {timing_mode}");
 
@@ -235,6 +297,7 @@ impl SnesEmulator {
             aspect_ratio: config.aspect_ratio,
             frame_count: 0,
             last_sram_checksum: sram_checksum,
+            sram_dirty: false,
             coprocessor_roms,
             emulator_config: config,
         };
@@ -245,9 +308,24 @@ impl SnesEmulator {
         Ok(emulator)
     }
 
+    /// Number of bytes that [`Self::read_header_metadata`] needs from the start of a ROM image,
+    /// including a 512-byte copier header
+    pub const HEADER_PREFIX_LEN: usize = 0x200 + cartridge::HEADER_PREFIX_LEN;
+
+    /// Parse the title, serial number, and region from a ROM image's header without creating an
+    /// emulator.
+    #[must_use]
+    pub fn read_game_metadata(rom: &[u8]) -> GameMetadata {
+        cartridge::game_metadata_from_rom(rom)
+    }
+
+    /// Parse the title, serial number, and region from the start of a ROM image, e.g. to show
+    /// header information for many ROM files without reading them in full. `rom_prefix` should
+    /// contain the first [`Self::HEADER_PREFIX_LEN`] bytes of the image, and `rom_len` is the size
+    /// of the full image.
     #[must_use]
-    pub fn cartridge_title(&mut self) -> String {
-        self.memory.cartridge_title()
+    pub fn read_header_metadata(rom_prefix: &[u8], rom_len: usize) -> GameMetadata {
+        cartridge::game_metadata_from_rom_prefix(rom_prefix, rom_len)
     }
 
     #[inline]
@@ -260,6 +338,11 @@ impl SnesEmulator {
         self.ppu.copy_cgram(out);
     }
 
+    #[must_use]
+    pub fn dump_cgram(&self) -> Vec<u8> {
+        self.ppu.dump_cgram()
+    }
+
     pub fn copy_vram_2bpp(&self, out: &mut [Color], palette: u8, row_len: usize) {
         self.ppu.copy_vram_2bpp(out, palette, row_len);
     }
@@ -275,6 +358,54 @@ impl SnesEmulator {
     pub fn copy_vram_mode7(&self, out: &mut [Color], row_len: usize) {
         self.ppu.copy_vram_mode7(out, row_len);
     }
+
+    /// Describe the full 65816 memory map along with a more detailed map of the system area banks,
+    /// based on the cartridge's board type.
+    #[must_use]
+    pub fn memory_map_description(&self) -> Vec<MemoryMap> {
+        vec![self.memory.memory_map(), self.memory.system_bank_memory_map()]
+    }
+
+    #[must_use]
+    pub fn dump_memory(&self, region: MemoryRegion) -> Vec<u8> {
+        match region {
+            MemoryRegion::Wram => self.memory.main_ram().to_vec(),
+            MemoryRegion::Vram => self.ppu.dump_vram(),
+            MemoryRegion::Sram => self.memory.sram().map(<[u8]>::to_vec).unwrap_or_default(),
+        }
+    }
+
+    /// Overwrite a memory region, e.g. with the contents of a file previously written using
+    /// [`Self::dump_memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not the same length as the memory region.
+    pub fn restore_memory(&mut self, region: MemoryRegion, bytes: &[u8]) {
+        match region {
+            MemoryRegion::Wram => self.memory.main_ram_mut().copy_from_slice(bytes),
+            MemoryRegion::Vram => self.ppu.restore_vram(bytes),
+            MemoryRegion::Sram => match self.memory.sram_mut() {
+                Some(sram) => {
+                    sram.copy_from_slice(bytes);
+                    self.sram_dirty = true;
+                }
+                None => assert!(bytes.is_empty(), "cartridge has no SRAM"),
+            },
+        }
+    }
+
+    /// Mute or unmute one of the 8 DSP voices in the audio output.
+    pub fn set_dsp_voice_muted(&mut self, voice: usize, muted: bool) {
+        self.apu.set_voice_muted(voice, muted);
+    }
+
+    /// Whether one of the 8 DSP voices is currently muted. Mutes are not saved in save states, so
+    /// loading a state unmutes every voice.
+    #[must_use]
+    pub fn is_dsp_voice_muted(&self, voice: usize) -> bool {
+        self.apu.is_voice_muted(voice)
+    }
 }
 
 impl EmulatorTrait for SnesEmulator {
@@ -311,10 +442,13 @@ impl EmulatorTrait for SnesEmulator {
         } else {
             let mut bus = new_bus!(self);
 
-            match self.dma_unit.tick(&mut bus, self.total_master_cycles) {
+            match instrument!(
+                Subsystem::Cpu,
+                self.dma_unit.tick(&mut bus, self.total_master_cycles)
+            ) {
                 DmaStatus::None => {
                     // DMA not in progress, tick CPU
-                    self.main_cpu.tick(&mut bus);
+                    instrument!(Subsystem::Cpu, self.main_cpu.tick(&mut bus));
                     self.latched_interrupts = None;
 
                     (bus.access_master_cycles, bus.pending_write)
@@ -343,18 +477,24 @@ impl EmulatorTrait for SnesEmulator {
         }
 
         if let ApuTickEffect::OutputSample(sample_l, sample_r) =
-            self.apu.tick(master_cycles_elapsed)
+            instrument!(Subsystem::Apu, self.apu.tick(master_cycles_elapsed))
         {
             self.audio_resampler.collect_sample(sample_l, sample_r);
         }
 
-        self.audio_resampler.output_samples(audio_output).map_err(SnesError::AudioOutput)?;
+        self.audio_resampler
+            .output_samples(audio_output, |sample_l, sample_r| {
+                self.memory.mix_cartridge_audio(sample_l, sample_r)
+            })
+            .map_err(SnesError::AudioOutput)?;
 
         self.memory.tick(master_cycles_elapsed);
 
         let prev_scanline_mclk = self.ppu.scanline_master_cycles();
         let mut tick_effect = TickEffect::None;
-        if self.ppu.tick(master_cycles_elapsed) == PpuTickEffect::FrameComplete {
+        if instrument!(Subsystem::Ppu, self.ppu.tick(master_cycles_elapsed))
+            == PpuTickEffect::FrameComplete
+        {
             let frame_size = self.ppu.frame_size();
             let aspect_ratio = self.aspect_ratio.to_pixel_aspect_ratio(frame_size);
 
@@ -366,15 +506,16 @@ impl EmulatorTrait for SnesEmulator {
             // second because of the checksum calculation
             if self.memory.has_battery_backed_sram() {
                 if let Some(sram) = self.memory.sram() {
-                    if self.frame_count % 30 == 0 {
+                    if self.sram_dirty || self.frame_count % 30 == 0 {
                         let checksum = CRC.checksum(sram);
-                        if checksum != self.last_sram_checksum {
+                        if self.sram_dirty || checksum != self.last_sram_checksum {
                             save_writer.persist_bytes("sav", sram).map_err(SnesError::SaveWrite)?;
                             self.memory
                                 .write_auxiliary_save_files(save_writer)
                                 .map_err(SnesError::SaveWrite)?;
 
                             self.last_sram_checksum = checksum;
+                            self.sram_dirty = false;
                         }
                     }
                 }
@@ -411,6 +552,10 @@ impl EmulatorTrait for SnesEmulator {
         renderer.render_frame(self.ppu.frame_buffer(), frame_size, aspect_ratio)
     }
 
+    fn clear_audio_mutes(&mut self) {
+        self.apu.clear_muted_voices();
+    }
+
     fn reload_config(&mut self, config: &Self::Config) {
         self.aspect_ratio = config.aspect_ratio;
         self.ppu.update_config(*config);
@@ -425,6 +570,10 @@ impl EmulatorTrait for SnesEmulator {
         self.coprocessor_roms = mem::take(&mut other.coprocessor_roms);
     }
 
+    fn game_metadata(&mut self) -> GameMetadata {
+        self.memory.game_metadata()
+    }
+
     fn soft_reset(&mut self) {
         log::info!("Soft resetting");
 
@@ -448,6 +597,11 @@ impl EmulatorTrait for SnesEmulator {
             .expect("Hard resetting should never fail to load");
     }
 
+    fn save_state_safe_point() -> SaveStateSafePoint {
+        // The PPU renders into a frame buffer that is not saved
+        SaveStateSafePoint::FrameBoundary
+    }
+
     fn target_fps(&self) -> f64 {
         match (self.timing_mode, self.emulator_config.audio_60hz_hack) {
             (TimingMode::Ntsc, true) => 60.0,
@@ -459,5 +613,6 @@ impl EmulatorTrait for SnesEmulator {
 
     fn update_audio_output_frequency(&mut self, output_frequency: u64) {
         self.audio_resampler.update_output_frequency(output_frequency);
+        self.memory.update_audio_output_frequency(output_frequency);
     }
 }
@@ -18,15 +18,24 @@ use spc700_emu::traits::BusInterface;
 
 const AUDIO_RAM_LEN: usize = 64 * 1024;
 
+pub const BOOT_ROM_LEN: usize = 64;
+
+pub type BootRom = [u8; BOOT_ROM_LEN];
+
 // The APU frequency is 32000 Hz on paper, but hardware tends to run slightly faster than that
 pub const OUTPUT_FREQUENCY: u64 = 32040;
 
 // Roughly 24.607 MHz
 const ACTUAL_APU_MASTER_CLOCK_FREQUENCY: u64 = OUTPUT_FREQUENCY * 768;
 
-// APU master clock rate increased such that audio signal is timed to 60Hz for NTSC (and slightly under 50Hz for PAL)
+// APU master clock rate increased such that audio signal is timed to 60Hz for NTSC
 // Specifically, (actual_mclk_rate * 60.099 / 60.0)
-const ADJUSTED_APU_MASTER_CLOCK_FREQUENCY: u64 = ACTUAL_APU_MASTER_CLOCK_FREQUENCY * 60099 / 60000;
+const NTSC_ADJUSTED_APU_MASTER_CLOCK_FREQUENCY: u64 =
+    ACTUAL_APU_MASTER_CLOCK_FREQUENCY * 60099 / 60000;
+
+// Same as above but timed to 50Hz for PAL, (actual_mclk_rate * 50.007 / 50.0)
+const PAL_ADJUSTED_APU_MASTER_CLOCK_FREQUENCY: u64 =
+    ACTUAL_APU_MASTER_CLOCK_FREQUENCY * 50007 / 50000;
 
 // APU outputs a sample every 24 * 32 master clocks
 const SAMPLE_DIVIDER: u8 = 32;
@@ -174,6 +183,7 @@ struct Spc700Bus<'a> {
     dsp: &'a mut AudioDsp,
     audio_ram: &'a mut Box<AudioRam>,
     registers: &'a mut ApuRegisters,
+    boot_rom: &'a BootRom,
 }
 
 impl BusInterface for Spc700Bus<'_> {
@@ -186,7 +196,7 @@ impl BusInterface for Spc700Bus<'_> {
             0x00F0..=0x00FF => self.registers.read(address & 0xF, self.dsp),
             0xFFC0..=0xFFFF => {
                 if self.registers.boot_rom_mapped {
-                    bootrom::SPC700_BOOT_ROM[(address & 0x003F) as usize]
+                    self.boot_rom[(address & 0x003F) as usize]
                 } else {
                     self.audio_ram[address as usize]
                 }
@@ -225,7 +235,9 @@ pub struct Apu {
     dsp: AudioDsp,
     audio_ram: Box<AudioRam>,
     registers: ApuRegisters,
+    boot_rom: BootRom,
     main_master_clock_frequency: u64,
+    adjusted_apu_master_clock_frequency: u64,
     master_cycles_product: u64,
     sample_divider: u8,
     enable_audio_60hz_hack: bool,
@@ -237,23 +249,39 @@ macro_rules! new_spc700_bus {
             dsp: &mut $self.dsp,
             audio_ram: &mut $self.audio_ram,
             registers: &mut $self.registers,
+            boot_rom: &$self.boot_rom,
         }
     };
 }
 
 impl Apu {
-    pub fn new(timing_mode: TimingMode, config: SnesEmulatorConfig) -> Self {
-        let main_master_clock_frequency = match timing_mode {
-            TimingMode::Ntsc => constants::NTSC_MASTER_CLOCK_FREQUENCY,
-            TimingMode::Pal => constants::PAL_MASTER_CLOCK_FREQUENCY,
+    /// Create a new APU. Uses the built-in copy of the IPL boot ROM if `boot_rom` is `None`.
+    pub fn new(
+        timing_mode: TimingMode,
+        config: SnesEmulatorConfig,
+        boot_rom: Option<BootRom>,
+    ) -> Self {
+        let (main_master_clock_frequency, adjusted_apu_master_clock_frequency) = match timing_mode {
+            TimingMode::Ntsc => {
+                (constants::NTSC_MASTER_CLOCK_FREQUENCY, NTSC_ADJUSTED_APU_MASTER_CLOCK_FREQUENCY)
+            }
+            TimingMode::Pal => {
+                (constants::PAL_MASTER_CLOCK_FREQUENCY, PAL_ADJUSTED_APU_MASTER_CLOCK_FREQUENCY)
+            }
         };
 
+        if boot_rom.is_some() {
+            log::info!("Using dumped SPC700 IPL boot ROM");
+        }
+
         let mut apu = Self {
             spc700: Spc700::new(),
             dsp: AudioDsp::new(config.audio_interpolation),
             audio_ram: vec![0; AUDIO_RAM_LEN].into_boxed_slice().try_into().unwrap(),
             registers: ApuRegisters::new(),
+            boot_rom: boot_rom.unwrap_or(*bootrom::SPC700_BOOT_ROM),
             main_master_clock_frequency,
+            adjusted_apu_master_clock_frequency,
             master_cycles_product: 0,
             sample_divider: SAMPLE_DIVIDER,
             enable_audio_60hz_hack: config.audio_60hz_hack,
@@ -267,7 +295,7 @@ impl Apu {
     #[must_use]
     pub fn tick(&mut self, main_master_cycles: u64) -> ApuTickEffect {
         let apu_master_clock_frequency = if self.enable_audio_60hz_hack {
-            ADJUSTED_APU_MASTER_CLOCK_FREQUENCY
+            self.adjusted_apu_master_clock_frequency
         } else {
             ACTUAL_APU_MASTER_CLOCK_FREQUENCY
         };
@@ -317,4 +345,91 @@ impl Apu {
         self.dsp.update_audio_interpolation(config.audio_interpolation);
         self.enable_audio_60hz_hack = config.audio_60hz_hack;
     }
+
+    pub fn set_voice_muted(&mut self, voice: usize, muted: bool) {
+        self.dsp.set_voice_muted(voice, muted);
+    }
+
+    pub fn is_voice_muted(&self, voice: usize) -> bool {
+        self.dsp.is_voice_muted(voice)
+    }
+
+    pub fn clear_muted_voices(&mut self) {
+        self.dsp.clear_muted_voices();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api;
+
+    fn new_apu(boot_rom: Option<BootRom>) -> Apu {
+        Apu::new(TimingMode::Ntsc, api::test_config(), boot_rom)
+    }
+
+    fn samples_per_second(timing_mode: TimingMode, audio_60hz_hack: bool) -> u64 {
+        let config = SnesEmulatorConfig { audio_60hz_hack, ..api::test_config() };
+        let mut apu = Apu::new(timing_mode, config, None);
+
+        let master_clock_frequency = match timing_mode {
+            TimingMode::Ntsc => constants::NTSC_MASTER_CLOCK_FREQUENCY,
+            TimingMode::Pal => constants::PAL_MASTER_CLOCK_FREQUENCY,
+        };
+
+        let mut samples = 0;
+        for _ in 0..master_clock_frequency / 8 {
+            if let ApuTickEffect::OutputSample(..) = apu.tick(8) {
+                samples += 1;
+            }
+        }
+        samples
+    }
+
+    #[test]
+    fn apu_clock_independent_of_timing_mode() {
+        // The APU has its own oscillator, so one second of emulated time should produce the same
+        // number of samples regardless of the main master clock rate
+        for timing_mode in [TimingMode::Ntsc, TimingMode::Pal] {
+            let samples = samples_per_second(timing_mode, false);
+            assert!(samples.abs_diff(OUTPUT_FREQUENCY) <= 1, "{timing_mode}: {samples}");
+        }
+
+        // With the 60Hz/50Hz hack, the APU runs slightly faster so that audio matches the rounded
+        // frame rate
+        let ntsc_samples = samples_per_second(TimingMode::Ntsc, true);
+        assert!(ntsc_samples.abs_diff(OUTPUT_FREQUENCY * 60099 / 60000) <= 1, "{ntsc_samples}");
+        let pal_samples = samples_per_second(TimingMode::Pal, true);
+        assert!(pal_samples.abs_diff(OUTPUT_FREQUENCY * 50007 / 50000) <= 1, "{pal_samples}");
+    }
+
+    fn clocks_until_ports(apu: &mut Apu, expected: [u8; 2], max_clocks: u32) -> Option<u32> {
+        (1..=max_clocks).find(|_| {
+            apu.clock();
+            apu.read_port(0) == expected[0] && apu.read_port(1) == expected[1]
+        })
+    }
+
+    #[test]
+    fn ipl_handshake_timing() {
+        let mut apu = new_apu(None);
+
+        // The IPL clears $00-$EF (239 iterations of a 10-cycle loop, minus 2 cycles for the final
+        // untaken branch), then signals readiness by writing $AA and $BB to ports 0 and 1 using two
+        // 5-cycle MOV dp, #imm instructions, after 6 cycles of setup
+        let expected_clocks = 6 + 239 * 10 - 2 + 5 + 5;
+        assert_eq!(clocks_until_ports(&mut apu, [0xAA, 0xBB], 10000), Some(expected_clocks));
+    }
+
+    #[test]
+    fn dumped_ipl() {
+        let mut boot_rom = [0; BOOT_ROM_LEN];
+        // MOV $F4, #$12; MOV $F5, #$34; BRA -2
+        boot_rom[..8].copy_from_slice(&[0x8F, 0x12, 0xF4, 0x8F, 0x34, 0xF5, 0x2F, 0xFE]);
+        // Reset vector
+        boot_rom[0x3E..].copy_from_slice(&[0xC0, 0xFF]);
+
+        let mut apu = new_apu(Some(boot_rom));
+        assert_eq!(clocks_until_ports(&mut apu, [0x12, 0x34], 100), Some(10));
+    }
 }
@@ -1,6 +1,8 @@
+use crate::apu::BootRom;
+
 // From https://problemkaputt.github.io/fullsnes.htm#snesapumaincpucommunicationport
 #[rustfmt::skip]
-pub const SPC700_BOOT_ROM: &[u8; 64] = &[
+pub const SPC700_BOOT_ROM: &BootRom = &[
     // MOV X, $EF
     0xCD, 0xEF,
     // MOV SP, X
@@ -8,6 +8,7 @@ use crate::apu::AudioRam;
 use crate::apu::dsp::interpolate::InterpolateArgs;
 use bincode::{Decode, Encode};
 use jgenesis_common::num::{GetBit, U16Ext};
+use jgenesis_proc_macros::SaveState;
 use std::array;
 use std::ops::Index;
 
@@ -100,9 +101,12 @@ impl Index<u16> for BrrRingBuffer {
     }
 }
 
-#[derive(Debug, Clone, Encode, Decode)]
+#[derive(Debug, Clone, SaveState)]
 struct Voice {
     audio_interpolation: AudioInterpolationMode,
+    // Debug-only setting controlled by the frontend, not part of emulated state
+    #[savestate(skip)]
+    muted: bool,
     // Registers
     instrument_number: u8,
     sample_rate: u16,
@@ -140,6 +144,7 @@ impl Voice {
     fn new(audio_interpolation: AudioInterpolationMode) -> Self {
         Self {
             audio_interpolation,
+            muted: false,
             instrument_number: 0,
             sample_rate: 0,
             pitch_modulation_enabled: false,
@@ -318,6 +323,7 @@ impl Voice {
             match self.audio_interpolation {
                 AudioInterpolationMode::Gaussian => interpolate::gaussian(args),
                 AudioInterpolationMode::Hermite => interpolate::hermite(args),
+                AudioInterpolationMode::None => interpolate::none(args),
             }
         };
 
@@ -1108,6 +1114,12 @@ impl AudioDsp {
             voice_samples_l[i] = voice_sample_l;
             voice_samples_r[i] = voice_sample_r;
 
+            // Muted voices still feed the echo filter, which writes to audio RAM; only exclude them
+            // from the main output
+            if voice.muted {
+                continue;
+            }
+
             voice_sum_l += voice_sample_l;
             voice_sum_r += voice_sample_r;
 
@@ -1154,4 +1166,18 @@ impl AudioDsp {
             voice.audio_interpolation = audio_interpolation;
         }
     }
+
+    pub fn set_voice_muted(&mut self, voice: usize, muted: bool) {
+        self.voices[voice].muted = muted;
+    }
+
+    pub fn is_voice_muted(&self, voice: usize) -> bool {
+        self.voices[voice].muted
+    }
+
+    pub fn clear_muted_voices(&mut self) {
+        for voice in &mut self.voices {
+            voice.muted = false;
+        }
+    }
 }
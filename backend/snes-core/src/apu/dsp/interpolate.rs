@@ -38,6 +38,12 @@ pub fn gaussian(
     sum.clamp((i16::MIN >> 1).into(), (i16::MAX >> 1).into()) as i16
 }
 
+// Hold each sample until the pitch counter reaches the next one. This lines up with the other modes,
+// which interpolate between the older and old samples
+pub fn none(InterpolateArgs { older, .. }: InterpolateArgs) -> i16 {
+    older.clamp(i16::MIN >> 1, i16::MAX >> 1)
+}
+
 pub fn hermite(
     InterpolateArgs { pitch_counter, oldest, older, old, sample }: InterpolateArgs,
 ) -> i16 {
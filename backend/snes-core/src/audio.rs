@@ -33,8 +33,14 @@ impl AudioResampler {
         self.resampler.collect_sample(sample_l, sample_r);
     }
 
-    pub fn output_samples<A: AudioOutput>(&mut self, audio_output: &mut A) -> Result<(), A::Err> {
+    // `mix_fn` mixes in cartridge audio, if any
+    pub fn output_samples<A: AudioOutput>(
+        &mut self,
+        audio_output: &mut A,
+        mut mix_fn: impl FnMut(f64, f64) -> (f64, f64),
+    ) -> Result<(), A::Err> {
         while let Some((sample_l, sample_r)) = self.resampler.output_buffer_pop_front() {
+            let (sample_l, sample_r) = mix_fn(sample_l, sample_r);
             audio_output.push_sample(sample_l, sample_r)?;
         }
 
@@ -49,7 +49,7 @@ impl Bus<'_> {
                 self.access_master_cycles = FAST_MASTER_CYCLES;
 
                 // PPU ports
-                self.ppu.read_port(address).unwrap_or(self.memory.cpu_open_bus())
+                self.ppu.read_port(address).unwrap_or(self.memory.cpu_open_bus().value())
             }
             0x2140..=0x217F => {
                 self.access_master_cycles = FAST_MASTER_CYCLES;
@@ -69,9 +69,9 @@ impl Bus<'_> {
                 // $4016 and $4017 are CPU I/O ports (manual joypad ports)
                 // The rest of this range is CPU open bus with XSlow memory speed
                 let cpu_open_bus = self.memory.cpu_open_bus();
-                self.cpu_registers.read_register(address, cpu_open_bus).unwrap_or_else(|| {
-                    self.memory.read_cartridge(full_address).unwrap_or(cpu_open_bus)
-                })
+                self.cpu_registers
+                    .read_register(address, cpu_open_bus)
+                    .unwrap_or_else(|| self.memory.read_cartridge_or_open_bus(full_address))
             }
             0x4200..=0x5FFF => {
                 self.access_master_cycles = FAST_MASTER_CYCLES;
@@ -79,23 +79,23 @@ impl Bus<'_> {
                 // CPU I/O ports (everything except manual joypad ports)
                 // $4220-$42FF and $4380-$5FFF are CPU open bus with Fast memory speed
                 let cpu_open_bus = self.memory.cpu_open_bus();
-                self.cpu_registers.read_register(address, cpu_open_bus).unwrap_or_else(|| {
-                    self.memory.read_cartridge(full_address).unwrap_or(cpu_open_bus)
-                })
+                self.cpu_registers
+                    .read_register(address, cpu_open_bus)
+                    .unwrap_or_else(|| self.memory.read_cartridge_or_open_bus(full_address))
             }
             0x2000..=0x20FF | 0x2181..=0x3FFF => {
                 self.access_master_cycles = FAST_MASTER_CYCLES;
 
                 // Open bus with Fast memory speed
                 // Send to the cartridge first because some cartridges respond to these addresses
-                self.memory.read_cartridge(full_address).unwrap_or(self.memory.cpu_open_bus())
+                self.memory.read_cartridge_or_open_bus(full_address)
             }
             0x6000..=0x7FFF => {
                 self.access_master_cycles = SLOW_MASTER_CYCLES;
 
                 // Open bus with Slow memory speed
                 // Send to the cartridge first because some cartridges respond to these addresses
-                self.memory.read_cartridge(full_address).unwrap_or(self.memory.cpu_open_bus())
+                self.memory.read_cartridge_or_open_bus(full_address)
             }
             _ => panic!("invalid system area address: {full_address:06X}"),
         }
@@ -192,13 +192,13 @@ impl BusInterface for Bus<'_> {
                 self.access_master_cycles = SLOW_MASTER_CYCLES;
 
                 // Cartridge (Memory-1)
-                self.memory.read_cartridge(address).unwrap_or(self.memory.cpu_open_bus())
+                self.memory.read_cartridge_or_open_bus(address)
             }
             (0x80..=0xBF, 0x8000..=0xFFFF) | (0xC0..=0xFF, _) => {
                 self.access_master_cycles = self.cpu_registers.memory_2_speed().master_cycles();
 
                 // Cartridge (Memory-2)
-                self.memory.read_cartridge(address).unwrap_or(self.memory.cpu_open_bus())
+                self.memory.read_cartridge_or_open_bus(address)
             }
             (0x7E..=0x7F, _) => {
                 self.access_master_cycles = SLOW_MASTER_CYCLES;
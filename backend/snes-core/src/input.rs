@@ -1,6 +1,6 @@
 use bincode::{Decode, Encode};
 use jgenesis_common::define_controller_inputs;
-use jgenesis_common::frontend::{DisplayArea, FrameSize, MappableInputs};
+use jgenesis_common::frontend::{AnalogInput, MappableInputs};
 use jgenesis_common::input::Player;
 
 define_controller_inputs! {
@@ -153,30 +153,30 @@ impl MappableInputs<SnesButton> for SnesInputs {
         }
     }
 
-    #[inline]
-    fn handle_mouse_motion(
-        &mut self,
-        x: i32,
-        y: i32,
-        frame_size: FrameSize,
-        display_area: DisplayArea,
-    ) {
-        if let SnesInputDevice::SuperScope(super_scope_state) = &mut self.p2 {
-            super_scope_state.position =
-                jgenesis_common::input::viewport_position_to_frame_position(
-                    x,
-                    y,
-                    frame_size,
-                    display_area,
-                );
-            log::debug!("Set Super Scope position to {:?}", super_scope_state.position);
+    fn button_states(&self, player: Player) -> Vec<(SnesButton, bool)> {
+        match (player, self.p2) {
+            (Player::One, _) => self.p1.button_states(),
+            (Player::Two, SnesInputDevice::Controller(joypad_state)) => {
+                joypad_state.button_states()
+            }
+            (Player::Two, SnesInputDevice::SuperScope(super_scope_state)) => vec![
+                (SnesButton::SuperScopeFire, super_scope_state.fire),
+                (SnesButton::SuperScopeCursor, super_scope_state.cursor),
+                (SnesButton::SuperScopePause, super_scope_state.pause),
+                (SnesButton::SuperScopeTurboToggle, super_scope_state.turbo),
+            ],
         }
     }
 
     #[inline]
-    fn handle_mouse_leave(&mut self) {
+    fn handle_analog_input(&mut self, input: AnalogInput) {
+        if !matches!(input, AnalogInput::MousePosition { .. } | AnalogInput::MouseLeave) {
+            return;
+        }
+
         if let SnesInputDevice::SuperScope(super_scope_state) = &mut self.p2 {
-            super_scope_state.position = None;
+            super_scope_state.position = input.frame_position();
+            log::debug!("Set Super Scope position to {:?}", super_scope_state.position);
         }
     }
 }
@@ -2,7 +2,7 @@ pub mod api;
 mod apu;
 mod audio;
 mod bus;
-pub(crate) mod constants;
+pub mod constants;
 pub mod input;
 mod memory;
 mod ppu;
@@ -6,15 +6,17 @@ mod inputs;
 
 use crate::api::{CoprocessorRoms, SnesLoadResult};
 use crate::input::SnesInputs;
-use crate::memory::cartridge::Cartridge;
+use crate::memory::cartridge::{Cartridge, CartridgeRegion};
 use crate::memory::inputs::InputState;
 use crate::ppu::Ppu;
 use bincode::{Decode, Encode};
-use jgenesis_common::frontend::{SaveWriter, TimingMode};
+use jgenesis_common::bus::OpenBus;
+use jgenesis_common::clockratio::ClockRatio;
+use jgenesis_common::frontend::{GameMetadata, SaveWriter, TimingMode};
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
 use jgenesis_common::num::{GetBit, U16Ext, U24Ext};
 use jgenesis_proc_macros::PartialClone;
 use std::array;
-use std::num::NonZeroU64;
 
 const MAIN_RAM_LEN: usize = 128 * 1024;
 
@@ -45,7 +47,56 @@ pub struct Memory {
     cartridge: Cartridge,
     main_ram: Box<MainRam>,
     wram_port_address: u32,
-    cpu_open_bus: u8,
+    cpu_open_bus: OpenBus,
+    timing_mode: TimingMode,
+}
+
+struct CartridgeChunk {
+    start: u32,
+    end: u32,
+    kind: RegionKind,
+    name: &'static str,
+    offsets: Option<(u32, u32)>,
+}
+
+impl CartridgeChunk {
+    fn new(region: CartridgeRegion, start: u32) -> Option<Self> {
+        let (kind, name, offset) = match region {
+            CartridgeRegion::None => return None,
+            CartridgeRegion::Rom(rom_addr) => (RegionKind::Rom, "Cartridge ROM", Some(rom_addr)),
+            CartridgeRegion::Sram(sram_addr) => {
+                (RegionKind::Ram, "Cartridge SRAM", Some(sram_addr))
+            }
+            CartridgeRegion::Coprocessor => (RegionKind::Registers, "Enhancement chip", None),
+        };
+
+        Some(Self {
+            start,
+            end: start + 0x1FFF,
+            kind,
+            name,
+            offsets: offset.map(|o| (o, o + 0x1FFF)),
+        })
+    }
+
+    fn can_merge(&self, next: &Self) -> bool {
+        self.end + 1 == next.start
+            && self.kind == next.kind
+            && self.name == next.name
+            && match (self.offsets, next.offsets) {
+                (Some((_, end)), Some((start, _))) => end + 1 == start,
+                (None, None) => true,
+                _ => false,
+            }
+    }
+
+    fn add_to(self, map: &mut MemoryMap, board_name: &'static str) {
+        let detail = match self.offsets {
+            Some((start, end)) => format!("${start:06X}-${end:06X}"),
+            None => board_name.into(),
+        };
+        map.add_mapped(self.start, self.end, self.kind, self.name, detail);
+    }
 }
 
 impl Memory {
@@ -54,14 +105,16 @@ impl Memory {
         initial_sram: Option<Vec<u8>>,
         coprocessor_roms: &CoprocessorRoms,
         forced_timing_mode: Option<TimingMode>,
-        gsu_overclock_factor: NonZeroU64,
+        gsu_overclock_factor: ClockRatio,
         save_writer: &mut S,
     ) -> SnesLoadResult<Self> {
+        let timing_mode = forced_timing_mode.unwrap_or_else(|| cartridge::detect_timing_mode(&rom));
+
         let cartridge = Cartridge::create(
             rom,
             initial_sram,
             coprocessor_roms,
-            forced_timing_mode,
+            timing_mode,
             gsu_overclock_factor,
             save_writer,
         )?;
@@ -72,18 +125,89 @@ impl Memory {
             cartridge,
             main_ram: vec![0; MAIN_RAM_LEN].into_boxed_slice().try_into().unwrap(),
             wram_port_address: 0,
-            cpu_open_bus: 0,
+            cpu_open_bus: OpenBus::new(),
+            timing_mode,
         })
     }
 
-    pub fn read_cartridge(&mut self, address: u32) -> Option<u8> {
-        match self.cartridge.read(address) {
-            Some(value) => {
-                self.cpu_open_bus = value;
-                Some(value)
+    /// Describe the system area that is mapped into banks $00-$3F and $80-$BF, along with what the
+    /// cartridge maps into the rest of bank $00.
+    #[must_use]
+    pub fn system_bank_memory_map(&self) -> MemoryMap {
+        let mut map = MemoryMap::new("65816 bank $00", 16);
+        map.add_mapped(0x0000, 0x1FFF, RegionKind::Ram, "WRAM", "Mirror of $7E0000-$7E1FFF")
+            .add(0x2100, 0x213F, RegionKind::Registers, "PPU registers")
+            .add(0x2140, 0x217F, RegionKind::Registers, "APU ports")
+            .add(0x2180, 0x2183, RegionKind::Registers, "WRAM port")
+            .add(0x4016, 0x4017, RegionKind::Registers, "Joypad ports")
+            .add(0x4200, 0x421F, RegionKind::Registers, "CPU registers")
+            .add(0x4300, 0x437F, RegionKind::Registers, "DMA registers");
+        self.describe_cartridge_regions(&mut map, 0x006000, 0x00FFFF);
+        map.fill_unmapped();
+
+        map
+    }
+
+    /// Describe the full 24-bit address space. The system area in banks $00-$3F and $80-$BF is
+    /// described in more detail by [`Self::system_bank_memory_map`].
+    #[must_use]
+    pub fn memory_map(&self) -> MemoryMap {
+        let mut map = MemoryMap::new("65816", 24);
+        for bank in (0x00..=0x3F).chain(0x80..=0xBF) {
+            map.add_mapped(
+                bank << 16,
+                (bank << 16) | 0x5FFF,
+                RegionKind::Registers,
+                "System area",
+                "See bank $00 map",
+            );
+            self.describe_cartridge_regions(&mut map, (bank << 16) | 0x6000, (bank << 16) | 0xFFFF);
+        }
+        self.describe_cartridge_regions(&mut map, 0x400000, 0x7DFFFF);
+        map.add(0x7E0000, 0x7FFFFF, RegionKind::Ram, "WRAM");
+        self.describe_cartridge_regions(&mut map, 0xC00000, 0xFFFFFF);
+        map.fill_unmapped();
+
+        map
+    }
+
+    // Probes every 8KB because no board maps anything smaller than that outside of the system area,
+    // merging adjacent chunks that map to contiguous ROM or SRAM addresses
+    fn describe_cartridge_regions(&self, map: &mut MemoryMap, start: u32, end: u32) {
+        let mut current: Option<CartridgeChunk> = None;
+        for chunk_start in (start..=end).step_by(0x2000) {
+            let chunk = CartridgeChunk::new(self.cartridge.peek_region(chunk_start), chunk_start);
+
+            match (&mut current, chunk) {
+                (Some(current), Some(chunk)) if current.can_merge(&chunk) => {
+                    current.end = chunk.end;
+                    current.offsets = current.offsets.zip(chunk.offsets).map(|(a, b)| (a.0, b.1));
+                }
+                (_, chunk) => {
+                    if let Some(current) = current.take() {
+                        current.add_to(map, self.cartridge.board_name());
+                    }
+                    current = chunk;
+                }
             }
-            None => None,
         }
+
+        if let Some(current) = current {
+            current.add_to(map, self.cartridge.board_name());
+        }
+    }
+
+    pub fn read_cartridge(&mut self, address: u32) -> Option<u8> {
+        let value = self.cartridge.read(address);
+        if let Some(value) = value {
+            self.cpu_open_bus.drive(value);
+        }
+        value
+    }
+
+    pub fn read_cartridge_or_open_bus(&mut self, address: u32) -> u8 {
+        let value = self.cartridge.read(address);
+        self.cpu_open_bus.read(value)
     }
 
     pub fn write_cartridge(&mut self, address: u32, value: u8) {
@@ -94,28 +218,24 @@ impl Memory {
         self.cartridge.irq()
     }
 
-    pub fn cartridge_title(&mut self) -> String {
-        // Cartridge title is always at $00FFC0-$00FFD4 (inclusive)
-        let mut title_bytes = [0; 0xFFD4 - 0xFFC0 + 1];
-        for (i, byte) in title_bytes.iter_mut().enumerate() {
-            *byte = self.read_cartridge(0xFFC0 + i as u32).unwrap_or(0);
+    pub fn game_metadata(&mut self) -> GameMetadata {
+        // The Super Game Boy BIOS header describes the BIOS rather than the Game Boy game
+        if matches!(self.cartridge, Cartridge::SuperGameBoy(..)) {
+            return GameMetadata::default();
+        }
+
+        // Header is always at $00FFB0-$00FFDF (inclusive), including the extended header area
+        let header_start = 0xFFC0 - cartridge::EXTENDED_HEADER_LEN as u32;
+        let mut header = [0; cartridge::FULL_HEADER_LEN];
+        for (i, byte) in header.iter_mut().enumerate() {
+            *byte = self.read_cartridge(header_start + i as u32).unwrap_or(0);
         }
 
-        title_bytes
-            .into_iter()
-            .filter_map(|byte| {
-                (byte.is_ascii_whitespace()
-                    || byte.is_ascii_alphanumeric()
-                    || byte.is_ascii_punctuation())
-                .then_some(byte as char)
-            })
-            .collect()
+        cartridge::parse_header_metadata(&header)
     }
 
-    pub fn cartridge_timing_mode(&mut self) -> TimingMode {
-        // Region byte is always at $00FFD9
-        let region_byte = self.read_cartridge(0xFFD9).unwrap_or(0);
-        cartridge::region_to_timing_mode(region_byte)
+    pub fn timing_mode(&self) -> TimingMode {
+        self.timing_mode
     }
 
     pub fn read_wram(&self, address: u32) -> u8 {
@@ -169,6 +289,18 @@ impl Memory {
         self.cartridge.sram()
     }
 
+    pub fn sram_mut(&mut self) -> Option<&mut [u8]> {
+        self.cartridge.sram_mut()
+    }
+
+    pub fn main_ram(&self) -> &[u8] {
+        self.main_ram.as_ref()
+    }
+
+    pub fn main_ram_mut(&mut self) -> &mut [u8] {
+        self.main_ram.as_mut()
+    }
+
     pub fn write_auxiliary_save_files<S: SaveWriter>(
         &self,
         save_writer: &mut S,
@@ -180,10 +312,14 @@ impl Memory {
         self.cartridge.has_battery()
     }
 
-    pub fn cpu_open_bus(&self) -> u8 {
+    pub fn cpu_open_bus(&self) -> OpenBus {
         self.cpu_open_bus
     }
 
+    pub fn set_cpu_open_bus(&mut self, value: u8) {
+        self.cpu_open_bus.drive(value);
+    }
+
     pub fn tick(&mut self, master_cycles_elapsed: u64) {
         self.cartridge.tick(master_cycles_elapsed);
     }
@@ -203,9 +339,17 @@ impl Memory {
         self.cartridge.notify_dma_end();
     }
 
-    pub fn update_gsu_overclock_factor(&mut self, overclock_factor: NonZeroU64) {
+    pub fn update_gsu_overclock_factor(&mut self, overclock_factor: ClockRatio) {
         self.cartridge.update_gsu_overclock_factor(overclock_factor);
     }
+
+    pub fn mix_cartridge_audio(&mut self, sample_l: f64, sample_r: f64) -> (f64, f64) {
+        self.cartridge.mix_audio_sample(sample_l, sample_r)
+    }
+
+    pub fn update_audio_output_frequency(&mut self, output_frequency: u64) {
+        self.cartridge.update_audio_output_frequency(output_frequency);
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode)]
@@ -378,20 +522,20 @@ impl CpuInternalRegisters {
         }
     }
 
-    pub fn read_register(&mut self, address: u32, cpu_open_bus: u8) -> Option<u8> {
+    pub fn read_register(&mut self, address: u32, cpu_open_bus: OpenBus) -> Option<u8> {
         log::trace!("Read CPU register: {address:06X}");
 
         let value = match address {
             0x4016 => {
                 // JOYA: Manual joypad register A
                 // Bits 7-2 are open bus
-                u8::from(self.input_state.next_manual_p1_bit()) | (cpu_open_bus & 0xFC)
+                cpu_open_bus.merge(self.input_state.next_manual_p1_bit().into(), 0x03)
             }
             0x4017 => {
                 // JOYB: Manual joypad register B
                 // Bits 2-4 always set
                 // Bits 7-5 are open bus
-                0x1C | u8::from(self.input_state.next_manual_p2_bit()) | (cpu_open_bus & 0xE0)
+                cpu_open_bus.merge(0x1C | u8::from(self.input_state.next_manual_p2_bit()), 0x1F)
             }
             0x4210 => {
                 // RDNMI: VBlank NMI flag and CPU version number
@@ -402,7 +546,7 @@ impl CpuInternalRegisters {
 
                 // Hardcode version number to 2
                 // Bits 6-4 are open bus
-                (u8::from(vblank_nmi_flag) << 7) | 0x02 | (cpu_open_bus & 0x70)
+                cpu_open_bus.merge((u8::from(vblank_nmi_flag) << 7) | 0x02, 0x8F)
             }
             0x4211 => {
                 // TIMEUP: H/V IRQ flag
@@ -412,15 +556,15 @@ impl CpuInternalRegisters {
                 self.irq_pending = false;
 
                 // Bits 6-0 are open bus
-                (u8::from(irq_pending) << 7) | (cpu_open_bus & 0x7F)
+                cpu_open_bus.merge(u8::from(irq_pending) << 7, 0x80)
             }
             0x4212 => {
                 // HVBJOY: H/V blank flags and auto joypad in-progress flag
                 // Bits 5-1 are open bus
-                (u8::from(self.vblank_flag) << 7)
+                let value = (u8::from(self.vblank_flag) << 7)
                     | (u8::from(self.hblank_flag) << 6)
-                    | (cpu_open_bus & 0x3E)
-                    | u8::from(self.input_state.auto_joypad_read_in_progress())
+                    | u8::from(self.input_state.auto_joypad_read_in_progress());
+                cpu_open_bus.merge(value, 0xC1)
             }
             0x4213 => {
                 // RDIO: Programmable joypad I/O port (read)
@@ -845,12 +989,8 @@ impl CpuInternalRegisters {
         match self.irq_mode {
             IrqMode::Off => {}
             IrqMode::H => {
-                // Generate H IRQ at H=HTIME+3.5, every line (mclks: 4*HTIME + 14)
-                if check_htime_passed(
-                    prev_scanline_mclk,
-                    ppu.scanline_master_cycles(),
-                    self.irq_htime,
-                ) {
+                // Generate H IRQ at H=HTIME+3.5, every line
+                if check_htime_passed(prev_scanline_mclk, ppu, self.irq_htime) {
                     self.irq_pending = true;
                 }
             }
@@ -863,17 +1003,13 @@ impl CpuInternalRegisters {
                 }
             }
             IrqMode::HV => {
-                // Generate HV IRQ at V=VTIME and H=HTIME+3.5 (mclks: 4*HTIME + 14)
+                // Generate HV IRQ at V=VTIME and H=HTIME+3.5
                 // Unless HTIME=0, then generate at V=VTIME and H=2.5 (same as V IRQ)
                 if ppu.scanline() == self.irq_vtime {
                     let htime_passed = if self.irq_htime == 0 {
                         check_v_irq(ppu.scanline_master_cycles(), master_cycles_elapsed)
                     } else {
-                        check_htime_passed(
-                            prev_scanline_mclk,
-                            ppu.scanline_master_cycles(),
-                            self.irq_htime,
-                        )
+                        check_htime_passed(prev_scanline_mclk, ppu, self.irq_htime)
                     };
 
                     if htime_passed {
@@ -926,10 +1062,11 @@ fn check_v_irq(scanline_mclk: u64, master_cycles_elapsed: u64) -> bool {
         && scanline_mclk.saturating_sub(master_cycles_elapsed) < V_IRQ_H_MCLK
 }
 
-fn check_htime_passed(prev_scanline_mclk: u64, scanline_mclk: u64, htime: u16) -> bool {
-    // H IRQs and HV IRQs should trigger at H=HTIME+3.5, or mclks=4*(HTIME+3.5)
+fn check_htime_passed(prev_scanline_mclk: u64, ppu: &Ppu, htime: u16) -> bool {
+    // H IRQs and HV IRQs should trigger at H=HTIME+3.5, or 14 mclks after dot HTIME starts
     // Allow the +3.5 to go past the end of the scanline, but also take care not to miss low HTIMEs
-    let htime_mclk: u64 = (4 * htime + 14).into();
+    let scanline_mclk = ppu.scanline_master_cycles();
+    let htime_mclk = ppu.h_counter_to_mclk(htime) + 14;
     scanline_mclk >= htime_mclk
         && (prev_scanline_mclk < htime_mclk || scanline_mclk < prev_scanline_mclk)
 }
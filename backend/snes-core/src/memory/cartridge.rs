@@ -3,9 +3,12 @@
 use crate::api::{CoprocessorRoms, SnesLoadError, SnesLoadResult};
 use bincode::{Decode, Encode};
 use crc::Crc;
-use jgenesis_common::frontend::{PartialClone, SaveWriter, TimingMode};
+use jgenesis_common::clockratio::ClockRatio;
+use jgenesis_common::frontend::{GameMetadata, PartialClone, SaveWriter, TimingMode};
+use jgenesis_common::sram;
 use jgenesis_proc_macros::{FakeDecode, FakeEncode};
 use snes_coprocessors::cx4::Cx4;
+use snes_coprocessors::icd2::Icd2;
 use snes_coprocessors::obc1::Obc1;
 use snes_coprocessors::sa1::Sa1;
 use snes_coprocessors::sdd1::Sdd1;
@@ -14,10 +17,10 @@ use snes_coprocessors::srtc::SRtc;
 use snes_coprocessors::superfx::SuperFx;
 use snes_coprocessors::upd77c25::{Upd77c25, Upd77c25Variant};
 use snes_coprocessors::{superfx, upd77c25};
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::mem;
-use std::num::NonZeroU64;
 use std::ops::Deref;
 
 #[derive(Debug, Clone, FakeEncode, FakeDecode)]
@@ -48,6 +51,23 @@ enum CartridgeType {
     Sdd1,
     Spc7110,
     SuperFx,
+    SuperGameBoy,
+}
+
+impl CartridgeType {
+    fn header_addr(self) -> usize {
+        match self {
+            Self::LoRom
+            | Self::Cx4
+            | Self::Obc1
+            | Self::Sa1
+            | Self::Sdd1
+            | Self::SuperFx
+            | Self::SuperGameBoy => LOROM_HEADER_ADDR,
+            Self::HiRom | Self::Spc7110 => HIROM_HEADER_ADDR,
+            Self::ExHiRom => EXHIROM_HEADER_ADDR,
+        }
+    }
 }
 
 impl Display for CartridgeType {
@@ -62,6 +82,7 @@ impl Display for CartridgeType {
             Self::Sdd1 => write!(f, "S-DD1"),
             Self::Spc7110 => write!(f, "SPC7110"),
             Self::SuperFx => write!(f, "Super FX"),
+            Self::SuperGameBoy => write!(f, "Super Game Boy"),
         }
     }
 }
@@ -113,7 +134,15 @@ const LOROM_HEADER_ADDR: usize = 0x007FC0;
 const HIROM_HEADER_ADDR: usize = 0x00FFC0;
 const EXHIROM_HEADER_ADDR: usize = 0x40FFC0;
 
+const HEADER_TITLE_LEN: usize = 21;
 const HEADER_MAP_OFFSET: usize = 0x15;
+const HEADER_REGION_OFFSET: usize = 0x19;
+const HEADER_DEVELOPER_ID_OFFSET: usize = 0x1A;
+
+// Games with developer ID $33 have an extended header in the 16 bytes before the standard header
+pub(crate) const EXTENDED_HEADER_LEN: usize = 0x10;
+pub(crate) const FULL_HEADER_LEN: usize = EXTENDED_HEADER_LEN + 0x20;
+const EXTENDED_HEADER_GAME_CODE_OFFSET: usize = 0x02;
 
 const LOROM_RESET_VECTOR: usize = 0x7FFC;
 const HIROM_RESET_VECTOR: usize = 0xFFFC;
@@ -154,6 +183,7 @@ pub enum Cartridge {
     Sdd1(#[partial_clone(partial)] Sdd1),
     Spc7110(#[partial_clone(partial)] Spc7110),
     SuperFx(#[partial_clone(partial)] SuperFx),
+    SuperGameBoy(#[partial_clone(partial)] Box<Icd2>),
     St01x {
         #[partial_clone(default)]
         rom: Rom,
@@ -163,17 +193,14 @@ pub enum Cartridge {
 
 impl Cartridge {
     pub fn create<S: SaveWriter>(
-        mut rom: Vec<u8>,
+        rom: Vec<u8>,
         initial_sram: Option<Vec<u8>>,
         coprocessor_roms: &CoprocessorRoms,
-        forced_timing_mode: Option<TimingMode>,
-        gsu_overclock_factor: NonZeroU64,
+        timing_mode: TimingMode,
+        gsu_overclock_factor: ClockRatio,
         save_writer: &mut S,
     ) -> SnesLoadResult<Self> {
-        // Older SNES ROM images have an extra 512-byte header; check for that and strip it off
-        if rom.len() & 0x7FFF == 0x0200 {
-            rom = rom[0x200..].to_vec();
-        }
+        let mut rom = ensure_rom_in_expected_format(&rom).into_owned();
 
         let cartridge_type = guess_cartridge_type(&rom).unwrap_or_else(|| {
             log::error!("Unable to confidently determine ROM type; defaulting to LoROM");
@@ -189,20 +216,7 @@ impl Cartridge {
 
         let rom = rom.into_boxed_slice();
 
-        let rom_header_addr = match cartridge_type {
-            CartridgeType::LoRom
-            | CartridgeType::Cx4
-            | CartridgeType::Obc1
-            | CartridgeType::Sa1
-            | CartridgeType::Sdd1
-            | CartridgeType::SuperFx => LOROM_HEADER_ADDR,
-            CartridgeType::HiRom | CartridgeType::Spc7110 => HIROM_HEADER_ADDR,
-            CartridgeType::ExHiRom => EXHIROM_HEADER_ADDR,
-        };
-
-        // Determine NTSC/PAL
-        let region_byte = rom[rom_header_addr + 0x19];
-        let timing_mode = forced_timing_mode.unwrap_or_else(|| region_to_timing_mode(region_byte));
+        let rom_header_addr = cartridge_type.header_addr();
 
         // $FFD8 contains SRAM size as a kilobytes power of 2
         let sram_header_byte = rom[rom_header_addr | 0x0018];
@@ -223,10 +237,10 @@ impl Cartridge {
             1 << (10 + sram_header_byte)
         };
 
-        let sram = match initial_sram {
-            Some(sram) if sram.len() == sram_len => sram.into_boxed_slice(),
-            _ => vec![0; sram_len].into_boxed_slice(),
-        };
+        let sram = initial_sram
+            .and_then(|sram| sram::normalize(sram, sram_len))
+            .unwrap_or_else(|| vec![0; sram_len])
+            .into_boxed_slice();
 
         log::info!("Using mapper {cartridge_type} with SRAM size {sram_len}");
 
@@ -295,6 +309,9 @@ impl Cartridge {
             CartridgeType::Sdd1 => Self::Sdd1(Sdd1::new(rom, sram)),
             CartridgeType::Spc7110 => Self::Spc7110(Spc7110::new(rom, sram, save_writer)),
             CartridgeType::SuperFx => Self::SuperFx(SuperFx::new(rom, sram, gsu_overclock_factor)),
+            CartridgeType::SuperGameBoy => {
+                new_super_game_boy_cartridge(rom, coprocessor_roms, timing_mode, save_writer)?
+            }
         })
     }
 
@@ -332,6 +349,7 @@ impl Cartridge {
             Self::Sdd1(sdd1) => return sdd1.read(address),
             Self::Spc7110(spc7110) => return spc7110.read(address),
             Self::SuperFx(sfx) => return sfx.read(address),
+            Self::SuperGameBoy(icd2) => return icd2.read(address),
             Self::St01x { rom, upd77c25 } => {
                 return match (bank, offset) {
                     (0x60..=0x67, 0x0000) => Some(upd77c25.read_data()),
@@ -425,6 +443,9 @@ impl Cartridge {
             Self::SuperFx(sfx) => {
                 sfx.write(address, value);
             }
+            Self::SuperGameBoy(icd2) => {
+                icd2.write(address, value);
+            }
             Self::St01x { upd77c25, .. } => match (bank, offset) {
                 (0x60..=0x67, 0x0000) => upd77c25.write_data(value),
                 (0x68..=0x6F, 0x0000..=0x0FFF) => {
@@ -436,6 +457,71 @@ impl Cartridge {
         }
     }
 
+    /// The cartridge's board type, including the enhancement chip if there is one.
+    pub fn board_name(&self) -> &'static str {
+        match self {
+            Self::LoRom { .. } => "LoROM",
+            Self::HiRom { .. } => "HiROM",
+            Self::ExHiRom { .. } => "ExHiROM",
+            Self::Cx4(..) => "CX4",
+            Self::DspLoRom { .. } => "LoROM + DSP",
+            Self::DspHiRom { .. } => "HiROM + DSP",
+            Self::Obc1(..) => "OBC1",
+            Self::Sa1(..) => "SA-1",
+            Self::Sdd1(..) => "S-DD1",
+            Self::Spc7110(..) => "SPC7110",
+            Self::SuperFx(..) => "Super FX",
+            Self::SuperGameBoy(..) => "Super Game Boy",
+            Self::St01x { .. } => "ST010 / ST011",
+        }
+    }
+
+    /// Determine what the cartridge maps to the given address without any side effects.
+    ///
+    /// Boards with enhancement chips that do their own address decoding only report which address
+    /// ranges the chip responds to.
+    pub(crate) fn peek_region(&self, address: u32) -> CartridgeRegion {
+        let bank = (address >> 16) & 0xFF;
+        let offset = address & 0xFFFF;
+        match self {
+            Self::LoRom { rom, sram } => {
+                lorom_map_address(address, rom.len() as u32, sram.len() as u32).into()
+            }
+            Self::HiRom { rom, sram } => {
+                hirom_map_address(address, rom.len() as u32, sram.len() as u32).into()
+            }
+            Self::ExHiRom { rom, sram, .. } => {
+                exhirom_map_address(address, rom.len() as u32, sram.len() as u32).into()
+            }
+            Self::DspLoRom { rom, sram, .. } => match (bank, offset) {
+                (0x30..=0x3F | 0xC0..=0xCF, 0x8000..=0xFFFF) => CartridgeRegion::Coprocessor,
+                _ => lorom_map_address(address, rom.len() as u32, sram.len() as u32).into(),
+            },
+            Self::DspHiRom { rom, sram, .. } => match (bank, offset) {
+                (0x00..=0x0F | 0x80..=0x8F, 0x6000..=0x7FFF) => CartridgeRegion::Coprocessor,
+                _ => hirom_map_address(address, rom.len() as u32, sram.len() as u32).into(),
+            },
+            Self::St01x { rom, .. } => match (bank, offset) {
+                (0x60..=0x67, 0x0000..=0x0001) | (0x68..=0x6F, 0x0000..=0x0FFF) => {
+                    CartridgeRegion::Coprocessor
+                }
+                _ => lorom_map_address(address, rom.len() as u32, 0).into(),
+            },
+            Self::Cx4(..)
+            | Self::Obc1(..)
+            | Self::Sa1(..)
+            | Self::Sdd1(..)
+            | Self::Spc7110(..)
+            | Self::SuperFx(..)
+            | Self::SuperGameBoy(..) => match (bank, offset) {
+                (0x00..=0x3F | 0x80..=0xBF, 0x6000..=0xFFFF) | (0x40..=0x7D | 0xC0..=0xFF, _) => {
+                    CartridgeRegion::Coprocessor
+                }
+                _ => CartridgeRegion::None,
+            },
+        }
+    }
+
     pub fn irq(&self) -> bool {
         match self {
             Self::Sa1(sa1) => sa1.snes_irq(),
@@ -458,10 +544,17 @@ impl Cartridge {
             Self::Sdd1(sdd1) => sdd1.take_rom(),
             Self::Spc7110(spc7110) => spc7110.take_rom(),
             Self::SuperFx(sfx) => sfx.take_rom(),
+            Self::SuperGameBoy(icd2) => icd2.take_rom(),
         }
     }
 
     pub fn take_rom_from(&mut self, other: &mut Self) {
+        if let (Self::SuperGameBoy(icd2), Self::SuperGameBoy(other_icd2)) =
+            (&mut *self, &mut *other)
+        {
+            icd2.take_gb_rom_from(other_icd2);
+        }
+
         let other_rom = other.take_rom();
 
         match self {
@@ -491,6 +584,9 @@ impl Cartridge {
             Self::SuperFx(sfx) => {
                 sfx.set_rom(other_rom);
             }
+            Self::SuperGameBoy(icd2) => {
+                icd2.set_rom(other_rom);
+            }
         }
     }
 
@@ -505,6 +601,7 @@ impl Cartridge {
             Self::Sa1(sa1) => sa1.has_battery(),
             Self::Sdd1(sdd1) => sdd1.has_battery(),
             Self::SuperFx(sfx) => sfx.has_battery(),
+            Self::SuperGameBoy(icd2) => icd2.has_battery(),
         }
     }
 
@@ -530,10 +627,38 @@ impl Cartridge {
             Self::Sdd1(sdd1) => sdd1.sram(),
             Self::Spc7110(spc7110) => Some(spc7110.sram()),
             Self::SuperFx(sfx) => Some(sfx.sram()),
+            Self::SuperGameBoy(icd2) => icd2.sram(),
             Self::St01x { upd77c25, .. } => Some(upd77c25.sram()),
         }
     }
 
+    pub fn sram_mut(&mut self) -> Option<&mut [u8]> {
+        match self {
+            Self::LoRom { sram, .. }
+            | Self::HiRom { sram, .. }
+            | Self::ExHiRom { sram, .. }
+            | Self::DspLoRom { sram, .. }
+            | Self::DspHiRom { sram, .. }
+                if !sram.is_empty() =>
+            {
+                Some(sram)
+            }
+            Self::LoRom { .. }
+            | Self::HiRom { .. }
+            | Self::ExHiRom { .. }
+            | Self::DspLoRom { .. }
+            | Self::DspHiRom { .. }
+            | Self::Cx4 { .. } => None,
+            Self::Obc1(obc1) => Some(obc1.sram_mut()),
+            Self::Sa1(sa1) => sa1.sram_mut(),
+            Self::Sdd1(sdd1) => sdd1.sram_mut(),
+            Self::Spc7110(spc7110) => Some(spc7110.sram_mut()),
+            Self::SuperFx(sfx) => Some(sfx.sram_mut()),
+            Self::SuperGameBoy(icd2) => icd2.sram_mut(),
+            Self::St01x { upd77c25, .. } => Some(upd77c25.sram_mut()),
+        }
+    }
+
     pub fn write_auxiliary_save_files<S: SaveWriter>(
         &self,
         save_writer: &mut S,
@@ -547,6 +672,9 @@ impl Cartridge {
                     save_writer.persist_serialized("rtc", rtc)?;
                 }
             }
+            Self::SuperGameBoy(icd2) => {
+                icd2.save_rtc_state(save_writer)?;
+            }
             _ => {}
         }
 
@@ -566,6 +694,9 @@ impl Cartridge {
             Self::SuperFx(sfx) => {
                 sfx.tick(master_cycles_elapsed);
             }
+            Self::SuperGameBoy(icd2) => {
+                icd2.tick(master_cycles_elapsed);
+            }
             _ => {}
         }
     }
@@ -589,6 +720,9 @@ impl Cartridge {
             Self::SuperFx(sfx) => {
                 sfx.reset();
             }
+            Self::SuperGameBoy(icd2) => {
+                icd2.reset();
+            }
             _ => {}
         }
     }
@@ -609,11 +743,41 @@ impl Cartridge {
         }
     }
 
-    pub fn update_gsu_overclock_factor(&mut self, overclock_factor: NonZeroU64) {
+    pub fn update_gsu_overclock_factor(&mut self, overclock_factor: ClockRatio) {
         if let Self::SuperFx(sfx) = self {
             sfx.update_gsu_overclock_factor(overclock_factor);
         }
     }
+
+    // The Super Game Boy mixes Game Boy audio into the SNES audio output
+    pub fn mix_audio_sample(&mut self, sample_l: f64, sample_r: f64) -> (f64, f64) {
+        match self {
+            Self::SuperGameBoy(icd2) => icd2.mix_audio_sample(sample_l, sample_r),
+            _ => (sample_l, sample_r),
+        }
+    }
+
+    pub fn update_audio_output_frequency(&mut self, output_frequency: u64) {
+        if let Self::SuperGameBoy(icd2) = self {
+            icd2.update_audio_output_frequency(output_frequency);
+        }
+    }
+}
+
+fn new_super_game_boy_cartridge<S: SaveWriter>(
+    rom: Box<[u8]>,
+    coprocessor_roms: &CoprocessorRoms,
+    timing_mode: TimingMode,
+    save_writer: &mut S,
+) -> SnesLoadResult<Cartridge> {
+    let gb_rom_fn =
+        coprocessor_roms.sgb_cartridge.as_ref().ok_or(SnesLoadError::MissingSgbCartridge)?;
+    let gb_rom =
+        gb_rom_fn().map_err(|(source, path)| SnesLoadError::CoprocessorRomLoad { source, path })?;
+
+    let icd2 = Icd2::new(rom, gb_rom, timing_mode, save_writer)?;
+
+    Ok(Cartridge::SuperGameBoy(Box::new(icd2)))
 }
 
 fn new_exhirom_cartridge<S: SaveWriter>(
@@ -634,7 +798,168 @@ fn new_exhirom_cartridge<S: SaveWriter>(
     Cartridge::ExHiRom { rom: Rom(rom), sram: initial_sram, srtc }
 }
 
-pub fn region_to_timing_mode(region_byte: u8) -> TimingMode {
+// Older SNES ROM images have an extra 512-byte header
+fn has_copier_header(rom_len: usize) -> bool {
+    rom_len & 0x7FFF == 0x0200
+}
+
+// Remove a copier header and undo HiROM interleaving if present. The ROM file itself is never
+// modified
+fn ensure_rom_in_expected_format(rom: &[u8]) -> Cow<'_, [u8]> {
+    let rom = if has_copier_header(rom.len()) { &rom[0x200..] } else { rom };
+
+    if is_interleaved_hirom(rom, rom.len()) {
+        log::info!("ROM image appears to be an interleaved HiROM image; deinterleaving it");
+        Cow::Owned(deinterleave_hirom(rom))
+    } else {
+        Cow::Borrowed(rom)
+    }
+}
+
+// Some older HiROM images, usually dumped by Game Doctor copiers, are interleaved: the upper 32KB
+// of each 64KB bank is stored in the first half of the file and the lower 32KB of each bank is
+// stored in the second half. This moves the HiROM header to where a LoROM header would normally be.
+// `rom` only needs to contain the first 64KB of the image
+fn is_interleaved_hirom(rom: &[u8], rom_len: usize) -> bool {
+    if rom_len < 0x20000 || rom_len & 0xFFFF != 0 {
+        return false;
+    }
+
+    let is_hirom_map_byte = |map_byte: u8| map_byte == 0x21 || map_byte == 0x31;
+    if is_hirom_map_byte(rom[HIROM_HEADER_ADDR + HEADER_MAP_OFFSET])
+        || !is_hirom_map_byte(rom[LOROM_HEADER_ADDR + HEADER_MAP_OFFSET])
+    {
+        return false;
+    }
+
+    // Require a consistent checksum/complement pair so that a LoROM game with garbage in its map
+    // byte is not mistaken for an interleaved HiROM game
+    let checksum_bytes = &rom[LOROM_HEADER_ADDR + 0x1C..LOROM_HEADER_ADDR + 0x20];
+    let complement = u16::from_le_bytes([checksum_bytes[0], checksum_bytes[1]]);
+    let checksum = u16::from_le_bytes([checksum_bytes[2], checksum_bytes[3]]);
+    checksum ^ complement == 0xFFFF
+}
+
+fn deinterleave_hirom(rom: &[u8]) -> Vec<u8> {
+    let (upper_halves, lower_halves) = rom.split_at(rom.len() / 2);
+
+    let mut deinterleaved = Vec::with_capacity(rom.len());
+    for (lower, upper) in lower_halves.chunks_exact(0x8000).zip(upper_halves.chunks_exact(0x8000)) {
+        deinterleaved.extend_from_slice(lower);
+        deinterleaved.extend_from_slice(upper);
+    }
+
+    deinterleaved
+}
+
+/// Determine NTSC/PAL from the region byte in the cartridge header, using the same header location
+/// that the mapper detection picks.
+pub fn detect_timing_mode(rom: &[u8]) -> TimingMode {
+    let rom = ensure_rom_in_expected_format(rom);
+
+    let Some(cartridge_type) = guess_cartridge_type(&rom) else { return TimingMode::Ntsc };
+    match rom.get(cartridge_type.header_addr() + HEADER_REGION_OFFSET) {
+        Some(&region_byte) => region_to_timing_mode(region_byte),
+        None => TimingMode::Ntsc,
+    }
+}
+
+/// Parse game metadata from the cartridge header, using the same header location that the mapper
+/// detection picks.
+pub fn game_metadata_from_rom(rom: &[u8]) -> GameMetadata {
+    let rom = ensure_rom_in_expected_format(rom);
+
+    let Some(cartridge_type) = guess_cartridge_type(&rom) else { return GameMetadata::default() };
+    let header_start = cartridge_type.header_addr() - EXTENDED_HEADER_LEN;
+    match rom.get(header_start..header_start + FULL_HEADER_LEN) {
+        Some(header) => parse_header_metadata(header.try_into().unwrap()),
+        None => GameMetadata::default(),
+    }
+}
+
+/// Number of bytes after any copier header that [`game_metadata_from_rom_prefix`] looks at, which
+/// covers the `LoROM` and `HiROM` header locations and reset vectors
+pub const HEADER_PREFIX_LEN: usize = 0x10000;
+
+/// Parse game metadata from the start of a ROM image without reading the rest of it. `rom_prefix`
+/// should contain the first [`HEADER_PREFIX_LEN`] bytes of the image plus the 512-byte copier
+/// header if there is one, and `rom_len` is the size of the full image.
+///
+/// An `ExHiROM` header is past the prefix, so images large enough to be `ExHiROM` return no metadata
+/// rather than metadata parsed from the wrong header.
+pub fn game_metadata_from_rom_prefix(rom_prefix: &[u8], rom_len: usize) -> GameMetadata {
+    let (rom_prefix, rom_len) = if has_copier_header(rom_len) {
+        (rom_prefix.get(0x200..).unwrap_or(&[]), rom_len - 0x200)
+    } else {
+        (rom_prefix, rom_len)
+    };
+
+    if rom_len >= 0x410000 || rom_prefix.len() < rom_len.min(HEADER_PREFIX_LEN) {
+        return GameMetadata::default();
+    }
+    let rom_prefix = &rom_prefix[..rom_len.min(HEADER_PREFIX_LEN)];
+
+    let header_addr = if is_interleaved_hirom(rom_prefix, rom_len) {
+        // Deinterleaving would move this to the HiROM header location
+        LOROM_HEADER_ADDR
+    } else {
+        let Some(cartridge_type) = guess_cartridge_type(rom_prefix) else {
+            return GameMetadata::default();
+        };
+        cartridge_type.header_addr()
+    };
+
+    let header_start = header_addr - EXTENDED_HEADER_LEN;
+    parse_header_metadata(
+        rom_prefix[header_start..header_start + FULL_HEADER_LEN].try_into().unwrap(),
+    )
+}
+
+/// Parse game metadata from the header bytes at $FFB0-$FFDF, including the extended header area
+pub(crate) fn parse_header_metadata(header: &[u8; FULL_HEADER_LEN]) -> GameMetadata {
+    let (extended_header, header) = header.split_at(EXTENDED_HEADER_LEN);
+
+    let title = GameMetadata::parse_header_string(&header[..HEADER_TITLE_LEN]);
+
+    // Only later games have a game code, and only in the extended header
+    let serial = (header[HEADER_DEVELOPER_ID_OFFSET] == 0x33)
+        .then(|| {
+            let game_code = &extended_header[EXTENDED_HEADER_GAME_CODE_OFFSET..][..4];
+            GameMetadata::parse_header_string(game_code)
+        })
+        .flatten();
+
+    let region = region_name(header[HEADER_REGION_OFFSET]).map(String::from);
+
+    GameMetadata { title, serial, region, quirks: vec![] }
+}
+
+fn region_name(region_byte: u8) -> Option<&'static str> {
+    let name = match region_byte {
+        0x00 => "Japan",
+        0x01 => "North America",
+        0x02 => "Europe",
+        0x03 => "Scandinavia",
+        0x04 => "Finland",
+        0x05 => "Denmark",
+        0x06 => "France",
+        0x07 => "Netherlands",
+        0x08 => "Spain",
+        0x09 => "Germany",
+        0x0A => "Italy",
+        0x0B => "China",
+        0x0C => "Indonesia",
+        0x0D => "South Korea",
+        0x0E => "International",
+        0x0F => "Canada",
+        0x10 => "Brazil",
+        0x11 => "Australia",
+        _ => return None,
+    };
+    Some(name)
+}
+
+fn region_to_timing_mode(region_byte: u8) -> TimingMode {
     match region_byte {
         // Japan / USA / South Korea / Canada / Brazil
         0x00 | 0x01 | 0x0D | 0x0F | 0x10 => TimingMode::Ntsc,
@@ -758,6 +1083,12 @@ fn check_for_lorom_coprocessor(rom: &[u8]) -> Option<CartridgeType> {
         return Some(CartridgeType::SuperFx);
     }
 
+    // Check for Super Game Boy BIOS
+    // Identified by map == $20 and chipset == $E3 in the LoROM header area
+    if lorom_map_byte == 0x20 && rom[LOROM_HEADER_ADDR + 0x16] == 0xE3 {
+        return Some(CartridgeType::SuperGameBoy);
+    }
+
     // Check for OBC1
     // Identified by chipset $25 in the LoROM header area
     if rom[LOROM_HEADER_ADDR + 0x16] == 0x25 {
@@ -799,6 +1130,26 @@ fn seems_like_valid_reset_vector(rom: &[u8], vector: u16) -> bool {
     vector < rom.len() && (rom[vector] == CLC_OPCODE || rom[vector] == SEI_OPCODE)
 }
 
+/// What a cartridge maps to an address, as shown in the memory map viewer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CartridgeRegion {
+    None,
+    Rom(u32),
+    Sram(u32),
+    /// Decoded by the cartridge's enhancement chip, which may map ROM, RAM, or registers here
+    Coprocessor,
+}
+
+impl From<CartridgeAddress> for CartridgeRegion {
+    fn from(value: CartridgeAddress) -> Self {
+        match value {
+            CartridgeAddress::None => Self::None,
+            CartridgeAddress::Rom(rom_addr) => Self::Rom(rom_addr),
+            CartridgeAddress::Sram(sram_addr) => Self::Sram(sram_addr),
+        }
+    }
+}
+
 pub(crate) enum CartridgeAddress {
     None,
     Rom(u32),
@@ -965,4 +1316,96 @@ mod tests {
         mirror_to_next_power_of_two(&mut rom);
         assert_eq!(rom, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 10, 8, 9, 10, 10]);
     }
+
+    #[test]
+    fn timing_mode_from_header() {
+        let mut rom = vec![0; 0x8000];
+        rom[LOROM_HEADER_ADDR + HEADER_MAP_OFFSET] = 0x20;
+
+        rom[LOROM_HEADER_ADDR + HEADER_REGION_OFFSET] = 0x01;
+        assert_eq!(detect_timing_mode(&rom), TimingMode::Ntsc);
+
+        rom[LOROM_HEADER_ADDR + HEADER_REGION_OFFSET] = 0x02;
+        assert_eq!(detect_timing_mode(&rom), TimingMode::Pal);
+
+        let mut copier_rom = vec![0; 0x200];
+        copier_rom.extend_from_slice(&rom);
+        assert_eq!(detect_timing_mode(&copier_rom), TimingMode::Pal);
+    }
+
+    #[test]
+    fn metadata_from_header() {
+        let mut rom = vec![0; 0x10000];
+        let header = &mut rom[HIROM_HEADER_ADDR..];
+        header[..HEADER_TITLE_LEN].copy_from_slice(b"SUPER MARIOWORLD     ");
+        header[HEADER_MAP_OFFSET] = 0x21;
+        header[HEADER_REGION_OFFSET] = 0x01;
+        rom[HIROM_RESET_VECTOR..HIROM_RESET_VECTOR + 2].copy_from_slice(&[0x00, 0x80]);
+
+        let metadata = game_metadata_from_rom(&rom);
+        assert_eq!(metadata.title.as_deref(), Some("SUPER MARIOWORLD"));
+        assert_eq!(metadata.serial, None);
+        assert_eq!(metadata.region.as_deref(), Some("North America"));
+
+        rom[HIROM_HEADER_ADDR + HEADER_DEVELOPER_ID_OFFSET] = 0x33;
+        rom[HIROM_HEADER_ADDR - EXTENDED_HEADER_LEN + EXTENDED_HEADER_GAME_CODE_OFFSET..][..4]
+            .copy_from_slice(b"AMWE");
+        assert_eq!(game_metadata_from_rom(&rom).serial.as_deref(), Some("AMWE"));
+
+        let mut copier_rom = vec![0; 0x200];
+        copier_rom.extend_from_slice(&rom);
+        copier_rom.resize(0x200 + 0x80000, 0);
+        let metadata = game_metadata_from_rom_prefix(
+            &copier_rom[..0x200 + HEADER_PREFIX_LEN],
+            copier_rom.len(),
+        );
+        assert_eq!(metadata.title.as_deref(), Some("SUPER MARIOWORLD"));
+        assert_eq!(metadata.serial.as_deref(), Some("AMWE"));
+
+        // Could be ExHiROM
+        assert_eq!(
+            game_metadata_from_rom_prefix(&rom, 0x410000 + HEADER_PREFIX_LEN),
+            GameMetadata::default()
+        );
+    }
+
+    #[test]
+    fn deinterleave_hirom_header() {
+        let mut rom = vec![0; 0x20000];
+        rom[HIROM_HEADER_ADDR + HEADER_MAP_OFFSET] = 0x21;
+        rom[HIROM_HEADER_ADDR + 0x1C..HIROM_HEADER_ADDR + 0x20]
+            .copy_from_slice(&[0x34, 0x12, 0xCB, 0xED]);
+        rom[HIROM_HEADER_ADDR + HEADER_REGION_OFFSET] = 0x02;
+
+        // Upper halves of each bank followed by lower halves
+        let mut interleaved = Vec::new();
+        interleaved.extend_from_slice(&rom[0x08000..0x10000]);
+        interleaved.extend_from_slice(&rom[0x18000..0x20000]);
+        interleaved.extend_from_slice(&rom[0x00000..0x08000]);
+        interleaved.extend_from_slice(&rom[0x10000..0x18000]);
+
+        assert!(!is_interleaved_hirom(&rom, rom.len()));
+        assert!(is_interleaved_hirom(&interleaved, interleaved.len()));
+        assert_eq!(ensure_rom_in_expected_format(&interleaved).as_ref(), rom.as_slice());
+        assert_eq!(detect_timing_mode(&interleaved), TimingMode::Pal);
+        assert_eq!(
+            game_metadata_from_rom_prefix(&interleaved[..HEADER_PREFIX_LEN], interleaved.len())
+                .region
+                .as_deref(),
+            Some("Europe")
+        );
+    }
+
+    #[test]
+    fn peek_hirom_regions() {
+        let cartridge = Cartridge::HiRom {
+            rom: Rom(vec![0; 0x100000].into_boxed_slice()),
+            sram: vec![0; 0x2000].into_boxed_slice(),
+        };
+
+        assert_eq!(cartridge.peek_region(0x008000), CartridgeRegion::Rom(0x008000));
+        assert_eq!(cartridge.peek_region(0xC12345), CartridgeRegion::Rom(0x012345));
+        assert_eq!(cartridge.peek_region(0x206000), CartridgeRegion::Sram(0x0000));
+        assert_eq!(cartridge.peek_region(0x006000), CartridgeRegion::None);
+    }
 }
@@ -9,6 +9,12 @@ use wdc65816_emu::traits::BusInterface;
 // Bus B (8-bit) is mapped to $2100-$21FF in Bus A (24-bit)
 const BUS_B_BASE_ADDRESS: u32 = 0x002100;
 
+// HDMA transfers start at H=276 on every active line
+const HDMA_TRANSFER_MCLK: u64 = 4 * 276;
+
+// HDMA init on line 0 happens at master cycle 12 plus a 1-8 cycle delay to align to the DMA clock
+const HDMA_INIT_BASE_MCLK: u64 = 12;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
 enum GpDmaState {
     Idle,
@@ -46,6 +52,7 @@ pub struct DmaUnit {
     hdma_state: HDmaState,
     hdma_do_transfer: [bool; 8],
     hdma_prev_scanline_mclk: u64,
+    hdma_init_mclk: u64,
 }
 
 impl DmaUnit {
@@ -55,13 +62,14 @@ impl DmaUnit {
             hdma_state: HDmaState::default(),
             hdma_do_transfer: [false; 8],
             hdma_prev_scanline_mclk: 0,
+            hdma_init_mclk: HDMA_INIT_BASE_MCLK + 8,
         }
     }
 
     #[must_use]
     pub fn tick(&mut self, bus: &mut Bus<'_>, total_master_cycles: u64) -> DmaStatus {
         // HDMA takes priority over GPDMA
-        let hdma_status = self.tick_hdma(bus);
+        let hdma_status = self.tick_hdma(bus, total_master_cycles);
         self.hdma_prev_scanline_mclk = bus.ppu.scanline_master_cycles();
 
         if let Some(status) = hdma_status {
@@ -71,16 +79,25 @@ impl DmaUnit {
         self.tick_gpdma(bus, total_master_cycles)
     }
 
-    fn tick_hdma(&mut self, bus: &mut Bus<'_>) -> Option<DmaStatus> {
+    fn tick_hdma(&mut self, bus: &mut Bus<'_>, total_master_cycles: u64) -> Option<DmaStatus> {
         let scanline_mclk = bus.ppu.scanline_master_cycles();
+        let new_line = self.hdma_prev_scanline_mclk > scanline_mclk;
+
+        if new_line && bus.ppu.scanline() == 0 {
+            // Latch the init position once per frame, relative to the master cycle count at the
+            // start of the line
+            let line_start_mclk = total_master_cycles.wrapping_sub(scanline_mclk);
+            self.hdma_init_mclk = HDMA_INIT_BASE_MCLK + 8 - (line_start_mclk & 7);
+        }
 
         let any_channels_active =
             bus.cpu_registers.active_hdma_channels.iter().copied().any(|active| active);
 
-        // Check if HDMA registers need to be reloaded (V=0 + H=6)
+        // Check if HDMA registers need to be reloaded (V=0, H~5)
+        let init_mclk = self.hdma_init_mclk;
         if bus.ppu.scanline() == 0
-            && scanline_mclk >= 24
-            && (self.hdma_prev_scanline_mclk < 24 || self.hdma_prev_scanline_mclk > scanline_mclk)
+            && scanline_mclk >= init_mclk
+            && (self.hdma_prev_scanline_mclk < init_mclk || new_line)
         {
             return if any_channels_active {
                 let master_cycles_elapsed = self.hdma_reload(bus);
@@ -100,10 +117,10 @@ impl DmaUnit {
 
         match self.hdma_state {
             HDmaState::Idle => {
-                // Check if HDMA transfer should start (V=0-224, H=278)
+                // Check if HDMA transfer should start (V=0-224, H=276)
                 if !bus.ppu.vblank_flag()
-                    && scanline_mclk >= 4 * 278
-                    && self.hdma_prev_scanline_mclk < 4 * 278
+                    && scanline_mclk >= HDMA_TRANSFER_MCLK
+                    && self.hdma_prev_scanline_mclk < HDMA_TRANSFER_MCLK
                 {
                     // Find the first channel that is active *and* has a non-zero line counter
                     let Some((first_active_channel, _)) =
@@ -441,16 +458,7 @@ fn hdma_copy_byte(
 
     let bus_b_full_address = BUS_B_BASE_ADDRESS | u32::from(bus_b_address);
 
-    match direction {
-        DmaDirection::AtoB => {
-            let byte = dma_read_bus_a(bus, bus_a_full_address);
-            bus.apply_write(bus_b_full_address, byte);
-        }
-        DmaDirection::BtoA => {
-            let byte = bus.read(bus_b_full_address);
-            dma_write_bus_a(bus, bus_a_full_address, byte);
-        }
-    }
+    dma_transfer_byte(bus, direction, bus_a_full_address, bus_b_full_address);
 }
 
 fn compute_gpdma_initial_wait_cycles(total_master_cycles: u64) -> u64 {
@@ -461,6 +469,30 @@ fn compute_gpdma_initial_wait_cycles(total_master_cycles: u64) -> u64 {
     8 + 8 + alignment_cycles
 }
 
+fn dma_transfer_byte(
+    bus: &mut Bus<'_>,
+    direction: DmaDirection,
+    bus_a_address: u32,
+    bus_b_address: u32,
+) {
+    let byte = match direction {
+        DmaDirection::AtoB => {
+            let byte = dma_read_bus_a(bus, bus_a_address);
+            bus.apply_write(bus_b_address, byte);
+            byte
+        }
+        DmaDirection::BtoA => {
+            let byte = bus.read(bus_b_address);
+            dma_write_bus_a(bus, bus_a_address, byte);
+            byte
+        }
+    };
+
+    // The transferred byte stays on the data bus, so any open bus read later in the same transfer
+    // (e.g. DMA reading from $43xx) or by the CPU after DMA finishes sees this value
+    bus.memory.set_cpu_open_bus(byte);
+}
+
 fn dma_read_bus_a(bus: &mut Bus<'_>, bus_a_address: u32) -> u8 {
     let bank = (bus_a_address >> 16) & 0xFF;
     let offset = bus_a_address & 0xFFFF;
@@ -468,7 +500,9 @@ fn dma_read_bus_a(bus: &mut Bus<'_>, bus_a_address: u32) -> u8 {
         // DMA cannot read bus B or DMA registers through bus A
         // Krusty's Super Fun House depends on this or else it will write incorrect BG color
         // palettes to CGRAM
-        (0x00..=0x3F | 0x80..=0xBF, 0x2100..=0x21FF | 0x4300..=0x43FF) => bus.memory.cpu_open_bus(),
+        (0x00..=0x3F | 0x80..=0xBF, 0x2100..=0x21FF | 0x4300..=0x43FF) => {
+            bus.memory.cpu_open_bus().value()
+        }
         _ => bus.read(bus_a_address),
     }
 }
@@ -517,16 +551,8 @@ fn gpdma_copy_byte(bus: &mut Bus<'_>, channel: u8, bytes_copied: u16) -> GpDmaSt
     let bus_b_address = BUS_B_BASE_ADDRESS
         | u32::from(bus.cpu_registers.dma_bus_b_address[channel].wrapping_add(bus_b_adjustment));
 
-    match bus.cpu_registers.dma_direction[channel] {
-        DmaDirection::AtoB => {
-            let byte = dma_read_bus_a(bus, bus_a_full_address);
-            bus.apply_write(bus_b_address, byte);
-        }
-        DmaDirection::BtoA => {
-            let byte = bus.read(bus_b_address);
-            dma_write_bus_a(bus, bus_a_full_address, byte);
-        }
-    }
+    let direction = bus.cpu_registers.dma_direction[channel];
+    dma_transfer_byte(bus, direction, bus_a_full_address, bus_b_address);
 
     match bus.cpu_registers.dma_increment_mode[channel] {
         DmaIncrementMode::Fixed0 | DmaIncrementMode::Fixed1 => {}
@@ -594,3 +620,199 @@ fn gpdma_start_log(bus: &Bus<'_>) {
         log::trace!("  Channel {i} increment mode: {:?}", bus.cpu_registers.dma_increment_mode[i]);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api;
+    use crate::api::CoprocessorRoms;
+    use crate::apu::Apu;
+    use crate::memory::{CpuInternalRegisters, Memory};
+    use crate::ppu::Ppu;
+    use jgenesis_common::frontend::{NullSaveWriter, TimingMode};
+
+    struct TestSystem {
+        memory: Memory,
+        cpu_registers: CpuInternalRegisters,
+        ppu: Ppu,
+        apu: Apu,
+        dma_unit: DmaUnit,
+        total_master_cycles: u64,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct DmaEvent {
+        scanline: u16,
+        scanline_mclk: u64,
+        total_master_cycles: u64,
+        master_cycles_elapsed: u64,
+    }
+
+    impl TestSystem {
+        fn new() -> Self {
+            let config = api::test_config();
+            let memory = Memory::create(
+                vec![0; 0x8000],
+                None,
+                &CoprocessorRoms::default(),
+                Some(TimingMode::Ntsc),
+                config.gsu_overclock_factor,
+                &mut NullSaveWriter,
+            )
+            .unwrap();
+
+            Self {
+                memory,
+                cpu_registers: CpuInternalRegisters::new(),
+                ppu: Ppu::new(TimingMode::Ntsc, config),
+                apu: Apu::new(TimingMode::Ntsc, config, None),
+                dma_unit: DmaUnit::new(),
+                total_master_cycles: 0,
+            }
+        }
+
+        fn tick_dma(&mut self) -> DmaStatus {
+            let mut bus = Bus {
+                memory: &mut self.memory,
+                cpu_registers: &mut self.cpu_registers,
+                ppu: &mut self.ppu,
+                apu: &mut self.apu,
+                latched_interrupts: None,
+                access_master_cycles: 0,
+                pending_write: None,
+            };
+            self.dma_unit.tick(&mut bus, self.total_master_cycles)
+        }
+
+        fn bus(&mut self) -> Bus<'_> {
+            Bus {
+                memory: &mut self.memory,
+                cpu_registers: &mut self.cpu_registers,
+                ppu: &mut self.ppu,
+                apu: &mut self.apu,
+                latched_interrupts: None,
+                access_master_cycles: 0,
+                pending_write: None,
+            }
+        }
+
+        // Set up HDMA channel 0 to write one byte to $21FF (unused) on every line, with the table
+        // at $7E:0000
+        fn start_hdma(&mut self) {
+            let table: Vec<u8> = (0..224).flat_map(|_| [0x01, 0x00]).chain([0x00]).collect();
+            self.memory.main_ram_mut()[..table.len()].copy_from_slice(&table);
+
+            let mut bus = self.bus();
+            for (address, value) in
+                [(0x4300, 0x00), (0x4301, 0xFF), (0x4302, 0x00), (0x4303, 0x00), (0x4304, 0x7E)]
+            {
+                bus.apply_write(address, value);
+            }
+            bus.apply_write(0x420C, 0x01);
+        }
+
+        // Run DMA one master cycle at a time (or for the full length of each DMA step) until the
+        // given number of frames have completed, returning every DMA step
+        fn run_frames(&mut self, frames: u32) -> Vec<DmaEvent> {
+            let mut events = Vec::new();
+            let mut frames_complete = 0;
+            while frames_complete < frames {
+                let total_master_cycles = self.total_master_cycles;
+                let scanline = self.ppu.scanline();
+                let scanline_mclk = self.ppu.scanline_master_cycles();
+
+                let master_cycles_elapsed = match self.tick_dma() {
+                    DmaStatus::None => 1,
+                    DmaStatus::InProgress { master_cycles_elapsed } => {
+                        events.push(DmaEvent {
+                            scanline,
+                            scanline_mclk,
+                            total_master_cycles,
+                            master_cycles_elapsed,
+                        });
+                        master_cycles_elapsed
+                    }
+                };
+
+                self.total_master_cycles += master_cycles_elapsed;
+                if self.ppu.tick(master_cycles_elapsed) == crate::ppu::PpuTickEffect::FrameComplete
+                {
+                    frames_complete += 1;
+                }
+            }
+
+            events
+        }
+    }
+
+    #[test]
+    fn hdma_init_aligns_to_dma_clock() {
+        let mut system = TestSystem::new();
+        system.start_hdma();
+
+        // Offset the master cycle counter so that line 0 does not start on a multiple of 8
+        system.total_master_cycles = 3;
+        system.run_frames(1);
+
+        for _ in 0..4 {
+            let events = system.run_frames(1);
+            let init = events.iter().find(|event| event.scanline == 0).unwrap();
+
+            // Init happens at H~5 (12 master cycles plus up to 8 cycles of alignment), on a master
+            // cycle that is aligned to the 8-cycle DMA clock
+            assert!((13..=20).contains(&init.scanline_mclk), "{init:?}");
+            assert_eq!(init.total_master_cycles & 7, 4, "{init:?}");
+
+            // Move the alignment for the next frame
+            system.total_master_cycles += 1;
+        }
+    }
+
+    #[test]
+    fn hdma_transfers_start_at_h276() {
+        let mut system = TestSystem::new();
+        system.start_hdma();
+        system.run_frames(1);
+
+        let events = system.run_frames(1);
+
+        // The first step on each line is the 18-cycle HDMA overhead
+        let transfer_starts: Vec<_> = events
+            .iter()
+            .filter(|event| event.scanline_mclk >= HDMA_TRANSFER_MCLK)
+            .filter(|event| event.master_cycles_elapsed == 18)
+            .collect();
+        assert_eq!(transfer_starts.len(), 224);
+        for event in transfer_starts {
+            assert_eq!(event.scanline_mclk, HDMA_TRANSFER_MCLK, "{event:?}");
+        }
+    }
+
+    #[test]
+    fn dma_byte_stays_on_open_bus() {
+        let mut system = TestSystem::new();
+        system.memory.main_ram_mut()[0x10] = 0xA5;
+
+        // GPDMA 1 byte from $7E:0010 to $21FF
+        let mut bus = system.bus();
+        for (address, value) in [
+            (0x4300, 0x00),
+            (0x4301, 0xFF),
+            (0x4302, 0x10),
+            (0x4303, 0x00),
+            (0x4304, 0x7E),
+            (0x4305, 0x01),
+            (0x4306, 0x00),
+        ] {
+            bus.apply_write(address, value);
+        }
+        bus.apply_write(0x420B, 0x01);
+
+        while system.cpu_registers.active_gpdma_channels[0] {
+            let _ = system.tick_dma();
+            system.total_master_cycles += 8;
+        }
+
+        assert_eq!(system.memory.cpu_open_bus().value(), 0xA5);
+    }
+}
@@ -1,9 +1,12 @@
 use crate::input::{SnesInputDevice, SnesInputs, SnesJoypadState, SuperScopeState};
 use bincode::{Decode, Encode};
 use jgenesis_common::num::GetBit;
-use std::mem;
+use std::cmp;
 
 const AUTO_JOYPAD_DURATION_MCLK: u64 = 4224;
+// Auto joypad read latches the controllers, then reads one bit from each port every 256 mclk
+const AUTO_JOYPAD_LATCH_MCLK: u64 = AUTO_JOYPAD_DURATION_MCLK - 16 * AUTO_JOYPAD_BIT_MCLK;
+const AUTO_JOYPAD_BIT_MCLK: u64 = 256;
 
 #[derive(Debug, Clone, Copy, Encode, Decode)]
 struct SuperScopeRegister {
@@ -70,6 +73,7 @@ impl SuperScopeRegister {
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct InputState {
     auto_read_cycles_remaining: u64,
+    auto_read_bits_read: u8,
     auto_joypad_p1_inputs: u16,
     auto_joypad_p2_inputs: u16,
     strobe: bool,
@@ -84,6 +88,7 @@ impl InputState {
     pub fn new() -> Self {
         Self {
             auto_read_cycles_remaining: 0,
+            auto_read_bits_read: 0,
             auto_joypad_p1_inputs: SnesJoypadState::default().to_register_word(),
             auto_joypad_p2_inputs: SnesJoypadState::default().to_register_word(),
             strobe: false,
@@ -151,6 +156,17 @@ impl InputState {
 
     pub fn start_auto_joypad_read(&mut self) {
         self.auto_read_cycles_remaining = AUTO_JOYPAD_DURATION_MCLK;
+        self.auto_read_bits_read = 0;
+
+        // Auto joypad read strobes the joypad before reading inputs; this populates the manual
+        // joypad read registers
+        self.set_strobe(true);
+        self.set_strobe(false);
+
+        // JOY1-4 registers are cleared at the start of auto joypad read and then filled in one
+        // bit at a time. Games that read them during the read window will see in-progress values
+        self.auto_joypad_p1_inputs = 0;
+        self.auto_joypad_p2_inputs = 0;
     }
 
     pub fn tick(&mut self, master_cycles_elapsed: u64, inputs: SnesInputs) {
@@ -165,17 +181,20 @@ impl InputState {
         self.auto_read_cycles_remaining =
             self.auto_read_cycles_remaining.saturating_sub(master_cycles_elapsed);
 
-        if self.auto_read_cycles_remaining == 0 {
-            // Auto joypad read strobes the joypad while reading inputs; this populates the manual
-            // joypad read registers
-            self.set_strobe(true);
-            self.set_strobe(false);
-
-            // Drain the manual joypad read registers into the auto joypad read registers
-            // Donkey Kong Country depends on the manual joypad read registers reading out 1s after
-            // auto joypad read finishes
-            self.auto_joypad_p1_inputs = mem::replace(&mut self.manual_joypad_p1_inputs, !0);
-            self.auto_joypad_p2_inputs = mem::replace(&mut self.manual_joypad_p2_inputs, !0);
+        let elapsed = AUTO_JOYPAD_DURATION_MCLK - self.auto_read_cycles_remaining;
+        let target_bits_read =
+            cmp::min(16, elapsed.saturating_sub(AUTO_JOYPAD_LATCH_MCLK) / AUTO_JOYPAD_BIT_MCLK)
+                as u8;
+
+        // Shift bits from the manual joypad read registers into the auto joypad read registers.
+        // Donkey Kong Country depends on the manual joypad read registers reading out 1s after
+        // auto joypad read finishes, which falls out of this naturally
+        while self.auto_read_bits_read < target_bits_read {
+            let p1_bit = self.next_manual_p1_bit();
+            let p2_bit = self.next_manual_p2_bit();
+            self.auto_joypad_p1_inputs = (self.auto_joypad_p1_inputs << 1) | u16::from(p1_bit);
+            self.auto_joypad_p2_inputs = (self.auto_joypad_p2_inputs << 1) | u16::from(p2_bit);
+            self.auto_read_bits_read += 1;
         }
     }
 
@@ -189,3 +208,38 @@ impl InputState {
             .flatten()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_joypad_read_in_progress_values() {
+        let mut inputs = SnesInputs::default();
+        inputs.p1.b = true;
+        inputs.p1.a = true;
+
+        let mut state = InputState::new();
+        state.tick(0, inputs);
+        state.start_auto_joypad_read();
+        assert!(state.auto_joypad_read_in_progress());
+        assert_eq!(state.auto_joypad_p1_inputs(), 0x0000);
+
+        // Latch period plus one bit
+        state.tick(AUTO_JOYPAD_LATCH_MCLK + AUTO_JOYPAD_BIT_MCLK, inputs);
+        assert_eq!(state.auto_joypad_p1_inputs(), 0x0001);
+
+        // 8 bits total
+        state.tick(7 * AUTO_JOYPAD_BIT_MCLK, inputs);
+        assert_eq!(state.auto_joypad_p1_inputs(), 0x0080);
+        assert!(state.auto_joypad_read_in_progress());
+
+        state.tick(8 * AUTO_JOYPAD_BIT_MCLK, inputs);
+        assert!(!state.auto_joypad_read_in_progress());
+        assert_eq!(state.auto_joypad_p1_inputs(), 0x8080);
+        assert_eq!(state.auto_joypad_p2_inputs(), 0x0000);
+
+        // Manual joypad registers should read out 1s after auto read finishes
+        assert!(state.next_manual_p1_bit());
+    }
+}
@@ -10,9 +10,10 @@ use crate::ppu::registers::{
     ObjPriorityMode, Registers, TileSize, VramIncrementMode,
 };
 use bincode::{Decode, Encode};
+use jgenesis_common::bus::OpenBus;
 use jgenesis_common::frontend::{Color, FrameSize, TimingMode};
 use jgenesis_common::num::{GetBit, U16Ext};
-use jgenesis_proc_macros::{FakeDecode, FakeEncode};
+use jgenesis_proc_macros::SaveState;
 use std::array;
 use std::ops::{Deref, DerefMut};
 
@@ -38,6 +39,10 @@ const MCLKS_PER_NORMAL_SCANLINE: u64 = 1364;
 const MCLKS_PER_SHORT_SCANLINE: u64 = 1360;
 const MCLKS_PER_LONG_SCANLINE: u64 = 1368;
 
+// Dots 323 and 327 are 6 master cycles long instead of 4, except on the short NTSC scanline
+const FIRST_LONG_DOT: u64 = 323;
+const SECOND_LONG_DOT: u64 = 327;
+
 type Vram = [u16; VRAM_LEN_WORDS];
 type Oam = [u8; OAM_LEN_BYTES];
 type Cgram = [u16; CGRAM_LEN_WORDS];
@@ -48,8 +53,8 @@ struct State {
     scanline_master_cycles: u64,
     odd_frame: bool,
     pending_sprite_pixel_overflow: bool,
-    ppu1_open_bus: u8,
-    ppu2_open_bus: u8,
+    ppu1_open_bus: OpenBus,
+    ppu2_open_bus: OpenBus,
     last_rendered_scanline: Option<u16>,
     // Tracks if Mode 5/6 or pseudo-hi-res was enabled at any point during active display
     h_hi_res_frame: bool,
@@ -64,8 +69,8 @@ impl State {
             scanline_master_cycles: 0,
             odd_frame: false,
             pending_sprite_pixel_overflow: false,
-            ppu1_open_bus: 0,
-            ppu2_open_bus: 0,
+            ppu1_open_bus: OpenBus::new(),
+            ppu2_open_bus: OpenBus::new(),
             last_rendered_scanline: None,
             h_hi_res_frame: false,
             v_hi_res_frame: false,
@@ -122,11 +127,13 @@ struct RenderedPixel {
     color: u16,
     palette: u8,
     layer: Layer,
+    // CGRAM address that the PPU read this pixel's color from
+    cgram_address: u8,
 }
 
 impl Default for RenderedPixel {
     fn default() -> Self {
-        Self { color: 0, palette: 0, layer: Layer::Backdrop }
+        Self { color: 0, palette: 0, layer: Layer::Backdrop, cgram_address: 0 }
     }
 }
 
@@ -157,7 +164,7 @@ impl Buffers {
     }
 }
 
-#[derive(Debug, Clone, FakeEncode, FakeDecode)]
+#[derive(Debug, Clone)]
 struct FrameBuffer(Box<[Color; FRAME_BUFFER_LEN]>);
 
 impl FrameBuffer {
@@ -349,7 +356,7 @@ impl HiResMode {
     }
 }
 
-#[derive(Debug, Clone, Encode, Decode)]
+#[derive(Debug, Clone, SaveState)]
 pub struct Ppu {
     timing_mode: TimingMode,
     registers: Registers,
@@ -358,10 +365,25 @@ pub struct Ppu {
     vram: Box<Vram>,
     oam: Box<Oam>,
     cgram: Box<Cgram>,
+    #[savestate(skip, default = FrameBuffer::new())]
     frame_buffer: FrameBuffer,
     sprite_buffer: Vec<SpriteData>,
     sprite_tile_buffer: Vec<SpriteTileData>,
+    #[savestate(skip, default = FrameBuffer::new())]
+    output_frame_buffer: FrameBuffer,
+    // Set if the current frame should be presented from the output frame buffer rather than the
+    // frame buffer, because it was upscaled and/or blended
+    output_frame_size: Option<FrameSize>,
+    // Once a game has presented a hi-res / interlaced frame, later low-res frames are pixel doubled
+    // to that resolution until the BG mode changes or the console is reset. Games that toggle
+    // hi-res or interlacing for menus and text boxes would otherwise change the output frame size
+    // every time, which makes scaling filters and shaders visibly shimmer
+    h_hi_res_hold: bool,
+    v_hi_res_hold: bool,
+    hold_bg_mode: BgMode,
     deinterlace: bool,
+    hi_res_blending: bool,
+    priority_overlay: bool,
 }
 
 // In actual hardware, PPU starts rendering pixels at H=22 / mclk=88
@@ -389,7 +411,14 @@ impl Ppu {
             frame_buffer: FrameBuffer::new(),
             sprite_buffer: Vec::with_capacity(MAX_SPRITES_PER_LINE),
             sprite_tile_buffer: Vec::with_capacity(MAX_SPRITE_TILES_PER_LINE),
+            output_frame_buffer: FrameBuffer::new(),
+            output_frame_size: None,
+            h_hi_res_hold: false,
+            v_hi_res_hold: false,
+            hold_bg_mode: BgMode::default(),
             deinterlace: config.deinterlace,
+            hi_res_blending: config.hi_res_blending,
+            priority_overlay: config.priority_overlay,
         }
     }
 
@@ -449,6 +478,9 @@ impl Ppu {
                     self.registers.oam_address = self.registers.oam_address_reload_value << 1;
                 }
 
+                self.update_resolution_hold();
+                self.prepare_output_frame();
+
                 tick_effect = PpuTickEffect::FrameComplete;
             }
         } else if is_active_scanline
@@ -461,14 +493,14 @@ impl Ppu {
             && is_active_scanline
             && (RENDER_LINE_MCLK..END_RENDER_LINE_MCLK).contains(&new_scanline_mclks)
         {
-            // Between H=22 and H=276 and INIDISP or one of the scroll registers was just modified;
-            // partially render current line
+            // Between H=22 and H=276 and INIDISP, one of the scroll registers, or CGRAM was just
+            // modified; partially render current line
             let mid_line_update = self.registers.mid_line_update.unwrap();
 
             // Scroll register writes don't seem to apply immediately - see the "Good Luck"
             // animation in Air Strike Patrol
             let pixel_offset = match mid_line_update {
-                MidScanlineUpdate::Inidisp => 0,
+                MidScanlineUpdate::Inidisp | MidScanlineUpdate::Cgram => 0,
                 MidScanlineUpdate::Scroll => 15,
             };
 
@@ -540,12 +572,15 @@ impl Ppu {
                 self.render_scanline(2 * scanline, hi_res_mode, screen_from_pixel);
             }
         } else if !self.state.v_hi_res_frame && v_hi_res {
-            // Probably should never happen - PPU is in 448px mode but interlacing was disabled at
-            // start of frame
-            let y = if self.state.odd_frame { 2 * scanline } else { 2 * scanline - 1 };
+            // Interlacing was enabled mid-frame while in mode 5/6. The frame buffer stays at 224px
+            // until the next frame, so only one of the two interlaced lines can be drawn: the line
+            // for the current field, or always the even line when deinterlacing so that the output
+            // does not alternate between fields
+            let odd_line = !self.deinterlace && self.state.odd_frame;
+            let y = if odd_line { 2 * scanline } else { 2 * scanline - 1 };
 
             if from_pixel == 0 {
-                self.render_obj_layer(scanline, y % 2 == 0);
+                self.render_obj_layer(scanline, odd_line);
             }
 
             self.render_bg_layers_to_buffer(y, hi_res_mode, bg_from_pixel);
@@ -555,7 +590,7 @@ impl Ppu {
             if !self.deinterlace {
                 let odd_frame: u16 = self.state.odd_frame.into();
                 self.render_obj_layer(scanline, self.state.odd_frame);
-                self.render_bg_layers_to_buffer(scanline, hi_res_mode, screen_from_pixel);
+                self.render_bg_layers_to_buffer(scanline, hi_res_mode, bg_from_pixel);
                 self.render_scanline(2 * scanline - 1 + odd_frame, hi_res_mode, screen_from_pixel);
             } else {
                 // Render even line
@@ -595,7 +630,7 @@ impl Ppu {
         let mode = self.registers.bg_mode;
 
         let bg1_enabled = self.registers.main_bg_enabled[0] || self.registers.sub_bg_enabled[0];
-        let bg2_enabled = mode.bg2_enabled()
+        let bg2_enabled = self.registers.bg2_layer_enabled()
             && (self.registers.main_bg_enabled[1] || self.registers.sub_bg_enabled[1]);
         let bg3_enabled = mode.bg3_enabled()
             && (self.registers.main_bg_enabled[2] || self.registers.sub_bg_enabled[2]);
@@ -607,15 +642,19 @@ impl Ppu {
             self.populate_offset_per_tile_buffers(hi_res_mode);
         }
 
-        if bg1_enabled {
-            match mode {
-                BgMode::Seven => self.render_mode_7_to_buffer(scanline, from_pixel),
-                _ => self.render_bg_to_buffer(0, scanline, hi_res_mode, from_pixel),
+        if mode == BgMode::Seven {
+            // BG1 and the EXTBG BG2 layer are both rendered from the same Mode 7 pixels
+            if bg1_enabled || bg2_enabled {
+                self.render_mode_7_to_buffer(scanline, from_pixel);
+            }
+        } else {
+            if bg1_enabled {
+                self.render_bg_to_buffer(0, scanline, hi_res_mode, from_pixel);
             }
-        }
 
-        if bg2_enabled {
-            self.render_bg_to_buffer(1, scanline, hi_res_mode, from_pixel);
+            if bg2_enabled {
+                self.render_bg_to_buffer(1, scanline, hi_res_mode, from_pixel);
+            }
         }
 
         if bg3_enabled {
@@ -753,13 +792,26 @@ impl Ppu {
         let v_flip = self.registers.mode_7_v_flip;
 
         let oob_behavior = self.registers.mode_7_oob_behavior;
+        let extbg_enabled = self.registers.extbg_enabled;
 
         for pixel in from_pixel..NORMAL_SCREEN_WIDTH as u16 {
-            let (base_y, mosaic_x) = self.apply_mosaic(0, scanline, pixel, HiResMode::None);
-            if mosaic_x != pixel {
-                // Copy last pixel and move on
+            // Vertical mosaic is always controlled by BG1's mosaic flag because both layers share
+            // the same Mode 7 fetches, but horizontal mosaic is controlled separately for each layer
+            let (base_y, bg1_mosaic_x) = self.apply_mosaic(0, scanline, pixel, HiResMode::None);
+            let bg1_mosaic_copy = bg1_mosaic_x != pixel;
+            let bg2_mosaic_copy =
+                extbg_enabled && self.apply_mosaic(1, scanline, pixel, HiResMode::None).1 != pixel;
+
+            // Copy last pixel for any layer that is in the middle of a mosaic block
+            if bg1_mosaic_copy {
                 self.buffers.bg_pixels[0][pixel as usize] =
                     self.buffers.bg_pixels[0][(pixel - 1) as usize];
+            }
+            if bg2_mosaic_copy {
+                self.buffers.bg_pixels[1][pixel as usize] =
+                    self.buffers.bg_pixels[1][(pixel - 1) as usize];
+            }
+            if bg1_mosaic_copy && (!extbg_enabled || bg2_mosaic_copy) {
                 continue;
             }
 
@@ -782,6 +834,7 @@ impl Ppu {
             tile_map_y >>= 8;
 
             let mut force_tile_0 = false;
+            let mut transparent = false;
             if tile_map_x < 0
                 || tile_map_y < 0
                 || tile_map_x >= TILE_MAP_SIZE_PIXELS
@@ -793,8 +846,7 @@ impl Ppu {
                         tile_map_y &= TILE_MAP_SIZE_PIXELS - 1;
                     }
                     Mode7OobBehavior::Transparent => {
-                        self.buffers.bg_pixels[0][pixel as usize] = Pixel::TRANSPARENT;
-                        continue;
+                        transparent = true;
                     }
                     Mode7OobBehavior::Tile0 => {
                         tile_map_x &= 0x07;
@@ -804,22 +856,35 @@ impl Ppu {
                 }
             }
 
-            let tile_number = if force_tile_0 {
+            let color = if transparent {
                 0
             } else {
-                // Mode 7 tile map is always located at $0000
-                let tile_map_row = tile_map_y / 8;
-                let tile_map_col = tile_map_x / 8;
-                let tile_map_addr = tile_map_row * TILE_MAP_SIZE_PIXELS / 8 + tile_map_col;
-                self.vram[tile_map_addr as usize] & 0x00FF
+                let tile_number = if force_tile_0 {
+                    0
+                } else {
+                    // Mode 7 tile map is always located at $0000
+                    let tile_map_row = tile_map_y / 8;
+                    let tile_map_col = tile_map_x / 8;
+                    let tile_map_addr = tile_map_row * TILE_MAP_SIZE_PIXELS / 8 + tile_map_col;
+                    self.vram[tile_map_addr as usize] & 0x00FF
+                };
+
+                let tile_row = (tile_map_y % 8) as u16;
+                let tile_col = (tile_map_x % 8) as u16;
+                let pixel_addr = 64 * tile_number + 8 * tile_row + tile_col;
+                self.vram[pixel_addr as usize].msb()
             };
 
-            let tile_row = (tile_map_y % 8) as u16;
-            let tile_col = (tile_map_x % 8) as u16;
-            let pixel_addr = 64 * tile_number + 8 * tile_row + tile_col;
-            let color = self.vram[pixel_addr as usize].msb();
+            if !bg1_mosaic_copy {
+                self.buffers.bg_pixels[0][pixel as usize] =
+                    Pixel { palette: 0, color, priority: 0 };
+            }
 
-            self.buffers.bg_pixels[0][pixel as usize] = Pixel { palette: 0, color, priority: 0 };
+            if extbg_enabled && !bg2_mosaic_copy {
+                // EXTBG uses the highest color bit as a per-pixel priority bit for BG2
+                self.buffers.bg_pixels[1][pixel as usize] =
+                    Pixel { palette: 0, color: color & 0x7F, priority: color >> 7 };
+            }
         }
     }
 
@@ -827,62 +892,82 @@ impl Ppu {
         let screen_width =
             if hi_res_mode == HiResMode::True { HIRES_SCREEN_WIDTH } else { NORMAL_SCREEN_WIDTH };
 
-        let mode = self.registers.bg_mode;
         let bg3_h_scroll = self.registers.bg_h_scroll[2];
-        let bg3_v_scroll = self.registers.bg_v_scroll[2];
 
-        for pixel in 0..screen_width as u16 {
-            // Lowest 3 bits of BG3 H scroll do not apply in offset-per-tile
-            let bg3_x = (pixel.wrapping_sub(8) & !0x7).wrapping_add(bg3_h_scroll & !0x7);
-
-            let (h_offset_entry, v_offset_entry) = match mode {
-                BgMode::Four => {
-                    // In Mode 4, instead of loading both map entries, the PPU uses the highest bit
-                    // of the first entry to determine whether to apply offset to H or V
-                    let bg3_entry =
-                        get_bg_map_entry(&self.vram, &self.registers, 2, bg3_x, bg3_v_scroll);
-                    if bg3_entry.bit(15) {
-                        // Apply to V scroll
-                        (0, bg3_entry)
-                    } else {
-                        // Apply to H scroll
-                        (bg3_entry, 0)
-                    }
-                }
-                _ => {
-                    let h_offset_entry =
-                        get_bg_map_entry(&self.vram, &self.registers, 2, bg3_x, bg3_v_scroll);
-                    let v_offset_entry =
-                        get_bg_map_entry(&self.vram, &self.registers, 2, bg3_x, bg3_v_scroll + 8);
-                    (h_offset_entry, v_offset_entry)
-                }
-            };
-
-            // Offset-per-tile can only apply to BG1 and BG2
-            for bg in 0..2 {
-                let bg_h_scroll = self.registers.bg_h_scroll[bg];
-                let bg_v_scroll = self.registers.bg_v_scroll[bg];
-                if pixel + (bg_h_scroll & 0x07) < 8 {
-                    // Offset-per-tile only applies to the 2nd visible tile and onwards
-                    self.buffers.offset_per_tile_h_scroll[bg][pixel as usize] = bg_h_scroll;
-                    self.buffers.offset_per_tile_v_scroll[bg][pixel as usize] = bg_v_scroll;
+        // Offset-per-tile can only apply to BG1 and BG2
+        for bg in 0..2 {
+            let bg_h_scroll = self.registers.bg_h_scroll[bg];
+            let bg_v_scroll = self.registers.bg_v_scroll[bg];
+
+            // BG1 uses bit 13 to determine whether to apply offset-per-tile, while BG2 uses bit 14
+            let bg_offset_bit = if bg == 0 { 13 } else { 14 };
+
+            let mut last_bg3_x = None;
+            let mut scroll = (bg_h_scroll, bg_v_scroll);
+            for pixel in 0..screen_width as u16 {
+                // Offset-per-tile columns are 8 pixels wide at normal resolution, which is one
+                // 16-pixel-wide tile in Mode 6
+                let screen_x = if hi_res_mode == HiResMode::True { pixel >> 1 } else { pixel };
+
+                // Only fetch offsets once per column
+                let bg3_x = offset_per_tile_bg3_x(screen_x, bg_h_scroll, bg3_h_scroll);
+                if bg3_x == last_bg3_x {
+                    self.buffers.offset_per_tile_h_scroll[bg][pixel as usize] = scroll.0;
+                    self.buffers.offset_per_tile_v_scroll[bg][pixel as usize] = scroll.1;
                     continue;
                 }
+                last_bg3_x = bg3_x;
+
+                scroll = match bg3_x {
+                    // Offset-per-tile does not apply to the first visible tile
+                    None => (bg_h_scroll, bg_v_scroll),
+                    Some(bg3_x) => {
+                        let (h_offset_entry, v_offset_entry) = self.offset_per_tile_entries(bg3_x);
+
+                        let h_scroll = if h_offset_entry.bit(bg_offset_bit) {
+                            offset_per_tile_h_scroll(h_offset_entry, bg_h_scroll)
+                        } else {
+                            bg_h_scroll
+                        };
+                        let v_scroll = if v_offset_entry.bit(bg_offset_bit) {
+                            v_offset_entry & 0x03FF
+                        } else {
+                            bg_v_scroll
+                        };
+                        (h_scroll, v_scroll)
+                    }
+                };
+
+                self.buffers.offset_per_tile_h_scroll[bg][pixel as usize] = scroll.0;
+                self.buffers.offset_per_tile_v_scroll[bg][pixel as usize] = scroll.1;
+            }
+        }
+    }
+
+    // Returns (H offset entry, V offset entry) for the given BG3 X coordinate
+    fn offset_per_tile_entries(&self, bg3_x: u16) -> (u16, u16) {
+        let bg3_v_scroll = self.registers.bg_v_scroll[2];
 
-                // BG1 uses bit 13 to determine whether to apply offset-per-tile, while BG2 uses bit 14
-                let bg_offset_bit = if bg == 0 { 13 } else { 14 };
-                self.buffers.offset_per_tile_h_scroll[bg][pixel as usize] =
-                    if h_offset_entry.bit(bg_offset_bit) {
-                        h_offset_entry & 0x03FF
-                    } else {
-                        bg_h_scroll
-                    };
-                self.buffers.offset_per_tile_v_scroll[bg][pixel as usize] =
-                    if v_offset_entry.bit(bg_offset_bit) {
-                        v_offset_entry & 0x03FF
-                    } else {
-                        bg_v_scroll
-                    };
+        match self.registers.bg_mode {
+            BgMode::Four => {
+                // In Mode 4, instead of loading both map entries, the PPU uses the highest bit
+                // of the first entry to determine whether to apply offset to H or V
+                let bg3_entry =
+                    get_offset_per_tile_entry(&self.vram, &self.registers, bg3_x, bg3_v_scroll);
+                if bg3_entry.bit(15) {
+                    // Apply to V scroll
+                    (0, bg3_entry)
+                } else {
+                    // Apply to H scroll
+                    (bg3_entry, 0)
+                }
+            }
+            _ => {
+                let h_offset_entry =
+                    get_offset_per_tile_entry(&self.vram, &self.registers, bg3_x, bg3_v_scroll);
+                let v_offset_entry =
+                    get_offset_per_tile_entry(&self.vram, &self.registers, bg3_x, bg3_v_scroll + 8);
+                (h_offset_entry, v_offset_entry)
             }
         }
     }
@@ -904,8 +989,12 @@ impl Ppu {
             if hi_res_mode.is_hi_res() { HIRES_SCREEN_WIDTH } else { NORMAL_SCREEN_WIDTH };
 
         let brightness = self.registers.brightness;
-        let main_backdrop_pixel =
-            RenderedPixel { palette: 0, color: self.cgram[0], layer: Layer::Backdrop };
+        let main_backdrop_pixel = RenderedPixel {
+            palette: 0,
+            color: self.cgram[0],
+            layer: Layer::Backdrop,
+            cgram_address: 0,
+        };
         let sub_backdrop_color = self.registers.sub_backdrop_color;
 
         for pixel in screen_from_pixel..screen_width as u16 {
@@ -972,7 +1061,10 @@ impl Ppu {
                 main_screen_pixel.color
             };
 
-            let final_color = convert_snes_color(snes_color, brightness);
+            let mut final_color = convert_snes_color(snes_color, brightness);
+            if self.priority_overlay {
+                final_color = tint_by_layer(final_color, main_screen_pixel.layer);
+            }
 
             if self.state.h_hi_res_frame && !hi_res_mode.is_hi_res() {
                 // Hi-res mode is not currently enabled, but it was enabled earlier in the frame;
@@ -1067,29 +1159,12 @@ impl Ppu {
                 let bg1_pixel = self.buffers.bg_pixels[0][screen_x as usize];
                 if !bg1_pixel.is_transparent() {
                     priority_resolver.add_bg1(bg1_pixel, is_mode_0_or_1);
-
-                    if mode == BgMode::Seven && self.registers.extbg_enabled {
-                        // When EXTBG is enabled in Mode 7, BG1 pixels are duplicated into BG2
-                        // but use the highest color bit as priority
-                        let bg2_pixel_color = bg1_pixel.color & 0x7F;
-                        if bg2_pixel_color != 0 {
-                            let bg2_priority = bg1_pixel.color >> 7;
-                            priority_resolver.add_bg2(
-                                Pixel {
-                                    priority: bg2_priority,
-                                    color: bg2_pixel_color,
-                                    palette: bg1_pixel.palette,
-                                },
-                                is_mode_0_or_1,
-                            );
-                        }
-                    }
                 }
             }
         }
 
-        // BG2 layer (enabled in all modes except 6 and 7)
-        if mode.bg2_enabled() && bg_enabled[1] {
+        // BG2 layer (enabled in all modes except 6, and in mode 7 only with EXTBG)
+        if self.registers.bg2_layer_enabled() && bg_enabled[1] {
             for (x, priority_resolver) in screen_pixels.iter_mut().enumerate() {
                 let screen_x = apply_screen_shift(x, screen_x_shift, screen_x_offset);
 
@@ -1147,8 +1222,12 @@ impl Ppu {
             }
         }
 
-        let backdrop_pixel =
-            RenderedPixel { color: backdrop_color, palette: 0, layer: Layer::Backdrop };
+        let backdrop_pixel = RenderedPixel {
+            color: backdrop_color,
+            palette: 0,
+            layer: Layer::Backdrop,
+            cgram_address: 0,
+        };
         for (priority_resolver, rendered_pixel) in
             screen_pixels.iter_mut().zip(screen_rendered_pixels)
         {
@@ -1161,7 +1240,8 @@ impl Ppu {
                     pixel.palette,
                     pixel.color,
                 );
-                RenderedPixel { color, palette: pixel.palette, layer }
+                let cgram_address = pixel_cgram_address(layer, mode, pixel.palette, pixel.color);
+                RenderedPixel { color, palette: pixel.palette, layer, cgram_address }
             });
         }
     }
@@ -1524,11 +1604,100 @@ impl Ppu {
         self.state.scanline_master_cycles
     }
 
+    /// Current H counter value, accounting for the two long dots in each scanline.
+    pub fn h_counter(&self) -> u16 {
+        mclk_to_h_counter(self.state.scanline_master_cycles, self.is_short_scanline())
+    }
+
+    /// Master cycles into the current scanline at which the given H counter value begins.
+    pub fn h_counter_to_mclk(&self, h_counter: u16) -> u64 {
+        let h_counter = u64::from(h_counter);
+        if self.is_short_scanline() || h_counter <= FIRST_LONG_DOT {
+            4 * h_counter
+        } else if h_counter <= SECOND_LONG_DOT {
+            4 * h_counter + 2
+        } else {
+            4 * h_counter + 4
+        }
+    }
+
     pub fn frame_buffer(&self) -> &[Color] {
-        self.frame_buffer.as_ref()
+        if self.output_frame_size.is_some() {
+            self.output_frame_buffer.as_ref()
+        } else {
+            self.frame_buffer.as_ref()
+        }
+    }
+
+    fn update_resolution_hold(&mut self) {
+        if self.registers.bg_mode != self.hold_bg_mode {
+            self.hold_bg_mode = self.registers.bg_mode;
+            self.clear_resolution_hold();
+        }
+
+        self.h_hi_res_hold |= self.state.h_hi_res_frame;
+        self.v_hi_res_hold |= self.state.v_hi_res_frame;
+    }
+
+    fn clear_resolution_hold(&mut self) {
+        self.h_hi_res_hold = false;
+        self.v_hi_res_hold = false;
+    }
+
+    // Upscale the frame if a higher resolution is being held, and blend hi-res frames if enabled.
+    // The frame buffer itself is never modified here because the PPU reads back previous lines
+    // when switching resolutions mid-frame
+    fn prepare_output_frame(&mut self) {
+        let native_size = self.native_frame_size();
+        let h_double = !self.state.h_hi_res_frame && self.h_hi_res_hold;
+        let v_double = !self.state.v_hi_res_frame && self.v_hi_res_hold;
+        let blend = self.hi_res_blending && self.state.h_hi_res_frame;
+
+        if !h_double && !v_double && !blend {
+            self.output_frame_size = None;
+            return;
+        }
+
+        let output_size = FrameSize {
+            width: if h_double { 2 * native_size.width } else { native_size.width },
+            height: if v_double { 2 * native_size.height } else { native_size.height },
+        };
+        let (native_width, output_width) = (native_size.width as usize, output_size.width as usize);
+
+        for y in 0..output_size.height as usize {
+            let native_y = if v_double { y / 2 } else { y };
+            let native_row =
+                &self.frame_buffer[native_y * native_width..(native_y + 1) * native_width];
+            let output_row =
+                &mut self.output_frame_buffer[y * output_width..(y + 1) * output_width];
+
+            if h_double {
+                for (output_pair, &color) in output_row.chunks_exact_mut(2).zip(native_row) {
+                    output_pair.fill(color);
+                }
+            } else {
+                output_row.copy_from_slice(native_row);
+            }
+
+            if blend {
+                // Pseudo-hi-res games render the sub screen and main screen to alternating
+                // columns, relying on the horizontal blurring of composite video to blend them
+                // into transparency. Approximate that by blending every pixel with the pixel to
+                // its left
+                for x in (1..output_width).rev() {
+                    output_row[x] = blend_colors(output_row[x - 1], output_row[x]);
+                }
+            }
+        }
+
+        self.output_frame_size = Some(output_size);
     }
 
     pub fn frame_size(&self) -> FrameSize {
+        self.output_frame_size.unwrap_or_else(|| self.native_frame_size())
+    }
+
+    fn native_frame_size(&self) -> FrameSize {
         let screen_width = self.state.frame_screen_width();
 
         let mut screen_height = self.registers.v_display_size.to_lines();
@@ -1549,7 +1718,7 @@ impl Ppu {
             0x36 => self.registers.read_mpyh(),
             0x37 => {
                 // SLHV: Latch H/V counter
-                let h_counter = (self.state.scanline_master_cycles >> 2) as u16;
+                let h_counter = self.h_counter();
                 let v_counter = self.state.scanline;
                 self.registers.read_slhv(h_counter, v_counter);
 
@@ -1578,20 +1747,22 @@ impl Ppu {
                 // STAT77: PPU1 status and version number
                 // Version number hardcoded to 1
                 // Bit 4 is PPU1 open bus
-                (u8::from(self.registers.sprite_pixel_overflow) << 7)
+                let value = (u8::from(self.registers.sprite_pixel_overflow) << 7)
                     | (u8::from(self.registers.sprite_overflow) << 6)
-                    | (self.state.ppu1_open_bus & 0x10)
-                    | 0x01
+                    | 0x01;
+                self.state.ppu1_open_bus.merge(value, !0x10)
             }
             0x3F => {
                 // STAT78: PPU2 status and version number
                 // Version number hardcoded to 1
                 // Bit 5 is PPU2 open bus
-                let value = (u8::from(self.state.odd_frame) << 7)
-                    | (u8::from(self.registers.new_hv_latched) << 6)
-                    | (self.state.ppu2_open_bus & 0x20)
-                    | (u8::from(self.timing_mode == TimingMode::Pal) << 4)
-                    | 0x01;
+                let value = self.state.ppu2_open_bus.merge(
+                    (u8::from(self.state.odd_frame) << 7)
+                        | (u8::from(self.registers.new_hv_latched) << 6)
+                        | (u8::from(self.timing_mode == TimingMode::Pal) << 4)
+                        | 0x01,
+                    !0x20,
+                );
 
                 self.registers.new_hv_latched = false;
                 self.registers.reset_hv_counter_flipflops();
@@ -1601,7 +1772,7 @@ impl Ppu {
             0x04 | 0x05 | 0x06 | 0x08 | 0x09 | 0x0A | 0x14 | 0x15 | 0x16 | 0x18 | 0x19 | 0x1A
             | 0x24 | 0x25 | 0x26 | 0x28 | 0x29 | 0x2A => {
                 // PPU1 open bus (all 8 bits)
-                self.state.ppu1_open_bus
+                self.state.ppu1_open_bus.value()
             }
             _ => {
                 // CPU open bus
@@ -1614,10 +1785,10 @@ impl Ppu {
             || address_lsb == 0x3E
         {
             // Reading $2134-$2136, $2138-$213A, or $213E sets PPU1 open bus
-            self.state.ppu1_open_bus = value;
+            self.state.ppu1_open_bus.drive(value);
         } else if (0x3B..0x3E).contains(&address_lsb) || address_lsb == 0x3F {
             // Reading $213B-$213D or $213F sets PPU2 open bus
-            self.state.ppu2_open_bus = value;
+            self.state.ppu2_open_bus.drive(value);
         }
 
         Some(value)
@@ -1784,19 +1955,41 @@ impl Ppu {
         self.registers.vram_prefetch_buffer = self.vram[vram_addr as usize];
     }
 
+    fn in_active_display(&self) -> bool {
+        !self.registers.forced_blanking && !self.vblank_flag()
+    }
+
+    // During active display the PPU is driving the OAM address bus for sprite evaluation, so data
+    // port accesses go to whichever address the PPU is reading rather than the port address
+    fn oam_access_address(&self, port_address: u16) -> usize {
+        let address = if self.in_active_display() {
+            // Sprite evaluation reads one sprite every 2 dots during H=0-255
+            let oam_offset = match self.registers.obj_priority_mode {
+                ObjPriorityMode::Normal => 0,
+                ObjPriorityMode::Rotate => (self.registers.oam_address >> 2) & 0x7F,
+            };
+            let dot = (self.state.scanline_master_cycles / 4).min(255) as u16;
+            ((oam_offset + dot / 2) & 0x7F) << 2
+        } else {
+            port_address
+        };
+
+        // $220-$3FF are mirrors of $200-$21F
+        if address >= 0x200 { (0x200 | (address & 0x01F)) as usize } else { address as usize }
+    }
+
     fn write_oam_data_port(&mut self, value: u8) {
         let oam_addr = self.registers.oam_address;
         if oam_addr >= 0x200 {
             // Writes to $200 or higher immediately go through
-            // $220-$3FF are mirrors of $200-$21F
-            self.oam[(0x200 | (oam_addr & 0x01F)) as usize] = value;
+            self.oam[self.oam_access_address(oam_addr)] = value;
         } else if !oam_addr.bit(0) {
             // Even address < $200: latch LSB
             self.registers.oam_write_buffer = value;
         } else {
             // Odd address < $200: Write word to OAM
-            self.oam[(oam_addr & !0x001) as usize] = self.registers.oam_write_buffer;
-            self.oam[oam_addr as usize] = value;
+            self.oam[self.oam_access_address(oam_addr & !0x001)] = self.registers.oam_write_buffer;
+            self.oam[self.oam_access_address(oam_addr)] = value;
         }
 
         self.registers.oam_address = (oam_addr + 1) & OAM_ADDRESS_MASK;
@@ -1804,18 +1997,29 @@ impl Ppu {
 
     fn read_oam_data_port(&mut self) -> u8 {
         let oam_addr = self.registers.oam_address;
-        let oam_byte = if oam_addr >= 0x200 {
-            // $220-$3FF are mirrors of $200-$21F
-            self.oam[(0x200 | (oam_addr & 0x01F)) as usize]
-        } else {
-            self.oam[oam_addr as usize]
-        };
+        let oam_byte = self.oam[self.oam_access_address(oam_addr)];
 
         self.registers.oam_address = (oam_addr + 1) & OAM_ADDRESS_MASK;
 
         oam_byte
     }
 
+    // Similar to OAM, CGRAM accesses while the PPU is outputting pixels go to the address of the
+    // color that the PPU most recently read (H=22-273 on active lines)
+    fn cgram_access_address(&self) -> usize {
+        let mclk = self.state.scanline_master_cycles;
+        let v_display_size = self.registers.v_display_size.to_lines();
+        if self.registers.forced_blanking
+            || !(1..=v_display_size).contains(&self.state.scanline)
+            || !(88..1096).contains(&mclk)
+        {
+            return self.registers.cgram_address as usize;
+        }
+
+        let pixel = ((mclk - 88) / 4).min(NORMAL_SCREEN_WIDTH as u64 - 1) as usize;
+        self.buffers.main_screen_rendered_pixels[pixel].cgram_address as usize
+    }
+
     fn write_cgram_data_port(&mut self, value: u8) {
         match self.registers.cgram_flipflop {
             AccessFlipflop::First => {
@@ -1824,17 +2028,20 @@ impl Ppu {
             }
             AccessFlipflop::Second => {
                 // Only bits 6-0 of high byte are persisted
-                self.cgram[self.registers.cgram_address as usize] =
+                self.cgram[self.cgram_access_address()] =
                     u16::from_le_bytes([self.registers.cgram_write_buffer, value & 0x7F]);
                 self.registers.cgram_flipflop = AccessFlipflop::First;
 
+                // Writes during active display (e.g. color 0 gradients) affect the rest of the line
+                self.registers.mid_line_update = Some(MidScanlineUpdate::Cgram);
+
                 self.registers.cgram_address = self.registers.cgram_address.wrapping_add(1);
             }
         }
     }
 
     fn read_cgram_data_port(&mut self) -> u8 {
-        let word = self.cgram[self.registers.cgram_address as usize];
+        let word = self.cgram[self.cgram_access_address()];
 
         match self.registers.cgram_flipflop {
             AccessFlipflop::First => {
@@ -1848,23 +2055,24 @@ impl Ppu {
                 self.registers.cgram_flipflop = AccessFlipflop::First;
                 self.registers.cgram_address = self.registers.cgram_address.wrapping_add(1);
 
-                (self.state.ppu2_open_bus & 0x80) | word.msb()
+                self.state.ppu2_open_bus.merge(word.msb(), 0x7F)
             }
         }
     }
 
     pub fn update_wrio(&mut self, wrio: u8) {
         if wrio != self.registers.programmable_joypad_port {
-            let h_counter = (self.state.scanline_master_cycles >> 2) as u16;
+            let h_counter = self.h_counter();
             let v_counter = self.state.scanline;
             self.registers.update_wrio(wrio, h_counter, v_counter);
         }
     }
 
     pub fn update_controller_hv_latch(&mut self, h: u16, v: u16, master_cycles_elapsed: u64) {
+        let h_mclk = self.h_counter_to_mclk(h);
         if v == self.state.scanline
-            && h > (self.state.scanline_master_cycles / 4) as u16
-            && h <= ((self.state.scanline_master_cycles + master_cycles_elapsed) / 4) as u16
+            && h_mclk > self.state.scanline_master_cycles
+            && h_mclk <= self.state.scanline_master_cycles + master_cycles_elapsed
         {
             self.registers.latched_h_counter = h;
             self.registers.latched_v_counter = v;
@@ -1874,6 +2082,14 @@ impl Ppu {
 
     pub fn update_config(&mut self, config: SnesEmulatorConfig) {
         self.deinterlace = config.deinterlace;
+        self.priority_overlay = config.priority_overlay;
+
+        if config.hi_res_blending != self.hi_res_blending {
+            self.hi_res_blending = config.hi_res_blending;
+
+            // Update the current frame in case it's redisplayed, e.g. while paused
+            self.prepare_output_frame();
+        }
     }
 
     pub fn reset(&mut self) {
@@ -1882,6 +2098,8 @@ impl Ppu {
 
         // Return to default rendering mode (224-line, non-interlaced, no pseudo-hi-res or smaller OBJs)
         self.registers.write_setini(0x00);
+
+        self.clear_resolution_hold();
     }
 }
 
@@ -1929,6 +2147,24 @@ fn get_bg_map_entry(vram: &Vram, registers: &Registers, bg: usize, x: u16, y: u1
     let bg_tile_size = registers.bg_tile_size[bg];
     let (bg_tile_width_pixels, bg_tile_height_pixels) = get_bg_tile_size(bg_mode, bg_tile_size);
 
+    get_bg_map_entry_sized(vram, registers, bg, x, y, bg_tile_width_pixels, bg_tile_height_pixels)
+}
+
+// Offset-per-tile data is always read from BG3's tile map as if it had 8x8 tiles, regardless of
+// BG3's tile size setting and even in Mode 6 where BG tiles are normally 16 pixels wide
+fn get_offset_per_tile_entry(vram: &Vram, registers: &Registers, x: u16, y: u16) -> u16 {
+    get_bg_map_entry_sized(vram, registers, 2, x, y, 8, 8)
+}
+
+fn get_bg_map_entry_sized(
+    vram: &Vram,
+    registers: &Registers,
+    bg: usize,
+    x: u16,
+    y: u16,
+    bg_tile_width_pixels: u16,
+    bg_tile_height_pixels: u16,
+) -> u16 {
     let bg_screen_size = registers.bg_screen_size[bg];
     let screen_width_pixels = bg_screen_size.width_tiles() * bg_tile_width_pixels;
     let screen_height_pixels = bg_screen_size.height_tiles() * bg_tile_height_pixels;
@@ -1964,6 +2200,20 @@ fn get_bg_map_entry(vram: &Vram, registers: &Registers, bg: usize, x: u16, y: u1
     vram[(bg_map_base_addr.wrapping_add(tile_map_addr) & VRAM_ADDRESS_MASK) as usize]
 }
 
+// Returns the BG3 X coordinate that a BG1/BG2 pixel's offset-per-tile data should be read from, or
+// None if the pixel is in the first visible tile column (which is never affected by offset-per-tile).
+// Columns follow the BG's own fine scroll so that the offset always changes on tile boundaries
+fn offset_per_tile_bg3_x(screen_x: u16, bg_h_scroll: u16, bg3_h_scroll: u16) -> Option<u16> {
+    let column = (screen_x + (bg_h_scroll & 0x07)) / 8;
+    (column != 0).then(|| (8 * (column - 1)).wrapping_add(bg3_h_scroll & !0x07))
+}
+
+// Offset-per-tile H offsets only replace the tile portion of the H scroll; the BG's own fine scroll
+// is always kept
+fn offset_per_tile_h_scroll(h_offset_entry: u16, bg_h_scroll: u16) -> u16 {
+    (h_offset_entry & 0x03F8) | (bg_h_scroll & 0x07)
+}
+
 fn get_bg_tile_size(bg_mode: BgMode, tile_size: TileSize) -> (u16, u16) {
     match (bg_mode, tile_size) {
         (BgMode::Six, _) | (BgMode::Five, TileSize::Small) => (16, 8),
@@ -1990,7 +2240,17 @@ fn resolve_pixel_color(
     palette: u8,
     color: u8,
 ) -> u16 {
-    let bpp = match (layer, bg_mode) {
+    // Direct color only applies to BG1; the Mode 7 EXTBG BG2 layer always reads from CGRAM
+    if direct_color_mode && layer == Layer::Bg1 && pixel_bpp(layer, bg_mode) == BitsPerPixel::Eight
+    {
+        return resolve_direct_color(palette, color);
+    }
+
+    cgram[pixel_cgram_address(layer, bg_mode, palette, color) as usize]
+}
+
+fn pixel_bpp(layer: Layer, bg_mode: BgMode) -> BitsPerPixel {
+    match (layer, bg_mode) {
         (Layer::Bg1 | Layer::Bg2, BgMode::Seven) => BitsPerPixel::Eight,
         (Layer::Bg1, _) => bg_mode.bg1_bpp(),
         (Layer::Bg2, _) => bg_mode.bg2_bpp(),
@@ -1998,9 +2258,13 @@ fn resolve_pixel_color(
         (Layer::Bg4, _) => BitsPerPixel::BG4,
         (Layer::Obj, _) => BitsPerPixel::OBJ,
         (Layer::Backdrop, _) => {
-            panic!("invalid input to resolve_pixel_color: mode={bg_mode:?}, layer={layer:?}")
+            panic!("invalid input to pixel_bpp: mode={bg_mode:?}, layer={layer:?}")
         }
-    };
+    }
+}
+
+fn pixel_cgram_address(layer: Layer, bg_mode: BgMode, palette: u8, color: u8) -> u8 {
+    let bpp = pixel_bpp(layer, bg_mode);
 
     let two_bpp_offset = if bg_mode == BgMode::Zero {
         // Mode 0 gives each BG layer its own set of 8 palettes
@@ -2009,7 +2273,7 @@ fn resolve_pixel_color(
             Layer::Bg2 => 0x20,
             Layer::Bg3 => 0x40,
             Layer::Bg4 => 0x60,
-            Layer::Backdrop => unreachable!("above match checks layer is not backdrop"),
+            Layer::Backdrop => unreachable!("pixel_bpp() checks layer is not backdrop"),
         }
     } else {
         0
@@ -2019,15 +2283,9 @@ fn resolve_pixel_color(
     let four_bpp_offset = if layer == Layer::Obj { 0x80 } else { 0 };
 
     match bpp {
-        BitsPerPixel::Two => cgram[(two_bpp_offset | (palette << 2) | color) as usize],
-        BitsPerPixel::Four => cgram[(four_bpp_offset | (palette << 4) | color) as usize],
-        BitsPerPixel::Eight => {
-            if direct_color_mode {
-                resolve_direct_color(palette, color)
-            } else {
-                cgram[color as usize]
-            }
-        }
+        BitsPerPixel::Two => two_bpp_offset | (palette << 2) | color,
+        BitsPerPixel::Four => four_bpp_offset | (palette << 4) | color,
+        BitsPerPixel::Eight => color,
     }
 }
 
@@ -2053,9 +2311,159 @@ fn convert_snes_color(snes_color: u16, brightness: u8) -> Color {
     Color::rgb(r, g, b)
 }
 
+fn blend_colors(a: Color, b: Color) -> Color {
+    Color::rgb(a.r.midpoint(b.r), a.g.midpoint(b.g), a.b.midpoint(b.b))
+}
+
+// Priority overlay: keep the pixel's brightness so that graphics stay recognizable, but blend it
+// with a color that identifies which layer won priority resolution
+fn tint_by_layer(color: Color, layer: Layer) -> Color {
+    let tint = match layer {
+        Layer::Bg1 => Color::rgb(255, 0, 0),
+        Layer::Bg2 => Color::rgb(0, 255, 0),
+        Layer::Bg3 => Color::rgb(0, 0, 255),
+        Layer::Bg4 => Color::rgb(255, 255, 0),
+        Layer::Obj => Color::rgb(255, 0, 255),
+        Layer::Backdrop => Color::rgb(0, 0, 0),
+    };
+
+    let luma =
+        ((u16::from(color.r) * 77 + u16::from(color.g) * 150 + u16::from(color.b) * 29) >> 8) as u8;
+    blend_colors(Color::rgb(luma, luma, luma), tint)
+}
+
+fn mclk_to_h_counter(scanline_mclk: u64, short_scanline: bool) -> u16 {
+    let h_counter = if short_scanline || scanline_mclk < 4 * FIRST_LONG_DOT {
+        scanline_mclk / 4
+    } else if scanline_mclk < 4 * FIRST_LONG_DOT + 6 {
+        FIRST_LONG_DOT
+    } else if scanline_mclk < 4 * SECOND_LONG_DOT + 2 {
+        (scanline_mclk - 2) / 4
+    } else if scanline_mclk < 4 * SECOND_LONG_DOT + 8 {
+        SECOND_LONG_DOT
+    } else {
+        (scanline_mclk - 4) / 4
+    };
+    h_counter as u16
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::ops::Range;
+
+    fn new_test_ppu() -> Ppu {
+        Ppu::new(
+            TimingMode::Ntsc,
+            SnesEmulatorConfig {
+                forced_timing_mode: None,
+                aspect_ratio: crate::api::SnesAspectRatio::default(),
+                deinterlace: true,
+                hi_res_blending: false,
+                priority_overlay: false,
+                audio_interpolation: crate::api::AudioInterpolationMode::default(),
+                audio_60hz_hack: false,
+                gsu_overclock_factor: jgenesis_common::clockratio::ClockRatio::from_integer(1),
+            },
+        )
+    }
+
+    #[test]
+    fn h_counter_long_dots() {
+        // Normal scanline: 340 dots with dots 323 and 327 lasting 6 master cycles
+        for (mclk, h) in [
+            (0, 0),
+            (1291, 322),
+            (1292, 323),
+            (1297, 323),
+            (1298, 324),
+            (1309, 326),
+            (1310, 327),
+            (1315, 327),
+            (1316, 328),
+            (1363, 339),
+        ] {
+            assert_eq!(mclk_to_h_counter(mclk, false), h, "mclk {mclk}");
+        }
+
+        // PAL interlaced long scanline has an extra dot
+        assert_eq!(mclk_to_h_counter(MCLKS_PER_LONG_SCANLINE - 1, false), 340);
+
+        // NTSC short scanline has no long dots
+        assert_eq!(mclk_to_h_counter(1292, true), 323);
+        assert_eq!(mclk_to_h_counter(1298, true), 324);
+        assert_eq!(mclk_to_h_counter(MCLKS_PER_SHORT_SCANLINE - 1, true), 339);
+
+        let ppu = new_test_ppu();
+        for h in 0..340 {
+            let mclk = ppu.h_counter_to_mclk(h);
+            assert_eq!(mclk_to_h_counter(mclk, false), h);
+            assert_eq!(mclk_to_h_counter(mclk.saturating_sub(1), false), h.saturating_sub(1));
+        }
+    }
+
+    #[test]
+    fn holds_hi_res_output_size() {
+        let mut ppu = new_test_ppu();
+        ppu.state.h_hi_res_frame = true;
+        ppu.update_resolution_hold();
+        ppu.prepare_output_frame();
+        assert_eq!(ppu.frame_size(), FrameSize { width: 512, height: 224 });
+
+        // A following 256px frame should be presented at 512px with every pixel doubled
+        ppu.state.h_hi_res_frame = false;
+        ppu.frame_buffer[0] = Color::rgb(1, 2, 3);
+        ppu.frame_buffer[1] = Color::rgb(4, 5, 6);
+        ppu.update_resolution_hold();
+        ppu.prepare_output_frame();
+        assert_eq!(ppu.frame_size(), FrameSize { width: 512, height: 224 });
+        assert_eq!(
+            ppu.frame_buffer()[..4],
+            [Color::rgb(1, 2, 3), Color::rgb(1, 2, 3), Color::rgb(4, 5, 6), Color::rgb(4, 5, 6)]
+        );
+
+        // The held resolution is kept for as long as the BG mode stays the same
+        for _ in 0..1000 {
+            ppu.update_resolution_hold();
+        }
+        ppu.prepare_output_frame();
+        assert_eq!(ppu.frame_size(), FrameSize { width: 512, height: 224 });
+
+        // Changing the BG mode drops the held resolution
+        ppu.registers.bg_mode = BgMode::One;
+        ppu.update_resolution_hold();
+        ppu.prepare_output_frame();
+        assert_eq!(ppu.frame_size(), FrameSize { width: 256, height: 224 });
+
+        // So does a reset
+        ppu.state.h_hi_res_frame = true;
+        ppu.update_resolution_hold();
+        ppu.state.h_hi_res_frame = false;
+        ppu.reset();
+        ppu.update_resolution_hold();
+        ppu.prepare_output_frame();
+        assert_eq!(ppu.frame_size(), FrameSize { width: 256, height: 224 });
+    }
+
+    #[test]
+    fn hi_res_blending() {
+        assert_eq!(
+            Color::rgb(127, 0, 127),
+            blend_colors(Color::rgb(255, 0, 0), Color::rgb(0, 0, 255))
+        );
+        assert_eq!(
+            Color::rgb(10, 20, 30),
+            blend_colors(Color::rgb(10, 20, 30), Color::rgb(10, 20, 30))
+        );
+    }
+
+    #[test]
+    fn priority_overlay_tints() {
+        let white = Color::rgb(255, 255, 255);
+        assert_eq!(tint_by_layer(white, Layer::Bg1), Color::rgb(255, 127, 127));
+        assert_eq!(tint_by_layer(white, Layer::Obj), Color::rgb(255, 127, 255));
+        assert_eq!(tint_by_layer(Color::BLACK, Layer::Backdrop), Color::BLACK);
+    }
 
     #[test]
     fn direct_color() {
@@ -2070,4 +2478,181 @@ mod tests {
 
         assert_eq!(0b11100_11110_11110, resolve_direct_color(0b111, 0b11_111_111));
     }
+
+    #[test]
+    fn cgram_address() {
+        // Mode 0: each BG layer has its own 2bpp palettes
+        assert_eq!(0x2E, pixel_cgram_address(Layer::Bg2, BgMode::Zero, 3, 2));
+        assert_eq!(0x0E, pixel_cgram_address(Layer::Bg3, BgMode::One, 3, 2));
+
+        // Sprites always use the second half of CGRAM
+        assert_eq!(0x95, pixel_cgram_address(Layer::Obj, BgMode::One, 1, 5));
+
+        // 8bpp BG1 indexes CGRAM directly
+        assert_eq!(0xC3, pixel_cgram_address(Layer::Bg1, BgMode::Three, 0, 0xC3));
+    }
+
+    #[test]
+    fn mode_7_extbg_ignores_direct_color() {
+        let mut cgram = [0; 256];
+        cgram[0x45] = 0x1234;
+
+        // BG1 uses direct color, but the EXTBG BG2 layer always reads from CGRAM
+        assert_eq!(
+            resolve_direct_color(0, 0x45),
+            resolve_pixel_color(&cgram, Layer::Bg1, BgMode::Seven, true, 0, 0x45)
+        );
+        assert_eq!(0x1234, resolve_pixel_color(&cgram, Layer::Bg2, BgMode::Seven, true, 0, 0x45));
+    }
+
+    #[test]
+    fn offset_per_tile_columns() {
+        // First visible column is never affected
+        assert_eq!(None, offset_per_tile_bg3_x(0, 0, 0));
+        assert_eq!(None, offset_per_tile_bg3_x(7, 0, 0));
+        assert_eq!(Some(0), offset_per_tile_bg3_x(8, 0, 0));
+        assert_eq!(Some(8), offset_per_tile_bg3_x(16, 0, 0));
+
+        // Column boundaries follow the BG's fine scroll, not the screen
+        assert_eq!(None, offset_per_tile_bg3_x(4, 3, 0));
+        assert_eq!(Some(0), offset_per_tile_bg3_x(5, 3, 0));
+        assert_eq!(Some(0), offset_per_tile_bg3_x(12, 3, 0));
+        assert_eq!(Some(8), offset_per_tile_bg3_x(13, 3, 0));
+
+        // BG3 fine scroll is ignored
+        assert_eq!(Some(0x20), offset_per_tile_bg3_x(8, 0, 0x25));
+        assert_eq!(Some(0x28), offset_per_tile_bg3_x(16, 0, 0x27));
+    }
+
+    const WHITE: u16 = 0x7FFF;
+
+    // Mode 5/6 BG1 tiles are 16px wide and use tile N and N+1; fill one row of both tiles with
+    // color 1 so that only that line of the BG is opaque
+    fn new_hi_res_test_ppu(opaque_tile_row: usize) -> Ppu {
+        let mut ppu = new_test_ppu();
+        ppu.write_port(0x2100, 0x0F);
+        ppu.write_port(0x2107, 0x7C);
+        ppu.write_port(0x212C, 0x01);
+        ppu.write_port(0x212D, 0x01);
+        ppu.cgram[1] = WHITE;
+        for tile in 0..2 {
+            ppu.vram[16 * tile + opaque_tile_row] = 0x00FF;
+        }
+        ppu
+    }
+
+    fn line_colors(ppu: &Ppu, line: u16, pixels: Range<u32>) -> Vec<Color> {
+        let screen_width = ppu.state.frame_screen_width();
+        pixels
+            .map(|pixel| ppu.frame_buffer[((u32::from(line) - 1) * screen_width + pixel) as usize])
+            .collect()
+    }
+
+    #[test]
+    fn interlace_enabled_mid_frame_in_hi_res_mode() {
+        let white = convert_snes_color(WHITE, 15);
+
+        for deinterlace in [false, true] {
+            for odd_frame in [false, true] {
+                // Tile row 1 is BG line 1, which is the even interlaced line for scanline 1
+                let mut ppu = new_hi_res_test_ppu(1);
+                ppu.deinterlace = deinterlace;
+                ppu.state.h_hi_res_frame = true;
+                ppu.state.odd_frame = odd_frame;
+
+                // Enable mode 5 + interlacing after the start of a non-interlaced frame
+                ppu.write_port(0x2105, 0x05);
+                ppu.write_port(0x2133, 0x01);
+                ppu.state.scanline = 1;
+                ppu.render_current_line(0);
+
+                let expected = if !deinterlace && odd_frame { Color::BLACK } else { white };
+                assert!(
+                    line_colors(&ppu, 1, 0..512).iter().all(|&color| color == expected),
+                    "deinterlace={deinterlace}, odd_frame={odd_frame}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pseudo_hi_res_mid_line_update_in_interlaced_frame() {
+        let mut ppu = new_hi_res_test_ppu(1);
+        ppu.deinterlace = false;
+        ppu.state.h_hi_res_frame = true;
+        ppu.state.v_hi_res_frame = true;
+
+        // Mode 1 + pseudo hi-res, with interlacing disabled after the start of an interlaced frame
+        ppu.write_port(0x2105, 0x01);
+        ppu.write_port(0x2133, 0x08);
+        ppu.state.scanline = 1;
+        ppu.render_current_line(0);
+        assert!(line_colors(&ppu, 1, 0..512).iter().all(|&color| color != Color::BLACK));
+
+        // Scroll BG1 down one line onto a transparent tile row, then redraw from pixel 16
+        ppu.write_port(0x210E, 0x01);
+        ppu.write_port(0x210E, 0x00);
+        ppu.render_current_line(16);
+        assert!(line_colors(&ppu, 1, 0..32).iter().all(|&color| color != Color::BLACK));
+        assert!(line_colors(&ppu, 1, 32..512).iter().all(|&color| color == Color::BLACK));
+    }
+
+    #[test]
+    fn offset_per_tile_keeps_fine_scroll() {
+        assert_eq!(0x0125, offset_per_tile_h_scroll(0x2123, 0x0005));
+        assert_eq!(0x03F8, offset_per_tile_h_scroll(0xFFFF, 0x0000));
+    }
+
+    #[test]
+    fn ppu1_open_bus() {
+        let mut ppu = new_test_ppu();
+
+        // Write $5A to OAM address 0 and read it back through RDOAM, which drives PPU1 open bus
+        ppu.write_port(0x2102, 0x00);
+        ppu.write_port(0x2103, 0x00);
+        ppu.write_port(0x2104, 0x5A);
+        ppu.write_port(0x2104, 0x00);
+        ppu.write_port(0x2102, 0x00);
+        ppu.write_port(0x2103, 0x00);
+        assert_eq!(Some(0x5A), ppu.read_port(0x2138));
+
+        // Write-only registers decoded by PPU1 return PPU1 open bus; other write-only registers
+        // return CPU open bus
+        for address in [0x2104, 0x2105, 0x2106, 0x2108, 0x2109, 0x210A, 0x2114, 0x2118, 0x212A] {
+            assert_eq!(Some(0x5A), ppu.read_port(address), "address={address:04X}");
+        }
+        for address in [0x2100, 0x2107, 0x210D, 0x2121, 0x2133] {
+            assert_eq!(None, ppu.read_port(address), "address={address:04X}");
+        }
+
+        // STAT77 bit 4 is PPU1 open bus
+        assert_eq!(0x10, ppu.read_port(0x213E).unwrap() & 0x10);
+    }
+
+    #[test]
+    fn ppu2_open_bus() {
+        let mut ppu = new_test_ppu();
+
+        // Write color $2A55 to CGRAM address 0 and read back the low byte, which drives PPU2 open bus
+        ppu.write_port(0x2121, 0x00);
+        ppu.write_port(0x2122, 0x55);
+        ppu.write_port(0x2122, 0x2A);
+        ppu.write_port(0x2121, 0x00);
+        assert_eq!(Some(0x55), ppu.read_port(0x213B));
+
+        // RDCGRAM high byte bit 7 is PPU2 open bus
+        assert_eq!(Some(0x2A), ppu.read_port(0x213B));
+
+        // OPHCT/OPVCT high byte bits 1-7 are PPU2 open bus
+        ppu.read_port(0x213F);
+        ppu.read_port(0x213C);
+        ppu.state.ppu2_open_bus.drive(0xF0);
+        assert_eq!(Some(0xF0), ppu.read_port(0x213C));
+
+        // STAT78 bit 5 is PPU2 open bus
+        ppu.state.ppu2_open_bus.drive(0x20);
+        assert_eq!(0x20, ppu.read_port(0x213F).unwrap() & 0x20);
+        ppu.state.ppu2_open_bus.drive(0x00);
+        assert_eq!(0x00, ppu.read_port(0x213F).unwrap() & 0x20);
+    }
 }
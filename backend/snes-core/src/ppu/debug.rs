@@ -11,6 +11,24 @@ impl Ppu {
         }
     }
 
+    /// Raw CGRAM contents as little-endian words, the same format that is written through $2122.
+    pub fn dump_cgram(&self) -> Vec<u8> {
+        self.cgram.iter().flat_map(|word| word.to_le_bytes()).collect()
+    }
+
+    /// Raw VRAM contents as little-endian words.
+    pub fn dump_vram(&self) -> Vec<u8> {
+        self.vram.iter().flat_map(|word| word.to_le_bytes()).collect()
+    }
+
+    pub fn restore_vram(&mut self, bytes: &[u8]) {
+        assert_eq!(bytes.len(), 2 * self.vram.len());
+
+        for (word, chunk) in self.vram.iter_mut().zip(bytes.chunks_exact(2)) {
+            *word = u16::from_le_bytes([chunk[0], chunk[1]]);
+        }
+    }
+
     #[allow(clippy::needless_range_loop)]
     pub fn copy_vram_2bpp(&self, out: &mut [Color], palette: u8, row_len: usize) {
         let mut registers = Registers::new();
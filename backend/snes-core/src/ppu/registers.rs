@@ -1,6 +1,7 @@
 use crate::ppu;
 use crate::ppu::Vram;
 use bincode::{Decode, Encode};
+use jgenesis_common::bus::OpenBus;
 use jgenesis_common::num::{GetBit, U16Ext, U24Ext};
 use std::cmp;
 
@@ -102,7 +103,8 @@ impl BgMode {
     }
 
     pub fn bg2_enabled(self) -> bool {
-        // BG2 is enabled in all modes except 6 and 7
+        // BG2 is enabled in all modes except 6 and 7. Mode 7 can still display BG2 through EXTBG,
+        // which is checked separately in Registers::bg2_layer_enabled()
         !matches!(self, Self::Six | Self::Seven)
     }
 
@@ -112,7 +114,8 @@ impl BgMode {
         match self {
             Self::Zero | Self::Four | Self::Five => BPP::Two,
             Self::One | Self::Two | Self::Three => BPP::Four,
-            // BG2 is not rendered in mode 6 or 7; return value doesn't matter
+            // BG2 is not rendered in mode 6, and Mode 7 EXTBG pixels come from the Mode 7 fetches
+            // rather than from BG2 tiles; return value doesn't matter
             Self::Six | Self::Seven => BPP::Eight,
         }
     }
@@ -461,6 +464,9 @@ impl AccessFlipflop {
 pub enum MidScanlineUpdate {
     Inidisp,
     Scroll,
+    // OAM writes do not need an equivalent: sprites for a line are evaluated and their tiles
+    // fetched during the previous line, so mid-line OAM writes can only affect later lines
+    Cgram,
 }
 
 #[derive(Debug, Clone, Encode, Decode)]
@@ -1168,21 +1174,21 @@ impl Registers {
         }
     }
 
-    pub fn read_ophct(&mut self, ppu2_open_bus: u8) -> u8 {
+    pub fn read_ophct(&mut self, ppu2_open_bus: OpenBus) -> u8 {
         // Bits 1-7 of high byte are PPU2 open bus
         let value = match self.h_counter_flipflop {
             AccessFlipflop::First => self.latched_h_counter.lsb(),
-            AccessFlipflop::Second => (ppu2_open_bus & 0xFE) | self.latched_h_counter.msb(),
+            AccessFlipflop::Second => ppu2_open_bus.merge(self.latched_h_counter.msb(), 0x01),
         };
         self.h_counter_flipflop = self.h_counter_flipflop.toggle();
         value
     }
 
-    pub fn read_opvct(&mut self, ppu2_open_bus: u8) -> u8 {
+    pub fn read_opvct(&mut self, ppu2_open_bus: OpenBus) -> u8 {
         // Bits 1-7 of high byte are PPU2 open bus
         let value = match self.v_counter_flipflop {
             AccessFlipflop::First => self.latched_v_counter.lsb(),
-            AccessFlipflop::Second => (ppu2_open_bus & 0xFE) | self.latched_v_counter.msb(),
+            AccessFlipflop::Second => ppu2_open_bus.merge(self.latched_v_counter.msb(), 0x01),
         };
         self.v_counter_flipflop = self.v_counter_flipflop.toggle();
         value
@@ -1201,6 +1207,11 @@ impl Registers {
         self.programmable_joypad_port = wrio;
     }
 
+    pub fn bg2_layer_enabled(&self) -> bool {
+        // In Mode 7, BG2 only exists when EXTBG is enabled
+        self.bg_mode.bg2_enabled() || (self.bg_mode == BgMode::Seven && self.extbg_enabled)
+    }
+
     #[allow(clippy::range_plus_one)]
     pub fn is_inside_window_1(&self, pixel: u16) -> bool {
         (self.window_1_left..self.window_1_right + 1).contains(&pixel)
@@ -59,6 +59,8 @@ pub enum CdRomError {
         "CD-ROM error detection check failed for track {track_number} sector {sector_number}; expected={expected:08X}, actual={actual:08X}"
     )]
     DiscReadInvalidChecksum { track_number: u8, sector_number: u32, expected: u32, actual: u32 },
+    #[error("Disc loading was cancelled")]
+    LoadCancelled,
 }
 
 pub type CdRomResult<T> = Result<T, CdRomError>;
@@ -1,11 +1,17 @@
 //! Code for reading CD-ROM files
 
+mod cache;
 mod chd;
 mod cuebin;
+mod progress;
 mod seekvec;
 
+pub use cache::SectorCacheConfig;
+pub use progress::LoadProgress;
+
 use crate::cdtime::CdTime;
 use crate::cue::{CueSheet, TrackMode, TrackType};
+use crate::reader::cache::SectorCache;
 use crate::reader::chd::ChdFile;
 use crate::reader::cuebin::CdBinFiles;
 use crate::reader::seekvec::SeekableVec;
@@ -14,7 +20,6 @@ use bincode::{Decode, Encode};
 use crc::Crc;
 use jgenesis_proc_macros::{FakeDecode, FakeEncode};
 use std::ffi::OsStr;
-use std::fs;
 use std::fs::File;
 use std::io::BufReader;
 use std::ops::Range;
@@ -56,6 +61,10 @@ impl Default for CdRomReader {
 }
 
 impl CdRomReader {
+    fn is_in_memory(&self) -> bool {
+        matches!(self, Self::CueBinMemory(_) | Self::ChdMemory(_))
+    }
+
     fn read_sector(
         &mut self,
         track_number: u8,
@@ -122,9 +131,14 @@ impl Mode2Form {
 pub struct CdRom {
     cue_sheet: CueSheet,
     reader: CdRomReader,
+    sector_cache: SectorCache,
 }
 
 impl CdRom {
+    fn new(cue_sheet: CueSheet, reader: CdRomReader) -> Self {
+        Self { cue_sheet, reader, sector_cache: SectorCache::default() }
+    }
+
     /// Open a CD-ROM reader that will read from the filesystem as needed.
     ///
     /// # Errors
@@ -141,7 +155,7 @@ impl CdRom {
     fn open_cue_bin<P: AsRef<Path>>(cue_path: P) -> CdRomResult<Self> {
         let (bin_files, cue_sheet) = CdBinFiles::create(cue_path, |path| File::open(path))?;
 
-        Ok(Self { cue_sheet, reader: CdRomReader::CueBin(bin_files) })
+        Ok(Self::new(cue_sheet, CdRomReader::CueBin(bin_files)))
     }
 
     fn open_chd<P: AsRef<Path>>(chd_path: P) -> CdRomResult<Self> {
@@ -153,7 +167,7 @@ impl CdRom {
         })?;
         let (chd_file, cue_sheet) = ChdFile::open(BufReader::new(file))?;
 
-        Ok(Self { cue_sheet, reader: CdRomReader::ChdFs(chd_file) })
+        Ok(Self::new(cue_sheet, CdRomReader::ChdFs(chd_file)))
     }
 
     /// Open a CD-ROM reader that will load the entire disc image into memory.
@@ -163,18 +177,38 @@ impl CdRom {
     /// Will return any error encountered while reading from disk, or if the CD-ROM metadata appears
     /// invalid.
     pub fn open_in_memory<P: AsRef<Path>>(path: P, format: CdRomFileFormat) -> CdRomResult<Self> {
+        Self::open_in_memory_with_progress(path, format, &LoadProgress::new())
+    }
+
+    /// Same as [`Self::open_in_memory`], but reports progress and checks for cancellation using
+    /// the given progress tracker. BIN files are read in parallel.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::open_in_memory`], and additionally returns [`CdRomError::LoadCancelled`]
+    /// if the load is cancelled.
+    pub fn open_in_memory_with_progress<P: AsRef<Path>>(
+        path: P,
+        format: CdRomFileFormat,
+        progress: &LoadProgress,
+    ) -> CdRomResult<Self> {
         let path = path.as_ref();
 
-        match format {
-            CdRomFileFormat::CueBin => Self::open_cue_bin_in_memory(path),
-            CdRomFileFormat::Chd => {
-                let chd_bytes = fs::read(path).map_err(|source| CdRomError::ChdOpen {
-                    path: path.display().to_string(),
-                    source,
-                })?;
-                Self::open_chd_in_memory(chd_bytes)
-            }
+        progress.start();
+        let result = match format {
+            CdRomFileFormat::CueBin => Self::open_cue_bin_in_memory_with_progress(path, progress),
+            CdRomFileFormat::Chd => progress
+                .read_file(path)
+                .map_err(|source| CdRomError::ChdOpen { path: path.display().to_string(), source })
+                .and_then(Self::open_chd_in_memory),
+        };
+        progress.finish();
+
+        if progress.is_cancelled() {
+            return Err(CdRomError::LoadCancelled);
         }
+
+        result
     }
 
     /// Open a CD-ROM reader that will read from CUE/BIN files that will be read into memory.
@@ -184,12 +218,19 @@ impl CdRom {
     /// Will return any error encountered while reading from disk, or if the CUE file appears to be
     /// invalid.
     pub fn open_cue_bin_in_memory<P: AsRef<Path>>(cue_path: P) -> CdRomResult<Self> {
+        Self::open_cue_bin_in_memory_with_progress(cue_path.as_ref(), &LoadProgress::new())
+    }
+
+    fn open_cue_bin_in_memory_with_progress(
+        cue_path: &Path,
+        progress: &LoadProgress,
+    ) -> CdRomResult<Self> {
         let (bin_files, cue_sheet) = CdBinFiles::create(cue_path, |path| {
-            let bin_bytes = fs::read(path)?;
+            let bin_bytes = progress.read_file(path)?;
             Ok(SeekableVec::new(bin_bytes))
         })?;
 
-        Ok(Self { reader: CdRomReader::CueBinMemory(bin_files), cue_sheet })
+        Ok(Self::new(cue_sheet, CdRomReader::CueBinMemory(bin_files)))
     }
 
     /// Open a CD-ROM reader that will read from a CHD file that has been read into memory.
@@ -201,7 +242,7 @@ impl CdRom {
         let seekable_vec = SeekableVec::new(chd_bytes);
         let (chd_file, cue_sheet) = ChdFile::open(seekable_vec)?;
 
-        Ok(Self { cue_sheet, reader: CdRomReader::ChdMemory(chd_file) })
+        Ok(Self::new(cue_sheet, CdRomReader::ChdMemory(chd_file)))
     }
 
     #[must_use]
@@ -209,6 +250,24 @@ impl CdRom {
         &self.cue_sheet
     }
 
+    /// Keep recently read sectors in memory, and after every cache miss, read ahead the configured
+    /// number of sectors on the same track. This reduces stalls when reading from slow storage
+    /// without loading the entire disc into memory. `None` disables the cache.
+    ///
+    /// Changing the config clears the cache. Discs that were loaded into memory never use a cache.
+    pub fn set_sector_cache(&mut self, config: Option<SectorCacheConfig>) {
+        if self.reader.is_in_memory() {
+            return;
+        }
+
+        let config =
+            config.unwrap_or(SectorCacheConfig { capacity_sectors: 0, prefetch_sectors: 0 });
+        if config != self.sector_cache.config() {
+            log::info!("Setting CD-ROM sector cache config to {config:?}");
+            self.sector_cache = SectorCache::new(config);
+        }
+    }
+
     /// Read a 2352-byte sector from the given track into a buffer.
     ///
     /// # Errors
@@ -242,15 +301,57 @@ impl CdRom {
             return Ok(());
         }
 
+        let track_mode = track.mode;
         let relative_sector_number = (relative_time - track.pregap_len).to_sector_number();
-        self.reader.read_sector(track_number, relative_sector_number, out)?;
+        let track_data_sectors =
+            (track.end_time - track.postgap_len - track.start_time - track.pregap_len)
+                .to_sector_number();
+        self.read_data_sector(track_number, relative_sector_number, track_data_sectors, out)?;
 
-        validate_edc(track.mode, track_number, relative_sector_number, out)?;
+        validate_edc(track_mode, track_number, relative_sector_number, out)?;
 
         // TODO check P/Q ECC?
 
         Ok(())
     }
+
+    fn read_data_sector(
+        &mut self,
+        track_number: u8,
+        relative_sector_number: u32,
+        track_data_sectors: u32,
+        out: &mut [u8],
+    ) -> CdRomResult<()> {
+        if !self.sector_cache.is_enabled() {
+            return self.reader.read_sector(track_number, relative_sector_number, out);
+        }
+
+        if self.sector_cache.read(track_number, relative_sector_number, out) {
+            return Ok(());
+        }
+
+        self.reader.read_sector(track_number, relative_sector_number, out)?;
+        self.sector_cache.insert(track_number, relative_sector_number, out);
+
+        // Read ahead while the file is already positioned at the next sector. Errors here are not
+        // fatal; the sector will be read again (and the error reported) if it's actually needed
+        let prefetch_end = track_data_sectors
+            .min(relative_sector_number.saturating_add(1 + self.sector_cache.prefetch_len()));
+        let mut buffer = [0; crate::BYTES_PER_SECTOR as usize];
+        for sector_number in relative_sector_number + 1..prefetch_end {
+            if self.sector_cache.contains(track_number, sector_number) {
+                continue;
+            }
+
+            if let Err(err) = self.reader.read_sector(track_number, sector_number, &mut buffer) {
+                log::debug!("Error prefetching track {track_number} sector {sector_number}: {err}");
+                break;
+            }
+            self.sector_cache.insert(track_number, sector_number, &buffer);
+        }
+
+        Ok(())
+    }
 }
 
 fn validate_edc(
@@ -333,3 +434,45 @@ fn time_component_to_bcd(component: u8) -> u8 {
     let lsb = component % 10;
     (msb << 4) | lsb
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn sector_cache_matches_uncached_reads() {
+        const SECTORS: u32 = 40;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let bin: Vec<u8> = (0..SECTORS)
+            .flat_map(|sector| vec![sector as u8; crate::BYTES_PER_SECTOR as usize])
+            .collect();
+        fs::write(dir.join("track.bin"), bin).unwrap();
+        fs::write(
+            dir.join("disc.cue"),
+            "FILE \"track.bin\" BINARY\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00\n",
+        )
+        .unwrap();
+
+        let cue_path = dir.join("disc.cue");
+        let mut uncached = CdRom::open(&cue_path, CdRomFileFormat::CueBin).unwrap();
+        let mut cached = CdRom::open(&cue_path, CdRomFileFormat::CueBin).unwrap();
+        cached
+            .set_sector_cache(Some(SectorCacheConfig { capacity_sectors: 8, prefetch_sectors: 4 }));
+
+        // Sequential reads, backwards seeks into evicted sectors, and reads near the end of the track
+        // where prefetching must stop
+        let mut expected = vec![0; crate::BYTES_PER_SECTOR as usize];
+        let mut actual = vec![0; crate::BYTES_PER_SECTOR as usize];
+        for sector in (0..20).chain([3, 2, 1]).chain(30..SECTORS) {
+            let time = CdTime::from_sector_number(sector);
+            uncached.read_sector(1, time, &mut expected).unwrap();
+            cached.read_sector(1, time, &mut actual).unwrap();
+
+            assert_eq!(expected, actual, "sector {sector}");
+            assert_eq!(actual[0], sector as u8);
+        }
+    }
+}
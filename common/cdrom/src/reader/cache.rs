@@ -0,0 +1,151 @@
+//! LRU cache of raw sectors for CD-ROM images that are read from the filesystem
+
+use jgenesis_proc_macros::{FakeDecode, FakeEncode};
+use std::collections::{BTreeMap, HashMap};
+
+const BYTES_PER_SECTOR: usize = crate::BYTES_PER_SECTOR as usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectorCacheConfig {
+    /// Maximum number of sectors to keep in the cache
+    pub capacity_sectors: u32,
+    /// Number of sectors after a missed sector to read ahead on the same track
+    pub prefetch_sectors: u32,
+}
+
+impl SectorCacheConfig {
+    #[must_use]
+    pub fn from_size_mb(size_mb: u32, prefetch_sectors: u32) -> Self {
+        let capacity_sectors = (u64::from(size_mb) * 1024 * 1024 / crate::BYTES_PER_SECTOR) as u32;
+        Self { capacity_sectors, prefetch_sectors }
+    }
+}
+
+// (track number, sector number relative to the start of the track's data)
+type SectorKey = (u8, u32);
+
+#[derive(Debug)]
+struct CachedSector {
+    data: Box<[u8; BYTES_PER_SECTOR]>,
+    last_used: u64,
+}
+
+// Not persisted in save states; the cache is moved over from the current disc when loading a state
+#[derive(Debug, FakeEncode, FakeDecode)]
+pub(crate) struct SectorCache {
+    config: SectorCacheConfig,
+    sectors: HashMap<SectorKey, CachedSector>,
+    // Keyed by last-used timestamp, so the first entry is always the least recently used sector
+    lru_order: BTreeMap<u64, SectorKey>,
+    timestamp: u64,
+}
+
+impl Default for SectorCache {
+    fn default() -> Self {
+        Self::new(SectorCacheConfig { capacity_sectors: 0, prefetch_sectors: 0 })
+    }
+}
+
+impl SectorCache {
+    pub(crate) fn new(config: SectorCacheConfig) -> Self {
+        Self { config, sectors: HashMap::new(), lru_order: BTreeMap::new(), timestamp: 0 }
+    }
+
+    pub(crate) fn config(&self) -> SectorCacheConfig {
+        self.config
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.config.capacity_sectors != 0
+    }
+
+    /// Number of sectors to read ahead after a miss. Never fills the entire cache, so that
+    /// prefetching cannot evict the sector that missed.
+    pub(crate) fn prefetch_len(&self) -> u32 {
+        self.config.prefetch_sectors.min(self.config.capacity_sectors.saturating_sub(1))
+    }
+
+    pub(crate) fn contains(&self, track_number: u8, sector_number: u32) -> bool {
+        self.sectors.contains_key(&(track_number, sector_number))
+    }
+
+    /// Copy a cached sector into `out` and mark it as most recently used. Returns `false` if the
+    /// sector is not in the cache.
+    pub(crate) fn read(&mut self, track_number: u8, sector_number: u32, out: &mut [u8]) -> bool {
+        let key = (track_number, sector_number);
+        let Some(sector) = self.sectors.get_mut(&key) else { return false };
+
+        self.lru_order.remove(&sector.last_used);
+        self.timestamp += 1;
+        sector.last_used = self.timestamp;
+        self.lru_order.insert(sector.last_used, key);
+
+        out[..BYTES_PER_SECTOR].copy_from_slice(sector.data.as_slice());
+        true
+    }
+
+    pub(crate) fn insert(&mut self, track_number: u8, sector_number: u32, data: &[u8]) {
+        if self.config.capacity_sectors == 0 {
+            return;
+        }
+
+        let key = (track_number, sector_number);
+        if let Some(existing) = self.sectors.remove(&key) {
+            self.lru_order.remove(&existing.last_used);
+        }
+
+        while self.sectors.len() >= self.config.capacity_sectors as usize {
+            let Some((_, evicted_key)) = self.lru_order.pop_first() else { break };
+            self.sectors.remove(&evicted_key);
+        }
+
+        self.timestamp += 1;
+        self.sectors.insert(
+            key,
+            CachedSector {
+                data: Box::new(data[..BYTES_PER_SECTOR].try_into().unwrap()),
+                last_used: self.timestamp,
+            },
+        );
+        self.lru_order.insert(self.timestamp, key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sector(value: u8) -> Vec<u8> {
+        vec![value; BYTES_PER_SECTOR]
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache =
+            SectorCache::new(SectorCacheConfig { capacity_sectors: 2, prefetch_sectors: 0 });
+        let mut out = sector(0);
+
+        cache.insert(1, 0, &sector(10));
+        cache.insert(1, 1, &sector(11));
+
+        // Reading sector 0 makes sector 1 the least recently used
+        assert!(cache.read(1, 0, &mut out));
+        assert_eq!(out, sector(10));
+
+        cache.insert(2, 0, &sector(20));
+        assert!(cache.contains(1, 0));
+        assert!(!cache.contains(1, 1));
+        assert!(cache.contains(2, 0));
+
+        assert!(!cache.read(1, 1, &mut out));
+        assert_eq!(out, sector(10));
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut cache =
+            SectorCache::new(SectorCacheConfig { capacity_sectors: 0, prefetch_sectors: 0 });
+        cache.insert(1, 0, &sector(10));
+        assert!(!cache.contains(1, 0));
+    }
+}
@@ -7,9 +7,14 @@ use bincode::{Decode, Encode};
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
 use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::sync::LazyLock;
-use std::{fs, io, mem};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::{fs, io, mem, thread};
+
+// Maximum number of threads used to open BIN files in parallel
+const MAX_OPEN_THREADS: usize = 8;
 
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct TrackMetadata {
@@ -40,33 +45,39 @@ impl<F: Read + Seek> CdBinFiles<F> {
         Self { files: HashMap::new(), track_metadata: Vec::new() }
     }
 
+    /// Parse a CUE file and open every BIN file that it references. BIN files are opened in
+    /// parallel on a bounded number of threads, which speeds up loading discs with many tracks
+    /// into memory.
     pub fn create<OpenFn, P: AsRef<Path>>(
         cue_path: P,
         bin_open_fn: OpenFn,
     ) -> CdRomResult<(Self, CueSheet)>
     where
-        OpenFn: for<'a> Fn(&'a Path) -> io::Result<F>,
+        F: Send,
+        OpenFn: for<'a> Fn(&'a Path) -> io::Result<F> + Sync,
     {
         let cue_path = cue_path.as_ref();
 
         let (cue_sheet, track_metadata) = parse_cue(cue_path)?;
 
-        let file_names: HashSet<_> =
-            track_metadata.iter().map(|metadata| metadata.file_name.clone()).collect();
+        let file_names: Vec<_> = track_metadata
+            .iter()
+            .map(|metadata| metadata.file_name.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
 
         let parent_dir = cue_path
             .parent()
             .ok_or_else(|| CdRomError::CueParentDir(cue_path.display().to_string()))?;
 
-        let mut files = HashMap::with_capacity(file_names.len());
-        for file_name in file_names {
-            let file_path = parent_dir.join(Path::new(&file_name));
-            let file = bin_open_fn(&file_path).map_err(|source| CdRomError::BinOpen {
-                path: file_path.display().to_string(),
-                source,
-            })?;
-            files.insert(file_name, CdRomFile::new(file));
-        }
+        // Discs can reference dozens of BIN files, so open them from a small fixed pool of worker
+        // threads rather than one thread per file
+        let num_workers = thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(MAX_OPEN_THREADS)
+            .min(file_names.len());
+        let files = open_bin_files(parent_dir, &file_names, &bin_open_fn, num_workers)?;
 
         let bin_files = Self { files, track_metadata };
         Ok((bin_files, cue_sheet))
@@ -309,6 +320,56 @@ impl CueParser {
     }
 }
 
+fn open_bin_files<F, OpenFn>(
+    parent_dir: &Path,
+    file_names: &[String],
+    bin_open_fn: &OpenFn,
+    num_workers: usize,
+) -> CdRomResult<HashMap<String, CdRomFile<F>>>
+where
+    F: Read + Seek + Send,
+    OpenFn: for<'a> Fn(&'a Path) -> io::Result<F> + Sync,
+{
+    let next_file = AtomicUsize::new(0);
+    // Set when any worker fails so that the others stop opening files that will be thrown away
+    let failed = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..num_workers)
+            .map(|_| {
+                let next_file = &next_file;
+                let failed = &failed;
+                scope.spawn(move || {
+                    let mut opened = Vec::new();
+                    while !failed.load(Ordering::Relaxed) {
+                        let Some(file_name) =
+                            file_names.get(next_file.fetch_add(1, Ordering::Relaxed))
+                        else {
+                            break;
+                        };
+
+                        let file_path = parent_dir.join(Path::new(file_name));
+                        let file = bin_open_fn(&file_path).map_err(|source| {
+                            failed.store(true, Ordering::Relaxed);
+                            CdRomError::BinOpen { path: file_path.display().to_string(), source }
+                        })?;
+                        opened.push((file_name.clone(), CdRomFile::new(file)));
+                    }
+                    Ok(opened)
+                })
+            })
+            .collect();
+
+        let mut files = HashMap::with_capacity(file_names.len());
+        for handle in handles {
+            let opened: CdRomResult<Vec<_>> =
+                handle.join().expect("BIN file open thread should not panic");
+            files.extend(opened?);
+        }
+        Ok(files)
+    })
+}
+
 fn parse_cue<P: AsRef<Path>>(cue_path: P) -> CdRomResult<(CueSheet, Vec<TrackMetadata>)> {
     let cue_path = cue_path.as_ref();
 
@@ -398,3 +459,33 @@ fn to_cue_sheet(
 
     Ok((CueSheet::new(tracks), track_metadata))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    #[test]
+    fn open_stops_after_first_error() {
+        const NUM_FILES: usize = 64;
+
+        let file_names: Vec<_> = (0..NUM_FILES).map(|i| format!("track{i:02}.bin")).collect();
+        let open_count = AtomicUsize::new(0);
+        let result = open_bin_files(
+            Path::new(""),
+            &file_names,
+            &|_: &Path| {
+                if open_count.fetch_add(1, Ordering::Relaxed) == 0 {
+                    return Err(io::Error::other("open failed"));
+                }
+                thread::sleep(Duration::from_millis(5));
+                Ok(Cursor::new(Vec::<u8>::new()))
+            },
+            4,
+        );
+
+        assert!(matches!(result, Err(CdRomError::BinOpen { .. })));
+        assert!(open_count.load(Ordering::Relaxed) < NUM_FILES);
+    }
+}
@@ -0,0 +1,127 @@
+//! Progress reporting and cancellation for loading disc images into memory
+
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+// Read files in chunks so that progress updates and cancellation requests are noticed promptly
+const CHUNK_LEN: u64 = 4 * 1024 * 1024;
+
+/// Progress of loading a disc image into memory. This is intended to be shared with another thread
+/// (e.g. a GUI) that displays progress and can cancel the load.
+#[derive(Debug, Default)]
+pub struct LoadProgress {
+    loaded_bytes: AtomicU64,
+    total_bytes: AtomicU64,
+    cancelled: AtomicBool,
+}
+
+impl LoadProgress {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether a disc image is currently being loaded.
+    #[must_use]
+    pub fn is_loading(&self) -> bool {
+        self.total_bytes.load(Ordering::Relaxed) != 0 && !self.is_cancelled()
+    }
+
+    /// Fraction of the disc image that has been loaded so far, from 0 to 1.
+    #[must_use]
+    pub fn fraction(&self) -> f32 {
+        let total = self.total_bytes.load(Ordering::Relaxed);
+        if total == 0 {
+            return 0.0;
+        }
+
+        (self.loaded_bytes.load(Ordering::Relaxed) as f64 / total as f64) as f32
+    }
+
+    /// Request that the current load stop as soon as possible, in which case it will fail with
+    /// [`CdRomError::LoadCancelled`](crate::CdRomError::LoadCancelled).
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the most recent load was cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn start(&self) {
+        self.loaded_bytes.store(0, Ordering::Relaxed);
+        self.total_bytes.store(0, Ordering::Relaxed);
+        self.cancelled.store(false, Ordering::Relaxed);
+    }
+
+    pub(crate) fn finish(&self) {
+        self.total_bytes.store(0, Ordering::Relaxed);
+    }
+
+    /// Read an entire file into memory, updating progress as it is read. May be called from
+    /// multiple threads at once to read several files in parallel.
+    pub(crate) fn read_file(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        self.total_bytes.fetch_add(len, Ordering::Relaxed);
+
+        let mut file = file.take(len);
+        let mut bytes = Vec::with_capacity(len as usize);
+        loop {
+            if self.is_cancelled() {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "disc loading cancelled"));
+            }
+
+            let chunk_len = (&mut file).take(CHUNK_LEN).read_to_end(&mut bytes)?;
+            if chunk_len == 0 {
+                return Ok(bytes);
+            }
+
+            self.loaded_bytes.fetch_add(chunk_len as u64, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_file_tracks_progress() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("progress.bin");
+        std::fs::write(&path, vec![0xAB; 1000]).unwrap();
+
+        let progress = LoadProgress::new();
+        progress.start();
+        let bytes = progress.read_file(&path).unwrap();
+
+        assert_eq!(bytes, vec![0xAB; 1000]);
+        assert!(progress.is_loading());
+        assert!((progress.fraction() - 1.0).abs() < f32::EPSILON);
+
+        progress.finish();
+        assert!(!progress.is_loading());
+    }
+
+    #[test]
+    fn cancelled_read_fails() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("progress.bin");
+        std::fs::write(&path, vec![0; 1000]).unwrap();
+
+        let progress = LoadProgress::new();
+        progress.start();
+        progress.cancel();
+        let result = progress.read_file(&path);
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Interrupted);
+        assert!(progress.is_cancelled());
+        assert!(!progress.is_loading());
+    }
+}
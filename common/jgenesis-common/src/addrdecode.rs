@@ -0,0 +1,164 @@
+//! Declarative bus address decoding.
+//!
+//! An [`AddressDecoder`] maps address ranges to bus-specific region values, optionally with a
+//! mirroring mask, so that a bus implementation can match on the decoded region instead of
+//! hand-writing a cascade of address range patterns. Because decoders are plain data, mirroring
+//! behavior can be tested without constructing the rest of the bus.
+
+/// The result of decoding an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decoded<R> {
+    pub region: R,
+    /// Offset from the start of the region's address range, after applying the mirroring mask
+    pub offset: u32,
+}
+
+/// An address range mapped to a region. Pass a list of these to [`AddressDecoder::new`].
+#[derive(Debug, Clone, Copy)]
+pub struct AddressRange<R> {
+    start: u32,
+    /// Inclusive
+    end: u32,
+    mask: u32,
+    region: R,
+}
+
+impl<R: Copy> AddressRange<R> {
+    /// Map the `start..=end` range to a region with no mirroring.
+    #[must_use]
+    pub const fn new(start: u32, end: u32, region: R) -> Self {
+        Self { start, end, mask: u32::MAX, region }
+    }
+
+    /// Map the `start..=end` range to a region that is mirrored every `size` bytes. `size` must be
+    /// a power of two.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two.
+    #[must_use]
+    #[track_caller]
+    pub const fn mirrored(start: u32, end: u32, size: u32, region: R) -> Self {
+        assert!(size.is_power_of_two(), "mirror size must be a power of two");
+
+        Self { start, end, mask: size - 1, region }
+    }
+}
+
+/// Maps address ranges to regions.
+///
+/// Decoders are meant to be built in a `const` so that invalid or overlapping ranges fail to
+/// compile and so that decoding needs no lazy initialization.
+#[derive(Debug, Clone)]
+pub struct AddressDecoder<R, const N: usize> {
+    // Sorted by start address and non-overlapping
+    ranges: [AddressRange<R>; N],
+}
+
+impl<R: Copy, const N: usize> AddressDecoder<R, N> {
+    /// # Panics
+    ///
+    /// Panics if any range has `end < start` or if any two ranges overlap.
+    #[must_use]
+    #[track_caller]
+    pub const fn new(mut ranges: [AddressRange<R>; N]) -> Self {
+        // Insertion sort by start address; iterators and sort() are not usable in const fn
+        let mut i = 1;
+        while i < N {
+            let mut j = i;
+            while j > 0 && ranges[j - 1].start > ranges[j].start {
+                let tmp = ranges[j];
+                ranges[j] = ranges[j - 1];
+                ranges[j - 1] = tmp;
+                j -= 1;
+            }
+            i += 1;
+        }
+
+        let mut i = 0;
+        while i < N {
+            assert!(ranges[i].start <= ranges[i].end, "invalid address range");
+            assert!(i == 0 || ranges[i - 1].end < ranges[i].start, "overlapping address ranges");
+            i += 1;
+        }
+
+        Self { ranges }
+    }
+
+    /// Decode an address, returning `None` if the address is not in any mapped range.
+    #[inline]
+    #[must_use]
+    pub fn decode(&self, address: u32) -> Option<Decoded<R>> {
+        let idx = self.ranges.partition_point(|range| range.end < address);
+        let range = self.ranges.get(idx).filter(|range| range.start <= address)?;
+
+        Some(Decoded { region: range.region, offset: (address - range.start) & range.mask })
+    }
+
+    /// Decode an address, returning only the region.
+    #[inline]
+    #[must_use]
+    pub fn region(&self, address: u32) -> Option<R> {
+        self.decode(address).map(|decoded| decoded.region)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Region {
+        Rom,
+        Ram,
+        Registers,
+    }
+
+    fn decoded(region: Region, offset: u32) -> Decoded<Region> {
+        Decoded { region, offset }
+    }
+
+    #[test]
+    fn decode() {
+        const DECODER: AddressDecoder<Region, 3> = AddressDecoder::new([
+            AddressRange::new(0x4000, 0x40FF, Region::Registers),
+            AddressRange::new(0x0000, 0x1FFF, Region::Rom),
+            AddressRange::mirrored(0x8000, 0xFFFF, 0x800, Region::Ram),
+        ]);
+        let decoder = &DECODER;
+
+        assert_eq!(decoder.decode(0x0000), Some(decoded(Region::Rom, 0x0000)));
+        assert_eq!(decoder.decode(0x1FFF), Some(decoded(Region::Rom, 0x1FFF)));
+        assert_eq!(decoder.decode(0x2000), None);
+        assert_eq!(decoder.decode(0x4010), Some(decoded(Region::Registers, 0x10)));
+        assert_eq!(decoder.decode(0x4100), None);
+
+        assert_eq!(decoder.decode(0x8000), Some(decoded(Region::Ram, 0x000)));
+        assert_eq!(decoder.decode(0x87FF), Some(decoded(Region::Ram, 0x7FF)));
+        assert_eq!(decoder.decode(0x8800), Some(decoded(Region::Ram, 0x000)));
+        assert_eq!(decoder.decode(0xFFFF), Some(decoded(Region::Ram, 0x7FF)));
+        assert_eq!(decoder.decode(0x10000), None);
+
+        assert_eq!(decoder.region(0x1234), Some(Region::Rom));
+    }
+
+    #[test]
+    fn full_address_space() {
+        let decoder = AddressDecoder::new([
+            AddressRange::new(0x00000000, 0x7FFFFFFF, Region::Rom),
+            AddressRange::new(0x80000000, 0xFFFFFFFF, Region::Ram),
+        ]);
+
+        assert_eq!(decoder.decode(0), Some(decoded(Region::Rom, 0)));
+        assert_eq!(decoder.decode(u32::MAX), Some(decoded(Region::Ram, 0x7FFFFFFF)));
+    }
+
+    #[test]
+    #[should_panic(expected = "overlapping")]
+    fn overlap() {
+        let _ = AddressDecoder::new([
+            AddressRange::new(0x0000, 0x1FFF, Region::Rom),
+            AddressRange::new(0x1000, 0x2FFF, Region::Ram),
+        ]);
+    }
+}
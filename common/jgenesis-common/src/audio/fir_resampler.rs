@@ -151,6 +151,16 @@ impl<const LPF_TAPS: usize, const ZERO_PADDING: usize> FirResampler<LPF_TAPS, ZE
         self.output.pop_front()
     }
 
+    /// Resample as if the source frequency was `factor` times the nominal source frequency. This is
+    /// for gradually pulling resamplers whose output is mixed together back in sync if one of them
+    /// drifts from the others; a factor very close to 1 changes pitch inaudibly, unlike dropping
+    /// or repeating samples.
+    #[inline]
+    pub fn adjust_source_frequency(&mut self, factor: f64) {
+        self.padded_scaled_source_frequency =
+            Self::pad_and_scale_frequency(self.original_source_frequency * factor);
+    }
+
     #[inline]
     pub fn update_output_frequency(&mut self, output_frequency: u64) {
         self.output_frequency = output_frequency;
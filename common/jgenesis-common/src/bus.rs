@@ -0,0 +1,75 @@
+//! Helpers for bus behavior that is common across systems: open bus values and reads from unmapped
+//! regions.
+
+use bincode::{Decode, Encode};
+
+/// The last value driven onto a data bus, which is what reads from unmapped addresses return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct OpenBus {
+    value: u8,
+}
+
+impl OpenBus {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { value: 0 }
+    }
+
+    /// The last value driven onto the bus.
+    #[inline]
+    #[must_use]
+    pub fn value(&self) -> u8 {
+        self.value
+    }
+
+    #[inline]
+    pub fn drive(&mut self, value: u8) {
+        self.value = value;
+    }
+
+    /// Resolve a read that may have hit an unmapped address. Mapped reads (`Some`) drive the bus
+    /// and return the read value; unmapped reads (`None`) return the current open bus value.
+    #[inline]
+    pub fn read(&mut self, value: Option<u8>) -> u8 {
+        match value {
+            Some(value) => {
+                self.value = value;
+                value
+            }
+            None => self.value,
+        }
+    }
+
+    /// Combine a value that only drives some bits of the bus with open bus. Bits set in
+    /// `driven_mask` come from `value`, and all other bits come from open bus.
+    #[inline]
+    #[must_use]
+    pub fn merge(&self, value: u8, driven_mask: u8) -> u8 {
+        (value & driven_mask) | (self.value & !driven_mask)
+    }
+}
+
+impl Default for OpenBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unmapped_reads() {
+        let mut open_bus = OpenBus::new();
+
+        assert_eq!(open_bus.read(Some(0x12)), 0x12);
+        assert_eq!(open_bus.read(None), 0x12);
+        assert_eq!(open_bus.merge(0x80, 0x80), 0x92);
+        assert_eq!(open_bus.merge(0x00, 0xF0), 0x02);
+
+        open_bus.drive(0x34);
+        assert_eq!(open_bus.read(None), 0x34);
+        assert_eq!(open_bus.value(), 0x34);
+    }
+}
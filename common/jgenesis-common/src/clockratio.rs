@@ -0,0 +1,132 @@
+//! Fixed-point clock dividers and multipliers for overclocking settings that allow fractional values.
+
+use bincode::{Decode, Encode};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroU32;
+use std::str::FromStr;
+
+/// A positive clock divider or multiplier with a precision of 1/100.
+///
+/// Stored as fixed-point so that configs containing it can still be compared for equality.
+/// Serializes as a plain number, so configs that previously stored an integer divider or
+/// multiplier deserialize to the same value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode)]
+pub struct ClockRatio(NonZeroU32);
+
+impl ClockRatio {
+    pub const SCALE: u32 = 100;
+
+    /// Granularity used by UI controls that select a fractional ratio.
+    pub const STEP: f64 = 0.05;
+
+    /// # Panics
+    ///
+    /// Panics if `value` is 0 or too large to represent.
+    #[must_use]
+    pub const fn from_integer(value: u32) -> Self {
+        match value.checked_mul(Self::SCALE) {
+            Some(hundredths) => match NonZeroU32::new(hundredths) {
+                Some(hundredths) => Self(hundredths),
+                None => panic!("Clock ratio must be non-zero"),
+            },
+            None => panic!("Clock ratio is too large"),
+        }
+    }
+
+    /// Round the given value to the nearest 1/100. Returns `None` if the result would not be
+    /// positive.
+    #[must_use]
+    pub fn from_f64(value: f64) -> Option<Self> {
+        let hundredths = (value * f64::from(Self::SCALE)).round();
+        if !(1.0..=f64::from(u32::MAX)).contains(&hundredths) {
+            return None;
+        }
+
+        NonZeroU32::new(hundredths as u32).map(Self)
+    }
+
+    #[must_use]
+    pub fn to_f64(self) -> f64 {
+        f64::from(self.0.get()) / f64::from(Self::SCALE)
+    }
+
+    /// Multiply a cycle count by this ratio, carrying the fractional part of the result in
+    /// `remainder` so that no cycles are lost across repeated calls.
+    #[must_use]
+    #[inline]
+    pub fn scale_cycles(self, cycles: u64, remainder: &mut u32) -> u64 {
+        let scale = u64::from(Self::SCALE);
+        let scaled = cycles * u64::from(self.0.get()) + u64::from(*remainder);
+        *remainder = (scaled % scale) as u32;
+        scaled / scale
+    }
+}
+
+impl Display for ClockRatio {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_f64())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseClockRatioError(String);
+
+impl Display for ParseClockRatioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid clock ratio '{}'; expected a positive number", self.0)
+    }
+}
+
+impl Error for ParseClockRatioError {}
+
+impl FromStr for ClockRatio {
+    type Err = ParseClockRatioError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<f64>().ok().and_then(Self::from_f64).ok_or_else(|| ParseClockRatioError(s.into()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ClockRatio {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.to_f64())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ClockRatio {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = f64::deserialize(deserializer)?;
+        Self::from_f64(value).ok_or_else(|| {
+            serde::de::Error::custom(format!("invalid clock ratio {value}; must be positive"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_cycles_carries_remainder() {
+        let ratio = ClockRatio::from_f64(2.5).unwrap();
+
+        let mut remainder = 0;
+        let total: u64 = (0..4).map(|_| ratio.scale_cycles(3, &mut remainder)).sum();
+        assert_eq!(total, 30);
+        assert_eq!(remainder, 0);
+
+        assert_eq!(ClockRatio::from_integer(15).scale_cycles(4, &mut remainder), 60);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("15".parse(), Ok(ClockRatio::from_integer(15)));
+        assert_eq!("1.25".parse::<ClockRatio>().map(ClockRatio::to_f64), Ok(1.25));
+        assert!("0".parse::<ClockRatio>().is_err());
+        assert!("-2".parse::<ClockRatio>().is_err());
+        assert!("fast".parse::<ClockRatio>().is_err());
+    }
+}
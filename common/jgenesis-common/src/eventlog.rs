@@ -0,0 +1,142 @@
+//! Timestamped log of cross-CPU events in multi-CPU systems (e.g. communication register writes and
+//! interrupts that one CPU raises for another), displayed in the debugger's event log view.
+//!
+//! All CPUs in a system log to the same [`EventLog`] using a shared timestamp, so the log shows the
+//! order in which handshakes between CPUs actually happened. Timestamps are in master clock cycles
+//! of whichever clock the system uses as its time base.
+//!
+//! The log is only used for debugging. It is not persisted in save states, and nothing is recorded
+//! unless it has been enabled.
+
+use jgenesis_proc_macros::{FakeDecode, FakeEncode};
+use std::collections::VecDeque;
+
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedEvent {
+    pub timestamp: u64,
+    /// The CPU that caused the event, e.g. "68000" or "Master SH-2"
+    pub source: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, FakeEncode, FakeDecode)]
+pub struct EventLog {
+    enabled: bool,
+    timestamp: u64,
+    capacity: usize,
+    events: VecDeque<LoggedEvent>,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl EventLog {
+    /// Create a new log that retains up to `capacity` events, discarding the oldest events once it
+    /// is full.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self { enabled: false, timestamp: 0, capacity, events: VecDeque::new() }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// The current time, which events logged using [`Self::log`] are stamped with.
+    #[inline]
+    #[must_use]
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    #[inline]
+    pub fn advance(&mut self, cycles: u64) {
+        self.timestamp += cycles;
+    }
+
+    #[inline]
+    pub fn set_timestamp(&mut self, timestamp: u64) {
+        self.timestamp = timestamp;
+    }
+
+    /// Record an event at the current time. The message is only formatted if the log is enabled.
+    #[inline]
+    pub fn log(&mut self, source: &'static str, message: impl FnOnce() -> String) {
+        self.log_at(self.timestamp, source, message);
+    }
+
+    /// Record an event at a specific time, for CPUs that track their own position relative to the
+    /// log's time base (e.g. CPUs that execute in batches ahead of or behind the main CPU).
+    #[inline]
+    pub fn log_at(
+        &mut self,
+        timestamp: u64,
+        source: &'static str,
+        message: impl FnOnce() -> String,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back(LoggedEvent { timestamp, source, message: message() });
+    }
+
+    /// Logged events, ordered by timestamp. Events with the same timestamp are returned in the order
+    /// that they were logged.
+    #[must_use]
+    pub fn events(&self) -> Vec<&LoggedEvent> {
+        let mut events: Vec<_> = self.events.iter().collect();
+        events.sort_by_key(|event| event.timestamp);
+        events
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_log_records_nothing() {
+        let mut log = EventLog::default();
+        log.log("CPU", || panic!("message should not be formatted"));
+        assert!(log.events().is_empty());
+    }
+
+    #[test]
+    fn events_sorted_by_timestamp() {
+        let mut log = EventLog::new(3);
+        log.set_enabled(true);
+
+        log.advance(100);
+        log.log("A", || "first".into());
+        log.log_at(50, "B", || "earlier".into());
+        log.log_at(100, "B", || "same time".into());
+        log.log_at(75, "B", || "overflow".into());
+
+        let events: Vec<_> = log
+            .events()
+            .into_iter()
+            .map(|event| (event.timestamp, event.message.as_str()))
+            .collect();
+        // "first" was the oldest logged event, so it is the one discarded
+        assert_eq!(events, vec![(50, "earlier"), (75, "overflow"), (100, "same time")]);
+    }
+}
@@ -0,0 +1,50 @@
+//! File writing helpers that are safe to use when multiple emulator processes share the same config,
+//! save, and save state files
+
+use std::path::{Path, PathBuf};
+use std::{fs, io, process};
+
+/// Path of a temporary file next to `path` whose name is unique to the current process, e.g.
+/// `game.srm.1234.tmp` for `game.srm`.
+#[must_use]
+pub fn process_temp_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{}.tmp", process::id()));
+    path.with_file_name(file_name)
+}
+
+/// Write a file by writing to a process-unique temp file and then renaming it over `path`, so that
+/// other processes never observe a partially written file.
+///
+/// # Errors
+///
+/// Propagates any error encountered while writing the temp file or renaming it.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let temp_path = process_temp_path(path);
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temp_path_is_per_process() {
+        let temp_path = process_temp_path(Path::new("saves/game.srm"));
+        assert_eq!(temp_path, PathBuf::from(format!("saves/game.srm.{}.tmp", process::id())));
+    }
+
+    #[test]
+    fn atomic_write_replaces_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        fs::write(&path, "old contents").unwrap();
+
+        write_atomic(&path, "new").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert!(!process_temp_path(&path).exists());
+    }
+}
@@ -1,8 +1,10 @@
 use bincode::{Decode, Encode};
 use jgenesis_proc_macros::{EnumAll, EnumDisplay, EnumFromStr};
+use std::convert::Infallible;
 use std::error::Error;
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
+use std::io;
 use std::num::NonZeroU32;
 
 #[repr(C)]
@@ -39,12 +41,74 @@ impl Default for Color {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Encode, Decode)]
 pub struct FrameSize {
     pub width: u32,
     pub height: u32,
 }
 
+/// A rectangular region of a frame buffer to display, e.g. to crop out borders or overscan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Encode, Decode)]
+pub struct FrameCrop {
+    pub left: u32,
+    pub top: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl FrameCrop {
+    /// A crop region that covers the entire frame.
+    #[must_use]
+    #[inline]
+    pub fn full(frame_size: FrameSize) -> Self {
+        Self { left: 0, top: 0, width: frame_size.width, height: frame_size.height }
+    }
+
+    /// The size of the frame after cropping.
+    #[must_use]
+    #[inline]
+    pub fn size(self) -> FrameSize {
+        FrameSize { width: self.width, height: self.height }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_full(self, frame_size: FrameSize) -> bool {
+        self == Self::full(frame_size)
+    }
+
+    /// Returns whether this region lies entirely within a frame of the given size.
+    #[must_use]
+    pub fn fits_within(self, frame_size: FrameSize) -> bool {
+        self.left.checked_add(self.width).is_some_and(|right| right <= frame_size.width)
+            && self.top.checked_add(self.height).is_some_and(|bottom| bottom <= frame_size.height)
+    }
+}
+
+/// Copy the `crop` region out of a frame buffer with the given frame size.
+///
+/// # Panics
+///
+/// This function will panic if the crop region does not fit within the frame or if the frame
+/// buffer is smaller than the frame size.
+#[must_use]
+pub fn crop_frame_buffer(
+    frame_buffer: &[Color],
+    frame_size: FrameSize,
+    crop: FrameCrop,
+) -> Vec<Color> {
+    assert!(crop.fits_within(frame_size), "crop {crop:?} does not fit within frame {frame_size:?}");
+
+    let frame_width = frame_size.width as usize;
+    let mut cropped = Vec::with_capacity((crop.width * crop.height) as usize);
+    let rows =
+        frame_buffer.chunks_exact(frame_width).skip(crop.top as usize).take(crop.height as usize);
+    for row in rows {
+        cropped.extend_from_slice(&row[crop.left as usize..(crop.left + crop.width) as usize]);
+    }
+    cropped
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct DisplayArea {
     pub width: u32,
@@ -108,6 +172,86 @@ pub trait Renderer {
         frame_size: FrameSize,
         pixel_aspect_ratio: Option<PixelAspectRatio>,
     ) -> Result<(), Self::Err>;
+
+    /// Render only the `crop` region of a frame, for cores whose displayed area can change from
+    /// frame to frame (e.g. optional border cropping). `frame_size` is the size of the entire frame
+    /// buffer and the pixel aspect ratio applies to the cropped frame.
+    ///
+    /// The default implementation copies the cropped region to a new buffer and passes it to
+    /// [`Self::render_frame`]. Renderers that can read a region of the original buffer directly
+    /// should override this.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if it is unable to render the frame.
+    fn render_cropped_frame(
+        &mut self,
+        frame_buffer: &[Color],
+        frame_size: FrameSize,
+        crop: FrameCrop,
+        pixel_aspect_ratio: Option<PixelAspectRatio>,
+    ) -> Result<(), Self::Err> {
+        if crop.is_full(frame_size) {
+            return self.render_frame(frame_buffer, frame_size, pixel_aspect_ratio);
+        }
+
+        let cropped = crop_frame_buffer(frame_buffer, frame_size, crop);
+        self.render_frame(&cropped, crop.size(), pixel_aspect_ratio)
+    }
+}
+
+/// An owned copy of a rendered frame, e.g. for save state thumbnails.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct RgbaFrame {
+    pub width: u32,
+    pub height: u32,
+    pub pixel_aspect_ratio: Option<PixelAspectRatio>,
+    pub pixels: Vec<Color>,
+}
+
+// Renderer that copies the frame buffer instead of displaying it
+#[derive(Debug, Default)]
+pub(crate) struct FrameCapture {
+    pub(crate) frame: Option<RgbaFrame>,
+}
+
+impl Renderer for FrameCapture {
+    type Err = Infallible;
+
+    fn render_frame(
+        &mut self,
+        frame_buffer: &[Color],
+        frame_size: FrameSize,
+        pixel_aspect_ratio: Option<PixelAspectRatio>,
+    ) -> Result<(), Self::Err> {
+        let len = (frame_size.width * frame_size.height) as usize;
+        self.frame = Some(RgbaFrame {
+            width: frame_size.width,
+            height: frame_size.height,
+            pixel_aspect_ratio,
+            pixels: frame_buffer[..len].to_vec(),
+        });
+
+        Ok(())
+    }
+
+    fn render_cropped_frame(
+        &mut self,
+        frame_buffer: &[Color],
+        frame_size: FrameSize,
+        crop: FrameCrop,
+        pixel_aspect_ratio: Option<PixelAspectRatio>,
+    ) -> Result<(), Self::Err> {
+        // Avoid copying the cropped region twice
+        self.frame = Some(RgbaFrame {
+            width: crop.width,
+            height: crop.height,
+            pixel_aspect_ratio,
+            pixels: crop_frame_buffer(frame_buffer, frame_size, crop),
+        });
+
+        Ok(())
+    }
 }
 
 pub trait AudioOutput {
@@ -121,6 +265,18 @@ pub trait AudioOutput {
     fn push_sample(&mut self, sample_l: f64, sample_r: f64) -> Result<(), Self::Err>;
 }
 
+/// Audio output that discards all samples.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullAudioOutput;
+
+impl AudioOutput for NullAudioOutput {
+    type Err = Infallible;
+
+    fn push_sample(&mut self, _sample_l: f64, _sample_r: f64) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
 pub trait SaveWriter {
     type Err;
 
@@ -157,6 +313,34 @@ pub trait SaveWriter {
     fn persist_serialized<E: Encode>(&mut self, extension: &str, data: E) -> Result<(), Self::Err>;
 }
 
+/// Save writer that never loads or persists anything, for frontends that do not need save files.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullSaveWriter;
+
+impl SaveWriter for NullSaveWriter {
+    type Err = io::Error;
+
+    fn load_bytes(&mut self, _extension: &str) -> Result<Vec<u8>, Self::Err> {
+        Err(io::ErrorKind::NotFound.into())
+    }
+
+    fn persist_bytes(&mut self, _extension: &str, _bytes: &[u8]) -> Result<(), Self::Err> {
+        Ok(())
+    }
+
+    fn load_serialized<D: Decode>(&mut self, _extension: &str) -> Result<D, Self::Err> {
+        Err(io::ErrorKind::NotFound.into())
+    }
+
+    fn persist_serialized<E: Encode>(
+        &mut self,
+        _extension: &str,
+        _data: E,
+    ) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
 pub trait PartialClone {
     /// Create a partial clone of `self`, which clones all emulation state but may not clone
     /// read-only fields such as ROMs and frame buffers.
@@ -164,6 +348,12 @@ pub trait PartialClone {
     fn partial_clone(&self) -> Self;
 }
 
+impl<T: PartialClone> PartialClone for Box<T> {
+    fn partial_clone(&self) -> Self {
+        Box::new(T::partial_clone(self))
+    }
+}
+
 use crate::input::Player;
 pub use jgenesis_proc_macros::PartialClone;
 
@@ -186,20 +376,132 @@ pub enum TickEffect {
 
 pub type TickResult<Err> = Result<TickEffect, Err>;
 
+/// Points during emulation at which a core's state can be saved and later restored without
+/// changing emulation results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveStateSafePoint {
+    /// Between any two calls to `tick()`, i.e. after the current instruction completes
+    Tick,
+    /// Only after a call to `tick()` that returned [`TickEffect::FrameRendered`]
+    FrameBoundary,
+}
+
+/// Game information parsed from a cartridge or disc header.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode)]
+pub struct GameMetadata {
+    pub title: Option<String>,
+    /// Serial number / product code as written in the header
+    pub serial: Option<String>,
+    /// Region the header says the game was released for
+    pub region: Option<String>,
+    /// Descriptions of the game-specific workarounds that apply to this game
+    pub quirks: Vec<String>,
+}
+
+impl GameMetadata {
+    /// Convert a fixed-length header string field for display: trims and collapses runs of
+    /// whitespace, treating control characters and $FF padding as whitespace. Half-width katakana
+    /// (single-byte JIS X 0201 / Shift-JIS) are decoded. Any other non-ASCII character, e.g. a
+    /// double-byte Shift-JIS character, is shown as U+FFFD so that it's visible that part of the
+    /// title could not be decoded. Returns `None` if nothing is left.
+    #[must_use]
+    pub fn parse_header_string(bytes: &[u8]) -> Option<String> {
+        let mut s = String::with_capacity(bytes.len());
+        let mut bytes = bytes.iter().copied();
+        while let Some(b) = bytes.next() {
+            match b {
+                0x20..=0x7E => s.push(char::from(b)),
+                0x00..=0x1F | 0x7F | 0xFF => s.push(' '),
+                0xA1..=0xDF => s.extend(char::from_u32(0xFF61 + u32::from(b - 0xA1))),
+                0x81..=0x9F | 0xE0..=0xFC => {
+                    // Shift-JIS lead byte; the next byte is part of the same character
+                    bytes.next();
+                    s.push(char::REPLACEMENT_CHARACTER);
+                }
+                _ => s.push(char::REPLACEMENT_CHARACTER),
+            }
+        }
+        let s = s.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        (!s.is_empty()).then_some(s)
+    }
+}
+
+impl Display for GameMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let field = |value: &Option<String>| value.as_deref().unwrap_or("(unknown)").to_owned();
+        write!(
+            f,
+            "title={}, serial={}, region={}",
+            field(&self.title),
+            field(&self.serial),
+            field(&self.region)
+        )?;
+
+        if !self.quirks.is_empty() {
+            write!(f, ", workarounds=[{}]", self.quirks.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Analog input passed from the frontend to [`MappableInputs::handle_analog_input`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnalogInput {
+    /// Mouse cursor position in frame pixels. This is not clamped to the frame, so coordinates can
+    /// be negative or past the frame edges if the cursor is outside of the display area.
+    MousePosition { x: f64, y: f64, frame_size: FrameSize },
+    /// Relative mouse motion in window pixels
+    MouseMotion { dx: i32, dy: i32 },
+    /// The mouse cursor left the window
+    MouseLeave,
+    /// Gamepad axis value after applying the axis deadzone, saturation, and response curve. Ranges
+    /// from -1.0 to 1.0 for sticks and from 0.0 to 1.0 for triggers.
+    GamepadAxis { player: Player, axis_idx: u8, value: f64 },
+}
+
+impl AnalogInput {
+    /// For mouse position input, the position rounded to the nearest frame pixel. Returns `None`
+    /// for other inputs or if the cursor is outside of the frame.
+    #[must_use]
+    pub fn frame_position(self) -> Option<(u16, u16)> {
+        let Self::MousePosition { x, y, frame_size } = self else { return None };
+
+        let in_frame = (0.0..f64::from(frame_size.width)).contains(&x)
+            && (0.0..f64::from(frame_size.height)).contains(&y);
+        in_frame.then(|| (x.round() as u16, y.round() as u16))
+    }
+}
+
+/// Keyboard text entry passed from the frontend to [`EmulatorTrait::handle_text_input`]. This is
+/// separate from button mappings so that typing text does not also press mapped buttons.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextInputEvent {
+    /// Text typed by the user, after applying the keyboard layout and any input method
+    Text(String),
+    /// Delete the character before the cursor
+    Backspace,
+    /// Confirm the entered text
+    Submit,
+    /// Cancel text entry
+    Cancel,
+}
+
 pub trait MappableInputs<Button> {
     fn set_field(&mut self, button: Button, player: Player, pressed: bool);
 
+    /// Current state of every button for the given player, in a stable order. Used to display
+    /// inputs on screen.
     #[allow(unused_variables)]
-    fn handle_mouse_motion(
-        &mut self,
-        x: i32,
-        y: i32,
-        frame_size: FrameSize,
-        display_area: DisplayArea,
-    ) {
+    fn button_states(&self, player: Player) -> Vec<(Button, bool)> {
+        vec![]
     }
 
-    fn handle_mouse_leave(&mut self) {}
+    /// Handle analog input, for devices that read positions or axis values directly rather than
+    /// only mapping them to buttons.
+    #[allow(unused_variables)]
+    fn handle_analog_input(&mut self, input: AnalogInput) {}
 }
 
 pub trait EmulatorConfigTrait: Clone {
@@ -207,6 +509,17 @@ pub trait EmulatorConfigTrait: Clone {
     fn with_overclocking_disabled(&self) -> Self {
         self.clone()
     }
+
+    /// Return a copy of this config with every setting that alters emulation away from real
+    /// hardware behavior reverted to its accurate value: overclocks, sprite limit removal, blending
+    /// hacks, layer and sound channel toggles, etc.
+    ///
+    /// This is what frontends apply in purist mode. The default implementation only disables
+    /// overclocking.
+    #[must_use]
+    fn with_enhancements_disabled(&self) -> Self {
+        self.with_overclocking_disabled()
+    }
 }
 
 pub trait EmulatorTrait: Encode + Decode + PartialClone {
@@ -247,14 +560,75 @@ pub trait EmulatorTrait: Encode + Decode + PartialClone {
     where
         R: Renderer;
 
+    /// Copy the current frame buffer as RGBA pixels without displaying it.
+    ///
+    /// Returns `None` if the core did not render anything.
+    #[must_use]
+    fn current_frame_rgba(&mut self) -> Option<RgbaFrame> {
+        let mut capture = FrameCapture::default();
+        let Ok(()) = self.force_render(&mut capture);
+        capture.frame
+    }
+
+    /// Hash of the current frame buffer, for comparing against known-good output in regression
+    /// tests.
+    ///
+    /// Returns `None` if the core did not render anything.
+    #[must_use]
+    fn frame_hash(&mut self) -> Option<u64> {
+        self.current_frame_rgba().map(|frame| crate::hash::frame_hash(&frame))
+    }
+
+    /// Returns whether the system is still running a BIOS boot sequence (boot animation, license
+    /// screen, etc.) and has not yet started running game code. Frontends can use this to
+    /// fast-forward through the boot sequence.
+    #[must_use]
+    fn is_booting(&self) -> bool {
+        false
+    }
+
+    /// Unmute every audio channel that was muted for debugging, e.g. when the debugger closes.
+    fn clear_audio_mutes(&mut self) {}
+
+    /// Whether the emulated system currently accepts keyboard text entry, e.g. because a keyboard
+    /// is attached. While this returns true, the frontend sends typed text to
+    /// [`Self::handle_text_input`] instead of mapping printable keys to buttons or hotkeys.
+    #[must_use]
+    fn wants_text_input(&self) -> bool {
+        false
+    }
+
+    #[allow(unused_variables)]
+    fn handle_text_input(&mut self, event: TextInputEvent) {}
+
+    /// Apply a new config to the running emulator.
+    ///
+    /// Implementations should apply every setting that can safely change mid-game. Settings that
+    /// determine the emulated hardware (e.g. forced timing mode or region) are only read when the
+    /// emulator is created, and frontends should not allow changing them while a game is running.
     fn reload_config(&mut self, config: &Self::Config);
 
     fn take_rom_from(&mut self, other: &mut Self);
 
+    /// Title, serial number, and region from the game's header, for display in window titles and
+    /// similar. Cores for systems without a usable header return empty metadata.
+    ///
+    /// This takes `&mut self` because some cores can only read the header through the cartridge
+    /// mapper.
+    fn game_metadata(&mut self) -> GameMetadata {
+        GameMetadata::default()
+    }
+
     fn soft_reset(&mut self);
 
     fn hard_reset<S: SaveWriter>(&mut self, save_writer: &mut S);
 
+    /// When this core's state can be safely saved or loaded. Frontends should defer save state
+    /// operations requested at other times (e.g. while stopped mid-frame in a debugger) until the
+    /// next safe point.
+    #[must_use]
+    fn save_state_safe_point() -> SaveStateSafePoint;
+
     // All cores start at save state version 0; they can override this function when they need to change it
     #[must_use]
     fn save_state_version() -> u16 {
@@ -265,3 +639,52 @@ pub trait EmulatorTrait: Encode + Decode + PartialClone {
 
     fn update_audio_output_frequency(&mut self, output_frequency: u64);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crop() {
+        let frame_size = FrameSize { width: 4, height: 3 };
+        let frame_buffer: Vec<_> = (0..12).map(|i| Color::rgb(i, 0, 0)).collect();
+
+        let crop = FrameCrop { left: 1, top: 1, width: 2, height: 2 };
+        let cropped: Vec<_> = crop_frame_buffer(&frame_buffer, frame_size, crop)
+            .iter()
+            .map(|color| color.r)
+            .collect();
+        assert_eq!(cropped, vec![5, 6, 9, 10]);
+
+        assert!(FrameCrop::full(frame_size).fits_within(frame_size));
+        assert!(!FrameCrop { left: 3, top: 0, width: 2, height: 1 }.fits_within(frame_size));
+        assert!(!FrameCrop { left: 0, top: 0, width: 4, height: 4 }.fits_within(frame_size));
+    }
+
+    #[test]
+    fn analog_frame_position() {
+        let frame_size = FrameSize { width: 256, height: 224 };
+        let position = |x, y| AnalogInput::MousePosition { x, y, frame_size }.frame_position();
+
+        assert_eq!(position(10.4, 20.6), Some((10, 21)));
+        assert_eq!(position(-0.5, 20.0), None);
+        assert_eq!(position(256.0, 20.0), None);
+        assert_eq!(position(10.0, 224.0), None);
+        assert_eq!(AnalogInput::MouseLeave.frame_position(), None);
+    }
+
+    #[test]
+    fn header_strings() {
+        assert_eq!(
+            GameMetadata::parse_header_string(b"SONIC THE    HEDGEHOG    \0\0"),
+            Some("SONIC THE HEDGEHOG".into())
+        );
+        assert_eq!(GameMetadata::parse_header_string(b"  \0\xFF  "), None);
+
+        // Half-width katakana, then two Shift-JIS double-byte characters
+        assert_eq!(
+            GameMetadata::parse_header_string(b"\xBF\xDE\xD9\xC0\xDE  \x83\x5C\x83\x6D"),
+            Some("\u{FF7F}\u{FF9E}\u{FF99}\u{FF80}\u{FF9E} \u{FFFD}\u{FFFD}".into())
+        );
+    }
+}
@@ -0,0 +1,350 @@
+//! ROM hashing that computes CRC32, MD5, and SHA-1 digests in a single pass.
+//!
+//! Hashes of files are cached by path, size, and modification time so that large images are not
+//! rehashed every time the same file is opened. Callers that only need the MD5 can use
+//! [`md5_hex`] or [`file_md5_hex`] to skip computing the other digests.
+//!
+//! Also contains CRC-64 hashes of emulator video and audio output, which regression tests can
+//! compare against known-good baselines.
+
+use crate::frontend::{AudioOutput, RgbaFrame};
+use md5::{Digest, Md5};
+use sha1::Sha1;
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::fmt::Write;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::SystemTime;
+use std::{fs, io};
+
+const CRC32: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+const CRC64: crc::Crc<u64> = crc::Crc::<u64>::new(&crc::CRC_64_XZ);
+
+const READ_BUFFER_LEN: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RomHashes {
+    pub crc32: u32,
+    pub md5: [u8; 16],
+    pub sha1: [u8; 20],
+}
+
+impl RomHashes {
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut hasher = RomHasher::new();
+        hasher.update(bytes);
+        hasher.finish()
+    }
+
+    /// Hash everything that can be read from the given reader, without holding all of it in memory
+    /// at once.
+    ///
+    /// # Errors
+    ///
+    /// Propagates any I/O errors encountered while reading.
+    pub fn from_reader<R: Read>(reader: R) -> io::Result<Self> {
+        let mut hasher = RomHasher::new();
+        for_each_chunk(reader, |chunk| hasher.update(chunk))?;
+        Ok(hasher.finish())
+    }
+
+    /// Hash the contents of a file, reusing a previously computed result if the file has not been
+    /// modified since it was last hashed.
+    ///
+    /// # Errors
+    ///
+    /// Propagates any I/O errors encountered while reading the file or its metadata.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let key = CacheKey::new(path)?;
+
+        if let Some(hashes) = cache_lookup(path, &key).and_then(|entry| entry.all) {
+            return Ok(hashes);
+        }
+
+        let hashes = Self::from_reader(File::open(path)?)?;
+        cache_insert(path, key, CacheEntry { md5: hashes.md5, all: Some(hashes) });
+
+        Ok(hashes)
+    }
+
+    /// CRC32 as an 8-character lowercase hex string.
+    #[must_use]
+    pub fn crc32_hex(&self) -> String {
+        format!("{:08x}", self.crc32)
+    }
+
+    /// MD5 as a 32-character lowercase hex string.
+    #[must_use]
+    pub fn md5_hex(&self) -> String {
+        to_hex(&self.md5)
+    }
+
+    /// SHA-1 as a 40-character lowercase hex string.
+    #[must_use]
+    pub fn sha1_hex(&self) -> String {
+        to_hex(&self.sha1)
+    }
+}
+
+/// MD5 of the given bytes as a 32-character lowercase hex string, without computing the other
+/// digests.
+#[must_use]
+pub fn md5_hex(bytes: &[u8]) -> String {
+    to_hex(&Md5::digest(bytes))
+}
+
+/// MD5 of a file's contents as a 32-character lowercase hex string. Uses the cache the same way as
+/// [`RomHashes::from_file`], but only computes the MD5 on a cache miss.
+///
+/// # Errors
+///
+/// Propagates any I/O errors encountered while reading the file or its metadata.
+pub fn file_md5_hex<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    let path = path.as_ref();
+    let key = CacheKey::new(path)?;
+
+    if let Some(entry) = cache_lookup(path, &key) {
+        return Ok(to_hex(&entry.md5));
+    }
+
+    let mut md5 = Md5::new();
+    for_each_chunk(File::open(path)?, |chunk| md5.update(chunk))?;
+    let md5: [u8; 16] = md5.finalize().into();
+    cache_insert(path, key, CacheEntry { md5, all: None });
+
+    Ok(to_hex(&md5))
+}
+
+fn for_each_chunk<R: Read>(mut reader: R, mut f: impl FnMut(&[u8])) -> io::Result<()> {
+    let mut buffer = vec![0; READ_BUFFER_LEN];
+    loop {
+        let len = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(len) => len,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        f(&buffer[..len]);
+    }
+}
+
+struct RomHasher {
+    crc32: crc::Digest<'static, u32>,
+    md5: Md5,
+    sha1: Sha1,
+}
+
+impl RomHasher {
+    fn new() -> Self {
+        Self { crc32: CRC32.digest(), md5: Md5::new(), sha1: Sha1::new() }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.crc32.update(bytes);
+        self.md5.update(bytes);
+        self.sha1.update(bytes);
+    }
+
+    fn finish(self) -> RomHashes {
+        RomHashes {
+            crc32: self.crc32.finalize(),
+            md5: self.md5.finalize().into(),
+            sha1: self.sha1.finalize().into(),
+        }
+    }
+}
+
+// Maximum number of files whose hashes are cached; the oldest entry is evicted when full
+const MAX_CACHED_FILES: usize = 256;
+
+// Identifies a specific version of a file, so that modified files are rehashed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CacheKey {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl CacheKey {
+    fn new(path: &Path) -> io::Result<Self> {
+        let metadata = fs::metadata(path)?;
+        Ok(Self { len: metadata.len(), modified: metadata.modified().ok() })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CacheEntry {
+    md5: [u8; 16],
+    // None if only the MD5 has been computed
+    all: Option<RomHashes>,
+}
+
+#[derive(Debug, Default)]
+struct HashCache {
+    entries: HashMap<PathBuf, (CacheKey, CacheEntry)>,
+    insertion_order: VecDeque<PathBuf>,
+}
+
+impl HashCache {
+    fn get(&self, path: &Path, key: &CacheKey) -> Option<CacheEntry> {
+        self.entries.get(path).and_then(|(cached_key, entry)| (cached_key == key).then_some(*entry))
+    }
+
+    fn insert(&mut self, path: &Path, key: CacheKey, entry: CacheEntry) {
+        // Each path has at most one entry, so a modified file replaces its stale hashes
+        if self.entries.insert(path.to_path_buf(), (key, entry)).is_some() {
+            return;
+        }
+
+        self.insertion_order.push_back(path.to_path_buf());
+        if self.insertion_order.len() > MAX_CACHED_FILES {
+            if let Some(oldest) = self.insertion_order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+static HASH_CACHE: LazyLock<Mutex<HashCache>> = LazyLock::new(|| Mutex::new(HashCache::default()));
+
+// The cache is only an optimization, so ignore it if the mutex is somehow poisoned
+fn cache_lookup(path: &Path, key: &CacheKey) -> Option<CacheEntry> {
+    HASH_CACHE.lock().ok().and_then(|cache| cache.get(path, key))
+}
+
+fn cache_insert(path: &Path, key: CacheKey, entry: CacheEntry) {
+    if let Ok(mut cache) = HASH_CACHE.lock() {
+        cache.insert(path, key, entry);
+    }
+}
+
+/// Hash a frame's dimensions and pixels.
+#[must_use]
+pub fn frame_hash(frame: &RgbaFrame) -> u64 {
+    let mut digest = CRC64.digest();
+    digest.update(&frame.width.to_le_bytes());
+    digest.update(&frame.height.to_le_bytes());
+    digest.update(bytemuck::cast_slice(&frame.pixels));
+    digest.finalize()
+}
+
+/// Audio output that hashes every sample pushed to it instead of playing it.
+#[derive(Clone)]
+pub struct AudioHasher {
+    digest: crc::Digest<'static, u64>,
+    samples: u64,
+}
+
+impl AudioHasher {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { digest: CRC64.digest(), samples: 0 }
+    }
+
+    /// Hash of all samples pushed so far.
+    #[must_use]
+    pub fn audio_hash(&self) -> u64 {
+        self.digest.clone().finalize()
+    }
+
+    #[must_use]
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+}
+
+impl Default for AudioHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioOutput for AudioHasher {
+    type Err = Infallible;
+
+    fn push_sample(&mut self, sample_l: f64, sample_r: f64) -> Result<(), Self::Err> {
+        self.digest.update(&sample_l.to_le_bytes());
+        self.digest.update(&sample_r.to_le_bytes());
+        self.samples += 1;
+
+        Ok(())
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::with_capacity(2 * bytes.len()), |mut s, byte| {
+        write!(s, "{byte:02x}").unwrap();
+        s
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_digests() {
+        let hashes = RomHashes::from_bytes(b"The quick brown fox jumps over the lazy dog");
+
+        assert_eq!(hashes.crc32_hex(), "414fa339");
+        assert_eq!(hashes.md5_hex(), "9e107d9d372bb6826bd81d3542a419d6");
+        assert_eq!(hashes.sha1_hex(), "2fd4e1c67a2d28fced849ee1bb76e7391b93eb12");
+    }
+
+    #[test]
+    fn streaming_matches_in_memory() {
+        let bytes: Vec<u8> = (0..3 * READ_BUFFER_LEN + 17).map(|i| i as u8).collect();
+
+        assert_eq!(
+            RomHashes::from_reader(bytes.as_slice()).unwrap(),
+            RomHashes::from_bytes(&bytes)
+        );
+    }
+
+    #[test]
+    fn md5_only_matches_full_hash() {
+        let bytes = b"The quick brown fox jumps over the lazy dog";
+
+        assert_eq!(md5_hex(bytes), RomHashes::from_bytes(bytes).md5_hex());
+    }
+
+    #[test]
+    fn cache_is_bounded() {
+        let key = CacheKey { len: 0, modified: None };
+        let entry = CacheEntry { md5: [0; 16], all: None };
+
+        let mut cache = HashCache::default();
+        for i in 0..MAX_CACHED_FILES + 10 {
+            cache.insert(Path::new(&i.to_string()), key, entry);
+        }
+        assert_eq!(cache.entries.len(), MAX_CACHED_FILES);
+        assert_eq!(cache.insertion_order.len(), MAX_CACHED_FILES);
+        assert!(cache.get(Path::new("0"), &key).is_none());
+        assert!(cache.get(Path::new(&(MAX_CACHED_FILES + 9).to_string()), &key).is_some());
+
+        // Rehashing a modified file replaces its entry instead of adding another
+        let modified_key = CacheKey { len: 1, modified: None };
+        cache.insert(Path::new("20"), modified_key, entry);
+        assert_eq!(cache.entries.len(), MAX_CACHED_FILES);
+        assert!(cache.get(Path::new("20"), &key).is_none());
+        assert!(cache.get(Path::new("20"), &modified_key).is_some());
+    }
+
+    #[test]
+    fn audio_hash_depends_on_sample_order() {
+        let mut a = AudioHasher::new();
+        let mut b = AudioHasher::new();
+        let Ok(()) = a.push_sample(0.25, -0.5);
+        let Ok(()) = a.push_sample(0.0, 1.0);
+        let Ok(()) = b.push_sample(0.0, 1.0);
+        let Ok(()) = b.push_sample(0.25, -0.5);
+
+        assert_eq!(a.samples(), 2);
+        assert_ne!(a.audio_hash(), b.audio_hash());
+        assert_ne!(a.audio_hash(), AudioHasher::new().audio_hash());
+    }
+}
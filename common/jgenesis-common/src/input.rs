@@ -4,6 +4,19 @@ pub enum Player {
     Two,
 }
 
+impl Player {
+    #[inline]
+    #[must_use]
+    pub fn other(self) -> Self {
+        match self {
+            Self::One => Self::Two,
+            Self::Two => Self::One,
+        }
+    }
+}
+
+/// Map a position in the window to a position in frame pixels. The result is not clamped to the
+/// frame.
 #[inline]
 #[must_use]
 pub fn viewport_position_to_frame_position(
@@ -11,33 +24,20 @@ pub fn viewport_position_to_frame_position(
     y: i32,
     frame_size: FrameSize,
     display_area: DisplayArea,
-) -> Option<(u16, u16)> {
-    let display_left = display_area.x as i32;
-    let display_right = display_left + display_area.width as i32;
-    let display_top = display_area.y as i32;
-    let display_bottom = display_top + display_area.height as i32;
-
-    if !(display_left..display_right).contains(&x) || !(display_top..display_bottom).contains(&y) {
-        return None;
-    }
+) -> (f64, f64) {
+    let display_width: f64 = display_area.width.max(1).into();
+    let display_height: f64 = display_area.height.max(1).into();
 
-    let x: f64 = x.into();
-    let y: f64 = y.into();
-    let display_left: f64 = display_left.into();
-    let display_width: f64 = display_area.width.into();
-    let frame_width: f64 = frame_size.width.into();
-    let display_top: f64 = display_top.into();
-    let display_height: f64 = display_area.height.into();
-    let frame_height: f64 = frame_size.height.into();
-
-    let frame_x = ((x - display_left) * frame_width / display_width).round() as u16;
-    let frame_y = ((y - display_top) * frame_height / display_height).round() as u16;
+    let frame_x =
+        (f64::from(x) - f64::from(display_area.x)) * f64::from(frame_size.width) / display_width;
+    let frame_y =
+        (f64::from(y) - f64::from(display_area.y)) * f64::from(frame_size.height) / display_height;
 
     log::trace!(
         "Mapped mouse position ({x}, {y}) to ({frame_x}, {frame_y}) (frame size {frame_size:?}, display_area {display_area:?})"
     );
 
-    Some((frame_x, frame_y))
+    (frame_x, frame_y)
 }
 
 #[macro_export]
@@ -118,6 +118,10 @@ macro_rules! define_controller_inputs {
                 self.set_button(button, pressed);
                 self
             }
+
+            pub fn button_states(&self) -> Vec<($button_enum, bool)> {
+                vec![$(($button_enum::$button, self.$button_field)),*]
+            }
         }
 
         $(
@@ -162,6 +166,29 @@ macro_rules! define_controller_inputs {
                         )*
                     }
                 }
+
+                #[allow(unused_mut)]
+                fn button_states(
+                    &self,
+                    player: ::jgenesis_common::input::Player,
+                ) -> Vec<($button_enum, bool)> {
+                    let mut states = match player {
+                        $(
+                            ::jgenesis_common::input::Player::$player_value => {
+                                self.$player_field.button_states()
+                            }
+                        )*
+                    };
+
+                    // Buttons that are not part of a joypad are shown with player 1
+                    if player == ::jgenesis_common::input::Player::One {
+                        $($(
+                            states.push(($button_enum::$ex_button, self.$ex_button_field));
+                        )*)?
+                    }
+
+                    states
+                }
             }
         )?
     }
@@ -0,0 +1,203 @@
+//! Coarse per-subsystem instrumentation, used by the headless benchmark mode and the profiler.
+//!
+//! [`instrument!`](crate::instrument) scopes do two independent things depending on which features
+//! are enabled:
+//! * With the `instrumentation` feature, time spent in each [`Subsystem`] is accumulated for
+//!   [`take_subsystem_times`]. Without it, [`take_subsystem_times`] always returns `None`.
+//! * With the `profiling` feature, named scopes (`instrument!("name")`) are emitted as `tracing`
+//!   spans at TRACE level so that any `tracing` subscriber can consume them. Subsystem scopes are
+//!   entered on every tick, so they are only timed and never emit spans.
+//!
+//! With neither feature, `instrument!` expands to the wrapped expression.
+//!
+//! Timed scopes are exclusive: if a subsystem scope is entered while another scope is active
+//! (e.g. a CPU memory access that ticks the PPU), the outer scope's clock is paused until the inner
+//! scope exits.
+
+use std::time::Duration;
+
+#[cfg(feature = "profiling")]
+pub use tracing;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subsystem {
+    Cpu,
+    Ppu,
+    Apu,
+}
+
+impl Subsystem {
+    pub const ALL: [Self; 3] = [Self::Cpu, Self::Ppu, Self::Apu];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Cpu => "CPU",
+            Self::Ppu => "PPU",
+            Self::Apu => "APU",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubsystemTimes {
+    pub cpu: Duration,
+    pub ppu: Duration,
+    pub apu: Duration,
+}
+
+impl SubsystemTimes {
+    #[must_use]
+    pub fn get(&self, subsystem: Subsystem) -> Duration {
+        match subsystem {
+            Subsystem::Cpu => self.cpu,
+            Subsystem::Ppu => self.ppu,
+            Subsystem::Apu => self.apu,
+        }
+    }
+
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.cpu + self.ppu + self.apu
+    }
+}
+
+#[cfg(feature = "instrumentation")]
+mod imp {
+    use super::{Subsystem, SubsystemTimes};
+    use std::cell::Cell;
+    use std::time::{Duration, Instant};
+
+    thread_local! {
+        static TIMES: Cell<SubsystemTimes> = const {
+            Cell::new(SubsystemTimes { cpu: Duration::ZERO, ppu: Duration::ZERO, apu: Duration::ZERO })
+        };
+        static ACTIVE: Cell<Option<(Subsystem, Instant)>> = const { Cell::new(None) };
+    }
+
+    fn charge_active(now: Instant) {
+        if let Some((subsystem, start)) = ACTIVE.get() {
+            TIMES.with(|times| {
+                let mut value = times.get();
+                let field = match subsystem {
+                    Subsystem::Cpu => &mut value.cpu,
+                    Subsystem::Ppu => &mut value.ppu,
+                    Subsystem::Apu => &mut value.apu,
+                };
+                *field += now - start;
+                times.set(value);
+            });
+        }
+    }
+
+    pub struct TimingGuard {
+        prev: Option<Subsystem>,
+    }
+
+    impl TimingGuard {
+        #[inline]
+        #[must_use]
+        pub fn enter(subsystem: Subsystem) -> Self {
+            let now = Instant::now();
+            charge_active(now);
+            let prev = ACTIVE.replace(Some((subsystem, now))).map(|(prev, _)| prev);
+            Self { prev }
+        }
+    }
+
+    impl Drop for TimingGuard {
+        #[inline]
+        fn drop(&mut self) {
+            let now = Instant::now();
+            charge_active(now);
+            ACTIVE.set(self.prev.map(|prev| (prev, now)));
+        }
+    }
+
+    pub fn take_times() -> SubsystemTimes {
+        TIMES.take()
+    }
+}
+
+/// Guard returned by [`instrument!`](crate::instrument) for a [`Subsystem`] scope; the scope ends
+/// when the guard is dropped.
+#[must_use]
+pub struct SubsystemGuard {
+    #[cfg(feature = "instrumentation")]
+    _timing: imp::TimingGuard,
+}
+
+impl SubsystemGuard {
+    #[inline]
+    #[allow(unused_variables)]
+    pub fn enter(subsystem: Subsystem) -> Self {
+        Self {
+            #[cfg(feature = "instrumentation")]
+            _timing: imp::TimingGuard::enter(subsystem),
+        }
+    }
+}
+
+/// Return the time spent in each subsystem on the current thread since the last call, or `None`
+/// if instrumentation was not compiled in.
+#[must_use]
+pub fn take_subsystem_times() -> Option<SubsystemTimes> {
+    #[cfg(feature = "instrumentation")]
+    {
+        Some(imp::take_times())
+    }
+
+    #[cfg(not(feature = "instrumentation"))]
+    {
+        None
+    }
+}
+
+/// Evaluate an expression inside a [`Subsystem`] scope, attributing the time spent to that
+/// subsystem if instrumentation is enabled.
+///
+/// Also accepts a span name instead of a subsystem (with no expression) to open a profiling span
+/// that lasts until the end of the enclosing block. These spans are not timed by the benchmark and
+/// should only be used for coarse scopes, e.g. once per frame or once per scanline; per-tick scopes
+/// should use a subsystem instead.
+///
+/// ```
+/// use jgenesis_common::instrument;
+/// use jgenesis_common::instrumentation::Subsystem;
+///
+/// fn render_frame() -> u32 {
+///     instrument!("render_frame");
+///
+///     instrument!(Subsystem::Cpu, 1 + 2)
+/// }
+///
+/// assert_eq!(render_frame(), 3);
+/// ```
+#[macro_export]
+macro_rules! instrument {
+    ($name:literal) => {
+        $crate::__instrument_span!($name);
+    };
+    ($subsystem:expr, $e:expr $(,)?) => {{
+        let _guard = $crate::instrumentation::SubsystemGuard::enter($subsystem);
+        $e
+    }};
+}
+
+// Whether named spans are compiled in depends on jgenesis-common's features rather than the
+// calling crate's, so the span half of `instrument!` is defined separately for each configuration
+#[cfg(feature = "profiling")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __instrument_span {
+    ($name:literal) => {
+        let _instrument_span = $crate::instrumentation::tracing::trace_span!($name).entered();
+    };
+}
+
+#[cfg(not(feature = "profiling"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __instrument_span {
+    ($name:literal) => {};
+}
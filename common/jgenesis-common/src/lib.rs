@@ -1,9 +1,21 @@
+pub mod addrdecode;
 pub mod appimage;
 pub mod audio;
 pub mod boxedarray;
+pub mod bus;
+pub mod clockratio;
+pub mod eventlog;
+pub mod files;
 pub mod frontend;
+pub mod hash;
 pub mod input;
+pub mod instrumentation;
+pub mod memmap;
 pub mod num;
+pub mod quirks;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod savestate;
+pub mod sram;
 pub mod timeutils;
 
 pub use appimage::{fix_appimage_relative_path, is_appimage_build};
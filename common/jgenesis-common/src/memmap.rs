@@ -0,0 +1,274 @@
+//! Descriptions of a console's bus address spaces, displayed in the debugger's memory map view.
+//!
+//! Each core builds these on demand from its current state, so regions that are controlled by
+//! mapper or bank registers describe whatever is mapped at the time of the call.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionKind {
+    Rom,
+    Ram,
+    Registers,
+    Unmapped,
+}
+
+impl RegionKind {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Rom => "ROM",
+            Self::Ram => "RAM",
+            Self::Registers => "I/O",
+            Self::Unmapped => "Unmapped",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryMapEntry {
+    pub start: u32,
+    /// Inclusive
+    pub end: u32,
+    pub kind: RegionKind,
+    pub name: &'static str,
+    /// What is currently mapped into this region, e.g. a ROM bank number or the region that this
+    /// region mirrors
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryMap {
+    pub name: &'static str,
+    pub address_bits: u32,
+    pub entries: Vec<MemoryMapEntry>,
+}
+
+impl MemoryMap {
+    #[must_use]
+    pub fn new(name: &'static str, address_bits: u32) -> Self {
+        Self { name, address_bits, entries: Vec::new() }
+    }
+
+    /// Add a region with no additional detail.
+    pub fn add(&mut self, start: u32, end: u32, kind: RegionKind, name: &'static str) -> &mut Self {
+        self.entries.push(MemoryMapEntry { start, end, kind, name, detail: None });
+        self
+    }
+
+    /// Add a region along with a description of what is currently mapped into it.
+    pub fn add_mapped(
+        &mut self,
+        start: u32,
+        end: u32,
+        kind: RegionKind,
+        name: &'static str,
+        detail: impl Into<String>,
+    ) -> &mut Self {
+        self.entries.push(MemoryMapEntry { start, end, kind, name, detail: Some(detail.into()) });
+        self
+    }
+
+    /// Add a region that mirrors the `mirror_start..=mirror_end` range of this address space.
+    pub fn add_mirror(
+        &mut self,
+        start: u32,
+        end: u32,
+        kind: RegionKind,
+        name: &'static str,
+        mirror_start: u32,
+        mirror_end: u32,
+    ) -> &mut Self {
+        let detail = format!("Mirror of {}", self.format_range(mirror_start, mirror_end));
+        self.add_mapped(start, end, kind, name, detail)
+    }
+
+    /// Add a region that takes precedence over any previously added regions that it overlaps, e.g.
+    /// cartridge RAM that is mapped over part of the ROM address range. Overlapped regions are
+    /// truncated or split around the new region.
+    pub fn add_overlay(
+        &mut self,
+        start: u32,
+        end: u32,
+        kind: RegionKind,
+        name: &'static str,
+        detail: Option<String>,
+    ) -> &mut Self {
+        let mut entries = Vec::with_capacity(self.entries.len() + 2);
+        for entry in self.entries.drain(..) {
+            if entry.end < start || entry.start > end {
+                entries.push(entry);
+                continue;
+            }
+
+            if entry.start < start {
+                entries.push(MemoryMapEntry { end: start - 1, ..entry.clone() });
+            }
+            if entry.end > end {
+                entries.push(MemoryMapEntry { start: end + 1, ..entry });
+            }
+        }
+
+        entries.push(MemoryMapEntry { start, end, kind, name, detail });
+        self.entries = entries;
+        self
+    }
+
+    /// Sort regions by address and fill any gaps between them with unmapped regions. Should be
+    /// called after all regions have been added.
+    pub fn fill_unmapped(&mut self) {
+        self.entries.sort_by_key(|entry| entry.start);
+
+        let mut entries = Vec::with_capacity(self.entries.len());
+        let mut next_address = Some(0_u32);
+        for entry in self.entries.drain(..) {
+            if let Some(next) = next_address.filter(|&next| next < entry.start) {
+                entries.push(MemoryMapEntry {
+                    start: next,
+                    end: entry.start - 1,
+                    kind: RegionKind::Unmapped,
+                    name: "Unused",
+                    detail: None,
+                });
+            }
+
+            next_address = entry.end.checked_add(1);
+            entries.push(entry);
+        }
+
+        if let Some(next) = next_address.filter(|&next| next <= self.max_address()) {
+            entries.push(MemoryMapEntry {
+                start: next,
+                end: self.max_address(),
+                kind: RegionKind::Unmapped,
+                name: "Unused",
+                detail: None,
+            });
+        }
+
+        self.entries = entries;
+    }
+
+    /// The highest address in this address space.
+    #[must_use]
+    pub fn max_address(&self) -> u32 {
+        if self.address_bits >= 32 { u32::MAX } else { (1 << self.address_bits) - 1 }
+    }
+
+    #[must_use]
+    pub fn entry_at(&self, address: u32) -> Option<&MemoryMapEntry> {
+        self.entries.iter().find(|entry| (entry.start..=entry.end).contains(&address))
+    }
+
+    /// Returns whether the entries are in address order, do not overlap, and cover the entire
+    /// address space with no gaps.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        let mut next_address = Some(0_u32);
+        for entry in &self.entries {
+            if next_address != Some(entry.start) || entry.end < entry.start {
+                return false;
+            }
+            next_address = entry.end.checked_add(1);
+        }
+
+        match next_address {
+            Some(next_address) => next_address == self.max_address().wrapping_add(1),
+            None => self.max_address() == u32::MAX,
+        }
+    }
+
+    #[must_use]
+    pub fn format_address(&self, address: u32) -> String {
+        let digits = self.address_bits.div_ceil(4) as usize;
+        format!("${address:0digits$X}")
+    }
+
+    #[must_use]
+    pub fn format_range(&self, start: u32, end: u32) -> String {
+        format!("{}-{}", self.format_address(start), self.format_address(end))
+    }
+}
+
+/// Describe the bank that is mapped into a bank window, e.g. "Bank $05 ($014000-$017FFF)".
+#[must_use]
+pub fn describe_bank(bank: u32, bank_size: u32) -> String {
+    let start = bank * bank_size;
+    format!("Bank ${bank:02X} (${start:06X}-${:06X})", start + bank_size - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_map() -> MemoryMap {
+        let mut map = MemoryMap::new("Test", 16);
+        map.add(0x0000, 0x07FF, RegionKind::Ram, "RAM")
+            .add_mirror(0x0800, 0x1FFF, RegionKind::Ram, "RAM", 0x0000, 0x07FF)
+            .add(0x2000, 0x7FFF, RegionKind::Unmapped, "Unused")
+            .add_mapped(0x8000, 0xFFFF, RegionKind::Rom, "ROM", describe_bank(1, 0x8000));
+        map
+    }
+
+    #[test]
+    fn entry_at() {
+        let map = test_map();
+
+        assert_eq!(map.entry_at(0x0000).map(|entry| entry.start), Some(0x0000));
+        assert_eq!(map.entry_at(0x1234).map(|entry| entry.start), Some(0x0800));
+        assert_eq!(map.entry_at(0xFFFF).map(|entry| entry.start), Some(0x8000));
+        assert_eq!(map.entry_at(0x10000), None);
+    }
+
+    #[test]
+    fn complete() {
+        assert!(test_map().is_complete());
+
+        let mut gap = test_map();
+        gap.entries.remove(2);
+        assert!(!gap.is_complete());
+
+        let mut short = test_map();
+        short.entries.pop();
+        assert!(!short.is_complete());
+
+        let mut full_32_bit = MemoryMap::new("Test", 32);
+        full_32_bit.add(0, 0x7FFF_FFFF, RegionKind::Ram, "A").add(
+            0x8000_0000,
+            u32::MAX,
+            RegionKind::Rom,
+            "B",
+        );
+        assert!(full_32_bit.is_complete());
+    }
+
+    #[test]
+    fn formatting() {
+        let map = test_map();
+
+        assert_eq!(map.entries[1].detail.as_deref(), Some("Mirror of $0000-$07FF"));
+        assert_eq!(map.entries[3].detail.as_deref(), Some("Bank $01 ($008000-$00FFFF)"));
+        assert_eq!(MemoryMap::new("Test", 24).format_address(0xA10000), "$A10000");
+    }
+
+    #[test]
+    fn overlay_and_fill() {
+        let mut map = MemoryMap::new("Test", 16);
+        map.add(0x8000, 0xFFFF, RegionKind::Rom, "ROM").add(0x0000, 0x07FF, RegionKind::Ram, "RAM");
+        map.add_overlay(0xA000, 0xBFFF, RegionKind::Ram, "Cartridge RAM", None);
+        map.fill_unmapped();
+
+        assert!(map.is_complete());
+
+        let ranges: Vec<_> =
+            map.entries.iter().map(|entry| (entry.start, entry.end, entry.name)).collect();
+        assert_eq!(
+            ranges,
+            vec![
+                (0x0000, 0x07FF, "RAM"),
+                (0x0800, 0x7FFF, "Unused"),
+                (0x8000, 0x9FFF, "ROM"),
+                (0xA000, 0xBFFF, "Cartridge RAM"),
+                (0xC000, 0xFFFF, "ROM"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Per-game emulation workarounds, keyed by ROM hash or header serial number.
+//!
+//! Each core defines its own quirk type and database of affected games; this module only provides
+//! the matching logic so that every core looks games up the same way.
+
+use std::fmt::Debug;
+
+/// Identifies a game in a quirks database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameKey {
+    /// CRC32 of the full ROM image as loaded from disk
+    Crc32(u32),
+    /// Serial number field from the ROM header, compared byte-for-byte against the raw field
+    Serial(&'static [u8]),
+}
+
+impl GameKey {
+    fn matches(self, crc32: u32, serial: &[u8]) -> bool {
+        match self {
+            Self::Crc32(expected) => crc32 == expected,
+            Self::Serial(expected) => serial == expected,
+        }
+    }
+}
+
+pub trait GameQuirk: Debug + Copy + PartialEq {
+    /// Short description of the workaround, shown to users so that they know when emulation
+    /// differs from default behavior.
+    fn description(self) -> &'static str;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct QuirkEntry<Quirk: 'static> {
+    /// Name of the affected game(s), used for logging
+    pub game: &'static str,
+    pub keys: &'static [GameKey],
+    pub quirks: &'static [Quirk],
+}
+
+/// Find every quirk that applies to a game with the given ROM CRC32 and raw header serial number.
+#[must_use]
+pub fn find_quirks<Quirk: GameQuirk>(
+    database: &[QuirkEntry<Quirk>],
+    crc32: u32,
+    serial: &[u8],
+) -> Vec<Quirk> {
+    let mut quirks = Vec::new();
+
+    for entry in database {
+        if !entry.keys.iter().any(|key| key.matches(crc32, serial)) {
+            continue;
+        }
+
+        log::info!("Applying game-specific workarounds for {}: {:?}", entry.game, entry.quirks);
+        for &quirk in entry.quirks {
+            if !quirks.contains(&quirk) {
+                quirks.push(quirk);
+            }
+        }
+    }
+
+    quirks
+}
+
+/// Descriptions of the given quirks, for [`GameMetadata::quirks`](crate::frontend::GameMetadata).
+#[must_use]
+pub fn describe_quirks<Quirk: GameQuirk>(quirks: &[Quirk]) -> Vec<String> {
+    quirks.iter().map(|quirk| quirk.description().into()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum TestQuirk {
+        A,
+        B,
+    }
+
+    impl GameQuirk for TestQuirk {
+        fn description(self) -> &'static str {
+            match self {
+                Self::A => "A",
+                Self::B => "B",
+            }
+        }
+    }
+
+    const DATABASE: &[QuirkEntry<TestQuirk>] = &[
+        QuirkEntry {
+            game: "Game 1",
+            keys: &[GameKey::Serial(b"T-1234"), GameKey::Crc32(0x12345678)],
+            quirks: &[TestQuirk::A],
+        },
+        QuirkEntry {
+            game: "Game 2",
+            keys: &[GameKey::Crc32(0x12345678)],
+            quirks: &[TestQuirk::A, TestQuirk::B],
+        },
+    ];
+
+    #[test]
+    fn lookup() {
+        assert_eq!(find_quirks(DATABASE, 0, b"T-1234"), vec![TestQuirk::A]);
+        assert_eq!(find_quirks(DATABASE, 0x12345678, b""), vec![TestQuirk::A, TestQuirk::B]);
+        assert_eq!(find_quirks(DATABASE, 0, b"T-12345"), vec![]);
+        assert_eq!(describe_quirks(&[TestQuirk::B]), vec!["B".to_string()]);
+    }
+}
@@ -0,0 +1,134 @@
+//! Save state round-trip checks for emulator cores.
+//!
+//! Cores call [`assert_round_trip_deterministic`] from their tests to verify that saving and
+//! loading a state at the core's declared [`SaveStateSafePoint`] never changes emulation results,
+//! which catches state that is missing from save states or that is skipped when it shouldn't be.
+//!
+//! Only available in tests and with the `test-utils` feature.
+
+use crate::frontend::{
+    EmulatorTrait, FrameCapture, NullAudioOutput, NullSaveWriter, SaveStateSafePoint, TickEffect,
+};
+use std::{panic, thread};
+
+// Creating and decoding emulator structs in unoptimized builds can overflow the default 2MB test
+// thread stack
+const STACK_SIZE: usize = 16 * 1024 * 1024;
+
+macro_rules! bincode_config {
+    () => {
+        bincode::config::standard().with_little_endian().with_fixed_int_encoding()
+    };
+}
+
+/// Run two instances of an emulator side by side for `ticks` ticks with no inputs pressed. One
+/// instance is saved and reloaded at the first safe point after every `interval` ticks.
+///
+/// # Panics
+///
+/// Panics if the two instances ever render different frames or return different tick effects, if
+/// their serialized states differ after a round trip or at the end, or if no round trip happened
+/// at all (e.g. because a core that is only safe at frame boundaries never rendered a frame).
+pub fn assert_round_trip_deterministic<Emulator: EmulatorTrait>(
+    create_emulator: impl FnMut() -> Emulator + Send,
+    ticks: u64,
+    interval: u64,
+) {
+    thread::scope(|scope| {
+        let handle = thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(scope, || run_side_by_side(create_emulator, ticks, interval))
+            .unwrap();
+
+        if let Err(err) = handle.join() {
+            panic::resume_unwind(err);
+        }
+    });
+}
+
+/// Build a zero-filled test ROM of `len` bytes with each `(offset, code)` segment copied in at its
+/// offset, e.g. a main loop at the entry point and an interrupt handler at its vector.
+///
+/// # Panics
+///
+/// Panics if any segment extends past the end of the ROM.
+#[must_use]
+pub fn test_rom(len: usize, segments: &[(usize, &[u8])]) -> Vec<u8> {
+    let mut rom = vec![0; len];
+    for &(offset, code) in segments {
+        rom[offset..offset + code.len()].copy_from_slice(code);
+    }
+    rom
+}
+
+fn run_side_by_side<Emulator: EmulatorTrait>(
+    mut create_emulator: impl FnMut() -> Emulator,
+    ticks: u64,
+    interval: u64,
+) {
+    let inputs = Emulator::Inputs::default();
+
+    let mut expected = create_emulator();
+    let mut actual = create_emulator();
+
+    // Cores may randomize power-on state (e.g. NES RAM contents), so start both instances from
+    // the same state
+    load(&mut actual, &encode(&expected));
+
+    let mut expected_renderer = FrameCapture::default();
+    let mut actual_renderer = FrameCapture::default();
+
+    let mut ticks_since_round_trip = 0;
+    let mut round_trips = 0;
+    for tick in 0..ticks {
+        let expected_effect = tick_emulator(&mut expected, &mut expected_renderer, &inputs);
+        let actual_effect = tick_emulator(&mut actual, &mut actual_renderer, &inputs);
+        assert_eq!(expected_effect, actual_effect, "tick effect diverged at tick {tick}");
+
+        if actual_effect == TickEffect::FrameRendered {
+            assert!(
+                expected_renderer.frame == actual_renderer.frame,
+                "rendered frame diverged at tick {tick}"
+            );
+        }
+
+        ticks_since_round_trip += 1;
+        let at_safe_point = match Emulator::save_state_safe_point() {
+            SaveStateSafePoint::Tick => true,
+            SaveStateSafePoint::FrameBoundary => actual_effect == TickEffect::FrameRendered,
+        };
+        if ticks_since_round_trip >= interval && at_safe_point {
+            let state = encode(&actual);
+            load(&mut actual, &state);
+            assert!(
+                encode(&expected) == encode(&actual),
+                "state changed by save state round trip at tick {tick}"
+            );
+
+            ticks_since_round_trip = 0;
+            round_trips += 1;
+        }
+    }
+
+    assert_ne!(round_trips, 0, "no save state safe point reached in {ticks} ticks");
+    assert!(encode(&expected) == encode(&actual), "state diverged after {round_trips} round trips");
+}
+
+fn tick_emulator<Emulator: EmulatorTrait>(
+    emulator: &mut Emulator,
+    renderer: &mut FrameCapture,
+    inputs: &Emulator::Inputs,
+) -> TickEffect {
+    emulator.tick(renderer, &mut NullAudioOutput, inputs, &mut NullSaveWriter).unwrap()
+}
+
+fn encode<Emulator: EmulatorTrait>(emulator: &Emulator) -> Vec<u8> {
+    bincode::encode_to_vec(emulator, bincode_config!()).unwrap()
+}
+
+fn load<Emulator: EmulatorTrait>(emulator: &mut Emulator, state: &[u8]) {
+    let (mut loaded, _): (Emulator, usize) =
+        bincode::decode_from_slice(state, bincode_config!()).unwrap();
+    loaded.take_rom_from(emulator);
+    *emulator = loaded;
+}
@@ -0,0 +1,121 @@
+//! Compatibility helpers for loading battery-backed save files (`.sav` / `.srm`) written by other
+//! emulators and flash carts.
+//!
+//! Save files written by this emulator are always a raw dump of the cartridge's save memory in
+//! native byte order, exactly as many bytes as the cartridge has. Other programs commonly write
+//! variants of the same data:
+//! - Files padded past the end of save memory, either with `$00`/`$FF` bytes or with mirrored
+//!   copies of the save memory (e.g. flash carts that always write a fixed-size file)
+//! - Files that are shorter than the save memory size declared in the ROM header because the real
+//!   cartridge's chip is smaller and mirrored
+//! - For 8-bit Genesis SRAM, files that store one SRAM byte per 16-bit word as it appears on the
+//!   68000 bus
+//! - For 16-bit data, files with each pair of bytes swapped
+
+/// Convert a save file from another emulator to a raw save memory image of exactly `len` bytes, or
+/// return `None` if the file doesn't appear to be a variant of a `len`-byte save.
+#[must_use]
+pub fn normalize(sram: Vec<u8>, len: usize) -> Option<Vec<u8>> {
+    if sram.len() == len {
+        return Some(sram);
+    }
+
+    if len == 0 || sram.is_empty() {
+        return None;
+    }
+
+    if sram.len() > len {
+        let (data, extra) = sram.split_at(len);
+
+        let padded = extra.iter().all(|&b| b == 0x00) || extra.iter().all(|&b| b == 0xFF);
+        let mirrored =
+            sram.len().is_multiple_of(len) && extra.chunks(len).all(|chunk| chunk == data);
+        if padded || mirrored {
+            log::info!("Truncating {}-byte save file to {len} bytes", sram.len());
+            return Some(data.to_vec());
+        }
+
+        return None;
+    }
+
+    // Shorter than expected; fill the rest of save memory with mirrors if the file could be a
+    // smaller mirrored chip
+    if sram.len().is_power_of_two() && len.is_multiple_of(sram.len()) {
+        log::info!("Mirroring {}-byte save file to fill {len} bytes", sram.len());
+        return Some(sram.repeat(len / sram.len()));
+    }
+
+    None
+}
+
+/// Convert a save file that stores 8-bit SRAM as 16-bit words (one SRAM byte per word, at odd or
+/// even addresses) to one byte per SRAM byte.
+///
+/// Returns `None` if the bytes at the unused addresses contain anything other than `$00`/`$FF`
+/// padding or copies of the SRAM bytes, in which case the file is probably not interleaved.
+#[must_use]
+pub fn deinterleave_8bit(sram: &[u8], odd_addresses: bool) -> Option<Vec<u8>> {
+    if sram.is_empty() || !sram.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let (used_lane, unused_lane) = if odd_addresses { (1, 0) } else { (0, 1) };
+    let words = || sram.chunks_exact(2);
+
+    let unused_fill = words().all(|word| word[unused_lane] == 0x00)
+        || words().all(|word| word[unused_lane] == 0xFF)
+        || words().all(|word| word[unused_lane] == word[used_lane]);
+    if !unused_fill {
+        return None;
+    }
+
+    Some(words().map(|word| word[used_lane]).collect())
+}
+
+/// Swap the bytes in every 16-bit word, converting between big-endian and little-endian order.
+pub fn byteswap_words(bytes: &mut [u8]) {
+    for word in bytes.chunks_exact_mut(2) {
+        word.swap(0, 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_exact_size() {
+        assert_eq!(normalize(vec![1, 2, 3, 4], 4), Some(vec![1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn normalize_padded() {
+        assert_eq!(normalize(vec![1, 2, 0, 0, 0, 0], 2), Some(vec![1, 2]));
+        assert_eq!(normalize(vec![1, 2, 0xFF, 0xFF], 2), Some(vec![1, 2]));
+        assert_eq!(normalize(vec![1, 2, 1, 2, 1, 2, 1, 2], 2), Some(vec![1, 2]));
+        assert_eq!(normalize(vec![1, 2, 3, 4], 2), None);
+    }
+
+    #[test]
+    fn normalize_mirrored_chip() {
+        assert_eq!(normalize(vec![1, 2], 8), Some(vec![1, 2, 1, 2, 1, 2, 1, 2]));
+        assert_eq!(normalize(vec![1, 2, 3], 6), None);
+        assert_eq!(normalize(vec![], 8), None);
+    }
+
+    #[test]
+    fn deinterleave() {
+        assert_eq!(deinterleave_8bit(&[0xFF, 1, 0xFF, 2], true), Some(vec![1, 2]));
+        assert_eq!(deinterleave_8bit(&[1, 0, 2, 0], false), Some(vec![1, 2]));
+        assert_eq!(deinterleave_8bit(&[1, 1, 2, 2], true), Some(vec![1, 2]));
+        assert_eq!(deinterleave_8bit(&[5, 1, 6, 2], true), None);
+        assert_eq!(deinterleave_8bit(&[1, 2, 3], true), None);
+    }
+
+    #[test]
+    fn byteswap() {
+        let mut bytes = [1, 2, 3, 4];
+        byteswap_words(&mut bytes);
+        assert_eq!(bytes, [2, 1, 4, 3]);
+    }
+}
@@ -1,7 +1,7 @@
 use cfg_if::cfg_if;
 use std::thread;
 use std::time::Duration;
-use time::{Date, Month, Weekday};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, UtcOffset, Weekday};
 
 /// Read the time since the Unix epoch in nanoseconds. Will return 0 if the system-reported time is
 /// somehow before the Unix epoch.
@@ -21,6 +21,20 @@ pub fn current_time_nanos() -> u128 {
     }
 }
 
+/// Read the current date and time in the host's local time zone, falling back to UTC if the local
+/// UTC offset cannot be determined.
+#[must_use]
+pub fn current_date_time() -> PrimitiveDateTime {
+    let utc_date_time = OffsetDateTime::from_unix_timestamp_nanos(current_time_nanos() as i128)
+        .unwrap_or(OffsetDateTime::UNIX_EPOCH);
+    let date_time = UtcOffset::current_local_offset()
+        .ok()
+        .and_then(|offset| utc_date_time.checked_to_offset(offset))
+        .unwrap_or(utc_date_time);
+
+    PrimitiveDateTime::new(date_time.date(), date_time.time())
+}
+
 /// Sleep until at least the specified time. Returns the current time in nanoseconds after sleeping.
 ///
 /// This implementation will try to sleep until 1ms before the target time and then it will busy
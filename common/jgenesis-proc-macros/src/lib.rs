@@ -2,6 +2,7 @@ mod config;
 mod encode;
 mod enums;
 mod partialclone;
+mod savestate;
 
 use proc_macro::TokenStream;
 
@@ -210,6 +211,53 @@ pub fn fake_decode(input: TokenStream) -> TokenStream {
     encode::fake_decode(input)
 }
 
+/// Implements the `bincode::Encode`, `bincode::Decode`, and `bincode::BorrowDecode` traits for the
+/// given struct, with support for skipping fields that do not need to be saved in save states.
+///
+/// Fields that are not marked with a `#[savestate]` attribute are encoded and decoded in
+/// declaration order, the same as with bincode's own derive macros.
+///
+/// Fields that are marked with `#[savestate(skip)]` are not encoded, and they are set to the
+/// default value for that type (via the `Default` trait) when decoding. This is meant for large
+/// fields that can be reconstructed after loading a state, such as caches and frame buffers that
+/// are fully overwritten before they are next read.
+///
+/// Fields that are marked with `#[savestate(skip, default = <expr>)]` are not encoded, and they are
+/// set to the result of evaluating the given expression when decoding.
+///
+/// If the struct has any generic type parameters, the traits will only be implemented where all of
+/// the generic types implement the corresponding trait.
+///
+/// Example:
+/// ```
+/// use jgenesis_proc_macros::SaveState;
+///
+/// #[derive(Debug, PartialEq, SaveState)]
+/// struct Example {
+///     registers: [u8; 4],
+///     #[savestate(skip)]
+///     cache: Vec<u16>,
+///     #[savestate(skip, default = vec![0; 16])]
+///     frame_buffer: Vec<u32>,
+/// }
+///
+/// let config = bincode::config::standard();
+/// let example = Example { registers: [1, 2, 3, 4], cache: vec![5, 6], frame_buffer: vec![7; 16] };
+/// let bytes = bincode::encode_to_vec(&example, config).unwrap();
+/// let (decoded, _): (Example, _) = bincode::decode_from_slice(&bytes, config).unwrap();
+///
+/// assert_eq!(decoded, Example { registers: [1, 2, 3, 4], cache: vec![], frame_buffer: vec![0; 16] });
+/// ```
+///
+/// # Panics
+///
+/// This macro currently only supports structs, and it will panic if applied to an enum or a union.
+/// It will also panic if a `savestate` attribute does not include `skip`.
+#[proc_macro_derive(SaveState, attributes(savestate))]
+pub fn save_state(input: TokenStream) -> TokenStream {
+    savestate::save_state(input)
+}
+
 /// Implement the `jgenesis_common::frontend::PartialClone` trait for a given struct or enum.
 ///
 /// This macro should be imported through `jgenesis_common` instead of directly from this crate so
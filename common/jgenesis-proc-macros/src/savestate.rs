@@ -0,0 +1,151 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Expr, Field, Fields, parse_quote};
+
+pub fn save_state(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = syn::parse(input).expect("Unable to parse input");
+
+    let type_ident = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        panic!("SaveState only supports structs; {type_ident} is not a struct");
+    };
+
+    let fields: Vec<_> = data.fields.iter().map(parse_savestate_attr).collect();
+
+    let field_accessors: Vec<_> = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => quote! { #ident },
+            None => {
+                let i = syn::Index::from(i);
+                quote! { #i }
+            }
+        })
+        .collect();
+
+    // Avoid unused variable warnings in structs where every field is skipped
+    let (encoder, decoder) = if fields.iter().any(|attr| matches!(attr, SaveStateAttr::None)) {
+        (format_ident!("encoder"), format_ident!("decoder"))
+    } else {
+        (format_ident!("_encoder"), format_ident!("_decoder"))
+    };
+
+    let encode_statements: Vec<_> = fields
+        .iter()
+        .zip(&field_accessors)
+        .filter(|(attr, _)| matches!(attr, SaveStateAttr::None))
+        .map(|(_, accessor)| {
+            quote! {
+                ::bincode::Encode::encode(&self.#accessor, #encoder)?;
+            }
+        })
+        .collect();
+
+    // Fields are decoded into locals in declaration order before constructing Self so that any
+    // default expressions for skipped fields are evaluated after all serialized fields are read
+    let field_locals: Vec<_> = (0..fields.len()).map(|i| format_ident!("field{i}")).collect();
+    let decode_statements: Vec<_> = fields
+        .iter()
+        .zip(&field_locals)
+        .map(|(attr, local)| match attr {
+            SaveStateAttr::None => quote! {
+                let #local = ::bincode::Decode::decode(#decoder)?;
+            },
+            SaveStateAttr::Skip(None) => quote! {
+                let #local = ::std::default::Default::default();
+            },
+            SaveStateAttr::Skip(Some(default)) => quote! {
+                let #local = #default;
+            },
+        })
+        .collect();
+
+    let constructor = match &data.fields {
+        Fields::Unit => quote! { Self },
+        Fields::Unnamed(_) => quote! { Self(#(#field_locals,)*) },
+        Fields::Named(_) => quote! { Self { #(#field_accessors: #field_locals,)* } },
+    };
+
+    let mut encode_generics = input.generics.clone();
+    for type_param in encode_generics.type_params_mut() {
+        type_param.bounds.push(parse_quote!(::bincode::Encode));
+    }
+    let (encode_impl_generics, type_generics, encode_where_clause) =
+        encode_generics.split_for_impl();
+
+    let mut decode_generics = input.generics.clone();
+    for type_param in decode_generics.type_params_mut() {
+        type_param.bounds.push(parse_quote!(::bincode::Decode));
+    }
+    let (decode_impl_generics, _, decode_where_clause) = decode_generics.split_for_impl();
+
+    let mut borrow_decode_generics = decode_generics.clone();
+    borrow_decode_generics.params.insert(0, parse_quote!('__de));
+    let (borrow_decode_impl_generics, _, _) = borrow_decode_generics.split_for_impl();
+
+    let gen = quote! {
+        impl #encode_impl_generics ::bincode::Encode for #type_ident #type_generics #encode_where_clause {
+            fn encode<__E: ::bincode::enc::Encoder>(
+                &self,
+                #encoder: &mut __E
+            ) -> ::std::result::Result<(), ::bincode::error::EncodeError> {
+                #(#encode_statements)*
+                ::std::result::Result::Ok(())
+            }
+        }
+
+        impl #decode_impl_generics ::bincode::Decode for #type_ident #type_generics #decode_where_clause {
+            fn decode<__D: ::bincode::de::Decoder>(
+                #decoder: &mut __D
+            ) -> ::std::result::Result<Self, ::bincode::error::DecodeError> {
+                #(#decode_statements)*
+                ::std::result::Result::Ok(#constructor)
+            }
+        }
+
+        impl #borrow_decode_impl_generics ::bincode::BorrowDecode<'__de> for #type_ident #type_generics #decode_where_clause {
+            fn borrow_decode<__D: ::bincode::de::BorrowDecoder<'__de>>(
+                decoder: &mut __D
+            ) -> ::std::result::Result<Self, ::bincode::error::DecodeError> {
+                ::bincode::Decode::decode(decoder)
+            }
+        }
+    };
+
+    gen.into()
+}
+
+enum SaveStateAttr {
+    None,
+    Skip(Option<Expr>),
+}
+
+fn parse_savestate_attr(field: &Field) -> SaveStateAttr {
+    field
+        .attrs
+        .iter()
+        .find_map(|attr| {
+            attr.path().is_ident("savestate").then(|| {
+                let mut skip = false;
+                let mut default = None;
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("skip") {
+                        skip = true;
+                        Ok(())
+                    } else if meta.path.is_ident("default") {
+                        default = Some(meta.value()?.parse::<Expr>()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("nested savestate attribute must be 'skip' or 'default'"))
+                    }
+                })
+                .expect("Unable to parse savestate attribute");
+
+                assert!(skip, "savestate attribute must include 'skip'");
+                SaveStateAttr::Skip(default)
+            })
+        })
+        .unwrap_or(SaveStateAttr::None)
+}
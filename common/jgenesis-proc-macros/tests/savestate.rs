@@ -0,0 +1,81 @@
+use bincode::{Decode, Encode};
+use jgenesis_proc_macros::SaveState;
+
+fn round_trip<T: Encode + Decode>(value: &T) -> (T, usize) {
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(value, config).unwrap();
+    let (decoded, _) = bincode::decode_from_slice(&bytes, config).unwrap();
+    (decoded, bytes.len())
+}
+
+#[derive(Debug, PartialEq, SaveState)]
+struct UnitStruct;
+
+#[test]
+fn unit_struct() {
+    assert_eq!(round_trip(&UnitStruct), (UnitStruct, 0));
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Unskipped {
+    a: u8,
+    b: Vec<u16>,
+    c: String,
+}
+
+#[derive(Debug, PartialEq, SaveState)]
+struct NamedFields {
+    a: u8,
+    #[savestate(skip)]
+    b: Vec<u16>,
+    c: String,
+}
+
+#[test]
+fn named_fields() {
+    let value = NamedFields { a: 5, b: vec![1, 2, 3], c: "hello".into() };
+    let (decoded, len) = round_trip(&value);
+    assert_eq!(decoded, NamedFields { a: 5, b: vec![], c: "hello".into() });
+
+    // Skipped fields should take up no space, and the remaining fields should be encoded the same
+    // way that bincode's derive would encode them
+    let (_, unskipped_len) = round_trip(&Unskipped { a: 5, b: vec![], c: "hello".into() });
+    assert_eq!(len, unskipped_len - 1);
+}
+
+fn new_buffer() -> Vec<u32> {
+    vec![0xFF; 8]
+}
+
+#[derive(Debug, PartialEq, SaveState)]
+struct UnnamedFields(#[savestate(skip, default = new_buffer())] Vec<u32>, u16);
+
+#[test]
+fn unnamed_fields_default_expr() {
+    let (decoded, _) = round_trip(&UnnamedFields(vec![1, 2], 300));
+    assert_eq!(decoded, UnnamedFields(new_buffer(), 300));
+}
+
+#[derive(Debug, PartialEq, SaveState)]
+struct Generic<T> {
+    value: T,
+    #[savestate(skip)]
+    cache: Option<T>,
+}
+
+#[test]
+fn generic() {
+    let (decoded, _) = round_trip(&Generic { value: 1234_u32, cache: Some(5678) });
+    assert_eq!(decoded, Generic { value: 1234, cache: None });
+}
+
+#[derive(Debug, PartialEq, SaveState)]
+struct AllSkipped {
+    #[savestate(skip)]
+    a: Vec<u8>,
+}
+
+#[test]
+fn all_fields_skipped() {
+    assert_eq!(round_trip(&AllSkipped { a: vec![1, 2, 3] }), (AllSkipped { a: vec![] }, 0));
+}
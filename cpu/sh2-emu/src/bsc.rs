@@ -0,0 +1,355 @@
+//! SH7604 bus state controller (BSC)
+//!
+//! Only SDRAM CAS latency and CAS-before-RAS refresh timing are emulated. The remaining registers
+//! are stored so that they are R/W but do not affect bus timing
+
+use bincode::{Decode, Encode};
+use jgenesis_common::num::GetBit;
+
+// Bus control registers can only be written using longword writes with this value in the upper 16 bits
+const WRITE_PROTECT_CODE: u32 = 0xA55A;
+
+// Timing is calibrated for the CAS latency that the 32X boot ROMs configure
+const DEFAULT_CAS_LATENCY: u8 = 2;
+
+fn refresh_clock_shift(rtcsr: u8) -> Option<u8> {
+    match (rtcsr >> 3) & 7 {
+        // Refresh counter stopped
+        0 => None,
+        // sysclk/4
+        1 => Some(2),
+        // sysclk/16
+        2 => Some(4),
+        // sysclk/64
+        3 => Some(6),
+        // sysclk/256
+        4 => Some(8),
+        // sysclk/1024
+        5 => Some(10),
+        // sysclk/2048
+        6 => Some(11),
+        // sysclk/4096
+        7 => Some(12),
+        _ => unreachable!("value & 7 is always <= 7"),
+    }
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct BusStateController {
+    // Set by the MD5 pin; read-only BCR1 bit 15
+    slave: bool,
+    bcr1: u16,
+    bcr2: u16,
+    wcr: u16,
+    mcr: u16,
+    rtcsr: u8,
+    rtcnt: u8,
+    rtcor: u8,
+    cas_latency: u8,
+    refresh_clock_counter: u64,
+    pending_refresh_cycles: u64,
+}
+
+impl BusStateController {
+    pub fn new(slave: bool) -> Self {
+        Self {
+            slave,
+            bcr1: 0x03F0,
+            bcr2: 0x00FC,
+            wcr: 0xAAFF,
+            mcr: 0,
+            rtcsr: 0,
+            rtcnt: 0,
+            rtcor: 0,
+            cas_latency: DEFAULT_CAS_LATENCY,
+            refresh_clock_counter: 0,
+            pending_refresh_cycles: 0,
+        }
+    }
+
+    pub fn cas_latency(&self) -> u8 {
+        self.cas_latency
+    }
+
+    pub fn take_pending_refresh_cycles(&mut self) -> u64 {
+        std::mem::take(&mut self.pending_refresh_cycles)
+    }
+
+    fn refresh_enabled(&self) -> bool {
+        // RFSH=1 and RMODE=0 (CAS-before-RAS refresh rather than self-refresh)
+        self.mcr.bit(3) && !self.mcr.bit(2)
+    }
+
+    // Length of a CAS-before-RAS refresh cycle: RAS precharge time plus RAS assert time
+    fn refresh_cycle_len(&self) -> u64 {
+        let precharge = if self.mcr.bit(15) { 2 } else { 1 };
+        let ras_assert = match (self.mcr >> 11) & 3 {
+            0 => 2,
+            1 => 3,
+            // 3 is reserved
+            _ => 4,
+        };
+        precharge + ras_assert
+    }
+
+    pub fn tick(&mut self, system_cycles: u64) {
+        let Some(shift) = refresh_clock_shift(self.rtcsr) else { return };
+
+        self.refresh_clock_counter += system_cycles;
+        let elapsed = self.refresh_clock_counter >> shift;
+        self.refresh_clock_counter &= (1 << shift) - 1;
+
+        // The counter clears on the cycle after a compare match
+        let period = u64::from(self.rtcor) + 1;
+        let counter = u64::from(self.rtcnt) + elapsed;
+        let matches = counter / period;
+        self.rtcnt = (counter % period) as u8;
+
+        if matches != 0 {
+            // CMF
+            self.rtcsr |= 1 << 7;
+
+            if self.refresh_enabled() && !self.slave {
+                // Only the bus master performs refresh
+                self.pending_refresh_cycles += matches * self.refresh_cycle_len();
+            }
+        }
+    }
+
+    // $FFFF8000-$FFFFBFFF: SDRAM mode register; the mode is encoded in the address bits
+    pub fn write_sdram_mode(&mut self, address: u32) {
+        // MCR.SZ specifies 16-bit or 32-bit SDRAM, which determines which address bits are used
+        let mode = if self.mcr.bit(6) { (address & 0x3FFF) >> 2 } else { (address & 0x3FFF) >> 1 };
+
+        let cas_latency = ((mode >> 4) & 7) as u8;
+        if !(1..=3).contains(&cas_latency) {
+            log::warn!("Invalid SDRAM CAS latency in mode write to {address:08X}: {cas_latency}");
+            return;
+        }
+
+        self.cas_latency = cas_latency;
+
+        log::debug!("SDRAM mode write: {address:08X}");
+        log::debug!("  CAS latency: {cas_latency}");
+        log::debug!("  Burst length: {}", 1 << (mode & 7));
+    }
+
+    // $FFFFFFE0-$FFFFFFF8: Bus control registers
+    pub fn read_register(&self, address: u32) -> u32 {
+        match address {
+            0xFFFFFFE0 => (u16::from(self.slave) << 15 | self.bcr1).into(),
+            0xFFFFFFE4 => self.bcr2.into(),
+            0xFFFFFFE8 => self.wcr.into(),
+            0xFFFFFFEC => self.mcr.into(),
+            0xFFFFFFF0 => self.rtcsr.into(),
+            0xFFFFFFF4 => self.rtcnt.into(),
+            0xFFFFFFF8 => self.rtcor.into(),
+            _ => {
+                log::warn!("Unexpected bus control register read {address:08X}");
+                0
+            }
+        }
+    }
+
+    // $FFFFFFE0-$FFFFFFF8: Bus control registers
+    pub fn write_register(&mut self, address: u32, value: u32) {
+        if value >> 16 != WRITE_PROTECT_CODE {
+            log::warn!(
+                "Bus control register write without write protect code: {address:08X} {value:08X}"
+            );
+            return;
+        }
+
+        match address {
+            0xFFFFFFE0 => {
+                // Master/slave mode is read-only
+                self.bcr1 = value as u16 & 0x7FFF;
+
+                log::trace!("BCR1 write: {value:08X}");
+                log::trace!("  Big endian mode: {}", !value.bit(12));
+                log::trace!("  Area 0 burst ROM enabled: {}", value.bit(11));
+                log::trace!("  Partial-share master mode: {}", value.bit(10));
+                log::trace!(
+                    "  Long wait specification for areas 2/3: {} waits",
+                    ((value >> 8) & 3) + 3
+                );
+                log::trace!(
+                    "  Long wait specification for area 1: {} waits",
+                    ((value >> 6) & 3) + 3
+                );
+                log::trace!(
+                    "  Long wait specification for area 0: {} waits",
+                    ((value >> 4) & 3) + 3
+                );
+                log::trace!("  DRAM specification bits: {}", value & 7);
+            }
+            0xFFFFFFE4 => {
+                self.bcr2 = value as u16;
+
+                log::trace!("BCR2 write: {value:08X}");
+                log::trace!("  Size specification for area 3: {}", bus_area_size(value >> 6));
+                log::trace!("  Size specification for area 2: {}", bus_area_size(value >> 4));
+                log::trace!("  Size specification for area 1: {}", bus_area_size(value >> 2));
+            }
+            0xFFFFFFE8 => {
+                self.wcr = value as u16;
+
+                log::trace!("WCR write: {value:08X}");
+                log::trace!("  Idles between cycles for area 3: {}", idle_cycles(value >> 14));
+                log::trace!("  Idles between cycles for area 2: {}", idle_cycles(value >> 12));
+                log::trace!("  Idles between cycles for area 1: {}", idle_cycles(value >> 10));
+                log::trace!("  Idles between cycles for area 0: {}", idle_cycles(value >> 8));
+                log::trace!("  Wait control for area 3: {}", (value >> 6) & 3);
+                log::trace!("  Wait control for area 2: {}", (value >> 4) & 3);
+                log::trace!("  Wait control for area 1: {}", (value >> 2) & 3);
+                log::trace!("  Wait control for area 0: {}", value & 3);
+            }
+            0xFFFFFFEC => {
+                self.mcr = value as u16;
+
+                log::trace!("MCR write: {value:08X}");
+                log::trace!("  RAS precharge time: {}", if value.bit(15) { 2 } else { 1 });
+                log::trace!("  RAS-CAS delay: {}", if value.bit(14) { 2 } else { 1 });
+                log::trace!("  Write precharge delay: {}", if value.bit(13) { 2 } else { 1 });
+                log::trace!(
+                    "  CAS-before-RAS refresh RAS assert time: {}",
+                    match (value >> 11) & 3 {
+                        0 => "2 cycles",
+                        1 => "3 cycles",
+                        2 => "4 cycles",
+                        3 => "(Reserved)",
+                        _ => unreachable!(),
+                    }
+                );
+                log::trace!("  Burst enabled: {}", value.bit(10));
+                log::trace!("  RAS down mode enabled: {}", value.bit(9));
+                log::trace!(
+                    "  Address multiplexing bits: {}",
+                    ((value >> 5) & 0x4) | ((value >> 4) & 0x3)
+                );
+                log::trace!(
+                    "  DRAM memory data size: {}",
+                    if value.bit(6) { "Longword" } else { "Word" }
+                );
+                log::trace!("  DRAM refresh enabled: {}", value.bit(3));
+                log::trace!("  Self-refresh enabled: {}", value.bit(2));
+            }
+            0xFFFFFFF0 => {
+                // CMF can only be cleared by writing 0; writing 1 has no effect
+                let cmf = self.rtcsr.bit(7) && value.bit(7);
+                self.rtcsr = (u8::from(cmf) << 7) | (value as u8 & 0x78);
+
+                log::trace!("RTCSR write: {value:08X}");
+                log::trace!("  Compare match flag: {}", value.bit(7));
+                log::trace!("  Compare match interrupt enabled: {}", value.bit(6));
+                log::trace!("  Clock select bits: {}", (value >> 3) & 7);
+
+                if value.bit(6) {
+                    log::error!(
+                        "SH-2 refresh timer compare match interrupt was enabled; not emulated"
+                    );
+                }
+            }
+            0xFFFFFFF4 => {
+                self.rtcnt = value as u8;
+
+                log::trace!("RTCNT write: {value:08X}");
+                log::trace!("  Refresh timer counter: 0x{:02X}", value & 0xFF);
+            }
+            0xFFFFFFF8 => {
+                self.rtcor = value as u8;
+
+                log::trace!("RTCOR write: {value:08X}");
+                log::trace!("  Refresh time constant for compare: 0x{:02X}", value & 0xFF);
+            }
+            _ => log::warn!("Bus control register write {address:08X} {value:08X}"),
+        }
+    }
+}
+
+fn bus_area_size(value: u32) -> &'static str {
+    match value & 3 {
+        0 => "(Reserved)",
+        1 => "Byte",
+        2 => "Word",
+        3 => "Longword",
+        _ => unreachable!("value & 3 is always <= 3"),
+    }
+}
+
+fn idle_cycles(value: u32) -> &'static str {
+    match value & 3 {
+        0 => "0 cycles",
+        1 => "1 cycle",
+        2 => "2 cycles",
+        3 => "(Reserved)",
+        _ => unreachable!("value & 3 is always <= 3"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sdram_mode_write() {
+        let mut bsc = BusStateController::new(false);
+
+        // 16-bit SDRAM, CAS latency 1
+        bsc.write_sdram_mode(0xFFFF8426);
+        assert_eq!(bsc.cas_latency(), 1);
+
+        // 16-bit SDRAM, CAS latency 3
+        bsc.write_sdram_mode(0xFFFF8466);
+        assert_eq!(bsc.cas_latency(), 3);
+
+        // What the 32X boot ROMs write
+        bsc.write_sdram_mode(0xFFFF8446);
+        assert_eq!(bsc.cas_latency(), 2);
+    }
+
+    #[test]
+    fn refresh_timing() {
+        let mut bsc = BusStateController::new(false);
+
+        // Master mode, RFSH=1, RAS precharge 1 cycle, RAS assert 2 cycles
+        bsc.write_register(0xFFFFFFE0, 0xA55A0001);
+        bsc.write_register(0xFFFFFFEC, 0xA55A0008);
+        // Refresh every 55 ticks of sysclk/4
+        bsc.write_register(0xFFFFFFF8, 0xA55A0036);
+        bsc.write_register(0xFFFFFFF0, 0xA55A0008);
+
+        bsc.tick(54 * 4);
+        assert_eq!(bsc.take_pending_refresh_cycles(), 0);
+        assert_eq!(bsc.read_register(0xFFFFFFF4), 54);
+
+        bsc.tick(4);
+        assert_eq!(bsc.take_pending_refresh_cycles(), 3);
+        assert_eq!(bsc.read_register(0xFFFFFFF4), 0);
+        assert!(bsc.read_register(0xFFFFFFF0).bit(7));
+
+        bsc.tick(2 * 55 * 4);
+        assert_eq!(bsc.take_pending_refresh_cycles(), 6);
+
+        // Writes without the write protect code are ignored
+        bsc.write_register(0xFFFFFFEC, 0x00000000);
+        bsc.tick(55 * 4);
+        assert_eq!(bsc.take_pending_refresh_cycles(), 3);
+
+        // Master/slave mode is set by the MD5 pin and cannot be changed by writing BCR1
+        bsc.write_register(0xFFFFFFE0, 0xA55A8001);
+        assert_eq!(bsc.read_register(0xFFFFFFE0), 0x0001);
+        bsc.tick(55 * 4);
+        assert_eq!(bsc.take_pending_refresh_cycles(), 3);
+
+        // A slave CPU never performs refresh
+        let mut slave_bsc = BusStateController::new(true);
+        slave_bsc.write_register(0xFFFFFFE0, 0xA55A0001);
+        slave_bsc.write_register(0xFFFFFFEC, 0xA55A0008);
+        slave_bsc.write_register(0xFFFFFFF8, 0xA55A0036);
+        slave_bsc.write_register(0xFFFFFFF0, 0xA55A0008);
+        assert_eq!(slave_bsc.read_register(0xFFFFFFE0), 0x8001);
+        slave_bsc.tick(55 * 4);
+        assert_eq!(slave_bsc.take_pending_refresh_cycles(), 0);
+    }
+}
@@ -12,6 +12,10 @@ pub trait BusInterface {
 
     fn read_cache_line(&mut self, address: u32) -> [u32; 4];
 
+    /// Read a byte without any side effects or timing, for the debugger. Returns `None` if the
+    /// address cannot be read without side effects, e.g. memory-mapped registers.
+    fn peek_byte(&self, address: u32) -> Option<u8>;
+
     fn write_byte(&mut self, address: u32, value: u8);
 
     fn write_word(&mut self, address: u32, value: u16);
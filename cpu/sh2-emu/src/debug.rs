@@ -0,0 +1,472 @@
+//! Breakpoint and single-step support for attaching an external debugger to the SH-2
+
+pub mod expr;
+
+use crate::bus::BusInterface;
+use crate::debug::expr::{Expression, MemorySize};
+use crate::registers::Sh2Registers;
+use crate::{EXTERNAL_ADDRESS_MASK, Sh2};
+use bincode::{Decode, Encode};
+
+/// SH-2 registers that are visible to a debugger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum Sh2Register {
+    /// R0-R15
+    Gpr(u8),
+    Pc,
+    Pr,
+    Gbr,
+    Vbr,
+    Mach,
+    Macl,
+    Sr,
+}
+
+/// A breakpoint over a range of addresses that only triggers when its condition (if any)
+/// evaluates to a non-zero value.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct ConditionalBreakpoint {
+    /// First address in the range, inclusive
+    pub start: u32,
+    /// Last address in the range, inclusive
+    pub end: u32,
+    pub condition: Option<Expression>,
+}
+
+/// An expression that is evaluated before every instruction; the CPU stops whenever its value
+/// changes.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Watch {
+    pub expression: Expression,
+    // None until the first evaluation
+    value: Option<u32>,
+}
+
+impl Watch {
+    /// The expression's value as of the last instruction, or `None` if it has not been evaluated
+    /// yet.
+    #[must_use]
+    pub fn value(&self) -> Option<u32> {
+        self.value
+    }
+}
+
+/// The most recent change in a watch expression's value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct WatchHit {
+    pub index: usize,
+    pub old_value: u32,
+    pub new_value: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DebugState {
+    breakpoints: Vec<u32>,
+    conditional_breakpoints: Vec<ConditionalBreakpoint>,
+    watches: Vec<Watch>,
+    watch_hit: Option<WatchHit>,
+    stepping: bool,
+    stepped: bool,
+    stopped: bool,
+    // Set on resume so that continuing from a breakpoint does not immediately hit it again
+    skip_breakpoint_at: Option<u32>,
+    // Cached so that the per-instruction check is a single branch when no debugger is attached
+    active: bool,
+}
+
+impl DebugState {
+    fn update_active(&mut self) {
+        self.active = !self.breakpoints.is_empty()
+            || !self.conditional_breakpoints.is_empty()
+            || !self.watches.is_empty()
+            || self.stepping
+            || self.stopped;
+    }
+
+    #[inline]
+    pub(crate) fn active(&self) -> bool {
+        self.active
+    }
+
+    #[inline]
+    pub(crate) fn stopped(&self) -> bool {
+        self.stopped
+    }
+
+    // Called before executing the instruction at `pc`; returns true if the CPU should stop.
+    // `evaluate` evaluates breakpoint conditions and watch expressions in the current CPU state
+    pub(crate) fn should_break(
+        &mut self,
+        pc: u32,
+        mut evaluate: impl FnMut(&Expression) -> u32,
+    ) -> bool {
+        if self.stepping {
+            if self.stepped {
+                self.stepping = false;
+                self.stepped = false;
+                self.stopped = true;
+                return true;
+            }
+
+            self.stepped = true;
+        }
+
+        // Watches are updated even when skipping a breakpoint so that a change in value is never
+        // reported twice
+        let skip_breakpoint = self.skip_breakpoint_at.take() == Some(pc);
+
+        if self.update_watches(&mut evaluate) {
+            self.stopped = true;
+            return true;
+        }
+
+        if skip_breakpoint {
+            return false;
+        }
+
+        if self.breakpoints.contains(&pc)
+            || self.conditional_breakpoints.iter().any(|breakpoint| {
+                (breakpoint.start..=breakpoint.end).contains(&pc)
+                    && breakpoint
+                        .condition
+                        .as_ref()
+                        .is_none_or(|condition| evaluate(condition) != 0)
+            })
+        {
+            self.stopped = true;
+            return true;
+        }
+
+        false
+    }
+
+    // Returns true if any watch value changed
+    fn update_watches(&mut self, evaluate: &mut impl FnMut(&Expression) -> u32) -> bool {
+        let mut changed = false;
+        for (index, watch) in self.watches.iter_mut().enumerate() {
+            let new_value = evaluate(&watch.expression);
+            let Some(old_value) = watch.value.replace(new_value) else { continue };
+
+            if old_value != new_value && !changed {
+                changed = true;
+                self.watch_hit = Some(WatchHit { index, old_value, new_value });
+            }
+        }
+
+        changed
+    }
+}
+
+fn read_register(registers: &Sh2Registers, register: Sh2Register) -> u32 {
+    match register {
+        Sh2Register::Gpr(n) => registers.gpr[(n & 0xF) as usize],
+        Sh2Register::Pc => registers.pc,
+        Sh2Register::Pr => registers.pr,
+        Sh2Register::Gbr => registers.gbr,
+        Sh2Register::Vbr => registers.vbr,
+        Sh2Register::Mach => registers.mach,
+        Sh2Register::Macl => registers.macl,
+        Sh2Register::Sr => registers.sr.into(),
+    }
+}
+
+// Expressions peek memory from the bus without side effects, bypassing the cache, and can only see
+// the cached and cache-through external address areas. Everything else reads as 0, as do
+// addresses that cannot be read without side effects
+fn read_memory<B: BusInterface>(bus: &B, address: u32, size: MemorySize) -> u32 {
+    if address >> 29 > 1 {
+        return 0;
+    }
+
+    let address = address & EXTERNAL_ADDRESS_MASK;
+    let (address, len) = match size {
+        MemorySize::Byte => (address, 1),
+        MemorySize::Word => (address & !1, 2),
+        MemorySize::Longword => (address & !3, 4),
+    };
+
+    // Big-endian
+    (0..len).fold(0, |value, i| (value << 8) | u32::from(bus.peek_byte(address + i).unwrap_or(0)))
+}
+
+impl Sh2 {
+    #[inline]
+    pub(crate) fn debug_should_break<B: BusInterface>(&mut self, bus: &B) -> bool {
+        let registers = &self.registers;
+        self.debug.should_break(registers.pc, |expression| {
+            expression.evaluate(
+                |register| read_register(registers, register),
+                |address, size| read_memory(bus, address, size),
+            )
+        })
+    }
+
+    #[must_use]
+    pub fn read_register(&self, register: Sh2Register) -> u32 {
+        read_register(&self.registers, register)
+    }
+
+    pub fn write_register(&mut self, register: Sh2Register, value: u32) {
+        match register {
+            Sh2Register::Gpr(n) => self.registers.gpr[(n & 0xF) as usize] = value,
+            Sh2Register::Pc => {
+                self.registers.pc = value;
+                self.registers.next_pc = value.wrapping_add(2);
+                self.registers.next_op_in_delay_slot = false;
+            }
+            Sh2Register::Pr => self.registers.pr = value,
+            Sh2Register::Gbr => self.registers.gbr = value,
+            Sh2Register::Vbr => self.registers.vbr = value,
+            Sh2Register::Mach => self.registers.mach = value,
+            Sh2Register::Macl => self.registers.macl = value,
+            Sh2Register::Sr => self.registers.sr = value.into(),
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, address: u32) {
+        if !self.debug.breakpoints.contains(&address) {
+            self.debug.breakpoints.push(address);
+        }
+        self.debug.update_active();
+    }
+
+    pub fn remove_breakpoint(&mut self, address: u32) {
+        self.debug.breakpoints.retain(|&breakpoint| breakpoint != address);
+        self.debug.update_active();
+    }
+
+    pub fn add_conditional_breakpoint(&mut self, breakpoint: ConditionalBreakpoint) {
+        self.debug.conditional_breakpoints.push(breakpoint);
+        self.debug.update_active();
+    }
+
+    /// Returns `false` if there is no conditional breakpoint with the given index.
+    pub fn remove_conditional_breakpoint(&mut self, index: usize) -> bool {
+        if index >= self.debug.conditional_breakpoints.len() {
+            return false;
+        }
+
+        self.debug.conditional_breakpoints.remove(index);
+        self.debug.update_active();
+        true
+    }
+
+    #[must_use]
+    pub fn conditional_breakpoints(&self) -> &[ConditionalBreakpoint] {
+        &self.debug.conditional_breakpoints
+    }
+
+    pub fn add_watch(&mut self, expression: Expression) {
+        self.debug.watches.push(Watch { expression, value: None });
+        self.debug.update_active();
+    }
+
+    /// Returns `false` if there is no watch with the given index.
+    pub fn remove_watch(&mut self, index: usize) -> bool {
+        if index >= self.debug.watches.len() {
+            return false;
+        }
+
+        self.debug.watches.remove(index);
+        self.debug.update_active();
+        true
+    }
+
+    #[must_use]
+    pub fn watches(&self) -> &[Watch] {
+        &self.debug.watches
+    }
+
+    /// If the CPU stopped because a watch expression changed value, returns the change. Cleared
+    /// on resume.
+    #[must_use]
+    pub fn watch_hit(&self) -> Option<WatchHit> {
+        self.debug.watch_hit
+    }
+
+    /// Remove all breakpoints and watches and resume execution if stopped.
+    pub fn detach_debugger(&mut self) {
+        self.debug = DebugState::default();
+    }
+
+    /// Whether the CPU is stopped at a breakpoint or after a single step. A stopped CPU will not
+    /// execute any instructions until [`debug_resume`](Self::debug_resume) or
+    /// [`debug_step`](Self::debug_step) is called.
+    #[must_use]
+    pub fn debug_stopped(&self) -> bool {
+        self.debug.stopped
+    }
+
+    pub fn debug_resume(&mut self) {
+        if self.debug.stopped {
+            self.debug.stopped = false;
+            self.debug.skip_breakpoint_at = Some(self.registers.pc);
+        }
+        self.debug.watch_hit = None;
+        self.debug.update_active();
+    }
+
+    /// Resume execution for a single instruction, then stop again.
+    pub fn debug_step(&mut self) {
+        self.debug.stepping = true;
+        self.debug.stepped = false;
+        self.debug_resume();
+    }
+
+    /// Move debugger state (breakpoints and stop status) from another instance of this CPU, e.g.
+    /// after loading a save state.
+    pub fn take_debug_state_from(&mut self, other: &mut Self) {
+        self.debug = std::mem::take(&mut other.debug);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NullBus;
+
+    impl BusInterface for NullBus {
+        fn read_byte(&mut self, _address: u32) -> u8 {
+            0
+        }
+
+        fn read_word(&mut self, _address: u32) -> u16 {
+            0
+        }
+
+        fn read_longword(&mut self, _address: u32) -> u32 {
+            0
+        }
+
+        fn read_cache_line(&mut self, _address: u32) -> [u32; 4] {
+            [0; 4]
+        }
+
+        fn peek_byte(&self, address: u32) -> Option<u8> {
+            // Treat everything from $100000 up as registers that cannot be peeked
+            (address < 0x100000).then_some(address as u8)
+        }
+
+        fn write_byte(&mut self, _address: u32, _value: u8) {}
+
+        fn write_word(&mut self, _address: u32, _value: u16) {}
+
+        fn write_longword(&mut self, _address: u32, _value: u32) {}
+
+        fn reset(&self) -> bool {
+            false
+        }
+
+        fn interrupt_level(&self) -> u8 {
+            0
+        }
+
+        fn dma_request_0(&self) -> bool {
+            false
+        }
+
+        fn dma_request_1(&self) -> bool {
+            false
+        }
+
+        fn serial_rx(&mut self) -> Option<u8> {
+            None
+        }
+
+        fn serial_tx(&mut self, _value: u8) {}
+    }
+
+    #[test]
+    fn breakpoint_resume_and_step() {
+        let mut sh2 = Sh2::new("Test".into());
+        sh2.registers.pc = 0x100;
+        sh2.add_breakpoint(0x100);
+
+        assert!(sh2.debug.should_break(0x100, |_| 0));
+        assert!(sh2.debug_stopped());
+
+        // Resuming should not immediately re-hit the same breakpoint
+        sh2.debug_resume();
+        assert!(!sh2.debug.should_break(0x100, |_| 0));
+        assert!(!sh2.debug.should_break(0x102, |_| 0));
+
+        // Stepping executes exactly one instruction before stopping
+        sh2.registers.pc = 0x104;
+        sh2.debug_step();
+        assert!(!sh2.debug.should_break(0x104, |_| 0));
+        assert!(sh2.debug.should_break(0x106, |_| 0));
+        assert!(sh2.debug_stopped());
+
+        sh2.detach_debugger();
+        assert!(!sh2.debug_stopped());
+        assert!(!sh2.debug.active());
+    }
+
+    #[test]
+    fn expression_memory_reads_peek() {
+        assert_eq!(read_memory(&NullBus, 0x000011, MemorySize::Byte), 0x11);
+        assert_eq!(read_memory(&NullBus, 0x000011, MemorySize::Word), 0x1011);
+        assert_eq!(read_memory(&NullBus, 0x20000011, MemorySize::Longword), 0x10111213);
+        assert_eq!(read_memory(&NullBus, 0x100000, MemorySize::Longword), 0);
+        assert_eq!(read_memory(&NullBus, 0xFFFFFE00, MemorySize::Longword), 0);
+    }
+
+    #[test]
+    fn conditional_breakpoint_range() {
+        let mut sh2 = Sh2::new("Test".into());
+        sh2.add_conditional_breakpoint(ConditionalBreakpoint {
+            start: 0x200,
+            end: 0x210,
+            condition: Some(Expression::parse("r0 == 0x42").unwrap()),
+        });
+
+        sh2.registers.gpr[0] = 0x42;
+        sh2.registers.pc = 0x1FE;
+        assert!(!sh2.debug_should_break(&NullBus));
+
+        sh2.registers.gpr[0] = 0x41;
+        sh2.registers.pc = 0x208;
+        assert!(!sh2.debug_should_break(&NullBus));
+
+        sh2.registers.gpr[0] = 0x42;
+        assert!(sh2.debug_should_break(&NullBus));
+        assert!(sh2.debug_stopped());
+
+        assert!(sh2.remove_conditional_breakpoint(0));
+        assert!(!sh2.remove_conditional_breakpoint(0));
+        sh2.debug_resume();
+        assert!(!sh2.debug.active());
+    }
+
+    #[test]
+    fn watch_stops_on_change() {
+        let mut sh2 = Sh2::new("Test".into());
+        sh2.add_watch(Expression::parse("r1 + 1").unwrap());
+
+        sh2.registers.gpr[1] = 5;
+        assert!(!sh2.debug.should_break(0x100, |e| e.evaluate(|_| 5, |_, _| 0)));
+        assert!(!sh2.debug.should_break(0x102, |e| e.evaluate(|_| 5, |_, _| 0)));
+
+        assert!(sh2.debug.should_break(0x104, |e| e.evaluate(|_| 6, |_, _| 0)));
+        assert_eq!(sh2.watch_hit(), Some(WatchHit { index: 0, old_value: 6, new_value: 7 }));
+
+        sh2.debug_resume();
+        assert_eq!(sh2.watch_hit(), None);
+        assert!(!sh2.debug.should_break(0x104, |e| e.evaluate(|_| 6, |_, _| 0)));
+    }
+
+    #[test]
+    fn debug_state_not_saved() {
+        let mut sh2 = Sh2::new("Test".into());
+        sh2.add_breakpoint(0x100);
+
+        let config = bincode::config::standard();
+        let bytes = bincode::encode_to_vec(&sh2, config).unwrap();
+        let (mut loaded, _): (Sh2, _) = bincode::decode_from_slice(&bytes, config).unwrap();
+        assert!(!loaded.debug.active());
+
+        loaded.take_debug_state_from(&mut sh2);
+        assert!(loaded.debug.should_break(0x100, |_| 0));
+    }
+}
@@ -0,0 +1,472 @@
+//! A small expression language for debugger breakpoint conditions and watch expressions
+//!
+//! Expressions can only read registers and memory, and they are compiled to a flat list of
+//! operations, so evaluating one can never loop, modify emulator state, or panic.
+//!
+//! Syntax:
+//! - Literals: decimal (`66`) or hex (`0x42` or `$42`)
+//! - Registers: `r0`-`r15`, `pc`, `pr`, `gbr`, `vbr`, `mach`, `macl`, `sr`
+//! - Memory reads: `b[addr]` (byte), `w[addr]` (word), `l[addr]` (longword)
+//! - Operators, from lowest to highest precedence: `||`, `&&`, `|`, `^`, `&`, `==` `!=`,
+//!   `<` `<=` `>` `>=`, `<<` `>>`, `+` `-`, `*` `/` `%`, and unary `-` `~` `!`
+//!
+//! All arithmetic is wrapping unsigned 32-bit. Comparisons and logical operators produce 0 or 1,
+//! and division or remainder by 0 produces 0.
+
+use crate::debug::Sh2Register;
+use bincode::{Decode, Encode};
+use std::fmt::{Display, Formatter};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum MemorySize {
+    Byte,
+    Word,
+    Longword,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+enum UnaryOp {
+    Negate,
+    Not,
+    LogicalNot,
+}
+
+impl UnaryOp {
+    fn apply(self, operand: u32) -> u32 {
+        match self {
+            Self::Negate => operand.wrapping_neg(),
+            Self::Not => !operand,
+            Self::LogicalNot => (operand == 0).into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+enum BinaryOp {
+    LogicalOr,
+    LogicalAnd,
+    Or,
+    Xor,
+    And,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    ShiftLeft,
+    ShiftRight,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+impl BinaryOp {
+    fn apply(self, l: u32, r: u32) -> u32 {
+        match self {
+            Self::LogicalOr => (l != 0 || r != 0).into(),
+            Self::LogicalAnd => (l != 0 && r != 0).into(),
+            Self::Or => l | r,
+            Self::Xor => l ^ r,
+            Self::And => l & r,
+            Self::Equal => (l == r).into(),
+            Self::NotEqual => (l != r).into(),
+            Self::Less => (l < r).into(),
+            Self::LessEqual => (l <= r).into(),
+            Self::Greater => (l > r).into(),
+            Self::GreaterEqual => (l >= r).into(),
+            Self::ShiftLeft => l.checked_shl(r).unwrap_or(0),
+            Self::ShiftRight => l.checked_shr(r).unwrap_or(0),
+            Self::Add => l.wrapping_add(r),
+            Self::Subtract => l.wrapping_sub(r),
+            Self::Multiply => l.wrapping_mul(r),
+            Self::Divide => l.checked_div(r).unwrap_or(0),
+            Self::Remainder => l.checked_rem(r).unwrap_or(0),
+        }
+    }
+}
+
+// Operators grouped by precedence level, lowest first; all binary operators are left-associative
+const BINARY_PRECEDENCE: &[&[(&str, BinaryOp)]] = &[
+    &[("||", BinaryOp::LogicalOr)],
+    &[("&&", BinaryOp::LogicalAnd)],
+    &[("|", BinaryOp::Or)],
+    &[("^", BinaryOp::Xor)],
+    &[("&", BinaryOp::And)],
+    &[("==", BinaryOp::Equal), ("!=", BinaryOp::NotEqual)],
+    &[
+        ("<=", BinaryOp::LessEqual),
+        (">=", BinaryOp::GreaterEqual),
+        ("<", BinaryOp::Less),
+        (">", BinaryOp::Greater),
+    ],
+    &[("<<", BinaryOp::ShiftLeft), (">>", BinaryOp::ShiftRight)],
+    &[("+", BinaryOp::Add), ("-", BinaryOp::Subtract)],
+    &[("*", BinaryOp::Multiply), ("/", BinaryOp::Divide), ("%", BinaryOp::Remainder)],
+];
+
+// Maximum nesting of parentheses, memory reads, and unary operators; the parser is recursive, so
+// this keeps deeply nested input from overflowing the stack
+const MAX_NESTING_DEPTH: usize = 64;
+
+// Evaluated as a stack machine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+enum Op {
+    Constant(u32),
+    Register(Sh2Register),
+    Load(MemorySize),
+    Unary(UnaryOp),
+    Binary(BinaryOp),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message} at position {position}")]
+pub struct ParseError {
+    pub position: usize,
+    pub message: &'static str,
+}
+
+/// A compiled expression over SH-2 registers and memory.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Expression {
+    source: String,
+    ops: Vec<Op>,
+}
+
+impl Expression {
+    /// Parse an expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expression is not syntactically valid.
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let mut parser =
+            Parser { source: source.as_bytes(), position: 0, depth: 0, ops: Vec::new() };
+        parser.parse_binary(0)?;
+
+        parser.skip_whitespace();
+        if parser.position != source.len() {
+            return Err(parser.error("Unexpected character"));
+        }
+
+        Ok(Self { source: source.trim().into(), ops: parser.ops })
+    }
+
+    /// Evaluate the expression using the given register and memory accessors.
+    pub fn evaluate(
+        &self,
+        read_register: impl Fn(Sh2Register) -> u32,
+        mut read_memory: impl FnMut(u32, MemorySize) -> u32,
+    ) -> u32 {
+        let mut stack: Vec<u32> = Vec::with_capacity(self.ops.len());
+
+        // The parser only produces well-formed op lists, so the stack never underflows
+        for &op in &self.ops {
+            let value = match op {
+                Op::Constant(value) => value,
+                Op::Register(register) => read_register(register),
+                Op::Load(size) => {
+                    let address = stack.pop().unwrap_or(0);
+                    read_memory(address, size)
+                }
+                Op::Unary(op) => {
+                    let operand = stack.pop().unwrap_or(0);
+                    op.apply(operand)
+                }
+                Op::Binary(op) => {
+                    let r = stack.pop().unwrap_or(0);
+                    let l = stack.pop().unwrap_or(0);
+                    op.apply(l, r)
+                }
+            };
+            stack.push(value);
+        }
+
+        stack.pop().unwrap_or(0)
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+struct Parser<'a> {
+    source: &'a [u8],
+    position: usize,
+    depth: usize,
+    ops: Vec<Op>,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &'static str) -> ParseError {
+        ParseError { position: self.position, message }
+    }
+
+    fn nested(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<(), ParseError>,
+    ) -> Result<(), ParseError> {
+        if self.depth == MAX_NESTING_DEPTH {
+            return Err(self.error("Expression is nested too deeply"));
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.source.get(self.position).is_some_and(u8::is_ascii_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.source[self.position..].starts_with(token.as_bytes()) {
+            self.position += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str, message: &'static str) -> Result<(), ParseError> {
+        if self.eat(token) { Ok(()) } else { Err(self.error(message)) }
+    }
+
+    fn parse_binary(&mut self, level: usize) -> Result<(), ParseError> {
+        let Some(operators) = BINARY_PRECEDENCE.get(level) else {
+            return self.parse_unary();
+        };
+
+        self.parse_binary(level + 1)?;
+        'outer: loop {
+            for &(token, op) in *operators {
+                // Don't treat the first character of a longer operator as this operator, e.g. `|`
+                // in `||` or `<` in `<<`
+                if self.peek_operator(token) && self.eat(token) {
+                    self.parse_binary(level + 1)?;
+                    self.ops.push(Op::Binary(op));
+                    continue 'outer;
+                }
+            }
+
+            return Ok(());
+        }
+    }
+
+    fn peek_operator(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let rest = &self.source[self.position..];
+        if !rest.starts_with(token.as_bytes()) {
+            return false;
+        }
+
+        let next = rest.get(token.len()).copied();
+        match token {
+            "|" => next != Some(b'|'),
+            "&" => next != Some(b'&'),
+            "<" => !matches!(next, Some(b'<' | b'=')),
+            ">" => !matches!(next, Some(b'>' | b'=')),
+            _ => true,
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<(), ParseError> {
+        let op = if self.eat("-") {
+            UnaryOp::Negate
+        } else if self.eat("~") {
+            UnaryOp::Not
+        } else if self.peek_operator("!") && !self.source[self.position..].starts_with(b"!=") {
+            self.position += 1;
+            UnaryOp::LogicalNot
+        } else {
+            return self.parse_primary();
+        };
+
+        self.nested(Self::parse_unary)?;
+        self.ops.push(Op::Unary(op));
+        Ok(())
+    }
+
+    fn parse_primary(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+
+        if self.eat("(") {
+            self.nested(|parser| parser.parse_binary(0))?;
+            return self.expect(")", "Expected ')'");
+        }
+
+        if self.eat("$") {
+            let start = self.position;
+            let digits = self.take_while(|c| c.is_ascii_hexdigit());
+            let value = parse_u32(&digits, 16)
+                .ok_or(ParseError { position: start, message: "Invalid hex literal" })?;
+            self.ops.push(Op::Constant(value));
+            return Ok(());
+        }
+
+        let start = self.position;
+        let word = self.take_while(|c| c.is_ascii_alphanumeric() || c == b'_');
+        if word.is_empty() {
+            return Err(self.error("Expected an expression"));
+        }
+
+        let lower = word.to_ascii_lowercase();
+        if lower.as_bytes()[0].is_ascii_digit() {
+            let value = match lower.strip_prefix("0x") {
+                Some(hex) => parse_u32(hex, 16),
+                None => parse_u32(&lower, 10),
+            };
+            let value = value.ok_or(ParseError { position: start, message: "Invalid number" })?;
+            self.ops.push(Op::Constant(value));
+            return Ok(());
+        }
+
+        let size = match lower.as_str() {
+            "b" => Some(MemorySize::Byte),
+            "w" => Some(MemorySize::Word),
+            "l" => Some(MemorySize::Longword),
+            _ => None,
+        };
+        if let Some(size) = size {
+            self.expect("[", "Expected '[' after memory size")?;
+            self.nested(|parser| parser.parse_binary(0))?;
+            self.expect("]", "Expected ']'")?;
+            self.ops.push(Op::Load(size));
+            return Ok(());
+        }
+
+        let register = parse_register(&lower)
+            .ok_or(ParseError { position: start, message: "Unknown register" })?;
+        self.ops.push(Op::Register(register));
+
+        Ok(())
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> String {
+        let start = self.position;
+        while self.source.get(self.position).is_some_and(|&c| predicate(c)) {
+            self.position += 1;
+        }
+
+        String::from_utf8_lossy(&self.source[start..self.position]).into_owned()
+    }
+}
+
+fn parse_u32(digits: &str, radix: u32) -> Option<u32> {
+    if digits.is_empty() {
+        return None;
+    }
+
+    u32::from_str_radix(digits, radix).ok()
+}
+
+fn parse_register(name: &str) -> Option<Sh2Register> {
+    let register = match name {
+        "pc" => Sh2Register::Pc,
+        "pr" => Sh2Register::Pr,
+        "gbr" => Sh2Register::Gbr,
+        "vbr" => Sh2Register::Vbr,
+        "mach" => Sh2Register::Mach,
+        "macl" => Sh2Register::Macl,
+        "sr" => Sh2Register::Sr,
+        _ => {
+            let n: u8 = name.strip_prefix('r')?.parse().ok()?;
+            if n >= 16 {
+                return None;
+            }
+            Sh2Register::Gpr(n)
+        }
+    };
+
+    Some(register)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(source: &str) -> u32 {
+        Expression::parse(source).unwrap().evaluate(
+            |register| match register {
+                Sh2Register::Gpr(n) => 0x10 * u32::from(n),
+                Sh2Register::Pc => 0x06000100,
+                _ => 0,
+            },
+            |address, size| match size {
+                MemorySize::Byte => address & 0xFF,
+                MemorySize::Word => address & 0xFFFF,
+                MemorySize::Longword => address,
+            },
+        )
+    }
+
+    #[test]
+    fn literals_and_precedence() {
+        assert_eq!(evaluate("66"), 66);
+        assert_eq!(evaluate("0x42"), 0x42);
+        assert_eq!(evaluate("$42"), 0x42);
+        assert_eq!(evaluate("1 + 2 * 3"), 7);
+        assert_eq!(evaluate("(1 + 2) * 3"), 9);
+        assert_eq!(evaluate("1 << 4 | 1"), 0x11);
+        assert_eq!(evaluate("-1"), u32::MAX);
+        assert_eq!(evaluate("!0 + ~0"), 0);
+        assert_eq!(evaluate("7 / 0"), 0);
+        assert_eq!(evaluate("1 << 40"), 0);
+    }
+
+    #[test]
+    fn comparisons_and_logic() {
+        assert_eq!(evaluate("1 < 2 && 2 <= 2 && 3 > 2 && 3 >= 4"), 0);
+        assert_eq!(evaluate("1 != 2 || 0"), 1);
+        assert_eq!(evaluate("5 & 4 == 4"), 1);
+        assert_eq!(evaluate("!1 != 0"), 0);
+    }
+
+    #[test]
+    fn registers_and_memory() {
+        assert_eq!(evaluate("r2"), 0x20);
+        assert_eq!(evaluate("R15 + PC"), 0x060001F0);
+        assert_eq!(evaluate("r4 == 0x40 && pc >= 0x06000000 && pc < 0x06000200"), 1);
+        assert_eq!(evaluate("b[0x1234]"), 0x34);
+        assert_eq!(evaluate("w[r1 + 0x12340000]"), 0x0010);
+        assert_eq!(evaluate("l[pc + 2]"), 0x06000102);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Expression::parse("").is_err());
+        assert!(Expression::parse("1 +").is_err());
+        assert!(Expression::parse("(1").is_err());
+        assert!(Expression::parse("r16").is_err());
+        assert!(Expression::parse("x").is_err());
+        assert!(Expression::parse("w 4").is_err());
+        assert!(Expression::parse("0x").is_err());
+        assert!(Expression::parse("1 2").is_err());
+        assert_eq!(
+            Expression::parse("r0 == 0x42 )"),
+            Err(ParseError { position: 11, message: "Unexpected character" })
+        );
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(evaluate(&nested(MAX_NESTING_DEPTH)), 1);
+        assert!(Expression::parse(&nested(MAX_NESTING_DEPTH + 1)).is_err());
+
+        assert!(Expression::parse(&"-".repeat(100_000)).is_err());
+        assert!(
+            Expression::parse(&format!("{}0{}", "b[".repeat(100_000), "]".repeat(100_000)))
+                .is_err()
+        );
+    }
+}
@@ -4,8 +4,10 @@
 //! instruction plus memory access delays, using the `BusInterface` implementation to record memory
 //! accesses.
 
+mod bsc;
 pub mod bus;
 mod cache;
+pub mod debug;
 mod disassemble;
 mod divu;
 mod dma;
@@ -15,15 +17,17 @@ mod registers;
 mod sci;
 mod wdt;
 
+use crate::bsc::BusStateController;
 use crate::bus::BusInterface;
 use crate::cache::CpuCache;
+use crate::debug::DebugState;
 use crate::divu::DivisionUnit;
 use crate::dma::DmaController;
 use crate::frt::FreeRunTimer;
 use crate::registers::{Sh2Registers, Sh7604Registers};
 use crate::sci::SerialInterface;
 use crate::wdt::WatchdogTimer;
-use bincode::{Decode, Encode};
+use jgenesis_proc_macros::SaveState;
 use std::env;
 
 const RESET_PC_VECTOR: u32 = 0x00000000;
@@ -40,17 +44,21 @@ const SP: usize = 15;
 // Only A0-28 are visible externally; A29-31 are handled internally
 const EXTERNAL_ADDRESS_MASK: u32 = 0x1FFFFFFF;
 
-#[derive(Debug, Clone, Encode, Decode)]
+#[derive(Debug, Clone, SaveState)]
 pub struct Sh2 {
     registers: Sh2Registers,
     cache: CpuCache,
     sh7604: Sh7604Registers,
+    bsc: BusStateController,
     dmac: DmaController,
     free_run_timer: FreeRunTimer,
     watchdog_timer: WatchdogTimer,
     divu: DivisionUnit,
     serial: SerialInterface,
     reset_pending: bool,
+    // Breakpoints belong to the attached debugger, not to the emulated state
+    #[savestate(skip)]
+    debug: DebugState,
     name: String,
     trace_log_enabled: bool,
 }
@@ -68,23 +76,33 @@ impl Sh2 {
             registers: Sh2Registers::default(),
             cache: CpuCache::new(),
             sh7604: Sh7604Registers::new(),
+            bsc: BusStateController::new(false),
             dmac: DmaController::new(),
             free_run_timer: FreeRunTimer::new(),
             watchdog_timer: WatchdogTimer::new(),
             divu: DivisionUnit::new(),
             serial: SerialInterface::new(name.clone()),
             reset_pending: false,
+            debug: DebugState::default(),
             name,
             trace_log_enabled,
         }
     }
 
+    /// Create an SH-2 with the MD5 pin set for slave mode. Software can detect this through BCR1, and
+    /// a slave CPU never performs SDRAM refresh.
+    #[must_use]
+    pub fn new_slave(name: String) -> Self {
+        Self { bsc: BusStateController::new(true), ..Self::new(name) }
+    }
+
     /// Execute up to `ticks` instructions.
     ///
-    /// Will not execute any instructions if a reset is performed or an interrupt is handled.
+    /// Will not execute any instructions if a reset is performed or an interrupt is handled, or if
+    /// the CPU is stopped in the debugger.
     #[inline]
     pub fn execute<B: BusInterface>(&mut self, mut ticks: u64, bus: &mut B) {
-        if ticks == 0 {
+        if ticks == 0 || self.debug.stopped() {
             return;
         }
 
@@ -156,6 +174,10 @@ impl Sh2 {
         }
 
         for _ in 0..ticks {
+            if self.debug.active() && self.debug_should_break(&*bus) {
+                return;
+            }
+
             self.execute_single_instruction(bus);
         }
     }
@@ -187,15 +209,32 @@ impl Sh2 {
         instructions::decode(opcode)(self, opcode, bus);
     }
 
-    /// Advance internal peripherals by `system_cycles`, specifically the watchdog timer (WDT) and
-    /// the serial interface (SCI). Also updates internal interrupt state.
+    /// Advance internal peripherals by `system_cycles`, specifically the watchdog timer (WDT), the
+    /// serial interface (SCI), and the SDRAM refresh timer. Also updates internal interrupt state.
     #[inline]
     pub fn tick_peripherals<B: BusInterface>(&mut self, system_cycles: u64, bus: &mut B) {
         self.watchdog_timer.tick(system_cycles);
+        self.bsc.tick(system_cycles);
         self.serial.process(system_cycles, bus);
         self.update_internal_interrupt_level();
     }
 
+    /// SDRAM CAS latency in cycles, as configured by the last SDRAM mode register write.
+    ///
+    /// Defaults to 2 before software configures SDRAM.
+    #[must_use]
+    pub fn sdram_cas_latency(&self) -> u8 {
+        self.bsc.cas_latency()
+    }
+
+    /// Number of cycles that SDRAM has been busy with CAS-before-RAS refresh since the last call.
+    ///
+    /// Always 0 for a CPU created with [`Self::new_slave`] because only the bus master performs
+    /// refresh.
+    pub fn take_sdram_refresh_cycles(&mut self) -> u64 {
+        self.bsc.take_pending_refresh_cycles()
+    }
+
     fn read_byte<B: BusInterface + ?Sized>(&mut self, address: u32, bus: &mut B) -> u8 {
         match address >> 29 {
             0 => self.cached_read_byte(address, bus),
@@ -445,8 +445,7 @@ impl Sh2 {
             0xFFFFFF80..=0xFFFFFF9F | 0xFFFFFFB0 => self.dmac.read_register(address),
             0xFFFFFFA0 => self.sh7604.interrupts.dma0_vector.into(),
             0xFFFFFFA8 => self.sh7604.interrupts.dma1_vector.into(),
-            // Bus control register; not emulated, 32X games only ever write this value to it before reading
-            0xFFFFFFE0 => 0xA55A0001,
+            0xFFFFFFE0..=0xFFFFFFFF => self.bsc.read_register(address),
             _ => {
                 log::warn!(
                     "[{}] Unexpected internal register longword read: {address:08X}",
@@ -501,8 +500,7 @@ impl Sh2 {
         log::trace!("[{}] Internal register word write: {address:08X} {value:04X}", self.name);
 
         match address {
-            // Writing to this address sets SDRAM 16-bit CAS latency; ignore
-            0xFFFF8446 => {}
+            0xFFFF8000..=0xFFFFBFFF => self.bsc.write_sdram_mode(address),
             0xFFFFFE60 => self.sh7604.interrupts.write_iprb(value),
             0xFFFFFE62 => self.sh7604.interrupts.write_vcra(value),
             0xFFFFFE64 => self.sh7604.interrupts.write_vcrb(value),
@@ -538,7 +536,7 @@ impl Sh2 {
             0xFFFFFF80..=0xFFFFFF9F | 0xFFFFFFB0 => self.dmac.write_register(address, value),
             0xFFFFFFA0 => self.sh7604.interrupts.write_vcrdma0(value),
             0xFFFFFFA8 => self.sh7604.interrupts.write_vcrdma1(value),
-            0xFFFFFFE0..=0xFFFFFFFF => log_bus_control_write(address, value),
+            0xFFFFFFE0..=0xFFFFFFFF => self.bsc.write_register(address, value),
             _ => log::warn!(
                 "[{}] Unexpected internal register longword write: {address:08X} {value:08X}",
                 self.name
@@ -558,104 +556,3 @@ fn log_standby_control_write(value: u8, name: &str) {
     log::trace!("  FRT clock halted: {}", value.bit(1));
     log::trace!("  SCI clock halted: {}", value.bit(0));
 }
-
-fn log_bus_control_write(address: u32, value: u32) {
-    // TODO actually emulate these registers?
-    match address {
-        0xFFFFFFE0 => {
-            log::trace!("BCR1 write: {value:08X}");
-            log::trace!("  Master mode: {}", !value.bit(15));
-            log::trace!("  Big endian mode: {}", !value.bit(12));
-            log::trace!("  Area 0 burst ROM enabled: {}", value.bit(11));
-            log::trace!("  Partial-share master mode: {}", value.bit(10));
-            log::trace!(
-                "  Long wait specification for areas 2/3: {} waits",
-                ((value >> 8) & 3) + 3
-            );
-            log::trace!("  Long wait specification for area 1: {} waits", ((value >> 6) & 3) + 3);
-            log::trace!("  Long wait specification for area 0: {} waits", ((value >> 4) & 3) + 3);
-            log::trace!("  DRAM specification bits: {}", value & 7);
-        }
-        0xFFFFFFE4 => {
-            log::trace!("BCR2 write: {value:08X}");
-            log::trace!("  Size specification for area 3: {}", bus_area_size(value >> 6));
-            log::trace!("  Size specification for area 2: {}", bus_area_size(value >> 4));
-            log::trace!("  Size specification for area 1: {}", bus_area_size(value >> 2));
-        }
-        0xFFFFFFE8 => {
-            log::trace!("WCR write: {value:08X}");
-            log::trace!("  Idles between cycles for area 3: {}", idle_cycles(value >> 14));
-            log::trace!("  Idles between cycles for area 2: {}", idle_cycles(value >> 12));
-            log::trace!("  Idles between cycles for area 1: {}", idle_cycles(value >> 10));
-            log::trace!("  Idles between cycles for area 0: {}", idle_cycles(value >> 8));
-            log::trace!("  Wait control for area 3: {}", (value >> 6) & 3);
-            log::trace!("  Wait control for area 2: {}", (value >> 4) & 3);
-            log::trace!("  Wait control for area 1: {}", (value >> 2) & 3);
-            log::trace!("  Wait control for area 0: {}", value & 3);
-        }
-        0xFFFFFFEC => {
-            log::trace!("MCR write: {value:08X}");
-            log::trace!("  RAS precharge time: {}", if value.bit(15) { 2 } else { 1 });
-            log::trace!("  RAS-CAS delay: {}", if value.bit(14) { 2 } else { 1 });
-            log::trace!("  Write precharge delay: {}", if value.bit(13) { 2 } else { 1 });
-            log::trace!("  CAS-before-RAS refresh RAS assert time: {}", match (value >> 11) & 3 {
-                0 => "2 cycles",
-                1 => "3 cycles",
-                2 => "4 cycles",
-                3 => "(Reserved)",
-                _ => unreachable!(),
-            });
-            log::trace!("  Burst enabled: {}", value.bit(10));
-            log::trace!("  RAS down mode enabled: {}", value.bit(9));
-            log::trace!(
-                "  Address multiplexing bits: {}",
-                ((value >> 5) & 0x4) | ((value >> 4) & 0x3)
-            );
-            log::trace!(
-                "  DRAM memory data size: {}",
-                if value.bit(6) { "Longword" } else { "Word" }
-            );
-            log::trace!("  DRAM refresh enabled: {}", value.bit(3));
-            log::trace!("  Self-refresh enabled: {}", value.bit(2));
-        }
-        0xFFFFFFF0 => {
-            log::trace!("RTCSR write: {value:08X}");
-            log::trace!("  Compare match flag: {}", value.bit(7));
-            log::trace!("  Compare match interrupt enabled: {}", value.bit(6));
-            log::trace!("  Clock select bits: {}", (value >> 3) & 7);
-
-            if value.bit(6) {
-                log::error!("SH-2 FRT compare match interrupt was enabled; not emulated");
-            }
-        }
-        0xFFFFFFF4 => {
-            log::trace!("RTCNT write: {value:08X}");
-            log::trace!("  Refresh timer counter: 0x{:02X}", value & 0xFF);
-        }
-        0xFFFFFFF8 => {
-            log::trace!("RTCOR write: {value:08X}");
-            log::trace!("  Refresh time constant for compare: 0x{:02X}", value & 0xFF);
-        }
-        _ => log::warn!("Bus control register write {address:08X} {value:08X}"),
-    }
-}
-
-fn bus_area_size(value: u32) -> &'static str {
-    match value & 3 {
-        0 => "(Reserved)",
-        1 => "Byte",
-        2 => "Word",
-        3 => "Longword",
-        _ => unreachable!("value & 3 is always <= 3"),
-    }
-}
-
-fn idle_cycles(value: u32) -> &'static str {
-    match value & 3 {
-        0 => "0 cycles",
-        1 => "1 cycle",
-        2 => "2 cycles",
-        3 => "(Reserved)",
-        _ => unreachable!("value & 3 is always <= 3"),
-    }
-}
@@ -304,14 +304,18 @@ pub struct Z80 {
     registers: Registers,
     stalled: bool,
     t_cycles_wait: u32,
+    reset_cycles: u32,
 }
 
 impl Z80 {
     const MINIMUM_T_CYCLES: u32 = 3;
 
+    // RESET must be held for at least this many cycles to reset the Z80
+    const RESET_T_CYCLES: u32 = 3;
+
     #[must_use]
     pub fn new() -> Self {
-        Self { registers: Registers::new(), stalled: false, t_cycles_wait: 0 }
+        Self { registers: Registers::new(), stalled: false, t_cycles_wait: 0, reset_cycles: 0 }
     }
 
     #[must_use]
@@ -341,17 +345,7 @@ impl Z80 {
     pub fn execute_instruction<B: BusInterface>(&mut self, bus: &mut B) -> u32 {
         if bus.reset() {
             // RESET is asserted; reset internal state
-            self.registers.i = 0;
-            self.registers.r = 0;
-            self.registers.pc = 0;
-            self.registers.iff1 = false;
-            self.registers.iff2 = false;
-            self.registers.interrupt_mode = InterruptMode::Mode0;
-            self.registers.interrupt_delay = false;
-            self.registers.last_nmi = InterruptLine::High;
-            self.registers.halted = false;
-            self.stalled = false;
-
+            self.reset_internal_state();
             return Self::MINIMUM_T_CYCLES;
         }
 
@@ -371,8 +365,22 @@ impl Z80 {
     /// When run using this method, the Z80 will immediately execute an instruction in full and
     /// internally record how many cycles the instruction took; the next instruction will be
     /// executed after calling `tick()` that many times, and so on.
+    ///
+    /// Unlike the other signals, RESET is checked on every tick because it interrupts whatever
+    /// instruction is in progress. The Z80 does not advance while RESET is asserted, and it only
+    /// resets once RESET has been held for at least 3 cycles.
     #[inline]
     pub fn tick<B: BusInterface>(&mut self, bus: &mut B) {
+        if bus.reset() {
+            self.reset_cycles += 1;
+            if self.reset_cycles == Self::RESET_T_CYCLES {
+                self.reset_internal_state();
+                self.t_cycles_wait = 0;
+            }
+            return;
+        }
+        self.reset_cycles = 0;
+
         if self.t_cycles_wait > 0 {
             self.t_cycles_wait -= 1;
         } else {
@@ -380,6 +388,19 @@ impl Z80 {
             self.t_cycles_wait = self.execute_instruction(bus) - 1;
         }
     }
+
+    fn reset_internal_state(&mut self) {
+        self.registers.i = 0;
+        self.registers.r = 0;
+        self.registers.pc = 0;
+        self.registers.iff1 = false;
+        self.registers.iff2 = false;
+        self.registers.interrupt_mode = InterruptMode::Mode0;
+        self.registers.interrupt_delay = false;
+        self.registers.last_nmi = InterruptLine::High;
+        self.registers.halted = false;
+        self.stalled = false;
+    }
 }
 
 impl Default for Z80 {
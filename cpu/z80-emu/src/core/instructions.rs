@@ -204,7 +204,6 @@ impl<'registers, 'bus, B: BusInterface> InstructionExecutor<'registers, 'bus, B>
                 self.registers.iff1 = false;
                 self.registers.iff2 = false;
 
-                #[allow(unreachable_code)]
                 match self.registers.interrupt_mode {
                     // Modes 0 and 1 don't actually work the same way in actual hardware, but for
                     // the purposes of emulating these consoles they do.
@@ -219,8 +218,15 @@ impl<'registers, 'bus, B: BusInterface> InstructionExecutor<'registers, 'bus, B>
 
                         13
                     }
+                    // Mode 2 reads the low byte of a vector table address from the data bus and
+                    // combines it with I as the high byte, then jumps to the address stored at
+                    // that location in the table
                     InterruptMode::Mode2 => {
-                        todo!("interrupt mode 2");
+                        let vector = self.bus.interrupt_vector();
+                        let table_address = u16::from_le_bytes([vector, self.registers.i]);
+
+                        self.push_stack(self.registers.pc);
+                        self.registers.pc = self.read_memory_u16(table_address);
 
                         19
                     }
@@ -294,15 +300,15 @@ impl<'registers, 'bus, B: BusInterface> InstructionExecutor<'registers, 'bus, B>
             0x43 | 0x53 | 0x63 | 0x73 => self.ld_direct_dd(opcode2),
             0x44 => self.neg(),
             0x45 => self.retn(),
-            0x46 => self.im(InterruptMode::Mode0),
+            0x46 | 0x4E | 0x66 | 0x6E => self.im(InterruptMode::Mode0),
             0x47 => self.ld_ir_a(Register8::I),
             0x4A | 0x5A | 0x6A | 0x7A => self.adc_hl_ss(opcode2),
             0x4B | 0x5B | 0x6B | 0x7B => self.ld_dd_direct(opcode2),
             0x4D => self.reti(),
             0x4F => self.ld_ir_a(Register8::R),
-            0x56 => self.im(InterruptMode::Mode1),
+            0x56 | 0x76 => self.im(InterruptMode::Mode1),
             0x57 => self.ld_a_ir(Register8::I),
-            0x5E => self.im(InterruptMode::Mode2),
+            0x5E | 0x7E => self.im(InterruptMode::Mode2),
             0x5F => self.ld_a_ir(Register8::R),
             0x67 => self.rrd(),
             0x6F => self.rld(),
@@ -465,3 +471,211 @@ impl<'registers, 'bus, B: BusInterface> InstructionExecutor<'registers, 'bus, B>
 pub fn execute<B: BusInterface>(registers: &mut Registers, bus: &mut B) -> u32 {
     InstructionExecutor::new(registers, bus).execute()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Z80;
+    use crate::traits::InMemoryBus;
+
+    const NOP: u8 = 0x00;
+    const HALT: u8 = 0x76;
+    const EI: u8 = 0xFB;
+
+    fn new_registers(interrupt_mode: InterruptMode) -> Registers {
+        let mut registers = Registers::new();
+        registers.pc = 0x1000;
+        registers.sp = 0xE000;
+        registers.iff1 = true;
+        registers.iff2 = true;
+        registers.interrupt_mode = interrupt_mode;
+        registers
+    }
+
+    fn stack_top(registers: &Registers, bus: &InMemoryBus) -> u16 {
+        let sp = registers.sp as usize;
+        u16::from_le_bytes([bus.memory[sp], bus.memory[sp + 1]])
+    }
+
+    #[test]
+    fn mode_1_interrupt() {
+        let mut registers = new_registers(InterruptMode::Mode1);
+        let mut bus = InMemoryBus::new();
+        bus.int = InterruptLine::Low;
+
+        assert_eq!(execute(&mut registers, &mut bus), 13);
+        assert_eq!(registers.pc, 0x0038);
+        assert_eq!(stack_top(&registers, &bus), 0x1000);
+        assert!(!registers.iff1 && !registers.iff2);
+    }
+
+    #[test]
+    fn mode_2_interrupt() {
+        for vector in [0xFF, 0x40] {
+            let mut registers = new_registers(InterruptMode::Mode2);
+            registers.i = 0x12;
+
+            let mut bus = InMemoryBus::new();
+            bus.int = InterruptLine::Low;
+            bus.interrupt_vector = vector;
+
+            let table_address = u16::from_le_bytes([vector, 0x12]) as usize;
+            bus.memory[table_address] = 0x56;
+            bus.memory[table_address + 1] = 0x34;
+
+            assert_eq!(execute(&mut registers, &mut bus), 19, "vector {vector:02X}");
+            assert_eq!(registers.pc, 0x3456, "vector {vector:02X}");
+            assert_eq!(stack_top(&registers, &bus), 0x1000, "vector {vector:02X}");
+        }
+    }
+
+    #[test]
+    fn undocumented_im_opcodes() {
+        for (opcode, mode) in [
+            (0x4E, InterruptMode::Mode0),
+            (0x66, InterruptMode::Mode0),
+            (0x6E, InterruptMode::Mode0),
+            (0x76, InterruptMode::Mode1),
+            (0x7E, InterruptMode::Mode2),
+        ] {
+            let initial_mode = if mode == InterruptMode::Mode0 {
+                InterruptMode::Mode2
+            } else {
+                InterruptMode::Mode0
+            };
+            let mut registers = new_registers(initial_mode);
+
+            let mut bus = InMemoryBus::new();
+            bus.memory[0x1000] = 0xED;
+            bus.memory[0x1001] = opcode;
+
+            assert_eq!(execute(&mut registers, &mut bus), 8, "ED {opcode:02X}");
+            assert_eq!(registers.interrupt_mode, mode, "ED {opcode:02X}");
+        }
+    }
+
+    #[test]
+    fn interrupts_delayed_one_instruction_after_ei() {
+        let mut registers = new_registers(InterruptMode::Mode1);
+        registers.iff1 = false;
+        registers.iff2 = false;
+
+        let mut bus = InMemoryBus::new();
+        bus.memory[0x1000] = EI;
+        bus.memory[0x1001] = NOP;
+        bus.int = InterruptLine::Low;
+
+        execute(&mut registers, &mut bus);
+        assert_eq!(registers.pc, 0x1001);
+
+        // The instruction after EI always executes before the interrupt is accepted
+        execute(&mut registers, &mut bus);
+        assert_eq!(registers.pc, 0x1002);
+
+        assert_eq!(execute(&mut registers, &mut bus), 13);
+        assert_eq!(registers.pc, 0x0038);
+        assert_eq!(stack_top(&registers, &bus), 0x1002);
+    }
+
+    #[test]
+    fn interrupt_during_halt_returns_past_halt() {
+        let mut registers = new_registers(InterruptMode::Mode1);
+        let mut bus = InMemoryBus::new();
+        bus.memory[0x1000] = HALT;
+
+        execute(&mut registers, &mut bus);
+        for _ in 0..5 {
+            assert_eq!(execute(&mut registers, &mut bus), 4);
+            assert_eq!(registers.pc, 0x1001);
+        }
+
+        bus.int = InterruptLine::Low;
+        execute(&mut registers, &mut bus);
+        assert_eq!(registers.pc, 0x0038);
+        assert_eq!(stack_top(&registers, &bus), 0x1001);
+        assert!(!registers.halted);
+    }
+
+    #[test]
+    fn interrupt_sampled_at_end_of_instruction() {
+        let mut z80 = Z80::new();
+        z80.registers = new_registers(InterruptMode::Mode1);
+
+        // LD A, ($2000) takes 13 T-cycles
+        let mut bus = InMemoryBus::new();
+        bus.memory[0x1000] = 0x3A;
+        bus.memory[0x1001] = 0x00;
+        bus.memory[0x1002] = 0x20;
+
+        z80.tick(&mut bus);
+        assert_eq!(z80.pc(), 0x1003);
+
+        // INT asserted partway through the instruction should not be accepted until the
+        // instruction finishes
+        bus.int = InterruptLine::Low;
+        for _ in 1..13 {
+            z80.tick(&mut bus);
+            assert_eq!(z80.pc(), 0x1003);
+        }
+
+        z80.tick(&mut bus);
+        assert_eq!(z80.pc(), 0x0038);
+        assert_eq!(stack_top(&z80.registers, &bus), 0x1003);
+    }
+
+    #[test]
+    fn reset_interrupts_instruction_in_progress() {
+        let mut z80 = Z80::new();
+        z80.registers = new_registers(InterruptMode::Mode1);
+
+        // LD A, ($2000) takes 13 T-cycles
+        let mut bus = InMemoryBus::new();
+        bus.memory[0x1000] = 0x3A;
+
+        z80.tick(&mut bus);
+        assert_eq!(z80.pc(), 0x1003);
+
+        bus.reset = true;
+        for _ in 0..3 {
+            z80.tick(&mut bus);
+        }
+        assert_eq!(z80.pc(), 0x0000);
+        assert_eq!(z80.registers.interrupt_mode, InterruptMode::Mode0);
+        assert!(!z80.registers.iff1);
+
+        // Execution resumes from $0000 on the first tick after RESET is released
+        bus.reset = false;
+        bus.memory[0x0000] = NOP;
+        z80.tick(&mut bus);
+        assert_eq!(z80.pc(), 0x0001);
+    }
+
+    #[test]
+    fn short_reset_pulse_only_pauses() {
+        let mut z80 = Z80::new();
+        z80.registers = new_registers(InterruptMode::Mode1);
+
+        let mut bus = InMemoryBus::new();
+        bus.memory[0x1000] = NOP;
+        bus.memory[0x1001] = NOP;
+
+        z80.tick(&mut bus);
+        assert_eq!(z80.pc(), 0x1001);
+
+        bus.reset = true;
+        for _ in 0..2 {
+            z80.tick(&mut bus);
+        }
+        bus.reset = false;
+        assert_eq!(z80.pc(), 0x1001);
+
+        // The remaining 3 cycles of the first NOP, then the second NOP starts
+        for _ in 0..3 {
+            z80.tick(&mut bus);
+        }
+        assert_eq!(z80.pc(), 0x1001);
+        z80.tick(&mut bus);
+        assert_eq!(z80.pc(), 0x1002);
+        assert_eq!(z80.registers.interrupt_mode, InterruptMode::Mode1);
+    }
+}
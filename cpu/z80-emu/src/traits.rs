@@ -29,6 +29,16 @@ pub trait BusInterface {
 
     /// Poll the RESET line; setting this resets and halts the Z80
     fn reset(&self) -> bool;
+
+    /// Read the byte that the interrupting device places on the data bus while the Z80 is
+    /// acknowledging an INT interrupt. In interrupt mode 2, this is the low byte of the address
+    /// in the vector table.
+    ///
+    /// Nothing drives the data bus during the acknowledge cycle on the Master System, Game Gear,
+    /// or Genesis, so this defaults to $FF (open bus).
+    fn interrupt_vector(&mut self) -> u8 {
+        0xFF
+    }
 }
 
 #[cfg(test)]
@@ -39,6 +49,7 @@ pub(crate) struct InMemoryBus {
     pub(crate) nmi: InterruptLine,
     pub(crate) int: InterruptLine,
     pub(crate) reset: bool,
+    pub(crate) interrupt_vector: u8,
 }
 
 #[cfg(test)]
@@ -50,6 +61,7 @@ impl InMemoryBus {
             nmi: InterruptLine::High,
             int: InterruptLine::High,
             reset: false,
+            interrupt_vector: 0xFF,
         }
     }
 }
@@ -87,4 +99,8 @@ impl BusInterface for InMemoryBus {
     fn reset(&self) -> bool {
         self.reset
     }
+
+    fn interrupt_vector(&mut self) -> u8 {
+        self.interrupt_vector
+    }
 }
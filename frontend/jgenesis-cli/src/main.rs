@@ -6,30 +6,37 @@ use gb_core::api::{GbAspectRatio, GbPalette, GbcColorCorrection};
 use genesis_core::{
     GenesisAspectRatio, GenesisControllerType, GenesisLowPassFilter, GenesisRegion,
 };
-use jgenesis_common::frontend::{EmulatorTrait, TimingMode};
+use jgenesis_common::clockratio::ClockRatio;
+use jgenesis_common::frontend::TimingMode;
 use jgenesis_native_config::AppConfig;
 use jgenesis_native_config::common::ConfigSavePath;
-use jgenesis_native_driver::config::input::{NesControllerType, SnesControllerType};
-use jgenesis_native_driver::config::{FullscreenMode, HideMouseCursor};
-use jgenesis_native_driver::{NativeEmulator, NativeTickEffect, extensions};
+use jgenesis_native_driver::config::input::{
+    NesControllerType, SmsGgControllerType, SnesControllerType,
+};
+use jgenesis_native_driver::config::{
+    FullscreenMode, HideMouseCursor, InputViewerSkin, LinkCableMode, SyncMode, SystemConfig,
+};
+use jgenesis_native_driver::{EmulatorCore, NativeTickEffect, extensions};
 use jgenesis_proc_macros::{CustomValueEnum, EnumAll, EnumDisplay};
 use jgenesis_renderer::config::{
-    FilterMode, PreprocessShader, PrescaleFactor, Scanlines, VSyncMode, WgpuBackend,
+    FilterMode, FrameInsertion, OverlayPosition, PreprocessShader, PrescaleFactor,
+    RefreshMultiplier, Scanlines, VSyncMode, WgpuBackend,
 };
 use nes_core::api::NesAspectRatio;
 use s32x_core::api::S32XVideoOut;
 use segacd_core::api::{PcmInterpolation, PcmLowPassFilter};
 use smsgg_core::psg::Sn76489Version;
-use smsgg_core::{GgAspectRatio, SmsAspectRatio, SmsModel, SmsRegion};
+use smsgg_core::{GgAspectRatio, SmsAspectRatio, SmsGgHardware, SmsModel, SmsRegion};
 use snes_core::api::{AudioInterpolationMode, SnesAspectRatio};
 use std::fmt::Debug;
 use std::fs;
-use std::num::{NonZeroU16, NonZeroU32, NonZeroU64};
+use std::num::{NonZeroU16, NonZeroU64};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumAll, EnumDisplay, CustomValueEnum)]
 enum Hardware {
     MasterSystem,
+    Sg1000,
     Genesis,
     SegaCd,
     Sega32X,
@@ -67,6 +74,28 @@ struct Args {
     #[arg(long, value_name = "SLOT")]
     load_save_state: Option<usize>,
 
+    /// Run headless for the specified number of frames as fast as possible and print timing
+    /// statistics instead of opening a window. Build with the 'instrumentation' feature to include
+    /// a per-subsystem breakdown
+    #[arg(long, value_name = "FRAMES")]
+    bench: Option<u64>,
+
+    /// Run headless for the specified number of frames with no inputs and print hashes of the
+    /// final frame and of all audio output instead of opening a window. Used by
+    /// scripts/golden-test.py to detect rendering and audio regressions
+    #[arg(long, value_name = "FRAMES", conflicts_with = "bench")]
+    hash_frames: Option<u64>,
+
+    /// Plug in a link cable (Genesis controller port 2 or Game Gear EXT port) and wait for another
+    /// instance to connect to it on the specified localhost port
+    #[arg(long, value_name = "PORT")]
+    link_listen: Option<u16>,
+
+    /// Plug in a link cable (Genesis controller port 2 or Game Gear EXT port) and connect it to
+    /// another instance listening at the specified address (e.g. 127.0.0.1:5500)
+    #[arg(long, value_name = "ADDRESS", conflicts_with = "link_listen")]
+    link_connect: Option<String>,
+
     /// Force timing mode
     #[arg(long)]
     forced_timing_mode: Option<TimingMode>,
@@ -79,6 +108,44 @@ struct Args {
     #[arg(long)]
     hide_mouse_cursor: Option<HideMouseCursor>,
 
+    /// Accept gamepad input while the emulator window is not focused
+    #[arg(long)]
+    background_gamepad_input: Option<bool>,
+
+    /// Replay input events at the point in the frame when they occurred instead of only between
+    /// frames; improves light gun precision
+    #[arg(long)]
+    subframe_input: Option<bool>,
+
+    /// Automatically pause when the emulator window loses focus, and resume when it regains focus
+    #[arg(long)]
+    pause_when_unfocused: Option<bool>,
+
+    /// Automatically pause when the player 1 or player 2 controller disconnects
+    #[arg(long)]
+    pause_on_controller_disconnect: Option<bool>,
+
+    /// Display current controller button states on screen
+    #[arg(long)]
+    input_viewer_enabled: Option<bool>,
+
+    /// Window corner to display the input viewer in
+    #[arg(long)]
+    input_viewer_position: Option<OverlayPosition>,
+
+    /// Input viewer display style
+    #[arg(long)]
+    input_viewer_skin: Option<InputViewerSkin>,
+
+    /// Purist mode: disable all enhancements, overclocks, blending hacks, and debug layer/channel
+    /// toggles regardless of their individual settings
+    #[arg(long)]
+    purist_mode: Option<bool>,
+
+    /// Show the current system and game in your Discord status
+    #[arg(long, hide = !jgenesis_native_driver::discord_presence_available())]
+    discord_presence_enabled: Option<bool>,
+
     /// Save file path
     #[arg(long)]
     save_path: Option<ConfigSavePath>,
@@ -115,6 +182,10 @@ struct Args {
     #[arg(long, help_heading = SMSGG_OPTIONS_HEADING)]
     sms_region: Option<SmsRegion>,
 
+    /// Master System P1 controller type
+    #[arg(long, help_heading = SMSGG_OPTIONS_HEADING)]
+    sms_p1_controller_type: Option<SmsGgControllerType>,
+
     /// Crop SMS top and bottom border; almost all games display only the background color in this area
     #[arg(long, help_heading = SMSGG_OPTIONS_HEADING)]
     sms_crop_vertical_border: Option<bool>,
@@ -127,19 +198,35 @@ struct Args {
     #[arg(long, help_heading = SMSGG_OPTIONS_HEADING)]
     gg_use_sms_resolution: Option<bool>,
 
+    /// (Game Gear) Blend each frame with the previous frame to simulate LCD response time
+    #[arg(long, help_heading = SMSGG_OPTIONS_HEADING)]
+    gg_frame_blending: Option<bool>,
+
+    /// (Game Gear) Percentage of the previous frame's color to retain when frame blending (0-90)
+    #[arg(long, help_heading = SMSGG_OPTIONS_HEADING)]
+    gg_lcd_persistence: Option<u8>,
+
+    /// (SMS) Simulate NTSC composite video artifacts such as color bleed and dither blending
+    #[arg(long, help_heading = SMSGG_OPTIONS_HEADING)]
+    sms_ntsc_filter: Option<bool>,
+
     /// Enable SMS FM sound unit
     #[arg(long, help_heading = SMSGG_OPTIONS_HEADING)]
     sms_fm_unit_enabled: Option<bool>,
 
     /// Optionally decrease the Z80's clock divider (1-15, with 15 being actual hardware speed).
-    /// Lower divider = higher CPU clock speed
+    /// Lower divider = higher CPU clock speed. Fractional dividers such as 12.5 are allowed
     #[arg(long, help_heading = SMSGG_OPTIONS_HEADING)]
-    smsgg_z80_divider: Option<NonZeroU32>,
+    smsgg_z80_divider: Option<ClockRatio>,
 
     /// Emulate the VDP's non-linear DAC, which tends to brighten darker colors and darken brighter colors
     #[arg(long, help_heading = GENESIS_OPTIONS_HEADING)]
     emulate_non_linear_vdp_dac: Option<bool>,
 
+    /// Emulate 128KB of VRAM instead of 64KB (only used by software that enables the VDP's 128KB mode)
+    #[arg(long, help_heading = GENESIS_OPTIONS_HEADING)]
+    genesis_expanded_vram: Option<bool>,
+
     /// Deinterlace if a game enables an interlacing screen mode
     #[arg(long, help_heading = GENESIS_OPTIONS_HEADING)]
     genesis_deinterlace: Option<bool>,
@@ -157,6 +244,10 @@ struct Args {
     #[arg(long, help_heading = GENESIS_OPTIONS_HEADING)]
     genesis_render_horizontal_border: Option<bool>,
 
+    /// Simulate NTSC composite video artifacts, which blends dithered graphics into transparency
+    #[arg(long, help_heading = GENESIS_OPTIONS_HEADING)]
+    genesis_ntsc_filter: Option<bool>,
+
     /// Enable YM2612 channel output quantization
     #[arg(long, help_heading = GENESIS_OPTIONS_HEADING)]
     quantize_ym2612_output: Option<bool>,
@@ -209,6 +300,10 @@ struct Args {
     #[arg(long, help_heading = SCD_OPTIONS_HEADING)]
     enable_ram_cartridge: Option<bool>,
 
+    /// Write-protect the Sega CD's internal backup RAM
+    #[arg(long, help_heading = SCD_OPTIONS_HEADING)]
+    scd_backup_ram_write_protect: Option<bool>,
+
     /// Set the CD-ROM drive speed when reading data tracks (1 = native speed)
     #[arg(long, help_heading = SCD_OPTIONS_HEADING)]
     scd_drive_speed: Option<NonZeroU16>,
@@ -226,6 +321,22 @@ struct Args {
     #[arg(long, help_heading = SCD_OPTIONS_HEADING)]
     scd_load_disc_into_ram: Option<bool>,
 
+    /// Cache recently read CD-ROM sectors in RAM when not loading the entire image into RAM
+    #[arg(long, help_heading = SCD_OPTIONS_HEADING)]
+    scd_disc_cache: Option<bool>,
+
+    /// CD-ROM sector cache size in megabytes
+    #[arg(long, help_heading = SCD_OPTIONS_HEADING)]
+    scd_disc_cache_size_mb: Option<u16>,
+
+    /// Number of CD-ROM sectors to read ahead after every sector cache miss
+    #[arg(long, help_heading = SCD_OPTIONS_HEADING)]
+    scd_disc_prefetch_sectors: Option<u16>,
+
+    /// Fast-forward through the BIOS boot animation and license screen
+    #[arg(long, help_heading = SCD_OPTIONS_HEADING)]
+    scd_fast_boot: Option<bool>,
+
     /// PCM chip low-pass filter setting
     #[arg(long, help_heading = SCD_OPTIONS_HEADING)]
     scd_pcm_low_pass: Option<PcmLowPassFilter>,
@@ -258,6 +369,10 @@ struct Args {
     #[arg(long, help_heading = S32X_OPTIONS_HEADING)]
     s32x_pwm_enabled: Option<bool>,
 
+    /// Listen for GDB remote debugging connections to the 32X SH-2s on the specified localhost port
+    #[arg(long, value_name = "PORT", help_heading = S32X_OPTIONS_HEADING)]
+    s32x_gdb_port: Option<u16>,
+
     /// Aspect ratio
     #[arg(long, help_heading = NES_OPTIONS_HEADING)]
     nes_aspect_ratio: Option<NesAspectRatio>,
@@ -306,17 +421,26 @@ struct Args {
     #[arg(long, help_heading = SNES_OPTIONS_HEADING)]
     snes_deinterlace: Option<bool>,
 
+    /// Blend horizontally adjacent pixels in hi-res frames, for pseudo-hi-res transparency effects
+    #[arg(long, help_heading = SNES_OPTIONS_HEADING)]
+    snes_hi_res_blending: Option<bool>,
+
+    /// Debug overlay that tints every pixel by the layer that won priority resolution (BG1 red,
+    /// BG2 green, BG3 blue, BG4 yellow, sprites magenta, backdrop black)
+    #[arg(long, help_heading = SNES_OPTIONS_HEADING)]
+    snes_priority_overlay: Option<bool>,
+
     /// Audio interpolation mode
     #[arg(long, help_heading = SNES_OPTIONS_HEADING)]
     snes_audio_interpolation: Option<AudioInterpolationMode>,
 
-    /// Enable hack that times SNES audio sync to 60Hz instead of ~60.098Hz
+    /// Enable hack that times SNES audio sync to 60Hz NTSC / 50Hz PAL instead of ~60.099Hz NTSC / ~50.007Hz PAL
     #[arg(long, help_heading = SNES_OPTIONS_HEADING)]
     snes_audio_60hz_hack: Option<bool>,
 
-    /// Speed multiplier for the Super FX GSU
+    /// Speed multiplier for the Super FX GSU. Fractional multipliers such as 1.5 are allowed
     #[arg(long, help_heading = SNES_OPTIONS_HEADING)]
-    gsu_overclock_factor: Option<NonZeroU64>,
+    gsu_overclock_factor: Option<ClockRatio>,
 
     /// Player 2 input device
     #[arg(long, help_heading = SNES_OPTIONS_HEADING)]
@@ -346,6 +470,14 @@ struct Args {
     #[arg(long, help_heading = SNES_OPTIONS_HEADING)]
     st011_rom_path: Option<PathBuf>,
 
+    /// Specify Super Game Boy BIOS path (required to run Game Boy games with --hardware snes)
+    #[arg(long, help_heading = SNES_OPTIONS_HEADING)]
+    sgb_bios_path: Option<PathBuf>,
+
+    /// Specify a dumped SPC700 IPL boot ROM to use instead of the built-in copy
+    #[arg(long, help_heading = SNES_OPTIONS_HEADING)]
+    spc700_ipl_path: Option<PathBuf>,
+
     /// Force DMG / original Game Boy mode in software with Game Boy Color support
     #[arg(long, help_heading = GB_OPTIONS_HEADING)]
     force_dmg_mode: Option<bool>,
@@ -402,6 +534,15 @@ struct Args {
     #[arg(long, help_heading = VIDEO_OPTIONS_HEADING)]
     frame_time_sync: Option<bool>,
 
+    /// Present frames at evenly spaced timestamps instead of as soon as they are rendered; can
+    /// smooth out FMV playback on mismatched refresh rates
+    #[arg(long, help_heading = VIDEO_OPTIONS_HEADING)]
+    decoupled_frame_presentation: Option<bool>,
+
+    /// Pace frames at exactly 60Hz (NTSC) / 50Hz (PAL) instead of the console's native framerate
+    #[arg(long, help_heading = VIDEO_OPTIONS_HEADING)]
+    round_frame_rate: Option<bool>,
+
     /// Enable auto-prescaling
     #[arg(long, help_heading = VIDEO_OPTIONS_HEADING)]
     auto_prescale: Option<bool>,
@@ -426,10 +567,22 @@ struct Args {
     #[arg(long, help_heading = VIDEO_OPTIONS_HEADING)]
     preprocess_shader: Option<PreprocessShader>,
 
+    /// Frames to present between emulated frames on high refresh rate displays; requires VSync
+    #[arg(long, help_heading = VIDEO_OPTIONS_HEADING)]
+    frame_insertion: Option<FrameInsertion>,
+
+    /// Display refresh rate as a multiple of the emulated frame rate, used with frame insertion
+    #[arg(long, help_heading = VIDEO_OPTIONS_HEADING)]
+    refresh_multiplier: Option<RefreshMultiplier>,
+
     /// Audio output frequency (48000 recommended)
     #[arg(long, help_heading = AUDIO_OPTIONS_HEADING)]
     audio_output_frequency: Option<u64>,
 
+    /// Sync mode; overrides VSync mode, frame time sync, and audio sync unless set to custom
+    #[arg(long, help_heading = AUDIO_OPTIONS_HEADING)]
+    sync_mode: Option<SyncMode>,
+
     /// Enable audio sync
     #[arg(long, help_heading = AUDIO_OPTIONS_HEADING)]
     audio_sync: Option<bool>,
@@ -446,6 +599,10 @@ struct Args {
     #[arg(long, help_heading = AUDIO_OPTIONS_HEADING)]
     audio_gain_db: Option<f64>,
 
+    /// Master volume as a percentage (0-100)
+    #[arg(long, help_heading = AUDIO_OPTIONS_HEADING, value_parser = clap::value_parser!(u8).range(0..=100))]
+    audio_volume_percent: Option<u8>,
+
     /// Fast forward multiplier
     #[arg(long, help_heading = HOTKEY_OPTIONS_HEADING)]
     fast_forward_multiplier: Option<u64>,
@@ -457,6 +614,20 @@ struct Args {
     /// Attempt to load the most recent save state slot during startup
     #[arg(long, help_heading = HOTKEY_OPTIONS_HEADING)]
     load_recent_state_at_launch: Option<bool>,
+
+    /// Save state automatically on exit and offer to resume from it the next time the same ROM is
+    /// launched
+    #[arg(long, help_heading = HOTKEY_OPTIONS_HEADING)]
+    auto_save_state: Option<bool>,
+
+    /// Also write the automatic save state periodically while running, so that a crash loses at
+    /// most one interval of progress; requires --auto-save-state
+    #[arg(long, help_heading = HOTKEY_OPTIONS_HEADING)]
+    periodic_auto_save_state: Option<bool>,
+
+    /// Interval in minutes between periodic automatic save states
+    #[arg(long, help_heading = HOTKEY_OPTIONS_HEADING)]
+    auto_save_state_interval_minutes: Option<u64>,
 }
 
 macro_rules! apply_overrides {
@@ -500,6 +671,8 @@ impl Args {
         fix_optional_relative_path(&mut self.dsp4_rom_path);
         fix_optional_relative_path(&mut self.st010_rom_path);
         fix_optional_relative_path(&mut self.st011_rom_path);
+        fix_optional_relative_path(&mut self.sgb_bios_path);
+        fix_optional_relative_path(&mut self.spc700_ipl_path);
 
         self
     }
@@ -532,7 +705,20 @@ impl Args {
             config.nes.remove_sprite_limit = remove_sprite_limit;
         }
 
-        apply_overrides!(self, config.common, [hide_mouse_cursor, save_path, state_path]);
+        apply_overrides!(self, config.common, [
+            hide_mouse_cursor,
+            input_viewer_enabled,
+            input_viewer_position,
+            input_viewer_skin,
+            purist_mode,
+            discord_presence_enabled,
+            pause_when_unfocused,
+            pause_on_controller_disconnect,
+            save_path,
+            state_path,
+        ]);
+
+        apply_overrides!(self, config.input, [background_gamepad_input, subframe_input]);
 
         if let Some(custom_save_path) = &self.custom_save_path {
             config.common.custom_save_path.clone_from(custom_save_path);
@@ -552,6 +738,9 @@ impl Args {
             sms_crop_vertical_border,
             sms_crop_left_border,
             gg_use_sms_resolution,
+            gg_frame_blending,
+            gg_lcd_persistence,
+            sms_ntsc_filter -> ntsc_composite_filter,
             sms_fm_unit_enabled -> fm_sound_unit_enabled,
             smsgg_z80_divider -> z80_divider,
         ]);
@@ -559,15 +748,19 @@ impl Args {
         if let Some(psg_version) = self.psg_version {
             config.smsgg.psg_version = Some(psg_version);
         }
+
+        apply_overrides!(self, config.input.smsgg, [sms_p1_controller_type -> p1_type]);
     }
 
     fn apply_genesis_overrides(&self, config: &mut AppConfig) {
         apply_overrides!(self, config.genesis, [
             emulate_non_linear_vdp_dac,
+            genesis_expanded_vram -> expanded_vram,
             genesis_deinterlace -> deinterlace,
             m68k_clock_divider,
             genesis_render_vertical_border -> render_vertical_border,
             genesis_render_horizontal_border -> render_horizontal_border,
+            genesis_ntsc_filter -> ntsc_composite_filter,
             quantize_ym2612_output,
             emulate_ym2612_ladder_effect,
             genesis_low_pass -> low_pass,
@@ -592,8 +785,13 @@ impl Args {
 
         apply_overrides!(self, config.sega_cd, [
             enable_ram_cartridge,
+            scd_backup_ram_write_protect -> backup_ram_write_protected,
             scd_pcm_interpolation -> pcm_interpolation,
             scd_load_disc_into_ram -> load_disc_into_ram,
+            scd_disc_cache -> disc_cache_enabled,
+            scd_disc_cache_size_mb -> disc_cache_size_mb,
+            scd_disc_prefetch_sectors -> disc_prefetch_sectors,
+            scd_fast_boot -> fast_boot,
             scd_drive_speed -> disc_drive_speed,
             scd_sub_cpu_divider -> sub_cpu_divider,
             scd_pcm_low_pass -> pcm_low_pass,
@@ -635,6 +833,8 @@ impl Args {
         apply_overrides!(self, config.snes, [
             snes_aspect_ratio -> aspect_ratio,
             snes_deinterlace -> deinterlace,
+            snes_hi_res_blending -> hi_res_blending,
+            snes_priority_overlay -> priority_overlay,
             snes_audio_interpolation -> audio_interpolation,
             snes_audio_60hz_hack -> audio_60hz_hack,
             gsu_overclock_factor,
@@ -651,6 +851,8 @@ impl Args {
             dsp4_rom_path,
             st010_rom_path,
             st011_rom_path,
+            sgb_bios_path,
+            spc700_ipl_path,
         ]);
     }
 
@@ -694,11 +896,15 @@ impl Args {
             wgpu_backend,
             vsync_mode,
             frame_time_sync,
+            decoupled_frame_presentation,
+            round_frame_rate,
             auto_prescale,
             scanlines,
             force_integer_height_scaling,
             filter_mode,
             preprocess_shader,
+            frame_insertion,
+            refresh_multiplier,
         ]);
 
         if let Some(prescale_factor) = self.prescale_factor {
@@ -710,10 +916,12 @@ impl Args {
     fn apply_audio_overrides(&self, config: &mut AppConfig) {
         apply_overrides!(self, config.common, [
             audio_output_frequency,
+            sync_mode,
             audio_sync,
             audio_hardware_queue_size,
             audio_buffer_size,
             audio_gain_db,
+            audio_volume_percent,
         ]);
     }
 
@@ -721,6 +929,9 @@ impl Args {
         apply_overrides!(self, config.common, [
             fast_forward_multiplier,
             rewind_buffer_length_seconds,
+            auto_save_state,
+            periodic_auto_save_state,
+            auto_save_state_interval_minutes,
         ]);
 
         if self.load_save_state.is_some() {
@@ -779,7 +990,7 @@ fn main() -> anyhow::Result<()> {
         if !config_file_exists {
             let config_str = toml::to_string_pretty(&config)?;
             log::info!("Persisting default config to '{}'", config_path.display());
-            if let Err(err) = fs::write(&config_path, &config_str) {
+            if let Err(err) = jgenesis_common::files::write_atomic(&config_path, &config_str) {
                 log::error!(
                     "Error serializing default config file to '{}': {err}",
                     config_path.display()
@@ -794,15 +1005,22 @@ fn main() -> anyhow::Result<()> {
 
     args.apply_overrides(&mut config);
 
-    match hardware {
-        Hardware::MasterSystem => run_sms(args, config),
-        Hardware::Genesis => run_genesis(args, config),
-        Hardware::SegaCd => run_sega_cd(args, config),
-        Hardware::Sega32X => run_32x(args, config),
-        Hardware::Nes => run_nes(args, config),
-        Hardware::Snes => run_snes(args, config),
-        Hardware::GameBoy => run_gb(args, config),
+    let system_config = system_config(hardware, &args, &config);
+
+    if let Some(frames) = args.bench {
+        let report = jgenesis_native_driver::bench(system_config, frames)?;
+        print!("{report}");
+        return Ok(());
+    }
+
+    if let Some(frames) = args.hash_frames {
+        let report = jgenesis_native_driver::hash_frames(system_config, frames)?;
+        println!("{report}");
+        return Ok(());
     }
+
+    let mut emulator = jgenesis_native_driver::create_emulator(system_config)?;
+    run_emulator(emulator.as_mut(), &args)
 }
 
 fn guess_hardware(args: &Args) -> anyhow::Result<Hardware> {
@@ -861,56 +1079,45 @@ fn guess_hardware(args: &Args) -> anyhow::Result<Hardware> {
     })
 }
 
-fn run_sms(args: Args, config: AppConfig) -> anyhow::Result<()> {
-    let mut emulator =
-        jgenesis_native_driver::create_smsgg(config.smsgg_config(args.file_path.clone()))?;
-    run_emulator(&mut emulator, &args)
-}
-
-fn run_genesis(args: Args, config: AppConfig) -> anyhow::Result<()> {
-    let mut emulator =
-        jgenesis_native_driver::create_genesis(config.genesis_config(args.file_path.clone()))?;
-    run_emulator(&mut emulator, &args)
-}
-
-fn run_sega_cd(args: Args, config: AppConfig) -> anyhow::Result<()> {
-    let mut scd_config = config.sega_cd_config(args.file_path.clone());
-    scd_config.run_without_disc = args.scd_no_disc;
-
-    let mut emulator = jgenesis_native_driver::create_sega_cd(scd_config)?;
-    run_emulator(&mut emulator, &args)
-}
-
-fn run_32x(args: Args, config: AppConfig) -> anyhow::Result<()> {
-    let mut emulator =
-        jgenesis_native_driver::create_32x(config.sega_32x_config(args.file_path.clone()))?;
-    run_emulator(&mut emulator, &args)
-}
-
-fn run_nes(args: Args, config: AppConfig) -> anyhow::Result<()> {
-    let mut emulator =
-        jgenesis_native_driver::create_nes(config.nes_config(args.file_path.clone()))?;
-    run_emulator(&mut emulator, &args)
-}
-
-fn run_snes(args: Args, config: AppConfig) -> anyhow::Result<()> {
-    let mut emulator =
-        jgenesis_native_driver::create_snes(config.snes_config(args.file_path.clone()))?;
-    run_emulator(&mut emulator, &args)
-}
-
-fn run_gb(args: Args, config: AppConfig) -> anyhow::Result<()> {
-    let mut emulator = jgenesis_native_driver::create_gb(config.gb_config(args.file_path.clone()))?;
-    run_emulator(&mut emulator, &args)
+fn system_config(hardware: Hardware, args: &Args, config: &AppConfig) -> SystemConfig {
+    let file_path = args.file_path.clone();
+    let link_cable = args
+        .link_listen
+        .map(LinkCableMode::Listen)
+        .or_else(|| args.link_connect.clone().map(LinkCableMode::Connect));
+    match hardware {
+        Hardware::MasterSystem => {
+            let mut smsgg_config = config.smsgg_config(file_path);
+            smsgg_config.link_cable = link_cable;
+            SystemConfig::SmsGg(smsgg_config)
+        }
+        Hardware::Sg1000 => {
+            let mut smsgg_config = config.smsgg_config(file_path);
+            smsgg_config.forced_hardware = Some(SmsGgHardware::Sg1000);
+            SystemConfig::SmsGg(smsgg_config)
+        }
+        Hardware::Genesis => {
+            let mut genesis_config = config.genesis_config(file_path);
+            genesis_config.link_cable = link_cable;
+            SystemConfig::Genesis(genesis_config)
+        }
+        Hardware::SegaCd => {
+            let mut scd_config = config.sega_cd_config(file_path);
+            scd_config.run_without_disc = args.scd_no_disc;
+            SystemConfig::SegaCd(scd_config)
+        }
+        Hardware::Sega32X => {
+            let mut s32x_config = config.sega_32x_config(file_path);
+            s32x_config.gdb_port = args.s32x_gdb_port;
+            SystemConfig::Sega32X(s32x_config)
+        }
+        Hardware::Nes => SystemConfig::Nes(config.nes_config(file_path)),
+        Hardware::Snes => SystemConfig::Snes(config.snes_config(file_path)),
+        Hardware::GameBoy => SystemConfig::GameBoy(config.gb_config(file_path)),
+    }
 }
 
-fn run_emulator<Emulator>(
-    emulator: &mut NativeEmulator<Emulator>,
-    args: &Args,
-) -> anyhow::Result<()>
-where
-    Emulator: EmulatorTrait,
-{
+fn run_emulator(emulator: &mut dyn EmulatorCore, args: &Args) -> anyhow::Result<()> {
     if let Some(save_state_slot) = args.load_save_state {
         log::info!("Loading save state slot {save_state_slot} at launch");
 
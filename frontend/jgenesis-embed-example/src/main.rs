@@ -0,0 +1,239 @@
+//! Minimal example of embedding an emulation core in a custom frontend, using only the public APIs
+//! of `smsgg-core` and `jgenesis-common`.
+//!
+//! Video is drawn to a winit window using softbuffer, and audio is discarded. Save files are not
+//! loaded or written.
+//!
+//! Usage: `cargo run -p jgenesis-embed-example -- <path to .sms or .gg file>`
+//!
+//! Controls: Arrow keys for the D-pad, Z for button 1, X for button 2, Enter for Pause/Start
+
+use jgenesis_common::frontend::{
+    Color, EmulatorTrait, FrameSize, MappableInputs, NullAudioOutput, NullSaveWriter,
+    PixelAspectRatio, Renderer, TickEffect,
+};
+use jgenesis_common::input::Player;
+use smsgg_core::{SmsGgButton, SmsGgEmulator, SmsGgEmulatorConfig, SmsGgHardware, SmsGgInputs};
+use softbuffer::{Context, Surface};
+use std::convert::Infallible;
+use std::error::Error;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use std::{env, fs};
+use winit::application::ApplicationHandler;
+use winit::dpi::PhysicalSize;
+use winit::event::{ElementState, KeyEvent, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
+use winit::keyboard::{KeyCode, PhysicalKey};
+use winit::window::{Window, WindowId};
+
+const WINDOW_SCALE: u32 = 3;
+
+// Keeps a copy of the most recently rendered frame so that it can be redrawn at any time
+struct FrameBuffer {
+    pixels: Vec<Color>,
+    size: FrameSize,
+}
+
+impl Renderer for FrameBuffer {
+    type Err = Infallible;
+
+    fn render_frame(
+        &mut self,
+        frame_buffer: &[Color],
+        frame_size: FrameSize,
+        _pixel_aspect_ratio: Option<PixelAspectRatio>,
+    ) -> Result<(), Self::Err> {
+        let len = (frame_size.width * frame_size.height) as usize;
+        self.pixels.clear();
+        self.pixels.extend_from_slice(&frame_buffer[..len]);
+        self.size = frame_size;
+
+        Ok(())
+    }
+}
+
+fn map_key(key: KeyCode) -> Option<SmsGgButton> {
+    match key {
+        KeyCode::ArrowUp => Some(SmsGgButton::Up),
+        KeyCode::ArrowLeft => Some(SmsGgButton::Left),
+        KeyCode::ArrowRight => Some(SmsGgButton::Right),
+        KeyCode::ArrowDown => Some(SmsGgButton::Down),
+        KeyCode::KeyZ => Some(SmsGgButton::Button1),
+        KeyCode::KeyX => Some(SmsGgButton::Button2),
+        KeyCode::Enter => Some(SmsGgButton::Pause),
+        _ => None,
+    }
+}
+
+struct WindowState {
+    window: Rc<Window>,
+    surface: Surface<Rc<Window>, Rc<Window>>,
+}
+
+struct App {
+    emulator: SmsGgEmulator,
+    inputs: SmsGgInputs,
+    frame_buffer: FrameBuffer,
+    frame_duration: Duration,
+    next_frame_time: Instant,
+    window: Option<WindowState>,
+}
+
+impl App {
+    fn new(emulator: SmsGgEmulator) -> Self {
+        let frame_duration = Duration::from_secs_f64(1.0 / emulator.target_fps());
+
+        Self {
+            emulator,
+            inputs: SmsGgInputs::default(),
+            frame_buffer: FrameBuffer { pixels: vec![], size: FrameSize { width: 0, height: 0 } },
+            frame_duration,
+            next_frame_time: Instant::now(),
+            window: None,
+        }
+    }
+
+    fn run_frame(&mut self) -> Result<(), Box<dyn Error>> {
+        loop {
+            let tick_effect = self.emulator.tick(
+                &mut self.frame_buffer,
+                &mut NullAudioOutput,
+                &self.inputs,
+                &mut NullSaveWriter,
+            )?;
+            if tick_effect == TickEffect::FrameRendered {
+                return Ok(());
+            }
+        }
+    }
+
+    fn draw(&mut self) -> Result<(), Box<dyn Error>> {
+        let Some(WindowState { window, surface }) = &mut self.window else { return Ok(()) };
+
+        let window_size = window.inner_size();
+        let (Some(width), Some(height)) =
+            (NonZeroU32::new(window_size.width), NonZeroU32::new(window_size.height))
+        else {
+            return Ok(());
+        };
+        surface.resize(width, height)?;
+
+        let mut buffer = surface.buffer_mut()?;
+
+        // Nearest-neighbor scale the frame to fill the window
+        let FrameSize { width: frame_width, height: frame_height } = self.frame_buffer.size;
+        if frame_width != 0 && frame_height != 0 {
+            for y in 0..height.get() {
+                let frame_y = y * frame_height / height.get();
+                for x in 0..width.get() {
+                    let frame_x = x * frame_width / width.get();
+                    let Color { r, g, b, .. } =
+                        self.frame_buffer.pixels[(frame_y * frame_width + frame_x) as usize];
+                    buffer[(y * width.get() + x) as usize] =
+                        (u32::from(r) << 16) | (u32::from(g) << 8) | u32::from(b);
+                }
+            }
+        }
+
+        buffer.present()?;
+
+        Ok(())
+    }
+}
+
+impl ApplicationHandler for App {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.window.is_some() {
+            return;
+        }
+
+        let attributes = Window::default_attributes()
+            .with_title("jgenesis embed example")
+            .with_inner_size(PhysicalSize::new(256 * WINDOW_SCALE, 192 * WINDOW_SCALE));
+        let window = match event_loop.create_window(attributes) {
+            Ok(window) => Rc::new(window),
+            Err(err) => {
+                log::error!("Error creating window: {err}");
+                event_loop.exit();
+                return;
+            }
+        };
+
+        let surface = Context::new(Rc::clone(&window))
+            .and_then(|context| Surface::new(&context, Rc::clone(&window)));
+        match surface {
+            Ok(surface) => self.window = Some(WindowState { window, surface }),
+            Err(err) => {
+                log::error!("Error creating softbuffer surface: {err}");
+                event_loop.exit();
+            }
+        }
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, _: WindowId, event: WindowEvent) {
+        match event {
+            WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::KeyboardInput {
+                event: KeyEvent { physical_key: PhysicalKey::Code(key), state, .. },
+                ..
+            } => {
+                if let Some(button) = map_key(key) {
+                    let pressed = state == ElementState::Pressed;
+                    self.inputs.set_field(button, Player::One, pressed);
+                }
+            }
+            WindowEvent::RedrawRequested => {
+                if let Err(err) = self.draw() {
+                    log::error!("Error drawing frame: {err}");
+                    event_loop.exit();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let now = Instant::now();
+        if now >= self.next_frame_time {
+            if let Err(err) = self.run_frame() {
+                log::error!("Emulator error: {err}");
+                event_loop.exit();
+                return;
+            }
+
+            // Don't try to catch up if the emulator fell behind, e.g. because the window was
+            // being dragged
+            self.next_frame_time = (self.next_frame_time + self.frame_duration).max(now);
+
+            if let Some(WindowState { window, .. }) = &self.window {
+                window.request_redraw();
+            }
+        }
+
+        event_loop.set_control_flow(ControlFlow::WaitUntil(self.next_frame_time));
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let Some(rom_path) = env::args().nth(1) else {
+        return Err("usage: jgenesis-embed-example <rom path>".into());
+    };
+    let rom_path = Path::new(&rom_path);
+
+    let extension = rom_path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+    let hardware = SmsGgHardware::from_extension(extension).unwrap_or(SmsGgHardware::MasterSystem);
+
+    let rom = fs::read(rom_path)?;
+    let emulator =
+        SmsGgEmulator::create(rom, hardware, SmsGgEmulatorConfig::default(), &mut NullSaveWriter);
+
+    let event_loop = EventLoop::new()?;
+    event_loop.run_app(&mut App::new(emulator))?;
+
+    Ok(())
+}
@@ -2,44 +2,61 @@ mod common;
 mod gb;
 mod genesis;
 mod input;
+mod logwindow;
 mod nes;
+mod palette;
 mod romlist;
 mod smsgg;
 mod snes;
+mod theme;
 
 use crate::app::input::{GenericButton, InputMappingSet};
+use crate::app::logwindow::LogWindowState;
 use crate::app::nes::OverscanState;
+use crate::app::palette::CommandPaletteState;
 use crate::app::romlist::{RomListThreadHandle, RomMetadata};
 use crate::app::snes::HandledError;
+use crate::app::theme::UiStyle;
 use crate::emuthread;
 use crate::emuthread::{EmuThreadCommand, EmuThreadHandle, EmuThreadStatus};
 use crate::widgets::SavePathSelect;
 use eframe::Frame;
+use egui::load::SizedTexture;
 use egui::panel::TopBottomSide;
 use egui::{
-    Align, Button, CentralPanel, Color32, Context, Grid, Key, KeyboardShortcut, Layout, Modifiers,
-    Response, TextEdit, ThemePreference, TopBottomPanel, Ui, Vec2, ViewportCommand, Widget, Window,
-    menu,
+    Align, Button, CentralPanel, Color32, ColorImage, Context, Grid, Image, Key, KeyboardShortcut,
+    Layout, Modifiers, ProgressBar, Response, Slider, TextEdit, TextureHandle, TextureOptions,
+    ThemePreference, TopBottomPanel, Ui, Vec2, ViewportCommand, Widget, Window, menu,
 };
 use egui_extras::{Column, TableBuilder};
+use genesis_core::GenesisRegion;
+use jgenesis_common::frontend::TimingMode;
+use jgenesis_native_config::overrides::RegionOverride;
 use jgenesis_native_config::{AppConfig, EguiTheme, ListFilters, RecentOpen};
 use jgenesis_native_driver::config::HideMouseCursor;
-use jgenesis_native_driver::{NativeEmulatorError, extensions};
+use jgenesis_native_driver::{
+    NativeEmulatorError, SAVE_STATE_SLOTS, SaveStateMetadata, SegaCdBackupRam, extensions,
+};
 use jgenesis_proc_macros::{EnumAll, EnumDisplay, EnumFromStr};
 use jgenesis_renderer::config::Scanlines;
 use rfd::FileDialog;
+use smsgg_core::SmsRegion;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 use time::{OffsetDateTime, UtcOffset, format_description};
 
+const SAVE_STATE_THUMBNAIL_SIZE: Vec2 = Vec2::new(64.0, 48.0);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumAll, EnumDisplay, EnumFromStr)]
 pub enum Console {
     MasterSystem,
     GameGear,
+    Sg1000,
     Genesis,
     SegaCd,
     Sega32X,
@@ -64,6 +81,7 @@ impl ListFiltersExt for ListFilters {
         [
             self.master_system.then_some(Console::MasterSystem),
             self.game_gear.then_some(Console::GameGear),
+            self.sg_1000.then_some(Console::Sg1000),
             self.genesis.then_some(Console::Genesis),
             self.sega_cd.then_some(Console::SegaCd),
             self.sega_32x.then_some(Console::Sega32X),
@@ -90,7 +108,10 @@ impl ListFiltersExt for ListFilters {
         rom_list.iter().filter(move |metadata| {
             filters.contains(&metadata.console)
                 && (title_match_lowercase.is_empty()
-                    || metadata.file_name_no_ext.to_lowercase().contains(title_match_lowercase))
+                    || metadata.file_name_no_ext.to_lowercase().contains(title_match_lowercase)
+                    || metadata
+                        .header_title()
+                        .is_some_and(|title| title.to_lowercase().contains(title_match_lowercase)))
         })
     }
 }
@@ -125,15 +146,29 @@ enum OpenWindow {
     SnesPeripherals,
     GameBoyInput,
     Hotkeys,
+    RegionOverride,
+    FormatBackupRam,
+    CommandPalette,
+    Log,
     About,
 }
 
+// Hover text for settings that are only read when the emulator is created and so are disabled while
+// a game is running
+const REQUIRES_RESTART: &str = "This setting cannot be changed while a game is running";
+
 #[derive(Debug, Clone, Copy)]
 struct HelpText {
     heading: &'static str,
     text: &'static [&'static str],
 }
 
+struct RegionOverrideState {
+    path: PathBuf,
+    smsgg: bool,
+    region_override: RegionOverride,
+}
+
 struct AppState {
     current_file_path: PathBuf,
     open_windows: HashSet<OpenWindow>,
@@ -145,6 +180,8 @@ struct AppState {
     ff_multiplier_invalid: bool,
     rewind_buffer_len_text: String,
     rewind_buffer_len_invalid: bool,
+    auto_save_interval_text: String,
+    auto_save_interval_invalid: bool,
     audio_hardware_queue_size_text: String,
     audio_hardware_queue_size_invalid: bool,
     audio_buffer_size_text: String,
@@ -162,6 +199,12 @@ struct AppState {
     title_match_lowercase: Rc<str>,
     rendered_first_frame: bool,
     close_on_emulator_exit: bool,
+    // Keyed by save time so that a texture is only re-uploaded when its slot is overwritten
+    save_state_thumbnails: [Option<(u128, TextureHandle)>; SAVE_STATE_SLOTS],
+    region_override: Option<RegionOverrideState>,
+    log_window: LogWindowState,
+    command_palette: CommandPaletteState,
+    applied_ui_style: Option<UiStyle>,
 }
 
 impl AppState {
@@ -178,6 +221,8 @@ impl AppState {
             ff_multiplier_invalid: false,
             rewind_buffer_len_text: config.common.rewind_buffer_length_seconds.to_string(),
             rewind_buffer_len_invalid: false,
+            auto_save_interval_text: config.common.auto_save_state_interval_minutes.to_string(),
+            auto_save_interval_invalid: false,
             audio_hardware_queue_size_text: config.common.audio_hardware_queue_size.to_string(),
             audio_hardware_queue_size_invalid: false,
             audio_buffer_size_text: config.common.audio_buffer_size.to_string(),
@@ -195,6 +240,11 @@ impl AppState {
             recent_open_list,
             rendered_first_frame: false,
             close_on_emulator_exit: false,
+            save_state_thumbnails: Default::default(),
+            region_override: None,
+            log_window: LogWindowState::new(),
+            command_palette: CommandPaletteState::new(),
+            applied_ui_style: None,
         }
     }
 }
@@ -260,10 +310,16 @@ pub struct LoadAtStartup {
     pub load_state_slot: Option<usize>,
 }
 
+// How often to check whether another running instance has modified the config file
+const CONFIG_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 pub struct App {
     config: AppConfig,
     state: AppState,
     config_path: PathBuf,
+    // Modification time of the config file as of the last time this instance read or wrote it
+    config_modified: Option<SystemTime>,
+    last_config_check: Instant,
     emu_thread: EmuThreadHandle,
     rom_list_thread: RomListThreadHandle,
     load_at_startup: Option<LoadAtStartup>,
@@ -279,7 +335,8 @@ fn load_app_config(config_path: &Path) -> AppConfig {
     if let Some(migrated_config) = jgenesis_native_config::migrate_config(&config, &config_str) {
         if config != migrated_config {
             config = migrated_config;
-            if let Err(err) = fs::write(config_path, toml::to_string_pretty(&config).unwrap()) {
+            let config_str = toml::to_string_pretty(&config).unwrap();
+            if let Err(err) = jgenesis_common::files::write_atomic(config_path, config_str) {
                 log::error!("Error serializing app config: {err}");
             }
         }
@@ -288,10 +345,15 @@ fn load_app_config(config_path: &Path) -> AppConfig {
     config
 }
 
+fn config_modified_time(config_path: &Path) -> Option<SystemTime> {
+    fs::metadata(config_path).and_then(|metadata| metadata.modified()).ok()
+}
+
 impl App {
     #[must_use]
     pub fn new(config_path: PathBuf, load_at_startup: Option<LoadAtStartup>, ctx: Context) -> Self {
         let config = load_app_config(&config_path);
+        let config_modified = config_modified_time(&config_path);
 
         let state = AppState::from_config(&config);
         let emu_thread = emuthread::spawn(ctx.clone());
@@ -299,7 +361,16 @@ impl App {
         let rom_list_thread = RomListThreadHandle::spawn(Arc::clone(&state.rom_list), ctx);
         rom_list_thread.request_scan(config.rom_search_dirs.clone());
 
-        Self { config, state, config_path, emu_thread, rom_list_thread, load_at_startup }
+        Self {
+            config,
+            state,
+            config_path,
+            config_modified,
+            last_config_check: Instant::now(),
+            emu_thread,
+            rom_list_thread,
+            load_at_startup,
+        }
     }
 
     fn open_file(&mut self, console: Option<Console>) {
@@ -355,6 +426,13 @@ impl App {
         self.config.recent_open_list.truncate(10);
         self.state.recent_open_list = romlist::from_recent_opens(&self.config.recent_open_list);
 
+        // Game Boy games run in the SNES core when Super Game Boy mode is enabled
+        let console = if console == Console::GameBoy && self.config.snes.super_game_boy_mode {
+            Console::Snes
+        } else {
+            console
+        };
+
         self.emu_thread.stop_emulator_if_running();
         self.emu_thread.send(EmuThreadCommand::Run {
             console,
@@ -457,13 +535,161 @@ impl App {
                     ui.radio_value(&mut self.config.egui_theme, EguiTheme::Dark, "Dark");
                     ui.radio_value(&mut self.config.egui_theme, EguiTheme::Light, "Light");
                 });
+
+                ui.checkbox(&mut self.config.egui_high_contrast, "High contrast")
+                    .on_hover_text("Use solid black and white backgrounds, text, and outlines");
+
+                ui.horizontal(|ui| {
+                    let mut custom_accent = self.config.egui_accent_color.is_some();
+                    ui.checkbox(&mut custom_accent, "Custom accent color");
+
+                    match (custom_accent, &mut self.config.egui_accent_color) {
+                        (true, Some((r, g, b))) => {
+                            let mut rgb = [*r, *g, *b];
+                            ui.color_edit_button_srgb(&mut rgb);
+                            [*r, *g, *b] = rgb;
+                        }
+                        (true, accent_color @ None) => {
+                            let [r, g, b, _] = ui.visuals().selection.bg_fill.to_array();
+                            *accent_color = Some((r, g, b));
+                        }
+                        (false, accent_color) => *accent_color = None,
+                    }
+                });
             });
+
+            ui.add_space(5.0);
+
+            ui.group(|ui| {
+                ui.add(
+                    Slider::new(&mut self.config.egui_scale, theme::UI_SCALE_RANGE)
+                        .step_by(0.05)
+                        .fixed_decimals(2)
+                        .text("UI scale"),
+                );
+            });
+
+            ui.add_space(5.0);
+
+            ui.checkbox(&mut self.config.common.purist_mode, "Purist mode").on_hover_text(
+                "Disable all enhancements, overclocks, blending hacks, and debug layer/channel toggles regardless of their individual settings",
+            );
+
+            // Hidden entirely if no Discord application ID was provided, since it can't do anything
+            if jgenesis_native_driver::discord_presence_available() {
+                ui.checkbox(
+                    &mut self.config.common.discord_presence_enabled,
+                    "Show current game in Discord status",
+                )
+                .on_hover_text("Shows the current system and game title in your Discord status while a game is running");
+            }
+
+            ui.checkbox(
+                &mut self.config.common.pause_when_unfocused,
+                "Pause when emulator window loses focus",
+            );
+
+            ui.checkbox(
+                &mut self.config.common.pause_on_controller_disconnect,
+                "Pause when a player's controller disconnects",
+            );
         });
         if !open {
             self.state.open_windows.remove(&OpenWindow::Interface);
         }
     }
 
+    fn render_region_override(&mut self, ctx: &Context) {
+        let Some(state) = &mut self.state.region_override else {
+            self.state.open_windows.remove(&OpenWindow::RegionOverride);
+            return;
+        };
+        let prev_override = state.region_override;
+
+        let mut open = true;
+        Window::new("Region Override").open(&mut open).resizable(false).show(ctx, |ui| {
+            let file_name = state.path.file_name().unwrap_or_default().to_string_lossy();
+            ui.label(format!("Game: {file_name}"));
+
+            ui.add_space(5.0);
+
+            ui.group(|ui| {
+                ui.label("Region");
+
+                ui.horizontal(|ui| {
+                    if state.smsgg {
+                        let sms_region = &mut state.region_override.sms_region;
+                        ui.radio_value(sms_region, None, "Use global setting");
+                        ui.radio_value(
+                            sms_region,
+                            Some(SmsRegion::International),
+                            "International / Overseas",
+                        );
+                        ui.radio_value(sms_region, Some(SmsRegion::Domestic), "Domestic (Japan)");
+                    } else {
+                        let genesis_region = &mut state.region_override.genesis_region;
+                        ui.radio_value(genesis_region, None, "Use global setting");
+                        ui.radio_value(genesis_region, Some(GenesisRegion::Americas), "Americas");
+                        ui.radio_value(genesis_region, Some(GenesisRegion::Japan), "Japan");
+                        ui.radio_value(genesis_region, Some(GenesisRegion::Europe), "Europe");
+                    }
+                });
+            });
+
+            ui.group(|ui| {
+                ui.label("Timing / display mode");
+
+                ui.horizontal(|ui| {
+                    let timing_mode = &mut state.region_override.timing_mode;
+                    ui.radio_value(timing_mode, None, "Use global setting");
+                    ui.radio_value(timing_mode, Some(TimingMode::Ntsc), "NTSC (60Hz)");
+                    ui.radio_value(timing_mode, Some(TimingMode::Pal), "PAL (50Hz)");
+                });
+            });
+
+            ui.add_space(5.0);
+            ui.label("Changes will take effect the next time this game is launched");
+        });
+
+        if state.region_override != prev_override {
+            self.config.set_region_override(&state.path, state.region_override);
+        }
+
+        if !open {
+            self.state.region_override = None;
+            self.state.open_windows.remove(&OpenWindow::RegionOverride);
+        }
+    }
+
+    fn render_format_backup_ram(&mut self, ctx: &Context) {
+        if self.emu_thread.status() != EmuThreadStatus::RunningSegaCd {
+            self.state.open_windows.remove(&OpenWindow::FormatBackupRam);
+            return;
+        }
+
+        let mut open = true;
+        let mut confirmed = false;
+        let mut cancelled = false;
+        Window::new("Format Backup RAM").open(&mut open).resizable(false).show(ctx, |ui| {
+            ui.label("Erase all saves in the Sega CD internal backup RAM?");
+            ui.label("This cannot be undone.");
+
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                confirmed = ui.button("Format").clicked();
+                cancelled = ui.button("Cancel").clicked();
+            });
+        });
+
+        if confirmed {
+            self.emu_thread.send(EmuThreadCommand::SegaCdFormatBackupRam);
+        }
+
+        if !open || confirmed || cancelled {
+            self.state.open_windows.remove(&OpenWindow::FormatBackupRam);
+        }
+    }
+
     fn render_about(&mut self, ctx: &Context) {
         let mut open = true;
         Window::new("About").open(&mut open).resizable(false).show(ctx, |ui| {
@@ -487,6 +713,12 @@ impl App {
     }
 
     fn render_menu(&mut self, ctx: &Context) {
+        if !self.state.error_window_open
+            && ctx.input_mut(|input| input.consume_shortcut(&palette::PALETTE_SHORTCUT))
+        {
+            self.open_command_palette();
+        }
+
         TopBottomPanel::new(TopBottomSide::Top, "top_bottom_panel").show(ctx, |ui| {
             menu::bar(ui, |ui| {
                 ui.add_enabled_ui(!self.state.error_window_open, |ui| {
@@ -498,10 +730,29 @@ impl App {
                     self.render_input_menu(ui);
                     self.render_help_menu(ui);
                 });
+
+                self.render_performance_stats(ui);
             });
         });
     }
 
+    fn render_performance_stats(&self, ui: &mut Ui) {
+        let Some(stats) = self.emu_thread.performance_stats() else { return };
+
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            ui.label(format!(
+                "{:.0} FPS ({:.0}%) | {:.1} ms | Audio {:.0}%",
+                stats.frame.fps,
+                stats.speed_percent,
+                stats.frame.avg_frame_time_ms,
+                stats.audio_buffer_percent
+            ));
+        });
+
+        // Stats are updated from the emulation thread, so keep repainting while it's running
+        ui.ctx().request_repaint_after(Duration::from_secs(1));
+    }
+
     fn render_file_menu(&mut self, ctx: &Context, ui: &mut Ui) {
         let open_shortcut = KeyboardShortcut::new(Modifiers::CTRL, Key::O);
         if ctx.input_mut(|input| input.consume_shortcut(&open_shortcut)) {
@@ -538,6 +789,7 @@ impl App {
             ui.menu_button("Open Using", |ui| {
                 for console in [
                     Console::MasterSystem,
+                    Console::Sg1000,
                     Console::Genesis,
                     Console::SegaCd,
                     Console::Sega32X,
@@ -578,6 +830,50 @@ impl App {
         }
     }
 
+    fn render_save_state_thumbnail(
+        &mut self,
+        metadata: &SaveStateMetadata,
+        slot: usize,
+        ui: &mut Ui,
+    ) {
+        let (Some(time_nanos), Some(thumbnail)) =
+            (metadata.times_nanos[slot], &metadata.thumbnails[slot])
+        else {
+            // Keep labels aligned with slots that do have a thumbnail
+            ui.allocate_space(SAVE_STATE_THUMBNAIL_SIZE);
+            return;
+        };
+
+        let cached = &mut self.state.save_state_thumbnails[slot];
+        if cached.as_ref().is_none_or(|&(cached_time, _)| cached_time != time_nanos) {
+            let image = ColorImage {
+                size: [thumbnail.width as usize, thumbnail.height as usize],
+                pixels: thumbnail
+                    .pixels
+                    .iter()
+                    .map(|color| Color32::from_rgb(color.r, color.g, color.b))
+                    .collect(),
+            };
+            let texture = ui.ctx().load_texture(
+                format!("save_state_thumbnail_{slot}"),
+                image,
+                TextureOptions::LINEAR,
+            );
+            *cached = Some((time_nanos, texture));
+        }
+
+        let Some((_, texture)) = cached else { return };
+
+        let pixel_aspect_ratio = thumbnail.pixel_aspect_ratio.map_or(1.0, f64::from);
+        let texture_size = Vec2::new(
+            (pixel_aspect_ratio * f64::from(thumbnail.width)) as f32,
+            thumbnail.height as f32,
+        );
+        let image = Image::new(SizedTexture::new(texture.id(), texture_size))
+            .max_size(SAVE_STATE_THUMBNAIL_SIZE);
+        ui.add_sized(SAVE_STATE_THUMBNAIL_SIZE, image);
+    }
+
     fn render_emulation_menu(&mut self, ui: &mut Ui) {
         ui.menu_button("Emulation", |ui| {
             ui.add_enabled_ui(self.emu_thread.status().is_running(), |ui| {
@@ -586,43 +882,51 @@ impl App {
                 ui.menu_button("Load State", |ui| {
                     ui.set_min_width(200.0);
 
-                    for slot in 0..jgenesis_native_driver::SAVE_STATE_SLOTS {
-                        match save_state_metadata.times_nanos[slot] {
-                            Some(time_nanos) => {
-                                let formatted_time = format_time_nanos(time_nanos)
-                                    .unwrap_or_else(|| "Unknown".into());
-                                let label = format!("Slot {slot} - {formatted_time}");
-                                if ui.button(label).clicked() {
-                                    self.emu_thread.send(EmuThreadCommand::LoadState { slot });
-                                    ui.close_menu();
+                    for slot in 0..SAVE_STATE_SLOTS {
+                        ui.horizontal(|ui| {
+                            self.render_save_state_thumbnail(&save_state_metadata, slot, ui);
+
+                            match save_state_metadata.times_nanos[slot] {
+                                Some(time_nanos) => {
+                                    let formatted_time = format_time_nanos(time_nanos)
+                                        .unwrap_or_else(|| "Unknown".into());
+                                    let label = format!("Slot {slot} - {formatted_time}");
+                                    if ui.button(label).clicked() {
+                                        self.emu_thread.send(EmuThreadCommand::LoadState { slot });
+                                        ui.close_menu();
+                                    }
+                                }
+                                None => {
+                                    ui.add_enabled_ui(false, |ui| {
+                                        let _ = ui.button(format!("Slot {slot} - Empty"));
+                                    });
                                 }
                             }
-                            None => {
-                                ui.add_enabled_ui(false, |ui| {
-                                    let _ = ui.button(format!("Slot {slot} - Empty"));
-                                });
-                            }
-                        }
+                        });
                     }
                 });
 
                 ui.menu_button("Save State", |ui| {
                     ui.set_min_width(200.0);
 
-                    for slot in 0..jgenesis_native_driver::SAVE_STATE_SLOTS {
-                        let label = match save_state_metadata.times_nanos[slot] {
-                            Some(time_nanos) => {
-                                let formatted_time = format_time_nanos(time_nanos)
-                                    .unwrap_or_else(|| "Unknown".into());
-                                format!("Slot {slot} - {formatted_time}")
-                            }
-                            None => format!("Slot {slot} - Empty"),
-                        };
+                    for slot in 0..SAVE_STATE_SLOTS {
+                        ui.horizontal(|ui| {
+                            self.render_save_state_thumbnail(&save_state_metadata, slot, ui);
 
-                        if ui.button(label).clicked() {
-                            self.emu_thread.send(EmuThreadCommand::SaveState { slot });
-                            ui.close_menu();
-                        }
+                            let label = match save_state_metadata.times_nanos[slot] {
+                                Some(time_nanos) => {
+                                    let formatted_time = format_time_nanos(time_nanos)
+                                        .unwrap_or_else(|| "Unknown".into());
+                                    format!("Slot {slot} - {formatted_time}")
+                                }
+                                None => format!("Slot {slot} - Empty"),
+                            };
+
+                            if ui.button(label).clicked() {
+                                self.emu_thread.send(EmuThreadCommand::SaveState { slot });
+                                ui.close_menu();
+                            }
+                        });
                     }
                 });
 
@@ -633,6 +937,32 @@ impl App {
                     ui.close_menu();
                 }
 
+                if ui.button("Swap P1/P2 Inputs").clicked() {
+                    self.emu_thread.send(EmuThreadCommand::SwapPlayerInputs);
+                    ui.close_menu();
+                }
+
+                let status = self.emu_thread.status();
+                let cartridge_region = matches!(
+                    status,
+                    EmuThreadStatus::RunningSmsGg
+                        | EmuThreadStatus::RunningGenesis
+                        | EmuThreadStatus::Running32X
+                );
+                ui.add_enabled_ui(cartridge_region, |ui| {
+                    if ui.button("Region Override").clicked() {
+                        let path = self.state.current_file_path.clone();
+                        let region_override = self.config.region_override(&path);
+                        self.state.region_override = Some(RegionOverrideState {
+                            path,
+                            smsgg: status == EmuThreadStatus::RunningSmsGg,
+                            region_override,
+                        });
+                        self.state.open_windows.insert(OpenWindow::RegionOverride);
+                        ui.close_menu();
+                    }
+                });
+
                 ui.add_space(15.0);
 
                 let running_gb = self.emu_thread.status() == EmuThreadStatus::RunningGameBoy;
@@ -672,14 +1002,82 @@ impl App {
 
                             ui.close_menu();
                         }
+
+                        if ui.button("Format Backup RAM").clicked() {
+                            self.state.open_windows.insert(OpenWindow::FormatBackupRam);
+                            ui.close_menu();
+                        }
+
+                        ui.menu_button("Backup RAM Files", |ui| {
+                            self.render_sega_cd_backup_ram_menu(ui);
+                        });
                     },
                 );
             });
         });
     }
 
+    fn render_sega_cd_backup_ram_menu(&mut self, ui: &mut Ui) {
+        const EXTENSIONS: &[&str] = &["brm", "crm", "srm", "sav", "ramc", "bin"];
+        const INTERNAL_LEN: usize = 8 * 1024;
+        const RAM_CARTRIDGE_LEN: usize = 128 * 1024;
+        const GPGX_RAM_CARTRIDGE_LEN: usize = 512 * 1024;
+
+        let import_buttons = [
+            ("Import Internal Backup RAM...", SegaCdBackupRam::Internal),
+            ("Import RAM Cartridge...", SegaCdBackupRam::RamCartridge),
+        ];
+        for (label, backup_ram) in import_buttons {
+            if ui.button(label).clicked() {
+                if let Some(path) =
+                    FileDialog::new().add_filter("Backup RAM", EXTENSIONS).pick_file()
+                {
+                    self.emu_thread.send(EmuThreadCommand::SegaCdImportBackupRam(backup_ram, path));
+                }
+
+                ui.close_menu();
+            }
+        }
+
+        ui.separator();
+
+        let export_buttons = [
+            ("Export Internal Backup RAM...", SegaCdBackupRam::Internal, INTERNAL_LEN),
+            ("Export RAM Cartridge (128KB)...", SegaCdBackupRam::RamCartridge, RAM_CARTRIDGE_LEN),
+            (
+                "Export RAM Cartridge (512KB, Genesis Plus GX)...",
+                SegaCdBackupRam::RamCartridge,
+                GPGX_RAM_CARTRIDGE_LEN,
+            ),
+        ];
+        for (label, backup_ram, len) in export_buttons {
+            if ui.button(label).clicked() {
+                if let Some(path) =
+                    FileDialog::new().add_filter("Backup RAM", EXTENSIONS).save_file()
+                {
+                    self.emu_thread.send(EmuThreadCommand::SegaCdExportBackupRam {
+                        backup_ram,
+                        path,
+                        len,
+                    });
+                }
+
+                ui.close_menu();
+            }
+        }
+    }
+
     fn render_settings_menu(&mut self, ui: &mut Ui) {
         ui.menu_button("Settings", |ui| {
+            let search_button = Button::new("Search...")
+                .shortcut_text(ui.ctx().format_shortcut(&palette::PALETTE_SHORTCUT));
+            if search_button.ui(ui).clicked() {
+                self.open_command_palette();
+                ui.close_menu();
+            }
+
+            ui.separator();
+
             if ui.button("SMS / Game Gear").clicked() {
                 self.state.open_windows.insert(OpenWindow::SmsGgGeneral);
                 ui.close_menu();
@@ -850,6 +1248,11 @@ impl App {
 
     fn render_help_menu(&mut self, ui: &mut Ui) {
         ui.menu_button("Help", |ui| {
+            if ui.button("Log").clicked() {
+                self.state.open_windows.insert(OpenWindow::Log);
+                ui.close_menu();
+            }
+
             if ui.button("About").clicked() {
                 self.state.open_windows.insert(OpenWindow::About);
                 ui.close_menu();
@@ -913,12 +1316,28 @@ impl App {
                                     let metadata = &rom_list[row.index()];
 
                                     row.col(|ui| {
-                                        if Button::new(&metadata.file_name_no_ext)
+                                        let mut button = Button::new(&metadata.file_name_no_ext)
                                             .min_size(Vec2::new(300.0, 30.0))
                                             .wrap()
-                                            .ui(ui)
-                                            .clicked()
-                                        {
+                                            .ui(ui);
+                                        // Only check when hovered because this can read the whole ROM
+                                        if button.hovered() {
+                                            if let Some(header_summary) = metadata.header_summary()
+                                            {
+                                                button = button.on_hover_text(header_summary);
+                                            }
+                                        }
+
+                                        button.context_menu(|ui| {
+                                            if ui.button("Copy ROM hashes").clicked() {
+                                                if let Some(hashes) = metadata.rom_hashes() {
+                                                    ui.ctx().copy_text(hashes);
+                                                }
+                                                ui.close_menu();
+                                            }
+                                        });
+
+                                        if button.clicked() {
                                             self.emu_thread.stop_emulator_if_running();
                                             self.launch_emulator(metadata.full_path.clone(), None);
                                         }
@@ -974,6 +1393,7 @@ impl App {
 
             ui.checkbox(&mut self.config.list_filters.master_system, "SMS");
             ui.checkbox(&mut self.config.list_filters.game_gear, "GG");
+            ui.checkbox(&mut self.config.list_filters.sg_1000, "SG-1000");
             ui.checkbox(&mut self.config.list_filters.genesis, "Genesis");
             ui.checkbox(&mut self.config.list_filters.sega_cd, "Sega CD");
             ui.checkbox(&mut self.config.list_filters.sega_32x, "32X");
@@ -1009,6 +1429,9 @@ impl App {
             let mut open = true;
             match err {
                 NativeEmulatorError::SegaCdNoBios => self.render_scd_bios_error(ctx, &mut open),
+                NativeEmulatorError::SuperGameBoyNoBios => {
+                    self.render_sgb_bios_error(ctx, &mut open);
+                }
                 NativeEmulatorError::SnesLoad(snes_load_err) => {
                     match self.render_snes_load_error(ctx, snes_load_err, &mut open) {
                         HandledError::Yes => {}
@@ -1050,6 +1473,26 @@ impl App {
         }
     }
 
+    fn render_disc_load_progress(&mut self, ctx: &Context) {
+        let progress = self.emu_thread.disc_load_progress();
+        if !progress.is_loading() {
+            return;
+        }
+
+        Window::new("Loading Disc").resizable(false).collapsible(false).show(ctx, |ui| {
+            ui.add(ProgressBar::new(progress.fraction()).show_percentage().desired_width(250.0));
+
+            ui.add_space(5.0);
+
+            if ui.button("Cancel").clicked() {
+                progress.cancel();
+            }
+        });
+
+        // Progress is updated from the emulation thread, so keep repainting until loading finishes
+        ctx.request_repaint_after(Duration::from_millis(50));
+    }
+
     fn check_for_close_on_emu_exit(&mut self, ctx: &Context) {
         if self.state.close_on_emulator_exit {
             let status = self.emu_thread.status();
@@ -1065,6 +1508,14 @@ impl App {
             EguiTheme::Dark => ThemePreference::Dark,
             EguiTheme::Light => ThemePreference::Light,
         });
+
+        // Wait until the mouse is released before applying changes so that the UI does not rescale
+        // while the scale slider is being dragged
+        let ui_style = UiStyle::from_config(&self.config);
+        if self.state.applied_ui_style != Some(ui_style) && !ctx.input(|i| i.pointer.any_down()) {
+            ui_style.apply(ctx);
+            self.state.applied_ui_style = Some(ui_style);
+        }
     }
 
     fn reload_config(&mut self) {
@@ -1074,6 +1525,39 @@ impl App {
         ));
     }
 
+    // Multiple instances can share a config file. Pick up changes made by other instances so that
+    // the next settings change here does not overwrite them with stale values
+    fn check_external_config_change(&mut self) {
+        if self.last_config_check.elapsed() < CONFIG_CHECK_INTERVAL {
+            return;
+        }
+        self.last_config_check = Instant::now();
+
+        let modified = config_modified_time(&self.config_path);
+        if modified.is_none() || modified == self.config_modified {
+            return;
+        }
+        self.config_modified = modified;
+
+        let config = load_app_config(&self.config_path);
+        if config == self.config {
+            return;
+        }
+
+        log::info!(
+            "Config file '{}' was modified by another process; reloading",
+            self.config_path.display()
+        );
+
+        let prev_config = std::mem::replace(&mut self.config, config);
+        self.state.display_scanlines_warning = should_display_scanlines_warning(&self.config);
+        self.state.recent_open_list = romlist::from_recent_opens(&self.config.recent_open_list);
+
+        if should_reload_config(&prev_config, &self.config) {
+            self.reload_config();
+        }
+    }
+
     fn refresh_filtered_rom_list(&mut self) {
         let rom_list = self.state.rom_list.lock().unwrap();
 
@@ -1111,10 +1595,13 @@ impl eframe::App for App {
             }
         }
 
+        self.check_external_config_change();
+
         let prev_config = self.config.clone();
 
         self.check_emulator_error(ctx);
         self.check_waiting_for_input(ctx);
+        self.render_disc_load_progress(ctx);
         self.check_for_close_on_emu_exit(ctx);
 
         self.update_egui_theme(ctx);
@@ -1152,6 +1639,10 @@ impl eframe::App for App {
                 OpenWindow::SnesPeripherals => self.render_snes_peripheral_settings(ctx),
                 OpenWindow::GameBoyInput => self.render_gb_input_settings(ctx),
                 OpenWindow::Hotkeys => self.render_hotkey_settings(ctx),
+                OpenWindow::RegionOverride => self.render_region_override(ctx),
+                OpenWindow::FormatBackupRam => self.render_format_backup_ram(ctx),
+                OpenWindow::Log => self.render_log_window(ctx),
+                OpenWindow::CommandPalette => self.render_command_palette(ctx),
                 OpenWindow::About => self.render_about(ctx),
             }
         }
@@ -1164,9 +1655,10 @@ impl eframe::App for App {
             }
 
             let config_str = toml::to_string_pretty(&self.config).unwrap();
-            if let Err(err) = fs::write(&self.config_path, config_str) {
+            if let Err(err) = jgenesis_common::files::write_atomic(&self.config_path, config_str) {
                 log::error!("Error serializing app config: {err}");
             }
+            self.config_modified = config_modified_time(&self.config_path);
         }
 
         self.state.rendered_first_frame = true;
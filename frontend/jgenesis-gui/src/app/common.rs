@@ -1,12 +1,44 @@
 mod helptext;
 
-use crate::app::{App, NumericTextEdit, OpenWindow};
+use crate::app::{App, HelpText, NumericTextEdit, OpenWindow, REQUIRES_RESTART};
+use crate::emuthread::EmuThreadCommand;
 use eframe::epaint::Color32;
-use egui::{Context, Slider, Window};
-use jgenesis_native_driver::config::FullscreenMode;
-use jgenesis_renderer::config::{FilterMode, PreprocessShader, Scanlines, VSyncMode, WgpuBackend};
+use egui::{Button, Context, Grid, Slider, Window};
+use jgenesis_native_driver::config::{FullscreenMode, InputViewerSkin, SyncMode};
+use jgenesis_renderer::config::{
+    FilterMode, FrameInsertion, OverlayPosition, PreprocessShader, RefreshMultiplier, Scanlines,
+    VSyncMode, WgpuBackend,
+};
 use std::num::NonZeroU32;
 
+// Settings that can be found by searching the command palette
+pub(super) const SEARCHABLE_SETTINGS: &[(OpenWindow, HelpText)] = &[
+    (OpenWindow::CommonVideo, helptext::FULLSCREEN),
+    (OpenWindow::CommonVideo, helptext::FULLSCREEN_MODE),
+    (OpenWindow::CommonVideo, helptext::WGPU_BACKEND),
+    (OpenWindow::CommonVideo, helptext::FILTER_MODE),
+    (OpenWindow::CommonVideo, helptext::PREPROCESS_SHADER),
+    (OpenWindow::CommonVideo, helptext::SCANLINES),
+    (OpenWindow::CommonVideo, helptext::PRESCALING),
+    (OpenWindow::CommonVideo, helptext::INTEGER_HEIGHT_SCALING),
+    (OpenWindow::CommonVideo, helptext::FRAME_INSERTION),
+    (OpenWindow::CommonVideo, helptext::INPUT_VIEWER),
+    (OpenWindow::CommonAudio, helptext::AUDIO_SAMPLE_RATE),
+    (OpenWindow::CommonAudio, helptext::AUDIO_GAIN),
+    (OpenWindow::CommonAudio, helptext::MASTER_VOLUME),
+    (OpenWindow::CommonAudio, helptext::VOLUME_OFFSETS),
+    (OpenWindow::CommonAudio, helptext::AUDIO_OUTPUT_DEVICE),
+    (OpenWindow::Synchronization, helptext::SYNC_MODE),
+    (OpenWindow::Synchronization, helptext::VSYNC_MODE),
+    (OpenWindow::Synchronization, helptext::FRAME_TIME_SYNC),
+    (OpenWindow::Synchronization, helptext::AUDIO_SYNC),
+    (OpenWindow::Synchronization, helptext::AUDIO_DYNAMIC_RESAMPLING),
+    (OpenWindow::Synchronization, helptext::ROUND_FRAME_RATE),
+    (OpenWindow::Synchronization, helptext::DECOUPLED_FRAME_PRESENTATION),
+    (OpenWindow::Synchronization, helptext::AUDIO_BUFFER_SIZE),
+    (OpenWindow::Synchronization, helptext::AUDIO_HARDWARE_QUEUE_SIZE),
+];
+
 impl App {
     pub(super) fn render_common_video_settings(&mut self, ctx: &Context) {
         const WINDOW: OpenWindow = OpenWindow::CommonVideo;
@@ -33,7 +65,7 @@ impl App {
 
             let rect = ui.group(|ui| {
                 ui.add_enabled_ui(!self.emu_thread.status().is_running(), |ui| {
-                    ui.label("wgpu backend");
+                    ui.label("wgpu backend").on_disabled_hover_text(REQUIRES_RESTART);
                     ui.horizontal(|ui| {
                         ui.radio_value(&mut self.config.common.wgpu_backend, WgpuBackend::Auto, "Auto");
                         ui.radio_value(
@@ -170,6 +202,52 @@ impl App {
                 self.state.help_text.insert(WINDOW, helptext::INTEGER_HEIGHT_SCALING);
             }
 
+            let rect = ui.group(|ui| {
+                ui.label("Frame insertion");
+
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.config.common.frame_insertion, FrameInsertion::None, "None");
+                    ui.radio_value(&mut self.config.common.frame_insertion, FrameInsertion::BlackFrames, "Black frames");
+                    ui.radio_value(&mut self.config.common.frame_insertion, FrameInsertion::DuplicateFrames, "Duplicate frames");
+                });
+
+                ui.add_enabled_ui(self.config.common.frame_insertion != FrameInsertion::None, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Display refresh rate:");
+
+                        ui.radio_value(&mut self.config.common.refresh_multiplier, RefreshMultiplier::Double, "2x (120Hz)");
+                        ui.radio_value(&mut self.config.common.refresh_multiplier, RefreshMultiplier::Triple, "3x (180Hz)");
+                        ui.radio_value(&mut self.config.common.refresh_multiplier, RefreshMultiplier::Quadruple, "4x (240Hz)");
+                    });
+                });
+            }).response.interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::FRAME_INSERTION);
+            }
+
+            let rect = ui.group(|ui| {
+                ui.checkbox(&mut self.config.common.input_viewer_enabled, "Show input viewer");
+
+                ui.add_enabled_ui(self.config.common.input_viewer_enabled, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Position:");
+                        ui.radio_value(&mut self.config.common.input_viewer_position, OverlayPosition::TopLeft, "Top left");
+                        ui.radio_value(&mut self.config.common.input_viewer_position, OverlayPosition::TopRight, "Top right");
+                        ui.radio_value(&mut self.config.common.input_viewer_position, OverlayPosition::BottomLeft, "Bottom left");
+                        ui.radio_value(&mut self.config.common.input_viewer_position, OverlayPosition::BottomRight, "Bottom right");
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Style:");
+                        ui.radio_value(&mut self.config.common.input_viewer_skin, InputViewerSkin::Compact, "Compact");
+                        ui.radio_value(&mut self.config.common.input_viewer_skin, InputViewerSkin::Names, "Button names");
+                    });
+                });
+            }).response.interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::INPUT_VIEWER);
+            }
+
             if self.state.display_scanlines_warning {
                 ui.colored_label(Color32::RED, "Integer height scaling + even-numbered prescale factor strongly recommended when scanlines are enabled");
             }
@@ -235,6 +313,84 @@ impl App {
                 ui.colored_label(Color32::RED, "Audio gain must be a finite decimal number");
             }
 
+            ui.add_space(10.0);
+
+            let rect = ui
+                .horizontal(|ui| {
+                    ui.add(
+                        Slider::new(&mut self.config.common.audio_volume_percent, 0..=100)
+                            .suffix("%"),
+                    );
+                    ui.label("Master volume");
+
+                    ui.checkbox(&mut self.config.common.audio_muted, "Mute");
+                })
+                .response
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::MASTER_VOLUME);
+            }
+
+            ui.add_space(10.0);
+
+            let rect = ui
+                .group(|ui| {
+                    ui.label("Per-system volume offset (dB)");
+
+                    Grid::new("volume_offsets").spacing([10.0, 5.0]).show(ui, |ui| {
+                        for (label, volume_offset_db) in [
+                            ("SMS / Game Gear", &mut self.config.smsgg.volume_offset_db),
+                            ("Genesis", &mut self.config.genesis.volume_offset_db),
+                            ("Sega CD", &mut self.config.sega_cd.volume_offset_db),
+                            ("32X", &mut self.config.sega_32x.volume_offset_db),
+                            ("NES", &mut self.config.nes.volume_offset_db),
+                            ("SNES", &mut self.config.snes.volume_offset_db),
+                            ("Game Boy", &mut self.config.game_boy.volume_offset_db),
+                        ] {
+                            ui.label(label);
+                            ui.add(Slider::new(volume_offset_db, -20..=20));
+                            ui.end_row();
+                        }
+                    });
+                })
+                .response
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::VOLUME_OFFSETS);
+            }
+
+            ui.add_space(10.0);
+
+            let rect = ui
+                .group(|ui| {
+                    ui.label("Output device");
+
+                    let device_info = self.emu_thread.audio_device_info();
+                    let status = match device_info {
+                        Some(info) if info.connected => {
+                            format!("Default device ({}, {} Hz)", info.driver, info.frequency)
+                        }
+                        Some(info) => format!("Disconnected, retrying ({})", info.driver),
+                        None => "(no emulator running)".into(),
+                    };
+                    ui.label(status);
+
+                    if ui
+                        .add_enabled(
+                            device_info.is_some(),
+                            Button::new("Re-initialize audio device"),
+                        )
+                        .clicked()
+                    {
+                        self.emu_thread.send(EmuThreadCommand::ReinitAudio);
+                    }
+                })
+                .response
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::AUDIO_OUTPUT_DEVICE);
+            }
+
             self.render_help_text(ui, WINDOW);
         });
         if !open {
@@ -251,6 +407,27 @@ impl App {
 
         let mut open = true;
         Window::new("Synchronization Settings").open(&mut open).show(ctx, |ui| {
+            let rect = ui.group(|ui| {
+                ui.label("Sync mode");
+
+                ui.horizontal(|ui| {
+                    for (sync_mode, label) in [
+                        (SyncMode::Custom, "Custom"),
+                        (SyncMode::Audio, "Audio"),
+                        (SyncMode::Video, "Video"),
+                        (SyncMode::AudioAndVideo, "Audio + Video"),
+                        (SyncMode::Uncapped, "Uncapped"),
+                    ] {
+                        ui.radio_value(&mut self.config.common.sync_mode, sync_mode, label);
+                    }
+                });
+            }).response.interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::SYNC_MODE);
+            }
+
+            ui.add_space(10.0);
+
             let rect = ui.group(|ui| {
                 ui.add_enabled_ui(self.config.common.wgpu_backend != WgpuBackend::OpenGl, |ui| {
                     let disabled_text = "VSync mode is not configurable with the OpenGL backend";
@@ -279,23 +456,40 @@ impl App {
 
             ui.add_space(10.0);
 
-            let rect = ui.checkbox(&mut self.config.common.frame_time_sync, "Frame time sync enabled").interact_rect;
+            let custom_sync = self.config.common.sync_mode == SyncMode::Custom;
+            ui.add_enabled_ui(custom_sync, |ui| {
+                let rect = ui.checkbox(&mut self.config.common.frame_time_sync, "Frame time sync enabled").interact_rect;
+                if ui.rect_contains_pointer(rect) {
+                    self.state.help_text.insert(WINDOW, helptext::FRAME_TIME_SYNC);
+                }
+
+                let rect = ui.checkbox(&mut self.config.common.audio_sync, "Audio sync enabled").interact_rect;
+                if ui.rect_contains_pointer(rect) {
+                    self.state.help_text.insert(WINDOW, helptext::AUDIO_SYNC);
+                }
+
+                let rect = ui.checkbox(&mut self.config.common.audio_dynamic_resampling_ratio, "Audio dynamic resampling ratio enabled")
+                    .interact_rect;
+                if ui.rect_contains_pointer(rect) {
+                    self.state.help_text.insert(WINDOW, helptext::AUDIO_DYNAMIC_RESAMPLING);
+                }
+            });
+
+            let rect = ui.checkbox(&mut self.config.common.round_frame_rate, "Round frame rate to 60Hz (NTSC) / 50Hz (PAL)").interact_rect;
             if ui.rect_contains_pointer(rect) {
-                self.state.help_text.insert(WINDOW, helptext::FRAME_TIME_SYNC);
+                self.state.help_text.insert(WINDOW, helptext::ROUND_FRAME_RATE);
             }
 
-            let rect = ui.checkbox(&mut self.config.common.audio_sync, "Audio sync enabled").interact_rect;
-            if ui.rect_contains_pointer(rect) {
-                self.state.help_text.insert(WINDOW, helptext::AUDIO_SYNC);
-            }
-
-            let rect = ui.checkbox(&mut self.config.common.audio_dynamic_resampling_ratio, "Audio dynamic resampling ratio enabled")
-                .interact_rect;
+            let rect = ui.checkbox(&mut self.config.common.decoupled_frame_presentation, "Decouple frame presentation from emulation").interact_rect;
             if ui.rect_contains_pointer(rect) {
-                self.state.help_text.insert(WINDOW, helptext::AUDIO_DYNAMIC_RESAMPLING);
+                self.state.help_text.insert(WINDOW, helptext::DECOUPLED_FRAME_PRESENTATION);
             }
 
-            let any_sync_enabled = self.config.common.vsync_mode == VSyncMode::Enabled || self.config.common.audio_sync || self.config.common.frame_time_sync;
+            let any_sync_enabled = match self.config.common.sync_mode {
+                SyncMode::Custom => self.config.common.vsync_mode == VSyncMode::Enabled || self.config.common.audio_sync || self.config.common.frame_time_sync,
+                SyncMode::Audio | SyncMode::Video | SyncMode::AudioAndVideo => true,
+                SyncMode::Uncapped => false,
+            };
             if !any_sync_enabled {
                 ui.add_space(5.0);
                 ui.colored_label(Color32::RED, "No sync enabled; emulator will run at uncapped speed");
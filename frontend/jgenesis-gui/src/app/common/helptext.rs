@@ -20,6 +20,15 @@ pub const WGPU_BACKEND: HelpText = HelpText {
     ],
 };
 
+pub const SYNC_MODE: HelpText = HelpText {
+    heading: "Sync Mode",
+    text: &[
+        "Choose how emulation speed is synchronized. Custom uses the individual VSync, frame time sync, and audio settings below. The video sync modes use Fast VSync if it is selected and regular VSync otherwise.",
+        "Audio sync blocks on the audio buffer and disables VSync, which gives the most accurate emulation speed. Video sync uses VSync and drops audio samples if the audio buffer overflows, which may give smoother video on displays with a refresh rate matching the emulated console.",
+        "Audio + Video uses VSync and audio sync together, with dynamic audio resampling to avoid audio buffer underflow and overflow. Uncapped disables all synchronization.",
+    ],
+};
+
 pub const VSYNC_MODE: HelpText = HelpText {
     heading: "VSync Mode",
     text: &[
@@ -68,6 +77,22 @@ pub const INTEGER_HEIGHT_SCALING: HelpText = HelpText {
     ],
 };
 
+pub const FRAME_INSERTION: HelpText = HelpText {
+    heading: "Frame Insertion",
+    text: &[
+        "Present extra frames between emulated frames on displays that refresh at a multiple of the console's frame rate. Black frames reduce sample-and-hold motion blur at the cost of brightness, similar to a CRT.",
+        "Requires VSync to be enabled. Frame insertion is automatically disabled if the display does not refresh at least as fast as the selected multiple of the content frame rate (e.g. 120Hz for 60Hz content), and while fast forwarding.",
+    ],
+};
+
+pub const INPUT_VIEWER: HelpText = HelpText {
+    heading: "Input Viewer",
+    text: &[
+        "If enabled, the current state of each player's controller is displayed in a corner of the emulator window.",
+        "Compact style shows every button in a fixed position, using a distinct symbol for each button when it is pressed (arrows for the D-pad, a letter or number for other buttons). Button names style lists only the buttons that are currently pressed.",
+    ],
+};
+
 pub const AUDIO_SAMPLE_RATE: HelpText = HelpText {
     heading: "Audio Sample Rate",
     text: &[
@@ -76,6 +101,14 @@ pub const AUDIO_SAMPLE_RATE: HelpText = HelpText {
     ],
 };
 
+pub const AUDIO_OUTPUT_DEVICE: HelpText = HelpText {
+    heading: "Audio Output Device",
+    text: &[
+        "Audio always plays on the system default output device. If the device disconnects, the emulator keeps running and automatically reopens audio on the new default device.",
+        "Re-initializing the audio device manually can help after switching output devices if audio does not follow the switch.",
+    ],
+};
+
 pub const FRAME_TIME_SYNC: HelpText = HelpText {
     heading: "Frame Time Sync",
     text: &[
@@ -84,6 +117,22 @@ pub const FRAME_TIME_SYNC: HelpText = HelpText {
     ],
 };
 
+pub const DECOUPLED_FRAME_PRESENTATION: HelpText = HelpText {
+    heading: "Decoupled Frame Presentation",
+    text: &[
+        "If enabled, completed frames are queued and presented at evenly spaced times instead of as soon as the emulator finishes them. This can smooth out FMV playback in Sega CD games on displays whose refresh rate does not match the emulated system's, especially with audio sync.",
+        "Adds up to one frame of video latency. Works best with VSync disabled or set to Fast.",
+    ],
+};
+
+pub const ROUND_FRAME_RATE: HelpText = HelpText {
+    heading: "Round Frame Rate",
+    text: &[
+        "If enabled, pace frames at exactly 60Hz for NTSC games and exactly 50Hz for PAL games instead of at the emulated system's native framerate, which is always slightly off from those rates.",
+        "Audio is resampled to compensate, so audio sync stays stable. This can improve frame pacing with VSync or on 50Hz/60Hz and variable refresh rate displays, at the cost of running very slightly faster or slower than actual hardware.",
+    ],
+};
+
 pub const AUDIO_SYNC: HelpText = HelpText {
     heading: "Audio Sync",
     text: &[
@@ -128,3 +177,19 @@ pub const AUDIO_GAIN: HelpText = HelpText {
         "Setting this too high can cause audio distortion.",
     ],
 };
+
+pub const MASTER_VOLUME: HelpText = HelpText {
+    heading: "Master Volume",
+    text: &[
+        "Scale the final audio output for every system, applied after gain.",
+        "Mute silences audio output entirely. Audio can also be muted and unmuted while a game is running using the mute hotkey.",
+    ],
+};
+
+pub const VOLUME_OFFSETS: HelpText = HelpText {
+    heading: "Per-System Volume Offset",
+    text: &[
+        "Adjust the volume of individual systems relative to the others, in decibels.",
+        "This is useful for evening out systems that are noticeably louder or quieter than others.",
+    ],
+};
@@ -1,10 +1,20 @@
 mod helptext;
 
-use crate::app::{App, OpenWindow};
+use crate::app::{App, HelpText, OpenWindow, REQUIRES_RESTART};
 use crate::emuthread::EmuThreadStatus;
 use egui::{Context, Ui, Window};
 use gb_core::api::{GbAspectRatio, GbPalette, GbcColorCorrection};
 
+// Settings that can be found by searching the command palette
+pub(super) const SEARCHABLE_SETTINGS: &[(OpenWindow, HelpText)] = &[
+    (OpenWindow::GameBoyGeneral, helptext::FORCE_DMG_MODE),
+    (OpenWindow::GameBoyGeneral, helptext::PRETEND_GBA_MODE),
+    (OpenWindow::GameBoyGeneral, helptext::AUDIO_TIMING_HACK),
+    (OpenWindow::GameBoyVideo, helptext::ASPECT_RATIO),
+    (OpenWindow::GameBoyVideo, helptext::GB_COLOR_PALETTE),
+    (OpenWindow::GameBoyVideo, helptext::GBC_COLOR_CORRECTION),
+];
+
 impl App {
     pub(super) fn render_gb_general_settings(&mut self, ctx: &Context) {
         const WINDOW: OpenWindow = OpenWindow::GameBoyGeneral;
@@ -23,6 +33,7 @@ impl App {
                             &mut self.config.game_boy.force_dmg_mode,
                             "Force DMG mode in software with CGB support",
                         )
+                        .on_disabled_hover_text(REQUIRES_RESTART)
                         .interact_rect;
                     if ui.rect_contains_pointer(rect) {
                         self.state.help_text.insert(WINDOW, helptext::FORCE_DMG_MODE);
@@ -33,6 +44,7 @@ impl App {
                             &mut self.config.game_boy.pretend_to_be_gba,
                             "Pretend to be a Game Boy Advance",
                         )
+                        .on_disabled_hover_text(REQUIRES_RESTART)
                         .interact_rect;
                     if ui.rect_contains_pointer(rect) {
                         self.state.help_text.insert(WINDOW, helptext::PRETEND_GBA_MODE);
@@ -1,9 +1,9 @@
 mod helptext;
 
-use crate::app::{App, Console, OpenWindow};
+use crate::app::{App, Console, HelpText, OpenWindow, REQUIRES_RESTART};
 use crate::emuthread::EmuThreadStatus;
-use crate::widgets::OverclockSlider;
-use egui::{Context, Window};
+use crate::widgets::{ClockScaling, OverclockSlider};
+use egui::{Context, Slider, Window};
 use genesis_core::{GenesisAspectRatio, GenesisLowPassFilter, GenesisRegion};
 use jgenesis_common::frontend::TimingMode;
 use rfd::FileDialog;
@@ -12,6 +12,39 @@ use segacd_core::api::{PcmInterpolation, PcmLowPassFilter};
 use std::num::{NonZeroU16, NonZeroU64};
 use std::path::PathBuf;
 
+// Settings that can be found by searching the command palette
+pub(super) const SEARCHABLE_SETTINGS: &[(OpenWindow, HelpText)] = &[
+    (OpenWindow::GenesisGeneral, helptext::TIMING_MODE),
+    (OpenWindow::GenesisGeneral, helptext::REGION),
+    (OpenWindow::GenesisGeneral, helptext::SCD_BIOS_PATH),
+    (OpenWindow::GenesisGeneral, helptext::SCD_RAM_CARTRIDGE),
+    (OpenWindow::GenesisGeneral, helptext::SCD_BACKUP_RAM_WRITE_PROTECT),
+    (OpenWindow::GenesisGeneral, helptext::SCD_CDROM_IN_RAM),
+    (OpenWindow::GenesisGeneral, helptext::SCD_DISC_CACHE),
+    (OpenWindow::GenesisGeneral, helptext::SCD_FAST_BOOT),
+    (OpenWindow::GenesisGeneral, helptext::M68K_CLOCK_DIVIDER),
+    (OpenWindow::GenesisGeneral, helptext::SCD_SUB_CPU_DIVIDER),
+    (OpenWindow::GenesisGeneral, helptext::SCD_DRIVE_SPEED),
+    (OpenWindow::GenesisVideo, helptext::ASPECT_RATIO),
+    (OpenWindow::GenesisVideo, helptext::DEINTERLACING),
+    (OpenWindow::GenesisVideo, helptext::DOUBLE_SCREEN_INTERLACED_ASPECT),
+    (OpenWindow::GenesisVideo, helptext::REMOVE_SPRITE_LIMITS),
+    (OpenWindow::GenesisVideo, helptext::NON_LINEAR_COLOR_DAC),
+    (OpenWindow::GenesisVideo, helptext::EXPANDED_VRAM),
+    (OpenWindow::GenesisVideo, helptext::RENDER_BORDERS),
+    (OpenWindow::GenesisVideo, helptext::NTSC_COMPOSITE_FILTER),
+    (OpenWindow::GenesisVideo, helptext::ENABLED_LAYERS),
+    (OpenWindow::GenesisVideo, helptext::S32X_VIDEO_OUT),
+    (OpenWindow::GenesisAudio, helptext::QUANTIZE_YM2612_OUTPUT),
+    (OpenWindow::GenesisAudio, helptext::YM2612_LADDER_EFFECT),
+    (OpenWindow::GenesisAudio, helptext::GENESIS_LOW_PASS),
+    (OpenWindow::GenesisAudio, helptext::PCM_LOW_PASS),
+    (OpenWindow::GenesisAudio, helptext::SCD_GEN_LOW_PASS),
+    (OpenWindow::GenesisAudio, helptext::S32X_GEN_LOW_PASS),
+    (OpenWindow::GenesisAudio, helptext::SCD_PCM_INTERPOLATION),
+    (OpenWindow::GenesisAudio, helptext::SOUND_SOURCES),
+];
+
 impl App {
     pub(super) fn render_genesis_general_settings(&mut self, ctx: &Context) {
         const WINDOW: OpenWindow = OpenWindow::GenesisGeneral;
@@ -26,7 +59,7 @@ impl App {
             let rect = ui
                 .group(|ui| {
                     ui.add_enabled_ui(!running_genesis, |ui| {
-                        ui.label("Timing / display mode");
+                        ui.label("Timing / display mode").on_disabled_hover_text(REQUIRES_RESTART);
 
                         ui.horizontal(|ui| {
                             ui.radio_value(
@@ -56,7 +89,7 @@ impl App {
             let rect = ui
                 .group(|ui| {
                     ui.add_enabled_ui(!running_genesis, |ui| {
-                        ui.label("Region");
+                        ui.label("Region").on_disabled_hover_text(REQUIRES_RESTART);
 
                         ui.horizontal(|ui| {
                             ui.radio_value(&mut self.config.genesis.forced_region, None, "Auto");
@@ -102,7 +135,7 @@ impl App {
                                 }
                             }
 
-                            ui.label("Sega CD BIOS path");
+                            ui.label("Sega CD BIOS path").on_disabled_hover_text(REQUIRES_RESTART);
                         },
                     );
                 })
@@ -123,6 +156,17 @@ impl App {
                 self.state.help_text.insert(WINDOW, helptext::SCD_RAM_CARTRIDGE);
             }
 
+            ui.add_space(5.0);
+            let rect = ui
+                .checkbox(
+                    &mut self.config.sega_cd.backup_ram_write_protected,
+                    "(Sega CD) Write-protect internal backup RAM",
+                )
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::SCD_BACKUP_RAM_WRITE_PROTECT);
+            }
+
             ui.add_space(5.0);
             let rect = ui
                 .checkbox(
@@ -134,6 +178,49 @@ impl App {
                 self.state.help_text.insert(WINDOW, helptext::SCD_CDROM_IN_RAM);
             }
 
+            ui.add_space(5.0);
+            let rect = ui
+                .add_enabled_ui(!self.config.sega_cd.load_disc_into_ram, |ui| {
+                    ui.checkbox(
+                        &mut self.config.sega_cd.disc_cache_enabled,
+                        "(Sega CD) Cache CD-ROM sectors in host RAM",
+                    );
+
+                    ui.add_enabled_ui(self.config.sega_cd.disc_cache_enabled, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                Slider::new(&mut self.config.sega_cd.disc_cache_size_mb, 1..=256)
+                                    .suffix(" MB"),
+                            );
+                            ui.label("Cache size");
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.add(Slider::new(
+                                &mut self.config.sega_cd.disc_prefetch_sectors,
+                                0..=150,
+                            ));
+                            ui.label("Sectors to read ahead");
+                        });
+                    });
+                })
+                .response
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::SCD_DISC_CACHE);
+            }
+
+            ui.add_space(5.0);
+            let rect = ui
+                .checkbox(
+                    &mut self.config.sega_cd.fast_boot,
+                    "(Sega CD) Fast-forward through BIOS boot sequence",
+                )
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::SCD_FAST_BOOT);
+            }
+
             ui.add_space(5.0);
             ui.horizontal(|ui| {
                 ui.vertical(|ui| {
@@ -143,7 +230,8 @@ impl App {
                             current_value: &mut self.config.genesis.m68k_clock_divider,
                             range: 1..=7,
                             master_clock: genesis_core::audio::NTSC_GENESIS_MCLK_FREQUENCY,
-                            default_divider: genesis_core::timing::NATIVE_M68K_DIVIDER as f64,
+                            native_value: genesis_core::timing::NATIVE_M68K_DIVIDER as f64,
+                            scaling: ClockScaling::Divider,
                         })
                         .interact_rect;
                     if ui.rect_contains_pointer(rect) {
@@ -158,7 +246,8 @@ impl App {
                             current_value: &mut self.config.sega_cd.sub_cpu_divider,
                             range: NonZeroU64::new(1).unwrap()..=NonZeroU64::new(4).unwrap(),
                             master_clock: segacd_core::api::SEGA_CD_MASTER_CLOCK_RATE as f64,
-                            default_divider: segacd_core::api::DEFAULT_SUB_CPU_DIVIDER as f64,
+                            native_value: segacd_core::api::DEFAULT_SUB_CPU_DIVIDER as f64,
+                            scaling: ClockScaling::Divider,
                         })
                         .interact_rect;
                     if ui.rect_contains_pointer(rect) {
@@ -238,6 +327,12 @@ impl App {
                             "Square pixels",
                         )
                         .on_hover_text("1:1 pixel aspect ratio");
+                        ui.radio_value(
+                            &mut self.config.genesis.aspect_ratio,
+                            GenesisAspectRatio::SquarePixelsH320,
+                            "Square pixels (H320px)",
+                        )
+                        .on_hover_text("1:1 pixel aspect ratio in 320px mode, 5:4 in 256px mode");
                         ui.radio_value(
                             &mut self.config.genesis.aspect_ratio,
                             GenesisAspectRatio::Stretched,
@@ -291,6 +386,13 @@ impl App {
                 self.state.help_text.insert(WINDOW, helptext::NON_LINEAR_COLOR_DAC);
             }
 
+            let rect = ui
+                .checkbox(&mut self.config.genesis.expanded_vram, "Emulate 128KB VRAM expansion")
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::EXPANDED_VRAM);
+            }
+
             let rect = ui
                 .checkbox(&mut self.config.genesis.render_vertical_border, "Render vertical border")
                 .interact_rect;
@@ -308,6 +410,13 @@ impl App {
                 self.state.help_text.insert(WINDOW, helptext::RENDER_BORDERS);
             }
 
+            let rect = ui
+                .checkbox(&mut self.config.genesis.ntsc_composite_filter, "NTSC composite filter")
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::NTSC_COMPOSITE_FILTER);
+            }
+
             ui.add_space(5.0);
 
             let rect = ui
@@ -29,6 +29,14 @@ pub const SCD_RAM_CARTRIDGE: HelpText = HelpText {
     ],
 };
 
+pub const SCD_BACKUP_RAM_WRITE_PROTECT: HelpText = HelpText {
+    heading: "Sega CD Backup RAM Write Protection",
+    text: &[
+        "If enabled, all writes to the console's 8KB of builtin backup RAM are ignored, which prevents games from overwriting or deleting existing saves.",
+        "The RAM cartridge is not affected by this setting.",
+    ],
+};
+
 pub const SCD_CDROM_IN_RAM: HelpText = HelpText {
     heading: "Load CD-ROM Images into RAM",
     text: &[
@@ -37,6 +45,22 @@ pub const SCD_CDROM_IN_RAM: HelpText = HelpText {
     ],
 };
 
+pub const SCD_DISC_CACHE: HelpText = HelpText {
+    heading: "CD-ROM Sector Cache",
+    text: &[
+        "If enabled and CD-ROM images are not loaded into RAM, keep recently read sectors in host RAM, and read ahead the specified number of sectors every time a sector is not already cached.",
+        "This reduces stuttering when the image is stored on a slow hard drive or a network share, without the memory cost of loading the entire image into RAM.",
+    ],
+};
+
+pub const SCD_FAST_BOOT: HelpText = HelpText {
+    heading: "Sega CD Fast Boot",
+    text: &[
+        "If enabled, run at high speed through the BIOS boot animation and the Sega license screen, until the game's own code starts running.",
+        "This only speeds up emulation; the BIOS still runs normally, so it should not affect compatibility.",
+    ],
+};
+
 pub const M68K_CLOCK_DIVIDER: HelpText = HelpText {
     heading: "Genesis 68000 Clock Divider",
     text: &[
@@ -69,6 +93,7 @@ pub const ASPECT_RATIO: HelpText = HelpText {
         "Configure aspect ratio.",
         "NTSC - 8:7 pixel aspect ratio in H256px mode, 32:35 pixel aspect ratio in H320px mode",
         "PAL - 11:8 pixel aspect ratio in H256px mode, 11:10 pixel aspect ratio in H320px mode",
+        "Square pixels (H320px) - 1:1 pixel aspect ratio in H320px mode, 5:4 pixel aspect ratio in H256px mode so that the image stays the same width when games switch modes",
     ],
 };
 
@@ -104,6 +129,14 @@ pub const NON_LINEAR_COLOR_DAC: HelpText = HelpText {
     ],
 };
 
+pub const EXPANDED_VRAM: HelpText = HelpText {
+    heading: "128KB VRAM Expansion",
+    text: &[
+        "If enabled, emulate 128KB of VRAM instead of the standard 64KB, as in the Tera Drive and development hardware.",
+        "The extra VRAM is only accessible when software enables the VDP's 128KB mode. Retail games do not use it, so this should normally be left disabled.",
+    ],
+};
+
 pub const RENDER_BORDERS: HelpText = HelpText {
     heading: "Render Border",
     text: &[
@@ -112,6 +145,15 @@ pub const RENDER_BORDERS: HelpText = HelpText {
     ],
 };
 
+pub const NTSC_COMPOSITE_FILTER: HelpText = HelpText {
+    heading: "NTSC Composite Filter",
+    text: &[
+        "If enabled, simulate the color bleed and blur of NTSC composite video output.",
+        "Many games rely on composite video to blend dithered graphics into transparency effects, such as the waterfalls in Sonic the Hedgehog.",
+        "This filter is applied before the preprocess shader and only affects Genesis, Sega CD, and 32X games.",
+    ],
+};
+
 pub const ENABLED_LAYERS: HelpText = HelpText {
     heading: "Enabled Layers",
     text: &[
@@ -124,6 +166,8 @@ pub const S32X_VIDEO_OUT: HelpText = HelpText {
     heading: "32X Video Output",
     text: &[
         "Configure 32X video frame composition, optionally displaying only the Genesis VDP output or only the 32X VDP output.",
+        "In combined mode, each 32X pixel is displayed either in front of or behind the Genesis VDP output depending on the pixel's priority bit and the 32X priority register. Genesis VDP pixels that show only the backdrop color are always covered by the 32X frame.",
+        "The single-VDP modes are mainly useful for debugging. 32X VDP only mode ignores priority and blacks out the Genesis VDP output entirely, including the borders.",
     ],
 };
 
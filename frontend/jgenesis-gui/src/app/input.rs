@@ -7,10 +7,10 @@ use genesis_core::input::GenesisButton;
 use jgenesis_common::input::Player;
 use jgenesis_native_config::input::InputAppConfig;
 use jgenesis_native_driver::config::input::{
-    GameBoyInputMapping, GenesisControllerMapping, GenesisInputMapping, HotkeyMapping,
-    NesControllerMapping, NesControllerType, NesInputMapping, NesZapperMapping,
-    SmsGgControllerMapping, SmsGgInputMapping, SnesControllerMapping, SnesControllerType,
-    SnesInputMapping, SnesSuperScopeMapping,
+    AnalogInputConfig, AxisMode, AxisResponseCurve, GameBoyInputMapping, GenesisControllerMapping,
+    GenesisInputMapping, HotkeyMapping, NesControllerMapping, NesControllerType, NesInputMapping,
+    NesZapperMapping, SmsGgControllerMapping, SmsGgControllerType, SmsGgInputMapping,
+    SnesControllerMapping, SnesControllerType, SnesInputMapping, SnesSuperScopeMapping,
 };
 use jgenesis_native_driver::input::{GenericInput, Hotkey};
 use nes_core::input::NesButton;
@@ -213,6 +213,8 @@ fn hotkey_label(hotkey: Hotkey) -> &'static str {
         LoadState => "Load state from current slot:",
         NextSaveStateSlot => "Next save state slot:",
         PrevSaveStateSlot => "Previous save state slot:",
+        UndoLoadState => "Undo last state load:",
+        UndoSaveState => "Undo last state save:",
         SoftReset => "Soft reset:",
         HardReset => "Hard reset:",
         Pause => "Pause:",
@@ -221,6 +223,9 @@ fn hotkey_label(hotkey: Hotkey) -> &'static str {
         Rewind => "Rewind:",
         ToggleOverclocking => "Toggle overclocking enabled:",
         OpenDebugger => "Open memory viewer:",
+        SwapPlayerInputs => "Swap P1/P2 inputs:",
+        ToggleMute => "Toggle audio mute:",
+        TogglePerformanceHud => "Toggle performance HUD:",
         SaveStateSlot0 => "Save state to slot 0:",
         SaveStateSlot1 => "Save state to slot 1:",
         SaveStateSlot2 => "Save state to slot 2:",
@@ -412,6 +417,8 @@ fn access_hotkey(
         LoadState => &mut mapping_config.load_state,
         NextSaveStateSlot => &mut mapping_config.next_save_state_slot,
         PrevSaveStateSlot => &mut mapping_config.prev_save_state_slot,
+        UndoLoadState => &mut mapping_config.undo_load_state,
+        UndoSaveState => &mut mapping_config.undo_save_state,
         SoftReset => &mut mapping_config.soft_reset,
         HardReset => &mut mapping_config.hard_reset,
         Pause => &mut mapping_config.pause,
@@ -420,6 +427,9 @@ fn access_hotkey(
         Rewind => &mut mapping_config.rewind,
         ToggleOverclocking => &mut mapping_config.toggle_overclocking,
         OpenDebugger => &mut mapping_config.open_debugger,
+        SwapPlayerInputs => &mut mapping_config.swap_player_inputs,
+        ToggleMute => &mut mapping_config.toggle_mute,
+        TogglePerformanceHud => &mut mapping_config.toggle_performance_hud,
         SaveStateSlot0 => &mut mapping_config.save_state_slot_0,
         SaveStateSlot1 => &mut mapping_config.save_state_slot_1,
         SaveStateSlot2 => &mut mapping_config.save_state_slot_2,
@@ -447,10 +457,121 @@ impl App {
     pub(super) fn render_general_input_settings(&mut self, ctx: &Context) {
         let mut open = true;
         Window::new("General Input Settings").open(&mut open).show(ctx, |ui| {
-            ui.horizontal(|ui| {
-                ui.label("Gamepad joystick axis deadzone:");
-                ui.add(Slider::new(&mut self.config.input.axis_deadzone, 0..=i16::MAX));
+            ui.heading("Gamepad Axes");
+
+            Grid::new("gamepad_axes").spacing([10.0, 5.0]).show(ui, |ui| {
+                ui.label("Axis");
+                ui.label("Mode");
+                ui.label("Deadzone");
+                ui.label("Saturation");
+                ui.label("Response curve");
+                ui.label("Digital threshold");
+                ui.end_row();
+
+                for (axis_idx, axis) in self.config.input.analog.axes.iter_mut().enumerate() {
+                    ui.label(axis_idx.to_string());
+
+                    ComboBox::new(format!("axis_mode_{axis_idx}"), "")
+                        .selected_text(axis.mode.to_string())
+                        .show_ui(ui, |ui| {
+                            for mode in AxisMode::ALL {
+                                ui.selectable_value(&mut axis.mode, mode, mode.to_string());
+                            }
+                        });
+
+                    ui.add(Slider::new(&mut axis.deadzone, 0..=i16::MAX));
+                    ui.add(Slider::new(&mut axis.saturation, 0..=i16::MAX));
+
+                    ComboBox::new(format!("axis_curve_{axis_idx}"), "")
+                        .selected_text(axis.response_curve.to_string())
+                        .show_ui(ui, |ui| {
+                            for curve in AxisResponseCurve::ALL {
+                                ui.selectable_value(
+                                    &mut axis.response_curve,
+                                    curve,
+                                    curve.to_string(),
+                                );
+                            }
+                        });
+
+                    ui.add(Slider::new(&mut axis.digital_threshold, 0..=100).suffix("%"));
+                    ui.end_row();
+                }
             });
+
+            if ui.button("Restore Axis Defaults").clicked() {
+                self.config.input.analog = AnalogInputConfig::default();
+            }
+
+            ui.add_space(10.0);
+
+            ui.heading("Gamepad Assignments");
+
+            let assignments = self.emu_thread.gamepad_assignments();
+            if !self.emu_thread.status().is_running() {
+                ui.label("Connected gamepads are only listed while a game is running.");
+            }
+
+            Grid::new("gamepad_assignments").spacing([20.0, 5.0]).show(ui, |ui| {
+                for (player, lock) in [
+                    (Player::One, &mut self.config.input.gamepad_locks.p1),
+                    (Player::Two, &mut self.config.input.gamepad_locks.p2),
+                ] {
+                    let device_id = match player {
+                        Player::One => 0,
+                        Player::Two => 1,
+                    };
+                    let assigned = assignments.iter().find(|a| a.device_id == device_id);
+
+                    ui.label(format!("Player {}:", device_id + 1));
+                    ui.label(assigned.map_or("(None)", |assignment| assignment.name.as_str()));
+
+                    let lock_label = match lock {
+                        Some(guid) => assignments
+                            .iter()
+                            .find(|assignment| &assignment.guid == guid)
+                            .map_or_else(
+                                || format!("Locked to {guid}"),
+                                |assignment| format!("Locked to {}", assignment.name),
+                            ),
+                        None => "Auto-assign".into(),
+                    };
+                    ComboBox::new(format!("gamepad_lock_{device_id}"), "")
+                        .selected_text(lock_label)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(lock, None, "Auto-assign");
+
+                            for assignment in &assignments {
+                                ui.selectable_value(
+                                    lock,
+                                    Some(assignment.guid.clone()),
+                                    format!("Lock to {}", assignment.name),
+                                );
+                            }
+                        });
+                    ui.end_row();
+                }
+            });
+
+            for assignment in assignments.iter().filter(|assignment| assignment.device_id >= 2) {
+                ui.label(format!("Gamepad {}: {}", assignment.device_id, assignment.name));
+            }
+
+            ui.add_space(10.0);
+
+            ui.checkbox(
+                &mut self.config.input.background_gamepad_input,
+                "Accept gamepad input while the emulator window is not focused",
+            )
+            .on_hover_text("Keyboard input is only accepted while the emulator window is focused");
+
+            ui.checkbox(
+                &mut self.config.input.subframe_input,
+                "Apply input changes partway through frames (sub-frame input)",
+            )
+            .on_hover_text(
+                "Improves light gun precision, but adds up to one frame of input latency",
+            );
         });
         if !open {
             self.state.open_windows.remove(&OpenWindow::GeneralInput);
@@ -481,6 +602,28 @@ impl App {
         Window::new("SMS/GG Input Settings").open(&mut open).show(ctx, |ui| {
             self.disable_if_waiting_for_input(ui);
 
+            ui.group(|ui| {
+                ui.label("Player 1 device (Master System only)");
+
+                ui.horizontal(|ui| {
+                    for (controller_type, label) in [
+                        (SmsGgControllerType::Gamepad, "Gamepad"),
+                        (SmsGgControllerType::Paddle, "Paddle"),
+                        (SmsGgControllerType::SportsPad, "Sports Pad"),
+                    ] {
+                        ui.radio_value(
+                            &mut self.config.input.smsgg.p1_type,
+                            controller_type,
+                            label,
+                        );
+                    }
+                });
+
+                ui.label("Controlled using the mouse or the first gamepad's analog stick");
+            });
+
+            ui.separator();
+
             let mapping = self.render_mapping_set_selector(OpenWindow::SmsGgInput, ui);
             ui.separator();
 
@@ -959,6 +1102,10 @@ impl App {
                     *mapping_config = HotkeyMapping::default_keyboard();
                 }
 
+                if ui.button("Restore Gamepad Defaults").clicked() {
+                    *mapping_config = HotkeyMapping::default_gamepad();
+                }
+
                 if ui.button("Clear All").clicked() {
                     *mapping_config = HotkeyMapping::default();
                 }
@@ -1009,6 +1156,40 @@ impl App {
                 &mut self.config.common.load_recent_state_at_launch,
                 "Load most recent save state at launch",
             );
+
+            ui.checkbox(
+                &mut self.config.common.auto_save_state,
+                "Save state on exit and offer to resume at next launch",
+            );
+
+            ui.add_enabled_ui(self.config.common.auto_save_state, |ui| {
+                ui.checkbox(
+                    &mut self.config.common.periodic_auto_save_state,
+                    "Also save state periodically while running",
+                );
+
+                ui.add_enabled_ui(self.config.common.periodic_auto_save_state, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            NumericTextEdit::new(
+                                &mut self.state.auto_save_interval_text,
+                                &mut self.config.common.auto_save_state_interval_minutes,
+                                &mut self.state.auto_save_interval_invalid,
+                            )
+                            .with_validation(|value| value != 0)
+                            .desired_width(30.0),
+                        );
+
+                        ui.label("Automatic save state interval in minutes");
+                    });
+                    if self.state.auto_save_interval_invalid {
+                        ui.colored_label(
+                            Color32::RED,
+                            "Automatic save state interval must be a positive integer",
+                        );
+                    }
+                });
+            });
         });
         if !open {
             self.state.open_windows.remove(&OpenWindow::Hotkeys);
@@ -1036,7 +1217,7 @@ impl App {
                 let current_value_str = format_input_str(current_value.as_ref());
                 if ui.button(current_value_str).clicked() {
                     self.emu_thread.send(EmuThreadCommand::CollectInput {
-                        axis_deadzone: self.config.input.axis_deadzone,
+                        analog_config: self.config.input.analog.clone(),
                     });
                     self.state.waiting_for_input = Some((*button, mapping));
                 }
@@ -1098,13 +1279,16 @@ impl HotkeyExt for Hotkey {
 
         match self {
             PowerOff | Exit | ToggleFullscreen | SoftReset | HardReset | Pause | StepFrame
-            | FastForward | Rewind | ToggleOverclocking | OpenDebugger => HotkeyCategory::General,
-            SaveState | LoadState | NextSaveStateSlot | PrevSaveStateSlot | SaveStateSlot0
-            | SaveStateSlot1 | SaveStateSlot2 | SaveStateSlot3 | SaveStateSlot4
-            | SaveStateSlot5 | SaveStateSlot6 | SaveStateSlot7 | SaveStateSlot8
-            | SaveStateSlot9 | LoadStateSlot0 | LoadStateSlot1 | LoadStateSlot2
-            | LoadStateSlot3 | LoadStateSlot4 | LoadStateSlot5 | LoadStateSlot6
-            | LoadStateSlot7 | LoadStateSlot8 | LoadStateSlot9 => HotkeyCategory::SaveState,
+            | FastForward | Rewind | ToggleOverclocking | OpenDebugger | SwapPlayerInputs
+            | ToggleMute | TogglePerformanceHud => HotkeyCategory::General,
+            SaveState | LoadState | NextSaveStateSlot | PrevSaveStateSlot | UndoLoadState
+            | UndoSaveState | SaveStateSlot0 | SaveStateSlot1 | SaveStateSlot2 | SaveStateSlot3
+            | SaveStateSlot4 | SaveStateSlot5 | SaveStateSlot6 | SaveStateSlot7
+            | SaveStateSlot8 | SaveStateSlot9 | LoadStateSlot0 | LoadStateSlot1
+            | LoadStateSlot2 | LoadStateSlot3 | LoadStateSlot4 | LoadStateSlot5
+            | LoadStateSlot6 | LoadStateSlot7 | LoadStateSlot8 | LoadStateSlot9 => {
+                HotkeyCategory::SaveState
+            }
         }
     }
 }
@@ -0,0 +1,186 @@
+use crate::app::{App, OpenWindow};
+use crate::logging;
+use crate::logging::{CaptureFilters, LogCapture, ModuleFilter};
+use egui::{
+    Color32, ComboBox, Context, Grid, RichText, ScrollArea, TextEdit, TextStyle, Ui, Window,
+};
+use log::{Level, LevelFilter};
+use rfd::FileDialog;
+
+pub(super) struct LogWindowState {
+    filters: CaptureFilters,
+    text_filter: String,
+    rows: Vec<(Level, String)>,
+    rows_generation: Option<u64>,
+    rows_text_filter: String,
+    save_status: Option<String>,
+}
+
+impl LogWindowState {
+    pub(super) fn new() -> Self {
+        Self {
+            filters: logging::capture().map(LogCapture::filters).unwrap_or_default(),
+            text_filter: String::new(),
+            rows: Vec::new(),
+            rows_generation: None,
+            rows_text_filter: String::new(),
+            save_status: None,
+        }
+    }
+
+    fn render(&mut self, ui: &mut Ui, capture: &LogCapture) {
+        self.render_filters(ui, capture);
+
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            ui.label("Search");
+            ui.add(TextEdit::singleline(&mut self.text_filter).desired_width(200.0));
+
+            if ui.button("Clear").clicked() {
+                capture.clear();
+            }
+
+            if ui.button("Save to file…").clicked() {
+                self.save_to_file(capture);
+            }
+
+            if let Some(save_status) = &self.save_status {
+                ui.label(save_status);
+            }
+        });
+
+        self.refresh_rows(capture);
+
+        ui.add_space(5.0);
+
+        let row_height = ui.text_style_height(&TextStyle::Monospace);
+        ScrollArea::both().auto_shrink(false).stick_to_bottom(true).show_rows(
+            ui,
+            row_height,
+            self.rows.len(),
+            |ui, range| {
+                for (level, row) in &self.rows[range] {
+                    let mut text = RichText::new(row).monospace();
+                    if let Some(color) = level_color(*level) {
+                        text = text.color(color);
+                    }
+                    ui.label(text);
+                }
+            },
+        );
+    }
+
+    fn render_filters(&mut self, ui: &mut Ui, capture: &LogCapture) {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label("Default level");
+                level_combo(ui, "log_default_level", &mut self.filters.default_level);
+            });
+
+            let mut remove_idx = None;
+            Grid::new("log_module_filters").num_columns(3).show(ui, |ui| {
+                for (i, filter) in self.filters.modules.iter_mut().enumerate() {
+                    ui.add(
+                        TextEdit::singleline(&mut filter.module)
+                            .hint_text("e.g. segacd_core::cddrive")
+                            .desired_width(250.0),
+                    );
+                    level_combo(ui, ("log_module_level", i), &mut filter.level);
+                    if ui.button("Remove").clicked() {
+                        remove_idx = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some(i) = remove_idx {
+                self.filters.modules.remove(i);
+            }
+
+            if ui.button("Add module filter").clicked() {
+                self.filters
+                    .modules
+                    .push(ModuleFilter { module: String::new(), level: LevelFilter::Debug });
+            }
+
+            if cfg!(not(debug_assertions)) {
+                ui.label("Debug and trace messages are not available in release builds");
+            }
+        });
+
+        if self.filters != capture.filters() {
+            capture.set_filters(self.filters.clone());
+        }
+    }
+
+    fn refresh_rows(&mut self, capture: &LogCapture) {
+        let text_filter = self.text_filter.trim().to_lowercase();
+        let generation =
+            if text_filter == self.rows_text_filter { self.rows_generation } else { None };
+
+        let Some((records, generation)) = capture.records_since(generation.unwrap_or(u64::MAX))
+        else {
+            return;
+        };
+
+        self.rows = records
+            .iter()
+            .map(|record| (record.level, record.format()))
+            .filter(|(_, row)| text_filter.is_empty() || row.to_lowercase().contains(&text_filter))
+            .collect();
+        self.rows_generation = Some(generation);
+        self.rows_text_filter = text_filter;
+    }
+
+    fn save_to_file(&mut self, capture: &LogCapture) {
+        let Some(path) = FileDialog::new()
+            .set_file_name("jgenesis-log.txt")
+            .add_filter("Text", &["txt", "log"])
+            .save_file()
+        else {
+            return;
+        };
+
+        self.save_status = Some(match capture.save_to_file(&path) {
+            Ok(()) => format!("Saved to '{}'", path.display()),
+            Err(err) => {
+                log::error!("Error saving log to '{}': {err}", path.display());
+                format!("Error saving log: {err}")
+            }
+        });
+    }
+}
+
+fn level_combo(ui: &mut Ui, id: impl std::hash::Hash, level: &mut LevelFilter) {
+    ComboBox::from_id_salt(id).selected_text(level.as_str()).width(80.0).show_ui(ui, |ui| {
+        for option in LevelFilter::iter() {
+            ui.selectable_value(level, option, option.as_str());
+        }
+    });
+}
+
+fn level_color(level: Level) -> Option<Color32> {
+    match level {
+        Level::Error => Some(Color32::from_rgb(0xFF, 0x60, 0x60)),
+        Level::Warn => Some(Color32::YELLOW),
+        Level::Info => None,
+        Level::Debug | Level::Trace => Some(Color32::GRAY),
+    }
+}
+
+impl App {
+    pub(super) fn render_log_window(&mut self, ctx: &Context) {
+        let mut open = true;
+        Window::new("Log").open(&mut open).default_size([700.0, 400.0]).show(ctx, |ui| {
+            let Some(capture) = logging::capture() else {
+                ui.label("Log capture is not enabled");
+                return;
+            };
+
+            self.state.log_window.render(ui, capture);
+        });
+        if !open {
+            self.state.open_windows.remove(&OpenWindow::Log);
+        }
+    }
+}
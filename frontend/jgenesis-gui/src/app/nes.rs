@@ -1,6 +1,6 @@
 mod helptext;
 
-use crate::app::{App, NumericTextEdit, OpenWindow};
+use crate::app::{App, HelpText, NumericTextEdit, OpenWindow, REQUIRES_RESTART};
 use crate::emuthread::EmuThreadStatus;
 use eframe::emath::Align;
 use eframe::epaint::Color32;
@@ -8,6 +8,18 @@ use egui::{Context, Layout, Window};
 use jgenesis_common::frontend::TimingMode;
 use nes_core::api::{NesAspectRatio, Overscan};
 
+// Settings that can be found by searching the command palette
+pub(super) const SEARCHABLE_SETTINGS: &[(OpenWindow, HelpText)] = &[
+    (OpenWindow::NesGeneral, helptext::TIMING_MODE),
+    (OpenWindow::NesGeneral, helptext::OPPOSING_DIRECTIONAL_INPUTS),
+    (OpenWindow::NesVideo, helptext::ASPECT_RATIO),
+    (OpenWindow::NesVideo, helptext::REMOVE_SPRITE_LIMIT),
+    (OpenWindow::NesVideo, helptext::PAL_BLACK_BORDER),
+    (OpenWindow::NesVideo, helptext::OVERSCAN),
+    (OpenWindow::NesAudio, helptext::ULTRASONIC_TRIANGLE),
+    (OpenWindow::NesAudio, helptext::AUDIO_TIMING_HACK),
+];
+
 pub struct OverscanState {
     top_text: String,
     top_invalid: bool,
@@ -45,7 +57,8 @@ impl App {
                     ui.add_enabled_ui(
                         self.emu_thread.status() != EmuThreadStatus::RunningNes,
                         |ui| {
-                            ui.label("Timing / display mode");
+                            ui.label("Timing / display mode")
+                                .on_disabled_hover_text(REQUIRES_RESTART);
 
                             ui.horizontal(|ui| {
                                 ui.radio_value(
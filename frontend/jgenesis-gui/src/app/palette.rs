@@ -0,0 +1,314 @@
+use crate::app::{App, HelpText, OpenWindow, common, gb, genesis, nes, smsgg, snes};
+use crate::emuthread::{EmuThreadCommand, EmuThreadStatus};
+use egui::{
+    Align, Align2, Context, Id, Key, KeyboardShortcut, LayerId, Modifiers, Order, ScrollArea,
+    TextEdit, Window,
+};
+
+pub(super) const PALETTE_SHORTCUT: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::CTRL, Key::P);
+
+const MAX_RESULTS_HEIGHT: f32 = 300.0;
+
+#[derive(Debug, Clone, Copy)]
+enum PaletteCommand {
+    OpenFile,
+    Quit,
+    OpenWindow(OpenWindow),
+    ShowSetting(OpenWindow, HelpText),
+    Emulator(EmulatorCommand),
+}
+
+// Commands that are only available while a game is running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EmulatorCommand {
+    SoftReset,
+    HardReset,
+    PowerOff,
+    OpenMemoryViewer,
+    SwapPlayerInputs,
+}
+
+impl EmulatorCommand {
+    const ALL: [Self; 5] = [
+        Self::SoftReset,
+        Self::HardReset,
+        Self::PowerOff,
+        Self::OpenMemoryViewer,
+        Self::SwapPlayerInputs,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::SoftReset => "Soft Reset",
+            Self::HardReset => "Hard Reset",
+            Self::PowerOff => "Power Off",
+            Self::OpenMemoryViewer => "Open Memory Viewer",
+            Self::SwapPlayerInputs => "Swap P1/P2 Inputs",
+        }
+    }
+
+    fn to_emu_thread_command(self) -> EmuThreadCommand {
+        match self {
+            Self::SoftReset => EmuThreadCommand::SoftReset,
+            Self::HardReset => EmuThreadCommand::HardReset,
+            Self::PowerOff => EmuThreadCommand::StopEmulator,
+            Self::OpenMemoryViewer => EmuThreadCommand::OpenMemoryViewer,
+            Self::SwapPlayerInputs => EmuThreadCommand::SwapPlayerInputs,
+        }
+    }
+}
+
+// Windows that can be opened from the command palette. Titles must match the titles passed to
+// Window::new() so that an already-open window can be brought to the front
+const SETTINGS_WINDOWS: &[(OpenWindow, &str)] = &[
+    (OpenWindow::SmsGgGeneral, "SMS/GG General Settings"),
+    (OpenWindow::GenesisGeneral, "Genesis General Settings"),
+    (OpenWindow::NesGeneral, "NES General Settings"),
+    (OpenWindow::SnesGeneral, "SNES General Settings"),
+    (OpenWindow::GameBoyGeneral, "Game Boy General Settings"),
+    (OpenWindow::Synchronization, "Synchronization Settings"),
+    (OpenWindow::Paths, "Path Settings"),
+    (OpenWindow::Interface, "UI Settings"),
+    (OpenWindow::CommonVideo, "General Video Settings"),
+    (OpenWindow::SmsGgVideo, "SMS/GG Video Settings"),
+    (OpenWindow::GenesisVideo, "Genesis Video Settings"),
+    (OpenWindow::NesVideo, "NES Video Settings"),
+    (OpenWindow::SnesVideo, "SNES Video Settings"),
+    (OpenWindow::GameBoyVideo, "Game Boy Video Settings"),
+    (OpenWindow::CommonAudio, "General Audio Settings"),
+    (OpenWindow::SmsGgAudio, "SMS/GG Audio Settings"),
+    (OpenWindow::GenesisAudio, "Genesis Audio Settings"),
+    (OpenWindow::NesAudio, "NES Audio Settings"),
+    (OpenWindow::SnesAudio, "SNES Audio Settings"),
+    (OpenWindow::GeneralInput, "General Input Settings"),
+    (OpenWindow::SmsGgInput, "SMS/GG Input Settings"),
+    (OpenWindow::GenesisInput, "Genesis Input Settings"),
+    (OpenWindow::NesInput, "NES Input Settings"),
+    (OpenWindow::NesPeripherals, "NES Peripheral Settings"),
+    (OpenWindow::SnesInput, "SNES Input Settings"),
+    (OpenWindow::SnesPeripherals, "SNES Peripheral Settings"),
+    (OpenWindow::GameBoyInput, "Game Boy Input Settings"),
+    (OpenWindow::Hotkeys, "Hotkey Settings"),
+    (OpenWindow::Log, "Log"),
+    (OpenWindow::About, "About"),
+];
+
+const ALL_SETTINGS: [&[(OpenWindow, HelpText)]; 6] = [
+    common::SEARCHABLE_SETTINGS,
+    smsgg::SEARCHABLE_SETTINGS,
+    genesis::SEARCHABLE_SETTINGS,
+    nes::SEARCHABLE_SETTINGS,
+    snes::SEARCHABLE_SETTINGS,
+    gb::SEARCHABLE_SETTINGS,
+];
+
+fn window_title(window: OpenWindow) -> Option<&'static str> {
+    SETTINGS_WINDOWS.iter().find(|&&(w, _)| w == window).map(|&(_, title)| title)
+}
+
+#[derive(Debug, Clone)]
+struct PaletteEntry {
+    name: &'static str,
+    // Window title for settings, or a short description for everything else
+    category: &'static str,
+    command: PaletteCommand,
+}
+
+impl PaletteEntry {
+    fn matches(&self, query_terms: &[String]) -> bool {
+        let name = self.name.to_lowercase();
+        let category = self.category.to_lowercase();
+        query_terms.iter().all(|term| name.contains(term) || category.contains(term))
+    }
+}
+
+fn all_entries(emulator_running: bool) -> Vec<PaletteEntry> {
+    let mut entries = vec![
+        PaletteEntry { name: "Open", category: "File", command: PaletteCommand::OpenFile },
+        PaletteEntry { name: "Quit", category: "File", command: PaletteCommand::Quit },
+    ];
+
+    if emulator_running {
+        entries.extend(EmulatorCommand::ALL.into_iter().map(|command| PaletteEntry {
+            name: command.name(),
+            category: "Emulation",
+            command: PaletteCommand::Emulator(command),
+        }));
+    }
+
+    entries.extend(SETTINGS_WINDOWS.iter().map(|&(window, title)| PaletteEntry {
+        name: title,
+        category: "Window",
+        command: PaletteCommand::OpenWindow(window),
+    }));
+
+    entries.extend(ALL_SETTINGS.into_iter().flatten().filter_map(|&(window, help_text)| {
+        Some(PaletteEntry {
+            name: help_text.heading,
+            category: window_title(window)?,
+            command: PaletteCommand::ShowSetting(window, help_text),
+        })
+    }));
+
+    entries
+}
+
+pub(super) struct CommandPaletteState {
+    query: String,
+    selected: usize,
+    request_focus: bool,
+}
+
+impl CommandPaletteState {
+    pub(super) fn new() -> Self {
+        Self { query: String::new(), selected: 0, request_focus: false }
+    }
+}
+
+impl App {
+    pub(super) fn open_command_palette(&mut self) {
+        let state = &mut self.state.command_palette;
+        state.query.clear();
+        state.selected = 0;
+        state.request_focus = true;
+
+        self.state.open_windows.insert(OpenWindow::CommandPalette);
+    }
+
+    pub(super) fn render_command_palette(&mut self, ctx: &Context) {
+        let emulator_running = self.emu_thread.status().is_running();
+
+        let query_terms: Vec<_> =
+            self.state.command_palette.query.split_whitespace().map(str::to_lowercase).collect();
+        let results: Vec<_> = all_entries(emulator_running)
+            .into_iter()
+            .filter(|entry| entry.matches(&query_terms))
+            .collect();
+
+        let (up, down, enter, escape) = ctx.input_mut(|input| {
+            (
+                input.consume_key(Modifiers::NONE, Key::ArrowUp),
+                input.consume_key(Modifiers::NONE, Key::ArrowDown),
+                input.consume_key(Modifiers::NONE, Key::Enter),
+                input.consume_key(Modifiers::NONE, Key::Escape),
+            )
+        });
+
+        let state = &mut self.state.command_palette;
+        if up {
+            state.selected = state.selected.saturating_sub(1);
+        }
+        if down {
+            state.selected += 1;
+        }
+        state.selected = state.selected.min(results.len().saturating_sub(1));
+
+        let mut clicked = None;
+        let mut open = true;
+        Window::new("Command Palette")
+            .open(&mut open)
+            .anchor(Align2::CENTER_TOP, [0.0, 40.0])
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let search_box = ui.add(
+                    TextEdit::singleline(&mut state.query)
+                        .hint_text("Search settings and actions")
+                        .desired_width(400.0),
+                );
+                if state.request_focus {
+                    search_box.request_focus();
+                    state.request_focus = false;
+                }
+                if search_box.changed() {
+                    state.selected = 0;
+                }
+
+                ui.separator();
+
+                if results.is_empty() {
+                    ui.label("No matches");
+                    return;
+                }
+
+                ScrollArea::vertical().max_height(MAX_RESULTS_HEIGHT).show(ui, |ui| {
+                    for (i, entry) in results.iter().enumerate() {
+                        let label = format!("{}  ({})", entry.name, entry.category);
+                        let response = ui.selectable_label(i == state.selected, label);
+                        if i == state.selected && (up || down) {
+                            response.scroll_to_me(Some(Align::Center));
+                        }
+                        if response.clicked() {
+                            clicked = Some(i);
+                        }
+                    }
+                });
+            });
+
+        let chosen = clicked.or_else(|| enter.then_some(self.state.command_palette.selected));
+        if let Some(entry) = chosen.and_then(|i| results.get(i)) {
+            self.run_palette_command(ctx, entry.command);
+            open = false;
+        }
+
+        if !open || escape {
+            self.state.open_windows.remove(&OpenWindow::CommandPalette);
+        }
+    }
+
+    fn run_palette_command(&mut self, ctx: &Context, command: PaletteCommand) {
+        match command {
+            PaletteCommand::OpenFile => self.open_file(None),
+            PaletteCommand::Quit => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+            PaletteCommand::OpenWindow(window) => self.show_window(ctx, window),
+            PaletteCommand::ShowSetting(window, help_text) => {
+                self.state.help_text.insert(window, help_text);
+                self.show_window(ctx, window);
+            }
+            PaletteCommand::Emulator(command) => {
+                let running_gb = self.emu_thread.status() == EmuThreadStatus::RunningGameBoy;
+                if command == EmulatorCommand::SoftReset && running_gb {
+                    log::warn!("Soft reset is not supported for Game Boy");
+                    return;
+                }
+
+                self.emu_thread.send(command.to_emu_thread_command());
+            }
+        }
+    }
+
+    fn show_window(&mut self, ctx: &Context, window: OpenWindow) {
+        self.state.open_windows.insert(window);
+
+        if let Some(title) = window_title(window) {
+            ctx.move_to_top(LayerId::new(Order::Middle, Id::new(title)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_setting_has_an_entry() {
+        let setting_count: usize = ALL_SETTINGS.iter().map(|settings| settings.len()).sum();
+        let entry_count = all_entries(false)
+            .into_iter()
+            .filter(|entry| matches!(entry.command, PaletteCommand::ShowSetting(..)))
+            .count();
+        assert_eq!(setting_count, entry_count);
+    }
+
+    #[test]
+    fn search_matches_all_terms() {
+        let query_terms = ["super".to_string(), "fx".to_string()];
+        let names: Vec<_> = all_entries(false)
+            .into_iter()
+            .filter(|entry| entry.matches(&query_terms))
+            .map(|entry| entry.name)
+            .collect();
+        assert_eq!(names, ["Super FX Overclocking"]);
+    }
+}
@@ -1,20 +1,29 @@
 use crate::app::Console;
+use genesis_core::GenesisEmulator;
+use jgenesis_common::frontend::GameMetadata;
+use jgenesis_common::hash::RomHashes;
 use jgenesis_native_config::RecentOpen;
 use jgenesis_native_driver::extensions;
 use regex::Regex;
+use segacd_core::CdRomFileFormat;
+use segacd_core::api::SegaCdEmulator;
+use snes_core::api::SnesEmulator;
 use std::collections::{HashMap, HashSet};
 use std::ffi::OsStr;
+use std::fs::File;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::mpsc::Sender;
-use std::sync::{Arc, LazyLock, Mutex, mpsc};
+use std::sync::{Arc, LazyLock, Mutex, OnceLock, mpsc};
 use std::{fs, io, thread};
 
 fn build_extension_lookup() -> HashMap<&'static str, Console> {
     [
         (extensions::MASTER_SYSTEM, Console::MasterSystem),
         (extensions::GAME_GEAR, Console::GameGear),
+        (extensions::SG_1000, Console::Sg1000),
         (extensions::GENESIS, Console::Genesis),
         (extensions::SEGA_CD, Console::SegaCd),
         (extensions::SEGA_32X, Console::Sega32X),
@@ -40,6 +49,7 @@ impl Console {
         match self {
             Self::MasterSystem => "Master System",
             Self::GameGear => "Game Gear",
+            Self::Sg1000 => "SG-1000",
             Self::Genesis => "Genesis",
             Self::SegaCd => "Sega CD",
             Self::Sega32X => "32X",
@@ -53,7 +63,7 @@ impl Console {
     #[must_use]
     pub fn supported_extensions(self) -> &'static [&'static str] {
         match self {
-            Self::MasterSystem | Self::GameGear => &extensions::SMSGG,
+            Self::MasterSystem | Self::GameGear | Self::Sg1000 => &extensions::SMSGG,
             Self::Genesis => extensions::GENESIS,
             Self::SegaCd => extensions::SEGA_CD,
             Self::Sega32X => extensions::SEGA_32X,
@@ -70,6 +80,65 @@ pub struct RomMetadata {
     pub file_name_no_ext: String,
     pub console: Console,
     pub file_size: u64,
+    /// Parsed from the start of the ROM header while scanning; only populated for cartridge files
+    /// that are not in an archive
+    pub game_metadata: GameMetadata,
+    // Sega CD disc headers and game-specific workarounds require opening the disc or reading the
+    // whole ROM, so these are only read the first time that they're needed
+    full_game_metadata: Arc<OnceLock<GameMetadata>>,
+}
+
+impl RomMetadata {
+    /// Title, serial number, and region from the header, one per line, for any that were found,
+    /// followed by any game-specific workarounds that apply
+    ///
+    /// This may read the whole ROM file or open the disc image the first time it is called.
+    #[must_use]
+    pub fn header_summary(&self) -> Option<String> {
+        let GameMetadata { title, serial, region, quirks } =
+            self.full_game_metadata.get_or_init(|| {
+                read_full_game_metadata(&self.full_path, self.console, &self.game_metadata)
+            });
+        let mut lines: Vec<_> = [("Title", title), ("Serial", serial), ("Region", region)]
+            .into_iter()
+            .filter_map(|(label, value)| value.as_ref().map(|value| format!("{label}: {value}")))
+            .collect();
+        if !quirks.is_empty() {
+            lines.push(format!("Workarounds: {}", quirks.join(", ")));
+        }
+
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    /// Title from the header, if it has been read
+    #[must_use]
+    pub fn header_title(&self) -> Option<&str> {
+        self.full_game_metadata.get().unwrap_or(&self.game_metadata).title.as_deref()
+    }
+
+    /// CRC32, MD5, and SHA-1 of the file, one per line, for identifying the dump against ROM
+    /// databases. Returns None for archives and CUE sheets, whose hashes would not match the game
+    /// data.
+    #[must_use]
+    pub fn rom_hashes(&self) -> Option<String> {
+        let extension = self.full_path.extension().and_then(OsStr::to_str)?.to_lowercase();
+        if extension == "cue" || extensions::SUPPORTED_ARCHIVES.contains(&extension.as_str()) {
+            return None;
+        }
+
+        match RomHashes::from_file(&self.full_path) {
+            Ok(hashes) => Some(format!(
+                "CRC32: {}\nMD5: {}\nSHA-1: {}",
+                hashes.crc32_hex(),
+                hashes.md5_hex(),
+                hashes.sha1_hex()
+            )),
+            Err(err) => {
+                log::error!("Unable to hash ROM file '{}': {err}", self.full_path.display());
+                None
+            }
+        }
+    }
 }
 
 pub fn build(rom_search_dirs: &[String]) -> Vec<RomMetadata> {
@@ -144,6 +213,8 @@ fn process_file(file_name: &str, path: &Path, metadata: fs::Metadata) -> Option<
                 file_name_no_ext,
                 console,
                 file_size: zip_entry.size,
+                game_metadata: GameMetadata::default(),
+                full_game_metadata: Arc::default(),
             })
         }
         "7z" => {
@@ -159,6 +230,8 @@ fn process_file(file_name: &str, path: &Path, metadata: fs::Metadata) -> Option<
                 file_name_no_ext,
                 console,
                 file_size: zip_entry.size,
+                game_metadata: GameMetadata::default(),
+                full_game_metadata: Arc::default(),
             })
         }
         _ => {
@@ -168,11 +241,64 @@ fn process_file(file_name: &str, path: &Path, metadata: fs::Metadata) -> Option<
                 _ => metadata.len(),
             };
 
-            Some(RomMetadata { full_path: path.into(), file_name_no_ext, console, file_size })
+            Some(RomMetadata {
+                full_path: path.into(),
+                file_name_no_ext,
+                console,
+                file_size,
+                game_metadata: read_header_metadata(path, console, file_size),
+                full_game_metadata: Arc::default(),
+            })
         }
     }
 }
 
+fn read_header_metadata(path: &Path, console: Console, file_size: u64) -> GameMetadata {
+    let metadata = match console {
+        Console::Genesis | Console::Sega32X => {
+            read_file_prefix(path, GenesisEmulator::HEADER_PREFIX_LEN)
+                .map(|prefix| GenesisEmulator::read_header_metadata(&prefix))
+                .ok()
+        }
+        Console::Snes => read_file_prefix(path, SnesEmulator::HEADER_PREFIX_LEN)
+            .map(|prefix| SnesEmulator::read_header_metadata(&prefix, file_size as usize))
+            .ok(),
+        _ => None,
+    };
+
+    metadata.unwrap_or_default()
+}
+
+fn read_file_prefix(path: &Path, len: usize) -> io::Result<Vec<u8>> {
+    let mut prefix = Vec::with_capacity(len);
+    File::open(path)?.take(len as u64).read_to_end(&mut prefix)?;
+    Ok(prefix)
+}
+
+fn read_full_game_metadata(
+    path: &Path,
+    console: Console,
+    header_metadata: &GameMetadata,
+) -> GameMetadata {
+    let is_archive = extensions::from_path(path)
+        .is_some_and(|extension| extensions::SUPPORTED_ARCHIVES.contains(&extension.as_str()));
+    if is_archive {
+        return header_metadata.clone();
+    }
+
+    let metadata = match console {
+        // Reads the whole ROM because some workarounds are keyed by the ROM's CRC32
+        Console::Genesis | Console::Sega32X => {
+            fs::read(path).ok().map(GenesisEmulator::read_game_metadata)
+        }
+        Console::SegaCd => CdRomFileFormat::from_file_path(path)
+            .and_then(|format| SegaCdEmulator::read_disc_metadata(path, format).ok()),
+        _ => None,
+    };
+
+    metadata.unwrap_or_else(|| header_metadata.clone())
+}
+
 fn sega_cd_file_size(cue_path: &Path) -> io::Result<u64> {
     let cue_contents = fs::read_to_string(cue_path)?;
     let cue_directory =
@@ -213,7 +339,14 @@ pub fn from_recent_opens(recent_opens: &[RecentOpen]) -> Vec<RomMetadata> {
                 _ => metadata.len(),
             };
 
-            Some(RomMetadata { full_path: path_str.into(), file_name_no_ext, console, file_size })
+            Some(RomMetadata {
+                full_path: path_str.into(),
+                file_name_no_ext,
+                console,
+                file_size,
+                game_metadata: GameMetadata::default(),
+                full_game_metadata: Arc::default(),
+            })
         })
         .collect()
 }
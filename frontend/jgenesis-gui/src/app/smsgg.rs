@@ -1,13 +1,31 @@
 mod helptext;
 
-use crate::app::{App, OpenWindow};
+use crate::app::{App, HelpText, OpenWindow, REQUIRES_RESTART};
 use crate::emuthread::EmuThreadStatus;
-use crate::widgets::OverclockSlider;
-use egui::{Context, Window};
+use crate::widgets::{ClockScaling, OverclockSlider};
+use egui::{Context, Slider, Window};
+use jgenesis_common::clockratio::ClockRatio;
 use jgenesis_common::frontend::TimingMode;
 use smsgg_core::psg::Sn76489Version;
 use smsgg_core::{GgAspectRatio, SmsAspectRatio, SmsModel, SmsRegion};
-use std::num::NonZeroU32;
+
+// Settings that can be found by searching the command palette
+pub(super) const SEARCHABLE_SETTINGS: &[(OpenWindow, HelpText)] = &[
+    (OpenWindow::SmsGgGeneral, helptext::TIMING_MODE),
+    (OpenWindow::SmsGgGeneral, helptext::VDP_VERSION),
+    (OpenWindow::SmsGgGeneral, helptext::REGION),
+    (OpenWindow::SmsGgGeneral, helptext::Z80_OVERCLOCK),
+    (OpenWindow::SmsGgVideo, helptext::SMS_ASPECT_RATIO),
+    (OpenWindow::SmsGgVideo, helptext::GG_ASPECT_RATIO),
+    (OpenWindow::SmsGgVideo, helptext::REMOVE_SPRITE_LIMIT),
+    (OpenWindow::SmsGgVideo, helptext::SMS_CROP_VERTICAL_BORDER),
+    (OpenWindow::SmsGgVideo, helptext::SMS_CROP_LEFT_BORDER),
+    (OpenWindow::SmsGgVideo, helptext::GG_USE_SMS_RESOLUTION),
+    (OpenWindow::SmsGgVideo, helptext::GG_FRAME_BLENDING),
+    (OpenWindow::SmsGgVideo, helptext::SMS_NTSC_COMPOSITE_FILTER),
+    (OpenWindow::SmsGgAudio, helptext::PSG_VERSION),
+    (OpenWindow::SmsGgAudio, helptext::SMS_FM_UNIT),
+];
 
 impl App {
     pub(super) fn render_smsgg_general_settings(&mut self, ctx: &Context) {
@@ -68,9 +86,10 @@ impl App {
             let rect = ui.add(OverclockSlider {
                 label: "Z80 clock divider",
                 current_value: &mut self.config.smsgg.z80_divider,
-                range: NonZeroU32::new(1).unwrap()..=NonZeroU32::new(15).unwrap(),
+                range: ClockRatio::from_integer(1)..=ClockRatio::from_integer(15),
                 master_clock: smsgg_core::audio::NTSC_MCLK_FREQUENCY,
-                default_divider: smsgg_core::NATIVE_Z80_DIVIDER.into(),
+                native_value: smsgg_core::NATIVE_Z80_DIVIDER.into(),
+                scaling: ClockScaling::Divider,
             }).interact_rect;
             if ui.rect_contains_pointer(rect) {
                 self.state.help_text.insert(WINDOW, helptext::Z80_OVERCLOCK);
@@ -193,6 +212,38 @@ impl App {
                 self.state.help_text.insert(WINDOW, helptext::GG_USE_SMS_RESOLUTION);
             }
 
+            let rect = ui
+                .horizontal(|ui| {
+                    ui.checkbox(
+                        &mut self.config.smsgg.gg_frame_blending,
+                        "(Game Gear) LCD frame blending",
+                    );
+
+                    ui.add_enabled(
+                        self.config.smsgg.gg_frame_blending,
+                        Slider::new(
+                            &mut self.config.smsgg.gg_lcd_persistence,
+                            0..=smsgg_core::MAX_GG_LCD_PERSISTENCE,
+                        )
+                        .suffix("%"),
+                    );
+                })
+                .response
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::GG_FRAME_BLENDING);
+            }
+
+            let rect = ui
+                .checkbox(
+                    &mut self.config.smsgg.ntsc_composite_filter,
+                    "(SMS) NTSC composite filter",
+                )
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::SMS_NTSC_COMPOSITE_FILTER);
+            }
+
             self.render_help_text(ui, WINDOW);
         });
         if !open {
@@ -239,7 +290,8 @@ impl App {
                     ui.checkbox(
                         &mut self.config.smsgg.fm_sound_unit_enabled,
                         "Master System FM sound unit enabled",
-                    );
+                    )
+                    .on_disabled_hover_text(REQUIRES_RESTART);
                 })
                 .response
                 .interact_rect;
@@ -27,7 +27,7 @@ pub const REGION: HelpText = HelpText {
 pub const Z80_OVERCLOCK: HelpText = HelpText {
     heading: "Z80 Clock Divider",
     text: &[
-        "Optionally overclock the Z80 CPU by reducing the master clock divider.",
+        "Optionally overclock the Z80 CPU by reducing the master clock divider. Fractional dividers are supported for finer control over CPU speed.",
         "This can reduce or eliminate slowdown in some games but can also cause major glitches. Use with caution.",
     ],
 };
@@ -73,6 +73,14 @@ pub const SMS_CROP_LEFT_BORDER: HelpText = HelpText {
     ],
 };
 
+pub const SMS_NTSC_COMPOSITE_FILTER: HelpText = HelpText {
+    heading: "SMS NTSC Composite Filter",
+    text: &[
+        "If enabled, simulate the color bleed and blur of NTSC composite video output, which some games rely on to blend dithered graphics.",
+        "This filter is applied before the preprocess shader. It is never applied to Game Gear games because the Game Gear has a built-in LCD screen.",
+    ],
+};
+
 pub const GG_USE_SMS_RESOLUTION: HelpText = HelpText {
     heading: "Game Gear Expanded Resolution",
     text: &[
@@ -81,6 +89,14 @@ pub const GG_USE_SMS_RESOLUTION: HelpText = HelpText {
     ],
 };
 
+pub const GG_FRAME_BLENDING: HelpText = HelpText {
+    heading: "Game Gear LCD Frame Blending",
+    text: &[
+        "If enabled, blend each frame with the previous frame to simulate the slow response time of the Game Gear's LCD screen. Some games flicker sprites every other frame to create a transparency effect that depends on this.",
+        "The percentage controls how much of the previous frame persists. Higher values look smoother but produce more visible ghosting on moving objects.",
+    ],
+};
+
 pub const PSG_VERSION: HelpText = HelpText {
     heading: "PSG Version",
     text: &[
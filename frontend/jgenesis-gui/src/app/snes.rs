@@ -1,15 +1,31 @@
 mod helptext;
 
-use crate::app::{App, Console, OpenWindow};
+use crate::app::{App, Console, HelpText, OpenWindow, REQUIRES_RESTART};
 use crate::emuthread::EmuThreadStatus;
-use egui::{Context, Grid, Ui, Window};
+use crate::widgets::{ClockScaling, OverclockSlider};
+use egui::{Button, Context, Grid, Ui, Window};
+use jgenesis_common::clockratio::ClockRatio;
 use jgenesis_common::frontend::TimingMode;
 use jgenesis_native_config::snes::SnesAppConfig;
 use rfd::FileDialog;
 use snes_core::api::{AudioInterpolationMode, SnesAspectRatio, SnesLoadError};
-use std::num::NonZeroU64;
 use std::path::PathBuf;
 
+// Settings that can be found by searching the command palette
+pub(super) const SEARCHABLE_SETTINGS: &[(OpenWindow, HelpText)] = &[
+    (OpenWindow::SnesGeneral, helptext::TIMING_MODE),
+    (OpenWindow::SnesGeneral, helptext::SUPER_FX_OVERCLOCK),
+    (OpenWindow::SnesGeneral, helptext::COPROCESSOR_ROM_PATHS),
+    (OpenWindow::SnesGeneral, helptext::SUPER_GAME_BOY),
+    (OpenWindow::SnesGeneral, helptext::SPC700_IPL),
+    (OpenWindow::SnesVideo, helptext::ASPECT_RATIO),
+    (OpenWindow::SnesVideo, helptext::DEINTERLACING),
+    (OpenWindow::SnesVideo, helptext::HI_RES_BLENDING),
+    (OpenWindow::SnesVideo, helptext::PRIORITY_OVERLAY),
+    (OpenWindow::SnesAudio, helptext::ADPCM_INTERPOLATION),
+    (OpenWindow::SnesAudio, helptext::AUDIO_TIMING_HACK),
+];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HandledError {
     No,
@@ -61,7 +77,8 @@ impl App {
                     ui.add_enabled_ui(
                         self.emu_thread.status() != EmuThreadStatus::RunningSnes,
                         |ui| {
-                            ui.label("Timing / display mode");
+                            ui.label("Timing / display mode")
+                                .on_disabled_hover_text(REQUIRES_RESTART);
 
                             ui.horizontal(|ui| {
                                 ui.radio_value(
@@ -90,33 +107,14 @@ impl App {
             }
 
             let rect = ui
-                .group(|ui| {
-                    ui.label("Super FX GSU overclock factor");
-
-                    ui.horizontal(|ui| {
-                        ui.radio_value(
-                            &mut self.config.snes.gsu_overclock_factor,
-                            NonZeroU64::new(1).unwrap(),
-                            "None",
-                        );
-                        ui.radio_value(
-                            &mut self.config.snes.gsu_overclock_factor,
-                            NonZeroU64::new(2).unwrap(),
-                            "2x",
-                        );
-                        ui.radio_value(
-                            &mut self.config.snes.gsu_overclock_factor,
-                            NonZeroU64::new(3).unwrap(),
-                            "3x",
-                        );
-                        ui.radio_value(
-                            &mut self.config.snes.gsu_overclock_factor,
-                            NonZeroU64::new(4).unwrap(),
-                            "4x",
-                        );
-                    });
+                .add(OverclockSlider {
+                    label: "Super FX GSU overclock factor",
+                    current_value: &mut self.config.snes.gsu_overclock_factor,
+                    range: ClockRatio::from_integer(1)..=ClockRatio::from_integer(4),
+                    master_clock: snes_core::constants::NTSC_MASTER_CLOCK_FREQUENCY as f64,
+                    native_value: 1.0,
+                    scaling: ClockScaling::Multiplier,
                 })
-                .response
                 .interact_rect;
             if ui.rect_contains_pointer(rect) {
                 self.state.help_text.insert(WINDOW, helptext::SUPER_FX_OVERCLOCK);
@@ -144,6 +142,59 @@ impl App {
                 self.state.help_text.insert(WINDOW, helptext::COPROCESSOR_ROM_PATHS);
             }
 
+            let rect = ui
+                .group(|ui| {
+                    ui.label("Super Game Boy");
+
+                    ui.checkbox(
+                        &mut self.config.snes.super_game_boy_mode,
+                        "Run Game Boy games in Super Game Boy mode",
+                    );
+
+                    Grid::new("sgb_bios_path_grid").show(ui, |ui| {
+                        render_coprocessor_path_select(
+                            "SGB BIOS path",
+                            &mut self.config.snes.sgb_bios_path,
+                            ui,
+                        );
+                    });
+                })
+                .response
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::SUPER_GAME_BOY);
+            }
+
+            let rect = ui
+                .group(|ui| {
+                    ui.add_enabled_ui(
+                        self.emu_thread.status() != EmuThreadStatus::RunningSnes,
+                        |ui| {
+                            ui.label("SPC700 IPL boot ROM")
+                                .on_disabled_hover_text(REQUIRES_RESTART);
+
+                            Grid::new("spc700_ipl_path_grid").show(ui, |ui| {
+                                render_coprocessor_path_select(
+                                    "IPL ROM path",
+                                    &mut self.config.snes.spc700_ipl_path,
+                                    ui,
+                                );
+                            });
+
+                            let using_dump = self.config.snes.spc700_ipl_path.is_some();
+                            if ui.add_enabled(using_dump, Button::new("Use built-in IPL")).clicked()
+                            {
+                                self.config.snes.spc700_ipl_path = None;
+                            }
+                        },
+                    );
+                })
+                .response
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::SPC700_IPL);
+            }
+
             self.render_help_text(ui, WINDOW);
         });
         if !open {
@@ -202,6 +253,20 @@ impl App {
                 self.state.help_text.insert(WINDOW, helptext::DEINTERLACING);
             }
 
+            let rect = ui
+                .checkbox(&mut self.config.snes.hi_res_blending, "Hi-res blending enabled")
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::HI_RES_BLENDING);
+            }
+
+            let rect = ui
+                .checkbox(&mut self.config.snes.priority_overlay, "Priority overlay (debug)")
+                .interact_rect;
+            if ui.rect_contains_pointer(rect) {
+                self.state.help_text.insert(WINDOW, helptext::PRIORITY_OVERLAY);
+            }
+
             self.render_help_text(ui, WINDOW);
         });
         if !open {
@@ -230,6 +295,12 @@ impl App {
                         "Cubic Hermite (Sharp)",
                     )
                     .on_hover_text("More advanced algorithm than actual hardware");
+                    ui.radio_value(
+                        &mut self.config.snes.audio_interpolation,
+                        AudioInterpolationMode::None,
+                        "None (Raw)",
+                    )
+                    .on_hover_text("Output decoded samples without any interpolation");
                 })
                 .response
                 .interact_rect;
@@ -267,7 +338,10 @@ impl App {
             SnesLoadError::MissingDsp4Rom => CoprocessorRom::Dsp4,
             SnesLoadError::MissingSt010Rom => CoprocessorRom::St010,
             SnesLoadError::MissingSt011Rom => CoprocessorRom::St011,
-            SnesLoadError::CoprocessorRomLoad { .. } => return HandledError::No,
+            SnesLoadError::MissingSgbCartridge
+            | SnesLoadError::SgbCartridge(..)
+            | SnesLoadError::InvalidSpc700Ipl { .. }
+            | SnesLoadError::CoprocessorRomLoad { .. } => return HandledError::No,
         };
 
         let name = coprocessor_rom.name();
@@ -295,6 +369,40 @@ impl App {
 
         HandledError::Yes
     }
+
+    pub(super) fn render_sgb_bios_error(&mut self, ctx: &Context, open: &mut bool) {
+        let mut path_configured = false;
+        Window::new("Missing Super Game Boy BIOS").open(open).resizable(false).show(ctx, |ui| {
+            ui.label("No Super Game Boy BIOS path is configured. A Super Game Boy BIOS ROM is required to run Game Boy games in Super Game Boy mode.");
+
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Configure now:");
+                if ui.button("Configure SGB BIOS path").clicked() {
+                    pick_sgb_bios_path(&mut self.config.snes.sgb_bios_path);
+                    path_configured = self.config.snes.sgb_bios_path.is_some();
+                }
+            });
+        });
+
+        if path_configured {
+            *open = false;
+            self.launch_emulator(self.state.current_file_path.clone(), Some(Console::Snes));
+        }
+    }
+}
+
+fn pick_sgb_bios_path(out_path: &mut Option<PathBuf>) {
+    let Some(path) = FileDialog::new()
+        .add_filter("sfc", &["sfc", "smc", "bin"])
+        .add_filter("All Types", &["*"])
+        .pick_file()
+    else {
+        return;
+    };
+
+    *out_path = Some(path);
 }
 
 fn render_coprocessor_path_select(label: &str, value: &mut Option<PathBuf>, ui: &mut Ui) {
@@ -13,6 +13,7 @@ pub const SUPER_FX_OVERCLOCK: HelpText = HelpText {
     text: &[
         "Optionally overclock the GSU coprocessor in Super FX cartridges.",
         "This typically increases framerate in Super FX games but also typically increases game speed, since most Super FX games tied game speed to framerate.",
+        "Fractional multipliers are supported. The displayed speed assumes that the GSU is running at its maximum clock rate of 21.47 MHz.",
     ],
 };
 
@@ -24,6 +25,14 @@ pub const COPROCESSOR_ROM_PATHS: HelpText = HelpText {
     ],
 };
 
+pub const SUPER_GAME_BOY: HelpText = HelpText {
+    heading: "Super Game Boy",
+    text: &[
+        "If enabled, Game Boy games are run in the SNES core using an emulated Super Game Boy, which adds the borders, color palettes, and multiplayer support that some games have for the Super Game Boy.",
+        "This requires a Super Game Boy BIOS ROM. Game Boy Color games always run in the Game Boy core.",
+    ],
+};
+
 pub const ASPECT_RATIO: HelpText = HelpText {
     heading: "Aspect Ratio",
     text: &[
@@ -41,12 +50,29 @@ pub const DEINTERLACING: HelpText = HelpText {
     ],
 };
 
+pub const HI_RES_BLENDING: HelpText = HelpText {
+    heading: "Hi-Res Blending",
+    text: &[
+        "If enabled, blend horizontally adjacent pixels in frames that use the 512px hi-res or pseudo-hi-res modes.",
+        "Some games (e.g. Kirby's Dream Land 3 and Jurassic Park) draw alternating columns in pseudo-hi-res mode and rely on CRT blurring to blend them into transparency. This makes that effect visible, at the cost of slightly blurring true hi-res graphics.",
+    ],
+};
+
+pub const PRIORITY_OVERLAY: HelpText = HelpText {
+    heading: "Priority Overlay",
+    text: &[
+        "Debugging aid that tints every pixel based on which layer won priority resolution: BG1 is red, BG2 is green, BG3 is blue, BG4 is yellow, sprites are magenta, and the backdrop is black.",
+        "Useful for tracking down layer priority bugs in specific games.",
+    ],
+};
+
 pub const ADPCM_INTERPOLATION: HelpText = HelpText {
     heading: "ADPCM Sample Interpolation",
     text: &[
         "Configure the method used to interpolate between decoded ADPCM samples.",
         "Gaussian interpolation emulates how actual hardware interpolates between samples.",
         "Cubic Hermite interpolation uses a more advanced algorithm that usually creates a much sharper and less muffled sound, particularly in games with low sample rate audio.",
+        "No interpolation outputs the raw decoded samples, which produces a harsher and more aliased sound.",
     ],
 };
 
@@ -57,3 +83,11 @@ pub const AUDIO_TIMING_HACK: HelpText = HelpText {
         "Native framerate is approximately 60.0988 fps for NTSC and 50.007 fps for PAL.",
     ],
 };
+
+pub const SPC700_IPL: HelpText = HelpText {
+    heading: "SPC700 IPL ROM",
+    text: &[
+        "Optionally use a dumped copy of the SPC700 IPL boot ROM instead of the built-in copy. The IPL is the small program that the audio CPU runs at power-on to receive sound drivers from the main CPU.",
+        "The built-in copy is identical to the real IPL, so this is only useful for verifying a dump or for testing modified IPLs.",
+    ],
+};
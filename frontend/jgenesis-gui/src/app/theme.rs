@@ -0,0 +1,78 @@
+use egui::{Color32, Context, Stroke, Theme, Visuals};
+use jgenesis_native_config::AppConfig;
+use std::ops::RangeInclusive;
+
+pub(super) const UI_SCALE_RANGE: RangeInclusive<f32> = 0.5..=3.0;
+
+// Style settings that are applied on top of egui's default dark and light visuals
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct UiStyle {
+    accent_color: Option<(u8, u8, u8)>,
+    high_contrast: bool,
+    scale: f32,
+}
+
+impl UiStyle {
+    pub(super) fn from_config(config: &AppConfig) -> Self {
+        Self {
+            accent_color: config.egui_accent_color,
+            high_contrast: config.egui_high_contrast,
+            scale: config.egui_scale.clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end()),
+        }
+    }
+
+    pub(super) fn apply(self, ctx: &Context) {
+        for theme in [Theme::Dark, Theme::Light] {
+            let mut visuals = theme.default_visuals();
+
+            if self.high_contrast {
+                apply_high_contrast(&mut visuals, theme);
+            }
+
+            if let Some((r, g, b)) = self.accent_color {
+                apply_accent_color(&mut visuals, Color32::from_rgb(r, g, b));
+            }
+
+            ctx.set_visuals_of(theme, visuals);
+        }
+
+        ctx.set_zoom_factor(self.scale);
+    }
+}
+
+fn apply_high_contrast(visuals: &mut Visuals, theme: Theme) {
+    let (fg_color, bg_color) = match theme {
+        Theme::Dark => (Color32::WHITE, Color32::BLACK),
+        Theme::Light => (Color32::BLACK, Color32::WHITE),
+    };
+
+    visuals.override_text_color = Some(fg_color);
+    visuals.panel_fill = bg_color;
+    visuals.window_fill = bg_color;
+    visuals.extreme_bg_color = bg_color;
+    visuals.faint_bg_color = bg_color;
+    visuals.window_stroke = Stroke::new(1.0, fg_color);
+
+    let widgets = &mut visuals.widgets;
+    for widget in [
+        &mut widgets.noninteractive,
+        &mut widgets.inactive,
+        &mut widgets.hovered,
+        &mut widgets.active,
+        &mut widgets.open,
+    ] {
+        widget.fg_stroke.color = fg_color;
+        widget.bg_stroke = Stroke::new(widget.bg_stroke.width.max(1.0), fg_color);
+    }
+}
+
+fn apply_accent_color(visuals: &mut Visuals, accent_color: Color32) {
+    // Text drawn over the selection color needs to remain readable regardless of the accent color
+    let [r, g, b, _] = accent_color.to_array();
+    let luma = (77 * u32::from(r) + 150 * u32::from(g) + 29 * u32::from(b)) >> 8;
+    let text_color = if luma >= 128 { Color32::BLACK } else { Color32::WHITE };
+
+    visuals.selection.bg_fill = accent_color;
+    visuals.selection.stroke.color = text_color;
+    visuals.hyperlink_color = accent_color;
+}
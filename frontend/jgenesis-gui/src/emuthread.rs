@@ -4,19 +4,21 @@ use crate::app::Console;
 use crate::emuthread::inputwindow::InputWindow;
 use anyhow::anyhow;
 use jgenesis_native_config::AppConfig;
+use jgenesis_native_driver::config::SystemConfig;
+use jgenesis_native_driver::config::input::AnalogInputConfig;
 use jgenesis_native_driver::input::{
-    AxisDirection, GamepadAction, GenericInput, HatDirection, Joysticks,
+    AxisDirection, GamepadAction, GamepadAssignment, GenericInput, HatDirection, Joysticks,
+    axis_direction_pressed,
 };
 use jgenesis_native_driver::{
-    AudioError, Native32XEmulator, NativeEmulatorError, NativeEmulatorResult,
-    NativeGameBoyEmulator, NativeGenesisEmulator, NativeNesEmulator, NativeSegaCdEmulator,
-    NativeSmsGgEmulator, NativeSnesEmulator, NativeTickEffect, SaveStateMetadata,
+    AudioDeviceInfo, EmulatorCore, NativeEmulatorError, NativeTickEffect, PerformanceStats,
+    SaveStateMetadata, SegaCdBackupRam,
 };
-use jgenesis_proc_macros::MatchEachVariantMacro;
 use sdl2::EventPump;
 use sdl2::event::Event;
 use sdl2::joystick::{HatState, Joystick};
-use segacd_core::api::SegaCdLoadResult;
+use segacd_core::LoadProgress;
+use smsgg_core::SmsGgHardware;
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
@@ -71,7 +73,7 @@ impl EmuThreadStatus {
 impl Console {
     fn running_status(self) -> EmuThreadStatus {
         match self {
-            Self::MasterSystem | Self::GameGear => EmuThreadStatus::RunningSmsGg,
+            Self::MasterSystem | Self::GameGear | Self::Sg1000 => EmuThreadStatus::RunningSmsGg,
             Self::Genesis => EmuThreadStatus::RunningGenesis,
             Self::SegaCd => EmuThreadStatus::RunningSegaCd,
             Self::Sega32X => EmuThreadStatus::Running32X,
@@ -87,14 +89,19 @@ pub enum EmuThreadCommand {
     Run { console: Console, config: Box<AppConfig>, file_path: PathBuf },
     ReloadConfig(Box<AppConfig>, PathBuf),
     StopEmulator,
-    CollectInput { axis_deadzone: i16 },
+    CollectInput { analog_config: AnalogInputConfig },
     SoftReset,
     HardReset,
     OpenMemoryViewer,
+    SwapPlayerInputs,
+    ReinitAudio,
     SaveState { slot: usize },
     LoadState { slot: usize },
     SegaCdRemoveDisc,
     SegaCdChangeDisc(PathBuf),
+    SegaCdFormatBackupRam,
+    SegaCdImportBackupRam(SegaCdBackupRam, PathBuf),
+    SegaCdExportBackupRam { backup_ram: SegaCdBackupRam, path: PathBuf, len: usize },
 }
 
 pub struct EmuThreadHandle {
@@ -102,8 +109,12 @@ pub struct EmuThreadHandle {
     command_sender: Sender<EmuThreadCommand>,
     input_receiver: Receiver<Option<Vec<GenericInput>>>,
     save_state_metadata: Arc<Mutex<SaveStateMetadata>>,
+    gamepad_assignments: Arc<Mutex<Vec<GamepadAssignment>>>,
+    performance_stats: Arc<Mutex<Option<PerformanceStats>>>,
+    audio_device_info: Arc<Mutex<Option<AudioDeviceInfo>>>,
     emulator_error: Arc<Mutex<Option<NativeEmulatorError>>>,
     exit_signal: Arc<AtomicBool>,
+    disc_load_progress: Arc<LoadProgress>,
 }
 
 impl EmuThreadHandle {
@@ -119,6 +130,20 @@ impl EmuThreadHandle {
         self.save_state_metadata.lock().unwrap().clone()
     }
 
+    pub fn gamepad_assignments(&self) -> Vec<GamepadAssignment> {
+        self.gamepad_assignments.lock().unwrap().clone()
+    }
+
+    /// Performance stats for the running emulator, or `None` if no emulator is running.
+    pub fn performance_stats(&self) -> Option<PerformanceStats> {
+        *self.performance_stats.lock().unwrap()
+    }
+
+    /// Audio output device of the running emulator, or `None` if no emulator is running.
+    pub fn audio_device_info(&self) -> Option<AudioDeviceInfo> {
+        *self.audio_device_info.lock().unwrap()
+    }
+
     pub fn emulator_error(&self) -> Arc<Mutex<Option<NativeEmulatorError>>> {
         Arc::clone(&self.emulator_error)
     }
@@ -145,6 +170,10 @@ impl EmuThreadHandle {
     pub fn exit_signal(&self) -> bool {
         self.exit_signal.load(Ordering::Relaxed)
     }
+
+    pub fn disc_load_progress(&self) -> &LoadProgress {
+        &self.disc_load_progress
+    }
 }
 
 pub fn spawn(egui_ctx: egui::Context) -> EmuThreadHandle {
@@ -152,14 +181,22 @@ pub fn spawn(egui_ctx: egui::Context) -> EmuThreadHandle {
     let (command_sender, command_receiver) = mpsc::channel();
     let (input_sender, input_receiver) = mpsc::channel();
     let save_state_metadata = Arc::new(Mutex::new(SaveStateMetadata::default()));
+    let gamepad_assignments = Arc::new(Mutex::new(Vec::new()));
+    let performance_stats = Arc::new(Mutex::new(None));
+    let audio_device_info = Arc::new(Mutex::new(None));
     let emulator_error = Arc::new(Mutex::new(None));
     let exit_signal = Arc::new(AtomicBool::new(false));
+    let disc_load_progress = Arc::new(LoadProgress::new());
 
     {
         let status = Arc::clone(&status);
         let save_state_metadata = Arc::clone(&save_state_metadata);
+        let gamepad_assignments = Arc::clone(&gamepad_assignments);
+        let performance_stats = Arc::clone(&performance_stats);
+        let audio_device_info = Arc::clone(&audio_device_info);
         let emulator_error = Arc::clone(&emulator_error);
         let exit_signal = Arc::clone(&exit_signal);
+        let disc_load_progress = Arc::clone(&disc_load_progress);
         thread::spawn(move || {
             thread_run(EmuThreadContext {
                 egui_ctx,
@@ -167,8 +204,12 @@ pub fn spawn(egui_ctx: egui::Context) -> EmuThreadHandle {
                 input_sender,
                 status,
                 save_state_metadata,
+                gamepad_assignments,
+                performance_stats,
+                audio_device_info,
                 emulator_error,
                 exit_signal,
+                disc_load_progress,
             });
         });
     }
@@ -178,8 +219,12 @@ pub fn spawn(egui_ctx: egui::Context) -> EmuThreadHandle {
         command_sender,
         input_receiver,
         save_state_metadata,
+        gamepad_assignments,
+        performance_stats,
+        audio_device_info,
         emulator_error,
         exit_signal,
+        disc_load_progress,
     }
 }
 
@@ -189,8 +234,12 @@ struct EmuThreadContext {
     input_sender: Sender<Option<Vec<GenericInput>>>,
     status: Arc<AtomicU8>,
     save_state_metadata: Arc<Mutex<SaveStateMetadata>>,
+    gamepad_assignments: Arc<Mutex<Vec<GamepadAssignment>>>,
+    performance_stats: Arc<Mutex<Option<PerformanceStats>>>,
+    audio_device_info: Arc<Mutex<Option<AudioDeviceInfo>>>,
     emulator_error: Arc<Mutex<Option<NativeEmulatorError>>>,
     exit_signal: Arc<AtomicBool>,
+    disc_load_progress: Arc<LoadProgress>,
 }
 
 fn thread_run(ctx: EmuThreadContext) {
@@ -213,18 +262,30 @@ fn thread_run(ctx: EmuThreadContext) {
                     config.common.window_scale_factor = Some(native_ppi);
                 }
 
-                let emulator = match GenericEmulator::create(console, config, file_path) {
+                let mut system_config = system_config(console, &config, file_path);
+                if let SystemConfig::SegaCd(scd_config) = &mut system_config {
+                    scd_config.disc_load_progress = Some(Arc::clone(&ctx.disc_load_progress));
+                }
+
+                let mut emulator = match jgenesis_native_driver::create_emulator(system_config) {
                     Ok(emulator) => emulator,
+                    Err(NativeEmulatorError::SegaCdDisc(err)) if err.is_load_cancelled() => {
+                        log::info!("Disc loading cancelled");
+                        continue;
+                    }
                     Err(err) => {
                         log::error!("Error initializing emulator: {err}");
                         *ctx.emulator_error.lock().unwrap() = Some(err);
                         continue;
                     }
                 };
-                run_emulator(emulator, &ctx);
+                run_emulator(emulator.as_mut(), console, &ctx);
+                ctx.gamepad_assignments.lock().unwrap().clear();
+                *ctx.performance_stats.lock().unwrap() = None;
+                *ctx.audio_device_info.lock().unwrap() = None;
             }
-            Ok(EmuThreadCommand::CollectInput { axis_deadzone }) => {
-                match collect_input_not_running(axis_deadzone, ctx.egui_ctx.pixels_per_point()) {
+            Ok(EmuThreadCommand::CollectInput { analog_config }) => {
+                match collect_input_not_running(&analog_config, ctx.egui_ctx.pixels_per_point()) {
                     Ok(input) => {
                         ctx.input_sender.send(input).unwrap();
                         ctx.egui_ctx.request_repaint();
@@ -240,10 +301,15 @@ fn thread_run(ctx: EmuThreadContext) {
                 | EmuThreadCommand::SoftReset
                 | EmuThreadCommand::HardReset
                 | EmuThreadCommand::OpenMemoryViewer
+                | EmuThreadCommand::SwapPlayerInputs
+                | EmuThreadCommand::ReinitAudio
                 | EmuThreadCommand::SaveState { .. }
                 | EmuThreadCommand::LoadState { .. }
                 | EmuThreadCommand::SegaCdRemoveDisc
-                | EmuThreadCommand::SegaCdChangeDisc(_),
+                | EmuThreadCommand::SegaCdChangeDisc(_)
+                | EmuThreadCommand::SegaCdFormatBackupRam
+                | EmuThreadCommand::SegaCdImportBackupRam(..)
+                | EmuThreadCommand::SegaCdExportBackupRam { .. },
             ) => {}
             Err(err) => {
                 log::info!(
@@ -255,139 +321,52 @@ fn thread_run(ctx: EmuThreadContext) {
     }
 }
 
-#[derive(MatchEachVariantMacro)]
-enum GenericEmulator {
-    SmsGg(NativeSmsGgEmulator),
-    Genesis(NativeGenesisEmulator),
-    SegaCd(NativeSegaCdEmulator),
-    Sega32X(Native32XEmulator),
-    Nes(NativeNesEmulator),
-    Snes(NativeSnesEmulator),
-    GameBoy(NativeGameBoyEmulator),
-}
-
-impl GenericEmulator {
-    fn create(
-        console: Console,
-        config: Box<AppConfig>,
-        path: PathBuf,
-    ) -> NativeEmulatorResult<Self> {
-        let emulator = match console {
-            Console::MasterSystem | Console::GameGear => {
-                Self::SmsGg(jgenesis_native_driver::create_smsgg(config.smsgg_config(path))?)
-            }
-            Console::Genesis => {
-                Self::Genesis(jgenesis_native_driver::create_genesis(config.genesis_config(path))?)
-            }
-            Console::SegaCd => {
-                Self::SegaCd(jgenesis_native_driver::create_sega_cd(config.sega_cd_config(path))?)
-            }
-            Console::Sega32X => {
-                Self::Sega32X(jgenesis_native_driver::create_32x(config.sega_32x_config(path))?)
-            }
-            Console::Nes => Self::Nes(jgenesis_native_driver::create_nes(config.nes_config(path))?),
-            Console::Snes => {
-                Self::Snes(jgenesis_native_driver::create_snes(config.snes_config(path))?)
-            }
-            Console::GameBoy | Console::GameBoyColor => {
-                Self::GameBoy(jgenesis_native_driver::create_gb(config.gb_config(path))?)
-            }
-        };
-
-        Ok(emulator)
-    }
-
-    fn reload_config(&mut self, config: Box<AppConfig>, path: PathBuf) -> Result<(), AudioError> {
-        match self {
-            Self::SmsGg(emulator) => emulator.reload_smsgg_config(config.smsgg_config(path)),
-            Self::Genesis(emulator) => emulator.reload_genesis_config(config.genesis_config(path)),
-            Self::SegaCd(emulator) => emulator.reload_sega_cd_config(config.sega_cd_config(path)),
-            Self::Sega32X(emulator) => emulator.reload_32x_config(config.sega_32x_config(path)),
-            Self::Nes(emulator) => emulator.reload_nes_config(config.nes_config(path)),
-            Self::Snes(emulator) => emulator.reload_snes_config(config.snes_config(path)),
-            Self::GameBoy(emulator) => emulator.reload_gb_config(config.gb_config(path)),
-        }
-    }
-
-    fn remove_disc(&mut self) {
-        if let Self::SegaCd(emulator) = self {
-            emulator.remove_disc();
-        }
-    }
-
-    fn change_disc(&mut self, path: PathBuf) -> SegaCdLoadResult<()> {
-        if let Self::SegaCd(emulator) = self {
-            emulator.change_disc(path)?;
-        }
-
-        Ok(())
-    }
-
-    fn render_frame(&mut self) -> NativeEmulatorResult<Option<NativeTickEffect>> {
-        match_each_variant!(self, emulator => emulator.render_frame())
-    }
-
-    fn soft_reset(&mut self) {
-        match_each_variant!(self, emulator => emulator.soft_reset());
-    }
-
-    fn hard_reset(&mut self) {
-        match_each_variant!(self, emulator => emulator.hard_reset());
-    }
-
-    fn open_memory_viewer(&mut self) {
-        match_each_variant!(self, emulator => emulator.open_memory_viewer());
-    }
-
-    fn save_state(&mut self, slot: usize) {
-        if let Err(err) = match_each_variant!(self, emulator => emulator.save_state(slot)) {
-            log::error!("Failed to save state to slot {slot}: {err}");
-        }
-    }
-
-    fn load_state(&mut self, slot: usize) {
-        if let Err(err) = match_each_variant!(self, emulator => emulator.load_state(slot)) {
-            log::error!("Failed to load state from slot {slot}: {err}");
+fn system_config(console: Console, config: &AppConfig, path: PathBuf) -> SystemConfig {
+    match console {
+        Console::MasterSystem | Console::GameGear => SystemConfig::SmsGg(config.smsgg_config(path)),
+        Console::Sg1000 => {
+            let mut smsgg_config = config.smsgg_config(path);
+            smsgg_config.forced_hardware = Some(SmsGgHardware::Sg1000);
+            SystemConfig::SmsGg(smsgg_config)
         }
-    }
-
-    fn save_state_metadata(&self) -> SaveStateMetadata {
-        match_each_variant!(self, emulator => emulator.save_state_metadata().clone())
-    }
-
-    fn focus(&mut self) {
-        match_each_variant!(self, emulator => emulator.focus());
-    }
-
-    fn event_pump_and_joysticks_mut(&mut self) -> (&mut EventPump, &mut Joysticks) {
-        match_each_variant!(self, emulator => emulator.event_pump_and_joysticks_mut())
+        Console::Genesis => SystemConfig::Genesis(config.genesis_config(path)),
+        Console::SegaCd => SystemConfig::SegaCd(config.sega_cd_config(path)),
+        Console::Sega32X => SystemConfig::Sega32X(config.sega_32x_config(path)),
+        Console::Nes => SystemConfig::Nes(config.nes_config(path)),
+        Console::Snes => SystemConfig::Snes(config.snes_config(path)),
+        Console::GameBoy | Console::GameBoyColor => SystemConfig::GameBoy(config.gb_config(path)),
     }
 }
 
-fn run_emulator(mut emulator: GenericEmulator, ctx: &EmuThreadContext) {
+fn run_emulator(emulator: &mut dyn EmulatorCore, console: Console, ctx: &EmuThreadContext) {
     loop {
         match emulator.render_frame() {
             Ok(None) => {
-                *ctx.save_state_metadata.lock().unwrap() = emulator.save_state_metadata();
+                *ctx.save_state_metadata.lock().unwrap() = emulator.save_state_metadata().clone();
+                *ctx.gamepad_assignments.lock().unwrap() = emulator.gamepad_assignments();
+                *ctx.performance_stats.lock().unwrap() = Some(emulator.performance_stats());
+                *ctx.audio_device_info.lock().unwrap() = Some(emulator.audio_device_info());
 
                 while let Ok(command) = ctx.command_receiver.try_recv() {
                     match command {
                         EmuThreadCommand::ReloadConfig(config, path) => {
-                            if let Err(err) = emulator.reload_config(config, path) {
+                            let system_config = system_config(console, &config, path);
+                            if let Err(err) = emulator.reload_config(system_config) {
                                 *ctx.emulator_error.lock().unwrap() = Some(err.into());
                                 return;
                             }
                         }
                         EmuThreadCommand::StopEmulator => {
                             log::info!("Stopping emulator");
+                            emulator.save_auto_state();
                             return;
                         }
-                        EmuThreadCommand::CollectInput { axis_deadzone } => {
+                        EmuThreadCommand::CollectInput { analog_config } => {
                             log::debug!("Received collect input command");
 
                             emulator.focus();
                             let (event_pump, joysticks) = emulator.event_pump_and_joysticks_mut();
-                            let input = collect_input(event_pump, joysticks, axis_deadzone, None);
+                            let input = collect_input(event_pump, joysticks, &analog_config, None);
 
                             let is_none = input.is_none();
 
@@ -403,15 +382,42 @@ fn run_emulator(mut emulator: GenericEmulator, ctx: &EmuThreadContext) {
                         EmuThreadCommand::SoftReset => emulator.soft_reset(),
                         EmuThreadCommand::HardReset => emulator.hard_reset(),
                         EmuThreadCommand::OpenMemoryViewer => emulator.open_memory_viewer(),
-                        EmuThreadCommand::SaveState { slot } => emulator.save_state(slot),
-                        EmuThreadCommand::LoadState { slot } => emulator.load_state(slot),
+                        EmuThreadCommand::SwapPlayerInputs => emulator.swap_player_inputs(),
+                        EmuThreadCommand::ReinitAudio => emulator.reinit_audio(),
+                        EmuThreadCommand::SaveState { slot } => {
+                            if let Err(err) = emulator.save_state(slot) {
+                                log::error!("Failed to save state to slot {slot}: {err}");
+                            }
+                        }
+                        EmuThreadCommand::LoadState { slot } => {
+                            if let Err(err) = emulator.load_state(slot) {
+                                log::error!("Failed to load state from slot {slot}: {err}");
+                            }
+                        }
                         EmuThreadCommand::SegaCdRemoveDisc => emulator.remove_disc(),
                         EmuThreadCommand::SegaCdChangeDisc(path) => {
-                            if let Err(err) = emulator.change_disc(path) {
+                            if let Err(err) = emulator.change_disc(&path) {
                                 *ctx.emulator_error.lock().unwrap() = Some(err.into());
                                 return;
                             }
                         }
+                        EmuThreadCommand::SegaCdFormatBackupRam => emulator.format_backup_ram(),
+                        EmuThreadCommand::SegaCdImportBackupRam(backup_ram, path) => {
+                            if let Err(err) = emulator.import_backup_ram(backup_ram, &path) {
+                                log::error!(
+                                    "Failed to import backup RAM from '{}': {err}",
+                                    path.display()
+                                );
+                            }
+                        }
+                        EmuThreadCommand::SegaCdExportBackupRam { backup_ram, path, len } => {
+                            if let Err(err) = emulator.export_backup_ram(backup_ram, &path, len) {
+                                log::error!(
+                                    "Failed to export backup RAM to '{}': {err}",
+                                    path.display()
+                                );
+                            }
+                        }
                         EmuThreadCommand::Run { .. } => {}
                     }
                 }
@@ -433,7 +439,7 @@ fn run_emulator(mut emulator: GenericEmulator, ctx: &EmuThreadContext) {
 }
 
 fn collect_input_not_running(
-    axis_deadzone: i16,
+    analog_config: &AnalogInputConfig,
     scale_factor: f32,
 ) -> anyhow::Result<Option<Vec<GenericInput>>> {
     let sdl = sdl2::init().map_err(|err| anyhow!("Error initializing SDL2: {err}"))?;
@@ -457,7 +463,7 @@ fn collect_input_not_running(
 
     let mut joysticks = Joysticks::new(joystick_subsystem);
 
-    let input = collect_input(&mut event_pump, &mut joysticks, axis_deadzone, Some(window));
+    let input = collect_input(&mut event_pump, &mut joysticks, analog_config, Some(window));
 
     for _ in event_pump.poll_iter() {}
 
@@ -494,22 +500,27 @@ struct CollectedInputs {
 }
 
 impl CollectedInputs {
-    fn new(joysticks: &Joysticks, axis_deadzone: i16) -> Self {
+    fn new(joysticks: &Joysticks, analog_config: &AnalogInputConfig) -> Self {
         let gamepad_starting_states = joysticks
             .all_devices()
             .flat_map(|(device_id, joystick)| {
-                joystick_starting_state(device_id, joystick, axis_deadzone)
+                joystick_starting_state(device_id, joystick, analog_config)
             })
             .collect();
 
         Self { inputs: VecSet::new(), gamepad_starting_states }
     }
 
-    fn add_device(&mut self, device_id: u32, joystick: &Joystick, axis_deadzone: i16) {
+    fn add_device(
+        &mut self,
+        device_id: u32,
+        joystick: &Joystick,
+        analog_config: &AnalogInputConfig,
+    ) {
         self.gamepad_starting_states.extend(joystick_starting_state(
             device_id,
             joystick,
-            axis_deadzone,
+            analog_config,
         ));
     }
 
@@ -557,10 +568,10 @@ fn opposite_input(input: GenericInput) -> Option<GenericInput> {
 fn collect_input(
     event_pump: &mut EventPump,
     joysticks: &mut Joysticks,
-    axis_deadzone: i16,
+    analog_config: &AnalogInputConfig,
     mut window: Option<InputWindow>,
 ) -> Option<Vec<GenericInput>> {
-    let mut inputs = CollectedInputs::new(joysticks, axis_deadzone);
+    let mut inputs = CollectedInputs::new(joysticks, analog_config);
 
     loop {
         for event in event_pump.poll_iter() {
@@ -588,7 +599,7 @@ fn collect_input(
                     }
 
                     if let Some(joystick) = joysticks.device(device_id) {
-                        inputs.add_device(device_id, joystick, axis_deadzone);
+                        inputs.add_device(device_id, joystick, analog_config);
                     }
                 }
                 Event::JoyDeviceRemoved { which: instance_id, .. } => {
@@ -610,9 +621,8 @@ fn collect_input(
                         continue;
                     };
 
-                    let pressed = value.saturating_abs() > axis_deadzone;
-                    if pressed {
-                        let direction = AxisDirection::from_value(value);
+                    let axis_config = analog_config.axis(axis_idx);
+                    if let Some(direction) = axis_direction_pressed(axis_config, value) {
                         if inputs.insert(GenericInput::Gamepad {
                             gamepad_idx,
                             action: GamepadAction::Axis(axis_idx, direction),
@@ -694,13 +704,13 @@ fn hat_direction_for(state: HatState) -> Option<HatDirection> {
     }
 }
 
-fn joystick_starting_state(
+fn joystick_starting_state<'a>(
     device_id: u32,
-    joystick: &Joystick,
-    axis_deadzone: i16,
-) -> impl Iterator<Item = GenericInput> + use<'_> {
+    joystick: &'a Joystick,
+    analog_config: &'a AnalogInputConfig,
+) -> impl Iterator<Item = GenericInput> + use<'a> {
     buttons_starting_state(device_id, joystick)
-        .chain(axes_starting_state(device_id, joystick, axis_deadzone))
+        .chain(axes_starting_state(device_id, joystick, analog_config))
         .chain(hats_starting_state(device_id, joystick))
 }
 
@@ -717,18 +727,15 @@ fn buttons_starting_state(
     })
 }
 
-fn axes_starting_state(
+fn axes_starting_state<'a>(
     gamepad_idx: u32,
-    joystick: &Joystick,
-    deadzone: i16,
-) -> impl Iterator<Item = GenericInput> + use<'_> {
+    joystick: &'a Joystick,
+    analog_config: &'a AnalogInputConfig,
+) -> impl Iterator<Item = GenericInput> + use<'a> {
     (0..joystick.num_axes()).filter_map(move |axis_idx| {
         let axis_value = joystick.axis(axis_idx).ok()?;
-        if axis_value.saturating_abs() < deadzone {
-            return None;
-        }
+        let direction = axis_direction_pressed(analog_config.axis(axis_idx as u8), axis_value)?;
 
-        let direction = AxisDirection::from_value(axis_value);
         Some(GenericInput::Gamepad {
             gamepad_idx,
             action: GamepadAction::Axis(axis_idx as u8, direction),
@@ -1,3 +1,4 @@
 pub mod app;
 mod emuthread;
+pub mod logging;
 mod widgets;
@@ -0,0 +1,252 @@
+//! In-memory capture of `log` output for the GUI's log window.
+//!
+//! Everything is still written to stderr by `env_logger` using the `RUST_LOG` filters. Captured
+//! records use a separate set of per-module filters that can be changed while the application is
+//! running.
+
+use env_logger::Env;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+const DEFAULT_CAPACITY: usize = 10_000;
+
+static CAPTURE: OnceLock<LogCapture> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub(crate) struct CapturedRecord {
+    /// Time since logging was initialized
+    pub(crate) elapsed: Duration,
+    pub(crate) level: Level,
+    pub(crate) target: String,
+    pub(crate) message: String,
+}
+
+impl CapturedRecord {
+    #[must_use]
+    pub(crate) fn format(&self) -> String {
+        format!(
+            "[{:>10.3}] {:<5} {}: {}",
+            self.elapsed.as_secs_f64(),
+            self.level,
+            self.target,
+            self.message
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ModuleFilter {
+    /// Module path prefix, e.g. `segacd_core::cddrive`
+    pub(crate) module: String,
+    pub(crate) level: LevelFilter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CaptureFilters {
+    pub(crate) default_level: LevelFilter,
+    pub(crate) modules: Vec<ModuleFilter>,
+}
+
+impl Default for CaptureFilters {
+    fn default() -> Self {
+        // Same as the default stderr filters
+        Self {
+            default_level: LevelFilter::Info,
+            modules: vec![
+                ModuleFilter { module: "wgpu_core".into(), level: LevelFilter::Warn },
+                ModuleFilter { module: "wgpu_hal".into(), level: LevelFilter::Warn },
+            ],
+        }
+    }
+}
+
+impl CaptureFilters {
+    /// The most verbose level that is enabled for the given target. If more than one module filter
+    /// matches the target, the longest (most specific) one is used.
+    #[must_use]
+    pub(crate) fn level_for(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .filter(|filter| {
+                let module = filter.module.trim();
+                !module.is_empty()
+                    && target
+                        .strip_prefix(module)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|filter| filter.module.trim().len())
+            .map_or(self.default_level, |filter| filter.level)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.modules.iter().map(|filter| filter.level).fold(self.default_level, Ord::max)
+    }
+}
+
+#[derive(Debug, Default)]
+struct RecordBuffer {
+    records: VecDeque<CapturedRecord>,
+    // Incremented on every change so that viewers can tell when their cached copy is stale
+    generation: u64,
+}
+
+#[derive(Debug)]
+pub(crate) struct LogCapture {
+    start: Instant,
+    capacity: usize,
+    stderr_max_level: LevelFilter,
+    buffer: Mutex<RecordBuffer>,
+    filters: RwLock<CaptureFilters>,
+}
+
+impl LogCapture {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.filters.read().unwrap().level_for(metadata.target())
+    }
+
+    fn push(&self, record: &Record<'_>) {
+        let captured = CapturedRecord {
+            elapsed: self.start.elapsed(),
+            level: record.level(),
+            target: record.target().into(),
+            message: record.args().to_string(),
+        };
+
+        let mut buffer = self.buffer.lock().unwrap();
+        if buffer.records.len() == self.capacity {
+            buffer.records.pop_front();
+        }
+        buffer.records.push_back(captured);
+        buffer.generation += 1;
+    }
+
+    /// Returns a copy of all captured records if anything has changed since `generation`, along
+    /// with the new generation.
+    #[must_use]
+    pub(crate) fn records_since(&self, generation: u64) -> Option<(Vec<CapturedRecord>, u64)> {
+        let buffer = self.buffer.lock().unwrap();
+        (buffer.generation != generation)
+            .then(|| (buffer.records.iter().cloned().collect(), buffer.generation))
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut buffer = self.buffer.lock().unwrap();
+        buffer.records.clear();
+        buffer.generation += 1;
+    }
+
+    #[must_use]
+    pub(crate) fn filters(&self) -> CaptureFilters {
+        self.filters.read().unwrap().clone()
+    }
+
+    /// Change which records are captured. This only affects records logged after the change.
+    pub(crate) fn set_filters(&self, filters: CaptureFilters) {
+        log::set_max_level(self.stderr_max_level.max(filters.max_level()));
+        *self.filters.write().unwrap() = filters;
+    }
+
+    /// Write all captured records to a text file.
+    ///
+    /// # Errors
+    ///
+    /// Propagates any I/O errors encountered while writing the file.
+    pub(crate) fn save_to_file(&self, path: &Path) -> io::Result<()> {
+        // Copy first so that logging from other threads isn't blocked on file I/O
+        let records: Vec<_> = self.buffer.lock().unwrap().records.iter().cloned().collect();
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "jgenesis {}", env!("CARGO_PKG_VERSION"))?;
+        for record in &records {
+            writeln!(writer, "{}", record.format())?;
+        }
+        writer.flush()
+    }
+}
+
+struct CapturingLogger {
+    stderr: env_logger::Logger,
+    capture: &'static LogCapture,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.stderr.enabled(metadata) || self.capture.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.stderr.matches(record) {
+            self.stderr.log(record);
+        }
+
+        if self.capture.enabled(record.metadata()) {
+            self.capture.push(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.stderr.flush();
+    }
+}
+
+/// Initialize the global logger, which writes to stderr using filters from the given environment
+/// and also captures records in memory for the log window.
+///
+/// # Panics
+///
+/// This function will panic if called more than once or if another global logger has already been
+/// set.
+pub fn init(env: Env<'_>) {
+    let stderr = env_logger::Builder::from_env(env).build();
+    let stderr_max_level = stderr.filter();
+
+    let filters = CaptureFilters::default();
+    let max_level = stderr_max_level.max(filters.max_level());
+
+    let capture = CAPTURE.get_or_init(|| LogCapture {
+        start: Instant::now(),
+        capacity: DEFAULT_CAPACITY,
+        stderr_max_level,
+        buffer: Mutex::default(),
+        filters: RwLock::new(filters),
+    });
+
+    log::set_boxed_logger(Box::new(CapturingLogger { stderr, capture }))
+        .expect("Global logger should only be initialized once");
+    log::set_max_level(max_level);
+}
+
+/// The global log capture, or `None` if [`init`] has not been called.
+#[must_use]
+pub(crate) fn capture() -> Option<&'static LogCapture> {
+    CAPTURE.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn most_specific_module_filter() {
+        let filters = CaptureFilters {
+            default_level: LevelFilter::Warn,
+            modules: vec![
+                ModuleFilter { module: "segacd_core".into(), level: LevelFilter::Info },
+                ModuleFilter { module: "segacd_core::cddrive".into(), level: LevelFilter::Trace },
+            ],
+        };
+
+        assert_eq!(filters.level_for("segacd_core::cddrive::cdc"), LevelFilter::Trace);
+        assert_eq!(filters.level_for("segacd_core::cddrive"), LevelFilter::Trace);
+        assert_eq!(filters.level_for("segacd_core::api"), LevelFilter::Info);
+        assert_eq!(filters.level_for("segacd_core_extra"), LevelFilter::Warn);
+        assert_eq!(filters.level_for("genesis_core"), LevelFilter::Warn);
+        assert_eq!(filters.max_level(), LevelFilter::Trace);
+    }
+}
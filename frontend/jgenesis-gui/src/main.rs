@@ -85,10 +85,9 @@ fn steam_deck_dpi_hack() {
 }
 
 fn main() -> eframe::Result<()> {
-    env_logger::Builder::from_env(
+    jgenesis_gui::logging::init(
         Env::default().default_filter_or("info,wgpu_core=warn,wgpu_hal=warn"),
-    )
-    .init();
+    );
 
     let args = Args::parse().fix_appimage_relative_paths();
 
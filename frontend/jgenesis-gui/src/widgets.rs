@@ -1,6 +1,8 @@
 use egui::{Response, Slider, Ui, Widget};
+use jgenesis_common::clockratio::ClockRatio;
 use jgenesis_native_config::common::ConfigSavePath;
 use rfd::FileDialog;
+use std::num::{NonZeroU32, NonZeroU64};
 use std::ops::RangeInclusive;
 use std::path::PathBuf;
 
@@ -48,28 +50,90 @@ impl Widget for SavePathSelect<'_> {
     }
 }
 
+/// Whether an overclock setting divides or multiplies the clock that a processor runs at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockScaling {
+    Divider,
+    Multiplier,
+}
+
+/// Setting types that can be edited with an [`OverclockSlider`].
+pub trait OverclockValue: Copy {
+    const INTEGRAL: bool;
+
+    fn to_f64(self) -> f64;
+
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! impl_overclock_value_numeric {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl OverclockValue for $t {
+                const INTEGRAL: bool = <$t as emath::Numeric>::INTEGRAL;
+
+                fn to_f64(self) -> f64 {
+                    emath::Numeric::to_f64(self)
+                }
+
+                fn from_f64(value: f64) -> Self {
+                    <$t as emath::Numeric>::from_f64(value)
+                }
+            }
+        )*
+    }
+}
+
+impl_overclock_value_numeric!(u64, NonZeroU32, NonZeroU64);
+
+impl OverclockValue for ClockRatio {
+    const INTEGRAL: bool = false;
+
+    fn to_f64(self) -> f64 {
+        self.to_f64()
+    }
+
+    fn from_f64(value: f64) -> Self {
+        ClockRatio::from_f64(value).unwrap_or(ClockRatio::from_integer(1))
+    }
+}
+
 pub struct OverclockSlider<'a, Num> {
     pub label: &'a str,
     pub current_value: &'a mut Num,
     pub range: RangeInclusive<Num>,
+    /// Clock that the divider or multiplier applies to, in Hz
     pub master_clock: f64,
-    pub default_divider: f64,
+    /// Divider or multiplier that matches actual hardware
+    pub native_value: f64,
+    pub scaling: ClockScaling,
 }
 
-impl<Num: emath::Numeric> Widget for OverclockSlider<'_, Num> {
+impl<Num: OverclockValue> Widget for OverclockSlider<'_, Num> {
     fn ui(self, ui: &mut Ui) -> Response {
         ui.group(|ui| {
             ui.label(self.label);
 
-            ui.add(Slider::new(self.current_value, self.range));
+            let mut value = self.current_value.to_f64();
+            let range = self.range.start().to_f64()..=self.range.end().to_f64();
+            let slider = if Num::INTEGRAL {
+                Slider::new(&mut value, range).integer()
+            } else {
+                Slider::new(&mut value, range).step_by(ClockRatio::STEP).fixed_decimals(2)
+            };
+            if ui.add(slider).changed() {
+                *self.current_value = Num::from_f64(value);
+            }
 
-            let current_divider = self.current_value.to_f64();
-            let effective_speed_ratio = 100.0 * self.default_divider / current_divider;
-            let effective_speed_mhz = self.master_clock / current_divider / 1_000_000.0;
+            let (effective_speed_ratio, effective_speed_hz) = match self.scaling {
+                ClockScaling::Divider => (self.native_value / value, self.master_clock / value),
+                ClockScaling::Multiplier => (value / self.native_value, self.master_clock * value),
+            };
+            let effective_speed_mhz = effective_speed_hz / 1_000_000.0;
 
             ui.label(format!(
                 "Effective speed: {effective_speed_mhz:.2} MHz ({}%)",
-                effective_speed_ratio.round()
+                (100.0 * effective_speed_ratio).round()
             ));
         })
         .response
@@ -1,11 +1,11 @@
 use crate::AppConfig;
 use jgenesis_native_driver::config::{
-    CommonConfig, FullscreenMode, HideMouseCursor, SavePath, WindowSize,
+    CommonConfig, FullscreenMode, HideMouseCursor, InputViewerSkin, SavePath, SyncMode, WindowSize,
 };
 use jgenesis_proc_macros::{EnumAll, EnumDisplay};
 use jgenesis_renderer::config::{
-    FilterMode, PreprocessShader, PrescaleFactor, PrescaleMode, RendererConfig, Scanlines,
-    VSyncMode, WgpuBackend,
+    FilterMode, FrameInsertion, OverlayPosition, PreprocessShader, PrescaleFactor, PrescaleMode,
+    RefreshMultiplier, RendererConfig, Scanlines, VSyncMode, WgpuBackend,
 };
 use serde::{Deserialize, Serialize};
 use std::num::NonZeroU32;
@@ -26,6 +26,8 @@ pub enum ConfigSavePath {
 pub struct CommonAppConfig {
     #[serde(default = "default_audio_output_frequency")]
     pub audio_output_frequency: u64,
+    #[serde(default)]
+    pub sync_mode: SyncMode,
     #[serde(default = "true_fn")]
     pub audio_sync: bool,
     #[serde(default = "true_fn")]
@@ -36,6 +38,10 @@ pub struct CommonAppConfig {
     pub audio_buffer_size: u32,
     #[serde(default)]
     pub audio_gain_db: f64,
+    #[serde(default = "default_audio_volume_percent")]
+    pub audio_volume_percent: u8,
+    #[serde(default)]
+    pub audio_muted: bool,
     #[serde(default)]
     pub save_path: ConfigSavePath,
     #[serde(default = "default_custom_save_path")]
@@ -58,6 +64,10 @@ pub struct CommonAppConfig {
     #[serde(default = "true_fn")]
     pub frame_time_sync: bool,
     #[serde(default)]
+    pub decoupled_frame_presentation: bool,
+    #[serde(default)]
+    pub round_frame_rate: bool,
+    #[serde(default)]
     pub auto_prescale: bool,
     #[serde(default = "default_prescale_factor")]
     pub prescale_factor: PrescaleFactor,
@@ -70,13 +80,37 @@ pub struct CommonAppConfig {
     #[serde(default)]
     pub preprocess_shader: PreprocessShader,
     #[serde(default)]
+    pub frame_insertion: FrameInsertion,
+    #[serde(default)]
+    pub refresh_multiplier: RefreshMultiplier,
+    #[serde(default)]
     pub load_recent_state_at_launch: bool,
+    #[serde(default)]
+    pub auto_save_state: bool,
+    #[serde(default)]
+    pub periodic_auto_save_state: bool,
+    #[serde(default = "default_auto_save_state_interval")]
+    pub auto_save_state_interval_minutes: u64,
     #[serde(default = "default_fast_forward_multiplier")]
     pub fast_forward_multiplier: u64,
     #[serde(default = "default_rewind_buffer_length")]
     pub rewind_buffer_length_seconds: u64,
     #[serde(default)]
     pub hide_mouse_cursor: HideMouseCursor,
+    #[serde(default)]
+    pub input_viewer_enabled: bool,
+    #[serde(default)]
+    pub input_viewer_position: OverlayPosition,
+    #[serde(default)]
+    pub input_viewer_skin: InputViewerSkin,
+    #[serde(default)]
+    pub purist_mode: bool,
+    #[serde(default)]
+    pub discord_presence_enabled: bool,
+    #[serde(default)]
+    pub pause_when_unfocused: bool,
+    #[serde(default)]
+    pub pause_on_controller_disconnect: bool,
 }
 
 impl CommonAppConfig {
@@ -111,6 +145,10 @@ const fn default_audio_buffer_size() -> u32 {
     2048
 }
 
+const fn default_audio_volume_percent() -> u8 {
+    100
+}
+
 fn default_custom_path(subdir: &str) -> PathBuf {
     let Some(base_dirs) = directories::BaseDirs::new() else {
         log::error!("Unable to determine user base directories for default custom paths");
@@ -140,17 +178,26 @@ fn default_rewind_buffer_length() -> u64 {
     10
 }
 
+fn default_auto_save_state_interval() -> u64 {
+    5
+}
+
 impl AppConfig {
     #[must_use]
-    pub fn common_config(&self, path: PathBuf) -> CommonConfig {
+    pub fn common_config(&self, path: PathBuf, volume_offset_db: i8) -> CommonConfig {
         CommonConfig {
             rom_file_path: path,
             audio_output_frequency: self.common.audio_output_frequency,
+            sync_mode: self.common.sync_mode,
             audio_sync: self.common.audio_sync,
             audio_dynamic_resampling_ratio: self.common.audio_dynamic_resampling_ratio,
             audio_hardware_queue_size: self.common.audio_hardware_queue_size,
             audio_buffer_size: self.common.audio_buffer_size,
             audio_gain_db: self.common.audio_gain_db,
+            audio_volume_percent: self.common.audio_volume_percent,
+            audio_volume_offset_db: volume_offset_db.into(),
+            audio_muted: self.common.audio_muted,
+            round_frame_rate: self.common.round_frame_rate,
             save_path: save_path(self.common.save_path, &self.common.custom_save_path),
             state_path: save_path(self.common.state_path, &self.common.custom_state_path),
             window_size: self.common.window_size(),
@@ -159,6 +206,7 @@ impl AppConfig {
                 wgpu_backend: self.common.wgpu_backend,
                 vsync_mode: self.common.vsync_mode,
                 frame_time_sync: self.common.frame_time_sync,
+                decoupled_frame_presentation: self.common.decoupled_frame_presentation,
                 prescale_mode: if self.common.auto_prescale {
                     PrescaleMode::Auto
                 } else {
@@ -168,16 +216,32 @@ impl AppConfig {
                 force_integer_height_scaling: self.common.force_integer_height_scaling,
                 filter_mode: self.common.filter_mode,
                 preprocess_shader: self.common.preprocess_shader,
+                ntsc_filter: false,
+                frame_insertion: self.common.frame_insertion,
+                refresh_multiplier: self.common.refresh_multiplier,
                 use_webgl2_limits: false,
             },
             fast_forward_multiplier: self.common.fast_forward_multiplier,
             rewind_buffer_length_seconds: self.common.rewind_buffer_length_seconds,
             load_recent_state_at_launch: self.common.load_recent_state_at_launch,
+            auto_save_state: self.common.auto_save_state,
+            periodic_auto_save_state: self.common.periodic_auto_save_state,
+            auto_save_state_interval_minutes: self.common.auto_save_state_interval_minutes,
             launch_in_fullscreen: self.common.launch_in_fullscreen,
             fullscreen_mode: self.common.fullscreen_mode,
-            axis_deadzone: self.input.axis_deadzone,
+            analog_config: self.input.analog.clone(),
+            gamepad_locks: self.input.gamepad_locks.clone(),
+            background_gamepad_input: self.input.background_gamepad_input,
+            subframe_input: self.input.subframe_input,
             hotkey_config: self.input.hotkeys.clone(),
             hide_mouse_cursor: self.common.hide_mouse_cursor,
+            input_viewer_enabled: self.common.input_viewer_enabled,
+            input_viewer_position: self.common.input_viewer_position,
+            input_viewer_skin: self.common.input_viewer_skin,
+            purist_mode: self.common.purist_mode,
+            discord_presence_enabled: self.common.discord_presence_enabled,
+            pause_when_unfocused: self.common.pause_when_unfocused,
+            pause_on_controller_disconnect: self.common.pause_on_controller_disconnect,
         }
     }
 }
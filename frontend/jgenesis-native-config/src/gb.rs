@@ -20,6 +20,8 @@ pub struct GameBoyAppConfig {
     pub gbc_color_correction: GbcColorCorrection,
     #[serde(default)]
     pub audio_60hz_hack: bool,
+    #[serde(default)]
+    pub volume_offset_db: i8,
 }
 
 #[must_use]
@@ -38,7 +40,7 @@ impl AppConfig {
     #[must_use]
     pub fn gb_config(&self, path: PathBuf) -> Box<GameBoyConfig> {
         Box::new(GameBoyConfig {
-            common: self.common_config(path),
+            common: self.common_config(path, self.game_boy.volume_offset_db),
             inputs: self.input.game_boy.clone(),
             emulator_config: GameBoyEmulatorConfig {
                 force_dmg_mode: self.game_boy.force_dmg_mode,
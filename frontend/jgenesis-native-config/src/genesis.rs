@@ -1,4 +1,5 @@
 use crate::AppConfig;
+use crate::overrides::RegionOverride;
 use genesis_core::{
     GenesisAspectRatio, GenesisEmulatorConfig, GenesisLowPassFilter, GenesisRegion,
 };
@@ -26,12 +27,16 @@ pub struct GenesisAppConfig {
     pub m68k_clock_divider: u64,
     #[serde(default)]
     pub emulate_non_linear_vdp_dac: bool,
+    #[serde(default)]
+    pub expanded_vram: bool,
     #[serde(default = "true_fn")]
     pub deinterlace: bool,
     #[serde(default)]
     pub render_vertical_border: bool,
     #[serde(default)]
     pub render_horizontal_border: bool,
+    #[serde(default)]
+    pub ntsc_composite_filter: bool,
     #[serde(default = "true_fn")]
     pub plane_a_enabled: bool,
     #[serde(default = "true_fn")]
@@ -52,6 +57,8 @@ pub struct GenesisAppConfig {
     pub ym2612_enabled: bool,
     #[serde(default = "true_fn")]
     pub psg_enabled: bool,
+    #[serde(default)]
+    pub volume_offset_db: i8,
 }
 
 const fn true_fn() -> bool {
@@ -76,7 +83,17 @@ pub struct SegaCdAppConfig {
     #[serde(default = "true_fn")]
     pub enable_ram_cartridge: bool,
     #[serde(default)]
+    pub backup_ram_write_protected: bool,
+    #[serde(default)]
     pub load_disc_into_ram: bool,
+    #[serde(default = "true_fn")]
+    pub disc_cache_enabled: bool,
+    #[serde(default = "default_disc_cache_size_mb")]
+    pub disc_cache_size_mb: u16,
+    #[serde(default = "default_disc_prefetch_sectors")]
+    pub disc_prefetch_sectors: u16,
+    #[serde(default)]
+    pub fast_boot: bool,
     #[serde(default = "default_drive_speed")]
     pub disc_drive_speed: NonZeroU16,
     #[serde(default = "default_sub_divider")]
@@ -91,12 +108,22 @@ pub struct SegaCdAppConfig {
     pub pcm_enabled: bool,
     #[serde(default = "true_fn")]
     pub cd_audio_enabled: bool,
+    #[serde(default)]
+    pub volume_offset_db: i8,
 }
 
 fn default_drive_speed() -> NonZeroU16 {
     NonZeroU16::new(1).unwrap()
 }
 
+fn default_disc_cache_size_mb() -> u16 {
+    segacd_core::api::DEFAULT_DISC_CACHE_SIZE_MB
+}
+
+fn default_disc_prefetch_sectors() -> u16 {
+    segacd_core::api::DEFAULT_DISC_PREFETCH_SECTORS
+}
+
 fn default_sub_divider() -> NonZeroU64 {
     NonZeroU64::new(segacd_core::api::DEFAULT_SUB_CPU_DIVIDER).unwrap()
 }
@@ -115,6 +142,8 @@ pub struct Sega32XAppConfig {
     pub apply_genesis_lpf_to_pwm: bool,
     #[serde(default = "true_fn")]
     pub pwm_enabled: bool,
+    #[serde(default)]
+    pub volume_offset_db: i8,
 }
 
 impl Default for Sega32XAppConfig {
@@ -126,14 +155,26 @@ impl Default for Sega32XAppConfig {
 impl AppConfig {
     #[must_use]
     pub fn genesis_config(&self, path: PathBuf) -> Box<GenesisConfig> {
-        Box::new(GenesisConfig {
-            common: self.common_config(path),
+        let region_override = self.region_override(&path);
+        Box::new(self.genesis_config_with_override(path, region_override))
+    }
+
+    fn genesis_config_with_override(
+        &self,
+        path: PathBuf,
+        region_override: RegionOverride,
+    ) -> GenesisConfig {
+        let mut common_config = self.common_config(path, self.genesis.volume_offset_db);
+        common_config.renderer_config.ntsc_filter = self.genesis.ntsc_composite_filter;
+
+        GenesisConfig {
+            common: common_config,
             inputs: self.input.genesis.clone(),
             emulator_config: GenesisEmulatorConfig {
                 p1_controller_type: self.input.genesis.p1_type,
                 p2_controller_type: self.input.genesis.p2_type,
-                forced_timing_mode: self.genesis.forced_timing_mode,
-                forced_region: self.genesis.forced_region,
+                forced_timing_mode: region_override.timing_mode.or(self.genesis.forced_timing_mode),
+                forced_region: region_override.genesis_region.or(self.genesis.forced_region),
                 aspect_ratio: self.genesis.aspect_ratio,
                 adjust_aspect_ratio_in_2x_resolution: self
                     .genesis
@@ -141,6 +182,7 @@ impl AppConfig {
                 remove_sprite_limits: self.genesis.remove_sprite_limits,
                 m68k_clock_divider: self.genesis.m68k_clock_divider,
                 emulate_non_linear_vdp_dac: self.genesis.emulate_non_linear_vdp_dac,
+                expanded_vram: self.genesis.expanded_vram,
                 deinterlace: self.genesis.deinterlace,
                 render_vertical_border: self.genesis.render_vertical_border,
                 render_horizontal_border: self.genesis.render_horizontal_border,
@@ -155,22 +197,31 @@ impl AppConfig {
                 ym2612_enabled: self.genesis.ym2612_enabled,
                 psg_enabled: self.genesis.psg_enabled,
             },
-        })
+            link_cable: None,
+        }
     }
 
     #[must_use]
     pub fn sega_cd_config(&self, path: PathBuf) -> Box<SegaCdConfig> {
-        let genesis_config = *self.genesis_config(path);
+        // Region overrides are only supported for cartridges; hashing a disc image could take a while
+        let mut genesis_config = self.genesis_config_with_override(path, RegionOverride::default());
+        genesis_config.common.audio_volume_offset_db = self.sega_cd.volume_offset_db.into();
         let genesis_emu_config = genesis_config.emulator_config;
         Box::new(SegaCdConfig {
             genesis: genesis_config,
             bios_file_path: self.sega_cd.bios_path.clone(),
             run_without_disc: false,
+            fast_boot: self.sega_cd.fast_boot,
+            disc_load_progress: None,
             emulator_config: SegaCdEmulatorConfig {
                 genesis: genesis_emu_config,
                 pcm_interpolation: self.sega_cd.pcm_interpolation,
                 enable_ram_cartridge: self.sega_cd.enable_ram_cartridge,
+                backup_ram_write_protected: self.sega_cd.backup_ram_write_protected,
                 load_disc_into_ram: self.sega_cd.load_disc_into_ram,
+                disc_cache_enabled: self.sega_cd.disc_cache_enabled,
+                disc_cache_size_mb: self.sega_cd.disc_cache_size_mb,
+                disc_prefetch_sectors: self.sega_cd.disc_prefetch_sectors,
                 disc_drive_speed: self.sega_cd.disc_drive_speed,
                 sub_cpu_divider: self.sega_cd.sub_cpu_divider,
                 pcm_low_pass: self.sega_cd.pcm_low_pass,
@@ -184,7 +235,8 @@ impl AppConfig {
 
     #[must_use]
     pub fn sega_32x_config(&self, path: PathBuf) -> Box<Sega32XConfig> {
-        let genesis_config = *self.genesis_config(path);
+        let mut genesis_config = *self.genesis_config(path);
+        genesis_config.common.audio_volume_offset_db = self.sega_32x.volume_offset_db.into();
         let genesis_emu_config = genesis_config.emulator_config;
         Box::new(Sega32XConfig {
             genesis: genesis_config,
@@ -194,6 +246,7 @@ impl AppConfig {
                 apply_genesis_lpf_to_pwm: self.sega_32x.apply_genesis_lpf_to_pwm,
                 pwm_enabled: self.sega_32x.pwm_enabled,
             },
+            gdb_port: None,
         })
     }
 }
@@ -1,6 +1,6 @@
 use jgenesis_native_driver::config::input::{
-    GameBoyInputConfig, GenesisInputConfig, HotkeyConfig, NesInputConfig, SmsGgInputConfig,
-    SnesInputConfig,
+    AnalogInputConfig, GameBoyInputConfig, GamepadLockConfig, GenesisInputConfig, HotkeyConfig,
+    NesInputConfig, SmsGgInputConfig, SnesInputConfig,
 };
 use serde::{Deserialize, Serialize};
 
@@ -18,12 +18,14 @@ pub struct InputAppConfig {
     pub game_boy: GameBoyInputConfig,
     #[serde(default)]
     pub hotkeys: HotkeyConfig,
-    #[serde(default = "default_axis_deadzone")]
-    pub axis_deadzone: i16,
-}
-
-fn default_axis_deadzone() -> i16 {
-    8000
+    #[serde(default)]
+    pub analog: AnalogInputConfig,
+    #[serde(default)]
+    pub gamepad_locks: GamepadLockConfig,
+    #[serde(default)]
+    pub background_gamepad_input: bool,
+    #[serde(default)]
+    pub subframe_input: bool,
 }
 
 impl Default for InputAppConfig {
@@ -4,6 +4,7 @@ pub mod genesis;
 pub mod input;
 mod migration;
 pub mod nes;
+pub mod overrides;
 pub mod smsgg;
 pub mod snes;
 
@@ -14,10 +15,12 @@ use crate::gb::GameBoyAppConfig;
 use crate::genesis::{GenesisAppConfig, Sega32XAppConfig, SegaCdAppConfig};
 use crate::input::InputAppConfig;
 use crate::nes::NesAppConfig;
+use crate::overrides::RegionOverride;
 use crate::smsgg::SmsGgAppConfig;
 use crate::snes::SnesAppConfig;
 use cfg_if::cfg_if;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -28,6 +31,8 @@ pub struct ListFilters {
     #[serde(default = "true_fn")]
     pub game_gear: bool,
     #[serde(default = "true_fn")]
+    pub sg_1000: bool,
+    #[serde(default = "true_fn")]
     pub genesis: bool,
     #[serde(default = "true_fn")]
     pub sega_cd: bool,
@@ -45,6 +50,10 @@ fn true_fn() -> bool {
     true
 }
 
+fn default_egui_scale() -> f32 {
+    1.0
+}
+
 impl Default for ListFilters {
     fn default() -> Self {
         toml::from_str("").unwrap()
@@ -87,6 +96,9 @@ pub struct AppConfig {
     pub game_boy: GameBoyAppConfig,
     #[serde(default)]
     pub input: InputAppConfig,
+    /// Region overrides keyed by ROM MD5
+    #[serde(default)]
+    pub region_overrides: BTreeMap<String, RegionOverride>,
     // TODO move GUI-specific config/state somewhere else - separate file?
     #[serde(default)]
     pub list_filters: ListFilters,
@@ -96,6 +108,13 @@ pub struct AppConfig {
     pub recent_open_list: Vec<RecentOpen>,
     #[serde(default)]
     pub egui_theme: EguiTheme,
+    /// Accent color for selections and links; uses the theme's default color if not set
+    #[serde(default)]
+    pub egui_accent_color: Option<(u8, u8, u8)>,
+    #[serde(default)]
+    pub egui_high_contrast: bool,
+    #[serde(default = "default_egui_scale")]
+    pub egui_scale: f32,
 }
 
 impl AppConfig {
@@ -157,9 +176,61 @@ fn default_linux_config_path() -> PathBuf {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use jgenesis_common::frontend::EmulatorConfigTrait;
+    use jgenesis_native_driver::config::input::{AxisConfig, DEFAULT_CONFIGURABLE_AXES};
+    use std::num::NonZeroU64;
 
     #[test]
     fn config_default_does_not_panic() {
         let _ = AppConfig::default();
     }
+
+    #[test]
+    fn enhancements_disabled_matches_default_config() {
+        let default_config = AppConfig::default().sega_cd_config(PathBuf::new()).emulator_config;
+
+        let mut app_config = AppConfig::default();
+        app_config.genesis.remove_sprite_limits = true;
+        app_config.genesis.m68k_clock_divider = 1;
+        app_config.genesis.plane_a_enabled = false;
+        app_config.genesis.psg_enabled = false;
+        app_config.sega_cd.sub_cpu_divider = NonZeroU64::new(1).unwrap();
+        app_config.sega_cd.pcm_enabled = false;
+        let config = app_config.sega_cd_config(PathBuf::new()).emulator_config;
+
+        assert_eq!(
+            format!("{:?}", config.with_enhancements_disabled()),
+            format!("{default_config:?}")
+        );
+    }
+
+    #[test]
+    fn migrate_legacy_axis_deadzone() {
+        let config_str = format!(
+            "config_version = \"{}\"\n[input]\naxis_deadzone = 5000\n",
+            current_config_version()
+        );
+        let config: AppConfig = toml::from_str(&config_str).unwrap();
+
+        let migrated = migrate_config(&config, &config_str).unwrap();
+        assert!(migrated.input.analog.axes.iter().all(|axis| axis.deadzone == 5000));
+
+        // Per-axis settings take precedence over the legacy field
+        let config_str = format!("{config_str}[input.analog]\naxes = []\n");
+        let config: AppConfig = toml::from_str(&config_str).unwrap();
+        assert!(migrate_config(&config, &config_str).is_none());
+
+        // Versions after the per-axis settings were added never check for the legacy field
+        let config_str = "config_version = \"99.0.0\"\n[input]\naxis_deadzone = 5000\n";
+        let config: AppConfig = toml::from_str(config_str).unwrap();
+        let migrated = migrate_config(&config, config_str).unwrap();
+        assert_eq!(migrated.input.analog, config.input.analog);
+    }
+
+    #[test]
+    fn analog_config_without_axes() {
+        let config: AppConfig = toml::from_str("[input.analog]\n").unwrap();
+        assert_eq!(config.input.analog.axes.len(), DEFAULT_CONFIGURABLE_AXES);
+        assert!(config.input.analog.axes.iter().all(|&axis| axis == AxisConfig::default()));
+    }
 }
@@ -1,6 +1,7 @@
 use crate::AppConfig;
 use jgenesis_native_driver::config::input::HotkeyConfig;
 use jgenesis_native_driver::input::GenericInput;
+use serde::de::IgnoredAny;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
@@ -57,6 +58,10 @@ impl FromStr for SemVer {
     }
 }
 
+// The global axis deadzone was replaced by per-axis settings without a version bump, so configs
+// written by this version may have either
+const LAST_GLOBAL_AXIS_DEADZONE_VERSION: SemVer = SemVer::new(0, 8, 4);
+
 #[must_use]
 pub const fn current_config_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
@@ -64,20 +69,27 @@ pub const fn current_config_version() -> &'static str {
 
 #[must_use]
 pub fn migrate_config(config: &AppConfig, config_str: &str) -> Option<AppConfig> {
-    if config
-        .config_version
-        .as_ref()
-        .is_some_and(|version| version.as_str() == current_config_version())
-    {
-        return None;
-    }
-
     let old_version = config
         .config_version
         .as_ref()
         .and_then(|s| s.parse::<SemVer>().ok())
         .unwrap_or(SemVer::new(0, 0, 0));
 
+    let legacy_axis_deadzone = if old_version <= LAST_GLOBAL_AXIS_DEADZONE_VERSION {
+        legacy_axis_deadzone(config_str)
+    } else {
+        None
+    };
+
+    if legacy_axis_deadzone.is_none()
+        && config
+            .config_version
+            .as_ref()
+            .is_some_and(|version| version.as_str() == current_config_version())
+    {
+        return None;
+    }
+
     log::info!("Migrating config from version {old_version} to {}", current_config_version());
 
     let mut new_config = config.clone();
@@ -89,6 +101,10 @@ pub fn migrate_config(config: &AppConfig, config_str: &str) -> Option<AppConfig>
         migrate_config_0_8_4(&mut new_config);
     }
 
+    if let Some(deadzone) = legacy_axis_deadzone {
+        migrate_axis_deadzone(&mut new_config, deadzone);
+    }
+
     new_config.config_version = Some(current_config_version().into());
 
     Some(new_config)
@@ -108,13 +124,17 @@ struct OldHotkeyConfig {
     pub mapping_2: OldHotkeyMapping,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 struct OldInputConfig {
     #[serde(default)]
     pub hotkeys: OldHotkeyConfig,
+    #[serde(default)]
+    pub axis_deadzone: Option<i16>,
+    #[serde(default)]
+    pub analog: Option<IgnoredAny>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 struct OldAppConfig {
     #[serde(default)]
     pub input: OldInputConfig,
@@ -163,3 +183,23 @@ fn migrate_config_0_8_4(config: &mut AppConfig) {
     config.input.hotkeys.mapping_1.toggle_overclocking =
         HotkeyConfig::default().mapping_1.toggle_overclocking;
 }
+
+fn legacy_axis_deadzone(config_str: &str) -> Option<i16> {
+    let old_config = toml::from_str::<OldAppConfig>(config_str).ok()?;
+
+    // Configs that already have per-axis settings only need the old field dropped, which happens
+    // when the migrated config is written back out
+    if old_config.input.analog.is_some() {
+        return None;
+    }
+
+    old_config.input.axis_deadzone
+}
+
+fn migrate_axis_deadzone(config: &mut AppConfig, deadzone: i16) {
+    // Global axis deadzone replaced by per-axis settings
+    log::info!("Migrating global axis deadzone {deadzone} to per-axis deadzones");
+    for axis in &mut config.input.analog.axes {
+        axis.deadzone = deadzone;
+    }
+}
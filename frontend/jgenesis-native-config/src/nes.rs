@@ -22,6 +22,8 @@ pub struct NesAppConfig {
     pub audio_60hz_hack: bool,
     #[serde(default)]
     pub allow_opposing_joypad_inputs: bool,
+    #[serde(default)]
+    pub volume_offset_db: i8,
 }
 
 impl NesAppConfig {
@@ -41,7 +43,7 @@ impl AppConfig {
     #[must_use]
     pub fn nes_config(&self, path: PathBuf) -> Box<NesConfig> {
         Box::new(NesConfig {
-            common: self.common_config(path),
+            common: self.common_config(path, self.nes.volume_offset_db),
             inputs: self.input.nes.clone(),
             emulator_config: NesEmulatorConfig {
                 forced_timing_mode: self.nes.forced_timing_mode,
@@ -0,0 +1,67 @@
+//! Per-game region and timing mode overrides, for games whose region is misdetected or that need
+//! to run with a different region than the global setting.
+//!
+//! Overrides are keyed by ROM hash rather than path so that they follow the game if it is moved
+//! or renamed.
+
+use crate::AppConfig;
+use genesis_core::GenesisRegion;
+use jgenesis_common::frontend::TimingMode;
+use jgenesis_common::hash;
+use serde::{Deserialize, Serialize};
+use smsgg_core::SmsRegion;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RegionOverride {
+    #[serde(default)]
+    pub genesis_region: Option<GenesisRegion>,
+    #[serde(default)]
+    pub sms_region: Option<SmsRegion>,
+    #[serde(default)]
+    pub timing_mode: Option<TimingMode>,
+}
+
+impl RegionOverride {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn override_key(path: &Path) -> Option<String> {
+    match hash::file_md5_hex(path) {
+        Ok(md5) => Some(md5),
+        Err(err) => {
+            log::error!("Unable to hash ROM file '{}': {err}", path.display());
+            None
+        }
+    }
+}
+
+impl AppConfig {
+    /// Look up the region override for the given ROM file. Returns an empty override if there is
+    /// none.
+    #[must_use]
+    pub fn region_override(&self, path: &Path) -> RegionOverride {
+        // Avoid hashing the ROM file when no overrides have been set
+        if self.region_overrides.is_empty() {
+            return RegionOverride::default();
+        }
+
+        override_key(path)
+            .and_then(|key| self.region_overrides.get(&key).copied())
+            .unwrap_or_default()
+    }
+
+    /// Set the region override for the given ROM file. Setting an empty override removes it.
+    pub fn set_region_override(&mut self, path: &Path, region_override: RegionOverride) {
+        let Some(key) = override_key(path) else { return };
+
+        if region_override.is_empty() {
+            self.region_overrides.remove(&key);
+        } else {
+            self.region_overrides.insert(key, region_override);
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use crate::AppConfig;
+use jgenesis_common::clockratio::ClockRatio;
 use jgenesis_common::frontend::TimingMode;
 use jgenesis_native_driver::config::SmsGgConfig;
 use serde::{Deserialize, Serialize};
 use smsgg_core::psg::Sn76489Version;
 use smsgg_core::{GgAspectRatio, SmsAspectRatio, SmsGgEmulatorConfig, SmsModel, SmsRegion};
-use std::num::NonZeroU32;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -28,18 +28,30 @@ pub struct SmsGgAppConfig {
     pub sms_crop_left_border: bool,
     #[serde(default)]
     pub gg_use_sms_resolution: bool,
+    #[serde(default)]
+    pub gg_frame_blending: bool,
+    #[serde(default = "default_gg_lcd_persistence")]
+    pub gg_lcd_persistence: u8,
+    #[serde(default)]
+    pub ntsc_composite_filter: bool,
     #[serde(default = "true_fn")]
     pub fm_sound_unit_enabled: bool,
     #[serde(default = "default_z80_divider")]
-    pub z80_divider: NonZeroU32,
+    pub z80_divider: ClockRatio,
+    #[serde(default)]
+    pub volume_offset_db: i8,
 }
 
 const fn true_fn() -> bool {
     true
 }
 
-fn default_z80_divider() -> NonZeroU32 {
-    NonZeroU32::new(smsgg_core::NATIVE_Z80_DIVIDER).unwrap()
+fn default_gg_lcd_persistence() -> u8 {
+    smsgg_core::DEFAULT_GG_LCD_PERSISTENCE
+}
+
+fn default_z80_divider() -> ClockRatio {
+    ClockRatio::from_integer(smsgg_core::NATIVE_Z80_DIVIDER)
 }
 
 impl Default for SmsGgAppConfig {
@@ -51,23 +63,32 @@ impl Default for SmsGgAppConfig {
 impl AppConfig {
     #[must_use]
     pub fn smsgg_config(&self, path: PathBuf) -> Box<SmsGgConfig> {
+        let region_override = self.region_override(&path);
+
+        let mut common_config = self.common_config(path, self.smsgg.volume_offset_db);
+        common_config.renderer_config.ntsc_filter = self.smsgg.ntsc_composite_filter;
+
         Box::new(SmsGgConfig {
-            common: self.common_config(path),
+            common: common_config,
             inputs: self.input.smsgg.clone(),
             emulator_config: SmsGgEmulatorConfig {
-                sms_timing_mode: self.smsgg.sms_timing_mode,
+                sms_timing_mode: region_override.timing_mode.unwrap_or(self.smsgg.sms_timing_mode),
                 sms_model: self.smsgg.sms_model,
                 forced_psg_version: self.smsgg.psg_version,
                 remove_sprite_limit: self.smsgg.remove_sprite_limit,
                 sms_aspect_ratio: self.smsgg.sms_aspect_ratio,
                 gg_aspect_ratio: self.smsgg.gg_aspect_ratio,
-                sms_region: self.smsgg.sms_region,
+                sms_region: region_override.sms_region.unwrap_or(self.smsgg.sms_region),
                 sms_crop_vertical_border: self.smsgg.sms_crop_vertical_border,
                 sms_crop_left_border: self.smsgg.sms_crop_left_border,
                 gg_use_sms_resolution: self.smsgg.gg_use_sms_resolution,
+                gg_frame_blending: self.smsgg.gg_frame_blending,
+                gg_lcd_persistence: self.smsgg.gg_lcd_persistence,
                 fm_sound_unit_enabled: self.smsgg.fm_sound_unit_enabled,
                 z80_divider: self.smsgg.z80_divider,
             },
+            forced_hardware: None,
+            link_cable: None,
         })
     }
 }
@@ -1,9 +1,9 @@
 use crate::AppConfig;
+use jgenesis_common::clockratio::ClockRatio;
 use jgenesis_common::frontend::TimingMode;
 use jgenesis_native_driver::config::SnesConfig;
 use serde::{Deserialize, Serialize};
 use snes_core::api::{AudioInterpolationMode, SnesAspectRatio, SnesEmulatorConfig};
-use std::num::NonZeroU64;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -14,25 +14,37 @@ pub struct SnesAppConfig {
     #[serde(default = "true_fn")]
     pub deinterlace: bool,
     #[serde(default)]
+    pub hi_res_blending: bool,
+    #[serde(default)]
+    pub priority_overlay: bool,
+    #[serde(default)]
     pub audio_interpolation: AudioInterpolationMode,
     #[serde(default)]
     pub audio_60hz_hack: bool,
     #[serde(default = "default_gsu_overclock")]
-    pub gsu_overclock_factor: NonZeroU64,
+    pub gsu_overclock_factor: ClockRatio,
     pub dsp1_rom_path: Option<PathBuf>,
     pub dsp2_rom_path: Option<PathBuf>,
     pub dsp3_rom_path: Option<PathBuf>,
     pub dsp4_rom_path: Option<PathBuf>,
     pub st010_rom_path: Option<PathBuf>,
     pub st011_rom_path: Option<PathBuf>,
+    #[serde(default)]
+    pub sgb_bios_path: Option<PathBuf>,
+    #[serde(default)]
+    pub spc700_ipl_path: Option<PathBuf>,
+    #[serde(default)]
+    pub super_game_boy_mode: bool,
+    #[serde(default)]
+    pub volume_offset_db: i8,
 }
 
 const fn true_fn() -> bool {
     true
 }
 
-fn default_gsu_overclock() -> NonZeroU64 {
-    NonZeroU64::new(1).unwrap()
+fn default_gsu_overclock() -> ClockRatio {
+    ClockRatio::from_integer(1)
 }
 
 impl Default for SnesAppConfig {
@@ -45,12 +57,14 @@ impl AppConfig {
     #[must_use]
     pub fn snes_config(&self, path: PathBuf) -> Box<SnesConfig> {
         Box::new(SnesConfig {
-            common: self.common_config(path),
+            common: self.common_config(path, self.snes.volume_offset_db),
             inputs: self.input.snes.clone(),
             emulator_config: SnesEmulatorConfig {
                 forced_timing_mode: self.snes.forced_timing_mode,
                 aspect_ratio: self.snes.aspect_ratio,
                 deinterlace: self.snes.deinterlace,
+                hi_res_blending: self.snes.hi_res_blending,
+                priority_overlay: self.snes.priority_overlay,
                 audio_interpolation: self.snes.audio_interpolation,
                 audio_60hz_hack: self.snes.audio_60hz_hack,
                 gsu_overclock_factor: self.snes.gsu_overclock_factor,
@@ -61,6 +75,8 @@ impl AppConfig {
             dsp4_rom_path: self.snes.dsp4_rom_path.clone(),
             st010_rom_path: self.snes.st010_rom_path.clone(),
             st011_rom_path: self.snes.st011_rom_path.clone(),
+            sgb_bios_path: self.snes.sgb_bios_path.clone(),
+            spc700_ipl_path: self.snes.spc700_ipl_path.clone(),
         })
     }
 }
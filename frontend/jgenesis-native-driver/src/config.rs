@@ -1,8 +1,8 @@
 pub mod input;
 
 use crate::config::input::{
-    GameBoyInputConfig, GenesisInputConfig, HotkeyConfig, NesInputConfig, SmsGgInputConfig,
-    SnesInputConfig,
+    AnalogInputConfig, GameBoyInputConfig, GamepadLockConfig, GenesisInputConfig, HotkeyConfig,
+    NesInputConfig, SmsGgInputConfig, SnesInputConfig,
 };
 use crate::mainloop::NativeEmulatorError;
 use crate::{NativeEmulatorResult, archive};
@@ -10,9 +10,10 @@ use gb_core::api::GameBoyEmulatorConfig;
 use genesis_core::GenesisEmulatorConfig;
 use jgenesis_common::frontend::TimingMode;
 use jgenesis_proc_macros::{ConfigDisplay, EnumAll, EnumDisplay};
-use jgenesis_renderer::config::RendererConfig;
+use jgenesis_renderer::config::{OverlayPosition, RendererConfig, VSyncMode};
 use nes_core::api::NesEmulatorConfig;
 use s32x_core::api::Sega32XEmulatorConfig;
+use segacd_core::LoadProgress;
 use segacd_core::api::SegaCdEmulatorConfig;
 use serde::{Deserialize, Serialize};
 use smsgg_core::{SmsGgEmulatorConfig, SmsGgHardware};
@@ -21,6 +22,7 @@ use std::ffi::OsStr;
 use std::fmt::{Display, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 pub(crate) const DEFAULT_GENESIS_WINDOW_SIZE: WindowSize = WindowSize { width: 878, height: 672 };
 pub(crate) const DEFAULT_GB_WINDOW_SIZE: WindowSize =
@@ -73,6 +75,25 @@ pub enum FullscreenMode {
     Exclusive,
 }
 
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, EnumDisplay, EnumAll,
+)]
+#[cfg_attr(feature = "clap", derive(jgenesis_proc_macros::CustomValueEnum))]
+pub enum SyncMode {
+    /// Use the individual VSync, frame time sync, audio sync, and dynamic resampling settings
+    #[default]
+    Custom,
+    /// Block on the audio queue; VSync and frame time sync are disabled
+    Audio,
+    /// Sync to the display's refresh rate; audio samples are dropped if the audio queue overflows
+    Video,
+    /// Sync to the display's refresh rate and block on the audio queue, with dynamic audio
+    /// resampling to keep the audio queue near its target size
+    AudioAndVideo,
+    /// No synchronization; run at uncapped speed
+    Uncapped,
+}
+
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, EnumDisplay, EnumAll,
 )]
@@ -84,6 +105,19 @@ pub enum HideMouseCursor {
     Always,
 }
 
+/// How the on-screen input viewer displays each player's buttons.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, EnumDisplay, EnumAll,
+)]
+#[cfg_attr(feature = "clap", derive(jgenesis_proc_macros::CustomValueEnum))]
+pub enum InputViewerSkin {
+    /// Every button in a fixed position, shown as a distinct symbol when pressed or '.' otherwise
+    #[default]
+    Compact,
+    /// Full names of only the buttons that are currently pressed
+    Names,
+}
+
 impl HideMouseCursor {
     pub(crate) fn should_hide(self, fullscreen: bool) -> bool {
         self == Self::Always || (fullscreen && self == Self::Fullscreen)
@@ -101,11 +135,16 @@ pub struct CommonConfig {
     #[cfg_display(path)]
     pub rom_file_path: PathBuf,
     pub audio_output_frequency: u64,
+    pub sync_mode: SyncMode,
     pub audio_sync: bool,
     pub audio_dynamic_resampling_ratio: bool,
     pub audio_hardware_queue_size: u16,
     pub audio_buffer_size: u32,
     pub audio_gain_db: f64,
+    pub audio_volume_percent: u8,
+    pub audio_volume_offset_db: f64,
+    pub audio_muted: bool,
+    pub round_frame_rate: bool,
     pub save_path: SavePath,
     pub state_path: SavePath,
     #[cfg_display(debug_fmt)]
@@ -117,15 +156,72 @@ pub struct CommonConfig {
     pub fast_forward_multiplier: u64,
     pub rewind_buffer_length_seconds: u64,
     pub load_recent_state_at_launch: bool,
+    pub auto_save_state: bool,
+    pub periodic_auto_save_state: bool,
+    pub auto_save_state_interval_minutes: u64,
     pub launch_in_fullscreen: bool,
     pub fullscreen_mode: FullscreenMode,
-    pub axis_deadzone: i16,
+    #[cfg_display(indent_nested)]
+    pub analog_config: AnalogInputConfig,
+    #[cfg_display(indent_nested)]
+    pub gamepad_locks: GamepadLockConfig,
+    pub background_gamepad_input: bool,
+    pub subframe_input: bool,
     #[cfg_display(indent_nested)]
     pub hotkey_config: HotkeyConfig,
     pub hide_mouse_cursor: HideMouseCursor,
+    pub input_viewer_enabled: bool,
+    pub input_viewer_position: OverlayPosition,
+    pub input_viewer_skin: InputViewerSkin,
+    /// Revert all enhancements, hacks, and debug layer/channel toggles to accurate settings
+    pub purist_mode: bool,
+    pub discord_presence_enabled: bool,
+    pub pause_when_unfocused: bool,
+    pub pause_on_controller_disconnect: bool,
 }
 
 impl CommonConfig {
+    /// Returns a copy of this config with the individual sync settings overridden based on the
+    /// sync mode. This is a no-op if the sync mode is [`SyncMode::Custom`].
+    pub(crate) fn with_sync_mode_applied(&self) -> Self {
+        let mut config = self.clone();
+
+        let video_vsync_mode = match config.renderer_config.vsync_mode {
+            VSyncMode::Disabled => VSyncMode::Enabled,
+            vsync_mode @ (VSyncMode::Enabled | VSyncMode::Fast) => vsync_mode,
+        };
+
+        match config.sync_mode {
+            SyncMode::Custom => {}
+            SyncMode::Audio => {
+                config.audio_sync = true;
+                config.audio_dynamic_resampling_ratio = false;
+                config.renderer_config.vsync_mode = VSyncMode::Disabled;
+                config.renderer_config.frame_time_sync = false;
+            }
+            SyncMode::Video => {
+                config.audio_sync = false;
+                config.audio_dynamic_resampling_ratio = false;
+                config.renderer_config.vsync_mode = video_vsync_mode;
+                config.renderer_config.frame_time_sync = false;
+            }
+            SyncMode::AudioAndVideo => {
+                config.audio_sync = true;
+                config.audio_dynamic_resampling_ratio = true;
+                config.renderer_config.vsync_mode = video_vsync_mode;
+                config.renderer_config.frame_time_sync = false;
+            }
+            SyncMode::Uncapped => {
+                config.audio_sync = false;
+                config.audio_dynamic_resampling_ratio = false;
+                config.renderer_config.vsync_mode = VSyncMode::Disabled;
+                config.renderer_config.frame_time_sync = false;
+            }
+        }
+
+        config
+    }
+
     pub(crate) fn read_rom_file(
         &self,
         supported_extensions: &[&str],
@@ -157,6 +253,12 @@ pub struct SmsGgConfig {
     pub inputs: SmsGgInputConfig,
     #[cfg_display(indent_nested)]
     pub emulator_config: SmsGgEmulatorConfig,
+    /// Hardware to emulate; determined from the file extension if not set
+    #[cfg_display(debug_fmt)]
+    pub forced_hardware: Option<SmsGgHardware>,
+    /// Connect a Gear-to-Gear cable to the EXT port; only used for Game Gear games
+    #[cfg_display(debug_fmt)]
+    pub link_cable: Option<LinkCableMode>,
 }
 
 pub(crate) fn default_smsgg_window_size(
@@ -164,8 +266,12 @@ pub(crate) fn default_smsgg_window_size(
     sms_timing_mode: TimingMode,
 ) -> WindowSize {
     match (hardware, sms_timing_mode) {
-        (SmsGgHardware::MasterSystem, TimingMode::Ntsc) => WindowSize { width: 878, height: 576 },
-        (SmsGgHardware::MasterSystem, TimingMode::Pal) => WindowSize { width: 1056, height: 576 },
+        (SmsGgHardware::MasterSystem | SmsGgHardware::Sg1000, TimingMode::Ntsc) => {
+            WindowSize { width: 878, height: 576 }
+        }
+        (SmsGgHardware::MasterSystem | SmsGgHardware::Sg1000, TimingMode::Pal) => {
+            WindowSize { width: 1056, height: 576 }
+        }
         (SmsGgHardware::GameGear, _) => WindowSize { width: 576, height: 432 },
     }
 }
@@ -178,6 +284,18 @@ pub struct GenesisConfig {
     pub inputs: GenesisInputConfig,
     #[cfg_display(indent_nested)]
     pub emulator_config: GenesisEmulatorConfig,
+    /// Connect a link cable to controller port 2; only used for Genesis games
+    #[cfg_display(debug_fmt)]
+    pub link_cable: Option<LinkCableMode>,
+}
+
+/// How to reach the other emulator instance on the end of a link cable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkCableMode {
+    /// Wait for the other instance to connect on the given localhost port
+    Listen(u16),
+    /// Connect to another instance listening at the given address
+    Connect(String),
 }
 
 #[derive(Debug, Clone, ConfigDisplay)]
@@ -189,6 +307,12 @@ pub struct SegaCdConfig {
     #[cfg_display(path)]
     pub bios_file_path: Option<PathBuf>,
     pub run_without_disc: bool,
+    /// Fast-forward through the BIOS boot animation and license screen
+    pub fast_boot: bool,
+    /// Progress tracker for loading the disc into memory; frontends can poll this from another
+    /// thread to display progress and cancel loading
+    #[cfg_display(skip)]
+    pub disc_load_progress: Option<Arc<LoadProgress>>,
 }
 
 #[derive(Debug, Clone, ConfigDisplay)]
@@ -197,6 +321,9 @@ pub struct Sega32XConfig {
     pub genesis: GenesisConfig,
     #[cfg_display(indent_nested)]
     pub emulator_config: Sega32XEmulatorConfig,
+    /// Listen for GDB remote debugging connections to the SH-2s on this port
+    #[cfg_display(debug_fmt)]
+    pub gdb_port: Option<u16>,
 }
 
 #[derive(Debug, Clone, ConfigDisplay)]
@@ -229,6 +356,12 @@ pub struct SnesConfig {
     pub st010_rom_path: Option<PathBuf>,
     #[cfg_display(path)]
     pub st011_rom_path: Option<PathBuf>,
+    /// Super Game Boy BIOS, used to run Game Boy ROMs in the SNES core
+    #[cfg_display(path)]
+    pub sgb_bios_path: Option<PathBuf>,
+    /// Dumped SPC700 IPL boot ROM; the built-in copy is used if not set
+    #[cfg_display(path)]
+    pub spc700_ipl_path: Option<PathBuf>,
 }
 
 impl SnesConfig {
@@ -239,8 +372,9 @@ impl SnesConfig {
         let dsp4 = self.dsp4_rom_path.clone().map(coprocessor_read_fn);
         let st010 = self.st010_rom_path.clone().map(coprocessor_read_fn);
         let st011 = self.st011_rom_path.clone().map(coprocessor_read_fn);
+        let spc700_ipl = self.spc700_ipl_path.clone().map(coprocessor_read_fn);
 
-        CoprocessorRoms { dsp1, dsp2, dsp3, dsp4, st010, st011 }
+        CoprocessorRoms { dsp1, dsp2, dsp3, dsp4, st010, st011, sgb_cartridge: None, spc700_ipl }
     }
 }
 
@@ -257,3 +391,31 @@ pub struct GameBoyConfig {
     #[cfg_display(indent_nested)]
     pub emulator_config: GameBoyEmulatorConfig,
 }
+
+/// Config for any supported system, used to create or reload an emulator without the caller needing
+/// to know which concrete emulator type it is working with.
+#[derive(Debug, Clone)]
+pub enum SystemConfig {
+    SmsGg(Box<SmsGgConfig>),
+    Genesis(Box<GenesisConfig>),
+    SegaCd(Box<SegaCdConfig>),
+    Sega32X(Box<Sega32XConfig>),
+    Nes(Box<NesConfig>),
+    Snes(Box<SnesConfig>),
+    GameBoy(Box<GameBoyConfig>),
+}
+
+impl SystemConfig {
+    #[must_use]
+    pub fn system_name(&self) -> &'static str {
+        match self {
+            Self::SmsGg(_) => "SMS/GG",
+            Self::Genesis(_) => "Genesis",
+            Self::SegaCd(_) => "Sega CD",
+            Self::Sega32X(_) => "32X",
+            Self::Nes(_) => "NES",
+            Self::Snes(_) => "SNES",
+            Self::GameBoy(_) => "Game Boy",
+        }
+    }
+}
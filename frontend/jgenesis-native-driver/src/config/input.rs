@@ -1,4 +1,4 @@
-use crate::input::{GenericInput, Hotkey};
+use crate::input::{GamepadAction, GenericInput, Hotkey};
 use gb_core::inputs::GameBoyButton;
 use genesis_core::GenesisControllerType;
 use genesis_core::input::GenesisButton;
@@ -21,6 +21,16 @@ macro_rules! key_input {
     };
 }
 
+macro_rules! gamepad_combo {
+    ($($button:literal $(+)?)*) => {
+        Some(vec![
+            $(
+                GenericInput::Gamepad { gamepad_idx: 0, action: GamepadAction::Button($button) },
+            )*
+        ])
+    };
+}
+
 macro_rules! define_controller_mapping {
     (
         $name:ident,
@@ -176,8 +186,21 @@ impl SmsGgInputMapping {
     }
 }
 
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, EnumDisplay, EnumAll,
+)]
+#[cfg_attr(feature = "clap", derive(jgenesis_proc_macros::CustomValueEnum))]
+pub enum SmsGgControllerType {
+    #[default]
+    Gamepad,
+    Paddle,
+    SportsPad,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ConfigDisplay)]
 pub struct SmsGgInputConfig {
+    #[serde(default)]
+    pub p1_type: SmsGgControllerType,
     #[serde(default = "default_smsgg_mapping_1")]
     #[cfg_display(indent_nested)]
     pub mapping_1: SmsGgInputMapping,
@@ -200,7 +223,11 @@ fn default_smsgg_mapping_1() -> SmsGgInputMapping {
 
 impl Default for SmsGgInputConfig {
     fn default() -> Self {
-        Self { mapping_1: default_smsgg_mapping_1(), mapping_2: SmsGgInputMapping::default() }
+        Self {
+            p1_type: SmsGgControllerType::default(),
+            mapping_1: default_smsgg_mapping_1(),
+            mapping_2: SmsGgInputMapping::default(),
+        }
     }
 }
 
@@ -701,6 +728,8 @@ define_hotkey_mapping!(
     load_state: LoadState default F6,
     next_save_state_slot: NextSaveStateSlot default RightBracket,
     prev_save_state_slot: PrevSaveStateSlot default LeftBracket,
+    undo_load_state: UndoLoadState default none,
+    undo_save_state: UndoSaveState default none,
     soft_reset: SoftReset default F1,
     hard_reset: HardReset default F2,
     pause: Pause default P,
@@ -709,6 +738,9 @@ define_hotkey_mapping!(
     rewind: Rewind default Backquote,
     toggle_overclocking: ToggleOverclocking default Semicolon,
     open_debugger: OpenDebugger default Quote,
+    swap_player_inputs: SwapPlayerInputs default none,
+    toggle_mute: ToggleMute default M,
+    toggle_performance_hud: TogglePerformanceHud default none,
     save_state_slot_0: SaveStateSlot0 default none,
     save_state_slot_1: SaveStateSlot1 default none,
     save_state_slot_2: SaveStateSlot2 default none,
@@ -731,6 +763,19 @@ define_hotkey_mapping!(
     load_state_slot_9: LoadStateSlot9 default none,
 );
 
+// Button indices are for XInput-style controllers: 4 = LB, 5 = RB, 6 = Back/Select, 7 = Start
+impl HotkeyMapping {
+    #[must_use]
+    pub fn default_gamepad() -> Self {
+        Self {
+            save_state: gamepad_combo!(6 + 5),
+            load_state: gamepad_combo!(6 + 4),
+            pause: gamepad_combo!(6 + 7),
+            ..Self::default()
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ConfigDisplay)]
 pub struct HotkeyConfig {
     #[serde(default = "default_hotkey_mapping_1")]
@@ -761,3 +806,123 @@ impl Default for HotkeyConfig {
         Self { mapping_1: HotkeyMapping::default_keyboard(), mapping_2: HotkeyMapping::default() }
     }
 }
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, EnumDisplay, EnumAll,
+)]
+pub enum AxisMode {
+    /// Axis rests at the center of its range and has a separate input for each direction
+    #[default]
+    Stick,
+    /// Axis rests at the minimum of its range and only has a positive direction
+    Trigger,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, EnumDisplay, EnumAll,
+)]
+pub enum AxisResponseCurve {
+    #[default]
+    Linear,
+    Quadratic,
+    Cubic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AxisConfig {
+    #[serde(default)]
+    pub mode: AxisMode,
+    #[serde(default = "default_axis_deadzone")]
+    pub deadzone: i16,
+    #[serde(default = "default_axis_saturation")]
+    pub saturation: i16,
+    #[serde(default)]
+    pub response_curve: AxisResponseCurve,
+    /// Percentage of the axis range (after applying the deadzone, saturation, and response curve)
+    /// past which the axis is treated as a digital button press
+    #[serde(default)]
+    pub digital_threshold: u8,
+}
+
+fn default_axis_deadzone() -> i16 {
+    8000
+}
+
+fn default_axis_saturation() -> i16 {
+    i16::MAX
+}
+
+impl Default for AxisConfig {
+    fn default() -> Self {
+        Self {
+            mode: AxisMode::default(),
+            deadzone: default_axis_deadzone(),
+            saturation: default_axis_saturation(),
+            response_curve: AxisResponseCurve::default(),
+            digital_threshold: 0,
+        }
+    }
+}
+
+pub const DEFAULT_CONFIGURABLE_AXES: usize = 6;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalogInputConfig {
+    /// Per-axis settings, indexed by axis number; axes past the end of this list use the default
+    /// settings
+    #[serde(default = "default_analog_axes")]
+    pub axes: Vec<AxisConfig>,
+}
+
+fn default_analog_axes() -> Vec<AxisConfig> {
+    vec![AxisConfig::default(); DEFAULT_CONFIGURABLE_AXES]
+}
+
+impl AnalogInputConfig {
+    #[must_use]
+    pub fn axis(&self, axis_idx: u8) -> AxisConfig {
+        self.axes.get(axis_idx as usize).copied().unwrap_or_default()
+    }
+}
+
+impl Default for AnalogInputConfig {
+    fn default() -> Self {
+        Self { axes: default_analog_axes() }
+    }
+}
+
+impl std::fmt::Display for AnalogInputConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, axis) in self.axes.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+
+            write!(
+                f,
+                "  axis {i}: mode={}, deadzone={}, saturation={}, curve={}, threshold={}%",
+                axis.mode,
+                axis.deadzone,
+                axis.saturation,
+                axis.response_curve,
+                axis.digital_threshold
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, ConfigDisplay)]
+pub struct GamepadLockConfig {
+    /// GUID of the gamepad that should always be assigned to player 1
+    pub p1: Option<String>,
+    /// GUID of the gamepad that should always be assigned to player 2
+    pub p2: Option<String>,
+}
+
+impl GamepadLockConfig {
+    pub(crate) fn to_array(&self) -> [Option<String>; 2] {
+        [self.p1.clone(), self.p2.clone()]
+    }
+}
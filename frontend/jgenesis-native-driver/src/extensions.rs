@@ -4,7 +4,8 @@ use std::sync::LazyLock;
 
 pub const MASTER_SYSTEM: &[&str] = &["sms"];
 pub const GAME_GEAR: &[&str] = &["gg"];
-pub const GENESIS: &[&str] = &["gen", "md", "bin", "smd"];
+pub const SG_1000: &[&str] = &["sg", "sc"];
+pub const GENESIS: &[&str] = &["gen", "md", "bin", "smd", "mgd"];
 pub const SEGA_CD: &[&str] = &["cue", "chd"];
 pub const SEGA_32X: &[&str] = &["32x"];
 pub const NES: &[&str] = &["nes"];
@@ -15,15 +16,23 @@ pub const GAME_BOY_COLOR: &[&str] = &["gbc"];
 pub const SUPPORTED_ARCHIVES: &[&str] = &["zip", "7z"];
 
 pub static SMSGG: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
-    [MASTER_SYSTEM, GAME_GEAR].into_iter().flat_map(|system| system.iter().copied()).collect()
+    [MASTER_SYSTEM, GAME_GEAR, SG_1000]
+        .into_iter()
+        .flat_map(|system| system.iter().copied())
+        .collect()
 });
 
 pub static GB_GBC: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
     [GAME_BOY, GAME_BOY_COLOR].into_iter().flat_map(|system| system.iter().copied()).collect()
 });
 
+// Game Boy ROMs can also be loaded into the SNES core, which runs them in a Super Game Boy
+pub static SNES_SUPER_GAME_BOY: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
+    [SNES, GAME_BOY, GAME_BOY_COLOR].into_iter().flat_map(|system| system.iter().copied()).collect()
+});
+
 pub static ALL_CARTRIDGE_BASED: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
-    [MASTER_SYSTEM, GAME_GEAR, GENESIS, SEGA_32X, NES, SNES, GAME_BOY, GAME_BOY_COLOR]
+    [MASTER_SYSTEM, GAME_GEAR, SG_1000, GENESIS, SEGA_32X, NES, SNES, GAME_BOY, GAME_BOY_COLOR]
         .into_iter()
         .flat_map(|system| system.iter().copied())
         .collect()
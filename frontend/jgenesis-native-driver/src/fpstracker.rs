@@ -1,25 +1,61 @@
 use std::collections::VecDeque;
-use std::env;
 use std::time::{Duration, Instant};
 
-const LOG_INTERVAL_SECONDS: u64 = 1;
-const LOG_INTERVAL: Duration = Duration::from_secs(LOG_INTERVAL_SECONDS);
+const UPDATE_INTERVAL_SECONDS: u64 = 1;
+const UPDATE_INTERVAL: Duration = Duration::from_secs(UPDATE_INTERVAL_SECONDS);
 const WINDOW_INTERVAL_SECONDS: u64 = 3;
 const WINDOW_INTERVAL: Duration = Duration::from_secs(WINDOW_INTERVAL_SECONDS);
 
+/// Frame rate and frame time statistics over the last few seconds of emulation.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FrameStats {
+    /// Emulated frames per second
+    pub fps: f64,
+    /// Average time between emulated frames, in milliseconds
+    pub avg_frame_time_ms: f64,
+    /// Longest time between two consecutive emulated frames, in milliseconds
+    pub max_frame_time_ms: f64,
+}
+
+/// Performance statistics for a running emulator, for display in a frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PerformanceStats {
+    pub frame: FrameStats,
+    /// Emulation speed relative to the emulated console's native frame rate; 100% is full speed
+    pub speed_percent: f64,
+    /// Audio queue length relative to the configured audio buffer size. Near 0% means audio is
+    /// at risk of underflowing, and well above 100% means audio is lagging behind
+    pub audio_buffer_percent: f64,
+}
+
+impl PerformanceStats {
+    /// Compact multi-line summary, e.g. for an on-screen overlay.
+    #[must_use]
+    pub fn to_hud_text(&self) -> String {
+        format!(
+            "{:.0} FPS ({:.0}%)\n{:.1} ms avg / {:.1} ms max\nAudio buffer {:.0}%",
+            self.frame.fps,
+            self.speed_percent,
+            self.frame.avg_frame_time_ms,
+            self.frame.max_frame_time_ms,
+            self.audio_buffer_percent
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FpsTracker {
-    last_window_time: Instant,
-    last_log_time: Instant,
+    last_update_time: Instant,
     frame_times: VecDeque<Instant>,
+    stats: FrameStats,
 }
 
 impl FpsTracker {
     pub fn new() -> Self {
         Self {
-            last_window_time: Instant::now() - (WINDOW_INTERVAL - LOG_INTERVAL),
-            last_log_time: Instant::now(),
+            last_update_time: Instant::now(),
             frame_times: VecDeque::with_capacity((2 * WINDOW_INTERVAL_SECONDS * 60) as usize),
+            stats: FrameStats::default(),
         }
     }
 
@@ -27,30 +63,68 @@ impl FpsTracker {
         let now = Instant::now();
         self.frame_times.push_back(now);
 
-        let next_log_time = self.last_log_time + LOG_INTERVAL;
-        if now >= next_log_time {
-            let window_end_time = self.last_window_time + WINDOW_INTERVAL;
-            let mut frame_count = 0;
-            for &time in &self.frame_times {
-                if time >= window_end_time {
-                    break;
-                }
-                frame_count += 1;
-            }
-
-            let next_window_time = self.last_window_time + LOG_INTERVAL;
-            while self.frame_times.front().is_some_and(|&time| time < next_window_time) {
-                self.frame_times.pop_front();
-            }
-
-            self.last_window_time = next_window_time;
-            self.last_log_time = next_log_time;
-
-            // TODO expose FPS in the UI somewhere?
-            if env::var("JGENESIS_LOG_FPS").is_ok_and(|var| !var.is_empty()) {
-                let fps = f64::from(frame_count) / (WINDOW_INTERVAL_SECONDS as f64);
-                log::info!("FPS: {}", fps.round());
-            }
+        // Only recompute stats periodically so that they're readable when displayed every frame
+        if now < self.last_update_time + UPDATE_INTERVAL {
+            return;
         }
+        self.last_update_time = now;
+
+        while self.frame_times.front().is_some_and(|&time| now - time > WINDOW_INTERVAL) {
+            self.frame_times.pop_front();
+        }
+
+        self.stats = compute_stats(&self.frame_times);
+    }
+
+    pub fn stats(&self) -> FrameStats {
+        self.stats
+    }
+}
+
+fn compute_stats(frame_times: &VecDeque<Instant>) -> FrameStats {
+    let (Some(&first), Some(&last)) = (frame_times.front(), frame_times.back()) else {
+        return FrameStats::default();
+    };
+
+    let intervals = frame_times.len() - 1;
+    if intervals == 0 {
+        return FrameStats::default();
+    }
+
+    let elapsed = (last - first).as_secs_f64();
+    let max_frame_time = frame_times
+        .iter()
+        .zip(frame_times.iter().skip(1))
+        .map(|(&prev, &next)| next - prev)
+        .max()
+        .unwrap_or_default();
+
+    FrameStats {
+        fps: intervals as f64 / elapsed,
+        avg_frame_time_ms: 1000.0 * elapsed / intervals as f64,
+        max_frame_time_ms: 1000.0 * max_frame_time.as_secs_f64(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_from_frame_times() {
+        let start = Instant::now();
+        let frame_times: VecDeque<_> =
+            [0, 10, 20, 50].into_iter().map(|ms| start + Duration::from_millis(ms)).collect();
+
+        let stats = compute_stats(&frame_times);
+        assert!((stats.fps - 60.0).abs() < 1e-6, "{stats:?}");
+        assert!((stats.avg_frame_time_ms - 50.0 / 3.0).abs() < 1e-6, "{stats:?}");
+        assert!((stats.max_frame_time_ms - 30.0).abs() < 1e-6, "{stats:?}");
+    }
+
+    #[test]
+    fn stats_with_too_few_frames() {
+        assert_eq!(compute_stats(&VecDeque::new()), FrameStats::default());
+        assert_eq!(compute_stats(&VecDeque::from([Instant::now()])), FrameStats::default());
     }
 }
@@ -1,7 +1,10 @@
 mod serialize;
 
+use crate::config::input::{AnalogInputConfig, AxisConfig, AxisMode, AxisResponseCurve};
 use arrayvec::ArrayVec;
-use jgenesis_common::frontend::{DisplayArea, FrameSize, MappableInputs};
+use jgenesis_common::frontend::{
+    AnalogInput, DisplayArea, FrameSize, MappableInputs, TextInputEvent,
+};
 use jgenesis_common::input::Player;
 use jgenesis_proc_macros::{EnumAll, EnumDisplay, EnumFromStr};
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -16,6 +19,7 @@ use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::hash::Hash;
+use std::mem;
 use std::rc::Rc;
 use std::str::FromStr;
 
@@ -42,6 +46,54 @@ impl AxisDirection {
     }
 }
 
+// Magnitude in the range 0.0..=1.0 after applying the axis deadzone, saturation, and response
+// curve, paired with the axis direction. Returns None if the axis is within the deadzone
+fn processed_axis_magnitude(config: AxisConfig, value: i16) -> Option<(f64, AxisDirection)> {
+    let (magnitude, direction) = match config.mode {
+        AxisMode::Stick => (value.saturating_abs(), AxisDirection::from_value(value)),
+        AxisMode::Trigger => {
+            // Triggers rest at the minimum value; rescale to 0..=i16::MAX
+            let magnitude = (i32::from(value) - i32::from(i16::MIN)) / 2;
+            (magnitude as i16, AxisDirection::Positive)
+        }
+    };
+
+    if magnitude <= config.deadzone {
+        return None;
+    }
+
+    let deadzone = f64::from(config.deadzone.max(0));
+    let saturation = f64::from(config.saturation).max(deadzone + 1.0);
+    let normalized = ((f64::from(magnitude) - deadzone) / (saturation - deadzone)).clamp(0.0, 1.0);
+
+    let curved = match config.response_curve {
+        AxisResponseCurve::Linear => normalized,
+        AxisResponseCurve::Quadratic => normalized * normalized,
+        AxisResponseCurve::Cubic => normalized * normalized * normalized,
+    };
+
+    Some((curved, direction))
+}
+
+/// Determine which direction of an axis (if any) should be treated as a digital press, after
+/// applying the axis deadzone, saturation, response curve, and digital threshold.
+#[must_use]
+pub fn axis_direction_pressed(config: AxisConfig, value: i16) -> Option<AxisDirection> {
+    let (magnitude, direction) = processed_axis_magnitude(config, value)?;
+    (magnitude * 100.0 >= f64::from(config.digital_threshold)).then_some(direction)
+}
+
+/// Convert a raw axis value to an analog value after applying the axis deadzone, saturation, and
+/// response curve. Ranges from -1.0 to 1.0 for sticks and from 0.0 to 1.0 for triggers.
+#[must_use]
+pub fn analog_axis_value(config: AxisConfig, value: i16) -> f64 {
+    match processed_axis_magnitude(config, value) {
+        Some((magnitude, AxisDirection::Positive)) => magnitude,
+        Some((magnitude, AxisDirection::Negative)) => -magnitude,
+        None => 0.0,
+    }
+}
+
 impl Display for AxisDirection {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -234,10 +286,15 @@ pub enum Hotkey {
     Rewind,
     ToggleOverclocking,
     OpenDebugger,
+    SwapPlayerInputs,
+    ToggleMute,
+    TogglePerformanceHud,
     SaveState,
     LoadState,
     NextSaveStateSlot,
     PrevSaveStateSlot,
+    UndoLoadState,
+    UndoSaveState,
     SaveStateSlot0,
     LoadStateSlot0,
     SaveStateSlot1,
@@ -271,6 +328,8 @@ pub enum CompactHotkey {
     LoadStateSlot(usize),
     NextSaveStateSlot,
     PrevSaveStateSlot,
+    UndoLoadState,
+    UndoSaveState,
     SoftReset,
     HardReset,
     Pause,
@@ -279,6 +338,9 @@ pub enum CompactHotkey {
     Rewind,
     ToggleOverclocking,
     OpenDebugger,
+    SwapPlayerInputs,
+    ToggleMute,
+    TogglePerformanceHud,
 }
 
 impl Hotkey {
@@ -291,6 +353,8 @@ impl Hotkey {
             Self::LoadState => CompactHotkey::LoadState,
             Self::NextSaveStateSlot => CompactHotkey::NextSaveStateSlot,
             Self::PrevSaveStateSlot => CompactHotkey::PrevSaveStateSlot,
+            Self::UndoLoadState => CompactHotkey::UndoLoadState,
+            Self::UndoSaveState => CompactHotkey::UndoSaveState,
             Self::SoftReset => CompactHotkey::SoftReset,
             Self::HardReset => CompactHotkey::HardReset,
             Self::Pause => CompactHotkey::Pause,
@@ -299,6 +363,9 @@ impl Hotkey {
             Self::Rewind => CompactHotkey::Rewind,
             Self::ToggleOverclocking => CompactHotkey::ToggleOverclocking,
             Self::OpenDebugger => CompactHotkey::OpenDebugger,
+            Self::SwapPlayerInputs => CompactHotkey::SwapPlayerInputs,
+            Self::ToggleMute => CompactHotkey::ToggleMute,
+            Self::TogglePerformanceHud => CompactHotkey::TogglePerformanceHud,
             Self::SaveStateSlot0 => CompactHotkey::SaveStateSlot(0),
             Self::SaveStateSlot1 => CompactHotkey::SaveStateSlot(1),
             Self::SaveStateSlot2 => CompactHotkey::SaveStateSlot(2),
@@ -335,32 +402,138 @@ pub enum HotkeyEvent {
     Released(Hotkey),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GamepadAssignment {
+    pub device_id: u32,
+    pub name: String,
+    pub guid: String,
+}
+
+/// Assigns device IDs to connected gamepads, honoring player gamepad locks. Device IDs 0 and 1
+/// correspond to players 1 and 2.
+///
+/// This only tracks GUIDs so that the assignment policy doesn't depend on SDL joystick handles.
+#[derive(Debug, Clone, Default)]
+struct GamepadSlots {
+    player_locks: [Option<String>; 2],
+    assigned: BTreeMap<u32, String>,
+}
+
+impl GamepadSlots {
+    fn assign(&mut self, guid: String) -> u32 {
+        let device_id = self.next_device_id(&guid);
+        self.assigned.insert(device_id, guid);
+        device_id
+    }
+
+    fn release(&mut self, device_id: u32) {
+        self.assigned.remove(&device_id);
+    }
+
+    /// Returns `(old_device_id, new_device_id)` for every assigned gamepad, or `None` if the locks
+    /// did not change.
+    fn set_player_locks(&mut self, player_locks: [Option<String>; 2]) -> Option<Vec<(u32, u32)>> {
+        if self.player_locks == player_locks {
+            return None;
+        }
+
+        self.player_locks = player_locks;
+
+        // Re-assign every gamepad in order of its current device ID
+        let old_assigned = mem::take(&mut self.assigned);
+        Some(
+            old_assigned
+                .into_iter()
+                .map(|(old_device_id, guid)| (old_device_id, self.assign(guid)))
+                .collect(),
+        )
+    }
+
+    fn next_device_id(&self, guid: &str) -> u32 {
+        // Prefer a player slot that is locked to this gamepad
+        let locked_slot = (0..self.player_locks.len() as u32).find(|&device_id| {
+            self.player_locks[device_id as usize].as_deref() == Some(guid)
+                && !self.assigned.contains_key(&device_id)
+        });
+        if let Some(device_id) = locked_slot {
+            return device_id;
+        }
+
+        // Otherwise auto-assign to the lowest slot that is neither locked nor in use
+        let max_device_id = (self.assigned.len() + self.player_locks.len()) as u32;
+        (0..=max_device_id)
+            .find(|&device_id| {
+                let locked = self.player_locks.get(device_id as usize).is_some_and(Option::is_some);
+                !locked && !self.assigned.contains_key(&device_id)
+            })
+            .unwrap()
+    }
+}
+
 pub struct Joysticks {
     subsystem: JoystickSubsystem,
     devices: BTreeMap<u32, Joystick>,
     instance_id_to_device_id: FxHashMap<u32, u32>,
+    slots: GamepadSlots,
 }
 
 impl Joysticks {
     #[must_use]
     pub fn new(subsystem: JoystickSubsystem) -> Self {
-        Self { subsystem, devices: BTreeMap::new(), instance_id_to_device_id: FxHashMap::default() }
+        Self {
+            subsystem,
+            devices: BTreeMap::new(),
+            instance_id_to_device_id: FxHashMap::default(),
+            slots: GamepadSlots::default(),
+        }
+    }
+
+    /// Lock player slots to specific gamepad GUIDs. Device IDs 0 and 1 correspond to players 1
+    /// and 2; a locked slot is only ever assigned the gamepad with the matching GUID.
+    ///
+    /// Returns the device IDs of any connected gamepads whose assignments changed.
+    pub fn set_player_locks(&mut self, player_locks: [Option<String>; 2]) -> Vec<u32> {
+        let Some(reassigned) = self.slots.set_player_locks(player_locks) else { return vec![] };
+
+        let mut old_devices = mem::take(&mut self.devices);
+        self.instance_id_to_device_id.clear();
+
+        let mut changed = Vec::new();
+        for (old_device_id, device_id) in reassigned {
+            let Some(joystick) = old_devices.remove(&old_device_id) else { continue };
+
+            self.instance_id_to_device_id.insert(joystick.instance_id(), device_id);
+            self.devices.insert(device_id, joystick);
+
+            if device_id != old_device_id {
+                changed.extend([old_device_id, device_id]);
+            }
+        }
+
+        changed
     }
 
     #[allow(clippy::missing_errors_doc)]
     pub fn handle_device_added(&mut self, joystick_idx: u32) -> Result<(), IntegerOrSdlError> {
         let joystick = self.subsystem.open(joystick_idx)?;
+        if self.instance_id_to_device_id.contains_key(&joystick.instance_id()) {
+            // Already open
+            return Ok(());
+        }
 
-        log::info!("Added joystick {joystick_idx}: '{}'", joystick.name());
+        let name = joystick.name();
+        let device_id = self.slots.assign(joystick.guid().string());
+        self.instance_id_to_device_id.insert(joystick.instance_id(), device_id);
+        self.devices.insert(device_id, joystick);
 
-        self.instance_id_to_device_id.insert(joystick.instance_id(), joystick_idx);
-        self.devices.insert(joystick_idx, joystick);
+        log::info!("Added joystick {joystick_idx} as gamepad {device_id}: '{name}'");
 
         Ok(())
     }
 
     pub fn handle_device_removed(&mut self, instance_id: u32) -> Option<u32> {
         let device_id = self.instance_id_to_device_id.remove(&instance_id)?;
+        self.slots.release(device_id);
         let Some(_) = self.devices.remove(&device_id) else { return Some(device_id) };
 
         log::info!("Removed joystick {device_id}");
@@ -386,6 +559,17 @@ impl Joysticks {
     pub fn all_devices(&self) -> impl Iterator<Item = (u32, &'_ Joystick)> + '_ {
         self.devices.iter().map(|(&device_id, joystick)| (device_id, joystick))
     }
+
+    #[must_use]
+    pub fn assignments(&self) -> Vec<GamepadAssignment> {
+        self.all_devices()
+            .map(|(device_id, joystick)| GamepadAssignment {
+                device_id,
+                name: joystick.name(),
+                guid: joystick.guid().string(),
+            })
+            .collect()
+    }
 }
 
 struct InputMapperState<Inputs, Button> {
@@ -397,6 +581,9 @@ struct InputMapperState<Inputs, Button> {
     active_canonical_inputs: FxHashSet<CanonicalInput>,
     active_hotkeys: FxHashSet<Hotkey>,
     changed_button_buffers: [Vec<GenericButton<Button>>; MAX_MAPPING_LEN + 1],
+    players_swapped: bool,
+    text_entry: bool,
+    text_events: Vec<TextInputEvent>,
 }
 
 impl<Inputs, Button> InputMapperState<Inputs, Button>
@@ -414,6 +601,9 @@ where
             active_canonical_inputs: FxHashSet::default(),
             active_hotkeys: FxHashSet::default(),
             changed_button_buffers: array::from_fn(|_| Vec::with_capacity(10)),
+            players_swapped: false,
+            text_entry: false,
+            text_events: Vec::new(),
         }
     }
 
@@ -468,6 +658,35 @@ where
         }
     }
 
+    // While the emulated system wants text input, keys that type text are sent to it as text entry
+    // instead of being mapped. Other keys (e.g. arrow keys and function keys) are still mapped to
+    // buttons and hotkeys, as are all key releases so that buttons held when text entry started
+    // are released normally
+    fn handle_key_down(&mut self, keycode: Keycode) {
+        if !self.text_entry {
+            self.handle_input(GenericInput::Keyboard(keycode), true);
+            return;
+        }
+
+        match keycode {
+            Keycode::Backspace => self.text_events.push(TextInputEvent::Backspace),
+            Keycode::Return | Keycode::KpEnter => self.text_events.push(TextInputEvent::Submit),
+            Keycode::Escape => {
+                self.text_events.push(TextInputEvent::Cancel);
+                self.handle_input(GenericInput::Keyboard(keycode), true);
+            }
+            // Printable keys are typed through SDL text input events
+            _ if (0x20..0x7F).contains(&keycode.into_i32()) => {}
+            _ => self.handle_input(GenericInput::Keyboard(keycode), true),
+        }
+    }
+
+    fn handle_text(&mut self, text: &str) {
+        if self.text_entry {
+            self.text_events.push(TextInputEvent::Text(text.into()));
+        }
+    }
+
     fn handle_input(&mut self, raw_input: GenericInput, pressed: bool) {
         if pressed && !self.active_inputs.insert(raw_input) {
             // Input is already pressed
@@ -552,6 +771,7 @@ where
 
                 match button {
                     GenericButton::Button(button, player) => {
+                        let player = self.effective_player(player);
                         self.inputs.set_field(button, player, pressed);
                     }
                     GenericButton::Hotkey(hotkey) => {
@@ -578,6 +798,51 @@ where
         }
     }
 
+    fn effective_player(&self, player: Player) -> Player {
+        if self.players_swapped { player.other() } else { player }
+    }
+
+    // Gamepad device IDs 0 and 1 are the player 1 and 2 slots, which already account for gamepad
+    // locks; this applies player swap on top of that
+    fn gamepad_player(&self, gamepad_idx: u32) -> Option<Player> {
+        let player = match gamepad_idx {
+            0 => Player::One,
+            1 => Player::Two,
+            _ => return None,
+        };
+
+        Some(self.effective_player(player))
+    }
+
+    fn swap_players(&mut self) {
+        let buttons: Vec<_> = self
+            .mappings
+            .keys()
+            .filter_map(|&button| match button {
+                GenericButton::Button(button, player) => Some((button, player)),
+                GenericButton::Hotkey(_) => None,
+            })
+            .collect();
+
+        // Release everything under the old assignment, then re-press any buttons that are still
+        // held under the new assignment
+        for &(button, player) in &buttons {
+            self.inputs.set_field(button, self.effective_player(player), false);
+        }
+
+        self.players_swapped = !self.players_swapped;
+
+        for (button, player) in buttons {
+            let pressed =
+                self.mappings[&GenericButton::Button(button, player)].iter().any(|mapping| {
+                    mapping.iter().all(|input| self.active_canonical_inputs.contains(input))
+                });
+            if pressed {
+                self.inputs.set_field(button, self.effective_player(player), true);
+            }
+        }
+    }
+
     fn unset_all_gamepad_inputs(&mut self, idx: u32) {
         // Allocation to avoid borrow checker issues is fine, this won't be called frequently
         let gamepad_inputs: Vec<_> = self
@@ -598,8 +863,9 @@ where
 
 pub struct InputMapper<Inputs, Button> {
     joysticks: Joysticks,
-    axis_deadzone: i16,
+    analog_config: AnalogInputConfig,
     state: InputMapperState<Inputs, Button>,
+    disconnected_gamepad: Option<u32>,
 }
 
 impl<Inputs, Button> InputMapper<Inputs, Button>
@@ -610,7 +876,7 @@ where
     pub fn new(
         initial_inputs: Inputs,
         joystick_subsystem: JoystickSubsystem,
-        axis_deadzone: i16,
+        analog_config: &AnalogInputConfig,
         button_mappings: &[((Button, Player), &Vec<GenericInput>)],
         hotkey_mappings: &[(Hotkey, &Vec<GenericInput>)],
     ) -> Self {
@@ -619,20 +885,53 @@ where
         let mut state = InputMapperState::new(initial_inputs);
         state.update_mappings(button_mappings, hotkey_mappings);
 
-        Self { joysticks, axis_deadzone, state }
+        Self { joysticks, analog_config: analog_config.clone(), state, disconnected_gamepad: None }
     }
 
     pub fn inputs_mut(&mut self) -> &mut Inputs {
         &mut self.state.inputs
     }
 
+    /// Set whether keyboard input should be treated as text entry. See
+    /// [`EmulatorTrait::wants_text_input`](jgenesis_common::frontend::EmulatorTrait::wants_text_input).
+    pub fn set_text_entry(&mut self, text_entry: bool) {
+        self.state.text_entry = text_entry;
+        if !text_entry {
+            self.state.text_events.clear();
+        }
+    }
+
+    /// Text entry events received since the last call, to be passed to the emulator.
+    pub fn take_text_input(&mut self) -> impl Iterator<Item = TextInputEvent> + '_ {
+        self.state.text_events.drain(..)
+    }
+
+    /// Swap the player 1 and player 2 input mappings. Returns whether the players are now swapped.
+    pub fn swap_players(&mut self) -> bool {
+        self.state.swap_players();
+        self.state.players_swapped
+    }
+
+    /// If a gamepad assigned to player 1 or 2 was disconnected since the last call, returns the
+    /// player that it was controlling.
+    pub fn take_disconnected_player(&mut self) -> Option<Player> {
+        let gamepad_idx = self.disconnected_gamepad.take()?;
+        self.state.gamepad_player(gamepad_idx)
+    }
+
+    pub fn update_gamepad_locks(&mut self, player_locks: [Option<String>; 2]) {
+        for device_id in self.joysticks.set_player_locks(player_locks) {
+            self.state.unset_all_gamepad_inputs(device_id);
+        }
+    }
+
     pub fn update_mappings(
         &mut self,
-        axis_deadzone: i16,
+        analog_config: &AnalogInputConfig,
         button_mappings: &[((Button, Player), &Vec<GenericInput>)],
         hotkey_mappings: &[(Hotkey, &Vec<GenericInput>)],
     ) {
-        self.axis_deadzone = axis_deadzone;
+        self.analog_config.clone_from(analog_config);
         self.state.update_mappings(button_mappings, hotkey_mappings);
     }
 
@@ -648,13 +947,16 @@ where
             Event::KeyDown { keycode: Some(keycode), window_id, .. }
                 if window_id == emulator_window_id =>
             {
-                self.state.handle_input(GenericInput::Keyboard(keycode), true);
+                self.state.handle_key_down(keycode);
             }
             Event::KeyUp { keycode: Some(keycode), window_id, .. }
                 if window_id == emulator_window_id =>
             {
                 self.state.handle_input(GenericInput::Keyboard(keycode), false);
             }
+            Event::TextInput { ref text, window_id, .. } if window_id == emulator_window_id => {
+                self.state.handle_text(text);
+            }
             Event::MouseButtonDown { mouse_btn, window_id, .. }
                 if window_id == emulator_window_id =>
             {
@@ -665,15 +967,30 @@ where
             {
                 self.state.handle_input(GenericInput::Mouse(mouse_btn), false);
             }
-            Event::MouseMotion { x, y, window_id, .. } if window_id == emulator_window_id => {
+            Event::MouseMotion { x, y, xrel, yrel, window_id, .. }
+                if window_id == emulator_window_id =>
+            {
                 if let Some((frame_size, display_area)) = display_info {
-                    self.state.inputs.handle_mouse_motion(x, y, frame_size, display_area);
+                    let (x, y) = jgenesis_common::input::viewport_position_to_frame_position(
+                        x,
+                        y,
+                        frame_size,
+                        display_area,
+                    );
+                    self.state.inputs.handle_analog_input(AnalogInput::MousePosition {
+                        x,
+                        y,
+                        frame_size,
+                    });
                 }
+                self.state
+                    .inputs
+                    .handle_analog_input(AnalogInput::MouseMotion { dx: xrel, dy: yrel });
             }
             Event::Window { win_event: WindowEvent::Leave, window_id, .. }
                 if window_id == emulator_window_id =>
             {
-                self.state.inputs.handle_mouse_leave();
+                self.state.inputs.handle_analog_input(AnalogInput::MouseLeave);
             }
             Event::JoyButtonDown { which, button_idx, .. } => {
                 let Some(gamepad_idx) = self.joysticks.map_to_device_id(which) else { return };
@@ -711,16 +1028,26 @@ where
             Event::JoyDeviceRemoved { which, .. } => {
                 let Some(gamepad_idx) = self.joysticks.handle_device_removed(which) else { return };
                 self.state.unset_all_gamepad_inputs(gamepad_idx);
+                self.disconnected_gamepad = Some(gamepad_idx);
             }
             _ => {}
         }
     }
 
     fn handle_axis_input(&mut self, gamepad_idx: u32, axis_idx: u8, value: i16) {
-        let pressed = value.saturating_abs() > self.axis_deadzone;
+        let player = self.state.gamepad_player(gamepad_idx);
+        let axis_config = self.analog_config.axis(axis_idx);
+
+        if let Some(player) = player {
+            let value = analog_axis_value(axis_config, value);
+            self.state.inputs.handle_analog_input(AnalogInput::GamepadAxis {
+                player,
+                axis_idx,
+                value,
+            });
+        }
 
-        if pressed {
-            let direction = AxisDirection::from_value(value);
+        if let Some(direction) = axis_direction_pressed(axis_config, value) {
             self.state.handle_input(
                 GenericInput::Gamepad {
                     gamepad_idx,
@@ -771,6 +1098,10 @@ impl<Inputs, Button> InputMapper<Inputs, Button> {
         &self.state.inputs
     }
 
+    pub fn joysticks(&self) -> &Joysticks {
+        &self.joysticks
+    }
+
     pub fn joysticks_mut(&mut self) -> &mut Joysticks {
         &mut self.joysticks
     }
@@ -1057,6 +1388,197 @@ mod tests {
         assert_eq!(expected, take_hotkey_events(&mut state), "combination secondary key released");
     }
 
+    #[test]
+    fn gamepad_hotkey_combination() {
+        fn gamepad_button(button_idx: u8) -> GenericInput {
+            GenericInput::Gamepad { gamepad_idx: 0, action: GamepadAction::Button(button_idx) }
+        }
+
+        let mut state = InputMapperState::new(SmsGgInputs::default());
+        state.update_mappings(
+            &[((SmsGgButton::Button1, Player::One), &vec![gamepad_button(5)])],
+            &[(Hotkey::SaveState, &vec![gamepad_button(6), gamepad_button(5)])],
+        );
+
+        let mut expected_inputs = SmsGgInputs::default();
+        let mut expected_hotkeys: Vec<HotkeyEvent> = vec![];
+
+        state.handle_input(gamepad_button(5), true);
+        expected_inputs.p1.button1 = true;
+        assert_eq!(expected_inputs, state.inputs, "button pressed alone");
+        assert_eq!(expected_hotkeys, take_hotkey_events(&mut state), "button pressed alone");
+
+        state.handle_input(gamepad_button(5), false);
+        expected_inputs.p1.button1 = false;
+        assert_eq!(expected_inputs, state.inputs, "button released");
+        assert_eq!(expected_hotkeys, take_hotkey_events(&mut state), "button released");
+
+        state.handle_input(gamepad_button(6), true);
+        assert_eq!(expected_inputs, state.inputs, "1/2 pressed");
+        assert_eq!(expected_hotkeys, take_hotkey_events(&mut state), "1/2 pressed");
+
+        state.handle_input(gamepad_button(5), true);
+        expected_hotkeys = vec![HotkeyEvent::Pressed(Hotkey::SaveState)];
+        assert_eq!(expected_inputs, state.inputs, "2/2 pressed");
+        assert_eq!(expected_hotkeys, take_hotkey_events(&mut state), "2/2 pressed");
+
+        state.handle_input(gamepad_button(5), false);
+        expected_hotkeys = vec![HotkeyEvent::Released(Hotkey::SaveState)];
+        assert_eq!(expected_inputs, state.inputs, "combination released");
+        assert_eq!(expected_hotkeys, take_hotkey_events(&mut state), "combination released");
+    }
+
+    #[test]
+    fn axis_deadzone_and_threshold() {
+        let stick = AxisConfig::default();
+        assert_eq!(None, axis_direction_pressed(stick, 0));
+        assert_eq!(None, axis_direction_pressed(stick, stick.deadzone));
+        assert_eq!(
+            Some(AxisDirection::Positive),
+            axis_direction_pressed(stick, stick.deadzone + 1)
+        );
+        assert_eq!(Some(AxisDirection::Negative), axis_direction_pressed(stick, i16::MIN));
+
+        let stick_threshold = AxisConfig {
+            deadzone: 10000,
+            saturation: 30000,
+            response_curve: AxisResponseCurve::Quadratic,
+            digital_threshold: 25,
+            ..AxisConfig::default()
+        };
+        // (19000 - 10000) / 20000 = 0.45, squared ~= 0.2
+        assert_eq!(None, axis_direction_pressed(stick_threshold, 19000));
+        // (21000 - 10000) / 20000 = 0.55, squared ~= 0.3
+        assert_eq!(Some(AxisDirection::Positive), axis_direction_pressed(stick_threshold, 21000));
+
+        let trigger = AxisConfig {
+            mode: AxisMode::Trigger,
+            deadzone: 0,
+            digital_threshold: 50,
+            ..AxisConfig::default()
+        };
+        assert_eq!(None, axis_direction_pressed(trigger, i16::MIN));
+        assert_eq!(None, axis_direction_pressed(trigger, -1000));
+        assert_eq!(Some(AxisDirection::Positive), axis_direction_pressed(trigger, 1000));
+        assert_eq!(Some(AxisDirection::Positive), axis_direction_pressed(trigger, i16::MAX));
+    }
+
+    #[test]
+    fn analog_axis_values() {
+        // Compare as rounded percentages to avoid floating-point comparisons
+        let percent = |config, value| (analog_axis_value(config, value) * 100.0).round() as i32;
+
+        let stick = AxisConfig {
+            deadzone: 10000,
+            saturation: 30000,
+            response_curve: AxisResponseCurve::Linear,
+            ..AxisConfig::default()
+        };
+        assert_eq!(0, percent(stick, 9000));
+        assert_eq!(50, percent(stick, 20000));
+        assert_eq!(-50, percent(stick, -20000));
+        assert_eq!(100, percent(stick, i16::MAX));
+        assert_eq!(-100, percent(stick, i16::MIN));
+
+        let trigger = AxisConfig { mode: AxisMode::Trigger, deadzone: 0, ..AxisConfig::default() };
+        assert_eq!(0, percent(trigger, i16::MIN));
+        assert_eq!(100, percent(trigger, i16::MAX));
+    }
+
+    #[test]
+    fn text_entry_bypasses_mappings() {
+        let mut state = InputMapperState::new(SmsGgInputs::default());
+        state.update_mappings(
+            &[
+                ((SmsGgButton::Button1, Player::One), &vec![GenericInput::Keyboard(Keycode::F)]),
+                ((SmsGgButton::Up, Player::One), &vec![GenericInput::Keyboard(Keycode::Up)]),
+            ],
+            &[],
+        );
+
+        state.handle_key_down(Keycode::F);
+        assert!(state.inputs.p1.button1);
+
+        // Releasing a held button still works after text entry starts
+        state.text_entry = true;
+        state.handle_input(GenericInput::Keyboard(Keycode::F), false);
+        assert!(!state.inputs.p1.button1);
+
+        state.handle_key_down(Keycode::F);
+        state.handle_text("f");
+        state.handle_key_down(Keycode::Backspace);
+        state.handle_key_down(Keycode::Return);
+        assert!(!state.inputs.p1.button1);
+        assert_eq!(
+            state.text_events,
+            vec![
+                TextInputEvent::Text("f".into()),
+                TextInputEvent::Backspace,
+                TextInputEvent::Submit
+            ]
+        );
+
+        // Keys that do not type text are still mapped
+        state.handle_key_down(Keycode::Up);
+        assert!(state.inputs.p1.up);
+
+        // Text is ignored when the system does not want it
+        state.text_events.clear();
+        state.text_entry = false;
+        state.handle_text("h");
+        assert!(state.text_events.is_empty());
+    }
+
+    #[test]
+    fn swap_players() {
+        let mut state = InputMapperState::new(SmsGgInputs::default());
+        state.update_mappings(
+            &[
+                ((SmsGgButton::Button1, Player::One), &vec![GenericInput::Keyboard(Keycode::F)]),
+                ((SmsGgButton::Button1, Player::Two), &vec![GenericInput::Keyboard(Keycode::G)]),
+            ],
+            &[],
+        );
+
+        let mut expected = SmsGgInputs::default();
+
+        state.handle_input(GenericInput::Keyboard(Keycode::F), true);
+        expected.p1.button1 = true;
+        assert_eq!(expected, state.inputs, "P1 pressed before swap");
+
+        state.swap_players();
+        expected.p1.button1 = false;
+        expected.p2.button1 = true;
+        assert_eq!(expected, state.inputs, "held input moves to P2 on swap");
+
+        state.handle_input(GenericInput::Keyboard(Keycode::G), true);
+        expected.p1.button1 = true;
+        assert_eq!(expected, state.inputs, "P2 mapping controls P1 after swap");
+
+        state.handle_input(GenericInput::Keyboard(Keycode::F), false);
+        state.handle_input(GenericInput::Keyboard(Keycode::G), false);
+        expected = SmsGgInputs::default();
+        assert_eq!(expected, state.inputs, "all released after swap");
+
+        state.swap_players();
+        state.handle_input(GenericInput::Keyboard(Keycode::F), true);
+        expected.p1.button1 = true;
+        assert_eq!(expected, state.inputs, "P1 mapping controls P1 after swapping back");
+    }
+
+    #[test]
+    fn gamepad_player_follows_swap() {
+        let mut state = InputMapperState::new(SmsGgInputs::default());
+        assert_eq!(state.gamepad_player(0), Some(Player::One));
+        assert_eq!(state.gamepad_player(1), Some(Player::Two));
+        assert_eq!(state.gamepad_player(2), None);
+
+        state.swap_players();
+        assert_eq!(state.gamepad_player(0), Some(Player::Two));
+        assert_eq!(state.gamepad_player(1), Some(Player::One));
+        assert_eq!(state.gamepad_player(2), None);
+    }
+
     #[test]
     fn shift_canonicalization_basic() {
         let mut state = InputMapperState::new(SmsGgInputs::default());
@@ -1146,4 +1668,64 @@ mod tests {
             "Releasing RShift while LShift is not held should change mapping"
         );
     }
+
+    fn locks(p1: Option<&str>, p2: Option<&str>) -> [Option<String>; 2] {
+        [p1.map(String::from), p2.map(String::from)]
+    }
+
+    #[test]
+    fn gamepad_slots_auto_assign() {
+        let mut slots = GamepadSlots::default();
+        assert_eq!(slots.assign("a".into()), 0);
+        assert_eq!(slots.assign("b".into()), 1);
+        assert_eq!(slots.assign("c".into()), 2);
+
+        // Disconnecting frees the slot for the next gamepad
+        slots.release(0);
+        assert_eq!(slots.assign("d".into()), 0);
+    }
+
+    #[test]
+    fn gamepad_slots_locked_to_first_matching_gamepad() {
+        let mut slots = GamepadSlots::default();
+        assert_eq!(slots.set_player_locks(locks(Some("a"), None)), Some(vec![]));
+
+        // Other gamepads skip the locked slot, even while it's empty
+        assert_eq!(slots.assign("b".into()), 1);
+        assert_eq!(slots.assign("c".into()), 2);
+        assert_eq!(slots.assign("a".into()), 0);
+
+        // A second gamepad with the same GUID is treated like any other gamepad
+        assert_eq!(slots.assign("a".into()), 3);
+    }
+
+    #[test]
+    fn gamepad_slots_lock_released_on_disconnect() {
+        let mut slots = GamepadSlots::default();
+        slots.set_player_locks(locks(None, Some("a")));
+        assert_eq!(slots.assign("a".into()), 1);
+        assert_eq!(slots.assign("a".into()), 0);
+
+        // Disconnecting the locked gamepad frees its slot for the other gamepad with that GUID,
+        // but never for a gamepad with a different GUID
+        slots.release(1);
+        assert_eq!(slots.assign("b".into()), 2);
+        slots.release(0);
+        assert_eq!(slots.set_player_locks(locks(None, Some("a"))), None);
+        assert_eq!(slots.assign("a".into()), 1);
+        assert_eq!(slots.assign("c".into()), 0);
+    }
+
+    #[test]
+    fn gamepad_slots_reassigned_when_locks_change() {
+        let mut slots = GamepadSlots::default();
+        assert_eq!(slots.assign("a".into()), 0);
+        assert_eq!(slots.assign("b".into()), 1);
+
+        assert_eq!(slots.set_player_locks(locks(Some("b"), None)), Some(vec![(0, 1), (1, 0)]));
+        assert_eq!(slots.set_player_locks(locks(Some("b"), None)), None);
+
+        // Clearing the locks keeps the current assignments
+        assert_eq!(slots.set_player_locks(locks(None, None)), Some(vec![(0, 0), (1, 1)]));
+    }
 }
@@ -5,12 +5,16 @@ mod fpstracker;
 pub mod input;
 mod mainloop;
 
+pub use fpstracker::{FrameStats, PerformanceStats};
 pub use mainloop::{
-    AudioError, Native32XEmulator, NativeEmulator, NativeEmulatorError, NativeEmulatorResult,
+    AudioDeviceInfo, AudioError, BenchReport, EmulatorCore, FrameHashReport,
+    Native32XEmulator, NativeEmulator, NativeEmulatorError, NativeEmulatorResult,
     NativeGameBoyEmulator, NativeGenesisEmulator, NativeNesEmulator, NativeSegaCdEmulator,
     NativeSmsGgEmulator, NativeSnesEmulator, NativeTickEffect, SAVE_STATE_SLOTS, SaveStateMetadata,
-    SaveWriteError, create_32x, create_gb, create_genesis, create_nes, create_sega_cd,
-    create_smsgg, create_snes,
+    SaveWriteError, SegaCdBackupRam, bench, bench_32x, bench_gb, bench_genesis,
+    bench_nes, bench_sega_cd, bench_smsgg, bench_snes, create_32x, create_emulator, create_gb,
+    create_genesis, create_nes, create_sega_cd, create_smsgg, create_snes,
+    discord_presence_available, hash_frames,
 };
 use sdl2::VideoSubsystem;
 
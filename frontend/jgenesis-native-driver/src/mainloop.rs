@@ -1,47 +1,77 @@
 mod audio;
+mod bench;
 mod debug;
+mod emucore;
 mod gb;
+mod gdb;
 mod genesis;
+mod inputviewer;
+mod link;
 mod nes;
+mod presence;
+#[cfg(feature = "profiling")]
+mod profiler;
 mod rewind;
 mod save;
 mod smsgg;
 mod snes;
 mod state;
+mod subframe;
+mod undo;
 
+pub use bench::{
+    BenchReport, FrameHashReport, bench, bench_32x, bench_gb, bench_genesis, bench_nes,
+    bench_sega_cd, bench_smsgg, bench_snes, hash_frames,
+};
+pub use emucore::{EmulatorCore, create_emulator};
 pub use gb::{NativeGameBoyEmulator, create_gb};
 pub use genesis::{
-    Native32XEmulator, NativeGenesisEmulator, NativeSegaCdEmulator, create_32x, create_genesis,
-    create_sega_cd,
+    Native32XEmulator, NativeGenesisEmulator, NativeSegaCdEmulator, SegaCdBackupRam, create_32x,
+    create_genesis, create_sega_cd,
 };
 pub use nes::{NativeNesEmulator, create_nes};
+pub use presence::discord_presence_available;
 pub use smsgg::{NativeSmsGgEmulator, create_smsgg};
 pub use snes::{NativeSnesEmulator, create_snes};
 pub use state::{SAVE_STATE_SLOTS, SaveStateMetadata};
 
 use crate::archive::ArchiveError;
 use crate::config::input::ButtonMappingVec;
-use crate::config::{CommonConfig, FullscreenMode, HideMouseCursor, WindowSize};
-use crate::fpstracker::FpsTracker;
-use crate::input::{CompactHotkey, Hotkey, HotkeyEvent, InputMapper, Joysticks};
-use crate::mainloop::audio::SdlAudioOutput;
+use crate::config::{
+    CommonConfig, FullscreenMode, HideMouseCursor, InputViewerSkin, LinkCableMode, WindowSize,
+};
+use crate::fpstracker::{FpsTracker, PerformanceStats};
+use crate::input::{CompactHotkey, GamepadAssignment, Hotkey, HotkeyEvent, InputMapper, Joysticks};
+use crate::mainloop::audio::{AudioDeviceChange, SdlAudioOutput};
 use crate::mainloop::debug::{DebugRenderFn, DebuggerWindow};
+use crate::mainloop::gdb::GdbStub;
+use crate::mainloop::link::LinkCableBridge;
+use crate::mainloop::presence::DiscordPresence;
 use crate::mainloop::rewind::Rewinder;
 use crate::mainloop::save::{DeterminedPaths, FsSaveWriter};
-use crate::mainloop::state::SaveStatePaths;
-pub use audio::AudioError;
+use crate::mainloop::state::{SaveStatePaths, StateSnapshot};
+use crate::mainloop::subframe::SubframeInputQueue;
+use crate::mainloop::undo::{OverwrittenState, StateUndoBuffer};
+pub use audio::{AudioDeviceInfo, AudioError};
 use bincode::error::{DecodeError, EncodeError};
 use gb_core::api::GameBoyLoadError;
-use jgenesis_common::frontend::{EmulatorConfigTrait, EmulatorTrait, TickEffect};
+use jgenesis_common::frontend::{
+    EmulatorConfigTrait, EmulatorTrait, SaveStateSafePoint, TickEffect,
+};
+use jgenesis_common::input::Player;
+use jgenesis_common::instrument;
+use jgenesis_renderer::config::OverlayPosition;
 use jgenesis_renderer::renderer;
 use jgenesis_renderer::renderer::{RendererError, WgpuRenderer};
 use nes_core::api::NesInitializationError;
 pub use save::SaveWriteError;
 use sdl2::event::{Event, WindowEvent};
+use sdl2::messagebox;
+use sdl2::messagebox::{ButtonData, ClickedButton, MessageBoxButtonFlag, MessageBoxFlag};
 use sdl2::render::TextureValueError;
 use sdl2::video::{FullscreenType, Window, WindowBuildError};
 use sdl2::{AudioSubsystem, EventPump, IntegerOrSdlError, JoystickSubsystem, Sdl, VideoSubsystem};
-use segacd_core::api::SegaCdLoadError;
+use segacd_core::api::{BackupRamConvertError, SegaCdLoadError};
 use snes_core::api::SnesLoadError;
 use std::cell::RefCell;
 use std::error::Error;
@@ -49,12 +79,24 @@ use std::ffi::NulError;
 use std::fmt::Debug;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{io, thread};
 use thiserror::Error;
 
 const MODAL_DURATION: Duration = Duration::from_secs(3);
 
+const FAST_BOOT_SPEED_MULTIPLIER: u64 = 32;
+// Stop fast-forwarding after 60 seconds of emulated time in case the core never reports that the
+// game has started, e.g. because the disc is not bootable
+const FAST_BOOT_MAX_FRAMES: u32 = 3600;
+
+fn periodic_auto_save_interval(config: &CommonConfig) -> Option<Duration> {
+    (config.auto_save_state
+        && config.periodic_auto_save_state
+        && config.auto_save_state_interval_minutes != 0)
+        .then(|| Duration::from_secs(60 * config.auto_save_state_interval_minutes))
+}
+
 impl FullscreenMode {
     fn to_sdl_fullscreen(self) -> FullscreenType {
         match self {
@@ -128,9 +170,15 @@ struct HotkeyState<Emulator> {
     save_state_slot: usize,
     save_state_metadata: SaveStateMetadata,
     paused: bool,
+    // Set when emulation was paused automatically because the window lost focus, so that it can
+    // automatically resume when focus returns
+    paused_by_focus_loss: bool,
+    pause_when_unfocused: bool,
+    pause_on_controller_disconnect: bool,
     should_step_frame: bool,
     fast_forward_multiplier: u64,
     rewinder: Rewinder<Emulator>,
+    state_undo: StateUndoBuffer<Emulator>,
     overclocking_enabled: bool,
     debugger_window: Option<DebuggerWindow<Emulator>>,
     window_scale_factor: Option<f32>,
@@ -157,11 +205,15 @@ impl<Emulator: EmulatorTrait> HotkeyState<Emulator> {
             save_state_slot: 0,
             save_state_metadata,
             paused: false,
+            paused_by_focus_loss: false,
+            pause_when_unfocused: common_config.pause_when_unfocused,
+            pause_on_controller_disconnect: common_config.pause_on_controller_disconnect,
             should_step_frame: false,
             fast_forward_multiplier: common_config.fast_forward_multiplier,
             rewinder: Rewinder::new(Duration::from_secs(
                 common_config.rewind_buffer_length_seconds,
             )),
+            state_undo: StateUndoBuffer::new(),
             overclocking_enabled: true,
             debugger_window: None,
             window_scale_factor: common_config.window_scale_factor,
@@ -189,15 +241,27 @@ pub enum NativeTickEffect {
     Exit,
 }
 
+// Save state operation requested while the emulator was not at a save state safe point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeferredStateOperation {
+    Save { slot: usize },
+    Load { slot: usize },
+    UndoLoad,
+}
+
 pub struct NativeEmulator<Emulator: EmulatorTrait> {
     emulator: Emulator,
     // Config sent from the frontend
     raw_config: Emulator::Config,
-    // Config with overclocking maybe forcibly disabled due to hotkey state
+    // Config with overclocking maybe forcibly disabled due to hotkey state, or with all
+    // enhancements disabled in purist mode
     config: Emulator::Config,
     renderer: WgpuRenderer<Window>,
     audio_output: SdlAudioOutput,
     input_mapper: InputMapper<Emulator::Inputs, Emulator::Button>,
+    // Input events waiting to be replayed partway through the next frame, if sub-frame input is
+    // enabled
+    subframe_inputs: Option<SubframeInputQueue>,
     save_writer: FsSaveWriter,
     sdl: Sdl,
     event_pump: EventPump,
@@ -207,25 +271,55 @@ pub struct NativeEmulator<Emulator: EmulatorTrait> {
     fps_tracker: FpsTracker,
     rom_path: PathBuf,
     rom_extension: String,
+    rom_hash: Option<String>,
+    auto_state_path: Option<PathBuf>,
+    // Interval for periodically writing the automatic save state in the background, if enabled
+    auto_save_interval: Option<Duration>,
+    last_auto_save: Instant,
+    auto_save_thread: Option<thread::JoinHandle<()>>,
+    round_frame_rate: bool,
+    input_viewer: Option<InputViewerSkin>,
+    performance_hud: bool,
+    overlay_position: OverlayPosition,
+    gdb_server: Option<Box<dyn GdbStub<Emulator>>>,
+    link_cable: Option<Box<dyn LinkCableBridge<Emulator>>>,
+    discord_presence: DiscordPresence,
+    purist_mode: bool,
+    fast_boot_enabled: bool,
+    // Number of frames run so far while fast-forwarding through the boot sequence, if currently
+    // fast booting
+    fast_boot_frames: Option<u32>,
+    // Speed multiplier set by the fast forward hotkey, restored when fast boot ends
+    speed_multiplier: u64,
+    // Whether the last emulator tick did not complete a frame, e.g. because the debugger stopped
+    // emulation at a breakpoint
+    mid_frame: bool,
+    deferred_state_operation: Option<DeferredStateOperation>,
 }
 
 impl<Emulator: EmulatorTrait> NativeEmulator<Emulator> {
     fn reload_common_config(&mut self, config: &CommonConfig) -> Result<(), AudioError> {
-        self.renderer.reload_config(config.renderer_config);
+        let sync_config = config.with_sync_mode_applied();
+        self.renderer.reload_config(sync_config.renderer_config);
 
-        self.audio_output.reload_config(config)?;
-        self.emulator.update_audio_output_frequency(self.audio_output.output_frequency());
+        self.audio_output.reload_config(&sync_config)?;
+        self.round_frame_rate = config.round_frame_rate;
+        self.update_audio_output_frequency();
 
         self.hotkey_state.hide_mouse_cursor = config.hide_mouse_cursor;
         self.hotkey_state.fullscreen_mode = config.fullscreen_mode;
+
+        self.input_viewer = input_viewer_config(config);
+        self.overlay_position = config.input_viewer_position;
         if let Err(err) = self.renderer.update_fullscreen_mode(config.fullscreen_mode) {
             log::error!("Error updating fullscreen mode to {}: {err}", config.fullscreen_mode);
         }
 
         self.hotkey_state.fast_forward_multiplier = config.fast_forward_multiplier;
+        self.hotkey_state.pause_when_unfocused = config.pause_when_unfocused;
+        self.hotkey_state.pause_on_controller_disconnect = config.pause_on_controller_disconnect;
         // Reset speed multiplier in case the fast forward hotkey changed
-        self.renderer.set_speed_multiplier(1);
-        self.audio_output.set_speed_multiplier(1);
+        self.set_speed_multiplier(1);
 
         if let Err(err) = self.update_save_paths(config) {
             log::error!("Error updating save paths: {err}");
@@ -235,12 +329,55 @@ impl<Emulator: EmulatorTrait> NativeEmulator<Emulator> {
             .rewinder
             .set_buffer_duration(Duration::from_secs(config.rewind_buffer_length_seconds));
 
+        self.input_mapper.update_gamepad_locks(config.gamepad_locks.to_array());
+        set_background_gamepad_input(config.background_gamepad_input);
+        self.set_subframe_input_enabled(config.subframe_input);
+
+        self.discord_presence.set_enabled(config.discord_presence_enabled);
+        self.purist_mode = config.purist_mode;
+        self.auto_save_interval = periodic_auto_save_interval(config);
+
         let fullscreen = self.renderer.is_fullscreen();
         self.sdl.mouse().show_cursor(!config.hide_mouse_cursor.should_hide(fullscreen));
 
         Ok(())
     }
 
+    // Shared by all of the per-system config reload methods
+    fn reload_shared_config(
+        &mut self,
+        common_config: &CommonConfig,
+        emulator_config: &Emulator::Config,
+        button_mappings: &ButtonMappingVec<'_, Emulator::Button>,
+    ) -> Result<(), AudioError> {
+        self.reload_common_config(common_config)?;
+
+        self.update_emulator_config(emulator_config);
+
+        // Config change could have changed target framerate (e.g. NTSC vs. PAL)
+        let target_fps = paced_frame_rate(self.emulator.target_fps(), self.round_frame_rate);
+        self.renderer.set_target_fps(target_fps);
+        self.update_audio_output_frequency();
+
+        self.input_mapper.update_mappings(
+            &common_config.analog_config,
+            button_mappings,
+            &common_config.hotkey_config.to_mapping_vec(),
+        );
+
+        Ok(())
+    }
+
+    fn update_audio_output_frequency(&mut self) {
+        let native_fps = self.emulator.target_fps();
+        let paced_fps = paced_frame_rate(native_fps, self.round_frame_rate);
+        self.emulator.update_audio_output_frequency(emulator_output_frequency(
+            self.audio_output.output_frequency(),
+            native_fps,
+            paced_fps,
+        ));
+    }
+
     fn update_save_paths(&mut self, config: &CommonConfig) -> NativeEmulatorResult<()> {
         let DeterminedPaths { save_path, save_state_path } = save::determine_save_paths(
             &config.save_path,
@@ -251,10 +388,95 @@ impl<Emulator: EmulatorTrait> NativeEmulator<Emulator> {
 
         self.save_writer.update_path(save_path);
         self.hotkey_state.update_save_state_path(save_state_path)?;
+        self.update_auto_state_path(config.auto_save_state);
 
         Ok(())
     }
 
+    fn update_rom_hash(&mut self, path: &Path) {
+        self.rom_hash = match state::rom_hash(path) {
+            Ok(rom_hash) => Some(rom_hash),
+            Err(err) => {
+                log::error!("Error hashing '{}' for automatic save state: {err}", path.display());
+                None
+            }
+        };
+    }
+
+    fn update_auto_state_path(&mut self, enabled: bool) {
+        if !enabled {
+            self.auto_state_path = None;
+            return;
+        }
+
+        if self.rom_hash.is_none() {
+            self.update_rom_hash(&self.rom_path.clone());
+        }
+
+        let Some(rom_hash) = &self.rom_hash else { return };
+        match state::auto_state_path(&self.hotkey_state.base_save_state_path, rom_hash) {
+            Ok(path) => self.auto_state_path = Some(path),
+            Err(err) => log::error!("Error determining automatic save state path: {err}"),
+        }
+    }
+
+    fn set_subframe_input_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.apply_all_subframe_inputs();
+            self.subframe_inputs = None;
+            return;
+        }
+
+        if self.subframe_inputs.is_some() {
+            return;
+        }
+
+        match self.sdl.timer() {
+            Ok(timer) => self.subframe_inputs = Some(SubframeInputQueue::new(timer)),
+            Err(err) => log::error!("Error initializing SDL timer for sub-frame input: {err}"),
+        }
+    }
+
+    fn apply_due_subframe_inputs(&mut self) {
+        let Some(subframe_inputs) = &mut self.subframe_inputs else { return };
+
+        while let Some(event) = subframe_inputs.pop_due() {
+            self.input_mapper.handle_event(
+                &event,
+                self.renderer.window_id(),
+                self.renderer.current_display_info(),
+            );
+        }
+
+        self.forward_text_input();
+    }
+
+    // Apply any input events that were not replayed during the last frame, e.g. because the
+    // emulator is paused or the frame was shorter than expected
+    fn apply_all_subframe_inputs(&mut self) {
+        let Some(subframe_inputs) = &mut self.subframe_inputs else { return };
+
+        for event in subframe_inputs.pop_all() {
+            self.input_mapper.handle_event(
+                &event,
+                self.renderer.window_id(),
+                self.renderer.current_display_info(),
+            );
+        }
+
+        self.forward_text_input();
+    }
+
+    // Pass typed text to the emulator, and update whether the following keyboard events should be
+    // treated as text entry
+    fn forward_text_input(&mut self) {
+        for event in self.input_mapper.take_text_input() {
+            self.emulator.handle_text_input(event);
+        }
+
+        self.input_mapper.set_text_entry(self.emulator.wants_text_input());
+    }
+
     pub fn focus(&mut self) {
         self.renderer.focus();
     }
@@ -262,6 +484,11 @@ impl<Emulator: EmulatorTrait> NativeEmulator<Emulator> {
     pub fn event_pump_and_joysticks_mut(&mut self) -> (&mut EventPump, &mut Joysticks) {
         (&mut self.event_pump, self.input_mapper.joysticks_mut())
     }
+
+    #[must_use]
+    pub fn gamepad_assignments(&self) -> Vec<GamepadAssignment> {
+        self.input_mapper.joysticks().assignments()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -270,13 +497,48 @@ enum HotkeyEffect {
     Exit,
 }
 
+fn prompt_resume(window: &Window) -> bool {
+    const RESUME_BUTTON_ID: i32 = 0;
+    const START_OVER_BUTTON_ID: i32 = 1;
+
+    let buttons = [
+        ButtonData {
+            flags: MessageBoxButtonFlag::RETURNKEY_DEFAULT,
+            button_id: RESUME_BUTTON_ID,
+            text: "Resume",
+        },
+        ButtonData {
+            flags: MessageBoxButtonFlag::ESCAPEKEY_DEFAULT,
+            button_id: START_OVER_BUTTON_ID,
+            text: "Start over",
+        },
+    ];
+
+    match messagebox::show_message_box(
+        MessageBoxFlag::INFORMATION,
+        &buttons,
+        "Resume game",
+        "Resume from where you left off last time?",
+        window,
+        None,
+    ) {
+        Ok(ClickedButton::CustomButton(button)) => button.button_id == RESUME_BUTTON_ID,
+        Ok(ClickedButton::CloseButton) => false,
+        Err(err) => {
+            log::error!("Error showing resume prompt: {err}");
+            false
+        }
+    }
+}
+
 fn open_debugger_window<Emulator>(
     video: &VideoSubsystem,
     scale_factor: Option<f32>,
     debug_render_fn: fn() -> Box<DebugRenderFn<Emulator>>,
+    rom_path: &Path,
 ) -> Option<DebuggerWindow<Emulator>> {
     let render_fn = debug_render_fn();
-    match DebuggerWindow::new(video, scale_factor, render_fn) {
+    match DebuggerWindow::new(video, scale_factor, render_fn, rom_path.to_path_buf()) {
         Ok(debugger_window) => Some(debugger_window),
         Err(err) => {
             log::error!("Error opening debugger window: {err}");
@@ -285,6 +547,26 @@ fn open_debugger_window<Emulator>(
     }
 }
 
+fn input_viewer_config(config: &CommonConfig) -> Option<InputViewerSkin> {
+    config.input_viewer_enabled.then_some(config.input_viewer_skin)
+}
+
+// Every supported console runs at slightly more or less than either 60Hz (NTSC) or 50Hz (PAL)
+fn paced_frame_rate(native_fps: f64, round_frame_rate: bool) -> f64 {
+    if !round_frame_rate {
+        return native_fps;
+    }
+
+    if native_fps < 55.0 { 50.0 } else { 60.0 }
+}
+
+// When frames are paced faster or slower than the console's native frame rate, the emulator
+// produces audio samples faster or slower by the same ratio. Scale the output frequency that the
+// emulator resamples to so that audio output still matches the audio device's frequency
+fn emulator_output_frequency(audio_output_frequency: u64, native_fps: f64, paced_fps: f64) -> u64 {
+    (audio_output_frequency as f64 * native_fps / paced_fps).round() as u64
+}
+
 #[derive(Debug, Error)]
 pub enum NativeEmulatorError {
     #[error("{0}")]
@@ -347,6 +629,18 @@ pub enum NativeEmulatorError {
     },
     #[error("{0}")]
     Archive(#[from] ArchiveError),
+    #[error("Failed to start GDB server on port {port}: {source}")]
+    GdbBind {
+        port: u16,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to open link cable ({mode:?}): {source}")]
+    LinkCableOpen {
+        mode: LinkCableMode,
+        #[source]
+        source: io::Error,
+    },
     #[error("BIOS is required for Sega CD emulation")]
     SegaCdNoBios,
     #[error("Error opening BIOS file at '{path}': {source}")]
@@ -356,8 +650,24 @@ pub enum NativeEmulatorError {
         source: io::Error,
     },
 
+    #[error("Super Game Boy BIOS is required to run Game Boy games in the SNES core")]
+    SuperGameBoyNoBios,
+    #[error("Error opening Super Game Boy BIOS file at '{path}': {source}")]
+    SuperGameBoyBiosRead {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
     #[error("{0}")]
     SegaCdDisc(#[from] SegaCdLoadError),
+    #[error("I/O error accessing Sega CD backup RAM file '{path}': {source}")]
+    SegaCdBackupRamIo {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("{0}")]
+    SegaCdBackupRamConvert(#[from] BackupRamConvertError),
     #[error("{0}")]
     NesLoad(#[from] NesInitializationError),
     #[error("{0}")]
@@ -406,6 +716,11 @@ where
         initial_inputs: Emulator::Inputs,
         debug_render_fn: fn() -> Box<DebugRenderFn<Emulator>>,
     ) -> NativeEmulatorResult<Self> {
+        let common_config = common_config.with_sync_mode_applied();
+
+        #[cfg(feature = "profiling")]
+        profiler::FrameProfiler::install();
+
         let (sdl, video, audio, joystick, event_pump) = init_sdl(&common_config)?;
 
         let mut initial_window_size = common_config.window_size.unwrap_or(default_window_size);
@@ -427,20 +742,31 @@ where
             window_size,
             common_config.renderer_config,
         ))?;
-        renderer.set_target_fps(emulator.target_fps());
+        let refresh_rate = display_refresh_rate_millihz(renderer.window());
+        renderer.set_display_refresh_rate(refresh_rate);
+        let native_fps = emulator.target_fps();
+        let paced_fps = paced_frame_rate(native_fps, common_config.round_frame_rate);
+        renderer.set_target_fps(paced_fps);
 
         let audio_output = SdlAudioOutput::create_and_init(&audio, &common_config)?;
-        emulator.update_audio_output_frequency(audio_output.output_frequency());
+        emulator.update_audio_output_frequency(emulator_output_frequency(
+            audio_output.output_frequency(),
+            native_fps,
+            paced_fps,
+        ));
 
-        let input_mapper = InputMapper::new(
+        let mut input_mapper = InputMapper::new(
             initial_inputs,
             joystick,
-            common_config.axis_deadzone,
+            &common_config.analog_config,
             button_mappings,
             &common_config.hotkey_config.to_mapping_vec(),
         );
+        input_mapper.update_gamepad_locks(common_config.gamepad_locks.to_array());
 
         let hotkey_state = HotkeyState::new(&common_config, save_state_path, debug_render_fn)?;
+        let input_viewer = input_viewer_config(&common_config);
+        let auto_save_interval = periodic_auto_save_interval(&common_config);
 
         let mut emulator = Self {
             emulator,
@@ -449,6 +775,7 @@ where
             renderer,
             audio_output,
             input_mapper,
+            subframe_inputs: None,
             save_writer,
             sdl,
             event_pump,
@@ -458,9 +785,47 @@ where
             fps_tracker: FpsTracker::new(),
             rom_path: common_config.rom_file_path,
             rom_extension,
+            rom_hash: None,
+            auto_state_path: None,
+            auto_save_interval,
+            last_auto_save: Instant::now(),
+            auto_save_thread: None,
+            round_frame_rate: common_config.round_frame_rate,
+            input_viewer,
+            performance_hud: false,
+            overlay_position: common_config.input_viewer_position,
+            gdb_server: None,
+            link_cable: None,
+            discord_presence: DiscordPresence::new(common_config.discord_presence_enabled),
+            purist_mode: common_config.purist_mode,
+            fast_boot_enabled: false,
+            fast_boot_frames: None,
+            speed_multiplier: 1,
+            mid_frame: false,
+            deferred_state_operation: None,
         };
 
-        if common_config.load_recent_state_at_launch {
+        emulator.input_mapper.set_text_entry(emulator.emulator.wants_text_input());
+
+        // The emulator was created using the raw config
+        if emulator.purist_mode {
+            log::info!("Purist mode active; disabling all enhancements");
+            emulator.update_emulator_config(&emulator.raw_config.clone());
+        }
+
+        let quirks = emulator.emulator.game_metadata().quirks;
+        if !quirks.is_empty() {
+            emulator.renderer.add_modal(
+                format!("Game-specific workarounds active: {}", quirks.join(", ")),
+                MODAL_DURATION,
+            );
+        }
+
+        emulator.set_subframe_input_enabled(common_config.subframe_input);
+        emulator.update_auto_state_path(common_config.auto_save_state);
+        let resumed = emulator.try_resume_auto_state();
+
+        if !resumed && common_config.load_recent_state_at_launch {
             emulator.try_load_most_recent_state();
         }
 
@@ -477,33 +842,80 @@ where
     /// This method will propagate any errors encountered when rendering frames, pushing audio
     /// samples, or writing save files.
     pub fn render_frame(&mut self) -> NativeEmulatorResult<Option<NativeTickEffect>> {
+        #[cfg(feature = "profiling")]
+        profiler::finish_frame();
+        instrument!("frame");
+
+        if let Some(gdb_server) = &mut self.gdb_server {
+            gdb_server.poll(&mut self.emulator);
+        }
+
+        self.update_overlay();
+
         let rewinding = self.hotkey_state.rewinder.is_rewinding();
-        let should_run_emulator =
-            !rewinding && (!self.hotkey_state.paused || self.hotkey_state.should_step_frame);
+        let debugger_halted = self.gdb_server.as_ref().is_some_and(|gdb| gdb.halted());
+        let should_run_emulator = !rewinding
+            && !debugger_halted
+            && (!self.hotkey_state.paused || self.hotkey_state.should_step_frame);
 
         if should_run_emulator {
-            while self
-                .emulator
-                .tick(
-                    &mut self.renderer,
-                    &mut self.audio_output,
-                    self.input_mapper.inputs(),
-                    &mut self.save_writer,
-                )
-                .map_err(|err| NativeEmulatorError::Emulator(err.into()))?
-                != TickEffect::FrameRendered
-            {}
+            instrument!("emulate");
+
+            loop {
+                self.apply_due_subframe_inputs();
+
+                let tick_effect = self
+                    .emulator
+                    .tick(
+                        &mut self.renderer,
+                        &mut self.audio_output,
+                        self.input_mapper.inputs(),
+                        &mut self.save_writer,
+                    )
+                    .map_err(|err| NativeEmulatorError::Emulator(err.into()))?;
+
+                if let Some(subframe_inputs) = &mut self.subframe_inputs {
+                    subframe_inputs.record_tick();
+                    if tick_effect == TickEffect::FrameRendered {
+                        subframe_inputs.end_frame();
+                    }
+                }
+
+                if let Some(link_cable) = &mut self.link_cable {
+                    link_cable.exchange(&mut self.emulator);
+                }
+
+                // Stop mid-frame if the debugger hit a breakpoint
+                let debugger_stopped = self
+                    .gdb_server
+                    .as_mut()
+                    .is_some_and(|gdb| gdb.check_stopped(&mut self.emulator));
+
+                self.mid_frame = tick_effect != TickEffect::FrameRendered;
+                if tick_effect == TickEffect::FrameRendered || debugger_stopped {
+                    break;
+                }
+            }
+
+            self.run_deferred_state_operation()?;
 
             self.fps_tracker.record_frame();
             self.hotkey_state.rewinder.record_frame(&self.emulator);
+            self.check_periodic_auto_save();
+
+            self.update_fast_boot();
 
             self.audio_output.adjust_dynamic_resampling_ratio();
-            self.emulator.update_audio_output_frequency(self.audio_output.output_frequency());
+            self.update_audio_output_frequency();
         }
 
         self.hotkey_state.should_step_frame = false;
 
+        self.renderer.present_due_frame()?;
+
         if let Some(debugger_window) = &mut self.hotkey_state.debugger_window {
+            instrument!("debugger");
+
             if let Err(err) = debugger_window.update(&mut self.emulator) {
                 log::error!("Debugger window error: {err}");
             }
@@ -511,16 +923,28 @@ where
 
         // Gymnastics to avoid borrow checker errors that would otherwise occur due to
         // calling `&mut self` methods while mutably borrowing the event pump
+        instrument!("events");
+        self.apply_all_subframe_inputs();
+
         let event_buffer_ref = Rc::clone(&self.event_buffer);
         let mut event_buffer = event_buffer_ref.borrow_mut();
         event_buffer.extend(self.event_pump.poll_iter());
 
+        if let Some(subframe_inputs) = &mut self.subframe_inputs {
+            subframe_inputs.start_poll();
+        }
+
         for event in event_buffer.drain(..) {
-            self.input_mapper.handle_event(
-                &event,
-                self.renderer.window_id(),
-                self.renderer.current_display_info(),
-            );
+            match &mut self.subframe_inputs {
+                Some(subframe_inputs) if SubframeInputQueue::should_defer(&event) => {
+                    subframe_inputs.push(event.clone());
+                }
+                _ => self.input_mapper.handle_event(
+                    &event,
+                    self.renderer.window_id(),
+                    self.renderer.current_display_info(),
+                ),
+            }
 
             if let Some(debugger_window) = &mut self.hotkey_state.debugger_window {
                 debugger_window.handle_sdl_event(&event);
@@ -528,11 +952,13 @@ where
 
             match event {
                 Event::Quit { .. } => {
+                    self.save_auto_state();
                     return Ok(Some(NativeTickEffect::PowerOff));
                 }
                 Event::Window { win_event, window_id, .. } => {
                     if win_event == WindowEvent::Close {
                         if window_id == self.renderer.window_id() {
+                            self.save_auto_state();
                             return Ok(Some(NativeTickEffect::PowerOff));
                         }
 
@@ -543,24 +969,43 @@ where
                             .is_some_and(|debugger| window_id == debugger.window_id())
                         {
                             self.hotkey_state.debugger_window = None;
+
+                            // Channels muted in the debugger's audio mixer should not stay muted
+                            self.emulator.clear_audio_mutes();
                         }
                     }
 
                     if window_id == self.renderer.window_id() {
                         handle_window_event(win_event, &mut self.renderer);
                     }
+
+                    self.handle_focus_change(win_event, window_id);
                 }
                 _ => {}
             }
         }
 
+        self.forward_text_input();
+
+        if let Some(player) = self.input_mapper.take_disconnected_player() {
+            self.handle_controller_disconnected(player);
+        }
+
+        self.check_audio_device();
+
         let hotkey_events = self.input_mapper.hotkey_events();
         {
             let mut hotkey_events = hotkey_events.borrow_mut();
             for &hotkey_event in &*hotkey_events {
                 match self.handle_hotkey_event(hotkey_event)? {
-                    Some(HotkeyEffect::PowerOff) => return Ok(Some(NativeTickEffect::PowerOff)),
-                    Some(HotkeyEffect::Exit) => return Ok(Some(NativeTickEffect::Exit)),
+                    Some(HotkeyEffect::PowerOff) => {
+                        self.save_auto_state();
+                        return Ok(Some(NativeTickEffect::PowerOff));
+                    }
+                    Some(HotkeyEffect::Exit) => {
+                        self.save_auto_state();
+                        return Ok(Some(NativeTickEffect::Exit));
+                    }
                     None => {}
                 }
             }
@@ -576,7 +1021,7 @@ where
         }
 
         if !should_run_emulator {
-            // Don't spin loop when the emulator is paused or rewinding
+            // Don't spin loop when the emulator is paused, rewinding, or halted in the debugger
             thread::sleep(Duration::from_millis(1));
         }
 
@@ -587,8 +1032,130 @@ where
         self.emulator.soft_reset();
     }
 
+    /// Frame rate, emulation speed, and audio buffer statistics over the last few seconds.
+    #[must_use]
+    pub fn performance_stats(&self) -> PerformanceStats {
+        let frame = self.fps_tracker.stats();
+        PerformanceStats {
+            frame,
+            speed_percent: 100.0 * frame.fps / self.emulator.target_fps(),
+            audio_buffer_percent: self.audio_output.buffer_percent(),
+        }
+    }
+
+    /// The audio output device currently in use.
+    #[must_use]
+    pub fn audio_device_info(&self) -> AudioDeviceInfo {
+        self.audio_output.device_info()
+    }
+
+    /// Close the audio device and reopen it on the system default output device, e.g. after
+    /// switching output devices in the OS.
+    pub fn reinit_audio(&mut self) {
+        match self.audio_output.reopen_device() {
+            Ok(()) => {
+                log::info!("Re-initialized audio device: {:?}", self.audio_output.device_info());
+                self.update_audio_output_frequency();
+                self.renderer.add_modal("Audio device re-initialized".into(), MODAL_DURATION);
+            }
+            Err(err) => {
+                log::error!("Error re-initializing audio device: {err}");
+                self.renderer
+                    .add_modal("Failed to re-initialize audio device".into(), MODAL_DURATION);
+            }
+        }
+    }
+
+    fn check_audio_device(&mut self) {
+        match self.audio_output.check_device() {
+            Some(AudioDeviceChange::Disconnected) => {
+                self.renderer.add_modal("Audio device disconnected".into(), MODAL_DURATION);
+            }
+            Some(AudioDeviceChange::Reopened) => {
+                // The new device may not support the same output frequency as the old one
+                self.update_audio_output_frequency();
+                self.renderer.add_modal("Audio device reconnected".into(), MODAL_DURATION);
+            }
+            None => {}
+        }
+    }
+
+    fn toggle_performance_hud(&mut self) {
+        self.performance_hud = !self.performance_hud;
+        log::info!("Performance HUD enabled: {}", self.performance_hud);
+    }
+
+    // The performance HUD and input viewer share the overlay, with the HUD on top if both are enabled
+    fn update_overlay(&mut self) {
+        let hud_text = self.performance_hud.then(|| self.performance_stats().to_hud_text());
+        let input_text = self
+            .input_viewer
+            .and_then(|skin| inputviewer::format_inputs(self.input_mapper.inputs(), skin));
+
+        let text = match (hud_text, input_text) {
+            (Some(hud_text), Some(input_text)) => Some(format!("{hud_text}\n{input_text}")),
+            (hud_text, input_text) => hud_text.or(input_text),
+        };
+        self.renderer.set_overlay_text(text, self.overlay_position);
+    }
+
     pub fn hard_reset(&mut self) {
         self.emulator.hard_reset(&mut self.save_writer);
+
+        if self.fast_boot_enabled {
+            self.start_fast_boot();
+        }
+    }
+
+    /// Fast-forward through the BIOS boot sequence until the game starts running. This is also
+    /// re-applied after every hard reset.
+    fn enable_fast_boot(&mut self) {
+        self.fast_boot_enabled = true;
+        self.start_fast_boot();
+    }
+
+    fn start_fast_boot(&mut self) {
+        self.fast_boot_frames = Some(0);
+        self.apply_speed_multiplier(FAST_BOOT_SPEED_MULTIPLIER);
+    }
+
+    fn update_fast_boot(&mut self) {
+        let Some(frames) = &mut self.fast_boot_frames else { return };
+        *frames += 1;
+
+        let timed_out = *frames >= FAST_BOOT_MAX_FRAMES;
+        if !timed_out && self.emulator.is_booting() {
+            return;
+        }
+
+        if timed_out {
+            log::warn!("Game did not start after {FAST_BOOT_MAX_FRAMES} frames; ending fast boot");
+        } else {
+            log::info!("Boot sequence finished after {frames} frames");
+        }
+
+        self.fast_boot_frames = None;
+        self.apply_speed_multiplier(self.speed_multiplier);
+    }
+
+    /// Change the speed multiplier. While fast booting, this only takes effect once the boot
+    /// sequence finishes.
+    fn set_speed_multiplier(&mut self, speed_multiplier: u64) {
+        self.speed_multiplier = speed_multiplier;
+        if self.fast_boot_frames.is_none() {
+            self.apply_speed_multiplier(speed_multiplier);
+        }
+    }
+
+    fn apply_speed_multiplier(&mut self, speed_multiplier: u64) {
+        self.renderer.set_speed_multiplier(speed_multiplier);
+        self.audio_output.set_speed_multiplier(speed_multiplier);
+    }
+
+    pub fn swap_player_inputs(&mut self) {
+        let swapped = self.input_mapper.swap_players();
+        let message = if swapped { "Swapped P1/P2 inputs" } else { "Restored P1/P2 inputs" };
+        self.renderer.add_modal(message.into(), MODAL_DURATION);
     }
 
     pub fn open_memory_viewer(&mut self) {
@@ -597,16 +1164,33 @@ where
                 &self.video,
                 self.hotkey_state.window_scale_factor,
                 self.hotkey_state.debug_render_fn,
+                &self.rom_path,
             );
         }
     }
 
+    /// Save the current state to the given slot. If the emulator is not at a save state safe point
+    /// (e.g. the debugger stopped it mid-frame), the state is saved once the current frame ends.
+    ///
     /// # Errors
     ///
     /// Returns an error if the state cannot be saved (e.g. due to I/O error).
     pub fn save_state(&mut self, slot: usize) -> NativeEmulatorResult<()> {
+        if !self.at_save_state_safe_point() {
+            self.defer_state_operation(DeferredStateOperation::Save { slot });
+            return Ok(());
+        }
+
+        // Keep the previous contents of the slot in memory so that the save can be undone
+        let path = self.hotkey_state.save_state_paths[slot].clone();
+        let previous_contents = state::read_slot_contents(&path)
+            .inspect_err(|err| {
+                log::error!("Error reading save state slot {slot} in '{}': {err}", path.display());
+            })
+            .ok();
+
         if let Err(err) = state::save(
-            &self.emulator,
+            &mut self.emulator,
             &self.hotkey_state.save_state_paths,
             slot,
             &mut self.hotkey_state.save_state_metadata,
@@ -618,14 +1202,32 @@ where
         self.renderer.add_modal(format!("Saved state to slot {slot}"), MODAL_DURATION);
         self.hotkey_state.save_state_slot = slot;
 
+        if let Some(contents) = previous_contents {
+            self.hotkey_state.state_undo.push_overwritten(OverwrittenState {
+                slot,
+                path,
+                contents,
+            });
+        }
+
         Ok(())
     }
 
+    /// Load the state in the given slot. Like [`Self::save_state`], this is deferred until the end
+    /// of the current frame if the emulator is not at a save state safe point.
+    ///
     /// # Errors
     ///
     /// Return an error if the state cannot be loaded (e.g. due to I/O error or because the save
     /// state does not exist).
     pub fn load_state(&mut self, slot: usize) -> NativeEmulatorResult<()> {
+        if !self.at_save_state_safe_point() {
+            self.defer_state_operation(DeferredStateOperation::Load { slot });
+            return Ok(());
+        }
+
+        let pre_load_state = self.emulator.partial_clone();
+
         if let Err(err) =
             state::load(&mut self.emulator, &self.config, &self.hotkey_state.save_state_paths, slot)
         {
@@ -636,10 +1238,84 @@ where
 
         self.renderer.add_modal(format!("Loaded state from slot {slot}"), MODAL_DURATION);
         self.hotkey_state.save_state_slot = slot;
+        self.hotkey_state.state_undo.push_pre_load(pre_load_state);
+
+        Ok(())
+    }
+
+    // Restore the emulator state from right before the most recent save state load
+    fn undo_load_state(&mut self) -> NativeEmulatorResult<()> {
+        if !self.at_save_state_safe_point() {
+            self.defer_state_operation(DeferredStateOperation::UndoLoad);
+            return Ok(());
+        }
+
+        let Some(mut pre_load_state) = self.hotkey_state.state_undo.pop_pre_load() else {
+            self.renderer.add_modal("No save state load to undo".into(), MODAL_DURATION);
+            return Ok(());
+        };
+
+        pre_load_state.take_rom_from(&mut self.emulator);
+        self.emulator = pre_load_state;
+        self.emulator.reload_config(&self.config);
+        self.emulator.force_render(&mut self.renderer)?;
+
+        self.renderer.add_modal("Undid save state load".into(), MODAL_DURATION);
 
         Ok(())
     }
 
+    // Restore the previous contents of the most recently overwritten save state slot
+    fn undo_save_state(&mut self) {
+        let Some(overwritten) = self.hotkey_state.state_undo.pop_overwritten() else {
+            self.renderer.add_modal("No save state save to undo".into(), MODAL_DURATION);
+            return;
+        };
+
+        let slot = overwritten.slot;
+        if let Err(err) =
+            state::restore_slot_contents(&overwritten.path, overwritten.contents.as_deref())
+        {
+            log::error!(
+                "Error restoring save state slot {slot} in '{}': {err}",
+                overwritten.path.display()
+            );
+            self.renderer.add_modal(format!("Failed to undo save to slot {slot}"), MODAL_DURATION);
+            return;
+        }
+
+        self.hotkey_state.save_state_metadata = SaveStateMetadata::load(
+            &self.hotkey_state.save_state_paths,
+            Emulator::save_state_version(),
+        );
+        self.renderer.add_modal(format!("Undid save to slot {slot}"), MODAL_DURATION);
+    }
+
+    fn at_save_state_safe_point(&self) -> bool {
+        match Emulator::save_state_safe_point() {
+            SaveStateSafePoint::Tick => true,
+            SaveStateSafePoint::FrameBoundary => !self.mid_frame,
+        }
+    }
+
+    fn defer_state_operation(&mut self, operation: DeferredStateOperation) {
+        log::info!("Deferring {operation:?} until the end of the current frame");
+        self.deferred_state_operation = Some(operation);
+    }
+
+    fn run_deferred_state_operation(&mut self) -> NativeEmulatorResult<()> {
+        if !self.at_save_state_safe_point() {
+            return Ok(());
+        }
+
+        match self.deferred_state_operation.take() {
+            Some(DeferredStateOperation::Save { slot }) => self.save_state(slot),
+            Some(DeferredStateOperation::Load { slot }) => self.load_state(slot),
+            Some(DeferredStateOperation::UndoLoad) => self.undo_load_state(),
+            None => Ok(()),
+        }
+    }
+
     /// Try to load the most recent save state.
     ///
     /// If there are no save states or the most recent save state is invalid, this method will log
@@ -668,6 +1344,132 @@ where
         &self.hotkey_state.save_state_metadata
     }
 
+    /// Save the automatic save state if it is enabled. This should be called when the emulator is
+    /// about to shut down.
+    pub fn save_auto_state(&mut self) {
+        // Make sure a periodic save still in progress does not overwrite this state
+        self.join_auto_save_thread();
+
+        let Some(path) = &self.auto_state_path else { return };
+
+        match state::save_to_path(&mut self.emulator, path) {
+            Ok(_) => log::info!("Saved automatic save state to '{}'", path.display()),
+            Err(err) => {
+                log::error!("Error saving automatic save state to '{}': {err}", path.display());
+            }
+        }
+    }
+
+    fn check_periodic_auto_save(&mut self) {
+        let Some(interval) = self.auto_save_interval else { return };
+        if self.auto_state_path.is_none() {
+            return;
+        }
+
+        if self.last_auto_save.elapsed() < interval {
+            return;
+        }
+
+        // If the previous save has not finished yet (e.g. very slow disk), try again next frame
+        if self.auto_save_thread.as_ref().is_some_and(|thread| !thread.is_finished()) {
+            return;
+        }
+        self.join_auto_save_thread();
+        self.last_auto_save = Instant::now();
+
+        let snapshot = match StateSnapshot::capture(&mut self.emulator) {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                log::error!("Error capturing periodic automatic save state: {err}");
+                return;
+            }
+        };
+
+        let Some(path) = self.auto_state_path.clone() else { return };
+        let spawn_result = thread::Builder::new().name("auto-save-state".into()).spawn(move || {
+            match snapshot.write_to_path(&path) {
+                Ok(()) => {
+                    log::debug!("Saved periodic automatic save state to '{}'", path.display());
+                }
+                Err(err) => log::error!(
+                    "Error saving periodic automatic save state to '{}': {err}",
+                    path.display()
+                ),
+            }
+        });
+        match spawn_result {
+            Ok(handle) => self.auto_save_thread = Some(handle),
+            Err(err) => log::error!("Error spawning automatic save state thread: {err}"),
+        }
+    }
+
+    fn join_auto_save_thread(&mut self) {
+        let Some(handle) = self.auto_save_thread.take() else { return };
+        if handle.join().is_err() {
+            log::error!("Automatic save state thread panicked");
+        }
+    }
+
+    // Returns whether the automatic save state was loaded
+    fn try_resume_auto_state(&mut self) -> bool {
+        let Some(path) = self.auto_state_path.clone() else { return false };
+        if !path.is_file() {
+            return false;
+        }
+
+        if !prompt_resume(self.renderer.window()) {
+            return false;
+        }
+
+        if let Err(err) = state::load_from_path(&mut self.emulator, &self.config, &path) {
+            log::error!("Error loading automatic save state from '{}': {err}", path.display());
+            self.renderer
+                .add_modal("Failed to resume from automatic save state".into(), MODAL_DURATION);
+            return false;
+        }
+
+        self.renderer.add_modal("Resumed from automatic save state".into(), MODAL_DURATION);
+
+        true
+    }
+
+    fn handle_focus_change(&mut self, win_event: WindowEvent, window_id: u32) {
+        let hotkey_state = &mut self.hotkey_state;
+
+        match win_event {
+            WindowEvent::FocusLost
+                if window_id == self.renderer.window_id()
+                    && hotkey_state.pause_when_unfocused
+                    && !hotkey_state.paused =>
+            {
+                hotkey_state.paused = true;
+                hotkey_state.paused_by_focus_loss = true;
+            }
+            // Moving focus from the emulator window to the debugger window should not leave
+            // emulation paused
+            WindowEvent::FocusGained if hotkey_state.paused_by_focus_loss => {
+                hotkey_state.paused = false;
+                hotkey_state.paused_by_focus_loss = false;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_controller_disconnected(&mut self, player: Player) {
+        if !self.hotkey_state.pause_on_controller_disconnect {
+            return;
+        }
+
+        self.hotkey_state.paused = true;
+        self.hotkey_state.paused_by_focus_loss = false;
+
+        let message = match player {
+            Player::One => "P1 controller disconnected; paused",
+            Player::Two => "P2 controller disconnected; paused",
+        };
+        self.renderer.add_modal(message.into(), MODAL_DURATION);
+    }
+
     fn handle_hotkey_event(
         &mut self,
         event: HotkeyEvent,
@@ -680,8 +1482,7 @@ where
             }
             HotkeyEvent::Released(hotkey) => match hotkey {
                 Hotkey::FastForward => {
-                    self.renderer.set_speed_multiplier(1);
-                    self.audio_output.set_speed_multiplier(1);
+                    self.set_speed_multiplier(1);
                 }
                 Hotkey::Rewind => {
                     self.hotkey_state.rewinder.stop_rewinding();
@@ -706,12 +1507,15 @@ where
             CompactHotkey::SaveStateSlot(slot) => self.save_state(slot)?,
             CompactHotkey::LoadState => self.hotkey_load_state(None),
             CompactHotkey::LoadStateSlot(slot) => self.hotkey_load_state(Some(slot)),
+            CompactHotkey::UndoLoadState => self.undo_load_state()?,
+            CompactHotkey::UndoSaveState => self.undo_save_state(),
             CompactHotkey::SoftReset => self.emulator.soft_reset(),
-            CompactHotkey::HardReset => self.emulator.hard_reset(&mut self.save_writer),
+            CompactHotkey::HardReset => self.hard_reset(),
             CompactHotkey::NextSaveStateSlot => self.next_save_state_slot(),
             CompactHotkey::PrevSaveStateSlot => self.prev_save_state_slot(),
             CompactHotkey::Pause => {
                 self.hotkey_state.paused = !self.hotkey_state.paused;
+                self.hotkey_state.paused_by_focus_loss = false;
             }
             CompactHotkey::StepFrame => {
                 self.hotkey_state.should_step_frame = true;
@@ -720,6 +1524,9 @@ where
             CompactHotkey::Rewind => self.hotkey_state.rewinder.start_rewinding(),
             CompactHotkey::ToggleOverclocking => self.toggle_overclocking(),
             CompactHotkey::OpenDebugger => self.open_memory_viewer(),
+            CompactHotkey::SwapPlayerInputs => self.swap_player_inputs(),
+            CompactHotkey::ToggleMute => self.audio_output.toggle_mute(),
+            CompactHotkey::TogglePerformanceHud => self.toggle_performance_hud(),
         }
 
         Ok(None)
@@ -769,12 +1576,16 @@ where
     }
 
     fn enable_fast_forward(&mut self) {
-        let multiplier = self.hotkey_state.fast_forward_multiplier;
-        self.renderer.set_speed_multiplier(multiplier);
-        self.audio_output.set_speed_multiplier(multiplier);
+        self.set_speed_multiplier(self.hotkey_state.fast_forward_multiplier);
     }
 
     fn toggle_overclocking(&mut self) {
+        if self.purist_mode {
+            self.renderer
+                .add_modal("Overclocking is disabled in purist mode".into(), MODAL_DURATION);
+            return;
+        }
+
         self.hotkey_state.overclocking_enabled = !self.hotkey_state.overclocking_enabled;
         self.update_emulator_config(&self.raw_config.clone());
 
@@ -788,7 +1599,9 @@ where
 
     fn update_emulator_config(&mut self, config: &Emulator::Config) {
         self.raw_config = config.clone();
-        self.config = if self.hotkey_state.overclocking_enabled {
+        self.config = if self.purist_mode {
+            self.raw_config.with_enhancements_disabled()
+        } else if self.hotkey_state.overclocking_enabled {
             self.raw_config.clone()
         } else {
             self.raw_config.with_overclocking_disabled()
@@ -798,6 +1611,21 @@ where
     }
 }
 
+// Title for the window and Rich Presence: the title from the game's header if it has one,
+// otherwise the ROM file name
+fn game_title<Emulator: EmulatorTrait, P: AsRef<Path>>(
+    emulator: &mut Emulator,
+    rom_path: P,
+) -> NativeEmulatorResult<String> {
+    let metadata = emulator.game_metadata();
+    log::info!("Game metadata from header: {metadata}");
+
+    match metadata.title {
+        Some(title) => Ok(title),
+        None => file_name_no_ext(rom_path),
+    }
+}
+
 fn file_name_no_ext<P: AsRef<Path>>(path: P) -> NativeEmulatorResult<String> {
     path.as_ref()
         .with_extension("")
@@ -812,7 +1640,8 @@ fn init_sdl(
 ) -> NativeEmulatorResult<(Sdl, VideoSubsystem, AudioSubsystem, JoystickSubsystem, EventPump)> {
     let sdl = sdl2::init().map_err(NativeEmulatorError::SdlInit)?;
     let video = sdl.video().map_err(NativeEmulatorError::SdlVideoInit)?;
-    let audio = sdl.audio().map_err(NativeEmulatorError::SdlAudioInit)?;
+    let audio = init_sdl_audio(&sdl)?;
+    set_background_gamepad_input(config.background_gamepad_input);
     let joystick = sdl.joystick().map_err(NativeEmulatorError::SdlJoystickInit)?;
     let event_pump = sdl.event_pump().map_err(NativeEmulatorError::SdlEventPumpInit)?;
 
@@ -821,6 +1650,26 @@ fn init_sdl(
     Ok((sdl, video, audio, joystick, event_pump))
 }
 
+// SDL tries each of its audio drivers in order. If none of them work (e.g. no sound server is
+// running), fall back to the dummy driver so that emulation can still run without audio
+fn init_sdl_audio(sdl: &Sdl) -> NativeEmulatorResult<AudioSubsystem> {
+    match sdl.audio() {
+        Ok(audio) => Ok(audio),
+        Err(err) => {
+            log::error!("Error initializing SDL2 audio, falling back to dummy audio driver: {err}");
+            sdl2::hint::set("SDL_AUDIODRIVER", "dummy");
+            sdl.audio().map_err(NativeEmulatorError::SdlAudioInit)
+        }
+    }
+}
+
+// SDL only delivers keyboard events to the focused window, but gamepad events can be delivered
+// regardless of focus. SDL watches this hint, so it can be changed while the joystick subsystem is
+// running
+fn set_background_gamepad_input(enabled: bool) {
+    sdl2::hint::set("SDL_JOYSTICK_ALLOW_BACKGROUND_EVENTS", if enabled { "1" } else { "0" });
+}
+
 fn create_window(
     video: &VideoSubsystem,
     title: &str,
@@ -844,11 +1693,26 @@ fn sdl_window_size(window: &Window) -> renderer::WindowSize {
     renderer::WindowSize { width, height }
 }
 
+// SDL only reports whole hertz, and reports 0 if the refresh rate is unknown
+fn display_refresh_rate_millihz(window: &Window) -> Option<u32> {
+    let display_index = window.display_index().ok()?;
+    let display_mode = window.subsystem().current_display_mode(display_index).ok()?;
+    u32::try_from(display_mode.refresh_rate).ok().filter(|&hz| hz != 0).map(|hz| 1000 * hz)
+}
+
 fn handle_window_event(win_event: WindowEvent, renderer: &mut WgpuRenderer<Window>) {
     match win_event {
         WindowEvent::Resized(..) | WindowEvent::SizeChanged(..) | WindowEvent::Maximized => {
             let window_size = sdl_window_size(renderer.window());
             renderer.handle_resize(window_size);
+
+            // Switching to exclusive fullscreen can change the display mode
+            let refresh_rate = display_refresh_rate_millihz(renderer.window());
+            renderer.set_display_refresh_rate(refresh_rate);
+        }
+        WindowEvent::Moved(..) | WindowEvent::DisplayChanged(..) => {
+            let refresh_rate = display_refresh_rate_millihz(renderer.window());
+            renderer.set_display_refresh_rate(refresh_rate);
         }
         _ => {}
     }
@@ -1,10 +1,11 @@
 use crate::config::CommonConfig;
 use jgenesis_common::audio::DynamicResamplingRate;
 use jgenesis_common::frontend::AudioOutput;
+use jgenesis_common::instrument;
 use sdl2::AudioSubsystem;
-use sdl2::audio::{AudioQueue, AudioSpecDesired};
+use sdl2::audio::{AudioQueue, AudioSpecDesired, AudioStatus};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 // Always output in stereo
@@ -13,22 +14,51 @@ const CHANNELS: u8 = 2;
 // Number of samples to buffer before locking and pushing to the audio queue
 const INTERNAL_AUDIO_BUFFER_LEN: usize = 32;
 
+// How often to try reopening the audio device after it disconnects
+const REOPEN_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug, Error)]
 pub enum AudioError {
     #[error("Error opening SDL2 audio queue: {0}")]
     OpenQueue(String),
-    #[error("Error pushing audio samples to SDL2 audio queue: {0}")]
-    QueueAudio(String),
+}
+
+/// The audio output device currently in use, for display in a frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AudioDeviceInfo {
+    /// SDL audio driver, e.g. "pulseaudio" or "wasapi". This is "dummy" if no real audio driver
+    /// could be initialized
+    pub driver: &'static str,
+    /// Output sample rate of the open device
+    pub frequency: i32,
+    /// False if the device has disconnected and could not be reopened yet. Audio samples are
+    /// dropped while disconnected, and the device is periodically reopened on the system default
+    /// output device
+    pub connected: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioDeviceChange {
+    Reopened,
+    Disconnected,
 }
 
 pub struct SdlAudioOutput {
     audio_queue: AudioQueue<f32>,
+    // Set when the audio device has disconnected, e.g. a USB DAC was unplugged; samples are dropped
+    // until the device is successfully reopened
+    device_lost: bool,
+    last_reopen_attempt: Option<Instant>,
+    requested_frequency: u64,
+    requested_queue_size: u16,
     audio_buffer: Vec<f32>,
     audio_sync: bool,
     dynamic_resampling_ratio_enabled: bool,
     dynamic_resampling_rate: DynamicResamplingRate,
     audio_buffer_size: u32,
     audio_gain_multiplier: f64,
+    muted_by_config: bool,
+    muted_by_hotkey: bool,
     sample_count: u64,
     speed_multiplier: u64,
 }
@@ -38,11 +68,19 @@ impl SdlAudioOutput {
         audio: &AudioSubsystem,
         config: &CommonConfig,
     ) -> Result<Self, AudioError> {
-        let audio_queue = open_audio_queue(audio, config)?;
+        let audio_queue = open_audio_queue(
+            audio,
+            config.audio_output_frequency,
+            config.audio_hardware_queue_size,
+        )?;
         let output_frequency = audio_queue.spec().freq;
 
         Ok(Self {
             audio_queue,
+            device_lost: false,
+            last_reopen_attempt: None,
+            requested_frequency: config.audio_output_frequency,
+            requested_queue_size: config.audio_hardware_queue_size,
             audio_buffer: Vec::with_capacity(INTERNAL_AUDIO_BUFFER_LEN),
             audio_sync: config.audio_sync,
             dynamic_resampling_ratio_enabled: config.audio_dynamic_resampling_ratio,
@@ -51,7 +89,9 @@ impl SdlAudioOutput {
                 config.audio_buffer_size,
             ),
             audio_buffer_size: config.audio_buffer_size,
-            audio_gain_multiplier: decibels_to_multiplier(config.audio_gain_db),
+            audio_gain_multiplier: output_multiplier(config),
+            muted_by_config: config.audio_muted,
+            muted_by_hotkey: false,
             sample_count: 0,
             speed_multiplier: 1,
         })
@@ -61,7 +101,10 @@ impl SdlAudioOutput {
         self.audio_sync = config.audio_sync;
         self.dynamic_resampling_ratio_enabled = config.audio_dynamic_resampling_ratio;
         self.audio_buffer_size = config.audio_buffer_size;
-        self.audio_gain_multiplier = decibels_to_multiplier(config.audio_gain_db);
+        self.audio_gain_multiplier = output_multiplier(config);
+        self.muted_by_config = config.audio_muted;
+        self.requested_frequency = config.audio_output_frequency;
+        self.requested_queue_size = config.audio_hardware_queue_size;
 
         let spec = self.audio_queue.spec();
         if config.audio_output_frequency != spec.freq as u64
@@ -72,10 +115,7 @@ impl SdlAudioOutput {
                 config.audio_output_frequency,
                 config.audio_hardware_queue_size
             );
-            self.audio_queue.pause();
-
-            let new_audio_queue = open_audio_queue(self.audio_queue.subsystem(), config)?;
-            self.audio_queue = new_audio_queue;
+            self.reopen_device()?;
         } else if self.audio_queue_len_samples() >= 4 * self.audio_buffer_size {
             // Truncate audio queue on config reloads if it is way oversized
             self.audio_queue.clear();
@@ -87,6 +127,89 @@ impl SdlAudioOutput {
         Ok(())
     }
 
+    /// Close the current audio device and open the system default output device.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device cannot be opened. The output will keep dropping samples and
+    /// periodically retrying in that case.
+    pub fn reopen_device(&mut self) -> Result<(), AudioError> {
+        self.audio_queue.pause();
+        self.last_reopen_attempt = Some(Instant::now());
+
+        match open_audio_queue(
+            self.audio_queue.subsystem(),
+            self.requested_frequency,
+            self.requested_queue_size,
+        ) {
+            Ok(audio_queue) => {
+                self.audio_queue = audio_queue;
+                self.device_lost = false;
+                self.audio_buffer.clear();
+                self.dynamic_resampling_rate
+                    .update_config(self.audio_queue.spec().freq as u32, self.audio_buffer_size);
+                Ok(())
+            }
+            Err(err) => {
+                self.device_lost = true;
+                Err(err)
+            }
+        }
+    }
+
+    /// Check whether the audio device has disconnected, and if it has, periodically try to reopen
+    /// it on the default device. Should be called once per frame.
+    pub fn check_device(&mut self) -> Option<AudioDeviceChange> {
+        if !self.device_lost {
+            if self.audio_queue.status() != AudioStatus::Stopped {
+                return None;
+            }
+
+            // SDL reports disconnected devices as stopped; the queue is never explicitly stopped
+            log::warn!("Audio device disconnected; reopening on default device");
+            self.device_lost = true;
+            self.last_reopen_attempt = None;
+            return Some(AudioDeviceChange::Disconnected);
+        }
+
+        if self.last_reopen_attempt.is_some_and(|time| time.elapsed() < REOPEN_INTERVAL) {
+            return None;
+        }
+
+        match self.reopen_device() {
+            Ok(()) => {
+                log::info!(
+                    "Reopened audio device with driver {} at {} Hz",
+                    self.audio_queue.subsystem().current_audio_driver(),
+                    self.audio_queue.spec().freq
+                );
+                Some(AudioDeviceChange::Reopened)
+            }
+            Err(err) => {
+                log::debug!("Failed to reopen audio device, will retry: {err}");
+                None
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn device_info(&self) -> AudioDeviceInfo {
+        AudioDeviceInfo {
+            driver: self.audio_queue.subsystem().current_audio_driver(),
+            frequency: self.audio_queue.spec().freq,
+            connected: !self.device_lost,
+        }
+    }
+
+    pub fn toggle_mute(&mut self) {
+        self.muted_by_hotkey = !self.muted_by_hotkey;
+        log::info!("Audio muted: {}", self.is_muted());
+    }
+
+    fn is_muted(&self) -> bool {
+        self.muted_by_config || self.muted_by_hotkey
+    }
+
     pub fn set_speed_multiplier(&mut self, speed_multiplier: u64) {
         self.speed_multiplier = speed_multiplier;
     }
@@ -108,29 +231,40 @@ impl SdlAudioOutput {
         }
     }
 
+    /// Current audio queue length as a percentage of the configured audio buffer size.
+    #[must_use]
+    pub fn buffer_percent(&self) -> f64 {
+        100.0 * f64::from(self.audio_queue_len_samples()) / f64::from(self.audio_buffer_size)
+    }
+
     fn audio_queue_len_samples(&self) -> u32 {
         // 2 channels, 4 bytes per sample
         self.audio_queue.size() / 2 / 4
     }
 }
 
+// Always opens the system default output device so that the output follows the OS when the
+// previously used device disappears
 fn open_audio_queue(
     audio: &AudioSubsystem,
-    config: &CommonConfig,
+    frequency: u64,
+    hardware_queue_size: u16,
 ) -> Result<AudioQueue<f32>, AudioError> {
     let audio_queue = audio
-        .open_queue(None, &AudioSpecDesired {
-            freq: Some(config.audio_output_frequency as i32),
-            channels: Some(CHANNELS),
-            samples: Some(config.audio_hardware_queue_size),
-        })
+        .open_queue(
+            None,
+            &AudioSpecDesired {
+                freq: Some(frequency as i32),
+                channels: Some(CHANNELS),
+                samples: Some(hardware_queue_size),
+            },
+        )
         .map_err(AudioError::OpenQueue)?;
     audio_queue.resume();
 
-    if config.audio_output_frequency as i32 != audio_queue.spec().freq {
+    if frequency as i32 != audio_queue.spec().freq {
         log::error!(
-            "Audio device does not support requested frequency {}; set to {} instead",
-            config.audio_output_frequency,
+            "Audio device does not support requested frequency {frequency}; set to {} instead",
             audio_queue.spec().freq
         );
     }
@@ -142,20 +276,31 @@ fn decibels_to_multiplier(decibels: f64) -> f64 {
     10.0_f64.powf(decibels / 20.0)
 }
 
+// Gain and the per-system volume offset are both in decibels and stack, while the master volume is
+// a linear percentage applied on top
+fn output_multiplier(config: &CommonConfig) -> f64 {
+    let gain_db = config.audio_gain_db + config.audio_volume_offset_db;
+    decibels_to_multiplier(gain_db) * f64::from(config.audio_volume_percent) / 100.0
+}
+
 impl AudioOutput for SdlAudioOutput {
     type Err = AudioError;
 
     #[inline]
     fn push_sample(&mut self, sample_l: f64, sample_r: f64) -> Result<(), Self::Err> {
         self.sample_count += 1;
-        if self.sample_count % self.speed_multiplier != 0 {
+        if self.sample_count % self.speed_multiplier != 0 || self.device_lost {
             return Ok(());
         }
 
-        self.audio_buffer.push((sample_l * self.audio_gain_multiplier) as f32);
-        self.audio_buffer.push((sample_r * self.audio_gain_multiplier) as f32);
+        // Muting still pushes silent samples so that audio sync continues to work
+        let multiplier = if self.is_muted() { 0.0 } else { self.audio_gain_multiplier };
+        self.audio_buffer.push((sample_l * multiplier) as f32);
+        self.audio_buffer.push((sample_r * multiplier) as f32);
 
         if self.audio_buffer.len() >= INTERNAL_AUDIO_BUFFER_LEN {
+            instrument!("audio_queue");
+
             let audio_buffer_threshold = if self.dynamic_resampling_ratio_enabled {
                 // If dynamic resampling ratio is enabled, let the audio buffer grow to double size
                 // before dropping samples because the audio buffer size is also the target length
@@ -166,8 +311,14 @@ impl AudioOutput for SdlAudioOutput {
             };
 
             if self.audio_sync {
-                // Block until audio queue is not full
+                // Block until audio queue is not full. A disconnected device will never drain the
+                // queue, so stop waiting if the device disappears
                 while self.audio_queue_len_samples() > audio_buffer_threshold {
+                    if self.audio_queue.status() == AudioStatus::Stopped {
+                        self.audio_buffer.clear();
+                        return Ok(());
+                    }
+
                     thread::sleep(Duration::from_micros(250));
                 }
             } else if self.audio_queue_len_samples() > audio_buffer_threshold {
@@ -181,7 +332,11 @@ impl AudioOutput for SdlAudioOutput {
                 log::debug!("Potential audio buffer underflow");
             }
 
-            self.audio_queue.queue_audio(&self.audio_buffer).map_err(AudioError::QueueAudio)?;
+            if let Err(err) = self.audio_queue.queue_audio(&self.audio_buffer) {
+                // Treat this the same as a disconnect rather than terminating emulation
+                log::error!("Error pushing audio samples to SDL2 audio queue: {err}");
+                self.device_lost = true;
+            }
             self.audio_buffer.clear();
         }
 
@@ -0,0 +1,416 @@
+//! Headless modes that run an emulator core as fast as possible without opening a window or an
+//! audio device. Benchmark mode reports how long emulation took, and hash mode reports hashes of
+//! the video and audio output for comparing against known-good baselines in regression tests.
+
+use crate::config::{
+    GameBoyConfig, GenesisConfig, NesConfig, RomReadResult, Sega32XConfig, SegaCdConfig,
+    SmsGgConfig, SnesConfig, SystemConfig,
+};
+use crate::mainloop::NativeEmulatorError;
+use crate::mainloop::smsgg::hardware_for_ext;
+use crate::{NativeEmulatorResult, extensions};
+use gb_core::api::GameBoyEmulator;
+use genesis_core::GenesisEmulator;
+use jgenesis_common::frontend::{
+    Color, EmulatorTrait, FrameSize, NullAudioOutput, NullSaveWriter, PixelAspectRatio, Renderer,
+    TickEffect,
+};
+use jgenesis_common::hash::AudioHasher;
+use jgenesis_common::instrumentation;
+use jgenesis_common::instrumentation::{Subsystem, SubsystemTimes};
+use nes_core::api::NesEmulator;
+use s32x_core::api::Sega32XEmulator;
+use segacd_core::CdRomFileFormat;
+use segacd_core::api::SegaCdEmulator;
+use smsgg_core::SmsGgEmulator;
+use snes_core::api::SnesEmulator;
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub frames: u64,
+    pub total_time: Duration,
+    pub render_upload_time: Duration,
+    /// Only populated if the emulator was built with the `instrumentation` feature
+    pub subsystem_times: Option<SubsystemTimes>,
+}
+
+impl BenchReport {
+    #[must_use]
+    pub fn fps(&self) -> f64 {
+        self.frames as f64 / self.total_time.as_secs_f64()
+    }
+}
+
+impl Display for BenchReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let total_secs = self.total_time.as_secs_f64();
+        let ms_per_frame = 1000.0 * total_secs / self.frames.max(1) as f64;
+
+        writeln!(
+            f,
+            "Ran {} frames in {total_secs:.3}s ({:.1} fps, {ms_per_frame:.3} ms/frame)",
+            self.frames,
+            self.fps()
+        )?;
+
+        let write_line = |f: &mut Formatter<'_>, label: &str, time: Duration| {
+            let percentage = 100.0 * time.as_secs_f64() / total_secs;
+            let ms_per_frame = 1000.0 * time.as_secs_f64() / self.frames.max(1) as f64;
+            writeln!(
+                f,
+                "  {label:<14}{:>9.3}s {percentage:>6.2}% {ms_per_frame:>8.3} ms/frame",
+                time.as_secs_f64()
+            )
+        };
+
+        match self.subsystem_times {
+            Some(subsystem_times) => {
+                for subsystem in Subsystem::ALL {
+                    write_line(f, subsystem.name(), subsystem_times.get(subsystem))?;
+                }
+                write_line(f, "Render upload", self.render_upload_time)?;
+
+                let other = self
+                    .total_time
+                    .saturating_sub(subsystem_times.total())
+                    .saturating_sub(self.render_upload_time);
+                write_line(f, "Other", other)?;
+            }
+            None => {
+                write_line(f, "Render upload", self.render_upload_time)?;
+                writeln!(
+                    f,
+                    "  (build with the 'instrumentation' feature for a CPU/PPU/APU breakdown)"
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Copies each frame into a buffer, standing in for the texture upload that a real renderer does
+#[derive(Debug, Default)]
+struct BenchRenderer {
+    frame_buffer: Vec<Color>,
+    upload_time: Duration,
+}
+
+impl Renderer for BenchRenderer {
+    type Err = Infallible;
+
+    fn render_frame(
+        &mut self,
+        frame_buffer: &[Color],
+        frame_size: FrameSize,
+        _pixel_aspect_ratio: Option<PixelAspectRatio>,
+    ) -> Result<(), Self::Err> {
+        let start = Instant::now();
+
+        let len = (frame_size.width * frame_size.height) as usize;
+        self.frame_buffer.resize(len, Color::default());
+        self.frame_buffer.copy_from_slice(&frame_buffer[..len]);
+
+        self.upload_time += start.elapsed();
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameHashReport {
+    pub frames: u64,
+    /// Hash of the last rendered frame, or `None` if the core did not render anything
+    pub frame_hash: Option<u64>,
+    /// Hash of every audio sample output while running
+    pub audio_hash: u64,
+}
+
+impl Display for FrameHashReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.frame_hash {
+            Some(frame_hash) => write!(f, "frame_hash={frame_hash:016x}")?,
+            None => write!(f, "frame_hash=none")?,
+        }
+        write!(f, " audio_hash={:016x}", self.audio_hash)
+    }
+}
+
+// Something to do with a headless emulator once it has been created; this lets benchmark and hash
+// modes share the per-system loading code
+trait HeadlessRun {
+    type Output;
+
+    fn run<Emulator: EmulatorTrait>(self, emulator: Emulator)
+    -> NativeEmulatorResult<Self::Output>;
+}
+
+struct Benchmark {
+    frames: u64,
+}
+
+impl HeadlessRun for Benchmark {
+    type Output = BenchReport;
+
+    fn run<Emulator: EmulatorTrait>(
+        self,
+        mut emulator: Emulator,
+    ) -> NativeEmulatorResult<BenchReport> {
+        let mut renderer = BenchRenderer::default();
+        let inputs = Emulator::Inputs::default();
+
+        // Discard anything recorded while loading
+        let _ = instrumentation::take_subsystem_times();
+
+        let start = Instant::now();
+        let mut frames_rendered = 0;
+        while frames_rendered < self.frames {
+            let tick_effect = emulator
+                .tick(&mut renderer, &mut NullAudioOutput, &inputs, &mut NullSaveWriter)
+                .map_err(|err| NativeEmulatorError::Emulator(err.into()))?;
+            if tick_effect == TickEffect::FrameRendered {
+                frames_rendered += 1;
+            }
+        }
+        let total_time = start.elapsed();
+
+        Ok(BenchReport {
+            frames: self.frames,
+            total_time,
+            render_upload_time: renderer.upload_time,
+            subsystem_times: instrumentation::take_subsystem_times(),
+        })
+    }
+}
+
+struct FrameHashRun {
+    frames: u64,
+}
+
+impl HeadlessRun for FrameHashRun {
+    type Output = FrameHashReport;
+
+    fn run<Emulator: EmulatorTrait>(
+        self,
+        mut emulator: Emulator,
+    ) -> NativeEmulatorResult<FrameHashReport> {
+        let mut audio_hasher = AudioHasher::new();
+        let inputs = Emulator::Inputs::default();
+
+        // Frames are only hashed once at the end, so there is no need to copy them while running
+        let mut frames_rendered = 0;
+        while frames_rendered < self.frames {
+            let tick_effect = emulator
+                .tick(&mut NullRenderer, &mut audio_hasher, &inputs, &mut NullSaveWriter)
+                .map_err(|err| NativeEmulatorError::Emulator(err.into()))?;
+            if tick_effect == TickEffect::FrameRendered {
+                frames_rendered += 1;
+            }
+        }
+
+        Ok(FrameHashReport {
+            frames: self.frames,
+            frame_hash: emulator.frame_hash(),
+            audio_hash: audio_hasher.audio_hash(),
+        })
+    }
+}
+
+struct NullRenderer;
+
+impl Renderer for NullRenderer {
+    type Err = Infallible;
+
+    fn render_frame(
+        &mut self,
+        _frame_buffer: &[Color],
+        _frame_size: FrameSize,
+        _pixel_aspect_ratio: Option<PixelAspectRatio>,
+    ) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+fn run_smsgg<H: HeadlessRun>(config: Box<SmsGgConfig>, run: H) -> NativeEmulatorResult<H::Output> {
+    let RomReadResult { rom, extension } = config.common.read_rom_file(&extensions::SMSGG)?;
+    let hardware = config.forced_hardware.unwrap_or_else(|| hardware_for_ext(&extension));
+
+    let emulator =
+        SmsGgEmulator::create(rom, hardware, config.emulator_config, &mut NullSaveWriter);
+    run.run(emulator)
+}
+
+fn run_genesis<H: HeadlessRun>(
+    config: Box<GenesisConfig>,
+    run: H,
+) -> NativeEmulatorResult<H::Output> {
+    let RomReadResult { rom, .. } = config.common.read_rom_file(extensions::GENESIS)?;
+
+    let emulator = GenesisEmulator::create(rom, config.emulator_config, &mut NullSaveWriter);
+    run.run(emulator)
+}
+
+fn run_sega_cd<H: HeadlessRun>(
+    config: Box<SegaCdConfig>,
+    run: H,
+) -> NativeEmulatorResult<H::Output> {
+    let rom_path = Path::new(&config.genesis.common.rom_file_path);
+    let rom_format = CdRomFileFormat::from_file_path(rom_path).unwrap_or(CdRomFileFormat::CueBin);
+
+    let bios_file_path = config.bios_file_path.as_ref().ok_or(NativeEmulatorError::SegaCdNoBios)?;
+    let bios = fs::read(bios_file_path).map_err(|source| NativeEmulatorError::SegaCdBiosRead {
+        path: bios_file_path.clone(),
+        source,
+    })?;
+
+    let emulator = SegaCdEmulator::create(
+        bios,
+        rom_path,
+        rom_format,
+        config.run_without_disc,
+        config.emulator_config,
+        &mut NullSaveWriter,
+    )?;
+    run.run(emulator)
+}
+
+fn run_32x<H: HeadlessRun>(config: Box<Sega32XConfig>, run: H) -> NativeEmulatorResult<H::Output> {
+    let RomReadResult { rom, .. } = config.genesis.common.read_rom_file(extensions::SEGA_32X)?;
+
+    let emulator = Sega32XEmulator::create(
+        rom.into_boxed_slice(),
+        config.emulator_config,
+        &mut NullSaveWriter,
+    );
+    run.run(emulator)
+}
+
+fn run_nes<H: HeadlessRun>(config: Box<NesConfig>, run: H) -> NativeEmulatorResult<H::Output> {
+    let RomReadResult { rom, .. } = config.common.read_rom_file(extensions::NES)?;
+
+    let emulator = NesEmulator::create(rom, config.emulator_config, &mut NullSaveWriter)?;
+    run.run(emulator)
+}
+
+fn run_snes<H: HeadlessRun>(config: Box<SnesConfig>, run: H) -> NativeEmulatorResult<H::Output> {
+    let RomReadResult { rom, .. } = config.common.read_rom_file(extensions::SNES)?;
+
+    let emulator = SnesEmulator::create(
+        rom,
+        config.emulator_config,
+        config.to_coprocessor_roms(),
+        &mut NullSaveWriter,
+    )?;
+    run.run(emulator)
+}
+
+fn run_gb<H: HeadlessRun>(config: Box<GameBoyConfig>, run: H) -> NativeEmulatorResult<H::Output> {
+    let RomReadResult { rom, .. } = config.common.read_rom_file(&extensions::GB_GBC)?;
+
+    let emulator = GameBoyEmulator::create(rom, config.emulator_config, &mut NullSaveWriter)?;
+    run.run(emulator)
+}
+
+fn run_headless<H: HeadlessRun>(config: SystemConfig, run: H) -> NativeEmulatorResult<H::Output> {
+    match config {
+        SystemConfig::SmsGg(config) => run_smsgg(config, run),
+        SystemConfig::Genesis(config) => run_genesis(config, run),
+        SystemConfig::SegaCd(config) => run_sega_cd(config, run),
+        SystemConfig::Sega32X(config) => run_32x(config, run),
+        SystemConfig::Nes(config) => run_nes(config, run),
+        SystemConfig::Snes(config) => run_snes(config, run),
+        SystemConfig::GameBoy(config) => run_gb(config, run),
+    }
+}
+
+/// Run the SMS/GG core headless for the given number of frames.
+///
+/// # Errors
+///
+/// This function will return an error if unable to load the ROM or if the core returns an error.
+pub fn bench_smsgg(config: Box<SmsGgConfig>, frames: u64) -> NativeEmulatorResult<BenchReport> {
+    run_smsgg(config, Benchmark { frames })
+}
+
+/// Run the Genesis core headless for the given number of frames.
+///
+/// # Errors
+///
+/// This function will return an error if unable to load the ROM or if the core returns an error.
+pub fn bench_genesis(config: Box<GenesisConfig>, frames: u64) -> NativeEmulatorResult<BenchReport> {
+    run_genesis(config, Benchmark { frames })
+}
+
+/// Run the Sega CD core headless for the given number of frames.
+///
+/// # Errors
+///
+/// This function will return an error if unable to load the BIOS or the disc, or if the core
+/// returns an error.
+pub fn bench_sega_cd(config: Box<SegaCdConfig>, frames: u64) -> NativeEmulatorResult<BenchReport> {
+    run_sega_cd(config, Benchmark { frames })
+}
+
+/// Run the 32X core headless for the given number of frames.
+///
+/// # Errors
+///
+/// This function will return an error if unable to load the ROM or if the core returns an error.
+pub fn bench_32x(config: Box<Sega32XConfig>, frames: u64) -> NativeEmulatorResult<BenchReport> {
+    run_32x(config, Benchmark { frames })
+}
+
+/// Run the NES core headless for the given number of frames.
+///
+/// # Errors
+///
+/// This function will return an error if unable to load the ROM or if the core returns an error.
+pub fn bench_nes(config: Box<NesConfig>, frames: u64) -> NativeEmulatorResult<BenchReport> {
+    run_nes(config, Benchmark { frames })
+}
+
+/// Run the SNES core headless for the given number of frames.
+///
+/// # Errors
+///
+/// This function will return an error if unable to load the ROM or if the core returns an error.
+pub fn bench_snes(config: Box<SnesConfig>, frames: u64) -> NativeEmulatorResult<BenchReport> {
+    run_snes(config, Benchmark { frames })
+}
+
+/// Run the Game Boy core headless for the given number of frames.
+///
+/// # Errors
+///
+/// This function will return an error if unable to load the ROM or if the core returns an error.
+pub fn bench_gb(config: Box<GameBoyConfig>, frames: u64) -> NativeEmulatorResult<BenchReport> {
+    run_gb(config, Benchmark { frames })
+}
+
+/// Run whichever core the given config is for headless for the given number of frames.
+///
+/// # Errors
+///
+/// This function will propagate any error encountered while loading or running the emulator.
+pub fn bench(config: SystemConfig, frames: u64) -> NativeEmulatorResult<BenchReport> {
+    run_headless(config, Benchmark { frames })
+}
+
+/// Run whichever core the given config is for headless for the given number of frames with no
+/// inputs, and hash the final frame and all audio output.
+///
+/// Runs are deterministic as long as the core does not read the host clock (e.g. for a cartridge
+/// real-time clock), so the hashes can be compared against known-good values to detect
+/// regressions.
+///
+/// # Errors
+///
+/// This function will propagate any error encountered while loading or running the emulator.
+pub fn hash_frames(config: SystemConfig, frames: u64) -> NativeEmulatorResult<FrameHashReport> {
+    run_headless(config, FrameHashRun { frames })
+}
@@ -1,16 +1,22 @@
+mod eventlog;
+mod export;
 pub mod gb;
 pub mod genesis;
+mod memmap;
+mod memory;
 pub mod nes;
+mod palette;
 pub mod smsgg;
 pub mod snes;
 
 use sdl2::event::{Event, WindowEvent};
 
-use egui::{Button, Response, Ui, Widget, WidgetText};
+use egui::{Button, Grid, Pos2, Response, Ui, Widget, WidgetText};
 use egui_wgpu::ScreenDescriptor;
 use sdl2::VideoSubsystem;
 use sdl2::video::{Window, WindowBuildError};
 use std::iter;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use thiserror::Error;
 use wgpu::SurfaceTargetUnsafe;
@@ -35,6 +41,7 @@ pub enum DebuggerError {
 pub struct DebugRenderContext<'a, Emulator> {
     egui_ctx: &'a egui::Context,
     emulator: &'a mut Emulator,
+    rom_path: &'a Path,
     device: &'a wgpu::Device,
     queue: &'a wgpu::Queue,
     renderer: &'a mut egui_wgpu::Renderer,
@@ -51,6 +58,7 @@ pub struct DebuggerWindow<Emulator> {
     egui_renderer: egui_wgpu::Renderer,
     start_time: SystemTime,
     render_fn: Box<DebugRenderFn<Emulator>>,
+    rom_path: PathBuf,
     // SAFETY: The window must be dropped after the surface
     window: Window,
 }
@@ -60,6 +68,7 @@ impl<Emulator> DebuggerWindow<Emulator> {
         video: &VideoSubsystem,
         scale_factor: Option<f32>,
         render_fn: Box<DebugRenderFn<Emulator>>,
+        rom_path: PathBuf,
     ) -> Result<Self, DebuggerError> {
         let mut window_width = 800;
         let mut window_height = 700;
@@ -136,6 +145,7 @@ impl<Emulator> DebuggerWindow<Emulator> {
             egui_renderer,
             start_time,
             render_fn,
+            rom_path,
             window,
         })
     }
@@ -149,10 +159,14 @@ impl<Emulator> DebuggerWindow<Emulator> {
             (self.render_fn)(DebugRenderContext {
                 egui_ctx: ctx,
                 emulator,
+                rom_path: &self.rom_path,
                 device: &self.device,
                 queue: &self.queue,
                 renderer: &mut self.egui_renderer,
             });
+
+            #[cfg(feature = "profiling")]
+            super::profiler::render_window(ctx);
         });
 
         let output = match self.surface.get_current_texture() {
@@ -301,6 +315,71 @@ impl<T: Copy + PartialEq> Widget for SelectableButton<'_, T> {
     }
 }
 
+// Mute/solo state for the audio mixer window. Solo is resolved here rather than in the cores so
+// that the cores only need to support muting individual channels
+struct AudioMixer {
+    channel_names: &'static [&'static str],
+    muted: Vec<bool>,
+    soloed: Vec<bool>,
+}
+
+impl AudioMixer {
+    fn new(channel_names: &'static [&'static str]) -> Self {
+        Self {
+            channel_names,
+            muted: vec![false; channel_names.len()],
+            soloed: vec![false; channel_names.len()],
+        }
+    }
+
+    fn render_window(&mut self, ctx: &egui::Context, default_pos: Pos2) {
+        egui::Window::new("Audio Mixer").default_open(false).default_pos(default_pos).show(
+            ctx,
+            |ui| {
+                Grid::new("audio_mixer").num_columns(3).show(ui, |ui| {
+                    for (i, &name) in self.channel_names.iter().enumerate() {
+                        ui.label(name);
+                        ui.checkbox(&mut self.muted[i], "Mute");
+                        ui.checkbox(&mut self.soloed[i], "Solo");
+                        ui.end_row();
+                    }
+                });
+
+                if ui.button("Reset").clicked() {
+                    self.muted.fill(false);
+                    self.soloed.fill(false);
+                }
+            },
+        );
+    }
+
+    fn is_channel_muted(&self, channel: usize) -> bool {
+        let any_soloed = self.soloed.contains(&true);
+        self.muted[channel] || (any_soloed && !self.soloed[channel])
+    }
+
+    // Cores do not save channel mutes in save states, so loading a state unmutes every channel. If
+    // the core's mute state no longer matches what was last applied, reset the mixer to match the
+    // core instead of showing channels as muted that are not. Must be called before rendering so
+    // that changes made in the window are not mistaken for a desync
+    fn sync(&mut self, is_channel_muted: impl Fn(usize) -> bool) {
+        let in_sync =
+            (0..self.channel_names.len()).all(|i| is_channel_muted(i) == self.is_channel_muted(i));
+        if !in_sync {
+            for (i, muted) in self.muted.iter_mut().enumerate() {
+                *muted = is_channel_muted(i);
+            }
+            self.soloed.fill(false);
+        }
+    }
+
+    fn apply(&self, mut set_channel_muted: impl FnMut(usize, bool)) {
+        for channel in 0..self.channel_names.len() {
+            set_channel_muted(channel, self.is_channel_muted(channel));
+        }
+    }
+}
+
 fn write_textures<Emulator>(
     wgpu_texture: &wgpu::Texture,
     egui_texture: egui::TextureId,
@@ -0,0 +1,98 @@
+//! Viewer for a core's cross-CPU event log, e.g. 68000 / sub CPU handshakes in Sega CD games
+
+use egui::{ComboBox, RichText, ScrollArea, TextEdit, TextStyle, Ui};
+use jgenesis_common::eventlog::{EventLog, LoggedEvent};
+
+#[derive(Debug, Default)]
+pub(crate) struct EventLogViewer {
+    enabled: bool,
+    source_filter: Option<&'static str>,
+    text_filter: String,
+}
+
+impl EventLogViewer {
+    pub(crate) fn render(&mut self, ui: &mut Ui, event_log: &mut EventLog) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.enabled, "Enabled");
+
+            if ui.button("Clear").clicked() {
+                event_log.clear();
+            }
+        });
+
+        let events = event_log.events();
+
+        let mut sources: Vec<&'static str> = events.iter().map(|event| event.source).collect();
+        sources.sort_unstable();
+        sources.dedup();
+
+        ui.horizontal(|ui| {
+            ComboBox::new("debug_event_log_source", "Source")
+                .selected_text(self.source_filter.unwrap_or("All"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.source_filter, None, "All");
+                    for source in sources {
+                        ui.selectable_value(&mut self.source_filter, Some(source), source);
+                    }
+                });
+
+            ui.label("Filter");
+            ui.add(TextEdit::singleline(&mut self.text_filter).desired_width(150.0));
+        });
+
+        ui.add_space(10.0);
+
+        // Deltas are always relative to the previous event in the full log, not the previous
+        // event that passes the filters
+        let text_filter = self.text_filter.trim().to_lowercase();
+        let rows: Vec<_> = events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| self.source_filter.is_none_or(|source| source == event.source))
+            .filter(|(_, event)| {
+                text_filter.is_empty() || event.message.to_lowercase().contains(&text_filter)
+            })
+            .map(|(i, &event)| {
+                let delta = i.checked_sub(1).map(|prev| event.timestamp - events[prev].timestamp);
+                format_row(event, delta)
+            })
+            .collect();
+
+        if rows.is_empty() {
+            ui.label(if self.enabled { "No events logged" } else { "Event logging is disabled" });
+            return;
+        }
+
+        ui.label(
+            RichText::new(format!(
+                "{:>14}  {:>10}  {:<12}  Event",
+                "Cycle (mclk)", "Delta", "Source"
+            ))
+            .monospace()
+            .strong(),
+        );
+
+        let row_height = ui.text_style_height(&TextStyle::Monospace);
+        ScrollArea::vertical().auto_shrink(false).stick_to_bottom(true).show_rows(
+            ui,
+            row_height,
+            rows.len(),
+            |ui, range| {
+                for row in &rows[range] {
+                    ui.label(RichText::new(row).monospace());
+                }
+            },
+        );
+    }
+
+    /// Apply whether logging is enabled to the emulator's event log. This should be called every
+    /// frame because the emulator's log is reset when loading a save state.
+    pub(crate) fn apply(&self, event_log: &mut EventLog) {
+        event_log.set_enabled(self.enabled);
+    }
+}
+
+fn format_row(event: &LoggedEvent, delta: Option<u64>) -> String {
+    let delta = delta.map(|delta| format!("+{delta}")).unwrap_or_default();
+    format!("{:>14}  {delta:>10}  {:<12}  {}", event.timestamp, event.source, event.message)
+}
@@ -0,0 +1,180 @@
+//! Exporting debugger views (tile sheets, sprites, palettes) to files next to the ROM file
+
+use egui::Ui;
+use jgenesis_common::frontend::Color;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub(crate) enum ExportError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("PNG encoding error: {0}")]
+    Png(#[from] png::EncodingError),
+    #[error("file is {actual} bytes, expected {expected} bytes")]
+    SizeMismatch { expected: usize, actual: usize },
+}
+
+/// Result of the most recent export or import, displayed under the export buttons.
+#[derive(Debug, Default)]
+pub(crate) struct ExportStatus(Option<String>);
+
+impl ExportStatus {
+    pub(crate) fn record(&mut self, path: &Path, result: Result<(), ExportError>) {
+        let status = match result {
+            Ok(()) => format!("Wrote {}", path.display()),
+            Err(err) => format!("Error writing {}: {err}", path.display()),
+        };
+        self.set(status);
+    }
+
+    pub(crate) fn record_load(&mut self, path: &Path, result: Result<(), ExportError>) {
+        let status = match result {
+            Ok(()) => format!("Loaded {}", path.display()),
+            Err(err) => format!("Error loading {}: {err}", path.display()),
+        };
+        self.set(status);
+    }
+
+    fn set(&mut self, status: String) {
+        log::info!("{status}");
+        self.0 = Some(status);
+    }
+
+    pub(crate) fn render(&self, ui: &mut Ui) {
+        if let Some(status) = &self.0 {
+            ui.label(status);
+        }
+    }
+}
+
+/// Path for an exported file, e.g. `<rom name>_vram.png` for name "vram" and extension "png".
+pub(crate) fn export_path(rom_path: &Path, name: &str, extension: &str) -> PathBuf {
+    let stem = rom_path.file_stem().unwrap_or_default().to_string_lossy();
+    rom_path.with_file_name(format!("{stem}_{name}.{extension}"))
+}
+
+/// Same as [`export_path`], except that if the file already exists, a number is appended to the
+/// name (e.g. `<rom name>_vram_2.png`) so that exports never overwrite earlier ones.
+pub(crate) fn new_export_path(rom_path: &Path, name: &str, extension: &str) -> PathBuf {
+    let path = export_path(rom_path, name, extension);
+    if !path.exists() {
+        return path;
+    }
+
+    let mut n = 2;
+    loop {
+        let path = export_path(rom_path, &format!("{name}_{n}"), extension);
+        if !path.exists() {
+            return path;
+        }
+        n += 1;
+    }
+}
+
+/// Write an image to a new PNG file next to the ROM file and record the result.
+pub(crate) fn export_png(
+    status: &mut ExportStatus,
+    rom_path: &Path,
+    name: &str,
+    pixels: &[Color],
+    width: u32,
+    height: u32,
+) {
+    let path = new_export_path(rom_path, name, "png");
+    let result = encode_png(pixels, width, height)
+        .map_err(ExportError::from)
+        .and_then(|png| fs::write(&path, png).map_err(ExportError::from));
+    status.record(&path, result);
+}
+
+pub(crate) fn encode_png(
+    pixels: &[Color],
+    width: u32,
+    height: u32,
+) -> Result<Vec<u8>, png::EncodingError> {
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(bytemuck::cast_slice(&pixels[..(width * height) as usize]))?;
+    writer.finish()?;
+
+    Ok(png)
+}
+
+/// Copy a rectangle out of a larger image, e.g. a single sprite out of a sprite sheet.
+pub(crate) fn crop(
+    pixels: &[Color],
+    image_width: u32,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Vec<Color> {
+    (y..y + height)
+        .flat_map(|row| {
+            let start = (row * image_width + x) as usize;
+            pixels[start..start + width as usize].iter().copied()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_path_next_to_rom() {
+        let path = export_path(Path::new("/roms/game.md"), "cram", "pal");
+        assert_eq!(path, Path::new("/roms/game_cram.pal"));
+    }
+
+    #[test]
+    fn new_export_path_does_not_overwrite() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let rom_path = dir.join("game.md");
+
+        let first = new_export_path(&rom_path, "vram", "png");
+        assert_eq!(first, dir.join("game_vram.png"));
+        fs::write(&first, []).unwrap();
+
+        let second = new_export_path(&rom_path, "vram", "png");
+        assert_eq!(second, dir.join("game_vram_2.png"));
+        fs::write(&second, []).unwrap();
+
+        assert_eq!(new_export_path(&rom_path, "vram", "png"), dir.join("game_vram_3.png"));
+    }
+
+    #[test]
+    fn crop_sprite() {
+        let pixels: Vec<_> = (0..16).map(|i| Color::rgb(i, 0, 0)).collect();
+        let cropped = crop(&pixels, 4, 1, 2, 2, 2);
+        assert_eq!(
+            cropped,
+            vec![
+                Color::rgb(9, 0, 0),
+                Color::rgb(10, 0, 0),
+                Color::rgb(13, 0, 0),
+                Color::rgb(14, 0, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn png_round_trip() {
+        let pixels = [Color::rgb(255, 0, 0), Color::TRANSPARENT, Color::rgb(1, 2, 3), Color::BLACK];
+        let png = encode_png(&pixels, 2, 2).unwrap();
+
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut decoded = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut decoded).unwrap();
+
+        assert_eq!((info.width, info.height), (2, 2));
+        assert_eq!(decoded, bytemuck::cast_slice::<Color, u8>(&pixels));
+    }
+}
@@ -1,8 +1,14 @@
 use crate::mainloop::debug;
+use crate::mainloop::debug::export;
+use crate::mainloop::debug::export::ExportStatus;
+use crate::mainloop::debug::memmap::MemoryMapViewer;
+use crate::mainloop::debug::memory::{DumpRegion, MemoryDumper};
+use crate::mainloop::debug::palette::PaletteExporter;
 use crate::mainloop::debug::{DebugRenderContext, DebugRenderFn, SelectableButton};
-use egui::{CentralPanel, Grid, ScrollArea, Vec2};
-use gb_core::api::{BackgroundTileMap, GameBoyEmulator};
+use egui::{CentralPanel, DragValue, Grid, ScrollArea, Ui, Vec2};
+use gb_core::api::{BackgroundTileMap, GameBoyEmulator, MemoryRegion};
 use jgenesis_common::frontend::Color;
+use std::path::Path;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 enum Tab {
@@ -10,19 +16,35 @@ enum Tab {
     Background,
     Sprites,
     Palettes,
+    Memory,
+    MemoryMap,
 }
 
+const MEMORY_REGIONS: &[DumpRegion<MemoryRegion>] = &[
+    DumpRegion { region: MemoryRegion::Wram, label: "WRAM", name: "wram" },
+    DumpRegion { region: MemoryRegion::Vram, label: "VRAM", name: "vram" },
+    DumpRegion { region: MemoryRegion::Hram, label: "HRAM", name: "hram" },
+    DumpRegion { region: MemoryRegion::Sram, label: "Cartridge SRAM", name: "sram" },
+];
+
 #[derive(Debug)]
 struct State {
     tab: Tab,
     background_tile_map: BackgroundTileMap,
     background_buffer: Vec<Color>,
     background_texture: Option<(wgpu::Texture, egui::TextureId)>,
+    background_export_status: ExportStatus,
     sprites_buffer: Vec<Color>,
     sprites_texture: Option<(wgpu::Texture, egui::TextureId)>,
     sprites_double_height_texture: Option<(wgpu::Texture, egui::TextureId)>,
+    sprites_export_sprite: u8,
+    sprites_export_status: ExportStatus,
+    palettes_buffer: [Color; 64],
     bg_palettes_texture: Option<(wgpu::Texture, egui::TextureId)>,
     obj_palettes_texture: Option<(wgpu::Texture, egui::TextureId)>,
+    palettes_exporter: PaletteExporter,
+    memory_dumper: MemoryDumper,
+    memory_map_viewer: MemoryMapViewer,
 }
 
 impl State {
@@ -32,11 +54,18 @@ impl State {
             background_tile_map: BackgroundTileMap::default(),
             background_buffer: vec![Color::default(); 256 * 256],
             background_texture: None,
+            background_export_status: ExportStatus::default(),
             sprites_buffer: vec![Color::default(); 40 * 8 * 16],
             sprites_texture: None,
             sprites_double_height_texture: None,
+            sprites_export_sprite: 0,
+            sprites_export_status: ExportStatus::default(),
+            palettes_buffer: [Color::default(); 64],
             bg_palettes_texture: None,
             obj_palettes_texture: None,
+            palettes_exporter: PaletteExporter::new("palettes"),
+            memory_dumper: MemoryDumper::default(),
+            memory_map_viewer: MemoryMapViewer::default(),
         }
     }
 }
@@ -58,6 +87,8 @@ fn render(mut ctx: DebugRenderContext<'_, GameBoyEmulator>, state: &mut State) {
             ui.add(SelectableButton::new("Background", &mut state.tab, Tab::Background));
             ui.add(SelectableButton::new("Sprites", &mut state.tab, Tab::Sprites));
             ui.add(SelectableButton::new("Palettes", &mut state.tab, Tab::Palettes));
+            ui.add(SelectableButton::new("Memory", &mut state.tab, Tab::Memory));
+            ui.add(SelectableButton::new("Memory Map", &mut state.tab, Tab::MemoryMap));
         });
 
         ui.add_space(15.0);
@@ -77,8 +108,25 @@ fn render(mut ctx: DebugRenderContext<'_, GameBoyEmulator>, state: &mut State) {
                         BackgroundTileMap::One,
                         "$9C00-$9FFF",
                     );
+
+                    if ui.button("Export .png").clicked() {
+                        let name = match state.background_tile_map {
+                            BackgroundTileMap::Zero => "bg_9800",
+                            BackgroundTileMap::One => "bg_9c00",
+                        };
+                        export::export_png(
+                            &mut state.background_export_status,
+                            ctx.rom_path,
+                            name,
+                            &state.background_buffer,
+                            256,
+                            256,
+                        );
+                    }
                 });
 
+                state.background_export_status.render(ui);
+
                 ui.add_space(10.0);
 
                 ScrollArea::vertical().show(ui, |ui| {
@@ -92,6 +140,15 @@ fn render(mut ctx: DebugRenderContext<'_, GameBoyEmulator>, state: &mut State) {
                 });
             }
             Tab::Sprites => {
+                render_sprites_export(
+                    ui,
+                    state,
+                    ctx.rom_path,
+                    ctx.emulator.is_using_double_height_sprites(),
+                );
+
+                ui.add_space(10.0);
+
                 if ctx.emulator.is_using_double_height_sprites() {
                     ScrollArea::vertical().show(ui, |ui| {
                         ui.vertical_centered(|ui| {
@@ -135,11 +192,87 @@ fn render(mut ctx: DebugRenderContext<'_, GameBoyEmulator>, state: &mut State) {
                     ));
                     ui.end_row();
                 });
+
+                ui.add_space(10.0);
+
+                // DMG mode only has 1 BG palette and 2 OBJ palettes
+                let num_colors = if ctx.emulator.is_cgb_mode() { 64 } else { 12 };
+                state.palettes_exporter.render(
+                    ui,
+                    ctx.rom_path,
+                    &state.palettes_buffer[..num_colors],
+                    4,
+                    || ctx.emulator.dump_palettes(),
+                );
+            }
+            Tab::Memory => {
+                state.memory_dumper.render(
+                    ui,
+                    ctx.rom_path,
+                    ctx.emulator,
+                    MEMORY_REGIONS,
+                    GameBoyEmulator::dump_memory,
+                    GameBoyEmulator::restore_memory,
+                );
+            }
+            Tab::MemoryMap => {
+                state.memory_map_viewer.render(ui, &ctx.emulator.memory_map_description());
             }
         }
     });
 }
 
+fn render_sprites_export(
+    ui: &mut Ui,
+    state: &mut State,
+    rom_path: &Path,
+    double_height_sprites: bool,
+) {
+    // Sprites are laid out 8 per row
+    let sprite_height = if double_height_sprites { 16 } else { 8 };
+    let sheet_height = 5 * sprite_height;
+
+    ui.horizontal(|ui| {
+        if ui.button("Export sheet .png").clicked() {
+            export::export_png(
+                &mut state.sprites_export_status,
+                rom_path,
+                "oam",
+                &state.sprites_buffer,
+                8 * 8,
+                sheet_height,
+            );
+        }
+
+        ui.add_space(10.0);
+
+        ui.label("Sprite:");
+        ui.add(DragValue::new(&mut state.sprites_export_sprite).range(0..=39));
+
+        if ui.button("Export sprite .png").clicked() {
+            let sprite = u32::from(state.sprites_export_sprite);
+            let pixels = export::crop(
+                &state.sprites_buffer,
+                8 * 8,
+                (sprite % 8) * 8,
+                (sprite / 8) * sprite_height,
+                8,
+                sprite_height,
+            );
+            export::export_png(
+                &mut state.sprites_export_status,
+                rom_path,
+                &format!("sprite{sprite}"),
+                &pixels,
+                8,
+                sprite_height,
+            );
+        }
+    });
+
+    state.sprites_export_status.render(ui);
+}
+
 fn update_background_texture(ctx: &mut DebugRenderContext<'_, GameBoyEmulator>, state: &mut State) {
     if state.tab == Tab::Background {
         ctx.emulator.copy_background(state.background_tile_map, &mut state.background_buffer);
@@ -208,10 +341,8 @@ fn update_sprite_texture(ctx: &mut DebugRenderContext<'_, GameBoyEmulator>, stat
 }
 
 fn update_palettes_texture(ctx: &mut DebugRenderContext<'_, GameBoyEmulator>, state: &mut State) {
-    let mut palettes_buffer = [Color::default(); 64];
-
     if state.tab == Tab::Palettes {
-        ctx.emulator.copy_palettes(&mut palettes_buffer);
+        ctx.emulator.copy_palettes(&mut state.palettes_buffer);
     }
 
     if state.bg_palettes_texture.is_none() {
@@ -229,11 +360,11 @@ fn update_palettes_texture(ctx: &mut DebugRenderContext<'_, GameBoyEmulator>, st
     let mut bg_palettes_buffer = [Color::TRANSPARENT; 32];
     let mut obj_palettes_buffer = [Color::TRANSPARENT; 32];
     if ctx.emulator.is_cgb_mode() {
-        bg_palettes_buffer.copy_from_slice(&palettes_buffer[..32]);
-        obj_palettes_buffer.copy_from_slice(&palettes_buffer[32..]);
+        bg_palettes_buffer.copy_from_slice(&state.palettes_buffer[..32]);
+        obj_palettes_buffer.copy_from_slice(&state.palettes_buffer[32..]);
     } else {
-        bg_palettes_buffer[..4].copy_from_slice(&palettes_buffer[..4]);
-        obj_palettes_buffer[..8].copy_from_slice(&palettes_buffer[4..12]);
+        bg_palettes_buffer[..4].copy_from_slice(&state.palettes_buffer[..4]);
+        obj_palettes_buffer[..8].copy_from_slice(&state.palettes_buffer[4..12]);
     }
 
     let (bg_wgpu_texture, bg_egui_texture) = state.bg_palettes_texture.as_ref().unwrap();
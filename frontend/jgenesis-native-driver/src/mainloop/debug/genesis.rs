@@ -1,10 +1,20 @@
 use crate::mainloop::debug;
-use crate::mainloop::debug::{DebugRenderContext, DebugRenderFn};
-use egui::{Grid, Pos2, ScrollArea, Vec2, Window};
-use genesis_core::GenesisEmulator;
+use crate::mainloop::debug::eventlog::EventLogViewer;
+use crate::mainloop::debug::export;
+use crate::mainloop::debug::export::ExportStatus;
+use crate::mainloop::debug::memmap::MemoryMapViewer;
+use crate::mainloop::debug::memory::{DumpRegion, MemoryDumper};
+use crate::mainloop::debug::palette::PaletteExporter;
+use crate::mainloop::debug::{AudioMixer, DebugRenderContext, DebugRenderFn};
+use egui::{Color32, ComboBox, Grid, Pos2, Rect, ScrollArea, Sense, Vec2, Window};
+use genesis_core::vdp::{DebugRegister, Plane};
+use genesis_core::{GenesisEmulator, MemoryRegion as GenesisMemoryRegion};
+use jgenesis_common::eventlog::EventLog;
 use jgenesis_common::frontend::Color;
-use s32x_core::api::Sega32XEmulator;
-use segacd_core::api::SegaCdEmulator;
+use jgenesis_common::memmap::MemoryMap;
+use s32x_core::api::{MemoryRegion as S32XMemoryRegion, Sega32XEmulator};
+use segacd_core::api::{MemoryRegion as SegaCdMemoryRegion, SegaCdEmulator};
+use std::path::Path;
 
 struct State {
     vram_palette: u8,
@@ -12,8 +22,31 @@ struct State {
     vram_texture: Option<(wgpu::Texture, egui::TextureId)>,
     cram_buffer: Box<[Color; 64]>,
     vram_buffer: Box<[Color; 2048 * 64]>,
+    cram_exporter: PaletteExporter,
+    s32x_cram_buffer: Box<[Color; 256]>,
+    s32x_cram_exporter: PaletteExporter,
+    vram_export_status: ExportStatus,
+    memory_dumper: MemoryDumper,
+    memory_map_viewer: MemoryMapViewer,
+    event_log_viewer: EventLogViewer,
+    audio_mixer: AudioMixer,
 }
 
+// YM2612 channels followed by PSG channels
+const AUDIO_CHANNELS: &[&str] = &[
+    "FM 1",
+    "FM 2",
+    "FM 3",
+    "FM 4",
+    "FM 5",
+    "FM 6",
+    "PSG Square 1",
+    "PSG Square 2",
+    "PSG Square 3",
+    "PSG Noise",
+];
+const YM2612_CHANNELS: usize = 6;
+
 impl State {
     fn new() -> Self {
         Self {
@@ -22,23 +55,82 @@ impl State {
             vram_texture: None,
             cram_buffer: vec![Color::default(); 64].into_boxed_slice().try_into().unwrap(),
             vram_buffer: vec![Color::default(); 2048 * 64].into_boxed_slice().try_into().unwrap(),
+            cram_exporter: PaletteExporter::new("cram"),
+            s32x_cram_buffer: vec![Color::default(); 256].into_boxed_slice().try_into().unwrap(),
+            s32x_cram_exporter: PaletteExporter::new("32x_cram"),
+            vram_export_status: ExportStatus::default(),
+            memory_dumper: MemoryDumper::default(),
+            memory_map_viewer: MemoryMapViewer::default(),
+            event_log_viewer: EventLogViewer::default(),
+            audio_mixer: AudioMixer::new(AUDIO_CHANNELS),
         }
     }
 }
 
 pub(crate) trait GenesisBase {
+    type MemoryRegion: Copy + 'static;
+
+    const MEMORY_REGIONS: &'static [DumpRegion<Self::MemoryRegion>];
+
     fn copy_cram(&self, out: &mut [Color]);
 
+    fn dump_cram(&self) -> Vec<u8>;
+
+    /// Copy the 32X palette RAM, returning false if there is no 32X.
+    fn copy_32x_cram(&self, _out: &mut [Color]) -> bool {
+        false
+    }
+
+    fn dump_32x_cram(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
     fn copy_vram(&self, out: &mut [Color], palette: u8, row_len: usize);
 
     fn dump_vdp_registers(&self, callback: impl FnMut(&str, &[(&str, &str)]));
+
+    fn vdp_debug_register(&self) -> DebugRegister;
+
+    fn set_vdp_debug_register(&mut self, debug_register: DebugRegister);
+
+    fn is_ym2612_channel_muted(&self, channel: usize) -> bool;
+
+    fn set_ym2612_channel_muted(&mut self, channel: usize, muted: bool);
+
+    fn is_psg_channel_muted(&self, channel: usize) -> bool;
+
+    fn set_psg_channel_muted(&mut self, channel: usize, muted: bool);
+
+    fn dump_memory(&self, region: Self::MemoryRegion) -> Vec<u8>;
+
+    fn restore_memory(&mut self, region: Self::MemoryRegion, bytes: &[u8]);
+
+    fn memory_map_description(&self) -> Vec<MemoryMap>;
+
+    fn event_log_mut(&mut self) -> &mut EventLog;
 }
 
 impl GenesisBase for GenesisEmulator {
+    type MemoryRegion = GenesisMemoryRegion;
+
+    const MEMORY_REGIONS: &'static [DumpRegion<Self::MemoryRegion>] = &[
+        DumpRegion { region: GenesisMemoryRegion::WorkRam, label: "Work RAM", name: "wram" },
+        DumpRegion { region: GenesisMemoryRegion::Vram, label: "VRAM", name: "vram" },
+        DumpRegion {
+            region: GenesisMemoryRegion::CartridgeRam,
+            label: "Cartridge RAM",
+            name: "sram",
+        },
+    ];
+
     fn copy_cram(&self, out: &mut [Color]) {
         GenesisEmulator::copy_cram(self, out);
     }
 
+    fn dump_cram(&self) -> Vec<u8> {
+        GenesisEmulator::dump_cram(self)
+    }
+
     fn copy_vram(&self, out: &mut [Color], palette: u8, row_len: usize) {
         GenesisEmulator::copy_vram(self, out, palette, row_len);
     }
@@ -46,13 +138,67 @@ impl GenesisBase for GenesisEmulator {
     fn dump_vdp_registers(&self, callback: impl FnMut(&str, &[(&str, &str)])) {
         GenesisEmulator::dump_vdp_registers(self, callback);
     }
+
+    fn vdp_debug_register(&self) -> DebugRegister {
+        GenesisEmulator::vdp_debug_register(self)
+    }
+
+    fn set_vdp_debug_register(&mut self, debug_register: DebugRegister) {
+        GenesisEmulator::set_vdp_debug_register(self, debug_register);
+    }
+
+    fn is_ym2612_channel_muted(&self, channel: usize) -> bool {
+        GenesisEmulator::is_ym2612_channel_muted(self, channel)
+    }
+
+    fn set_ym2612_channel_muted(&mut self, channel: usize, muted: bool) {
+        GenesisEmulator::set_ym2612_channel_muted(self, channel, muted);
+    }
+
+    fn is_psg_channel_muted(&self, channel: usize) -> bool {
+        GenesisEmulator::is_psg_channel_muted(self, channel)
+    }
+
+    fn set_psg_channel_muted(&mut self, channel: usize, muted: bool) {
+        GenesisEmulator::set_psg_channel_muted(self, channel, muted);
+    }
+
+    fn dump_memory(&self, region: Self::MemoryRegion) -> Vec<u8> {
+        GenesisEmulator::dump_memory(self, region)
+    }
+
+    fn restore_memory(&mut self, region: Self::MemoryRegion, bytes: &[u8]) {
+        GenesisEmulator::restore_memory(self, region, bytes);
+    }
+
+    fn memory_map_description(&self) -> Vec<MemoryMap> {
+        GenesisEmulator::memory_map_description(self)
+    }
+
+    fn event_log_mut(&mut self) -> &mut EventLog {
+        GenesisEmulator::event_log_mut(self)
+    }
 }
 
 impl GenesisBase for SegaCdEmulator {
+    type MemoryRegion = SegaCdMemoryRegion;
+
+    const MEMORY_REGIONS: &'static [DumpRegion<Self::MemoryRegion>] = &[
+        DumpRegion { region: SegaCdMemoryRegion::WorkRam, label: "Work RAM", name: "wram" },
+        DumpRegion { region: SegaCdMemoryRegion::Vram, label: "VRAM", name: "vram" },
+        DumpRegion { region: SegaCdMemoryRegion::PrgRam, label: "PRG RAM", name: "prgram" },
+        DumpRegion { region: SegaCdMemoryRegion::WordRam, label: "Word RAM", name: "wordram" },
+        DumpRegion { region: SegaCdMemoryRegion::BackupRam, label: "Backup RAM", name: "bram" },
+    ];
+
     fn copy_cram(&self, out: &mut [Color]) {
         SegaCdEmulator::copy_cram(self, out);
     }
 
+    fn dump_cram(&self) -> Vec<u8> {
+        SegaCdEmulator::dump_cram(self)
+    }
+
     fn copy_vram(&self, out: &mut [Color], palette: u8, row_len: usize) {
         SegaCdEmulator::copy_vram(self, out, palette, row_len);
     }
@@ -60,13 +206,75 @@ impl GenesisBase for SegaCdEmulator {
     fn dump_vdp_registers(&self, callback: impl FnMut(&str, &[(&str, &str)])) {
         SegaCdEmulator::dump_vdp_registers(self, callback);
     }
+
+    fn vdp_debug_register(&self) -> DebugRegister {
+        SegaCdEmulator::vdp_debug_register(self)
+    }
+
+    fn set_vdp_debug_register(&mut self, debug_register: DebugRegister) {
+        SegaCdEmulator::set_vdp_debug_register(self, debug_register);
+    }
+
+    fn is_ym2612_channel_muted(&self, channel: usize) -> bool {
+        SegaCdEmulator::is_ym2612_channel_muted(self, channel)
+    }
+
+    fn set_ym2612_channel_muted(&mut self, channel: usize, muted: bool) {
+        SegaCdEmulator::set_ym2612_channel_muted(self, channel, muted);
+    }
+
+    fn is_psg_channel_muted(&self, channel: usize) -> bool {
+        SegaCdEmulator::is_psg_channel_muted(self, channel)
+    }
+
+    fn set_psg_channel_muted(&mut self, channel: usize, muted: bool) {
+        SegaCdEmulator::set_psg_channel_muted(self, channel, muted);
+    }
+
+    fn dump_memory(&self, region: Self::MemoryRegion) -> Vec<u8> {
+        SegaCdEmulator::dump_memory(self, region)
+    }
+
+    fn restore_memory(&mut self, region: Self::MemoryRegion, bytes: &[u8]) {
+        SegaCdEmulator::restore_memory(self, region, bytes);
+    }
+
+    fn memory_map_description(&self) -> Vec<MemoryMap> {
+        SegaCdEmulator::memory_map_description(self)
+    }
+
+    fn event_log_mut(&mut self) -> &mut EventLog {
+        SegaCdEmulator::event_log_mut(self)
+    }
 }
 
 impl GenesisBase for Sega32XEmulator {
+    type MemoryRegion = S32XMemoryRegion;
+
+    const MEMORY_REGIONS: &'static [DumpRegion<Self::MemoryRegion>] = &[
+        DumpRegion { region: S32XMemoryRegion::WorkRam, label: "Work RAM", name: "wram" },
+        DumpRegion { region: S32XMemoryRegion::Vram, label: "VRAM", name: "vram" },
+        DumpRegion { region: S32XMemoryRegion::Sdram, label: "SDRAM", name: "sdram" },
+        DumpRegion { region: S32XMemoryRegion::CartridgeRam, label: "Cartridge RAM", name: "sram" },
+    ];
+
     fn copy_cram(&self, out: &mut [Color]) {
         Sega32XEmulator::copy_cram(self, out);
     }
 
+    fn dump_cram(&self) -> Vec<u8> {
+        Sega32XEmulator::dump_cram(self)
+    }
+
+    fn copy_32x_cram(&self, out: &mut [Color]) -> bool {
+        Sega32XEmulator::copy_32x_cram(self, out);
+        true
+    }
+
+    fn dump_32x_cram(&self) -> Vec<u8> {
+        Sega32XEmulator::dump_32x_cram(self)
+    }
+
     fn copy_vram(&self, out: &mut [Color], palette: u8, row_len: usize) {
         Sega32XEmulator::copy_vram(self, out, palette, row_len);
     }
@@ -74,6 +282,46 @@ impl GenesisBase for Sega32XEmulator {
     fn dump_vdp_registers(&self, callback: impl FnMut(&str, &[(&str, &str)])) {
         Sega32XEmulator::dump_vdp_registers(self, callback);
     }
+
+    fn vdp_debug_register(&self) -> DebugRegister {
+        Sega32XEmulator::vdp_debug_register(self)
+    }
+
+    fn set_vdp_debug_register(&mut self, debug_register: DebugRegister) {
+        Sega32XEmulator::set_vdp_debug_register(self, debug_register);
+    }
+
+    fn is_ym2612_channel_muted(&self, channel: usize) -> bool {
+        Sega32XEmulator::is_ym2612_channel_muted(self, channel)
+    }
+
+    fn set_ym2612_channel_muted(&mut self, channel: usize, muted: bool) {
+        Sega32XEmulator::set_ym2612_channel_muted(self, channel, muted);
+    }
+
+    fn is_psg_channel_muted(&self, channel: usize) -> bool {
+        Sega32XEmulator::is_psg_channel_muted(self, channel)
+    }
+
+    fn set_psg_channel_muted(&mut self, channel: usize, muted: bool) {
+        Sega32XEmulator::set_psg_channel_muted(self, channel, muted);
+    }
+
+    fn dump_memory(&self, region: Self::MemoryRegion) -> Vec<u8> {
+        Sega32XEmulator::dump_memory(self, region)
+    }
+
+    fn restore_memory(&mut self, region: Self::MemoryRegion, bytes: &[u8]) {
+        Sega32XEmulator::restore_memory(self, region, bytes);
+    }
+
+    fn memory_map_description(&self) -> Vec<MemoryMap> {
+        Sega32XEmulator::memory_map_description(self)
+    }
+
+    fn event_log_mut(&mut self) -> &mut EventLog {
+        Sega32XEmulator::event_log_mut(self)
+    }
 }
 
 pub(crate) fn render_fn<Emulator: GenesisBase>() -> Box<DebugRenderFn<Emulator>> {
@@ -87,16 +335,62 @@ fn render<Emulator: GenesisBase>(mut ctx: DebugRenderContext<'_, Emulator>, stat
 
     let screen_width = debug::screen_width(ctx.egui_ctx);
 
-    render_cram_window(ctx.egui_ctx, state.cram_texture.as_ref().unwrap().1, screen_width);
+    render_cram_window(ctx.egui_ctx, state, screen_width, ctx.rom_path, ctx.emulator);
 
-    render_vram_window(
+    if ctx.emulator.copy_32x_cram(state.s32x_cram_buffer.as_mut()) {
+        render_32x_cram_window(ctx.egui_ctx, state, ctx.rom_path, ctx.emulator);
+    }
+
+    render_vram_window(ctx.egui_ctx, state, screen_width, ctx.rom_path);
+
+    render_vdp_registers_window(ctx.egui_ctx, ctx.emulator);
+
+    render_layer_forcing_window(ctx.egui_ctx, ctx.emulator);
+
+    Window::new("Memory Dump").default_open(false).default_pos(Pos2::new(5.0, 95.0)).show(
         ctx.egui_ctx,
-        &mut state.vram_palette,
-        state.vram_texture.as_ref().unwrap().1,
-        screen_width,
+        |ui| {
+            state.memory_dumper.render(
+                ui,
+                ctx.rom_path,
+                ctx.emulator,
+                Emulator::MEMORY_REGIONS,
+                Emulator::dump_memory,
+                Emulator::restore_memory,
+            );
+        },
     );
 
-    render_vdp_registers_window(ctx.egui_ctx, ctx.emulator);
+    Window::new("Memory Map").default_open(false).default_pos(Pos2::new(5.0, 125.0)).show(
+        ctx.egui_ctx,
+        |ui| {
+            state.memory_map_viewer.render(ui, &ctx.emulator.memory_map_description());
+        },
+    );
+
+    Window::new("Event Log").default_open(false).default_pos(Pos2::new(5.0, 155.0)).show(
+        ctx.egui_ctx,
+        |ui| {
+            state.event_log_viewer.render(ui, ctx.emulator.event_log_mut());
+        },
+    );
+    state.event_log_viewer.apply(ctx.emulator.event_log_mut());
+
+    state.audio_mixer.sync(|channel| {
+        if channel < YM2612_CHANNELS {
+            ctx.emulator.is_ym2612_channel_muted(channel)
+        } else {
+            ctx.emulator.is_psg_channel_muted(channel - YM2612_CHANNELS)
+        }
+    });
+    state.audio_mixer.render_window(ctx.egui_ctx, Pos2::new(5.0, 65.0));
+    state.audio_mixer.apply(|channel, muted| {
+        if channel < YM2612_CHANNELS {
+            ctx.emulator.set_ym2612_channel_muted(channel, muted);
+        } else {
+            ctx.emulator.set_psg_channel_muted(channel - YM2612_CHANNELS, muted);
+        }
+    });
 
     // CentralPanel::default().show(ctx.egui_ctx, |ui| {
     //     ui.horizontal(|ui| {
@@ -131,7 +425,13 @@ fn render<Emulator: GenesisBase>(mut ctx: DebugRenderContext<'_, Emulator>, stat
     // });
 }
 
-fn render_cram_window(ctx: &egui::Context, cram_texture: egui::TextureId, screen_width: f32) {
+fn render_cram_window(
+    ctx: &egui::Context,
+    state: &mut State,
+    screen_width: f32,
+    rom_path: &Path,
+    emulator: &impl GenesisBase,
+) {
     Window::new("CRAM").default_width(screen_width * 0.95).show(ctx, |ui| {
         let mut height = ui.available_width() * 0.25;
         if height > ui.available_height() {
@@ -139,31 +439,70 @@ fn render_cram_window(ctx: &egui::Context, cram_texture: egui::TextureId, screen
         }
         let width = height * 4.0;
 
+        let cram_texture = state.cram_texture.as_ref().unwrap().1;
         ui.image((cram_texture, Vec2::new(width, height)));
+
+        state
+            .cram_exporter
+            .render(ui, rom_path, state.cram_buffer.as_ref(), 16, || emulator.dump_cram());
     });
 }
 
-fn render_vram_window(
+fn render_32x_cram_window(
     ctx: &egui::Context,
-    palette: &mut u8,
-    vram_texture: egui::TextureId,
-    screen_width: f32,
+    state: &mut State,
+    rom_path: &Path,
+    emulator: &impl GenesisBase,
 ) {
+    const SWATCH_SIZE: f32 = 12.0;
+
+    Window::new("32X CRAM").default_open(false).show(ctx, |ui| {
+        let (rect, _) = ui.allocate_exact_size(Vec2::splat(16.0 * SWATCH_SIZE), Sense::hover());
+        for (i, color) in state.s32x_cram_buffer.iter().enumerate() {
+            let min = rect.min + SWATCH_SIZE * Vec2::new((i % 16) as f32, (i / 16) as f32);
+            ui.painter().rect_filled(
+                Rect::from_min_size(min, Vec2::splat(SWATCH_SIZE)),
+                0.0,
+                Color32::from_rgb(color.r, color.g, color.b),
+            );
+        }
+
+        state
+            .s32x_cram_exporter
+            .render(ui, rom_path, state.s32x_cram_buffer.as_ref(), 16, || emulator.dump_32x_cram());
+    });
+}
+
+fn render_vram_window(ctx: &egui::Context, state: &mut State, screen_width: f32, rom_path: &Path) {
     Window::new("VRAM").default_width(screen_width * 0.95).show(ctx, |ui| {
         ui.horizontal(|ui| {
             ui.label("Palette");
 
             for i in 0..4 {
-                ui.radio_value(palette, i, format!("{i}"));
+                ui.radio_value(&mut state.vram_palette, i, format!("{i}"));
+            }
+
+            if ui.button("Export .png").clicked() {
+                export::export_png(
+                    &mut state.vram_export_status,
+                    rom_path,
+                    &format!("vram_pal{}", state.vram_palette),
+                    state.vram_buffer.as_ref(),
+                    64 * 8,
+                    32 * 8,
+                );
             }
         });
 
+        state.vram_export_status.render(ui);
+
         let mut height = ui.available_width() * 0.5;
         if height > ui.available_height() {
             height = ui.available_height();
         }
         let width = height * 2.0;
 
+        let vram_texture = state.vram_texture.as_ref().unwrap().1;
         ui.image((vram_texture, Vec2::new(width, height)));
     });
 }
@@ -190,6 +529,35 @@ fn render_vdp_registers_window(ctx: &egui::Context, emulator: &impl GenesisBase)
     );
 }
 
+fn render_layer_forcing_window(ctx: &egui::Context, emulator: &mut impl GenesisBase) {
+    Window::new("Layer Forcing").default_open(false).default_pos(Pos2::new(5.0, 35.0)).show(
+        ctx,
+        |ui| {
+            // Mirrors the VDP debug register ($C0001C), which games can also write to
+            let mut debug_register = emulator.vdp_debug_register();
+            let prev_debug_register = debug_register;
+
+            ui.checkbox(&mut debug_register.display_disabled, "Disable display");
+
+            ComboBox::new("genesis_forced_layer", "Forced layer")
+                .selected_text(debug_register.forced_plane.to_string())
+                .show_ui(ui, |ui| {
+                    for plane in Plane::ALL {
+                        ui.selectable_value(
+                            &mut debug_register.forced_plane,
+                            plane,
+                            plane.to_string(),
+                        );
+                    }
+                });
+
+            if debug_register != prev_debug_register {
+                emulator.set_vdp_debug_register(debug_register);
+            }
+        },
+    );
+}
+
 fn update_cram_texture<Emulator: GenesisBase>(
     ctx: &mut DebugRenderContext<'_, Emulator>,
     state: &mut State,
@@ -0,0 +1,157 @@
+//! Live view of a console's bus memory map, as described by the core's `memory_map_description()`
+
+use egui::{Color32, ComboBox, Grid, RichText, ScrollArea, TextEdit, Ui};
+use jgenesis_common::memmap::{MemoryMap, RegionKind};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+// How long a region stays highlighted after what is mapped into it changes
+const CHANGE_HIGHLIGHT_DURATION: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Default)]
+pub(crate) struct MemoryMapViewer {
+    selected: usize,
+    lookup_text: String,
+    previous_details: HashMap<(usize, u32), Option<String>>,
+    changed_at: HashMap<(usize, u32), Instant>,
+}
+
+impl MemoryMapViewer {
+    pub(crate) fn render(&mut self, ui: &mut Ui, maps: &[MemoryMap]) {
+        let Some(map) = maps.get(self.selected).or_else(|| maps.first()) else {
+            ui.label("No memory map available");
+            return;
+        };
+        self.selected = self.selected.min(maps.len() - 1);
+
+        self.record_changes(maps);
+
+        ui.horizontal(|ui| {
+            ComboBox::new("debug_memory_map_space", "Address space")
+                .selected_text(map.name)
+                .show_ui(ui, |ui| {
+                    for (i, map) in maps.iter().enumerate() {
+                        ui.selectable_value(&mut self.selected, i, map.name);
+                    }
+                });
+        });
+
+        let lookup_address = parse_address(&self.lookup_text);
+        ui.horizontal(|ui| {
+            ui.label("Find address");
+            ui.add(TextEdit::singleline(&mut self.lookup_text).desired_width(100.0));
+
+            match lookup_address {
+                Some(address) => match map.entry_at(address) {
+                    Some(entry) => {
+                        ui.label(format!(
+                            "{} ({})",
+                            entry.name,
+                            map.format_range(entry.start, entry.end)
+                        ));
+                    }
+                    None => {
+                        ui.label("Out of range");
+                    }
+                },
+                None if !self.lookup_text.trim().is_empty() => {
+                    ui.label("Invalid address");
+                }
+                None => {}
+            }
+        });
+
+        ui.add_space(10.0);
+
+        let now = Instant::now();
+        ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+            Grid::new("debug_memory_map").num_columns(4).striped(true).show(ui, |ui| {
+                ui.strong("Range");
+                ui.strong("Kind");
+                ui.strong("Name");
+                ui.strong("Mapped");
+                ui.end_row();
+
+                for entry in &map.entries {
+                    let looked_up = lookup_address
+                        .is_some_and(|address| (entry.start..=entry.end).contains(&address));
+                    let recently_changed = self
+                        .changed_at
+                        .get(&(self.selected, entry.start))
+                        .is_some_and(|&changed_at| {
+                            now.duration_since(changed_at) < CHANGE_HIGHLIGHT_DURATION
+                        });
+
+                    let mut range =
+                        RichText::new(map.format_range(entry.start, entry.end)).monospace();
+                    if looked_up {
+                        range = range.strong().background_color(ui.visuals().selection.bg_fill);
+                    }
+                    ui.label(range);
+
+                    ui.label(RichText::new(entry.kind.label()).color(kind_color(entry.kind)));
+                    ui.label(entry.name);
+
+                    let mut detail = RichText::new(entry.detail.as_deref().unwrap_or(""));
+                    if recently_changed {
+                        detail = detail.color(Color32::YELLOW);
+                    }
+                    ui.label(detail);
+
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn record_changes(&mut self, maps: &[MemoryMap]) {
+        let now = Instant::now();
+        for (i, map) in maps.iter().enumerate() {
+            for entry in &map.entries {
+                let key = (i, entry.start);
+                if self.previous_details.get(&key).is_some_and(|previous| previous != &entry.detail)
+                {
+                    self.changed_at.insert(key, now);
+                }
+                self.previous_details.insert(key, entry.detail.clone());
+            }
+        }
+    }
+}
+
+fn kind_color(kind: RegionKind) -> Color32 {
+    match kind {
+        RegionKind::Rom => Color32::from_rgb(0x6F, 0xA8, 0xDC),
+        RegionKind::Ram => Color32::from_rgb(0x93, 0xC4, 0x7D),
+        RegionKind::Registers => Color32::from_rgb(0xF6, 0xB2, 0x6B),
+        RegionKind::Unmapped => Color32::GRAY,
+    }
+}
+
+fn parse_address(text: &str) -> Option<u32> {
+    let text = text.trim();
+    let hex = text
+        .strip_prefix('$')
+        .or_else(|| text.strip_prefix("0x"))
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+    if hex.is_empty() {
+        return None;
+    }
+
+    u32::from_str_radix(&hex.replace('_', ""), 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_address_formats() {
+        assert_eq!(parse_address("$A000"), Some(0xA000));
+        assert_eq!(parse_address("0xff80"), Some(0xFF80));
+        assert_eq!(parse_address(" 2000_0000 "), Some(0x2000_0000));
+        assert_eq!(parse_address("$"), None);
+        assert_eq!(parse_address("xyz"), None);
+    }
+}
@@ -0,0 +1,109 @@
+//! Dumping memory regions (work RAM, VRAM, cartridge RAM, etc.) to raw binary files next to the ROM
+//! file, and restoring them from those files
+
+use crate::mainloop::debug::export;
+use crate::mainloop::debug::export::{ExportError, ExportStatus};
+use egui::{ComboBox, Ui};
+use std::fs;
+use std::path::Path;
+
+/// A memory region as displayed in the debugger.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DumpRegion<Region> {
+    pub region: Region,
+    pub label: &'static str,
+    // Used in dump file names, e.g. "wram"
+    pub name: &'static str,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct MemoryDumper {
+    selected: usize,
+    // Size of the selected region, so that the region is only copied when it is actually dumped
+    // rather than on every frame; region sizes do not change while a game is running
+    selected_len: Option<usize>,
+    status: ExportStatus,
+}
+
+impl MemoryDumper {
+    /// Render the region selector and dump/restore buttons. Dumps are written to and restored from
+    /// `<rom name>_<region name>.bin` next to the ROM file.
+    pub(crate) fn render<Emulator, Region: Copy>(
+        &mut self,
+        ui: &mut Ui,
+        rom_path: &Path,
+        emulator: &mut Emulator,
+        regions: &[DumpRegion<Region>],
+        dump: fn(&Emulator, Region) -> Vec<u8>,
+        restore: fn(&mut Emulator, Region, &[u8]),
+    ) {
+        let selected = regions[self.selected];
+        let len = *self.selected_len.get_or_insert_with(|| dump(emulator, selected.region).len());
+
+        ui.horizontal(|ui| {
+            ComboBox::new("debug_memory_region", "Region").selected_text(selected.label).show_ui(
+                ui,
+                |ui| {
+                    for (i, region) in regions.iter().enumerate() {
+                        if ui.selectable_value(&mut self.selected, i, region.label).changed() {
+                            self.selected_len = None;
+                        }
+                    }
+                },
+            );
+
+            if len == 0 {
+                ui.label("Not present");
+            } else {
+                ui.label(format!("{len} bytes"));
+            }
+        });
+
+        let path = export::export_path(rom_path, selected.name, "bin");
+
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(len != 0, |ui| {
+                if ui.button("Dump .bin").clicked() {
+                    let result = fs::write(&path, dump(emulator, selected.region))
+                        .map_err(ExportError::from);
+                    self.status.record(&path, result);
+                }
+
+                if ui.button("Restore .bin").clicked() {
+                    let result = read_dump(&path, len)
+                        .map(|bytes| restore(emulator, selected.region, &bytes));
+                    self.status.record_load(&path, result);
+                }
+            });
+        });
+
+        self.status.render(ui);
+    }
+}
+
+fn read_dump(path: &Path, expected_len: usize) -> Result<Vec<u8>, ExportError> {
+    let bytes = fs::read(path)?;
+    if bytes.len() != expected_len {
+        return Err(ExportError::SizeMismatch { expected: expected_len, actual: bytes.len() });
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_dump_checks_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dump.bin");
+        fs::write(&path, [1, 2, 3, 4]).unwrap();
+
+        assert_eq!(read_dump(&path, 4).unwrap(), vec![1, 2, 3, 4]);
+        assert!(matches!(
+            read_dump(&path, 8),
+            Err(ExportError::SizeMismatch { expected: 8, actual: 4 })
+        ));
+    }
+}
@@ -1,8 +1,14 @@
 use crate::mainloop::debug;
+use crate::mainloop::debug::export;
+use crate::mainloop::debug::export::ExportStatus;
+use crate::mainloop::debug::memmap::MemoryMapViewer;
+use crate::mainloop::debug::memory::{DumpRegion, MemoryDumper};
+use crate::mainloop::debug::palette::PaletteExporter;
 use crate::mainloop::debug::{DebugRenderContext, DebugRenderFn, SelectableButton};
-use egui::{CentralPanel, ScrollArea, Vec2};
+use egui::{CentralPanel, DragValue, ScrollArea, Ui, Vec2};
 use jgenesis_common::frontend::Color;
-use nes_core::api::{NesEmulator, PatternTable};
+use nes_core::api::{MemoryRegion, NesEmulator, PatternTable};
+use std::path::Path;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 enum Tab {
@@ -10,19 +16,34 @@ enum Tab {
     Nametables,
     Oam,
     PaletteRam,
+    Memory,
+    MemoryMap,
 }
 
+const MEMORY_REGIONS: &[DumpRegion<MemoryRegion>] = &[
+    DumpRegion { region: MemoryRegion::WorkRam, label: "Work RAM", name: "wram" },
+    DumpRegion { region: MemoryRegion::Vram, label: "Nametable RAM", name: "vram" },
+    DumpRegion { region: MemoryRegion::PrgRam, label: "PRG RAM", name: "prgram" },
+];
+
 #[derive(Debug)]
 struct State {
     tab: Tab,
     nametables_pattern_table: PatternTable,
     nametables_buffer: Vec<Color>,
     nametables_texture: Option<(wgpu::Texture, egui::TextureId)>,
+    nametables_export_status: ExportStatus,
     oam_pattern_table: PatternTable,
     oam_buffer: Vec<Color>,
     oam_texture: Option<(wgpu::Texture, egui::TextureId)>,
     oam_double_height_texture: Option<(wgpu::Texture, egui::TextureId)>,
+    oam_export_sprite: u8,
+    oam_export_status: ExportStatus,
+    palette_ram_buffer: [Color; 32],
     palette_ram_texture: Option<(wgpu::Texture, egui::TextureId)>,
+    palette_ram_exporter: PaletteExporter,
+    memory_dumper: MemoryDumper,
+    memory_map_viewer: MemoryMapViewer,
 }
 
 impl State {
@@ -32,11 +53,18 @@ impl State {
             nametables_pattern_table: PatternTable::Zero,
             nametables_buffer: vec![Color::default(); 4 * 256 * 240],
             nametables_texture: None,
+            nametables_export_status: ExportStatus::default(),
             oam_pattern_table: PatternTable::One,
             oam_buffer: vec![Color::default(); 2 * 64 * 8 * 8],
             oam_texture: None,
             oam_double_height_texture: None,
+            oam_export_sprite: 0,
+            oam_export_status: ExportStatus::default(),
+            palette_ram_buffer: [Color::default(); 32],
             palette_ram_texture: None,
+            palette_ram_exporter: PaletteExporter::new("palette_ram"),
+            memory_dumper: MemoryDumper::default(),
+            memory_map_viewer: MemoryMapViewer::default(),
         }
     }
 }
@@ -58,6 +86,8 @@ fn render(mut ctx: DebugRenderContext<'_, NesEmulator>, state: &mut State) {
             ui.add(SelectableButton::new("Nametables", &mut state.tab, Tab::Nametables));
             ui.add(SelectableButton::new("OAM", &mut state.tab, Tab::Oam));
             ui.add(SelectableButton::new("Palette RAM", &mut state.tab, Tab::PaletteRam));
+            ui.add(SelectableButton::new("Memory", &mut state.tab, Tab::Memory));
+            ui.add(SelectableButton::new("Memory Map", &mut state.tab, Tab::MemoryMap));
         });
 
         ui.add_space(15.0);
@@ -73,8 +103,21 @@ fn render(mut ctx: DebugRenderContext<'_, NesEmulator>, state: &mut State) {
                         "$0000",
                     );
                     ui.radio_value(&mut state.nametables_pattern_table, PatternTable::One, "$1000");
+
+                    if ui.button("Export .png").clicked() {
+                        export::export_png(
+                            &mut state.nametables_export_status,
+                            ctx.rom_path,
+                            "nametables",
+                            &state.nametables_buffer,
+                            2 * 256,
+                            2 * 240,
+                        );
+                    }
                 });
 
+                state.nametables_export_status.render(ui);
+
                 ui.add_space(10.0);
 
                 ScrollArea::vertical().show(ui, |ui| {
@@ -97,6 +140,13 @@ fn render(mut ctx: DebugRenderContext<'_, NesEmulator>, state: &mut State) {
                     });
                 });
 
+                render_oam_export(
+                    ui,
+                    state,
+                    ctx.rom_path,
+                    ctx.emulator.using_double_height_sprites(),
+                );
+
                 ui.add_space(10.0);
 
                 ScrollArea::vertical().show(ui, |ui| {
@@ -122,11 +172,80 @@ fn render(mut ctx: DebugRenderContext<'_, NesEmulator>, state: &mut State) {
                     let egui_texture = state.palette_ram_texture.as_ref().unwrap().1;
                     ui.image((egui_texture, Vec2::new(screen_width * 0.325, screen_width * 0.65)));
                 });
+
+                ui.add_space(10.0);
+
+                state.palette_ram_exporter.render(
+                    ui,
+                    ctx.rom_path,
+                    &state.palette_ram_buffer,
+                    4,
+                    || ctx.emulator.dump_palette_ram(),
+                );
+            }
+            Tab::Memory => {
+                state.memory_dumper.render(
+                    ui,
+                    ctx.rom_path,
+                    ctx.emulator,
+                    MEMORY_REGIONS,
+                    NesEmulator::dump_memory,
+                    NesEmulator::restore_memory,
+                );
+            }
+            Tab::MemoryMap => {
+                state.memory_map_viewer.render(ui, &ctx.emulator.memory_map_description());
             }
         }
     });
 }
 
+fn render_oam_export(ui: &mut Ui, state: &mut State, rom_path: &Path, double_height_sprites: bool) {
+    // Sprites are laid out 8 per row
+    let sprite_height = if double_height_sprites { 16 } else { 8 };
+    let sheet_height = 8 * sprite_height;
+
+    ui.horizontal(|ui| {
+        if ui.button("Export sheet .png").clicked() {
+            export::export_png(
+                &mut state.oam_export_status,
+                rom_path,
+                "oam",
+                &state.oam_buffer,
+                8 * 8,
+                sheet_height,
+            );
+        }
+
+        ui.add_space(10.0);
+
+        ui.label("Sprite:");
+        ui.add(DragValue::new(&mut state.oam_export_sprite).range(0..=63));
+
+        if ui.button("Export sprite .png").clicked() {
+            let sprite = u32::from(state.oam_export_sprite);
+            let pixels = export::crop(
+                &state.oam_buffer,
+                8 * 8,
+                (sprite % 8) * 8,
+                (sprite / 8) * sprite_height,
+                8,
+                sprite_height,
+            );
+            export::export_png(
+                &mut state.oam_export_status,
+                rom_path,
+                &format!("sprite{sprite}"),
+                &pixels,
+                8,
+                sprite_height,
+            );
+        }
+    });
+
+    state.oam_export_status.render(ui);
+}
+
 fn update_nametables_texture(ctx: &mut DebugRenderContext<'_, NesEmulator>, state: &mut State) {
     if state.tab == Tab::Nametables {
         ctx.emulator.copy_nametables(state.nametables_pattern_table, &mut state.nametables_buffer);
@@ -195,8 +314,7 @@ fn update_oam_texture(ctx: &mut DebugRenderContext<'_, NesEmulator>, state: &mut
 }
 
 fn update_palette_ram_texture(ctx: &mut DebugRenderContext<'_, NesEmulator>, state: &mut State) {
-    let mut colors = [Color::default(); 32];
-    ctx.emulator.copy_palette_ram(&mut colors);
+    ctx.emulator.copy_palette_ram(&mut state.palette_ram_buffer);
 
     if state.palette_ram_texture.is_none() {
         let (wgpu_texture, egui_texture) =
@@ -207,5 +325,10 @@ fn update_palette_ram_texture(ctx: &mut DebugRenderContext<'_, NesEmulator>, sta
     let (wgpu_texture, egui_texture) = state.palette_ram_texture.as_ref().unwrap();
     let egui_texture = *egui_texture;
 
-    debug::write_textures(wgpu_texture, egui_texture, bytemuck::cast_slice(&colors), ctx);
+    debug::write_textures(
+        wgpu_texture,
+        egui_texture,
+        bytemuck::cast_slice(&state.palette_ram_buffer),
+        ctx,
+    );
 }
@@ -0,0 +1,136 @@
+//! Palette export from the debugger, either as a raw dump of palette RAM (for romhacking tools) or
+//! as the decoded colors (for sprite rippers and image editors).
+
+use crate::mainloop::debug::export;
+use crate::mainloop::debug::export::{ExportError, ExportStatus};
+use egui::Ui;
+use jgenesis_common::frontend::Color;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+// Width and height of each color in exported PNG swatches
+const SWATCH_SIZE: u32 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    Raw,
+    JascPal,
+    Png,
+}
+
+#[derive(Debug)]
+pub(crate) struct PaletteExporter {
+    // Used in exported file names, e.g. "cram"
+    name: &'static str,
+    status: ExportStatus,
+}
+
+impl PaletteExporter {
+    pub(crate) fn new(name: &'static str) -> Self {
+        Self { name, status: ExportStatus::default() }
+    }
+
+    /// Render the export buttons. Files are written next to the ROM file.
+    ///
+    /// `colors` should be in display order with `colors_per_row` colors in each palette row, and
+    /// `dump_raw` should return the raw contents of palette RAM in the console's native format.
+    pub(crate) fn render(
+        &mut self,
+        ui: &mut Ui,
+        rom_path: &Path,
+        colors: &[Color],
+        colors_per_row: usize,
+        dump_raw: impl FnOnce() -> Vec<u8>,
+    ) {
+        let mut clicked = None;
+        ui.horizontal(|ui| {
+            if ui.button("Dump raw").clicked() {
+                clicked = Some(ExportFormat::Raw);
+            }
+
+            if ui.button("Export .pal").clicked() {
+                clicked = Some(ExportFormat::JascPal);
+            }
+
+            if ui.button("Export .png").clicked() {
+                clicked = Some(ExportFormat::Png);
+            }
+        });
+
+        match clicked {
+            Some(ExportFormat::Raw) => {
+                let path = export::new_export_path(rom_path, self.name, "bin");
+                let result = fs::write(&path, dump_raw()).map_err(ExportError::from);
+                self.status.record(&path, result);
+            }
+            Some(ExportFormat::JascPal) => {
+                let path = export::new_export_path(rom_path, self.name, "pal");
+                let result = fs::write(&path, jasc_pal(colors)).map_err(ExportError::from);
+                self.status.record(&path, result);
+            }
+            Some(ExportFormat::Png) => {
+                let (pixels, width, height) = swatches(colors, colors_per_row);
+                export::export_png(&mut self.status, rom_path, self.name, &pixels, width, height);
+            }
+            None => {}
+        }
+
+        self.status.render(ui);
+    }
+}
+
+// Paint Shop Pro palette format, which most image editors and tile editors can import
+fn jasc_pal(colors: &[Color]) -> String {
+    let mut pal = format!("JASC-PAL\r\n0100\r\n{}\r\n", colors.len());
+    for color in colors {
+        write!(pal, "{} {} {}\r\n", color.r, color.g, color.b).unwrap();
+    }
+    pal
+}
+
+// Image with each color drawn as a square swatch, returned as (pixels, width, height)
+fn swatches(colors: &[Color], colors_per_row: usize) -> (Vec<Color>, u32, u32) {
+    let rows = colors.len().div_ceil(colors_per_row);
+    let width = colors_per_row as u32 * SWATCH_SIZE;
+    let height = rows as u32 * SWATCH_SIZE;
+
+    let mut pixels = vec![Color::BLACK; (width * height) as usize];
+    for (i, &color) in colors.iter().enumerate() {
+        let swatch_x = (i % colors_per_row) as u32 * SWATCH_SIZE;
+        let swatch_y = (i / colors_per_row) as u32 * SWATCH_SIZE;
+
+        for y in swatch_y..swatch_y + SWATCH_SIZE {
+            let row_start = (y * width + swatch_x) as usize;
+            pixels[row_start..row_start + SWATCH_SIZE as usize].fill(color);
+        }
+    }
+
+    (pixels, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jasc_pal_format() {
+        let colors = [Color::rgb(255, 0, 0), Color::rgb(1, 2, 3)];
+        assert_eq!(jasc_pal(&colors), "JASC-PAL\r\n0100\r\n2\r\n255 0 0\r\n1 2 3\r\n");
+    }
+
+    #[test]
+    fn color_swatches() {
+        let colors = [Color::rgb(255, 0, 0), Color::rgb(0, 255, 0), Color::rgb(0, 0, 255)];
+        let (pixels, width, height) = swatches(&colors, 2);
+
+        assert_eq!((width, height), (2 * SWATCH_SIZE, 2 * SWATCH_SIZE));
+
+        let pixel = |x: u32, y: u32| pixels[(y * width + x) as usize];
+        assert_eq!(pixel(0, 0), Color::rgb(255, 0, 0));
+        assert_eq!(pixel(SWATCH_SIZE, SWATCH_SIZE - 1), Color::rgb(0, 255, 0));
+        assert_eq!(pixel(SWATCH_SIZE - 1, SWATCH_SIZE), Color::rgb(0, 0, 255));
+        // Unused swatches are left black
+        assert_eq!(pixel(SWATCH_SIZE, SWATCH_SIZE), Color::BLACK);
+    }
+}
@@ -1,8 +1,13 @@
 use crate::mainloop::debug;
-use crate::mainloop::debug::{DebugRenderContext, DebugRenderFn};
+use crate::mainloop::debug::export;
+use crate::mainloop::debug::export::ExportStatus;
+use crate::mainloop::debug::memmap::MemoryMapViewer;
+use crate::mainloop::debug::memory::{DumpRegion, MemoryDumper};
+use crate::mainloop::debug::palette::PaletteExporter;
+use crate::mainloop::debug::{AudioMixer, DebugRenderContext, DebugRenderFn};
 use egui::{Grid, Pos2, ScrollArea, Vec2, Window};
 use jgenesis_common::frontend::Color;
-use smsgg_core::SmsGgEmulator;
+use smsgg_core::{MemoryRegion, SmsGgEmulator};
 
 struct State {
     vram_palette: u8,
@@ -10,8 +15,21 @@ struct State {
     vram_texture: Option<(wgpu::Texture, egui::TextureId)>,
     cram_buffer: Box<[Color; 32]>,
     vram_buffer: Box<[Color; 512 * 64]>,
+    cram_exporter: PaletteExporter,
+    vram_export_status: ExportStatus,
+    memory_dumper: MemoryDumper,
+    memory_map_viewer: MemoryMapViewer,
+    audio_mixer: AudioMixer,
 }
 
+const MEMORY_REGIONS: &[DumpRegion<MemoryRegion>] = &[
+    DumpRegion { region: MemoryRegion::SystemRam, label: "System RAM", name: "ram" },
+    DumpRegion { region: MemoryRegion::Vram, label: "VRAM", name: "vram" },
+    DumpRegion { region: MemoryRegion::CartridgeRam, label: "Cartridge RAM", name: "sram" },
+];
+
+const AUDIO_CHANNELS: &[&str] = &["PSG Square 1", "PSG Square 2", "PSG Square 3", "PSG Noise"];
+
 impl State {
     fn new() -> Self {
         Self {
@@ -20,6 +38,11 @@ impl State {
             vram_texture: None,
             cram_buffer: vec![Color::default(); 32].into_boxed_slice().try_into().unwrap(),
             vram_buffer: vec![Color::default(); 512 * 64].into_boxed_slice().try_into().unwrap(),
+            cram_exporter: PaletteExporter::new("cram"),
+            vram_export_status: ExportStatus::default(),
+            memory_dumper: MemoryDumper::default(),
+            memory_map_viewer: MemoryMapViewer::default(),
+            audio_mixer: AudioMixer::new(AUDIO_CHANNELS),
         }
     }
 }
@@ -44,6 +67,10 @@ fn render(mut ctx: DebugRenderContext<'_, SmsGgEmulator>, state: &mut State) {
 
         let cram_texture = state.cram_texture.as_ref().unwrap().1;
         ui.image((cram_texture, Vec2::new(width, height)));
+
+        state
+            .cram_exporter
+            .render(ui, ctx.rom_path, state.cram_buffer.as_ref(), 16, || ctx.emulator.dump_cram());
     });
 
     Window::new("VRAM").default_width(screen_width * 0.95).show(ctx.egui_ctx, |ui| {
@@ -52,8 +79,21 @@ fn render(mut ctx: DebugRenderContext<'_, SmsGgEmulator>, state: &mut State) {
 
             ui.radio_value(&mut state.vram_palette, 0, "0");
             ui.radio_value(&mut state.vram_palette, 1, "1");
+
+            if ui.button("Export .png").clicked() {
+                export::export_png(
+                    &mut state.vram_export_status,
+                    ctx.rom_path,
+                    &format!("vram_pal{}", state.vram_palette),
+                    state.vram_buffer.as_ref(),
+                    32 * 8,
+                    16 * 8,
+                );
+            }
         });
 
+        state.vram_export_status.render(ui);
+
         ui.add_space(5.0);
 
         let mut height = ui.available_width() * 0.5;
@@ -85,6 +125,31 @@ fn render(mut ctx: DebugRenderContext<'_, SmsGgEmulator>, state: &mut State) {
             });
         },
     );
+
+    Window::new("Memory Dump").default_open(false).default_pos(Pos2::new(5.0, 65.0)).show(
+        ctx.egui_ctx,
+        |ui| {
+            state.memory_dumper.render(
+                ui,
+                ctx.rom_path,
+                ctx.emulator,
+                MEMORY_REGIONS,
+                SmsGgEmulator::dump_memory,
+                SmsGgEmulator::restore_memory,
+            );
+        },
+    );
+
+    Window::new("Memory Map").default_open(false).default_pos(Pos2::new(5.0, 95.0)).show(
+        ctx.egui_ctx,
+        |ui| {
+            state.memory_map_viewer.render(ui, &ctx.emulator.memory_map_description());
+        },
+    );
+
+    state.audio_mixer.sync(|channel| ctx.emulator.is_psg_channel_muted(channel));
+    state.audio_mixer.render_window(ctx.egui_ctx, Pos2::new(5.0, 35.0));
+    state.audio_mixer.apply(|channel, muted| ctx.emulator.set_psg_channel_muted(channel, muted));
 }
 
 fn update_cram_texture(ctx: &mut DebugRenderContext<'_, SmsGgEmulator>, state: &mut State) {
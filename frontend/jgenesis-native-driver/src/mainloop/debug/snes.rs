@@ -1,14 +1,21 @@
 use crate::mainloop::debug;
-use crate::mainloop::debug::{DebugRenderContext, DebugRenderFn, SelectableButton};
-use egui::{CentralPanel, ScrollArea, Vec2};
+use crate::mainloop::debug::export;
+use crate::mainloop::debug::export::ExportStatus;
+use crate::mainloop::debug::memmap::MemoryMapViewer;
+use crate::mainloop::debug::memory::{DumpRegion, MemoryDumper};
+use crate::mainloop::debug::palette::PaletteExporter;
+use crate::mainloop::debug::{AudioMixer, DebugRenderContext, DebugRenderFn, SelectableButton};
+use egui::{CentralPanel, Pos2, ScrollArea, Vec2};
 use jgenesis_common::frontend::Color;
-use snes_core::api::SnesEmulator;
+use snes_core::api::{MemoryRegion, SnesEmulator};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 enum Tab {
     Cgram,
     #[default]
     Vram,
+    Memory,
+    MemoryMap,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -20,9 +27,42 @@ enum VramMode {
     Mode7,
 }
 
+impl VramMode {
+    // Width and height of the tile sheet in pixels
+    fn sheet_size(self) -> (u32, u32) {
+        match self {
+            Self::TwoBpp => (64 * 8, 64 * 8),
+            Self::FourBpp => (64 * 8, 32 * 8),
+            Self::EightBpp => (32 * 8, 32 * 8),
+            Self::Mode7 => (16 * 8, 16 * 8),
+        }
+    }
+
+    fn export_name(self, palette: u8) -> String {
+        let palette_name =
+            if palette < 8 { format!("bg{palette}") } else { format!("obj{}", palette - 8) };
+
+        match self {
+            Self::TwoBpp => format!("vram_2bpp_{palette_name}"),
+            Self::FourBpp => format!("vram_4bpp_{palette_name}"),
+            Self::EightBpp => "vram_8bpp".into(),
+            Self::Mode7 => "vram_mode7".into(),
+        }
+    }
+}
+
 const CGRAM_BUFFER_LEN: usize = 256;
 const VRAM_BUFFER_LEN: usize = 256 * 1024;
 
+const MEMORY_REGIONS: &[DumpRegion<MemoryRegion>] = &[
+    DumpRegion { region: MemoryRegion::Wram, label: "WRAM", name: "wram" },
+    DumpRegion { region: MemoryRegion::Vram, label: "VRAM", name: "vram" },
+    DumpRegion { region: MemoryRegion::Sram, label: "Cartridge SRAM", name: "sram" },
+];
+
+const AUDIO_CHANNELS: &[&str] =
+    &["Voice 0", "Voice 1", "Voice 2", "Voice 3", "Voice 4", "Voice 5", "Voice 6", "Voice 7"];
+
 struct State {
     tab: Tab,
     vram_mode: VramMode,
@@ -34,6 +74,11 @@ struct State {
     vram_8bpp_texture: Option<(wgpu::Texture, egui::TextureId)>,
     vram_mode7_texture: Option<(wgpu::Texture, egui::TextureId)>,
     vram_buffer: Box<[Color; VRAM_BUFFER_LEN]>,
+    cgram_exporter: PaletteExporter,
+    vram_export_status: ExportStatus,
+    memory_dumper: MemoryDumper,
+    memory_map_viewer: MemoryMapViewer,
+    audio_mixer: AudioMixer,
 }
 
 impl State {
@@ -55,6 +100,11 @@ impl State {
                 .into_boxed_slice()
                 .try_into()
                 .unwrap(),
+            cgram_exporter: PaletteExporter::new("cgram"),
+            vram_export_status: ExportStatus::default(),
+            memory_dumper: MemoryDumper::default(),
+            memory_map_viewer: MemoryMapViewer::default(),
+            audio_mixer: AudioMixer::new(AUDIO_CHANNELS),
         }
     }
 }
@@ -74,6 +124,8 @@ fn render(mut ctx: DebugRenderContext<'_, SnesEmulator>, state: &mut State) {
         ui.horizontal(|ui| {
             ui.add(SelectableButton::new("VRAM", &mut state.tab, Tab::Vram));
             ui.add(SelectableButton::new("CGRAM", &mut state.tab, Tab::Cgram));
+            ui.add(SelectableButton::new("Memory", &mut state.tab, Tab::Memory));
+            ui.add(SelectableButton::new("Memory Map", &mut state.tab, Tab::MemoryMap));
         });
 
         ui.add_space(15.0);
@@ -84,6 +136,16 @@ fn render(mut ctx: DebugRenderContext<'_, SnesEmulator>, state: &mut State) {
                     let egui_texture = state.cgram_texture.as_ref().unwrap().1;
                     ui.image((egui_texture, Vec2::new(screen_width * 0.65, screen_width * 0.65)));
                 });
+
+                ui.add_space(10.0);
+
+                state.cgram_exporter.render(
+                    ui,
+                    ctx.rom_path,
+                    state.cgram_buffer.as_ref(),
+                    16,
+                    || ctx.emulator.dump_cgram(),
+                );
             }
             Tab::Vram => {
                 let original_vram_mode = state.vram_mode;
@@ -126,6 +188,24 @@ fn render(mut ctx: DebugRenderContext<'_, SnesEmulator>, state: &mut State) {
                     },
                 );
 
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Export .png").clicked() {
+                        let (width, height) = original_vram_mode.sheet_size();
+                        export::export_png(
+                            &mut state.vram_export_status,
+                            ctx.rom_path,
+                            &original_vram_mode.export_name(state.vram_palette),
+                            state.vram_buffer.as_ref(),
+                            width,
+                            height,
+                        );
+                    }
+
+                    state.vram_export_status.render(ui);
+                });
+
                 ui.add_space(10.0);
 
                 ScrollArea::vertical().show(ui, |ui| match original_vram_mode {
@@ -147,8 +227,25 @@ fn render(mut ctx: DebugRenderContext<'_, SnesEmulator>, state: &mut State) {
                     }
                 });
             }
+            Tab::Memory => {
+                state.memory_dumper.render(
+                    ui,
+                    ctx.rom_path,
+                    ctx.emulator,
+                    MEMORY_REGIONS,
+                    SnesEmulator::dump_memory,
+                    SnesEmulator::restore_memory,
+                );
+            }
+            Tab::MemoryMap => {
+                state.memory_map_viewer.render(ui, &ctx.emulator.memory_map_description());
+            }
         }
     });
+
+    state.audio_mixer.sync(|voice| ctx.emulator.is_dsp_voice_muted(voice));
+    state.audio_mixer.render_window(ctx.egui_ctx, Pos2::new(screen_width * 0.6, 5.0));
+    state.audio_mixer.apply(|voice, muted| ctx.emulator.set_dsp_voice_muted(voice, muted));
 }
 
 fn update_cgram_texture(ctx: &mut DebugRenderContext<'_, SnesEmulator>, state: &mut State) {
@@ -0,0 +1,262 @@
+use crate::config::SystemConfig;
+use crate::fpstracker::PerformanceStats;
+use crate::input::{GamepadAssignment, Joysticks};
+use crate::mainloop::{NativeEmulatorResult, NativeTickEffect, SaveStateMetadata, SegaCdBackupRam};
+use crate::{AudioDeviceInfo, AudioError, NativeEmulator};
+use jgenesis_common::frontend::EmulatorTrait;
+use sdl2::EventPump;
+use segacd_core::api::SegaCdLoadResult;
+use std::path::Path;
+
+/// Object-safe interface over [`NativeEmulator`] for any system, so that frontends can drive an
+/// emulator without matching on every supported console.
+pub trait EmulatorCore {
+    /// Run the emulator until it renders a frame, then handle any pending input events.
+    ///
+    /// # Errors
+    ///
+    /// This method will propagate any errors encountered while running the emulator.
+    fn render_frame(&mut self) -> NativeEmulatorResult<Option<NativeTickEffect>>;
+
+    /// Reload config. The config must be for the same system that this emulator was created with;
+    /// a config for any other system is logged and ignored.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if it is unable to reload audio config.
+    fn reload_config(&mut self, config: SystemConfig) -> Result<(), AudioError>;
+
+    fn soft_reset(&mut self);
+
+    fn hard_reset(&mut self);
+
+    fn open_memory_viewer(&mut self);
+
+    fn swap_player_inputs(&mut self);
+
+    /// # Errors
+    ///
+    /// This method will return an error if unable to write the save state file.
+    fn save_state(&mut self, slot: usize) -> NativeEmulatorResult<()>;
+
+    /// # Errors
+    ///
+    /// This method will return an error if unable to read or decode the save state file.
+    fn load_state(&mut self, slot: usize) -> NativeEmulatorResult<()>;
+
+    fn save_state_metadata(&self) -> &SaveStateMetadata;
+
+    /// Save the automatic save state if it is enabled. Frontends should call this before stopping
+    /// the emulator for any reason other than a [`NativeTickEffect`], which saves it already.
+    fn save_auto_state(&mut self);
+
+    fn gamepad_assignments(&self) -> Vec<GamepadAssignment>;
+
+    fn performance_stats(&self) -> PerformanceStats;
+
+    fn audio_device_info(&self) -> AudioDeviceInfo;
+
+    /// Close the audio device and reopen it on the system default output device.
+    fn reinit_audio(&mut self);
+
+    fn focus(&mut self);
+
+    fn event_pump_and_joysticks_mut(&mut self) -> (&mut EventPump, &mut Joysticks);
+
+    /// Remove the current disc. Does nothing for systems without a disc drive.
+    fn remove_disc(&mut self) {}
+
+    /// Change the current disc. Does nothing for systems without a disc drive.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if unable to load the new disc.
+    fn change_disc(&mut self, _path: &Path) -> SegaCdLoadResult<()> {
+        Ok(())
+    }
+
+    /// Erase and re-format the console's internal backup RAM. Does nothing for systems without
+    /// backup RAM.
+    fn format_backup_ram(&mut self) {}
+
+    /// Replace Sega CD backup RAM with the contents of a backup RAM image file. Does nothing for
+    /// other systems.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if unable to read or convert the file.
+    fn import_backup_ram(
+        &mut self,
+        _backup_ram: SegaCdBackupRam,
+        _path: &Path,
+    ) -> NativeEmulatorResult<()> {
+        Ok(())
+    }
+
+    /// Write Sega CD backup RAM to a backup RAM image file of the given size. Does nothing for
+    /// other systems.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if unable to convert the contents or write the file.
+    fn export_backup_ram(
+        &mut self,
+        _backup_ram: SegaCdBackupRam,
+        _path: &Path,
+        _len: usize,
+    ) -> NativeEmulatorResult<()> {
+        Ok(())
+    }
+}
+
+// Per-system hooks that the blanket `EmulatorCore` impl delegates to
+pub trait NativeSystem: EmulatorTrait + Sized {
+    fn reload_system_config(
+        emulator: &mut NativeEmulator<Self>,
+        config: SystemConfig,
+    ) -> Result<(), AudioError>;
+
+    fn remove_disc(_emulator: &mut NativeEmulator<Self>) {}
+
+    fn change_disc(_emulator: &mut NativeEmulator<Self>, _path: &Path) -> SegaCdLoadResult<()> {
+        Ok(())
+    }
+
+    fn format_backup_ram(_emulator: &mut NativeEmulator<Self>) {}
+
+    fn import_backup_ram(
+        _emulator: &mut NativeEmulator<Self>,
+        _backup_ram: SegaCdBackupRam,
+        _path: &Path,
+    ) -> NativeEmulatorResult<()> {
+        Ok(())
+    }
+
+    fn export_backup_ram(
+        _emulator: &mut NativeEmulator<Self>,
+        _backup_ram: SegaCdBackupRam,
+        _path: &Path,
+        _len: usize,
+    ) -> NativeEmulatorResult<()> {
+        Ok(())
+    }
+}
+
+pub(super) fn log_mismatched_config(expected: &str, config: &SystemConfig) {
+    log::error!(
+        "Ignoring {} config passed to a {expected} emulator; this is a bug",
+        config.system_name()
+    );
+}
+
+impl<Emulator: NativeSystem> EmulatorCore for NativeEmulator<Emulator> {
+    fn render_frame(&mut self) -> NativeEmulatorResult<Option<NativeTickEffect>> {
+        NativeEmulator::render_frame(self)
+    }
+
+    fn reload_config(&mut self, config: SystemConfig) -> Result<(), AudioError> {
+        Emulator::reload_system_config(self, config)
+    }
+
+    fn soft_reset(&mut self) {
+        NativeEmulator::soft_reset(self);
+    }
+
+    fn hard_reset(&mut self) {
+        NativeEmulator::hard_reset(self);
+    }
+
+    fn open_memory_viewer(&mut self) {
+        NativeEmulator::open_memory_viewer(self);
+    }
+
+    fn swap_player_inputs(&mut self) {
+        NativeEmulator::swap_player_inputs(self);
+    }
+
+    fn save_state(&mut self, slot: usize) -> NativeEmulatorResult<()> {
+        NativeEmulator::save_state(self, slot)
+    }
+
+    fn load_state(&mut self, slot: usize) -> NativeEmulatorResult<()> {
+        NativeEmulator::load_state(self, slot)
+    }
+
+    fn save_state_metadata(&self) -> &SaveStateMetadata {
+        NativeEmulator::save_state_metadata(self)
+    }
+
+    fn save_auto_state(&mut self) {
+        NativeEmulator::save_auto_state(self);
+    }
+
+    fn gamepad_assignments(&self) -> Vec<GamepadAssignment> {
+        NativeEmulator::gamepad_assignments(self)
+    }
+
+    fn performance_stats(&self) -> PerformanceStats {
+        NativeEmulator::performance_stats(self)
+    }
+
+    fn audio_device_info(&self) -> AudioDeviceInfo {
+        NativeEmulator::audio_device_info(self)
+    }
+
+    fn reinit_audio(&mut self) {
+        NativeEmulator::reinit_audio(self);
+    }
+
+    fn focus(&mut self) {
+        NativeEmulator::focus(self);
+    }
+
+    fn event_pump_and_joysticks_mut(&mut self) -> (&mut EventPump, &mut Joysticks) {
+        NativeEmulator::event_pump_and_joysticks_mut(self)
+    }
+
+    fn remove_disc(&mut self) {
+        Emulator::remove_disc(self);
+    }
+
+    fn change_disc(&mut self, path: &Path) -> SegaCdLoadResult<()> {
+        Emulator::change_disc(self, path)
+    }
+
+    fn format_backup_ram(&mut self) {
+        Emulator::format_backup_ram(self);
+    }
+
+    fn import_backup_ram(
+        &mut self,
+        backup_ram: SegaCdBackupRam,
+        path: &Path,
+    ) -> NativeEmulatorResult<()> {
+        Emulator::import_backup_ram(self, backup_ram, path)
+    }
+
+    fn export_backup_ram(
+        &mut self,
+        backup_ram: SegaCdBackupRam,
+        path: &Path,
+        len: usize,
+    ) -> NativeEmulatorResult<()> {
+        Emulator::export_backup_ram(self, backup_ram, path, len)
+    }
+}
+
+/// Create an emulator for whichever system the given config is for.
+///
+/// # Errors
+///
+/// This function will propagate any error encountered while creating the emulator.
+pub fn create_emulator(config: SystemConfig) -> NativeEmulatorResult<Box<dyn EmulatorCore>> {
+    Ok(match config {
+        SystemConfig::SmsGg(config) => Box::new(super::create_smsgg(config)?),
+        SystemConfig::Genesis(config) => Box::new(super::create_genesis(config)?),
+        SystemConfig::SegaCd(config) => Box::new(super::create_sega_cd(config)?),
+        SystemConfig::Sega32X(config) => Box::new(super::create_32x(config)?),
+        SystemConfig::Nes(config) => Box::new(super::create_nes(config)?),
+        SystemConfig::Snes(config) => Box::new(super::create_snes(config)?),
+        SystemConfig::GameBoy(config) => Box::new(super::create_gb(config)?),
+    })
+}
@@ -1,11 +1,12 @@
 use crate::config::GameBoyConfig;
 use crate::config::RomReadResult;
+use crate::config::SystemConfig;
+use crate::mainloop::emucore::{self, NativeSystem};
 use crate::mainloop::save::{DeterminedPaths, FsSaveWriter};
 use crate::mainloop::{debug, file_name_no_ext, save};
 use crate::{AudioError, NativeEmulator, NativeEmulatorResult, config, extensions};
 use gb_core::api::GameBoyEmulator;
 use gb_core::inputs::GameBoyInputs;
-use jgenesis_common::frontend::EmulatorTrait;
 use std::path::Path;
 
 pub type NativeGameBoyEmulator = NativeEmulator<GameBoyEmulator>;
@@ -17,23 +18,31 @@ impl NativeGameBoyEmulator {
     pub fn reload_gb_config(&mut self, config: Box<GameBoyConfig>) -> Result<(), AudioError> {
         log::info!("Reloading config: {config}");
 
-        self.reload_common_config(&config.common)?;
-
-        self.update_emulator_config(&config.emulator_config);
-
-        // Config change could have changed target framerate (60 Hz hack)
-        self.renderer.set_target_fps(self.emulator.target_fps());
-
-        self.input_mapper.update_mappings(
-            config.common.axis_deadzone,
+        self.reload_shared_config(
+            &config.common,
+            &config.emulator_config,
             &config.inputs.to_mapping_vec(),
-            &config.common.hotkey_config.to_mapping_vec(),
-        );
+        )?;
 
         Ok(())
     }
 }
 
+impl NativeSystem for GameBoyEmulator {
+    fn reload_system_config(
+        emulator: &mut NativeEmulator<Self>,
+        config: SystemConfig,
+    ) -> Result<(), AudioError> {
+        match config {
+            SystemConfig::GameBoy(config) => emulator.reload_gb_config(config),
+            _ => {
+                emucore::log_mismatched_config("Game Boy", &config);
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Create an emulator with the Game Boy core with the given config.
 ///
 /// # Errors
@@ -60,7 +69,7 @@ pub fn create_gb(config: Box<GameBoyConfig>) -> NativeEmulatorResult<NativeGameB
     let rom_title = file_name_no_ext(&config.common.rom_file_path)?;
     let window_title = format!("gb - {rom_title}");
 
-    NativeGameBoyEmulator::new(
+    let mut emulator = NativeGameBoyEmulator::new(
         emulator,
         emulator_config,
         config.common,
@@ -72,5 +81,9 @@ pub fn create_gb(config: Box<GameBoyConfig>) -> NativeEmulatorResult<NativeGameB
         &config.inputs.to_mapping_vec(),
         GameBoyInputs::default(),
         debug::gb::render_fn,
-    )
+    )?;
+
+    emulator.discord_presence.set_game("Game Boy", rom_title);
+
+    Ok(emulator)
 }
@@ -0,0 +1,972 @@
+//! A minimal GDB remote serial protocol stub over TCP, so that gdb/lldb can connect to a running
+//! emulator to set breakpoints, single-step, and inspect registers and memory.
+//!
+//! Each emulated CPU is exposed to the debugger as a separate thread (thread IDs start at 1).
+//!
+//! Conditional breakpoints and watch expressions are available through `monitor` commands; see
+//! [`MONITOR_HELP`].
+
+use s32x_core::api::{ConditionalBreakpoint, Expression, Sega32XEmulator, Sh2Register, WhichCpu};
+use std::fmt::Write as _;
+use std::io;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+
+// Signal numbers used in stop replies
+const SIGINT: u8 = 2;
+const SIGTRAP: u8 = 5;
+
+const INTERRUPT_BYTE: u8 = 0x03;
+
+// Maximum packet size advertised to the client, in bytes
+const PACKET_SIZE: usize = 0x4000;
+
+// Each byte of memory is sent as 2 hex digits; leave room for the packet framing
+const MAX_MEMORY_READ_LEN: u32 = ((PACKET_SIZE - 4) / 2) as u32;
+
+/// A system that a GDB client can debug.
+pub trait GdbTarget {
+    /// Display names of the CPUs that are exposed as debugger threads, in thread ID order.
+    const THREAD_NAMES: &'static [&'static str];
+
+    /// Whether registers and memory should be transferred in big-endian byte order.
+    const BIG_ENDIAN: bool;
+
+    /// Register values for the given thread, in the order that GDB expects for this architecture.
+    fn read_registers(&self, thread: usize) -> Vec<u32>;
+
+    /// Returns `false` if the register index is invalid.
+    fn write_register(&mut self, thread: usize, index: usize, value: u32) -> bool;
+
+    /// Returns `None` if the address cannot be read by the debugger. Must not have any side effects
+    /// on emulated state.
+    fn read_memory(&self, thread: usize, address: u32) -> Option<u8>;
+
+    /// Returns `false` if the address cannot be written by the debugger.
+    fn write_memory(&mut self, thread: usize, address: u32, value: u8) -> bool;
+
+    fn add_breakpoint(&mut self, address: u32);
+
+    fn remove_breakpoint(&mut self, address: u32);
+
+    /// Resume all threads.
+    fn resume(&mut self);
+
+    /// Execute a single instruction on the given thread and then stop; all other threads resume.
+    fn step(&mut self, thread: usize);
+
+    /// The first thread that is stopped at a breakpoint or after a single step, if any.
+    fn stopped_thread(&self) -> Option<usize>;
+
+    /// Remove all breakpoints and resume all threads.
+    fn detach(&mut self);
+
+    /// Run a `monitor` command from the client and return the text to display.
+    fn monitor_command(&mut self, command: &str) -> String;
+
+    /// Extra detail about why the given thread stopped, e.g. which watch expression changed.
+    fn stop_message(&self, thread: usize) -> Option<String>;
+}
+
+const MONITOR_HELP: &str = "\
+break <addr>[-<end>] [if <expr>]  Break in an address range, optionally only if <expr> != 0
+watch <expr>                      Stop whenever the value of <expr> changes
+info                              List conditional breakpoints and watches
+delete break <n>                  Delete a conditional breakpoint
+delete watch <n>                  Delete a watch
+Expressions can use registers (r0-r15, pc, pr, gbr, vbr, mach, macl, sr), memory reads
+(b[addr], w[addr], l[addr]), hex or decimal literals, and C-like operators, e.g.
+  monitor break 6000100-6000200 if r0 == 0x42 && b[r4] != 0
+";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MonitorCommand {
+    Break(ConditionalBreakpoint),
+    Watch(Expression),
+    Info,
+    DeleteBreak(usize),
+    DeleteWatch(usize),
+    Help,
+}
+
+fn parse_monitor_command(command: &str) -> Result<MonitorCommand, String> {
+    let command = command.trim();
+    let (name, args) = command.split_once(char::is_whitespace).unwrap_or((command, ""));
+    let args = args.trim();
+
+    match name {
+        "break" => {
+            let (range, condition) = match args.split_once(" if ") {
+                Some((range, condition)) => (range.trim(), Some(condition)),
+                None => (args, None),
+            };
+
+            let (start, end) = range.split_once('-').unwrap_or((range, range));
+            let parse_address = |address: &str| {
+                let address = address.trim();
+                let address = address.strip_prefix("0x").unwrap_or(address);
+                u32::from_str_radix(address, 16).map_err(|_| format!("Invalid address: {address}"))
+            };
+            let start = parse_address(start)?;
+            let end = parse_address(end)?;
+            if end < start {
+                return Err("End address must not be less than start address".into());
+            }
+
+            let condition = condition
+                .map(Expression::parse)
+                .transpose()
+                .map_err(|err| format!("Invalid condition: {err}"))?;
+
+            Ok(MonitorCommand::Break(ConditionalBreakpoint { start, end, condition }))
+        }
+        "watch" => Expression::parse(args)
+            .map(MonitorCommand::Watch)
+            .map_err(|err| format!("Invalid expression: {err}")),
+        "info" => Ok(MonitorCommand::Info),
+        "delete" => {
+            let (kind, index) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+            let index: usize =
+                index.trim().parse().map_err(|_| format!("Invalid index: {}", index.trim()))?;
+            match kind {
+                "break" => Ok(MonitorCommand::DeleteBreak(index)),
+                "watch" => Ok(MonitorCommand::DeleteWatch(index)),
+                _ => Err("Expected 'delete break <n>' or 'delete watch <n>'".into()),
+            }
+        }
+        "help" | "" => Ok(MonitorCommand::Help),
+        _ => Err(format!("Unknown command '{name}'; try 'monitor help'")),
+    }
+}
+
+const SH2_REGISTERS: [Sh2Register; 23] = [
+    Sh2Register::Gpr(0),
+    Sh2Register::Gpr(1),
+    Sh2Register::Gpr(2),
+    Sh2Register::Gpr(3),
+    Sh2Register::Gpr(4),
+    Sh2Register::Gpr(5),
+    Sh2Register::Gpr(6),
+    Sh2Register::Gpr(7),
+    Sh2Register::Gpr(8),
+    Sh2Register::Gpr(9),
+    Sh2Register::Gpr(10),
+    Sh2Register::Gpr(11),
+    Sh2Register::Gpr(12),
+    Sh2Register::Gpr(13),
+    Sh2Register::Gpr(14),
+    Sh2Register::Gpr(15),
+    Sh2Register::Pc,
+    Sh2Register::Pr,
+    Sh2Register::Gbr,
+    Sh2Register::Vbr,
+    Sh2Register::Mach,
+    Sh2Register::Macl,
+    Sh2Register::Sr,
+];
+
+const SH2_CPUS: [WhichCpu; 2] = [WhichCpu::Master, WhichCpu::Slave];
+
+impl GdbTarget for Sega32XEmulator {
+    const THREAD_NAMES: &'static [&'static str] = &["Master SH-2", "Slave SH-2"];
+    const BIG_ENDIAN: bool = true;
+
+    fn read_registers(&self, thread: usize) -> Vec<u32> {
+        let sh2 = self.sh2(SH2_CPUS[thread]);
+        SH2_REGISTERS.iter().map(|&register| sh2.read_register(register)).collect()
+    }
+
+    fn write_register(&mut self, thread: usize, index: usize, value: u32) -> bool {
+        let Some(&register) = SH2_REGISTERS.get(index) else { return false };
+        self.sh2_mut(SH2_CPUS[thread]).write_register(register, value);
+        true
+    }
+
+    fn read_memory(&self, thread: usize, address: u32) -> Option<u8> {
+        self.read_sh2_memory(SH2_CPUS[thread], address)
+    }
+
+    fn write_memory(&mut self, thread: usize, address: u32, value: u8) -> bool {
+        self.write_sh2_memory(SH2_CPUS[thread], address, value)
+    }
+
+    fn add_breakpoint(&mut self, address: u32) {
+        for which in SH2_CPUS {
+            self.sh2_mut(which).add_breakpoint(address);
+        }
+    }
+
+    fn remove_breakpoint(&mut self, address: u32) {
+        for which in SH2_CPUS {
+            self.sh2_mut(which).remove_breakpoint(address);
+        }
+    }
+
+    fn resume(&mut self) {
+        for which in SH2_CPUS {
+            self.sh2_mut(which).debug_resume();
+        }
+    }
+
+    fn step(&mut self, thread: usize) {
+        for (i, which) in SH2_CPUS.into_iter().enumerate() {
+            if i == thread {
+                self.sh2_mut(which).debug_step();
+            } else {
+                self.sh2_mut(which).debug_resume();
+            }
+        }
+    }
+
+    fn stopped_thread(&self) -> Option<usize> {
+        SH2_CPUS.iter().position(|&which| self.sh2(which).debug_stopped())
+    }
+
+    fn detach(&mut self) {
+        for which in SH2_CPUS {
+            self.sh2_mut(which).detach_debugger();
+        }
+    }
+
+    fn monitor_command(&mut self, command: &str) -> String {
+        let command = match parse_monitor_command(command) {
+            Ok(command) => command,
+            Err(err) => return format!("{err}\n"),
+        };
+
+        // Conditional breakpoints and watches apply to both CPUs, and each CPU evaluates them
+        // using its own registers and view of memory
+        match command {
+            MonitorCommand::Break(breakpoint) => {
+                for which in SH2_CPUS {
+                    self.sh2_mut(which).add_conditional_breakpoint(breakpoint.clone());
+                }
+                let index = self.sh2(WhichCpu::Master).conditional_breakpoints().len() - 1;
+                format!("Breakpoint {index}: {}\n", describe_breakpoint(&breakpoint))
+            }
+            MonitorCommand::Watch(expression) => {
+                for which in SH2_CPUS {
+                    self.sh2_mut(which).add_watch(expression.clone());
+                }
+                let index = self.sh2(WhichCpu::Master).watches().len() - 1;
+                format!("Watch {index}: {expression}\n")
+            }
+            MonitorCommand::Info => {
+                let sh2 = self.sh2(WhichCpu::Master);
+
+                let mut output = String::new();
+                for (i, breakpoint) in sh2.conditional_breakpoints().iter().enumerate() {
+                    writeln!(output, "Breakpoint {i}: {}", describe_breakpoint(breakpoint))
+                        .unwrap();
+                }
+                for (i, watch) in sh2.watches().iter().enumerate() {
+                    write!(output, "Watch {i}: {}", watch.expression).unwrap();
+                    for (which, name) in SH2_CPUS.into_iter().zip(Self::THREAD_NAMES) {
+                        if let Some(value) = self.sh2(which).watches()[i].value() {
+                            write!(output, " ({name}: {value:08X})").unwrap();
+                        }
+                    }
+                    output.push('\n');
+                }
+
+                if output.is_empty() {
+                    "No conditional breakpoints or watches\n".into()
+                } else {
+                    output
+                }
+            }
+            MonitorCommand::DeleteBreak(index) => {
+                let mut removed = false;
+                for which in SH2_CPUS {
+                    removed = self.sh2_mut(which).remove_conditional_breakpoint(index);
+                }
+                if removed {
+                    format!("Deleted breakpoint {index}\n")
+                } else {
+                    format!("No breakpoint {index}\n")
+                }
+            }
+            MonitorCommand::DeleteWatch(index) => {
+                let mut removed = false;
+                for which in SH2_CPUS {
+                    removed = self.sh2_mut(which).remove_watch(index);
+                }
+                if removed {
+                    format!("Deleted watch {index}\n")
+                } else {
+                    format!("No watch {index}\n")
+                }
+            }
+            MonitorCommand::Help => MONITOR_HELP.into(),
+        }
+    }
+
+    fn stop_message(&self, thread: usize) -> Option<String> {
+        let sh2 = self.sh2(SH2_CPUS[thread]);
+        let hit = sh2.watch_hit()?;
+        let expression = &sh2.watches()[hit.index].expression;
+
+        Some(format!(
+            "Watch {}: {expression} changed from {:08X} to {:08X}\n",
+            hit.index, hit.old_value, hit.new_value
+        ))
+    }
+}
+
+fn describe_breakpoint(breakpoint: &ConditionalBreakpoint) -> String {
+    let mut description = format!("{:08X}", breakpoint.start);
+    if breakpoint.end != breakpoint.start {
+        write!(description, "-{:08X}", breakpoint.end).unwrap();
+    }
+    if let Some(condition) = &breakpoint.condition {
+        write!(description, " if {condition}").unwrap();
+    }
+    description
+}
+
+/// Type-erased interface that the main loop uses to drive a GDB server for its emulator.
+pub trait GdbStub<Emulator> {
+    /// Accept new connections and handle any pending packets from the connected client.
+    fn poll(&mut self, emulator: &mut Emulator);
+
+    /// Check whether the emulator just stopped at a breakpoint or after a single step, and if so
+    /// notify the client. Should be called after every emulator tick.
+    fn check_stopped(&mut self, emulator: &mut Emulator) -> bool;
+
+    /// Whether emulation is halted waiting for the debugger.
+    fn halted(&self) -> bool;
+}
+
+struct GdbClient {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+    // Output that could not be written yet because the socket's send buffer was full
+    send_buffer: Vec<u8>,
+}
+
+impl GdbClient {
+    fn new(stream: TcpStream) -> Self {
+        Self { stream, buffer: Vec::new(), send_buffer: Vec::new() }
+    }
+
+    // Writes as much buffered output as the socket will accept without blocking. Returns false if
+    // the connection was closed
+    fn flush(&mut self) -> bool {
+        while !self.send_buffer.is_empty() {
+            match self.stream.write(&self.send_buffer) {
+                Ok(0) => return false,
+                Ok(len) => {
+                    self.send_buffer.drain(..len);
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => return true,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => {
+                    log::error!("Error writing to GDB connection: {err}");
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+pub struct GdbServer {
+    listener: TcpListener,
+    client: Option<GdbClient>,
+    halted: bool,
+    stopped_thread: usize,
+    selected_thread: usize,
+}
+
+impl GdbServer {
+    /// Start listening for GDB connections on the given localhost port.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if unable to bind to the port.
+    pub fn bind(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        listener.set_nonblocking(true)?;
+
+        log::info!("GDB server listening on localhost:{port}");
+
+        Ok(Self { listener, client: None, halted: false, stopped_thread: 0, selected_thread: 0 })
+    }
+
+    fn accept(&mut self) {
+        let stream = match self.listener.accept() {
+            Ok((stream, addr)) => {
+                log::info!("GDB client connected from {addr}");
+                stream
+            }
+            Err(err) if err.kind() == ErrorKind::WouldBlock => return,
+            Err(err) => {
+                log::error!("Error accepting GDB connection: {err}");
+                return;
+            }
+        };
+
+        if let Err(err) = stream.set_nonblocking(true).and_then(|()| stream.set_nodelay(true)) {
+            log::error!("Error configuring GDB connection: {err}");
+            return;
+        }
+
+        // GDB expects the target to be stopped when it attaches
+        self.client = Some(GdbClient::new(stream));
+        self.halted = true;
+        self.stopped_thread = 0;
+        self.selected_thread = 0;
+    }
+
+    fn flush<E: GdbTarget>(&mut self, emulator: &mut E) {
+        if self.client.as_mut().is_some_and(|client| !client.flush()) {
+            self.disconnect(emulator);
+        }
+    }
+
+    fn disconnect<E: GdbTarget>(&mut self, emulator: &mut E) {
+        log::info!("GDB client disconnected");
+
+        // Best effort to deliver any final reply, e.g. the response to a detach request
+        if let Some(client) = &mut self.client {
+            client.flush();
+        }
+
+        emulator.detach();
+        self.client = None;
+        self.halted = false;
+    }
+
+    // Returns false if the connection was closed
+    fn read_available(&mut self) -> bool {
+        let Some(client) = &mut self.client else { return false };
+
+        let mut chunk = [0; 4096];
+        loop {
+            match client.stream.read(&mut chunk) {
+                Ok(0) => return false,
+                Ok(len) => client.buffer.extend_from_slice(&chunk[..len]),
+                Err(err) if err.kind() == ErrorKind::WouldBlock => return true,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => {
+                    log::error!("Error reading from GDB connection: {err}");
+                    return false;
+                }
+            }
+        }
+    }
+
+    // Pulls the next complete packet out of the receive buffer, acknowledging it. Interrupt
+    // requests are returned as a packet containing only the interrupt byte.
+    fn next_packet(&mut self) -> Option<Vec<u8>> {
+        let client = self.client.as_mut()?;
+
+        loop {
+            match client.buffer.first()? {
+                b'$' => break,
+                &INTERRUPT_BYTE => {
+                    client.buffer.remove(0);
+                    return Some(vec![INTERRUPT_BYTE]);
+                }
+                // Acks and anything unexpected between packets
+                _ => {
+                    client.buffer.remove(0);
+                }
+            }
+        }
+
+        let end = client.buffer.iter().position(|&b| b == b'#')?;
+        if client.buffer.len() < end + 3 {
+            return None;
+        }
+
+        let packet = client.buffer[1..end].to_vec();
+        client.buffer.drain(..end + 3);
+
+        // TCP is reliable; acknowledge without verifying the checksum
+        client.send_buffer.push(b'+');
+
+        Some(packet)
+    }
+
+    fn send_packet(&mut self, data: &str) {
+        let Some(client) = &mut self.client else { return };
+
+        let checksum = data.bytes().fold(0_u8, u8::wrapping_add);
+        client.send_buffer.extend_from_slice(format!("${data}#{checksum:02x}").as_bytes());
+    }
+
+    fn stop_reply(&self, signal: u8) -> String {
+        format!("T{signal:02x}thread:{:x};", self.stopped_thread + 1)
+    }
+
+    fn handle_packet<E: GdbTarget>(&mut self, packet: &[u8], emulator: &mut E) {
+        if packet == [INTERRUPT_BYTE] {
+            if !self.halted {
+                self.halted = true;
+                self.send_packet(&self.stop_reply(SIGINT));
+            }
+            return;
+        }
+
+        log::trace!("GDB packet: {}", String::from_utf8_lossy(packet));
+
+        let Some((&command, args)) = packet.split_first() else {
+            self.send_packet("");
+            return;
+        };
+        let Ok(args) = std::str::from_utf8(args) else {
+            self.send_packet("");
+            return;
+        };
+
+        let response = match command {
+            b'?' => self.stop_reply(SIGTRAP),
+            b'g' => {
+                let mut response = String::new();
+                for value in emulator.read_registers(self.selected_thread) {
+                    push_u32_hex::<E>(&mut response, value);
+                }
+                response
+            }
+            b'G' => {
+                let values = args.as_bytes().chunks(8).filter_map(parse_u32_hex::<E>);
+                for (i, value) in values.enumerate() {
+                    emulator.write_register(self.selected_thread, i, value);
+                }
+                "OK".into()
+            }
+            b'p' => {
+                let value = usize::from_str_radix(args, 16)
+                    .ok()
+                    .and_then(|i| emulator.read_registers(self.selected_thread).get(i).copied());
+                match value {
+                    Some(value) => {
+                        let mut response = String::new();
+                        push_u32_hex::<E>(&mut response, value);
+                        response
+                    }
+                    None => "E01".into(),
+                }
+            }
+            b'P' => {
+                let written = args.split_once('=').is_some_and(|(index, value)| {
+                    let index = usize::from_str_radix(index, 16).ok();
+                    let value = parse_u32_hex::<E>(value.as_bytes());
+                    index.zip(value).is_some_and(|(index, value)| {
+                        emulator.write_register(self.selected_thread, index, value)
+                    })
+                });
+                if written { "OK".into() } else { "E01".into() }
+            }
+            b'm' => self.read_memory(args, emulator).unwrap_or_else(|| "E01".into()),
+            b'M' => {
+                if self.write_memory(args, emulator) {
+                    "OK".into()
+                } else {
+                    "E01".into()
+                }
+            }
+            b'c' => {
+                emulator.resume();
+                self.halted = false;
+                return;
+            }
+            b's' => {
+                emulator.step(self.selected_thread);
+                self.halted = false;
+                return;
+            }
+            b'Z' | b'z' => match parse_breakpoint(args) {
+                Some(address) => {
+                    if command == b'Z' {
+                        emulator.add_breakpoint(address);
+                    } else {
+                        emulator.remove_breakpoint(address);
+                    }
+                    "OK".into()
+                }
+                None => String::new(),
+            },
+            b'H' => match parse_thread::<E>(args.get(1..).unwrap_or("")) {
+                Some(ThreadId::Any) => "OK".into(),
+                Some(ThreadId::Thread(thread)) => {
+                    self.selected_thread = thread;
+                    "OK".into()
+                }
+                None => "E01".into(),
+            },
+            b'T' => match parse_thread::<E>(args) {
+                Some(ThreadId::Thread(_)) => "OK".into(),
+                _ => "E01".into(),
+            },
+            b'q' => self.handle_query(args, emulator),
+            b'D' => {
+                self.send_packet("OK");
+                self.disconnect(emulator);
+                return;
+            }
+            b'k' => {
+                self.disconnect(emulator);
+                return;
+            }
+            _ => String::new(),
+        };
+
+        self.send_packet(&response);
+    }
+
+    fn handle_query<E: GdbTarget>(&self, query: &str, emulator: &mut E) -> String {
+        if query.starts_with("Supported") {
+            return format!("PacketSize={PACKET_SIZE:x}");
+        }
+
+        if let Some(command) = query.strip_prefix("Rcmd,") {
+            let Some(command) = parse_hex_string(command) else { return "E01".into() };
+            let output = emulator.monitor_command(&command);
+            return if output.is_empty() { "OK".into() } else { hex_string(output.as_bytes()) };
+        }
+
+        if let Some(thread) = query.strip_prefix("ThreadExtraInfo,") {
+            return match parse_thread::<E>(thread) {
+                Some(ThreadId::Thread(thread)) => hex_string(E::THREAD_NAMES[thread].as_bytes()),
+                _ => "E01".into(),
+            };
+        }
+
+        match query {
+            "Attached" => "1".into(),
+            "C" => format!("QC{:x}", self.stopped_thread + 1),
+            "fThreadInfo" => {
+                let ids: Vec<_> = (1..=E::THREAD_NAMES.len()).map(|id| format!("{id:x}")).collect();
+                format!("m{}", ids.join(","))
+            }
+            "sThreadInfo" => "l".into(),
+            _ => String::new(),
+        }
+    }
+
+    fn read_memory<E: GdbTarget>(&self, args: &str, emulator: &E) -> Option<String> {
+        let (address, len) = parse_address_len(args)?;
+
+        // Reads are allowed to return fewer bytes than requested; stop at the packet size limit or
+        // at the first unreadable address
+        let len = len.min(MAX_MEMORY_READ_LEN);
+        let mut response = String::with_capacity(2 * len as usize);
+        for i in 0..len {
+            let Some(value) = emulator.read_memory(self.selected_thread, address.wrapping_add(i))
+            else {
+                break;
+            };
+            write!(response, "{value:02x}").unwrap();
+        }
+
+        (!response.is_empty()).then_some(response)
+    }
+
+    fn write_memory<E: GdbTarget>(&self, args: &str, emulator: &mut E) -> bool {
+        let Some((address_len, data)) = args.split_once(':') else { return false };
+        let Some((address, len)) = parse_address_len(address_len) else { return false };
+
+        let bytes = data.as_bytes().chunks(2).filter_map(parse_u8_hex).take(len as usize);
+        for (i, value) in bytes.enumerate() {
+            if !emulator.write_memory(self.selected_thread, address.wrapping_add(i as u32), value) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl<E: GdbTarget> GdbStub<E> for GdbServer {
+    fn poll(&mut self, emulator: &mut E) {
+        if self.client.is_none() {
+            self.accept();
+            if self.client.is_none() {
+                return;
+            }
+        }
+
+        if !self.read_available() {
+            self.disconnect(emulator);
+            return;
+        }
+
+        while let Some(packet) = self.next_packet() {
+            self.handle_packet(&packet, emulator);
+            if self.client.is_none() {
+                return;
+            }
+        }
+
+        self.flush(emulator);
+    }
+
+    fn check_stopped(&mut self, emulator: &mut E) -> bool {
+        if self.client.is_none() || self.halted {
+            return false;
+        }
+
+        let Some(thread) = emulator.stopped_thread() else { return false };
+
+        self.halted = true;
+        self.stopped_thread = thread;
+        self.selected_thread = thread;
+
+        // Console output packets are only allowed before the stop reply
+        if let Some(message) = emulator.stop_message(thread) {
+            self.send_packet(&format!("O{}", hex_string(message.as_bytes())));
+        }
+        self.send_packet(&self.stop_reply(SIGTRAP));
+        self.flush(emulator);
+
+        true
+    }
+
+    fn halted(&self) -> bool {
+        self.halted
+    }
+}
+
+fn push_u32_hex<E: GdbTarget>(out: &mut String, value: u32) {
+    let bytes = if E::BIG_ENDIAN { value.to_be_bytes() } else { value.to_le_bytes() };
+    for b in bytes {
+        write!(out, "{b:02x}").unwrap();
+    }
+}
+
+fn parse_u8_hex(hex: &[u8]) -> Option<u8> {
+    u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()
+}
+
+fn parse_u32_hex<E: GdbTarget>(hex: &[u8]) -> Option<u32> {
+    if hex.len() != 8 {
+        return None;
+    }
+
+    let mut bytes = [0; 4];
+    for (byte, chunk) in bytes.iter_mut().zip(hex.chunks(2)) {
+        *byte = parse_u8_hex(chunk)?;
+    }
+
+    Some(if E::BIG_ENDIAN { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ThreadId {
+    // Thread IDs 0 (any thread) and -1 (all threads)
+    Any,
+    Thread(usize),
+}
+
+fn parse_thread<E: GdbTarget>(thread: &str) -> Option<ThreadId> {
+    if thread == "0" || thread == "-1" {
+        return Some(ThreadId::Any);
+    }
+
+    let id = usize::from_str_radix(thread, 16).ok()?;
+    (1..=E::THREAD_NAMES.len()).contains(&id).then_some(ThreadId::Thread(id - 1))
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 * bytes.len());
+    for b in bytes {
+        write!(hex, "{b:02x}").unwrap();
+    }
+    hex
+}
+
+fn parse_hex_string(hex: &str) -> Option<String> {
+    let bytes: Option<Vec<_>> = hex.as_bytes().chunks(2).map(parse_u8_hex).collect();
+    String::from_utf8(bytes?).ok()
+}
+
+// Parses "addr,length"
+fn parse_address_len(args: &str) -> Option<(u32, u32)> {
+    let (address, len) = args.split_once(',')?;
+    Some((u32::from_str_radix(address, 16).ok()?, u32::from_str_radix(len, 16).ok()?))
+}
+
+// Parses "type,addr,kind"; only software (0) and hardware (1) execution breakpoints are supported
+fn parse_breakpoint(args: &str) -> Option<u32> {
+    let mut fields = args.split(',');
+    let breakpoint_type = fields.next()?;
+    if breakpoint_type != "0" && breakpoint_type != "1" {
+        return None;
+    }
+
+    u32::from_str_radix(fields.next()?, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_packet_fields() {
+        assert_eq!(parse_address_len("6000100,20"), Some((0x06000100, 0x20)));
+        assert_eq!(parse_address_len("6000100"), None);
+
+        assert_eq!(parse_breakpoint("0,2000480,2"), Some(0x02000480));
+        assert_eq!(parse_breakpoint("1,2000480,2"), Some(0x02000480));
+        assert_eq!(parse_breakpoint("2,2000480,2"), None);
+
+        assert_eq!(parse_u32_hex::<Sega32XEmulator>(b"06000100"), Some(0x06000100));
+        assert_eq!(parse_u32_hex::<Sega32XEmulator>(b"0600010"), None);
+
+        let mut hex = String::new();
+        push_u32_hex::<Sega32XEmulator>(&mut hex, 0x06000100);
+        assert_eq!(hex, "06000100");
+    }
+
+    #[test]
+    fn parse_monitor_commands() {
+        assert_eq!(parse_hex_string("696e666f"), Some("info".into()));
+        assert_eq!(parse_monitor_command("info"), Ok(MonitorCommand::Info));
+
+        let Ok(MonitorCommand::Break(breakpoint)) =
+            parse_monitor_command("break 6000100-0x6000200 if r0 == 0x42")
+        else {
+            panic!("expected breakpoint");
+        };
+        assert_eq!((breakpoint.start, breakpoint.end), (0x06000100, 0x06000200));
+        assert_eq!(breakpoint.condition, Some(Expression::parse("r0 == 0x42").unwrap()));
+        assert_eq!(describe_breakpoint(&breakpoint), "06000100-06000200 if r0 == 0x42");
+
+        assert_eq!(
+            parse_monitor_command("break 2000480"),
+            Ok(MonitorCommand::Break(ConditionalBreakpoint {
+                start: 0x02000480,
+                end: 0x02000480,
+                condition: None
+            }))
+        );
+        assert!(parse_monitor_command("break 200-100").is_err());
+        assert!(parse_monitor_command("break 100 if r0 ==").is_err());
+
+        assert_eq!(
+            parse_monitor_command("watch l[r4]"),
+            Ok(MonitorCommand::Watch(Expression::parse("l[r4]").unwrap()))
+        );
+        assert_eq!(parse_monitor_command("delete watch 1"), Ok(MonitorCommand::DeleteWatch(1)));
+        assert_eq!(parse_monitor_command("delete break 0"), Ok(MonitorCommand::DeleteBreak(0)));
+        assert!(parse_monitor_command("delete foo 0").is_err());
+        assert!(parse_monitor_command("frobnicate").is_err());
+    }
+
+    struct TestTarget {
+        memory: Vec<u8>,
+    }
+
+    impl GdbTarget for TestTarget {
+        const THREAD_NAMES: &'static [&'static str] = &["CPU"];
+        const BIG_ENDIAN: bool = true;
+
+        fn read_registers(&self, _thread: usize) -> Vec<u32> {
+            vec![0; 4]
+        }
+
+        fn write_register(&mut self, _thread: usize, _index: usize, _value: u32) -> bool {
+            false
+        }
+
+        fn read_memory(&self, _thread: usize, address: u32) -> Option<u8> {
+            self.memory.get(address as usize).copied()
+        }
+
+        fn write_memory(&mut self, _thread: usize, _address: u32, _value: u8) -> bool {
+            false
+        }
+
+        fn add_breakpoint(&mut self, _address: u32) {}
+
+        fn remove_breakpoint(&mut self, _address: u32) {}
+
+        fn resume(&mut self) {}
+
+        fn step(&mut self, _thread: usize) {}
+
+        fn stopped_thread(&self) -> Option<usize> {
+            None
+        }
+
+        fn detach(&mut self) {}
+
+        fn monitor_command(&mut self, _command: &str) -> String {
+            String::new()
+        }
+
+        fn stop_message(&self, _thread: usize) -> Option<String> {
+            None
+        }
+    }
+
+    fn connected_server() -> (GdbServer, TcpStream) {
+        let mut server = GdbServer::bind(0).unwrap();
+        let address = server.listener.local_addr().unwrap();
+        let client = TcpStream::connect(address).unwrap();
+        client.set_nonblocking(true).unwrap();
+
+        while server.client.is_none() {
+            server.accept();
+        }
+
+        (server, client)
+    }
+
+    // Sends a packet and returns the (unacknowledged) reply packet's contents
+    fn request(
+        server: &mut GdbServer,
+        client: &mut TcpStream,
+        target: &mut TestTarget,
+        packet: &[u8],
+    ) -> String {
+        let checksum = packet.iter().copied().fold(0_u8, u8::wrapping_add);
+        client.write_all(b"$").unwrap();
+        client.write_all(packet).unwrap();
+        client.write_all(format!("#{checksum:02x}").as_bytes()).unwrap();
+
+        let mut reply = Vec::new();
+        while !(reply.len() >= 3 && reply[reply.len() - 3] == b'#') {
+            server.poll(target);
+
+            let mut chunk = [0; 4096];
+            match client.read(&mut chunk) {
+                Ok(len) => reply.extend_from_slice(&chunk[..len]),
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {}
+                Err(err) => panic!("{err}"),
+            }
+        }
+
+        let reply = String::from_utf8(reply).unwrap();
+        let reply = reply.strip_prefix("+$").unwrap();
+        reply[..reply.len() - 3].into()
+    }
+
+    #[test]
+    fn non_ascii_packets() {
+        let (mut server, mut client) = connected_server();
+        let mut target = TestTarget { memory: vec![] };
+
+        assert_eq!(request(&mut server, &mut client, &mut target, "é".as_bytes()), "");
+        assert_eq!(request(&mut server, &mut client, &mut target, b"m\xFF"), "");
+    }
+
+    #[test]
+    fn memory_reads_fit_in_packet() {
+        let (mut server, mut client) = connected_server();
+        let mut target = TestTarget { memory: (0..0x10000).map(|i| i as u8).collect() };
+
+        assert_eq!(request(&mut server, &mut client, &mut target, b"m10,4"), "10111213");
+
+        let response = request(&mut server, &mut client, &mut target, b"m0,ffffffff");
+        assert_eq!(response.len(), 2 * MAX_MEMORY_READ_LEN as usize);
+        assert!(response.len() + 4 <= PACKET_SIZE);
+
+        // Partial reads stop at the first unreadable address
+        assert_eq!(request(&mut server, &mut client, &mut target, b"mfffe,4"), "feff");
+        assert_eq!(request(&mut server, &mut client, &mut target, b"m10000,4"), "E01");
+    }
+}
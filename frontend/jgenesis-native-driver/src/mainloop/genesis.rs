@@ -1,15 +1,38 @@
 use crate::config::RomReadResult;
+use crate::config::SystemConfig;
 use crate::config::{GenesisConfig, Sega32XConfig, SegaCdConfig};
+use crate::mainloop::emucore::{self, NativeSystem};
+use crate::mainloop::gdb::GdbServer;
+use crate::mainloop::link::LinkCable;
 use crate::mainloop::save::{DeterminedPaths, FsSaveWriter};
-use crate::mainloop::{NativeEmulatorError, debug, save};
+use crate::mainloop::{MODAL_DURATION, NativeEmulatorError, debug, game_title, save};
 use crate::{AudioError, NativeEmulator, NativeEmulatorResult, config, extensions};
 use genesis_core::{GenesisEmulator, GenesisInputs};
+use jgenesis_common::frontend::EmulatorTrait;
 use s32x_core::api::Sega32XEmulator;
 use segacd_core::CdRomFileFormat;
 use segacd_core::api::{SegaCdEmulator, SegaCdLoadResult};
 use std::fs;
 use std::path::Path;
 
+/// Sega CD backup RAM that can be imported from or exported to files from other emulators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegaCdBackupRam {
+    /// 8KB internal backup RAM
+    Internal,
+    /// 128KB backup RAM cartridge
+    RamCartridge,
+}
+
+impl SegaCdBackupRam {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Internal => "internal backup RAM",
+            Self::RamCartridge => "RAM cartridge",
+        }
+    }
+}
+
 pub type NativeGenesisEmulator = NativeEmulator<GenesisEmulator>;
 
 impl NativeGenesisEmulator {
@@ -19,20 +42,31 @@ impl NativeGenesisEmulator {
     pub fn reload_genesis_config(&mut self, config: Box<GenesisConfig>) -> Result<(), AudioError> {
         log::info!("Reloading config: {config}");
 
-        self.reload_common_config(&config.common)?;
-
-        self.update_emulator_config(&config.emulator_config);
-
-        self.input_mapper.update_mappings(
-            config.common.axis_deadzone,
+        self.reload_shared_config(
+            &config.common,
+            &config.emulator_config,
             &config.inputs.to_mapping_vec(),
-            &config.common.hotkey_config.to_mapping_vec(),
-        );
+        )?;
 
         Ok(())
     }
 }
 
+impl NativeSystem for GenesisEmulator {
+    fn reload_system_config(
+        emulator: &mut NativeEmulator<Self>,
+        config: SystemConfig,
+    ) -> Result<(), AudioError> {
+        match config {
+            SystemConfig::Genesis(config) => emulator.reload_genesis_config(config),
+            _ => {
+                emucore::log_mismatched_config("Genesis", &config);
+                Ok(())
+            }
+        }
+    }
+}
+
 pub type NativeSegaCdEmulator = NativeEmulator<SegaCdEmulator>;
 
 impl NativeSegaCdEmulator {
@@ -42,15 +76,11 @@ impl NativeSegaCdEmulator {
     pub fn reload_sega_cd_config(&mut self, config: Box<SegaCdConfig>) -> Result<(), AudioError> {
         log::info!("Reloading config: {config}");
 
-        self.reload_common_config(&config.genesis.common)?;
-
-        self.update_emulator_config(&config.emulator_config);
-
-        self.input_mapper.update_mappings(
-            config.genesis.common.axis_deadzone,
+        self.reload_shared_config(
+            &config.genesis.common,
+            &config.emulator_config,
             &config.genesis.inputs.to_mapping_vec(),
-            &config.genesis.common.hotkey_config.to_mapping_vec(),
-        );
+        )?;
 
         Ok(())
     }
@@ -81,9 +111,21 @@ impl NativeSegaCdEmulator {
             CdRomFileFormat::CueBin
         });
 
-        self.emulator.change_disc(rom_path, rom_format)?;
+        self.emulator.change_disc(rom_path.as_ref(), rom_format)?;
+
+        // The automatic save state is for whichever disc is in the drive
+        if self.auto_state_path.is_some() {
+            self.update_rom_hash(rom_path.as_ref());
+            if self.rom_hash.is_some() {
+                self.update_auto_state_path(true);
+            } else {
+                self.auto_state_path = None;
+            }
+        }
 
-        let title = format!("sega cd - {}", self.emulator.disc_title());
+        let disc_title = self.emulator.game_metadata().title.unwrap_or_default();
+        let title = format!("sega cd - {disc_title}");
+        self.discord_presence.set_game("Sega CD", disc_title);
 
         // SAFETY: This is not reassigning the window
         unsafe {
@@ -95,6 +137,131 @@ impl NativeSegaCdEmulator {
 
         Ok(())
     }
+
+    pub fn format_backup_ram(&mut self) {
+        log::info!("Formatting Sega CD backup RAM");
+        self.emulator.format_backup_ram();
+    }
+
+    /// Replace internal backup RAM or RAM cartridge contents with a backup RAM image file, e.g. a
+    /// Genesis Plus GX `.brm` file. Images of other sizes are converted.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if unable to read the file or if the file is not a
+    /// convertible backup RAM image.
+    pub fn import_backup_ram(
+        &mut self,
+        backup_ram: SegaCdBackupRam,
+        path: &Path,
+    ) -> NativeEmulatorResult<()> {
+        log::info!("Importing Sega CD {} from '{}'", backup_ram.label(), path.display());
+
+        let result = fs::read(path)
+            .map_err(|source| NativeEmulatorError::SegaCdBackupRamIo {
+                path: path.display().to_string(),
+                source,
+            })
+            .and_then(|image| {
+                match backup_ram {
+                    SegaCdBackupRam::Internal => self.emulator.import_backup_ram(&image),
+                    SegaCdBackupRam::RamCartridge => self.emulator.import_ram_cartridge(&image),
+                }
+                .map_err(NativeEmulatorError::from)
+            });
+
+        let message = match &result {
+            Ok(()) => format!("Imported {}", backup_ram.label()),
+            Err(_) => format!("Failed to import {}", backup_ram.label()),
+        };
+        self.renderer.add_modal(message, MODAL_DURATION);
+
+        result
+    }
+
+    /// Write internal backup RAM or RAM cartridge contents to a file as an image of the given size,
+    /// e.g. 512KB for a Genesis Plus GX RAM cartridge.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the contents cannot be converted to the given size or
+    /// if unable to write the file.
+    pub fn export_backup_ram(
+        &mut self,
+        backup_ram: SegaCdBackupRam,
+        path: &Path,
+        len: usize,
+    ) -> NativeEmulatorResult<()> {
+        log::info!(
+            "Exporting Sega CD {} to '{}' ({len} bytes)",
+            backup_ram.label(),
+            path.display()
+        );
+
+        let result = match backup_ram {
+            SegaCdBackupRam::Internal => self.emulator.export_backup_ram(len),
+            SegaCdBackupRam::RamCartridge => self.emulator.export_ram_cartridge(len),
+        }
+        .map_err(NativeEmulatorError::from)
+        .and_then(|image| {
+            fs::write(path, image).map_err(|source| NativeEmulatorError::SegaCdBackupRamIo {
+                path: path.display().to_string(),
+                source,
+            })
+        });
+
+        let message = match &result {
+            Ok(()) => format!("Exported {}", backup_ram.label()),
+            Err(_) => format!("Failed to export {}", backup_ram.label()),
+        };
+        self.renderer.add_modal(message, MODAL_DURATION);
+
+        result
+    }
+}
+
+impl NativeSystem for SegaCdEmulator {
+    fn reload_system_config(
+        emulator: &mut NativeEmulator<Self>,
+        config: SystemConfig,
+    ) -> Result<(), AudioError> {
+        match config {
+            SystemConfig::SegaCd(config) => emulator.reload_sega_cd_config(config),
+            _ => {
+                emucore::log_mismatched_config("Sega CD", &config);
+                Ok(())
+            }
+        }
+    }
+
+    fn remove_disc(emulator: &mut NativeEmulator<Self>) {
+        emulator.remove_disc();
+    }
+
+    fn change_disc(emulator: &mut NativeEmulator<Self>, path: &Path) -> SegaCdLoadResult<()> {
+        emulator.change_disc(path)
+    }
+
+    fn format_backup_ram(emulator: &mut NativeEmulator<Self>) {
+        emulator.format_backup_ram();
+    }
+
+    fn import_backup_ram(
+        emulator: &mut NativeEmulator<Self>,
+        backup_ram: SegaCdBackupRam,
+        path: &Path,
+    ) -> NativeEmulatorResult<()> {
+        emulator.import_backup_ram(backup_ram, path)
+    }
+
+    fn export_backup_ram(
+        emulator: &mut NativeEmulator<Self>,
+        backup_ram: SegaCdBackupRam,
+        path: &Path,
+        len: usize,
+    ) -> NativeEmulatorResult<()> {
+        emulator.export_backup_ram(backup_ram, path, len)
+    }
 }
 
 pub type Native32XEmulator = NativeEmulator<Sega32XEmulator>;
@@ -106,20 +273,31 @@ impl Native32XEmulator {
     pub fn reload_32x_config(&mut self, config: Box<Sega32XConfig>) -> Result<(), AudioError> {
         log::info!("Reloading config: {config}");
 
-        self.reload_common_config(&config.genesis.common)?;
-
-        self.update_emulator_config(&config.emulator_config);
-
-        self.input_mapper.update_mappings(
-            config.genesis.common.axis_deadzone,
+        self.reload_shared_config(
+            &config.genesis.common,
+            &config.emulator_config,
             &config.genesis.inputs.to_mapping_vec(),
-            &config.genesis.common.hotkey_config.to_mapping_vec(),
-        );
+        )?;
 
         Ok(())
     }
 }
 
+impl NativeSystem for Sega32XEmulator {
+    fn reload_system_config(
+        emulator: &mut NativeEmulator<Self>,
+        config: SystemConfig,
+    ) -> Result<(), AudioError> {
+        match config {
+            SystemConfig::Sega32X(config) => emulator.reload_32x_config(config),
+            _ => {
+                emucore::log_mismatched_config("32X", &config);
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Create an emulator with the Genesis core with the given config.
 ///
 /// # Errors
@@ -141,16 +319,15 @@ pub fn create_genesis(config: Box<GenesisConfig>) -> NativeEmulatorResult<Native
     let mut save_writer = FsSaveWriter::new(save_path);
 
     let emulator_config = config.emulator_config;
-    let emulator = GenesisEmulator::create(rom, emulator_config, &mut save_writer);
+    let mut emulator = GenesisEmulator::create(rom, emulator_config, &mut save_writer);
 
-    let mut cartridge_title = emulator.cartridge_title();
-    // Remove non-printable characters
-    cartridge_title.retain(|c| {
-        c.is_ascii_alphanumeric() || c.is_ascii_whitespace() || c.is_ascii_punctuation()
-    });
+    let link_cable = LinkCable::open_if_configured(config.link_cable.as_ref())?;
+    emulator.set_link_cable_connected(link_cable.is_some());
+
+    let cartridge_title = game_title(&mut emulator, rom_path)?;
     let window_title = format!("genesis - {cartridge_title}");
 
-    NativeGenesisEmulator::new(
+    let mut emulator = NativeGenesisEmulator::new(
         emulator,
         emulator_config,
         config.common,
@@ -162,7 +339,15 @@ pub fn create_genesis(config: Box<GenesisConfig>) -> NativeEmulatorResult<Native
         &config.inputs.to_mapping_vec(),
         GenesisInputs::default(),
         debug::genesis::render_fn,
-    )
+    )?;
+
+    emulator.discord_presence.set_game("Genesis", cartridge_title);
+
+    if let Some(link_cable) = link_cable {
+        emulator.link_cable = Some(Box::new(link_cable));
+    }
+
+    Ok(emulator)
 }
 
 /// Create an emulator with the Sega CD core with the given config.
@@ -201,18 +386,21 @@ pub fn create_sega_cd(config: Box<SegaCdConfig>) -> NativeEmulatorResult<NativeS
     })?;
 
     let emulator_config = config.emulator_config;
-    let emulator = SegaCdEmulator::create(
+    let load_progress = config.disc_load_progress.clone().unwrap_or_default();
+    let mut emulator = SegaCdEmulator::create_with_progress(
         bios,
         rom_path,
         rom_format,
         config.run_without_disc,
         emulator_config,
         &mut save_writer,
+        &load_progress,
     )?;
 
-    let window_title = format!("sega cd - {}", emulator.disc_title());
+    let disc_title = emulator.game_metadata().title.unwrap_or_default();
+    let window_title = format!("sega cd - {disc_title}");
 
-    NativeSegaCdEmulator::new(
+    let mut emulator = NativeSegaCdEmulator::new(
         emulator,
         emulator_config,
         config.genesis.common,
@@ -224,7 +412,15 @@ pub fn create_sega_cd(config: Box<SegaCdConfig>) -> NativeEmulatorResult<NativeS
         &config.genesis.inputs.to_mapping_vec(),
         GenesisInputs::default(),
         debug::genesis::render_fn,
-    )
+    )?;
+
+    emulator.discord_presence.set_game("Sega CD", disc_title);
+
+    if config.fast_boot {
+        emulator.enable_fast_boot();
+    }
+
+    Ok(emulator)
 }
 
 /// Create an emulator with the 32X core with the given config.
@@ -249,13 +445,13 @@ pub fn create_32x(config: Box<Sega32XConfig>) -> NativeEmulatorResult<Native32XE
     let mut save_writer = FsSaveWriter::new(save_path);
 
     let emulator_config = config.emulator_config;
-    let emulator =
+    let mut emulator =
         Sega32XEmulator::create(rom.into_boxed_slice(), emulator_config, &mut save_writer);
 
-    let cartridge_title = emulator.cartridge_title();
+    let cartridge_title = game_title(&mut emulator, rom_path)?;
     let window_title = format!("32x - {cartridge_title}");
 
-    Native32XEmulator::new(
+    let mut emulator = Native32XEmulator::new(
         emulator,
         emulator_config,
         config.genesis.common,
@@ -267,5 +463,15 @@ pub fn create_32x(config: Box<Sega32XConfig>) -> NativeEmulatorResult<Native32XE
         &config.genesis.inputs.to_mapping_vec(),
         GenesisInputs::default(),
         debug::genesis::render_fn,
-    )
+    )?;
+
+    emulator.discord_presence.set_game("32X", cartridge_title);
+
+    if let Some(port) = config.gdb_port {
+        let gdb_server = GdbServer::bind(port)
+            .map_err(|source| NativeEmulatorError::GdbBind { port, source })?;
+        emulator.gdb_server = Some(Box::new(gdb_server));
+    }
+
+    Ok(emulator)
 }
@@ -0,0 +1,96 @@
+//! Formatting for the on-screen input viewer
+
+use crate::config::InputViewerSkin;
+use jgenesis_common::frontend::MappableInputs;
+use jgenesis_common::input::Player;
+use std::fmt::Debug;
+
+/// Format the current button states for every player, one line per player. Returns `None` if
+/// there are no buttons to display.
+pub(super) fn format_inputs<Button: Debug>(
+    inputs: &impl MappableInputs<Button>,
+    skin: InputViewerSkin,
+) -> Option<String> {
+    let lines: Vec<_> = [(Player::One, "P1"), (Player::Two, "P2")]
+        .into_iter()
+        .filter_map(|(player, label)| {
+            let states = inputs.button_states(player);
+            (!states.is_empty()).then(|| format!("{label} {}", format_player(&states, skin)))
+        })
+        .collect();
+
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn format_player<Button: Debug>(states: &[(Button, bool)], skin: InputViewerSkin) -> String {
+    match skin {
+        InputViewerSkin::Compact => {
+            let names: Vec<_> = states.iter().map(|(button, _)| format!("{button:?}")).collect();
+            compact_labels(&names)
+                .into_iter()
+                .zip(states)
+                .map(|(label, (_, pressed))| if *pressed { label } else { '.' })
+                .collect()
+        }
+        InputViewerSkin::Names => states
+            .iter()
+            .filter(|(_, pressed)| *pressed)
+            .map(|(button, _)| format!("{button:?}"))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+// One distinct character per button. D-pad directions use arrow-like symbols so that they do not
+// collide with L/R shoulder buttons, numbered buttons use their number, and other buttons use the
+// first letter of their name that is not already taken (e.g. Start = S, Select = s)
+fn compact_labels(names: &[String]) -> Vec<char> {
+    let mut labels = Vec::with_capacity(names.len());
+    for name in names {
+        let preferred = match name.as_str() {
+            "Up" => Some('^'),
+            "Down" => Some('v'),
+            "Left" => Some('<'),
+            "Right" => Some('>'),
+            _ => name.chars().last().filter(char::is_ascii_digit),
+        };
+
+        let letters = name.chars().filter(char::is_ascii_alphanumeric);
+        let candidates = preferred
+            .into_iter()
+            .chain(name.chars().take(1))
+            .chain(letters.flat_map(|c| [c.to_ascii_lowercase(), c.to_ascii_uppercase()]));
+        let label = candidates.filter(|&c| c != '.').find(|c| !labels.contains(c)).unwrap_or('?');
+        labels.push(label);
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(names: &[&str]) -> String {
+        let names: Vec<_> = names.iter().map(|&name| name.to_string()).collect();
+        compact_labels(&names).into_iter().collect()
+    }
+
+    #[test]
+    fn compact_labels_are_distinct() {
+        assert_eq!(
+            labels(&["Up", "Left", "Right", "Down", "A", "B", "Start", "Select"]),
+            "^<>vABSs"
+        );
+        assert_eq!(
+            labels(&[
+                "Up", "Left", "Right", "Down", "A", "B", "X", "Y", "L", "R", "Start", "Select"
+            ]),
+            "^<>vABXYLRSs"
+        );
+        assert_eq!(
+            labels(&["Up", "Left", "Right", "Down", "Button1", "Button2", "Pause"]),
+            "^<>v12P"
+        );
+        assert_eq!(labels(&["ZapperFire", "ZapperForceOffscreen"]), "Zz");
+    }
+}
@@ -0,0 +1,305 @@
+//! Link cable emulation between two emulator instances, bridged over a TCP socket. Used for the
+//! Genesis controller port 2 link cable and the Game Gear Gear-to-Gear cable.
+//!
+//! Each message is 2 bytes: a message type followed by a value. Pin messages are sent whenever
+//! the output pin levels change, and serial messages are sent for every byte transmitted in serial
+//! output mode.
+
+use crate::config::LinkCableMode;
+use crate::mainloop::{NativeEmulatorError, NativeEmulatorResult};
+use genesis_core::GenesisEmulator;
+use smsgg_core::SmsGgEmulator;
+use std::io;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+
+const MSG_PINS: u8 = 0;
+const MSG_SERIAL_BYTE: u8 = 1;
+
+// Checking the socket on every CPU instruction is very expensive, and the other console can't
+// respond that quickly anyway
+const READ_POLL_INTERVAL: u32 = 64;
+
+pub trait LinkCableBridge<Emulator> {
+    /// Exchange pin levels and serial bytes with the other console. Should be called after every
+    /// emulator tick.
+    fn exchange(&mut self, emulator: &mut Emulator);
+}
+
+// Emulator side of a link cable
+pub trait LinkPort {
+    fn output_pins(&self) -> u8;
+
+    fn set_input_pins(&mut self, pins: u8);
+
+    fn take_tx_byte(&mut self) -> Option<u8>;
+
+    fn receive_byte(&mut self, byte: u8);
+}
+
+macro_rules! impl_link_port {
+    ($emulator:ty) => {
+        impl LinkPort for $emulator {
+            fn output_pins(&self) -> u8 {
+                self.link_cable_output_pins()
+            }
+
+            fn set_input_pins(&mut self, pins: u8) {
+                self.set_link_cable_input_pins(pins);
+            }
+
+            fn take_tx_byte(&mut self) -> Option<u8> {
+                self.take_link_cable_tx_byte()
+            }
+
+            fn receive_byte(&mut self, byte: u8) {
+                self.receive_link_cable_byte(byte);
+            }
+        }
+    };
+}
+
+impl_link_port!(GenesisEmulator);
+impl_link_port!(SmsGgEmulator);
+
+enum Connection {
+    Listening,
+    Connected(TcpStream),
+    Closed,
+}
+
+pub struct LinkCable {
+    // Kept open in listen mode so that the other instance can reconnect after a disconnect
+    listener: Option<TcpListener>,
+    connection: Connection,
+    last_sent_pins: Option<u8>,
+    ticks_since_poll: u32,
+    read_buffer: Vec<u8>,
+    // Messages that could not be written yet because the socket's send buffer was full
+    write_buffer: Vec<u8>,
+}
+
+impl LinkCable {
+    /// Open a link cable in the given mode. When listening, the other emulator instance can
+    /// connect at any time; until then, the link cable behaves as if nothing is plugged into the
+    /// other end.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if unable to bind the listening socket or unable to connect to the other
+    /// emulator instance.
+    pub fn open(mode: &LinkCableMode) -> io::Result<Self> {
+        let (listener, connection) = match mode {
+            LinkCableMode::Listen(port) => {
+                let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, *port))?;
+                listener.set_nonblocking(true)?;
+                log::info!("Waiting for link cable connection on localhost port {port}");
+                (Some(listener), Connection::Listening)
+            }
+            LinkCableMode::Connect(address) => {
+                let stream = TcpStream::connect(address.as_str())?;
+                init_stream(&stream)?;
+                log::info!("Link cable connected to {address}");
+                (None, Connection::Connected(stream))
+            }
+        };
+
+        Ok(Self {
+            listener,
+            connection,
+            last_sent_pins: None,
+            ticks_since_poll: 0,
+            read_buffer: Vec::new(),
+            write_buffer: Vec::new(),
+        })
+    }
+
+    pub(super) fn open_if_configured(
+        mode: Option<&LinkCableMode>,
+    ) -> NativeEmulatorResult<Option<Self>> {
+        mode.map(|mode| {
+            Self::open(mode)
+                .map_err(|source| NativeEmulatorError::LinkCableOpen { mode: mode.clone(), source })
+        })
+        .transpose()
+    }
+
+    fn poll_accept(&mut self) {
+        let (Connection::Listening, Some(listener)) = (&self.connection, &self.listener) else {
+            return;
+        };
+
+        match listener.accept() {
+            Ok((stream, address)) => {
+                if let Err(err) = init_stream(&stream) {
+                    log::error!("Error initializing link cable connection from {address}: {err}");
+                    return;
+                }
+
+                log::info!("Link cable connection accepted from {address}");
+                self.connection = Connection::Connected(stream);
+                self.last_sent_pins = None;
+                self.read_buffer.clear();
+                self.write_buffer.clear();
+            }
+            Err(err) if err.kind() == ErrorKind::WouldBlock => {}
+            Err(err) => log::error!("Error accepting link cable connection: {err}"),
+        }
+    }
+
+    // Listen mode goes back to waiting for a connection; connect mode does not retry
+    fn disconnect(&mut self) {
+        self.connection =
+            if self.listener.is_some() { Connection::Listening } else { Connection::Closed };
+        self.last_sent_pins = None;
+        self.read_buffer.clear();
+        self.write_buffer.clear();
+
+        if self.listener.is_some() {
+            log::info!("Waiting for link cable to reconnect");
+        }
+    }
+
+    fn send(&mut self, message: [u8; 2]) {
+        if !matches!(self.connection, Connection::Connected(_)) {
+            return;
+        }
+
+        self.write_buffer.extend_from_slice(&message);
+        self.flush();
+    }
+
+    // Writes as much buffered output as the socket will accept without blocking; anything left over
+    // is retried on the next send or poll
+    fn flush(&mut self) {
+        let Connection::Connected(stream) = &mut self.connection else { return };
+
+        while !self.write_buffer.is_empty() {
+            match stream.write(&self.write_buffer) {
+                Ok(0) => {
+                    log::info!("Link cable disconnected");
+                    self.disconnect();
+                    return;
+                }
+                Ok(len) => {
+                    self.write_buffer.drain(..len);
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => return,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => {
+                    log::error!("Link cable disconnected: {err}");
+                    self.disconnect();
+                    return;
+                }
+            }
+        }
+    }
+
+    fn receive(&mut self) -> Vec<[u8; 2]> {
+        let Connection::Connected(stream) = &mut self.connection else { return vec![] };
+
+        let mut buffer = [0; 256];
+        loop {
+            match stream.read(&mut buffer) {
+                Ok(0) => {
+                    log::info!("Link cable disconnected");
+                    self.disconnect();
+                    break;
+                }
+                Ok(len) => self.read_buffer.extend_from_slice(&buffer[..len]),
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => {
+                    log::error!("Link cable disconnected: {err}");
+                    self.disconnect();
+                    break;
+                }
+            }
+        }
+
+        // Leave any partial message in the buffer until the rest of it arrives
+        let complete_len = self.read_buffer.len() & !1;
+        self.read_buffer
+            .drain(..complete_len)
+            .as_slice()
+            .chunks_exact(2)
+            .map(|chunk| [chunk[0], chunk[1]])
+            .collect()
+    }
+}
+
+fn init_stream(stream: &TcpStream) -> io::Result<()> {
+    // Pin changes are tiny and latency-sensitive
+    stream.set_nodelay(true)?;
+    stream.set_nonblocking(true)
+}
+
+impl<Emulator: LinkPort> LinkCableBridge<Emulator> for LinkCable {
+    fn exchange(&mut self, emulator: &mut Emulator) {
+        self.ticks_since_poll += 1;
+        if self.ticks_since_poll >= READ_POLL_INTERVAL {
+            self.ticks_since_poll = 0;
+
+            self.poll_accept();
+            self.flush();
+            for [msg_type, value] in self.receive() {
+                match msg_type {
+                    MSG_PINS => emulator.set_input_pins(value),
+                    MSG_SERIAL_BYTE => emulator.receive_byte(value),
+                    _ => log::warn!("Ignoring unknown link cable message type {msg_type}"),
+                }
+            }
+        }
+
+        if !matches!(self.connection, Connection::Connected(_)) {
+            // Nothing is driving the other end of the cable; input pins are pulled high
+            emulator.set_input_pins(0x7F);
+            emulator.take_tx_byte();
+            return;
+        }
+
+        let pins = emulator.output_pins();
+        if self.last_sent_pins != Some(pins) {
+            self.last_sent_pins = Some(pins);
+            self.send([MSG_PINS, pins]);
+        }
+
+        if let Some(byte) = emulator.take_tx_byte() {
+            self.send([MSG_SERIAL_BYTE, byte]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    fn poll_until(cable: &mut LinkCable, f: impl Fn(&Connection) -> bool) {
+        for _ in 0..100 {
+            cable.poll_accept();
+            cable.receive();
+            if f(&cable.connection) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("Link cable did not reach expected state");
+    }
+
+    #[test]
+    fn listener_accepts_again_after_disconnect() {
+        let mut cable = LinkCable::open(&LinkCableMode::Listen(0)).unwrap();
+        let address = cable.listener.as_ref().unwrap().local_addr().unwrap();
+
+        let client = TcpStream::connect(address).unwrap();
+        poll_until(&mut cable, |connection| matches!(connection, Connection::Connected(_)));
+
+        drop(client);
+        poll_until(&mut cable, |connection| matches!(connection, Connection::Listening));
+
+        let _client = TcpStream::connect(address).unwrap();
+        poll_until(&mut cable, |connection| matches!(connection, Connection::Connected(_)));
+    }
+}
@@ -1,9 +1,10 @@
 use crate::config::NesConfig;
+use crate::config::SystemConfig;
 
+use crate::mainloop::emucore::{self, NativeSystem};
 use crate::mainloop::save::{DeterminedPaths, FsSaveWriter};
 use crate::mainloop::{debug, file_name_no_ext, save};
 use crate::{AudioError, NativeEmulator, NativeEmulatorResult, config, extensions};
-use jgenesis_common::frontend::EmulatorTrait;
 
 use nes_core::api::NesEmulator;
 use nes_core::input::{NesInputDevice, NesInputs, NesJoypadState, ZapperState};
@@ -34,24 +35,32 @@ impl NativeNesEmulator {
     pub fn reload_nes_config(&mut self, config: Box<NesConfig>) -> Result<(), AudioError> {
         log::info!("Reloading config: {config}");
 
-        self.reload_common_config(&config.common)?;
-
-        self.update_emulator_config(&config.emulator_config);
-
-        // Config change could have changed target framerate (50/60 Hz hack)
-        self.renderer.set_target_fps(self.emulator.target_fps());
-
-        self.input_mapper.update_mappings(
-            config.common.axis_deadzone,
+        self.reload_shared_config(
+            &config.common,
+            &config.emulator_config,
             &config.inputs.to_mapping_vec(),
-            &config.common.hotkey_config.to_mapping_vec(),
-        );
+        )?;
         self.input_mapper.inputs_mut().p2 = config.inputs.p2_type.to_input_device();
 
         Ok(())
     }
 }
 
+impl NativeSystem for NesEmulator {
+    fn reload_system_config(
+        emulator: &mut NativeEmulator<Self>,
+        config: SystemConfig,
+    ) -> Result<(), AudioError> {
+        match config {
+            SystemConfig::Nes(config) => emulator.reload_nes_config(config),
+            _ => {
+                emucore::log_mismatched_config("NES", &config);
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Create an emulator with the NES core with the given config.
 ///
 /// # Errors
@@ -81,7 +90,7 @@ pub fn create_nes(config: Box<NesConfig>) -> NativeEmulatorResult<NativeNesEmula
     let initial_inputs =
         NesInputs { p1: NesJoypadState::default(), p2: config.inputs.p2_type.to_input_device() };
 
-    NativeNesEmulator::new(
+    let mut emulator = NativeNesEmulator::new(
         emulator,
         emulator_config,
         config.common,
@@ -93,5 +102,9 @@ pub fn create_nes(config: Box<NesConfig>) -> NativeEmulatorResult<NativeNesEmula
         &config.inputs.to_mapping_vec(),
         initial_inputs,
         debug::nes::render_fn,
-    )
+    )?;
+
+    emulator.discord_presence.set_game("NES", rom_title);
+
+    Ok(emulator)
 }
@@ -0,0 +1,147 @@
+//! Discord Rich Presence integration, which shows the current system and game in the user's Discord
+//! status.
+//!
+//! Discord is contacted over its local IPC socket from a background thread so that a missing or
+//! unresponsive Discord client never stalls emulation. Nothing is sent anywhere while disabled.
+
+use discord_rich_presence::activity::{Activity, Timestamps};
+use discord_rich_presence::{DiscordIpc, DiscordIpcClient};
+use std::sync::mpsc;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, thread};
+
+// Discord application that presence updates are attributed to. Discord requires every presence
+// update to come from a registered application, so this can be set either when building or at
+// runtime; the runtime value takes priority
+const APPLICATION_ID_VAR: &str = "JGENESIS_DISCORD_APPLICATION_ID";
+const BUILD_APPLICATION_ID: Option<&str> = option_env!("JGENESIS_DISCORD_APPLICATION_ID");
+
+fn application_id() -> Option<String> {
+    env::var(APPLICATION_ID_VAR)
+        .ok()
+        .or_else(|| BUILD_APPLICATION_ID.map(String::from))
+        .filter(|id| !id.is_empty())
+}
+
+/// Whether Discord Rich Presence can be enabled, i.e. whether a Discord application ID was set when
+/// building or through the `JGENESIS_DISCORD_APPLICATION_ID` environment variable.
+#[must_use]
+pub fn discord_presence_available() -> bool {
+    application_id().is_some()
+}
+
+// How often to try connecting again if Discord is not running
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone)]
+struct GameInfo {
+    system: &'static str,
+    title: String,
+}
+
+pub(crate) struct DiscordPresence {
+    game: Option<GameInfo>,
+    start_time_ms: i64,
+    worker: Option<Sender<GameInfo>>,
+}
+
+impl DiscordPresence {
+    pub(crate) fn new(enabled: bool) -> Self {
+        let start_time_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as i64);
+
+        let mut presence = Self { game: None, start_time_ms, worker: None };
+        presence.set_enabled(enabled);
+        presence
+    }
+
+    /// Start or stop updating the Discord status. Stopping clears the status.
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            // Worker thread clears the activity and exits once the channel is closed
+            self.worker = None;
+            return;
+        }
+
+        if self.worker.is_some() {
+            return;
+        }
+
+        let Some(application_id) = application_id() else {
+            log::warn!(
+                "Discord Rich Presence is enabled but no Discord application ID is configured; set the {APPLICATION_ID_VAR} environment variable to use it"
+            );
+            return;
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let start_time_ms = self.start_time_ms;
+        thread::spawn(move || run_worker(&application_id, start_time_ms, &rx));
+
+        if let Some(game) = &self.game {
+            let _ = tx.send(game.clone());
+        }
+        self.worker = Some(tx);
+    }
+
+    /// Update the system and game shown in the Discord status.
+    pub(crate) fn set_game(&mut self, system: &'static str, title: impl Into<String>) {
+        let game = GameInfo { system, title: title.into() };
+
+        if let Some(worker) = &self.worker {
+            let _ = worker.send(game.clone());
+        }
+        self.game = Some(game);
+    }
+}
+
+fn run_worker(application_id: &str, start_time_ms: i64, updates: &Receiver<GameInfo>) {
+    let mut client = DiscordIpcClient::new(application_id);
+    let mut connected = false;
+    let mut game: Option<GameInfo> = None;
+    let mut needs_update = false;
+
+    loop {
+        match updates.recv_timeout(RECONNECT_INTERVAL) {
+            Ok(update) => {
+                game = Some(update);
+                needs_update = true;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        if !connected {
+            if let Err(err) = client.connect() {
+                log::debug!("Unable to connect to Discord: {err}");
+                continue;
+            }
+
+            log::info!("Connected to Discord for Rich Presence");
+            connected = true;
+            needs_update = true;
+        }
+
+        let Some(game) = game.as_ref().filter(|_| needs_update) else { continue };
+
+        let activity = Activity::new()
+            .details(&game.title)
+            .state(game.system)
+            .timestamps(Timestamps::new().start(start_time_ms));
+        match client.set_activity(activity) {
+            Ok(()) => needs_update = false,
+            Err(err) => {
+                // Most likely Discord was closed; try to reconnect on the next interval
+                log::debug!("Error updating Discord Rich Presence: {err}");
+                connected = false;
+            }
+        }
+    }
+
+    if connected {
+        let _ = client.clear_activity();
+        let _ = client.close();
+    }
+}
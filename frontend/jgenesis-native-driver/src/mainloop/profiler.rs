@@ -0,0 +1,345 @@
+//! A minimal `tracing` subscriber that aggregates profiling spans into a per-frame call tree, plus
+//! an egui flamegraph viewer for the most recently completed frame.
+//!
+//! Only compiled with the `profiling` feature.
+
+use egui::{Align2, Color32, FontId, Painter, Pos2, Rect, Sense, Vec2, Window, pos2, vec2};
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Metadata, Subscriber};
+
+const ROOT: usize = 0;
+
+#[derive(Debug, Clone)]
+struct Node {
+    name: &'static str,
+    children: Vec<usize>,
+    total: Duration,
+    calls: u64,
+}
+
+impl Node {
+    fn new(name: &'static str) -> Self {
+        Self { name, children: Vec::new(), total: Duration::ZERO, calls: 0 }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    name: &'static str,
+    ref_count: u64,
+}
+
+// Span IDs must be unique among all open spans, including spans on other threads
+static NEXT_SPAN_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug)]
+struct ProfilerState {
+    spans: FxHashMap<u64, Span>,
+    nodes: Vec<Node>,
+    // (span ID, node index, enter time)
+    stack: Vec<(u64, usize, Instant)>,
+    last_frame: Vec<Node>,
+    paused: bool,
+}
+
+impl ProfilerState {
+    fn new() -> Self {
+        Self {
+            spans: FxHashMap::default(),
+            nodes: vec![Node::new("root")],
+            stack: Vec::new(),
+            last_frame: Vec::new(),
+            paused: false,
+        }
+    }
+
+    fn new_span(&mut self, name: &'static str) -> u64 {
+        let id = NEXT_SPAN_ID.fetch_add(1, Ordering::Relaxed);
+        self.spans.insert(id, Span { name, ref_count: 1 });
+        id
+    }
+
+    fn clone_span(&mut self, id: &Id) {
+        if let Some(span) = self.spans.get_mut(&id.into_u64()) {
+            span.ref_count += 1;
+        }
+    }
+
+    fn try_close(&mut self, id: &Id) -> bool {
+        let Some(span) = self.spans.get_mut(&id.into_u64()) else { return false };
+
+        span.ref_count -= 1;
+        if span.ref_count != 0 {
+            return false;
+        }
+
+        self.spans.remove(&id.into_u64());
+        true
+    }
+
+    fn enter(&mut self, id: &Id) {
+        // Spans created on a different thread are not profiled
+        let Some(&Span { name, .. }) = self.spans.get(&id.into_u64()) else { return };
+
+        let parent = self.stack.last().map_or(ROOT, |&(_, idx, _)| idx);
+        let existing = self.nodes[parent]
+            .children
+            .iter()
+            .copied()
+            .find(|&child| self.nodes[child].name == name);
+        let idx = existing.unwrap_or_else(|| {
+            self.nodes.push(Node::new(name));
+            let idx = self.nodes.len() - 1;
+            self.nodes[parent].children.push(idx);
+            idx
+        });
+
+        self.stack.push((id.into_u64(), idx, Instant::now()));
+    }
+
+    fn exit(&mut self, id: &Id) {
+        let Some(pos) = self.stack.iter().rposition(|&(span_id, ..)| span_id == id.into_u64())
+        else {
+            return;
+        };
+        let (_, idx, start) = self.stack.remove(pos);
+
+        let node = &mut self.nodes[idx];
+        node.total += start.elapsed();
+        node.calls += 1;
+    }
+
+    fn finish_frame(&mut self) {
+        // Spans that are still open will be attributed to the next frame
+        if !self.stack.is_empty() {
+            return;
+        }
+
+        let root_total =
+            self.nodes[ROOT].children.iter().map(|&child| self.nodes[child].total).sum();
+        self.nodes[ROOT].total = root_total;
+
+        if self.paused {
+            self.nodes.truncate(1);
+            self.nodes[ROOT] = Node::new("root");
+        } else {
+            self.last_frame = std::mem::replace(&mut self.nodes, vec![Node::new("root")]);
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<ProfilerState> = RefCell::new(ProfilerState::new());
+}
+
+/// Subscriber that feeds spans into the current thread's frame profile.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameProfiler;
+
+impl FrameProfiler {
+    /// Install the profiler as the global default `tracing` subscriber. Does nothing if a global
+    /// subscriber has already been installed.
+    pub fn install() {
+        if tracing::subscriber::set_global_default(Self).is_err() {
+            log::debug!("Global tracing subscriber already set; not installing frame profiler");
+        }
+    }
+}
+
+impl Subscriber for FrameProfiler {
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        Interest::always()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.is_span()
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let id = STATE.with_borrow_mut(|state| state.new_span(span.metadata().name()));
+        Id::from_u64(id)
+    }
+
+    fn clone_span(&self, id: &Id) -> Id {
+        STATE.with_borrow_mut(|state| state.clone_span(id));
+        id.clone()
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        STATE.with_borrow_mut(|state| state.try_close(&id))
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        STATE.with_borrow_mut(|state| state.enter(span));
+    }
+
+    fn exit(&self, span: &Id) {
+        STATE.with_borrow_mut(|state| state.exit(span));
+    }
+}
+
+/// Mark the end of a frame on the current thread. The aggregated spans since the previous call
+/// become the profile displayed by [`render_window`].
+pub fn finish_frame() {
+    STATE.with_borrow_mut(ProfilerState::finish_frame);
+}
+
+const ROW_HEIGHT: f32 = 20.0;
+const MIN_LABEL_WIDTH: f32 = 30.0;
+
+/// Render the flamegraph window for the current thread's most recent frame profile.
+pub fn render_window(ctx: &egui::Context) {
+    Window::new("Profiler").default_width(600.0).show(ctx, |ui| {
+        let nodes = STATE.with_borrow_mut(|state| {
+            ui.checkbox(&mut state.paused, "Pause");
+            state.last_frame.clone()
+        });
+
+        let Some(root) = nodes.first() else {
+            ui.label("No frames profiled yet");
+            return;
+        };
+
+        ui.label(format!("Frame time: {:.3} ms", root.total.as_secs_f64() * 1000.0));
+
+        let depth = max_depth(&nodes, ROOT);
+        let (response, painter) = ui.allocate_painter(
+            vec2(ui.available_width(), ROW_HEIGHT * depth as f32),
+            Sense::hover(),
+        );
+
+        let mut hovered = None;
+        let hover_pos = response.hover_pos();
+        let mut x = response.rect.left();
+        for &child in &root.children {
+            let width = response.rect.width() * fraction(&nodes[child], root);
+            draw_node(
+                &painter,
+                &nodes,
+                child,
+                pos2(x, response.rect.top()),
+                width,
+                hover_pos,
+                &mut hovered,
+            );
+            x += width;
+        }
+
+        if let Some(idx) = hovered {
+            let node = &nodes[idx];
+            response.on_hover_text_at_pointer(format!(
+                "{}\n{:.3} ms ({:.1}% of frame)\n{} calls",
+                node.name,
+                node.total.as_secs_f64() * 1000.0,
+                100.0 * fraction(node, root),
+                node.calls
+            ));
+        }
+    });
+}
+
+fn max_depth(nodes: &[Node], idx: usize) -> usize {
+    nodes[idx].children.iter().map(|&child| 1 + max_depth(nodes, child)).max().unwrap_or(0)
+}
+
+fn fraction(node: &Node, parent: &Node) -> f32 {
+    if parent.total.is_zero() {
+        return 0.0;
+    }
+
+    (node.total.as_secs_f64() / parent.total.as_secs_f64()) as f32
+}
+
+fn draw_node(
+    painter: &Painter,
+    nodes: &[Node],
+    idx: usize,
+    top_left: Pos2,
+    width: f32,
+    hover_pos: Option<Pos2>,
+    hovered: &mut Option<usize>,
+) {
+    let node = &nodes[idx];
+    let rect = Rect::from_min_size(top_left, vec2(width, ROW_HEIGHT));
+
+    painter.rect_filled(rect.shrink(0.5), 1.0, name_color(node.name));
+    if width >= MIN_LABEL_WIDTH {
+        painter.with_clip_rect(rect.intersect(painter.clip_rect())).text(
+            rect.left_center() + Vec2::new(3.0, 0.0),
+            Align2::LEFT_CENTER,
+            node.name,
+            FontId::monospace(12.0),
+            Color32::BLACK,
+        );
+    }
+
+    if hover_pos.is_some_and(|pos| rect.contains(pos)) {
+        *hovered = Some(idx);
+    }
+
+    let mut x = top_left.x;
+    for &child in &node.children {
+        let child_width = width * fraction(&nodes[child], node);
+        draw_node(
+            painter,
+            nodes,
+            child,
+            pos2(x, top_left.y + ROW_HEIGHT),
+            child_width,
+            hover_pos,
+            hovered,
+        );
+        x += child_width;
+    }
+}
+
+// Stable color per span name so that the same span is easy to follow across frames
+fn name_color(name: &str) -> Color32 {
+    let hash = name.bytes().fold(0_u32, |hash, b| hash.wrapping_mul(31).wrapping_add(b.into()));
+    let r = 200 + (hash % 56) as u8;
+    let g = 80 + ((hash >> 8) % 120) as u8;
+    let b = 40 + ((hash >> 16) % 40) as u8;
+    Color32::from_rgb(r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_spans_from_same_callsite() {
+        let mut state = ProfilerState::new();
+
+        let outer = Id::from_u64(state.new_span("step"));
+        let inner = Id::from_u64(state.new_span("step"));
+        assert_ne!(outer, inner);
+
+        state.enter(&outer);
+        state.enter(&inner);
+        state.exit(&inner);
+        state.exit(&outer);
+        state.finish_frame();
+
+        // root -> step -> step
+        assert_eq!(state.last_frame.len(), 3);
+        assert_eq!(state.last_frame[1].children, vec![2]);
+        assert_eq!(state.last_frame[2].calls, 1);
+
+        state.clone_span(&inner);
+        assert!(!state.try_close(&inner));
+        assert!(state.try_close(&inner));
+        assert!(state.try_close(&outer));
+        assert!(state.spans.is_empty());
+    }
+}
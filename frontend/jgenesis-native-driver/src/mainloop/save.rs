@@ -81,7 +81,9 @@ impl FsSaveWriter {
         // Double get necessary to avoid borrow checker issues related to returning a reference
         if !self.extension_to_paths.contains_key(extension) {
             let path = self.base_path.with_extension(extension);
-            let temp_path = self.base_path.with_extension(format!("{extension}.tmp"));
+            // Temp files are per-process so that concurrent instances playing the same game never
+            // write to the same temp file
+            let temp_path = jgenesis_common::files::process_temp_path(&path);
             self.extension_to_paths.insert(extension.into(), SavePaths { path, temp_path });
         }
 
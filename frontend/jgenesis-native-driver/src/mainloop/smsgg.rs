@@ -1,14 +1,40 @@
+use crate::config::CommonConfig;
 use crate::config::SmsGgConfig;
+use crate::config::SystemConfig;
 
+use crate::mainloop::emucore::{self, NativeSystem};
+use crate::mainloop::link::LinkCable;
 use crate::mainloop::save::{DeterminedPaths, FsSaveWriter};
 use crate::mainloop::{debug, file_name_no_ext, save};
 use crate::{AudioError, NativeEmulator, NativeEmulatorResult, config, extensions};
-use jgenesis_common::frontend::EmulatorTrait;
 
 use crate::config::RomReadResult;
-use smsgg_core::{SmsGgEmulator, SmsGgHardware, SmsGgInputs};
+use crate::config::input::SmsGgControllerType;
+use smsgg_core::{
+    PaddleState, SmsGgEmulator, SmsGgHardware, SmsGgInputs, SmsGgPeripheral, SportsPadState,
+};
+use std::mem;
 use std::path::Path;
 
+trait SmsGgControllerTypeExt {
+    fn to_peripheral(self, hardware: SmsGgHardware) -> SmsGgPeripheral;
+}
+
+impl SmsGgControllerTypeExt for SmsGgControllerType {
+    fn to_peripheral(self, hardware: SmsGgHardware) -> SmsGgPeripheral {
+        // The Game Gear has no controller ports
+        if hardware == SmsGgHardware::GameGear {
+            return SmsGgPeripheral::Controller;
+        }
+
+        match self {
+            Self::Gamepad => SmsGgPeripheral::Controller,
+            Self::Paddle => SmsGgPeripheral::Paddle(PaddleState::default()),
+            Self::SportsPad => SmsGgPeripheral::SportsPad(SportsPadState::default()),
+        }
+    }
+}
+
 pub type NativeSmsGgEmulator = NativeEmulator<SmsGgEmulator>;
 
 impl NativeSmsGgEmulator {
@@ -18,23 +44,41 @@ impl NativeSmsGgEmulator {
     pub fn reload_smsgg_config(&mut self, config: Box<SmsGgConfig>) -> Result<(), AudioError> {
         log::info!("Reloading config: {config}");
 
-        self.reload_common_config(&config.common)?;
+        let mut common_config = config.common;
+        disable_ntsc_filter_for_lcd(&mut common_config, self.emulator.hardware());
 
-        self.update_emulator_config(&config.emulator_config);
-
-        // Config change could have changed target framerate (NTSC vs. PAL)
-        self.renderer.set_target_fps(self.emulator.target_fps());
-
-        self.input_mapper.update_mappings(
-            config.common.axis_deadzone,
+        self.reload_shared_config(
+            &common_config,
+            &config.emulator_config,
             &config.inputs.to_mapping_vec(),
-            &config.common.hotkey_config.to_mapping_vec(),
-        );
+        )?;
+
+        // Only replace the peripheral if the type changed so that its position is not reset
+        let peripheral = &mut self.input_mapper.inputs_mut().p1_peripheral;
+        let new_peripheral = config.inputs.p1_type.to_peripheral(self.emulator.hardware());
+        if mem::discriminant(peripheral) != mem::discriminant(&new_peripheral) {
+            *peripheral = new_peripheral;
+        }
 
         Ok(())
     }
 }
 
+impl NativeSystem for SmsGgEmulator {
+    fn reload_system_config(
+        emulator: &mut NativeEmulator<Self>,
+        config: SystemConfig,
+    ) -> Result<(), AudioError> {
+        match config {
+            SystemConfig::SmsGg(config) => emulator.reload_smsgg_config(config),
+            _ => {
+                emucore::log_mismatched_config("SMS/GG", &config);
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Create an emulator with the SMS/GG core with the given config.
 ///
 /// # Errors
@@ -56,36 +100,74 @@ pub fn create_smsgg(config: Box<SmsGgConfig>) -> NativeEmulatorResult<NativeSmsG
 
     let mut save_writer = FsSaveWriter::new(save_path);
 
-    let hardware = hardware_for_ext(&extension);
+    let hardware = config.forced_hardware.unwrap_or_else(|| hardware_for_ext(&extension));
 
     let rom_title = file_name_no_ext(rom_path)?;
     let window_title = format!("smsgg - {rom_title}");
 
     let emulator_config = config.emulator_config;
-    let emulator = SmsGgEmulator::create(rom, hardware, emulator_config, &mut save_writer);
+    let mut emulator = SmsGgEmulator::create(rom, hardware, emulator_config, &mut save_writer);
 
-    NativeSmsGgEmulator::new(
+    let mut common_config = config.common;
+    disable_ntsc_filter_for_lcd(&mut common_config, hardware);
+
+    let link_cable = if hardware == SmsGgHardware::GameGear {
+        LinkCable::open_if_configured(config.link_cable.as_ref())?
+    } else {
+        if config.link_cable.is_some() {
+            log::warn!("Ignoring link cable config; link cables are only supported for Game Gear");
+        }
+        None
+    };
+    emulator.set_link_cable_connected(link_cable.is_some());
+
+    // SC-3000 software expects the built-in keyboard
+    emulator.set_keyboard_connected(extension.eq_ignore_ascii_case("sc"));
+
+    let mut emulator = NativeSmsGgEmulator::new(
         emulator,
         emulator_config,
-        config.common,
+        common_config,
         extension,
         config::default_smsgg_window_size(hardware, emulator_config.sms_timing_mode),
         &window_title,
         save_writer,
         save_state_path,
         &config.inputs.to_mapping_vec(),
-        SmsGgInputs::default(),
+        SmsGgInputs {
+            p1_peripheral: config.inputs.p1_type.to_peripheral(hardware),
+            ..SmsGgInputs::default()
+        },
         debug::smsgg::render_fn,
-    )
+    )?;
+
+    emulator.discord_presence.set_game(hardware_display_name(hardware), rom_title);
+
+    if let Some(link_cable) = link_cable {
+        emulator.link_cable = Some(Box::new(link_cable));
+    }
+
+    Ok(emulator)
 }
 
-fn hardware_for_ext(extension: &str) -> SmsGgHardware {
-    match extension.to_ascii_lowercase().as_str() {
-        "sms" => SmsGgHardware::MasterSystem,
-        "gg" => SmsGgHardware::GameGear,
-        _ => {
-            log::error!("Unrecognized file extension '{extension}', defaulting to SMS mode");
-            SmsGgHardware::MasterSystem
-        }
+pub(super) fn hardware_for_ext(extension: &str) -> SmsGgHardware {
+    SmsGgHardware::from_extension(extension).unwrap_or_else(|| {
+        log::error!("Unrecognized file extension '{extension}', defaulting to SMS mode");
+        SmsGgHardware::MasterSystem
+    })
+}
+
+// The Game Gear has a built-in LCD screen, so composite video artifacts do not apply
+fn disable_ntsc_filter_for_lcd(common_config: &mut CommonConfig, hardware: SmsGgHardware) {
+    if hardware == SmsGgHardware::GameGear {
+        common_config.renderer_config.ntsc_filter = false;
+    }
+}
+
+fn hardware_display_name(hardware: SmsGgHardware) -> &'static str {
+    match hardware {
+        SmsGgHardware::MasterSystem => "Master System",
+        SmsGgHardware::GameGear => "Game Gear",
+        SmsGgHardware::Sg1000 => "SG-1000",
     }
 }
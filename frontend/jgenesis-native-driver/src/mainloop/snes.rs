@@ -1,14 +1,18 @@
 use crate::config::SnesConfig;
+use crate::config::SystemConfig;
 
+use crate::mainloop::emucore::{self, NativeSystem};
 use crate::mainloop::save::{DeterminedPaths, FsSaveWriter};
-use crate::mainloop::{debug, save};
-use crate::{AudioError, NativeEmulator, NativeEmulatorResult, config, extensions};
-use jgenesis_common::frontend::EmulatorTrait;
+use crate::mainloop::{debug, game_title, save};
+use crate::{
+    AudioError, NativeEmulator, NativeEmulatorError, NativeEmulatorResult, config, extensions,
+};
 
 use crate::config::RomReadResult;
 use crate::config::input::SnesControllerType;
 use snes_core::api::SnesEmulator;
 use snes_core::input::{SnesInputDevice, SnesInputs, SnesJoypadState, SuperScopeState};
+use std::fs;
 use std::path::Path;
 
 trait SnesControllerTypeExt {
@@ -33,24 +37,32 @@ impl NativeSnesEmulator {
     pub fn reload_snes_config(&mut self, config: Box<SnesConfig>) -> Result<(), AudioError> {
         log::info!("Reloading config: {config}");
 
-        self.reload_common_config(&config.common)?;
-
-        self.update_emulator_config(&config.emulator_config);
-
-        // Config change could have changed target framerate (50/60 Hz hack)
-        self.renderer.set_target_fps(self.emulator.target_fps());
-
-        self.input_mapper.update_mappings(
-            config.common.axis_deadzone,
+        self.reload_shared_config(
+            &config.common,
+            &config.emulator_config,
             &config.inputs.to_mapping_vec(),
-            &config.common.hotkey_config.to_mapping_vec(),
-        );
+        )?;
         self.input_mapper.inputs_mut().p2 = config.inputs.p2_type.to_input_device();
 
         Ok(())
     }
 }
 
+impl NativeSystem for SnesEmulator {
+    fn reload_system_config(
+        emulator: &mut NativeEmulator<Self>,
+        config: SystemConfig,
+    ) -> Result<(), AudioError> {
+        match config {
+            SystemConfig::Snes(config) => emulator.reload_snes_config(config),
+            _ => {
+                emucore::log_mismatched_config("SNES", &config);
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Create an emulator with the SNES core with the given config.
 ///
 /// # Errors
@@ -60,7 +72,21 @@ pub fn create_snes(config: Box<SnesConfig>) -> NativeEmulatorResult<NativeSnesEm
     log::info!("Running with config: {config}");
 
     let rom_path = Path::new(&config.common.rom_file_path);
-    let RomReadResult { rom, extension } = config.common.read_rom_file(extensions::SNES)?;
+    let RomReadResult { rom, extension } =
+        config.common.read_rom_file(&extensions::SNES_SUPER_GAME_BOY)?;
+
+    let mut coprocessor_roms = config.to_coprocessor_roms();
+    let rom = if extensions::GB_GBC.contains(&extension.as_str()) {
+        // Game Boy ROM; run it in a Super Game Boy using the configured BIOS
+        log::info!("Running Game Boy ROM in Super Game Boy mode");
+
+        let bios = read_sgb_bios(&config)?;
+        coprocessor_roms.sgb_cartridge = Some(Box::new(move || Ok(rom.clone())));
+
+        bios
+    } else {
+        rom
+    };
 
     let DeterminedPaths { save_path, save_state_path } = save::determine_save_paths(
         &config.common.save_path,
@@ -72,17 +98,16 @@ pub fn create_snes(config: Box<SnesConfig>) -> NativeEmulatorResult<NativeSnesEm
     let mut save_writer = FsSaveWriter::new(save_path);
 
     let emulator_config = config.emulator_config;
-    let coprocessor_roms = config.to_coprocessor_roms();
     let mut emulator =
         SnesEmulator::create(rom, emulator_config, coprocessor_roms, &mut save_writer)?;
 
-    let cartridge_title = emulator.cartridge_title();
+    let cartridge_title = game_title(&mut emulator, rom_path)?;
     let window_title = format!("snes - {cartridge_title}");
 
     let initial_inputs =
         SnesInputs { p1: SnesJoypadState::default(), p2: config.inputs.p2_type.to_input_device() };
 
-    NativeSnesEmulator::new(
+    let mut emulator = NativeSnesEmulator::new(
         emulator,
         emulator_config,
         config.common,
@@ -94,5 +119,17 @@ pub fn create_snes(config: Box<SnesConfig>) -> NativeEmulatorResult<NativeSnesEm
         &config.inputs.to_mapping_vec(),
         initial_inputs,
         debug::snes::render_fn,
-    )
+    )?;
+
+    emulator.discord_presence.set_game("SNES", cartridge_title);
+
+    Ok(emulator)
+}
+
+fn read_sgb_bios(config: &SnesConfig) -> NativeEmulatorResult<Vec<u8>> {
+    let bios_path = config.sgb_bios_path.as_ref().ok_or(NativeEmulatorError::SuperGameBoyNoBios)?;
+    fs::read(bios_path).map_err(|source| NativeEmulatorError::SuperGameBoyBiosRead {
+        path: bios_path.clone(),
+        source,
+    })
 }
@@ -1,12 +1,17 @@
 use crate::NativeEmulatorResult;
 use crate::mainloop::{NativeEmulatorError, bincode_config};
-use jgenesis_common::frontend::EmulatorTrait;
+use jgenesis_common::frontend::{Color, EmulatorTrait, RgbaFrame};
+use jgenesis_common::hash;
+use segacd_core::CdRomFileFormat;
+use segacd_core::api::{SegaCdEmulator, SegaCdLoadError};
 use std::ffi::OsStr;
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Read, Write};
+use std::io::{BufReader, BufWriter, Cursor, Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::SystemTime;
-use std::{array, fs};
+use std::{array, fs, io};
+use thiserror::Error;
 
 pub const SAVE_STATE_SLOTS: usize = 10;
 pub const EXTENSION: &str = "jst";
@@ -16,6 +21,13 @@ const FILE_PREFIX: &[u8] = b"jgenstate";
 // Prefix + 2 bytes for version
 const HEADER_LEN: usize = FILE_PREFIX.len() + 2;
 
+// Optional block between the header and the emulator state: marker, 4-byte length, then a
+// zstd-compressed thumbnail. States saved before thumbnails were added do not have this block
+const THUMBNAIL_MARKER: [u8; 4] = *b"thmb";
+
+// Thumbnails are downscaled to this width before saving, preserving the frame's aspect ratio
+const THUMBNAIL_WIDTH: u32 = 160;
+
 pub type SaveStatePaths = [PathBuf; SAVE_STATE_SLOTS];
 
 pub fn init_paths(path: &Path) -> NativeEmulatorResult<[PathBuf; SAVE_STATE_SLOTS]> {
@@ -31,15 +43,52 @@ pub fn init_paths(path: &Path) -> NativeEmulatorResult<[PathBuf; SAVE_STATE_SLOT
     Ok(file_names.map(|name| path.with_file_name(name)))
 }
 
+/// Path of the state that is automatically saved on exit. The ROM hash is part of the file name so
+/// that a different ROM with the same file name never resumes from this state.
+pub fn auto_state_path(path: &Path, rom_hash: &str) -> NativeEmulatorResult<PathBuf> {
+    let path_no_ext = path.with_extension("");
+    let file_name = path_no_ext
+        .file_name()
+        .and_then(OsStr::to_str)
+        .ok_or_else(|| NativeEmulatorError::ParseFileName(path.display().to_string()))?;
+
+    Ok(path.with_file_name(format!("{file_name}_auto_{rom_hash}.{EXTENSION}")))
+}
+
+#[derive(Debug, Error)]
+pub enum RomHashError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Error reading disc: {0}")]
+    Disc(#[from] SegaCdLoadError),
+}
+
+pub fn rom_hash(rom_path: &Path) -> Result<String, RomHashError> {
+    // Discs are identified by the boot header at the start of the data track; a CUE file only lists
+    // the tracks, and CHD files are very large
+    let mut md5 = match CdRomFileFormat::from_file_path(rom_path) {
+        Some(format) => hash::md5_hex(&SegaCdEmulator::read_disc_boot_header(rom_path, format)?),
+        None => hash::file_md5_hex(rom_path)?,
+    };
+
+    // Half of the MD5 is plenty to tell ROMs with the same file name apart
+    md5.truncate(16);
+    Ok(md5)
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SaveStateMetadata {
     pub times_nanos: [Option<u128>; SAVE_STATE_SLOTS],
+    pub thumbnails: [Option<Arc<RgbaFrame>>; SAVE_STATE_SLOTS],
 }
 
 impl SaveStateMetadata {
     pub(crate) fn load(paths: &SaveStatePaths, version: u16) -> Self {
+        let valid: [bool; SAVE_STATE_SLOTS] =
+            array::from_fn(|i| read_version_from_header(&paths[i]) == Some(version));
+
         let times_nanos = array::from_fn(|i| {
-            if read_version_from_header(&paths[i]) != Some(version) {
+            if !valid[i] {
                 return None;
             }
 
@@ -48,7 +97,10 @@ impl SaveStateMetadata {
             Some(modified.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_nanos())
         });
 
-        Self { times_nanos }
+        let thumbnails =
+            array::from_fn(|i| valid[i].then(|| read_thumbnail(&paths[i]).map(Arc::new)).flatten());
+
+        Self { times_nanos, thumbnails }
     }
 }
 
@@ -69,42 +121,253 @@ fn read_version_from_buffer(buffer: &[u8]) -> u16 {
     u16::from_le_bytes([buffer[FILE_PREFIX.len()], buffer[FILE_PREFIX.len() + 1]])
 }
 
+enum ThumbnailBlock {
+    Compressed(Vec<u8>),
+    // The bytes that were read looking for the marker; these are the start of the emulator state
+    Missing { peeked: [u8; THUMBNAIL_MARKER.len()] },
+}
+
+fn read_thumbnail_block<R: Read>(reader: &mut R) -> io::Result<ThumbnailBlock> {
+    let mut marker = [0_u8; THUMBNAIL_MARKER.len()];
+    reader.read_exact(&mut marker)?;
+    if marker != THUMBNAIL_MARKER {
+        return Ok(ThumbnailBlock::Missing { peeked: marker });
+    }
+
+    let mut len_bytes = [0_u8; 4];
+    reader.read_exact(&mut len_bytes)?;
+    let len = u32::from_le_bytes(len_bytes);
+
+    let mut compressed = Vec::new();
+    reader.take(len.into()).read_to_end(&mut compressed)?;
+    if compressed.len() != len as usize {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    Ok(ThumbnailBlock::Compressed(compressed))
+}
+
+fn read_thumbnail(path: &Path) -> Option<RgbaFrame> {
+    let mut reader = BufReader::new(File::open(path).ok()?);
+
+    let mut header_buffer = [0_u8; HEADER_LEN];
+    reader.read_exact(&mut header_buffer).ok()?;
+
+    let ThumbnailBlock::Compressed(compressed) = read_thumbnail_block(&mut reader).ok()? else {
+        return None;
+    };
+
+    let encoded = zstd::decode_all(compressed.as_slice()).ok()?;
+    let (thumbnail, _): (RgbaFrame, _) =
+        bincode::decode_from_slice(&encoded, bincode_config!()).ok()?;
+
+    let expected_len = thumbnail.width as usize * thumbnail.height as usize;
+    (thumbnail.pixels.len() == expected_len).then_some(thumbnail)
+}
+
+fn capture_thumbnail<Emulator: EmulatorTrait>(emulator: &mut Emulator) -> Option<RgbaFrame> {
+    emulator.current_frame_rgba().map(downscale_thumbnail)
+}
+
+fn downscale_thumbnail(frame: RgbaFrame) -> RgbaFrame {
+    if frame.width <= THUMBNAIL_WIDTH {
+        return frame;
+    }
+
+    let width = THUMBNAIL_WIDTH;
+    let height =
+        (u64::from(frame.height) * u64::from(width) / u64::from(frame.width)).max(1) as u32;
+
+    // Average every source pixel that overlaps each destination pixel
+    let mut pixels = Vec::with_capacity(width as usize * height as usize);
+    for y in 0..height {
+        let src_y_start = y * frame.height / height;
+        let src_y_end = ((y + 1) * frame.height / height).max(src_y_start + 1);
+
+        for x in 0..width {
+            let src_x_start = x * frame.width / width;
+            let src_x_end = ((x + 1) * frame.width / width).max(src_x_start + 1);
+
+            let mut sums = [0_u32; 4];
+            for src_y in src_y_start..src_y_end {
+                let row_start = (src_y * frame.width) as usize;
+                let row = &frame.pixels[row_start..row_start + frame.width as usize];
+                for color in &row[src_x_start as usize..src_x_end as usize] {
+                    sums[0] += u32::from(color.r);
+                    sums[1] += u32::from(color.g);
+                    sums[2] += u32::from(color.b);
+                    sums[3] += u32::from(color.a);
+                }
+            }
+
+            let count = (src_y_end - src_y_start) * (src_x_end - src_x_start);
+            let [r, g, b, a] = sums.map(|sum| (sum / count) as u8);
+            pixels.push(Color::rgba(r, g, b, a));
+        }
+    }
+
+    RgbaFrame { width, height, pixel_aspect_ratio: frame.pixel_aspect_ratio, pixels }
+}
+
+fn write_thumbnail<W: Write>(writer: &mut W, thumbnail: &RgbaFrame) -> NativeEmulatorResult<()> {
+    let encoded = bincode::encode_to_vec(thumbnail, bincode_config!())?;
+    let compressed =
+        zstd::encode_all(encoded.as_slice(), 0).map_err(NativeEmulatorError::SaveStateIo)?;
+    let len: u32 = compressed.len().try_into().map_err(|_| {
+        NativeEmulatorError::SaveStateIo(io::Error::other("save state thumbnail is too large"))
+    })?;
+
+    writer.write_all(&THUMBNAIL_MARKER).map_err(NativeEmulatorError::SaveStateIo)?;
+    writer.write_all(&len.to_le_bytes()).map_err(NativeEmulatorError::SaveStateIo)?;
+    writer.write_all(&compressed).map_err(NativeEmulatorError::SaveStateIo)?;
+
+    Ok(())
+}
+
 pub fn save<Emulator: EmulatorTrait>(
-    emulator: &Emulator,
+    emulator: &mut Emulator,
     paths: &SaveStatePaths,
     slot: usize,
     metadata: &mut SaveStateMetadata,
 ) -> NativeEmulatorResult<()> {
-    let path = &paths[slot];
+    let thumbnail = save_to_path(emulator, &paths[slot])?;
+
+    let now_nanos = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_nanos();
+    metadata.times_nanos[slot] = Some(now_nanos);
+    metadata.thumbnails[slot] = thumbnail.map(Arc::new);
+
+    Ok(())
+}
+
+/// Save a state to the given path, returning the thumbnail that was saved with it.
+pub fn save_to_path<Emulator: EmulatorTrait>(
+    emulator: &mut Emulator,
+    path: &Path,
+) -> NativeEmulatorResult<Option<RgbaFrame>> {
+    // Write to a temp file first so that a concurrent instance loading this slot never reads a
+    // partially written state
+    let temp_path = jgenesis_common::files::process_temp_path(path);
+    let thumbnail = write_state(emulator, &temp_path)?;
+    fs::rename(&temp_path, path).map_err(NativeEmulatorError::SaveStateIo)?;
+
+    Ok(thumbnail)
+}
+
+fn write_state<Emulator: EmulatorTrait>(
+    emulator: &mut Emulator,
+    path: &Path,
+) -> NativeEmulatorResult<Option<RgbaFrame>> {
     let file = File::create(path).map_err(|source| NativeEmulatorError::StateFileOpen {
         path: path.display().to_string(),
         source,
     })?;
 
     let mut writer = BufWriter::new(file);
-    writer.write_all(FILE_PREFIX).map_err(NativeEmulatorError::SaveStateIo)?;
-    writer
-        .write_all(&Emulator::save_state_version().to_le_bytes())
-        .map_err(NativeEmulatorError::SaveStateIo)?;
+    let thumbnail = capture_thumbnail(emulator);
+    write_header(&mut writer, Emulator::save_state_version(), thumbnail.as_ref())?;
 
     let mut encoder =
         zstd::stream::Encoder::new(writer, 0).map_err(NativeEmulatorError::SaveStateIo)?;
-    bincode::encode_into_std_write(emulator, &mut encoder, bincode_config!())?;
+    bincode::encode_into_std_write(&*emulator, &mut encoder, bincode_config!())?;
     encoder.finish().map_err(NativeEmulatorError::SaveStateIo)?;
 
-    let now_nanos = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_nanos();
-    metadata.times_nanos[slot] = Some(now_nanos);
+    Ok(thumbnail)
+}
+
+fn write_header<W: Write>(
+    writer: &mut W,
+    version: u16,
+    thumbnail: Option<&RgbaFrame>,
+) -> NativeEmulatorResult<()> {
+    writer.write_all(FILE_PREFIX).map_err(NativeEmulatorError::SaveStateIo)?;
+    writer.write_all(&version.to_le_bytes()).map_err(NativeEmulatorError::SaveStateIo)?;
+
+    if let Some(thumbnail) = thumbnail {
+        write_thumbnail(writer, thumbnail)?;
+    }
 
     Ok(())
 }
 
+/// An uncompressed save state captured from a running emulator. Serializing the emulator is fast,
+/// so this can be done on the emulator thread while compressing and writing the state to disk is
+/// done on a background thread.
+#[derive(Debug)]
+pub struct StateSnapshot {
+    version: u16,
+    thumbnail: Option<RgbaFrame>,
+    state: Vec<u8>,
+}
+
+impl StateSnapshot {
+    pub fn capture<Emulator: EmulatorTrait>(emulator: &mut Emulator) -> NativeEmulatorResult<Self> {
+        let thumbnail = capture_thumbnail(emulator);
+        let state = bincode::encode_to_vec(&*emulator, bincode_config!())?;
+
+        Ok(Self { version: Emulator::save_state_version(), thumbnail, state })
+    }
+
+    /// Write this state to the given path, in the same format as [`save_to_path`].
+    pub fn write_to_path(&self, path: &Path) -> NativeEmulatorResult<()> {
+        let temp_path = jgenesis_common::files::process_temp_path(path);
+
+        let file = File::create(&temp_path).map_err(|source| {
+            NativeEmulatorError::StateFileOpen { path: temp_path.display().to_string(), source }
+        })?;
+
+        let mut writer = BufWriter::new(file);
+        write_header(&mut writer, self.version, self.thumbnail.as_ref())?;
+
+        let mut encoder =
+            zstd::stream::Encoder::new(writer, 0).map_err(NativeEmulatorError::SaveStateIo)?;
+        encoder.write_all(&self.state).map_err(NativeEmulatorError::SaveStateIo)?;
+        encoder.finish().map_err(NativeEmulatorError::SaveStateIo)?;
+
+        fs::rename(&temp_path, path).map_err(NativeEmulatorError::SaveStateIo)?;
+
+        Ok(())
+    }
+}
+
+/// Read the raw contents of a save state slot file so that it can be restored later. Returns `None`
+/// if the slot is empty.
+pub fn read_slot_contents(path: &Path) -> io::Result<Option<Vec<u8>>> {
+    match fs::read(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Restore a save state slot file to contents previously returned by [`read_slot_contents`],
+/// deleting the file if the slot was empty.
+pub fn restore_slot_contents(path: &Path, contents: Option<&[u8]>) -> io::Result<()> {
+    let Some(contents) = contents else {
+        return match fs::remove_file(path) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        };
+    };
+
+    let temp_path = jgenesis_common::files::process_temp_path(path);
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)
+}
+
 pub fn load<Emulator: EmulatorTrait>(
     emulator: &mut Emulator,
     config: &Emulator::Config,
     paths: &SaveStatePaths,
     slot: usize,
 ) -> NativeEmulatorResult<()> {
-    let path = &paths[slot];
+    load_from_path(emulator, config, &paths[slot])
+}
+
+pub fn load_from_path<Emulator: EmulatorTrait>(
+    emulator: &mut Emulator,
+    config: &Emulator::Config,
+    path: &Path,
+) -> NativeEmulatorResult<()> {
     let file = File::open(path).map_err(|source| NativeEmulatorError::StateFileOpen {
         path: path.display().to_string(),
         source,
@@ -127,6 +390,13 @@ pub fn load<Emulator: EmulatorTrait>(
         });
     }
 
+    let peeked =
+        match read_thumbnail_block(&mut reader).map_err(NativeEmulatorError::LoadStateIo)? {
+            ThumbnailBlock::Compressed(_) => Vec::new(),
+            ThumbnailBlock::Missing { peeked } => peeked.to_vec(),
+        };
+    let reader = Cursor::new(peeked).chain(reader);
+
     let mut decoder =
         zstd::stream::Decoder::new(reader).map_err(NativeEmulatorError::LoadStateIo)?;
     let mut loaded_emulator: Emulator =
@@ -138,3 +408,117 @@ pub fn load<Emulator: EmulatorTrait>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thumbnail_block_round_trip() {
+        let thumbnail = RgbaFrame {
+            width: 2,
+            height: 1,
+            pixel_aspect_ratio: None,
+            pixels: vec![Color::rgb(1, 2, 3), Color::rgb(4, 5, 6)],
+        };
+
+        let mut buffer = Vec::new();
+        write_thumbnail(&mut buffer, &thumbnail).unwrap();
+        buffer.extend_from_slice(b"rest");
+
+        let mut reader = buffer.as_slice();
+        let ThumbnailBlock::Compressed(compressed) = read_thumbnail_block(&mut reader).unwrap()
+        else {
+            panic!("thumbnail block not found");
+        };
+        assert_eq!(reader, b"rest");
+
+        let encoded = zstd::decode_all(compressed.as_slice()).unwrap();
+        let (decoded, _): (RgbaFrame, _) =
+            bincode::decode_from_slice(&encoded, bincode_config!()).unwrap();
+        assert_eq!(decoded, thumbnail);
+    }
+
+    #[test]
+    fn thumbnail_downscaled_to_fixed_width() {
+        // 320x224 frame with a 2x2 checkerboard of black and white pixels
+        let frame = RgbaFrame {
+            width: 320,
+            height: 224,
+            pixel_aspect_ratio: None,
+            pixels: (0..320 * 224)
+                .map(|i| {
+                    let (x, y) = (i % 320, i / 320);
+                    if (x + y) % 2 == 0 { Color::rgb(255, 255, 255) } else { Color::BLACK }
+                })
+                .collect(),
+        };
+
+        let thumbnail = downscale_thumbnail(frame);
+        assert_eq!((thumbnail.width, thumbnail.height), (THUMBNAIL_WIDTH, 112));
+        assert_eq!(thumbnail.pixels.len(), 160 * 112);
+        assert!(thumbnail.pixels.iter().all(|&color| color == Color::rgb(127, 127, 127)));
+
+        // Frames that are already small enough are stored as-is
+        let frame = RgbaFrame {
+            width: 2,
+            height: 1,
+            pixel_aspect_ratio: None,
+            pixels: vec![Color::BLACK; 2],
+        };
+        assert_eq!(downscale_thumbnail(frame.clone()), frame);
+    }
+
+    #[test]
+    fn auto_state_path_includes_rom_hash() {
+        let path = auto_state_path(Path::new("states/Sonic.jst"), "0123456789abcdef").unwrap();
+        assert_eq!(path, Path::new("states/Sonic_auto_0123456789abcdef.jst"));
+    }
+
+    #[test]
+    fn slot_contents_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.jst");
+
+        assert_eq!(read_slot_contents(&path).unwrap(), None);
+
+        fs::write(&path, b"old state").unwrap();
+        let contents = read_slot_contents(&path).unwrap();
+        fs::write(&path, b"new state").unwrap();
+
+        restore_slot_contents(&path, contents.as_deref()).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"old state");
+
+        restore_slot_contents(&path, None).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn snapshot_file_has_header_and_thumbnail() {
+        let thumbnail = RgbaFrame {
+            width: 1,
+            height: 1,
+            pixel_aspect_ratio: None,
+            pixels: vec![Color::rgb(7, 8, 9)],
+        };
+        let snapshot =
+            StateSnapshot { version: 12, thumbnail: Some(thumbnail.clone()), state: vec![1, 2, 3] };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.jst");
+        snapshot.write_to_path(&path).unwrap();
+
+        assert_eq!(read_version_from_header(&path), Some(12));
+        assert_eq!(read_thumbnail(&path), Some(thumbnail));
+    }
+
+    #[test]
+    fn missing_thumbnail_block_returns_peeked_bytes() {
+        let zstd_magic = [0x28, 0xB5, 0x2F, 0xFD];
+        let mut reader = zstd_magic.as_slice();
+        assert!(matches!(
+            read_thumbnail_block(&mut reader).unwrap(),
+            ThumbnailBlock::Missing { peeked } if peeked == zstd_magic
+        ));
+    }
+}
@@ -0,0 +1,108 @@
+//! Sub-frame input timing
+//!
+//! Input events are normally applied all at once between frames, so peripherals that latch input
+//! partway through a frame (light guns, paddles) only ever see input change at frame boundaries.
+//! With sub-frame input enabled, input events are timestamped when they are polled and then replayed
+//! during the next emulated frame at the point in the frame corresponding to when they occurred.
+
+use sdl2::TimerSubsystem;
+use sdl2::event::Event;
+use std::collections::VecDeque;
+
+pub struct SubframeInputQueue {
+    timer: TimerSubsystem,
+    // Position within the frame in the range [0, 1] paired with the event
+    events: VecDeque<(f64, Event)>,
+    // SDL ticks (milliseconds) of the previous and current event polls
+    poll_start_ticks: u32,
+    poll_end_ticks: Option<u32>,
+    // Number of emulator ticks in the last complete frame, used to estimate how far the current
+    // frame has progressed
+    ticks_last_frame: u32,
+    ticks_this_frame: u32,
+}
+
+impl SubframeInputQueue {
+    pub fn new(timer: TimerSubsystem) -> Self {
+        Self {
+            timer,
+            events: VecDeque::new(),
+            poll_start_ticks: 0,
+            poll_end_ticks: None,
+            ticks_last_frame: 0,
+            ticks_this_frame: 0,
+        }
+    }
+
+    /// Whether the given event should be replayed mid-frame rather than applied immediately.
+    pub fn should_defer(event: &Event) -> bool {
+        matches!(
+            event,
+            Event::KeyDown { .. }
+                | Event::KeyUp { .. }
+                | Event::TextInput { .. }
+                | Event::MouseMotion { .. }
+                | Event::MouseButtonDown { .. }
+                | Event::MouseButtonUp { .. }
+                | Event::ControllerAxisMotion { .. }
+                | Event::ControllerButtonDown { .. }
+                | Event::ControllerButtonUp { .. }
+                | Event::JoyAxisMotion { .. }
+                | Event::JoyHatMotion { .. }
+                | Event::JoyButtonDown { .. }
+                | Event::JoyButtonUp { .. }
+        )
+    }
+
+    /// Mark the start of an event poll. Events pushed after this are spread across the next frame
+    /// based on when they occurred relative to the previous poll.
+    pub fn start_poll(&mut self) {
+        let now = self.timer.ticks();
+        self.poll_start_ticks = self.poll_end_ticks.unwrap_or(now);
+        self.poll_end_ticks = Some(now);
+    }
+
+    pub fn push(&mut self, event: Event) {
+        let start = self.poll_start_ticks;
+        let elapsed = self.poll_end_ticks.map_or(0, |end| end.saturating_sub(start));
+
+        let position = if elapsed == 0 {
+            0.0
+        } else {
+            let since_start = event.get_timestamp().saturating_sub(start).min(elapsed);
+            f64::from(since_start) / f64::from(elapsed)
+        };
+
+        self.events.push_back((position, event));
+    }
+
+    /// Remove the next event if the current frame has progressed far enough for it to apply.
+    pub fn pop_due(&mut self) -> Option<Event> {
+        let &(position, _) = self.events.front()?;
+
+        let progress = if self.ticks_last_frame == 0 {
+            1.0
+        } else {
+            f64::from(self.ticks_this_frame) / f64::from(self.ticks_last_frame)
+        };
+        if position > progress {
+            return None;
+        }
+
+        self.events.pop_front().map(|(_, event)| event)
+    }
+
+    /// Remove all remaining events regardless of their position in the frame.
+    pub fn pop_all(&mut self) -> impl Iterator<Item = Event> + '_ {
+        self.events.drain(..).map(|(_, event)| event)
+    }
+
+    pub fn record_tick(&mut self) {
+        self.ticks_this_frame = self.ticks_this_frame.saturating_add(1);
+    }
+
+    pub fn end_frame(&mut self) {
+        self.ticks_last_frame = self.ticks_this_frame;
+        self.ticks_this_frame = 0;
+    }
+}
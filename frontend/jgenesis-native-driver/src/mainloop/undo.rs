@@ -0,0 +1,70 @@
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+// Number of pre-load emulator states and overwritten save state files to keep in memory
+const UNDO_BUFFER_LEN: usize = 5;
+
+// Contents of a save state slot file before it was overwritten
+pub struct OverwrittenState {
+    pub slot: usize,
+    pub path: PathBuf,
+    // None if the slot was empty before the save
+    pub contents: Option<Vec<u8>>,
+}
+
+// Small in-memory history that allows undoing accidental save state loads and saves. Loads are
+// undone by restoring the emulator state from right before the load, and saves are undone by
+// restoring the previous contents of the overwritten slot file
+pub struct StateUndoBuffer<Emulator> {
+    pre_load_states: VecDeque<Emulator>,
+    overwritten_states: VecDeque<OverwrittenState>,
+}
+
+impl<Emulator> StateUndoBuffer<Emulator> {
+    pub fn new() -> Self {
+        Self {
+            pre_load_states: VecDeque::with_capacity(UNDO_BUFFER_LEN + 1),
+            overwritten_states: VecDeque::with_capacity(UNDO_BUFFER_LEN + 1),
+        }
+    }
+
+    pub fn push_pre_load(&mut self, emulator: Emulator) {
+        push_bounded(&mut self.pre_load_states, emulator);
+    }
+
+    pub fn pop_pre_load(&mut self) -> Option<Emulator> {
+        self.pre_load_states.pop_back()
+    }
+
+    pub fn push_overwritten(&mut self, state: OverwrittenState) {
+        push_bounded(&mut self.overwritten_states, state);
+    }
+
+    pub fn pop_overwritten(&mut self) -> Option<OverwrittenState> {
+        self.overwritten_states.pop_back()
+    }
+}
+
+fn push_bounded<T>(buffer: &mut VecDeque<T>, value: T) {
+    buffer.push_back(value);
+    while buffer.len() > UNDO_BUFFER_LEN {
+        buffer.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_most_recent_entries() {
+        let mut buffer = VecDeque::new();
+        for i in 0..UNDO_BUFFER_LEN + 3 {
+            push_bounded(&mut buffer, i);
+        }
+
+        assert_eq!(buffer.len(), UNDO_BUFFER_LEN);
+        assert_eq!(buffer.pop_back(), Some(UNDO_BUFFER_LEN + 2));
+        assert_eq!(buffer.pop_front(), Some(3));
+    }
+}
@@ -13,6 +13,18 @@ pub enum WgpuBackend {
     OpenGl,
 }
 
+/// Which corner of the window to draw a persistent overlay in, such as the input viewer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, EnumDisplay, EnumAll)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "clap", derive(jgenesis_proc_macros::CustomValueEnum))]
+pub enum OverlayPosition {
+    TopLeft,
+    TopRight,
+    #[default]
+    BottomLeft,
+    BottomRight,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumDisplay, EnumAll)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "clap", derive(jgenesis_proc_macros::CustomValueEnum))]
@@ -142,15 +154,104 @@ pub enum PreprocessShader {
     AntiDitherStrong,
 }
 
+/// Extra frames to present after every emulated frame, for displays that refresh at a multiple of
+/// the emulated frame rate. Only takes effect when vsync is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, EnumDisplay, EnumFromStr, EnumAll)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "clap", derive(jgenesis_proc_macros::CustomValueEnum))]
+pub enum FrameInsertion {
+    #[default]
+    None,
+    /// Present black frames, which reduces sample-and-hold motion blur at the cost of brightness
+    BlackFrames,
+    /// Present the same frame again
+    DuplicateFrames,
+}
+
+/// Display refresh rate as a multiple of the emulated frame rate, e.g. `Double` for 120Hz with
+/// 60Hz content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, EnumDisplay, EnumFromStr, EnumAll)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "clap", derive(jgenesis_proc_macros::CustomValueEnum))]
+pub enum RefreshMultiplier {
+    #[default]
+    Double,
+    Triple,
+    Quadruple,
+}
+
+// Displays report nominal refresh rates (e.g. SDL only reports whole hertz), and some consoles run
+// slightly faster or slower than 60Hz, so allow the display to be marginally off from the exact multiple
+const REFRESH_RATE_TOLERANCE: f64 = 0.01;
+
+impl RefreshMultiplier {
+    #[must_use]
+    pub fn inserted_frames(self) -> u32 {
+        match self {
+            Self::Double => 1,
+            Self::Triple => 2,
+            Self::Quadruple => 3,
+        }
+    }
+
+    /// Whether a display refreshing at `display_refresh_millihz` can present `content_fps` at this
+    /// multiple, i.e. whether the display refresh rate is (nearly) exactly this multiple of the
+    /// content frame rate. If it isn't, some frames would be shown for more refreshes than others,
+    /// which with black frames is an uneven flicker rather than reduced motion blur.
+    #[must_use]
+    pub fn supported_by(self, display_refresh_millihz: u32, content_fps: f64) -> bool {
+        let required_hz = content_fps * f64::from(self.inserted_frames() + 1);
+        let display_hz = f64::from(display_refresh_millihz) / 1000.0;
+        (display_hz / required_hz - 1.0).abs() <= REFRESH_RATE_TOLERANCE
+    }
+}
+
 #[derive(Debug, Clone, Copy, ConfigDisplay)]
 pub struct RendererConfig {
     pub wgpu_backend: WgpuBackend,
     pub vsync_mode: VSyncMode,
     pub frame_time_sync: bool,
+    /// Queue completed frames and present them at evenly spaced timestamps instead of presenting
+    /// each frame as soon as the emulator finishes it
+    pub decoupled_frame_presentation: bool,
     pub prescale_mode: PrescaleMode,
     pub scanlines: Scanlines,
     pub force_integer_height_scaling: bool,
     pub filter_mode: FilterMode,
     pub preprocess_shader: PreprocessShader,
+    /// Simulate NTSC composite video artifacts (color bleed and dither blending). Applied before
+    /// the preprocess shader
+    pub ntsc_filter: bool,
+    pub frame_insertion: FrameInsertion,
+    pub refresh_multiplier: RefreshMultiplier,
     pub use_webgl2_limits: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_multiplier_requires_integer_multiple() {
+        // Genesis NTSC and SNES NTSC frame rates
+        for content_fps in [59.92, 60.0988] {
+            assert!(RefreshMultiplier::Double.supported_by(120_000, content_fps));
+            assert!(RefreshMultiplier::Double.supported_by(119_880, content_fps));
+            assert!(!RefreshMultiplier::Double.supported_by(240_000, content_fps));
+            assert!(!RefreshMultiplier::Double.supported_by(144_000, content_fps));
+            assert!(!RefreshMultiplier::Double.supported_by(165_000, content_fps));
+            assert!(!RefreshMultiplier::Double.supported_by(60_000, content_fps));
+            assert!(!RefreshMultiplier::Double.supported_by(100_000, content_fps));
+            assert!(!RefreshMultiplier::Triple.supported_by(120_000, content_fps));
+            assert!(RefreshMultiplier::Triple.supported_by(180_000, content_fps));
+            assert!(!RefreshMultiplier::Triple.supported_by(240_000, content_fps));
+            assert!(RefreshMultiplier::Quadruple.supported_by(240_000, content_fps));
+            assert!(!RefreshMultiplier::Quadruple.supported_by(165_000, content_fps));
+        }
+
+        // PAL content
+        assert!(RefreshMultiplier::Double.supported_by(100_000, 50.0));
+        assert!(!RefreshMultiplier::Double.supported_by(75_000, 50.0));
+        assert!(!RefreshMultiplier::Double.supported_by(120_000, 50.0));
+    }
+}
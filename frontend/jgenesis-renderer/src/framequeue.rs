@@ -0,0 +1,221 @@
+//! Timestamped frame queue for decoupled frame presentation.
+//!
+//! Normally every frame is presented as soon as the emulator finishes rendering it, so frame pacing
+//! depends entirely on when the emulation loop completes frames. With audio sync this happens in
+//! uneven bursts, which is especially visible in low-framerate FMV sequences on mismatched refresh
+//! rates. When presentation is decoupled, completed frames are instead queued (up to three, as with
+//! triple buffering) and each one is presented at a timestamp spaced one smoothed frame interval
+//! after the previous frame's.
+
+use jgenesis_common::frontend::{Color, FrameSize, PixelAspectRatio};
+use std::collections::VecDeque;
+
+const MAX_QUEUED_FRAMES: usize = 3;
+
+// Weight of each new frame completion interval in the smoothed frame interval
+const INTERVAL_SMOOTHING: f64 = 1.0 / 16.0;
+
+// Completion intervals this many times longer than the current estimate are treated as pauses
+// (e.g. loading or the emulator being paused) rather than as a change in framerate
+const PAUSE_INTERVAL_MULTIPLIER: f64 = 4.0;
+
+pub struct QueuedFrame {
+    pub buffer: Vec<Color>,
+    pub buffer_width: u32,
+    pub frame_size: FrameSize,
+    pub pixel_aspect_ratio: Option<PixelAspectRatio>,
+    present_at_nanos: u128,
+}
+
+pub struct FrameQueue {
+    frames: VecDeque<QueuedFrame>,
+    spare_buffers: Vec<Vec<Color>>,
+    frame_interval_nanos: f64,
+    last_completion_nanos: Option<u128>,
+    last_present_at_nanos: u128,
+}
+
+impl FrameQueue {
+    pub fn new() -> Self {
+        Self {
+            frames: VecDeque::with_capacity(MAX_QUEUED_FRAMES),
+            spare_buffers: Vec::with_capacity(MAX_QUEUED_FRAMES),
+            frame_interval_nanos: 1_000_000_000.0 / 60.0,
+            last_completion_nanos: None,
+            last_present_at_nanos: 0,
+        }
+    }
+
+    /// Queue a completed frame. `frame_buffer` should start at the top-left pixel of the frame,
+    /// with `buffer_width` being the distance between rows.
+    pub fn push(
+        &mut self,
+        frame_buffer: &[Color],
+        buffer_width: u32,
+        frame_size: FrameSize,
+        pixel_aspect_ratio: Option<PixelAspectRatio>,
+        now_nanos: u128,
+    ) {
+        if let Some(last_completion_nanos) = self.last_completion_nanos {
+            let interval = now_nanos.saturating_sub(last_completion_nanos) as f64;
+            if interval < PAUSE_INTERVAL_MULTIPLIER * self.frame_interval_nanos {
+                self.frame_interval_nanos +=
+                    (interval - self.frame_interval_nanos) * INTERVAL_SMOOTHING;
+            }
+        }
+        self.last_completion_nanos = Some(now_nanos);
+
+        // Space frames evenly after the previous frame, but never schedule a frame before it was
+        // completed or so far ahead that the queue only adds latency
+        let interval = self.frame_interval_nanos.round() as u128;
+        let latest = now_nanos + interval;
+        let present_at_nanos = (self.last_present_at_nanos + interval).clamp(now_nanos, latest);
+        self.last_present_at_nanos = present_at_nanos;
+
+        if self.frames.len() == MAX_QUEUED_FRAMES {
+            // Drop the oldest frame, same as triple buffering when the display falls behind
+            if let Some(dropped) = self.frames.pop_front() {
+                self.recycle(dropped.buffer);
+            }
+        }
+
+        let len = frame_buffer_len(frame_buffer, buffer_width, frame_size);
+        let mut buffer = self.spare_buffers.pop().unwrap_or_default();
+        buffer.clear();
+        buffer.extend_from_slice(&frame_buffer[..len]);
+
+        self.frames.push_back(QueuedFrame {
+            buffer,
+            buffer_width,
+            frame_size,
+            pixel_aspect_ratio,
+            present_at_nanos,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Remove and return the newest frame whose presentation time has arrived, dropping any older
+    /// frames that were never presented.
+    pub fn pop_due(&mut self, now_nanos: u128) -> Option<QueuedFrame> {
+        let mut due: Option<QueuedFrame> = None;
+        while self.frames.front().is_some_and(|frame| frame.present_at_nanos <= now_nanos) {
+            let frame = self.frames.pop_front()?;
+            if let Some(skipped) = due.replace(frame) {
+                self.recycle(skipped.buffer);
+            }
+        }
+        due
+    }
+
+    /// Return a presented frame's buffer so that it can be reused for a later frame.
+    pub fn recycle(&mut self, buffer: Vec<Color>) {
+        if self.spare_buffers.len() < MAX_QUEUED_FRAMES {
+            self.spare_buffers.push(buffer);
+        }
+    }
+
+    /// Drop every queued frame.
+    pub fn clear(&mut self) {
+        while let Some(frame) = self.frames.pop_front() {
+            self.recycle(frame.buffer);
+        }
+        self.last_completion_nanos = None;
+    }
+}
+
+// Number of buffer elements needed to hold the frame; the last row does not need to extend to the
+// full buffer width
+fn frame_buffer_len(frame_buffer: &[Color], buffer_width: u32, frame_size: FrameSize) -> usize {
+    let needed = (buffer_width * frame_size.height.saturating_sub(1) + frame_size.width) as usize;
+    needed.min(frame_buffer.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME_SIZE: FrameSize = FrameSize { width: 2, height: 2 };
+    const START_NANOS: u128 = 1_000_000_000;
+
+    fn push_frame(queue: &mut FrameQueue, value: u8, now_nanos: u128) {
+        let frame_buffer = [Color::rgb(value, 0, 0); 4];
+        queue.push(&frame_buffer, FRAME_SIZE.width, FRAME_SIZE, None, now_nanos);
+    }
+
+    fn frame_value(frame: &QueuedFrame) -> u8 {
+        frame.buffer[0].r
+    }
+
+    #[test]
+    fn frames_are_spaced_evenly() {
+        let mut queue = FrameQueue::new();
+
+        // Two frames completed in a burst
+        push_frame(&mut queue, 1, START_NANOS);
+        push_frame(&mut queue, 2, START_NANOS + 1_000_000);
+
+        // The first frame is due immediately, and the second is due one smoothed frame interval
+        // later rather than when it was completed
+        let second_due = queue.last_present_at_nanos;
+        assert!(second_due > START_NANOS + 1_000_000);
+        assert!(second_due < START_NANOS + 1_000_000_000 / 60);
+
+        assert_eq!(queue.pop_due(START_NANOS + 1_000_000).as_ref().map(frame_value), Some(1));
+        assert!(queue.pop_due(second_due - 1).is_none());
+        assert_eq!(queue.pop_due(second_due).as_ref().map(frame_value), Some(2));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn pop_due_skips_older_frames() {
+        let mut queue = FrameQueue::new();
+        for value in 1..=3 {
+            push_frame(&mut queue, value, START_NANOS);
+        }
+
+        assert_eq!(queue.pop_due(u128::MAX).as_ref().map(frame_value), Some(3));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn full_queue_drops_oldest_frame() {
+        let mut queue = FrameQueue::new();
+        for value in 1..=(MAX_QUEUED_FRAMES as u8 + 1) {
+            push_frame(&mut queue, value, START_NANOS);
+        }
+
+        assert_eq!(queue.frames.len(), MAX_QUEUED_FRAMES);
+        assert_eq!(queue.frames.front().map(frame_value), Some(2));
+    }
+
+    #[test]
+    fn pause_does_not_change_frame_interval() {
+        let mut queue = FrameQueue::new();
+        push_frame(&mut queue, 1, START_NANOS);
+        let interval = queue.frame_interval_nanos;
+
+        push_frame(&mut queue, 2, START_NANOS + 10_000_000_000);
+        assert_eq!(queue.frame_interval_nanos, interval);
+        assert_eq!(queue.last_present_at_nanos, START_NANOS + 10_000_000_000);
+    }
+
+    #[test]
+    fn buffers_are_trimmed_and_recycled() {
+        let mut queue = FrameQueue::new();
+
+        // Rows are 4 pixels apart, and the last row only needs the frame width
+        let frame_buffer = [Color::BLACK; 16];
+        queue.push(&frame_buffer, 4, FRAME_SIZE, None, START_NANOS);
+
+        let frame = queue.pop_due(START_NANOS).unwrap();
+        assert_eq!(frame.buffer.len(), 6);
+
+        queue.recycle(frame.buffer);
+        assert_eq!(queue.spare_buffers.len(), 1);
+        push_frame(&mut queue, 1, START_NANOS + 1_000_000);
+        assert!(queue.spare_buffers.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod config;
+mod framequeue;
 pub mod renderer;
 #[cfg(feature = "ttf")]
 mod ttf;
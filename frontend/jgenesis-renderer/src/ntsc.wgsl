@@ -0,0 +1,54 @@
+struct TextureWidth {
+    value: u32,
+    // Uniform values must be padded to a multiple of 16 bytes for WebGL
+    _padding0: u32,
+    _padding1: u32,
+    _padding2: u32,
+}
+
+@group(0) @binding(0)
+var texture_in: texture_2d<f32>;
+@group(0) @binding(1)
+var<uniform> texture_width: TextureWidth;
+
+const RGB_TO_YIQ = mat3x3f(
+    vec3f(0.299, 0.596, 0.211),
+    vec3f(0.587, -0.274, -0.523),
+    vec3f(0.114, -0.322, 0.312),
+);
+
+const YIQ_TO_RGB = mat3x3f(
+    vec3f(1.0, 1.0, 1.0),
+    vec3f(0.956, -0.272, -1.106),
+    vec3f(0.621, -0.647, 1.703),
+);
+
+// Composite video has much less bandwidth for chroma than for luma, so chroma is blurred across
+// a much wider window. The luma blur is what blends 2-pixel dither patterns into transparency
+const LUMA_WEIGHTS = array<f32, 7>(0.0, 0.0, 0.25, 0.5, 0.25, 0.0, 0.0);
+const CHROMA_WEIGHTS = array<f32, 7>(0.0625, 0.125, 0.1875, 0.25, 0.1875, 0.125, 0.0625);
+
+fn to_texture_position(fragment_position: vec4f) -> vec2u {
+    let texture_position = round(fragment_position.xy - vec2f(0.5));
+    return vec2u(u32(texture_position.x), u32(texture_position.y));
+}
+
+fn load_yiq(position: vec2u, offset: i32) -> vec3f {
+    let x = clamp(i32(position.x) + offset, 0, i32(texture_width.value) - 1);
+    let rgb = textureLoad(texture_in, vec2u(u32(x), position.y), 0).rgb;
+    return RGB_TO_YIQ * rgb;
+}
+
+@fragment
+fn ntsc_composite(@builtin(position) position: vec4f) -> @location(0) vec4f {
+    let t_position = to_texture_position(position);
+
+    var yiq = vec3f(0.0);
+    for (var i = 0; i < 7; i++) {
+        let sample = load_yiq(t_position, i - 3);
+        yiq += vec3f(LUMA_WEIGHTS[i], CHROMA_WEIGHTS[i], CHROMA_WEIGHTS[i]) * sample;
+    }
+
+    let color = clamp(YIQ_TO_RGB * yiq, vec3f(0.0), vec3f(1.0));
+    return vec4f(color, 1.0);
+}
@@ -1,7 +1,13 @@
-use crate::config::{PreprocessShader, PrescaleMode, RendererConfig, Scanlines, WgpuBackend};
+use crate::config::{
+    FrameInsertion, PreprocessShader, PrescaleMode, RendererConfig, Scanlines, VSyncMode,
+    WgpuBackend,
+};
+use crate::framequeue::FrameQueue;
 use cfg_if::cfg_if;
-use jgenesis_common::frontend::{Color, DisplayArea, FrameSize, PixelAspectRatio, Renderer};
-use jgenesis_common::timeutils;
+use jgenesis_common::frontend::{
+    Color, DisplayArea, FrameCrop, FrameSize, PixelAspectRatio, Renderer,
+};
+use jgenesis_common::{instrument, timeutils};
 use raw_window_handle::{HandleError, HasDisplayHandle, HasWindowHandle};
 use std::collections::HashMap;
 use std::error::Error;
@@ -54,90 +60,116 @@ impl PreprocessShaderExt for PreprocessShader {
     }
 }
 
-enum PreprocessPipeline {
-    None(wgpu::Texture),
-    PreprocessShader {
-        input: wgpu::Texture,
-        output: wgpu::Texture,
-        bind_groups: Vec<wgpu::BindGroup>,
-        pipeline: wgpu::RenderPipeline,
-    },
+struct ShaderStage {
+    output: wgpu::Texture,
+    bind_group: wgpu::BindGroup,
+    pipeline: wgpu::RenderPipeline,
+}
+
+// Shaders that run at native resolution before prescaling: the NTSC filter (if enabled) followed
+// by the configured preprocess shader (if any). Each stage reads the previous stage's output
+struct PreprocessPipeline {
+    input: wgpu::Texture,
+    stages: Vec<ShaderStage>,
 }
 
 impl PreprocessPipeline {
     fn create(
-        preprocess_shader: PreprocessShader,
+        renderer_config: &RendererConfig,
         device: &wgpu::Device,
         input_texture: wgpu::Texture,
         shaders: &Shaders,
     ) -> Self {
-        match preprocess_shader {
-            PreprocessShader::None => Self::None(input_texture),
-            PreprocessShader::HorizontalBlurTwoPixels
-            | PreprocessShader::HorizontalBlurThreePixels
-            | PreprocessShader::HorizontalBlurSnesAdaptive
-            | PreprocessShader::AntiDitherWeak
-            | PreprocessShader::AntiDitherStrong => {
-                create_horizontal_blur_pipeline(preprocess_shader, device, input_texture, shaders)
-            }
+        let mut stages: Vec<ShaderStage> = Vec::with_capacity(2);
+
+        if renderer_config.ntsc_filter {
+            stages.push(create_shader_stage(
+                device,
+                shaders,
+                &input_texture,
+                &shaders.ntsc,
+                "ntsc_composite",
+                1,
+            ));
+        }
+
+        let preprocess_shader = renderer_config.preprocess_shader;
+        if let Some(fs_main) = hblur_entry_point(preprocess_shader) {
+            let stage_input = stages.last().map_or(&input_texture, |stage| &stage.output);
+            let width_scale_factor = preprocess_shader.width_scale_factor(stage_input.width());
+            let stage = create_shader_stage(
+                device,
+                shaders,
+                stage_input,
+                &shaders.hblur,
+                fs_main,
+                width_scale_factor,
+            );
+            stages.push(stage);
         }
+
+        Self { input: input_texture, stages }
     }
 
     fn input_texture(&self) -> &wgpu::Texture {
-        match self {
-            Self::None(texture) => texture,
-            Self::PreprocessShader { input, .. } => input,
-        }
+        &self.input
     }
 
     fn output_texture(&self) -> &wgpu::Texture {
-        match self {
-            Self::None(texture) => texture,
-            Self::PreprocessShader { output, .. } => output,
-        }
+        self.stages.last().map_or(&self.input, |stage| &stage.output)
     }
 
     fn draw(&self, encoder: &mut wgpu::CommandEncoder) {
-        match self {
-            Self::None(..) => {}
-            Self::PreprocessShader { output, bind_groups, pipeline, .. } => {
-                let output_view = output.create_view(&wgpu::TextureViewDescriptor::default());
+        for ShaderStage { output, bind_group, pipeline } in &self.stages {
+            let output_view = output.create_view(&wgpu::TextureViewDescriptor::default());
 
-                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: "preprocess_render_pass".into(),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &output_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                            store: wgpu::StoreOp::Store,
-                        },
-                    })],
-                    depth_stencil_attachment: None,
-                    timestamp_writes: None,
-                    occlusion_query_set: None,
-                });
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: "preprocess_render_pass".into(),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &output_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
 
-                for (i, bind_group) in bind_groups.iter().enumerate() {
-                    render_pass.set_bind_group(i as u32, bind_group, &[]);
-                }
-                render_pass.set_pipeline(pipeline);
+            render_pass.set_bind_group(0, bind_group, &[]);
+            render_pass.set_pipeline(pipeline);
 
-                render_pass.draw(0..VERTICES.len() as u32, 0..1);
-            }
+            render_pass.draw(0..VERTICES.len() as u32, 0..1);
         }
     }
 }
 
-fn create_horizontal_blur_pipeline(
-    preprocess_shader: PreprocessShader,
+fn hblur_entry_point(preprocess_shader: PreprocessShader) -> Option<&'static str> {
+    match preprocess_shader {
+        PreprocessShader::None => None,
+        PreprocessShader::HorizontalBlurTwoPixels => Some("hblur_2px"),
+        PreprocessShader::HorizontalBlurThreePixels => Some("hblur_3px"),
+        PreprocessShader::HorizontalBlurSnesAdaptive => Some("hblur_snes"),
+        PreprocessShader::AntiDitherWeak => Some("anti_dither_weak"),
+        PreprocessShader::AntiDitherStrong => Some("anti_dither_strong"),
+    }
+}
+
+// Create a fullscreen fragment shader pass that reads from the input texture using textureLoad.
+// The shader module must declare the input texture at binding 0 and the input texture width at
+// binding 1
+fn create_shader_stage(
     device: &wgpu::Device,
-    input_texture: wgpu::Texture,
     shaders: &Shaders,
-) -> PreprocessPipeline {
+    input_texture: &wgpu::Texture,
+    fs_module: &wgpu::ShaderModule,
+    fs_main: &str,
+    width_scale_factor: u32,
+) -> ShaderStage {
     let input_texture_view = input_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
-    let width_scale_factor = preprocess_shader.width_scale_factor(input_texture.width());
     let output_texture = device.create_texture(&wgpu::TextureDescriptor {
         label: "preprocess_output_texture".into(),
         size: wgpu::Extent3d {
@@ -154,7 +186,7 @@ fn create_horizontal_blur_pipeline(
     });
 
     let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-        label: "hblur_bind_group_layout".into(),
+        label: "preprocess_bind_group_layout".into(),
         entries: &[
             wgpu::BindGroupLayoutEntry {
                 binding: 0,
@@ -180,12 +212,12 @@ fn create_horizontal_blur_pipeline(
     });
 
     let texture_width_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-        label: "hblur_texture_width_buffer".into(),
+        label: "preprocess_texture_width_buffer".into(),
         contents: bytemuck::cast_slice(&padded_u32(input_texture.size().width)),
         usage: wgpu::BufferUsages::UNIFORM,
     });
     let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-        label: "hblur_bind_group".into(),
+        label: "preprocess_bind_group".into(),
         layout: &bind_group_layout,
         entries: &[
             wgpu::BindGroupEntry {
@@ -204,21 +236,13 @@ fn create_horizontal_blur_pipeline(
     });
 
     let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-        label: "hblur_pipeline_layout".into(),
+        label: "preprocess_pipeline_layout".into(),
         bind_group_layouts: &[&bind_group_layout],
         push_constant_ranges: &[],
     });
 
-    let fs_main = match preprocess_shader {
-        PreprocessShader::HorizontalBlurTwoPixels => "hblur_2px",
-        PreprocessShader::HorizontalBlurThreePixels => "hblur_3px",
-        PreprocessShader::HorizontalBlurSnesAdaptive => "hblur_snes",
-        PreprocessShader::AntiDitherWeak => "anti_dither_weak",
-        PreprocessShader::AntiDitherStrong => "anti_dither_strong",
-        PreprocessShader::None => panic!("Not a horizontal blur shader: {preprocess_shader:?}"),
-    };
     let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-        label: "hblur_pipeline".into(),
+        label: "preprocess_pipeline".into(),
         layout: Some(&pipeline_layout),
         vertex: wgpu::VertexState {
             module: &shaders.identity,
@@ -242,7 +266,7 @@ fn create_horizontal_blur_pipeline(
             alpha_to_coverage_enabled: false,
         },
         fragment: Some(wgpu::FragmentState {
-            module: &shaders.hblur,
+            module: fs_module,
             entry_point: Some(fs_main),
             compilation_options: wgpu::PipelineCompilationOptions::default(),
             targets: &[Some(wgpu::ColorTargetState {
@@ -255,12 +279,7 @@ fn create_horizontal_blur_pipeline(
         cache: None,
     });
 
-    PreprocessPipeline::PreprocessShader {
-        input: input_texture,
-        output: output_texture,
-        bind_groups: vec![bind_group],
-        pipeline,
-    }
+    ShaderStage { output: output_texture, bind_group, pipeline }
 }
 
 // WebGL requires all uniforms to be padded to a multiple of 16 bytes
@@ -344,12 +363,8 @@ impl RenderingPipeline {
             usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::VERTEX,
         });
 
-        let preprocess_pipeline = PreprocessPipeline::create(
-            renderer_config.preprocess_shader,
-            device,
-            input_texture,
-            shaders,
-        );
+        let preprocess_pipeline =
+            PreprocessPipeline::create(&renderer_config, device, input_texture, shaders);
         let preprocess_output_texture = preprocess_pipeline.output_texture();
 
         let prescale_factor = match renderer_config.prescale_mode {
@@ -596,9 +611,11 @@ impl RenderingPipeline {
         queue: &wgpu::Queue,
         surface: &wgpu::Surface<'_>,
         frame_buffer: &[Color],
+        buffer_width: u32,
         #[cfg(feature = "ttf")] surface_config: &wgpu::SurfaceConfiguration,
         #[cfg(feature = "ttf")] modal_renderer: &mut ttf::ModalRenderer,
         frame_time_tracker: &mut FrameTimeTracker,
+        (frame_insertion, inserted_frames): (FrameInsertion, u32),
     ) -> Result<RenderResult, RendererError> {
         let output = surface.get_current_texture()?;
         let output_texture_view =
@@ -615,7 +632,8 @@ impl RenderingPipeline {
             bytemuck::cast_slice(frame_buffer),
             wgpu::ImageDataLayout {
                 offset: 0,
-                bytes_per_row: Some(self.frame_size.width * 4),
+                // The frame buffer may be wider than the frame if the frame is cropped
+                bytes_per_row: Some(buffer_width * 4),
                 rows_per_image: Some(self.frame_size.height),
             },
             input_texture.size(),
@@ -695,6 +713,51 @@ impl RenderingPipeline {
         frame_time_tracker.sync();
         output.present();
 
+        // Extra presents for displays that refresh at a multiple of the emulated frame rate; VSync
+        // paces these so that each emulated frame stays on screen for exactly one refresh
+        for _ in 0..inserted_frames {
+            let output = surface.get_current_texture()?;
+            let output_texture_view =
+                output.texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: "inserted_frame_encoder".into(),
+            });
+
+            {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: "inserted_frame_pass".into(),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &output_texture_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+                if frame_insertion == FrameInsertion::DuplicateFrames {
+                    render_pass.set_bind_group(0, &self.render_bind_group, &[]);
+                    render_pass.set_pipeline(&self.render_pipeline);
+                    render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+
+                    render_pass.draw(0..VERTICES.len() as u32, 0..1);
+
+                    #[cfg(feature = "ttf")]
+                    if let Some(modal_vertex_buffer) = &modal_vertex_buffer {
+                        modal_renderer.render(modal_vertex_buffer, &mut render_pass)?;
+                    }
+                }
+            }
+
+            queue.submit(iter::once(encoder.finish()));
+            output.present();
+        }
+
         Ok(render_result)
     }
 }
@@ -866,6 +929,7 @@ struct Shaders {
     prescale: wgpu::ShaderModule,
     identity: wgpu::ShaderModule,
     hblur: wgpu::ShaderModule,
+    ntsc: wgpu::ShaderModule,
 }
 
 impl Shaders {
@@ -874,8 +938,9 @@ impl Shaders {
         let prescale = device.create_shader_module(wgpu::include_wgsl!("prescale.wgsl"));
         let identity = device.create_shader_module(wgpu::include_wgsl!("identity.wgsl"));
         let hblur = device.create_shader_module(wgpu::include_wgsl!("hblur.wgsl"));
+        let ntsc = device.create_shader_module(wgpu::include_wgsl!("ntsc.wgsl"));
 
-        Self { render, prescale, identity, hblur }
+        Self { render, prescale, identity, hblur, ntsc }
     }
 }
 
@@ -983,6 +1048,9 @@ pub struct WgpuRenderer<Window> {
     frame_count: u64,
     speed_multiplier: u64,
     frame_time_tracker: FrameTimeTracker,
+    frame_queue: FrameQueue,
+    display_refresh_millihz: Option<u32>,
+    frame_insertion_supported: bool,
     // SAFETY: The surface must not outlive the window it was created from, thus the window must be
     // declared after the surface
     window: Window,
@@ -1106,6 +1174,9 @@ impl<Window: HasDisplayHandle + HasWindowHandle> WgpuRenderer<Window> {
             frame_count: 0,
             speed_multiplier: 1,
             frame_time_tracker: FrameTimeTracker::new(config.frame_time_sync),
+            frame_queue: FrameQueue::new(),
+            display_refresh_millihz: None,
+            frame_insertion_supported: false,
             window,
             window_size,
         })
@@ -1132,8 +1203,14 @@ impl<Window> WgpuRenderer<Window> {
         }
         self.frame_time_tracker.sync_enabled = config.frame_time_sync;
 
+        if !config.decoupled_frame_presentation {
+            // Frames left in the queue would never be presented
+            self.frame_queue.clear();
+        }
+
         self.renderer_config = config;
         self.surface.configure(&self.device, &self.surface_config);
+        self.update_frame_insertion_supported();
 
         // Force render pipeline to be recreated on the next render_frame() call
         self.pipelines.clear();
@@ -1190,6 +1267,20 @@ impl<Window> WgpuRenderer<Window> {
             "Set frame time interval to {}ns for target framerate {fps} FPS",
             self.frame_time_tracker.frame_interval_nanos
         );
+
+        self.update_frame_insertion_supported();
+    }
+
+    /// Set the refresh rate of the display that the window is currently on, in millihertz, or
+    /// `None` if it is not known. Frame insertion is only performed if the display is known to
+    /// refresh fast enough for the configured refresh multiplier.
+    pub fn set_display_refresh_rate(&mut self, refresh_rate_millihz: Option<u32>) {
+        if refresh_rate_millihz == self.display_refresh_millihz {
+            return;
+        }
+
+        self.display_refresh_millihz = refresh_rate_millihz;
+        self.update_frame_insertion_supported();
     }
 
     /// Obtain the last rendered frame size and the current display area within the window.
@@ -1205,22 +1296,126 @@ impl<Window> WgpuRenderer<Window> {
     pub fn add_modal(&mut self, text: String, duration: std::time::Duration) {
         self.modal_renderer.add_modal(text, duration);
     }
+
+    /// Set or clear text that is drawn over every frame in the given corner of the window until it
+    /// is replaced or cleared.
+    #[cfg(feature = "ttf")]
+    pub fn set_overlay_text(
+        &mut self,
+        text: Option<String>,
+        position: crate::config::OverlayPosition,
+    ) {
+        self.modal_renderer.set_overlay(text, position);
+    }
 }
 
-impl<Window> Renderer for WgpuRenderer<Window> {
-    type Err = RendererError;
+impl<Window> WgpuRenderer<Window> {
+    fn update_frame_insertion_supported(&mut self) {
+        let content_fps = 1_000_000_000.0 / self.frame_time_tracker.frame_interval_nanos as f64;
+        let multiplier = self.renderer_config.refresh_multiplier;
+        self.frame_insertion_supported = self
+            .display_refresh_millihz
+            .is_some_and(|refresh_rate| multiplier.supported_by(refresh_rate, content_fps));
+
+        if self.renderer_config.frame_insertion != FrameInsertion::None
+            && !self.frame_insertion_supported
+        {
+            log::warn!(
+                "Disabling frame insertion; display refresh rate ({}) is not known to be {}x the content frame rate ({content_fps:.2} FPS)",
+                self.display_refresh_millihz.map_or_else(
+                    || "unknown".into(),
+                    |refresh_rate| format!("{:.2}Hz", f64::from(refresh_rate) / 1000.0)
+                ),
+                multiplier.inserted_frames() + 1
+            );
+        }
+    }
 
-    fn render_frame(
+    fn frame_insertion(&self) -> (FrameInsertion, u32) {
+        // Inserted frames only make sense when presents are paced by VSync, and they would slow
+        // down fast forward. Never insert frames if the display can't show them at the configured
+        // multiple; black frames held for multiple refreshes produce a visible low-frequency flicker
+        let frame_insertion = self.renderer_config.frame_insertion;
+        if frame_insertion == FrameInsertion::None
+            || !self.frame_insertion_supported
+            || self.renderer_config.vsync_mode != VSyncMode::Enabled
+            || self.speed_multiplier != 1
+        {
+            return (FrameInsertion::None, 0);
+        }
+
+        (frame_insertion, self.renderer_config.refresh_multiplier.inserted_frames())
+    }
+}
+
+impl<Window> WgpuRenderer<Window> {
+    // frame_buffer should start at the top-left pixel of the frame, and buffer_width is the
+    // distance between rows in the frame buffer
+    fn render_frame_region(
         &mut self,
         frame_buffer: &[Color],
+        buffer_width: u32,
         frame_size: FrameSize,
         pixel_aspect_ratio: Option<PixelAspectRatio>,
-    ) -> Result<(), Self::Err> {
+    ) -> Result<(), RendererError> {
         self.frame_count += 1;
         if self.frame_count % self.speed_multiplier != 0 {
             return Ok(());
         }
 
+        // Queueing frames would only add latency while fast forwarding
+        if self.renderer_config.decoupled_frame_presentation && self.speed_multiplier == 1 {
+            self.frame_queue.push(
+                frame_buffer,
+                buffer_width,
+                frame_size,
+                pixel_aspect_ratio,
+                timeutils::current_time_nanos(),
+            );
+            return self.present_due_frame();
+        }
+
+        self.present_frame(frame_buffer, buffer_width, frame_size, pixel_aspect_ratio)
+    }
+
+    /// With decoupled frame presentation enabled, present the most recent queued frame whose
+    /// presentation time has arrived, if any. Frontends should call this once per iteration of
+    /// their main loop, between emulated frames, so that queued frames are presented even while
+    /// the emulator is paused.
+    ///
+    /// # Errors
+    ///
+    /// Propagates any errors encountered while rendering the frame.
+    pub fn present_due_frame(&mut self) -> Result<(), RendererError> {
+        if self.frame_queue.is_empty() {
+            return Ok(());
+        }
+
+        let Some(frame) = self.frame_queue.pop_due(timeutils::current_time_nanos()) else {
+            return Ok(());
+        };
+
+        let result = self.present_frame(
+            &frame.buffer,
+            frame.buffer_width,
+            frame.frame_size,
+            frame.pixel_aspect_ratio,
+        );
+        self.frame_queue.recycle(frame.buffer);
+        result
+    }
+
+    fn present_frame(
+        &mut self,
+        frame_buffer: &[Color],
+        buffer_width: u32,
+        frame_size: FrameSize,
+        pixel_aspect_ratio: Option<PixelAspectRatio>,
+    ) -> Result<(), RendererError> {
+        instrument!("wgpu_render");
+
+        let frame_insertion = self.frame_insertion();
+
         let pipeline = self.pipelines.get_or_insert(frame_size, pixel_aspect_ratio, || {
             log::info!("Creating render pipeline for frame size {frame_size:?} and pixel aspect ratio {pixel_aspect_ratio:?}");
 
@@ -1242,11 +1437,13 @@ impl<Window> Renderer for WgpuRenderer<Window> {
             &self.queue,
             &self.surface,
             frame_buffer,
+            buffer_width,
             #[cfg(feature = "ttf")]
             &self.surface_config,
             #[cfg(feature = "ttf")]
             &mut self.modal_renderer,
             &mut self.frame_time_tracker,
+            frame_insertion,
         ) {
             Ok(RenderResult::None) => {}
             Ok(RenderResult::SuboptimalSurface) => {
@@ -1270,3 +1467,38 @@ impl<Window> Renderer for WgpuRenderer<Window> {
         Ok(())
     }
 }
+
+impl<Window> Renderer for WgpuRenderer<Window> {
+    type Err = RendererError;
+
+    fn render_frame(
+        &mut self,
+        frame_buffer: &[Color],
+        frame_size: FrameSize,
+        pixel_aspect_ratio: Option<PixelAspectRatio>,
+    ) -> Result<(), Self::Err> {
+        self.render_frame_region(frame_buffer, frame_size.width, frame_size, pixel_aspect_ratio)
+    }
+
+    fn render_cropped_frame(
+        &mut self,
+        frame_buffer: &[Color],
+        frame_size: FrameSize,
+        crop: FrameCrop,
+        pixel_aspect_ratio: Option<PixelAspectRatio>,
+    ) -> Result<(), Self::Err> {
+        assert!(
+            crop.fits_within(frame_size),
+            "crop {crop:?} does not fit within frame {frame_size:?}"
+        );
+
+        // Upload directly from the full frame buffer instead of copying the cropped region
+        let start = (crop.top * frame_size.width + crop.left) as usize;
+        self.render_frame_region(
+            &frame_buffer[start..],
+            frame_size.width,
+            crop.size(),
+            pixel_aspect_ratio,
+        )
+    }
+}
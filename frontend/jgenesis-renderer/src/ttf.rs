@@ -1,3 +1,4 @@
+use crate::config::OverlayPosition;
 use bytemuck::{Pod, Zeroable};
 use glyphon::{
     Attrs, Buffer, Family, FontSystem, Metrics, Resolution, Shaping, SwashCache, TextArea,
@@ -12,6 +13,7 @@ const FONT_SIZE: f32 = 30.0;
 const LINE_HEIGHT: f32 = 60.0;
 const BORDER_OFFSET: f32 = 20.0;
 const BOX_OFFSET: f32 = 7.5;
+const OVERLAY_LINE_HEIGHT: f32 = 40.0;
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Zeroable, Pod)]
@@ -34,6 +36,12 @@ pub struct Modal {
     expiry_nanos: u128,
 }
 
+// Unlike modals, the overlay does not expire; it stays on screen until it is replaced or cleared
+struct Overlay {
+    text: String,
+    position: OverlayPosition,
+}
+
 pub struct ModalRenderer {
     font_system: FontSystem,
     swash_cache: SwashCache,
@@ -42,6 +50,8 @@ pub struct ModalRenderer {
     text_renderer: TextRenderer,
     buffers: Vec<Buffer>,
     modals: Vec<Modal>,
+    overlay: Option<Overlay>,
+    overlay_buffer: Buffer,
     bg_pipeline: wgpu::RenderPipeline,
 }
 
@@ -51,7 +61,7 @@ impl ModalRenderer {
         queue: &wgpu::Queue,
         surface_format: wgpu::TextureFormat,
     ) -> Self {
-        let font_system = FontSystem::new();
+        let mut font_system = FontSystem::new();
         let swash_cache = SwashCache::new();
         let glyphon_cache = glyphon::Cache::new(device);
         let mut atlas = TextAtlas::new(device, queue, &glyphon_cache, surface_format);
@@ -95,6 +105,9 @@ impl ModalRenderer {
 
         let viewport = glyphon::Viewport::new(device, &glyphon_cache);
 
+        let overlay_buffer =
+            Buffer::new(&mut font_system, Metrics::new(FONT_SIZE, OVERLAY_LINE_HEIGHT));
+
         Self {
             font_system,
             swash_cache,
@@ -103,10 +116,20 @@ impl ModalRenderer {
             text_renderer,
             buffers: Vec::with_capacity(10),
             modals: Vec::with_capacity(10),
+            overlay: None,
+            overlay_buffer,
             bg_pipeline,
         }
     }
 
+    pub fn set_overlay(&mut self, text: Option<String>, position: OverlayPosition) {
+        self.overlay = text.map(|text| Overlay { text, position });
+    }
+
+    fn box_count(&self) -> usize {
+        self.modals.len() + usize::from(self.overlay.is_some())
+    }
+
     pub fn add_modal(&mut self, text: String, duration: Duration) {
         let expiry_nanos = timeutils::current_time_nanos() + duration.as_nanos();
         self.modals.push(Modal { text, expiry_nanos });
@@ -122,7 +145,7 @@ impl ModalRenderer {
         let now_nanos = timeutils::current_time_nanos();
         self.modals.retain(|modal| modal.expiry_nanos > now_nanos);
 
-        if self.modals.is_empty() {
+        if self.box_count() == 0 {
             return Ok(None);
         }
 
@@ -131,8 +154,8 @@ impl ModalRenderer {
                 .push(Buffer::new(&mut self.font_system, Metrics::new(FONT_SIZE, LINE_HEIGHT)));
         }
 
-        let mut vertices = Vec::with_capacity(self.modals.len());
-        let mut text_areas = Vec::with_capacity(self.modals.len());
+        let mut vertices = Vec::with_capacity(6 * self.box_count());
+        let mut text_areas = Vec::with_capacity(self.box_count());
         let mut line_top = BORDER_OFFSET;
         for (modal, buffer) in self.modals.iter().zip(self.buffers.iter_mut()) {
             buffer.set_size(&mut self.font_system, Some(width as f32), Some(height as f32));
@@ -168,6 +191,55 @@ impl ModalRenderer {
             line_top += LINE_HEIGHT + BORDER_OFFSET;
         }
 
+        if let Some(overlay) = &self.overlay {
+            let buffer = &mut self.overlay_buffer;
+            buffer.set_size(&mut self.font_system, Some(width as f32), Some(height as f32));
+            buffer.set_text(
+                &mut self.font_system,
+                &overlay.text,
+                Attrs::new().family(Family::Monospace),
+                Shaping::Basic,
+            );
+            buffer.shape_until_scroll(&mut self.font_system, false);
+
+            let (text_width, text_height) = buffer
+                .layout_runs()
+                .fold((0.0_f32, 0.0_f32), |(w, h), run| (w.max(run.line_w), h + run.line_height));
+
+            let (left, top) =
+                overlay_top_left(overlay.position, text_width, text_height, width, height);
+
+            text_areas.push(TextArea {
+                buffer,
+                left,
+                top,
+                scale: 1.0,
+                bounds: TextBounds { left: 0, top: 0, right: width as i32, bottom: height as i32 },
+                default_color: glyphon::Color::rgb(255, 255, 255),
+                custom_glyphs: &[],
+            });
+
+            let box_vertices = normalize_vertices(
+                [
+                    [left - BOX_OFFSET, top - BOX_OFFSET],
+                    [left - BOX_OFFSET, top + text_height + BOX_OFFSET],
+                    [left + text_width + BOX_OFFSET, top - BOX_OFFSET],
+                    [left + text_width + BOX_OFFSET, top + text_height + BOX_OFFSET],
+                ]
+                .map(|position| Vertex { position }),
+                width as f32,
+                height as f32,
+            );
+            vertices.extend([
+                box_vertices[0],
+                box_vertices[1],
+                box_vertices[2],
+                box_vertices[1],
+                box_vertices[2],
+                box_vertices[3],
+            ]);
+        }
+
         self.viewport.update(queue, Resolution { width, height });
 
         self.text_renderer.prepare(
@@ -194,14 +266,14 @@ impl ModalRenderer {
         vertex_buffer: &'rpass wgpu::Buffer,
         render_pass: &mut wgpu::RenderPass<'rpass>,
     ) -> Result<(), glyphon::RenderError> {
-        if self.modals.is_empty() {
+        if self.box_count() == 0 {
             return Ok(());
         }
 
         render_pass.set_pipeline(&self.bg_pipeline);
         render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
 
-        let vertex_count = 6 * self.modals.len() as u32;
+        let vertex_count = 6 * self.box_count() as u32;
         render_pass.draw(0..vertex_count, 0..1);
 
         self.text_renderer.render(&self.atlas, &self.viewport, render_pass)
@@ -236,9 +308,37 @@ fn determine_box_positions(buffer: &Buffer, line_top: f32, width: f32, height: f
         },
     ];
 
+    normalize_vertices(unnormalized, width, height)
+}
+
+fn overlay_top_left(
+    position: OverlayPosition,
+    text_width: f32,
+    text_height: f32,
+    width: u32,
+    height: u32,
+) -> (f32, f32) {
+    let left = match position {
+        OverlayPosition::TopLeft | OverlayPosition::BottomLeft => BORDER_OFFSET,
+        OverlayPosition::TopRight | OverlayPosition::BottomRight => {
+            width as f32 - BORDER_OFFSET - text_width
+        }
+    };
+    let top = match position {
+        OverlayPosition::TopLeft | OverlayPosition::TopRight => BORDER_OFFSET,
+        OverlayPosition::BottomLeft | OverlayPosition::BottomRight => {
+            height as f32 - BORDER_OFFSET - text_height
+        }
+    };
+
+    (left, top)
+}
+
+// Convert from pixel coordinates to normalized device coordinates
+fn normalize_vertices(vertices: [Vertex; 4], width: f32, height: f32) -> [Vertex; 4] {
     let half_width = 0.5 * width;
     let half_height = 0.5 * height;
-    unnormalized.map(|v| Vertex {
+    vertices.map(|v| Vertex {
         position: [
             (v.position[0] - half_width) / half_width,
             -(v.position[1] - half_height) / half_height,
@@ -1,16 +1,17 @@
 use genesis_core::input::GenesisControllerType;
 use genesis_core::{GenesisAspectRatio, GenesisEmulatorConfig, GenesisLowPassFilter};
+use jgenesis_common::clockratio::ClockRatio;
 use jgenesis_common::frontend::TimingMode;
 use jgenesis_renderer::config::{
-    FilterMode, PreprocessShader, PrescaleFactor, PrescaleMode, RendererConfig, Scanlines,
-    VSyncMode, WgpuBackend,
+    FilterMode, FrameInsertion, PreprocessShader, PrescaleFactor, PrescaleMode, RefreshMultiplier,
+    RendererConfig, Scanlines, VSyncMode, WgpuBackend,
 };
 use segacd_core::api::{PcmInterpolation, PcmLowPassFilter, SegaCdEmulatorConfig};
 use smsgg_core::{GgAspectRatio, SmsAspectRatio, SmsGgEmulatorConfig, SmsModel, SmsRegion};
 use snes_core::api::{AudioInterpolationMode, SnesAspectRatio, SnesEmulatorConfig};
 use std::cell::RefCell;
 use std::collections::VecDeque;
-use std::num::{NonZeroU16, NonZeroU32, NonZeroU64};
+use std::num::{NonZeroU16, NonZeroU64};
 use std::ops::Deref;
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
@@ -39,11 +40,15 @@ impl CommonWebConfig {
             vsync_mode: VSyncMode::Enabled,
             // Frame time sync does not work on web because it blocks until the next frame time
             frame_time_sync: false,
+            decoupled_frame_presentation: false,
             prescale_mode: PrescaleMode::Manual(self.prescale_factor),
             scanlines: Scanlines::default(),
             force_integer_height_scaling: false,
             filter_mode: self.filter_mode,
             preprocess_shader: self.preprocess_shader,
+            ntsc_filter: false,
+            frame_insertion: FrameInsertion::None,
+            refresh_multiplier: RefreshMultiplier::default(),
             use_webgl2_limits: true,
         }
     }
@@ -89,8 +94,10 @@ impl SmsGgWebConfig {
             sms_crop_left_border: self.sms_crop_left_border,
             sms_crop_vertical_border: self.sms_crop_vertical_border,
             gg_use_sms_resolution: false,
+            gg_frame_blending: false,
+            gg_lcd_persistence: smsgg_core::DEFAULT_GG_LCD_PERSISTENCE,
             fm_sound_unit_enabled: self.fm_unit_enabled,
-            z80_divider: NonZeroU32::new(smsgg_core::NATIVE_Z80_DIVIDER).unwrap(),
+            z80_divider: ClockRatio::from_integer(smsgg_core::NATIVE_Z80_DIVIDER),
         }
     }
 }
@@ -132,6 +139,7 @@ impl GenesisWebConfig {
             remove_sprite_limits: self.remove_sprite_limits,
             m68k_clock_divider: self.m68k_divider,
             emulate_non_linear_vdp_dac: self.emulate_non_linear_vdp_dac,
+            expanded_vram: false,
             deinterlace: true,
             render_vertical_border: self.render_vertical_border,
             render_horizontal_border: self.render_horizontal_border,
@@ -161,9 +169,11 @@ impl SnesWebConfig {
             forced_timing_mode: None,
             aspect_ratio: self.aspect_ratio,
             deinterlace: true,
+            hi_res_blending: false,
+            priority_overlay: false,
             audio_interpolation: self.audio_interpolation,
             audio_60hz_hack: true,
-            gsu_overclock_factor: NonZeroU64::new(1).unwrap(),
+            gsu_overclock_factor: ClockRatio::from_integer(1),
         }
     }
 }
@@ -182,7 +192,11 @@ impl WebConfig {
             genesis: self.genesis.to_emulator_config(),
             pcm_interpolation: PcmInterpolation::CubicHermite,
             enable_ram_cartridge: true,
+            backup_ram_write_protected: false,
             load_disc_into_ram: true,
+            disc_cache_enabled: false,
+            disc_cache_size_mb: segacd_core::api::DEFAULT_DISC_CACHE_SIZE_MB,
+            disc_prefetch_sectors: segacd_core::api::DEFAULT_DISC_PREFETCH_SECTORS,
             disc_drive_speed: NonZeroU16::new(1).unwrap(),
             sub_cpu_divider: NonZeroU64::new(segacd_core::api::DEFAULT_SUB_CPU_DIVIDER).unwrap(),
             pcm_low_pass: PcmLowPassFilter::default(),
@@ -360,9 +360,15 @@ impl Emulator {
         match self {
             Self::None(..) => "(No ROM loaded)".into(),
             Self::SmsGg(..) => current_file_name.into(),
-            Self::Genesis(emulator, ..) => emulator.cartridge_title(),
-            Self::SegaCd(emulator, ..) => emulator.disc_title().into(),
-            Self::Snes(emulator, ..) => emulator.cartridge_title(),
+            Self::Genesis(emulator, ..) => {
+                emulator.game_metadata().title.unwrap_or_else(|| current_file_name.into())
+            }
+            Self::SegaCd(emulator, ..) => {
+                emulator.game_metadata().title.unwrap_or_else(|| current_file_name.into())
+            }
+            Self::Snes(emulator, ..) => {
+                emulator.game_metadata().title.unwrap_or_else(|| current_file_name.into())
+            }
         }
     }
 
@@ -760,7 +766,10 @@ fn run_event_loop(event_loop: EventLoop<JgenesisUserEvent>, mut state: AppState)
 
 async fn open_file(event_loop_proxy: EventLoopProxy<JgenesisUserEvent>) {
     let file = AsyncFileDialog::new()
-        .add_filter("Supported Files", &["sms", "gg", "gen", "md", "bin", "smd", "sfc", "smc"])
+        .add_filter(
+            "Supported Files",
+            &["sms", "gg", "sg", "sc", "gen", "md", "bin", "smd", "mgd", "sfc", "smc"],
+        )
         .add_filter("All Types", &["*"])
         .pick_file()
         .await;
@@ -833,12 +842,13 @@ fn open_emulator(
     });
 
     match file_ext.as_str() {
-        file_ext @ ("sms" | "gg") => {
+        file_ext @ ("sms" | "gg" | "sg" | "sc") => {
             js::showSmsGgConfig();
 
             let hardware = match file_ext {
                 "sms" => SmsGgHardware::MasterSystem,
                 "gg" => SmsGgHardware::GameGear,
+                "sg" | "sc" => SmsGgHardware::Sg1000,
                 _ => unreachable!("nested match expressions"),
             };
             let emulator = SmsGgEmulator::create(
@@ -849,7 +859,7 @@ fn open_emulator(
             );
             Ok(Emulator::SmsGg(emulator, SmsGgInputs::default()))
         }
-        "gen" | "md" | "bin" | "smd" => {
+        "gen" | "md" | "bin" | "smd" | "mgd" => {
             js::showGenesisConfig();
 
             let emulator = GenesisEmulator::create(
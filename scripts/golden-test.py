@@ -0,0 +1,124 @@
+#!/usr/bin/env python3
+#  Runs a list of test ROMs headless through jgenesis-cli --hash-frames and compares the resulting
+#  frame and audio hashes against stored baselines, to catch unintended changes in video or audio
+#  output.
+#
+#  Baseline files have one test per line, with blank lines and lines starting with '#' ignored:
+#    <hardware> <frames> <frame hash> <audio hash> <ROM path>
+#  ROM paths are relative to the baseline file. Use --update to record the current hashes.
+
+import argparse
+import os
+import subprocess
+import sys
+import tempfile
+
+
+def parse_baselines(path):
+    tests = []
+    with open(path) as f:
+        for line_number, line in enumerate(f, start=1):
+            line = line.strip()
+            if not line or line.startswith("#"):
+                continue
+
+            fields = line.split(maxsplit=4)
+            if len(fields) != 5:
+                sys.exit(f"{path}:{line_number}: expected 5 fields, got {len(fields)}")
+
+            hardware, frames, frame_hash, audio_hash, rom_path = fields
+            tests.append({
+                "hardware": hardware,
+                "frames": int(frames),
+                "frame_hash": frame_hash,
+                "audio_hash": audio_hash,
+                "rom_path": rom_path,
+            })
+    return tests
+
+
+def run_test(cli, config_path, rom_path, test):
+    # Use a fresh config file so that the user's settings do not affect the output
+    result = subprocess.run(
+        [
+            cli,
+            "--config", config_path,
+            "--hardware", test["hardware"],
+            "-f", rom_path,
+            "--hash-frames", str(test["frames"]),
+        ],
+        capture_output=True,
+        text=True,
+    )
+    if result.returncode != 0:
+        return None, result.stderr.strip()
+
+    # Output line looks like "frame_hash=0123456789abcdef audio_hash=0123456789abcdef"
+    hashes = dict(field.split("=", 1) for field in result.stdout.split())
+    return (hashes["frame_hash"], hashes["audio_hash"]), None
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Compare emulator output hashes against baselines")
+    parser.add_argument("baselines", help="baseline file listing test ROMs and expected hashes")
+    parser.add_argument("--cli", default="target/release/jgenesis-cli", help="path to jgenesis-cli")
+    parser.add_argument("--update", action="store_true", help="rewrite baselines with current hashes")
+    parser.add_argument("--skip-missing", action="store_true", help="skip tests whose ROMs are missing")
+    args = parser.parse_args()
+
+    base_dir = os.path.dirname(os.path.abspath(args.baselines))
+    tests = parse_baselines(args.baselines)
+
+    failures = 0
+    skipped = 0
+    with tempfile.TemporaryDirectory() as temp_dir:
+        config_path = os.path.join(temp_dir, "jgenesis-config.toml")
+
+        for test in tests:
+            rom_path = os.path.join(base_dir, test["rom_path"])
+            if not os.path.exists(rom_path):
+                if args.skip_missing:
+                    print(f"SKIP  {test['rom_path']} (ROM not found)")
+                    skipped += 1
+                    continue
+                print(f"FAIL  {test['rom_path']} (ROM not found)")
+                failures += 1
+                continue
+
+            hashes, error = run_test(args.cli, config_path, rom_path, test)
+            if hashes is None:
+                print(f"FAIL  {test['rom_path']} (jgenesis-cli exited with an error)")
+                print(f"      {error}")
+                failures += 1
+                continue
+
+            frame_hash, audio_hash = hashes
+            if args.update:
+                test["frame_hash"] = frame_hash
+                test["audio_hash"] = audio_hash
+                print(f"SAVE  {test['rom_path']}")
+            elif (frame_hash, audio_hash) == (test["frame_hash"], test["audio_hash"]):
+                print(f"PASS  {test['rom_path']}")
+            else:
+                print(f"FAIL  {test['rom_path']}")
+                if frame_hash != test["frame_hash"]:
+                    print(f"      frame hash {frame_hash}, expected {test['frame_hash']}")
+                if audio_hash != test["audio_hash"]:
+                    print(f"      audio hash {audio_hash}, expected {test['audio_hash']}")
+                failures += 1
+
+    if args.update:
+        with open(args.baselines, "w") as f:
+            f.write("# <hardware> <frames> <frame hash> <audio hash> <ROM path>\n")
+            for test in tests:
+                f.write(f"{test['hardware']} {test['frames']} {test['frame_hash']} "
+                        f"{test['audio_hash']} {test['rom_path']}\n")
+
+    ran = len(tests) - skipped
+    print(f"{ran - failures}/{ran} passed, {skipped} skipped")
+    if failures != 0:
+        sys.exit(1)
+
+
+if __name__ == "__main__":
+    main()